@@ -10,7 +10,7 @@ use gpui::{
 use language::{
     LanguageRegistry, LanguageServerBinaryStatus, LanguageServerId, LanguageServerName,
 };
-use project::{LanguageServerProgress, Project};
+use project::{on_save_runner::OnSaveRunStatus, LanguageServerProgress, Project};
 use smallvec::SmallVec;
 use std::{cmp::Reverse, fmt::Write, sync::Arc, time::Duration};
 use ui::{prelude::*, ButtonLike, ContextMenu, PopoverMenu, PopoverMenuHandle};
@@ -20,6 +20,7 @@ actions!(activity_indicator, [ShowErrorMessage]);
 
 pub enum Event {
     ShowError { lsp_name: Arc<str>, error: String },
+    ShowOnSaveOutput { label: String, output: String },
 }
 
 pub struct ActivityIndicator {
@@ -81,39 +82,36 @@ impl ActivityIndicator {
             }
         });
 
-        cx.subscribe(&this, move |_, _, event, cx| match event {
-            Event::ShowError { lsp_name, error } => {
-                let create_buffer = project.update(cx, |project, cx| project.create_buffer(cx));
-                let project = project.clone();
-                let error = error.clone();
-                let lsp_name = lsp_name.clone();
-                cx.spawn(|workspace, mut cx| async move {
-                    let buffer = create_buffer.await?;
-                    buffer.update(&mut cx, |buffer, cx| {
-                        buffer.edit(
-                            [(
-                                0..0,
-                                format!("Language server error: {}\n\n{}", lsp_name, error),
-                            )],
-                            None,
-                            cx,
-                        );
-                    })?;
-                    workspace.update(&mut cx, |workspace, cx| {
-                        workspace.add_item_to_active_pane(
-                            Box::new(cx.new_view(|cx| {
-                                Editor::for_buffer(buffer, Some(project.clone()), cx)
-                            })),
-                            None,
-                            true,
-                            cx,
-                        );
-                    })?;
+        cx.subscribe(&this, move |_, _, event, cx| {
+            let text = match event {
+                Event::ShowError { lsp_name, error } => {
+                    format!("Language server error: {}\n\n{}", lsp_name, error)
+                }
+                Event::ShowOnSaveOutput { label, output } => {
+                    format!("`{}` failed:\n\n{}", label, output)
+                }
+            };
+            let create_buffer = project.update(cx, |project, cx| project.create_buffer(cx));
+            let project = project.clone();
+            cx.spawn(|workspace, mut cx| async move {
+                let buffer = create_buffer.await?;
+                buffer.update(&mut cx, |buffer, cx| {
+                    buffer.edit([(0..0, text)], None, cx);
+                })?;
+                workspace.update(&mut cx, |workspace, cx| {
+                    workspace.add_item_to_active_pane(
+                        Box::new(
+                            cx.new_view(|cx| Editor::for_buffer(buffer, Some(project.clone()), cx)),
+                        ),
+                        None,
+                        true,
+                        cx,
+                    );
+                })?;
 
-                    anyhow::Ok(())
-                })
-                .detach();
-            }
+                anyhow::Ok(())
+            })
+            .detach();
         })
         .detach();
         this
@@ -276,6 +274,53 @@ impl ActivityIndicator {
             });
         }
 
+        // Show any commands run after saving that are running or failed.
+        let mut running = SmallVec::<[_; 3]>::new();
+        let mut failed_run = None;
+        for run in self.project.read(cx).on_save_runs(cx) {
+            match &run.status {
+                OnSaveRunStatus::Running => running.push(format!("`{}`", run.label)),
+                OnSaveRunStatus::Failed { output } => {
+                    failed_run.get_or_insert_with(|| (run.label.clone(), output.clone()));
+                }
+                OnSaveRunStatus::Finished => {}
+            }
+        }
+
+        if !running.is_empty() {
+            return Some(Content {
+                icon: Some(
+                    Icon::new(IconName::ArrowCircle)
+                        .size(IconSize::Small)
+                        .with_animation(
+                            "arrow-circle",
+                            Animation::new(Duration::from_secs(2)).repeat(),
+                            |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                        )
+                        .into_any_element(),
+                ),
+                message: format!("Running {}...", running.join(", ")),
+                on_click: None,
+            });
+        }
+
+        if let Some((label, output)) = failed_run {
+            return Some(Content {
+                icon: Some(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .into_any_element(),
+                ),
+                message: format!("`{}` failed. Click to show output.", label),
+                on_click: Some(Arc::new(move |_, cx| {
+                    cx.emit(Event::ShowOnSaveOutput {
+                        label: label.clone(),
+                        output: output.clone(),
+                    })
+                })),
+            });
+        }
+
         // Show any formatting failure
         if let Some(failure) = self.project.read(cx).last_formatting_failure() {
             return Some(Content {
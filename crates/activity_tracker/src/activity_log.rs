@@ -0,0 +1,243 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// What was being worked on: the name of the worktree and the language of the file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActivityKey {
+    pub project: String,
+    pub language: String,
+}
+
+/// The time spent in a project and language on a given day, as exported.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub date: NaiveDate,
+    pub project: String,
+    pub language: String,
+    pub seconds: f64,
+}
+
+/// The editing time recorded on this machine, per day, project and language.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivityLog {
+    totals: BTreeMap<(NaiveDate, ActivityKey), Duration>,
+}
+
+impl ActivityLog {
+    pub fn add(&mut self, date: NaiveDate, key: ActivityKey, duration: Duration) {
+        *self.totals.entry((date, key)).or_default() += duration;
+    }
+
+    pub fn merge(&mut self, other: ActivityLog) {
+        for ((date, key), duration) in other.totals {
+            self.add(date, key, duration);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = ActivityEntry> + '_ {
+        self.totals
+            .iter()
+            .map(|((date, key), duration)| ActivityEntry {
+                date: *date,
+                project: key.project.clone(),
+                language: key.language.clone(),
+                seconds: duration.as_secs_f64(),
+            })
+    }
+
+    /// The time recorded on or after the given day, or ever if there's none.
+    pub fn total_since(&self, since: Option<NaiveDate>) -> Duration {
+        self.totals_since(since).map(|(_, duration)| duration).sum()
+    }
+
+    /// The time recorded per project or per language on or after the given day, longest first.
+    pub fn grouped_totals_since(
+        &self,
+        since: Option<NaiveDate>,
+        group: impl Fn(&ActivityKey) -> &str,
+    ) -> Vec<(String, Duration)> {
+        let mut totals = HashMap::<&str, Duration>::default();
+        for (key, duration) in self.totals_since(since) {
+            *totals.entry(group(key)).or_default() += duration;
+        }
+        let mut totals = totals
+            .into_iter()
+            .map(|(name, duration)| (name.to_string(), duration))
+            .collect::<Vec<_>>();
+        totals.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+        totals
+    }
+
+    fn totals_since(
+        &self,
+        since: Option<NaiveDate>,
+    ) -> impl Iterator<Item = (&ActivityKey, Duration)> + '_ {
+        self.totals
+            .iter()
+            .filter(move |((date, _), _)| since.map_or(true, |since| *date >= since))
+            .map(|((_, key), duration)| (key, *duration))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        let mut log = Self::default();
+        for entry in serde_json::from_str::<Vec<ActivityEntry>>(text)? {
+            // Skip entries that were edited by hand into something that isn't a duration.
+            if let Ok(duration) = Duration::try_from_secs_f64(entry.seconds) {
+                let key = ActivityKey {
+                    project: entry.project,
+                    language: entry.language,
+                };
+                log.add(entry.date, key, duration);
+            }
+        }
+        Ok(log)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(
+            &self.entries().collect::<Vec<_>>(),
+        )?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,project,language,seconds\n");
+        for entry in self.entries() {
+            csv.push_str(&format!(
+                "{},{},{},{:.0}\n",
+                entry.date,
+                csv_field(&entry.project),
+                csv_field(&entry.language),
+                entry.seconds
+            ));
+        }
+        csv
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Attributes the time between two heartbeats to the activity of the first one, unless the
+/// user was idle in between.
+#[derive(Debug, Default)]
+pub struct Session {
+    last_heartbeat: Option<(Instant, ActivityKey)>,
+    duration: Duration,
+}
+
+impl Session {
+    /// Records a heartbeat, returning the activity the time since the last heartbeat is
+    /// attributed to.
+    pub fn heartbeat(
+        &mut self,
+        key: ActivityKey,
+        at: Instant,
+        idle_timeout: Duration,
+    ) -> Option<(ActivityKey, Duration)> {
+        let (last_at, last_key) = self.last_heartbeat.replace((at, key))?;
+        let elapsed = at.saturating_duration_since(last_at);
+        if elapsed > idle_timeout {
+            return None;
+        }
+        self.duration += elapsed;
+        Some((last_key, elapsed))
+    }
+
+    /// The time spent editing since the session started.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(project: &str, language: &str) -> ActivityKey {
+        ActivityKey {
+            project: project.into(),
+            language: language.into(),
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let idle_timeout = Duration::from_secs(60);
+        let start = Instant::now();
+        let mut session = Session::default();
+        assert_eq!(
+            session.heartbeat(key("zed", "Rust"), start, idle_timeout),
+            None
+        );
+        assert_eq!(
+            session.heartbeat(
+                key("zed", "Markdown"),
+                start + Duration::from_secs(30),
+                idle_timeout
+            ),
+            Some((key("zed", "Rust"), Duration::from_secs(30)))
+        );
+        // The user was away, so the gap isn't counted.
+        assert_eq!(
+            session.heartbeat(
+                key("zed", "Rust"),
+                start + Duration::from_secs(200),
+                idle_timeout
+            ),
+            None
+        );
+        assert_eq!(
+            session.heartbeat(
+                key("zed", "Rust"),
+                start + Duration::from_secs(210),
+                idle_timeout
+            ),
+            Some((key("zed", "Rust"), Duration::from_secs(10)))
+        );
+        assert_eq!(session.duration(), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn test_activity_log() {
+        let monday = NaiveDate::from_ymd_opt(2024, 9, 2).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+        let mut log = ActivityLog::default();
+        log.add(monday, key("zed", "Rust"), Duration::from_secs(60));
+        log.add(tuesday, key("zed", "Rust"), Duration::from_secs(30));
+        log.add(tuesday, key("web, \"new\"", "TSX"), Duration::from_secs(45));
+        log.add(tuesday, key("zed", "Rust"), Duration::from_secs(30));
+
+        assert_eq!(log.total_since(None), Duration::from_secs(165));
+        assert_eq!(log.total_since(Some(tuesday)), Duration::from_secs(105));
+        assert_eq!(
+            log.grouped_totals_since(None, |key| &key.project),
+            vec![
+                ("zed".to_string(), Duration::from_secs(120)),
+                ("web, \"new\"".to_string(), Duration::from_secs(45)),
+            ]
+        );
+        assert_eq!(
+            log.to_csv(),
+            "date,project,language,seconds\n\
+             2024-09-02,zed,Rust,60\n\
+             2024-09-03,\"web, \"\"new\"\"\",TSX,45\n\
+             2024-09-03,zed,Rust,60\n"
+        );
+        assert_eq!(
+            ActivityLog::from_json(&log.to_json().unwrap()).unwrap(),
+            log
+        );
+    }
+}
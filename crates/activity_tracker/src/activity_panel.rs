@@ -0,0 +1,280 @@
+use crate::{
+    ActivityStore, ActivityTrackerSettings, ClearActivity, ExportActivityAsCsv,
+    ExportActivityAsJson,
+};
+use anyhow::Result;
+use chrono::{Days, Local};
+use db::kvp::KEY_VALUE_STORE;
+use fs::Fs;
+use gpui::{
+    actions, AppContext, AsyncWindowContext, EventEmitter, FocusHandle, FocusableView, Model,
+    Render, Subscription, Task, View, ViewContext, WeakView,
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use std::{sync::Arc, time::Duration};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+const ACTIVITY_PANEL_KEY: &str = "ActivityPanel";
+
+actions!(activity_panel, [ToggleFocus]);
+
+#[derive(Serialize, Deserialize)]
+struct SerializedActivityPanel {
+    width: Option<Pixels>,
+}
+
+/// Summarizes the time spent editing, per project and language.
+pub struct ActivityPanel {
+    fs: Arc<dyn Fs>,
+    store: Model<ActivityStore>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ActivityPanel {
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        cx.new_view(|cx| {
+            let store = ActivityStore::global(cx);
+            let subscriptions = vec![cx.observe(&store, |_, _, cx| cx.notify())];
+            Self {
+                fs,
+                store,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(ACTIVITY_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedActivityPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        ACTIVITY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedActivityPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .justify_between()
+            .px_2()
+            .py_1()
+            // Match the height of the tab bar so they line up.
+            .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new("Activity"))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("export-activity-json", IconName::Download)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Export as JSON", &ExportActivityAsJson, cx)
+                            })
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ExportActivityAsJson))),
+                    )
+                    .child(
+                        IconButton::new("export-activity-csv", IconName::FileText)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Export as CSV", &ExportActivityAsCsv, cx)
+                            })
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ExportActivityAsCsv))),
+                    )
+                    .child(
+                        IconButton::new("clear-activity", IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::for_action("Clear Activity", &ClearActivity, cx))
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ClearActivity))),
+                    ),
+            )
+    }
+
+    fn render_section(
+        title: &'static str,
+        rows: impl IntoIterator<Item = (String, Duration)>,
+    ) -> impl IntoElement {
+        v_flex()
+            .py_1()
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .children(rows.into_iter().enumerate().map(|(ix, (name, duration))| {
+                ListItem::new((title, ix))
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .child(Label::new(name).single_line())
+                    .end_slot(
+                        Label::new(format_duration(duration))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+            }))
+    }
+}
+
+/// Formats a duration as hours and minutes, like `2h 05m`.
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "< 1m".to_string(),
+        (0, minutes) => format!("{minutes}m"),
+        (hours, minutes) => format!("{hours}h {minutes:02}m"),
+    }
+}
+
+impl Render for ActivityPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let store = self.store.read(cx);
+        let log = store.log();
+        let today = Local::now().date_naive();
+        let week_start = today.checked_sub_days(Days::new(6));
+        let content = if !ActivityTrackerSettings::get_global(cx).enabled {
+            v_flex()
+                .p_4()
+                .child(
+                    Label::new(
+                        "Activity tracking is disabled. Enable it with the `activity_tracker.enabled` setting to record your editing time on this machine.",
+                    )
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+                )
+                .into_any_element()
+        } else {
+            v_flex()
+                .id("activity-summary")
+                .size_full()
+                .overflow_y_scroll()
+                .child(Self::render_section(
+                    "Summary",
+                    [
+                        ("This session".to_string(), store.session_duration()),
+                        ("Today".to_string(), log.total_since(Some(today))),
+                        ("Last 7 days".to_string(), log.total_since(week_start)),
+                        ("All time".to_string(), log.total_since(None)),
+                    ],
+                ))
+                .child(Self::render_section(
+                    "Projects in the last 7 days",
+                    log.grouped_totals_since(week_start, |key| &key.project),
+                ))
+                .child(Self::render_section(
+                    "Languages in the last 7 days",
+                    log.grouped_totals_since(week_start, |key| &key.language),
+                ))
+                .into_any_element()
+        };
+        v_flex()
+            .key_context("ActivityPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_header(cx))
+            .child(content)
+    }
+}
+
+impl FocusableView for ActivityPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for ActivityPanel {}
+
+impl Panel for ActivityPanel {
+    fn persistent_name() -> &'static str {
+        "ActivityPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        ActivityTrackerSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<ActivityTrackerSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| ActivityTrackerSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        let settings = ActivityTrackerSettings::get_global(cx);
+        (settings.enabled && settings.button).then_some(IconName::CountdownTimer)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Activity Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
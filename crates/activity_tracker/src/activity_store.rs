@@ -0,0 +1,227 @@
+use crate::{
+    activity_log::{ActivityKey, ActivityLog, Session},
+    ActivityTrackerSettings,
+};
+use anyhow::Result;
+use chrono::Local;
+use fs::Fs;
+use gpui::{AppContext, Context as _, EventEmitter, Global, Model, ModelContext, Task};
+use settings::{Settings as _, SettingsStore};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use util::ResultExt as _;
+
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(30);
+
+pub(crate) fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    let store = cx.new_model(|cx| ActivityStore::new(fs, cx));
+    cx.set_global(GlobalActivityStore(store));
+}
+
+struct GlobalActivityStore(Model<ActivityStore>);
+
+impl Global for GlobalActivityStore {}
+
+/// A moment of activity in an editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heartbeat {
+    pub abs_path: Option<PathBuf>,
+    /// The name of the worktree containing the file.
+    pub project: String,
+    pub language: String,
+    /// Whether the file was saved, rather than edited or navigated.
+    pub is_write: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActivityEvent {
+    Heartbeat(Heartbeat),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// Records the time spent editing per project and language, when enabled, in a file that
+/// never leaves this machine.
+///
+/// Integrations reporting activity elsewhere, such as time trackers, can subscribe to the
+/// [`ActivityEvent`]s of the store instead of polling the editors. They're emitted whether
+/// or not tracking is enabled.
+pub struct ActivityStore {
+    fs: Arc<dyn Fs>,
+    log: ActivityLog,
+    session: Session,
+    load_task: Option<Task<()>>,
+    loaded: bool,
+    pending_save: Option<Task<()>>,
+    _subscriptions: Vec<gpui::Subscription>,
+}
+
+impl EventEmitter<ActivityEvent> for ActivityStore {}
+
+impl ActivityStore {
+    fn new(fs: Arc<dyn Fs>, cx: &mut ModelContext<Self>) -> Self {
+        let mut this = Self {
+            fs,
+            log: ActivityLog::default(),
+            session: Session::default(),
+            load_task: None,
+            loaded: false,
+            pending_save: None,
+            _subscriptions: vec![
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    if ActivityTrackerSettings::get_global(cx).enabled {
+                        this.load(cx);
+                    }
+                }),
+                cx.on_app_quit(|this, cx| {
+                    let save = this.save(cx);
+                    async move {
+                        save.await.log_err();
+                    }
+                }),
+            ],
+        };
+        if ActivityTrackerSettings::get_global(cx).enabled {
+            this.load(cx);
+        }
+        this
+    }
+
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalActivityStore>().0.clone()
+    }
+
+    pub fn try_global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalActivityStore>()
+            .map(|store| store.0.clone())
+    }
+
+    pub fn log(&self) -> &ActivityLog {
+        &self.log
+    }
+
+    /// The time spent editing since Zed was started.
+    pub fn session_duration(&self) -> Duration {
+        self.session.duration()
+    }
+
+    /// Notifies the subscribers of the heartbeat and, if tracking is enabled, attributes the
+    /// time since the previous heartbeat to its activity.
+    pub fn record_heartbeat(&mut self, heartbeat: Heartbeat, cx: &mut ModelContext<Self>) {
+        cx.emit(ActivityEvent::Heartbeat(heartbeat.clone()));
+
+        let settings = ActivityTrackerSettings::get_global(cx);
+        if !settings.enabled {
+            return;
+        }
+        let idle_timeout = Duration::from_secs(settings.idle_timeout_seconds);
+        let key = ActivityKey {
+            project: heartbeat.project,
+            language: heartbeat.language,
+        };
+        if let Some((key, duration)) = self.session.heartbeat(key, Instant::now(), idle_timeout) {
+            self.log.add(Local::now().date_naive(), key, duration);
+            self.schedule_save(cx);
+            cx.notify();
+        }
+    }
+
+    /// Deletes all the recorded activity.
+    pub fn clear(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        self.log = ActivityLog::default();
+        cx.notify();
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let path = paths::activity_file();
+            if fs.is_file(path).await {
+                fs.remove_file(path, Default::default()).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Prompts for a path and writes the recorded activity to it.
+    pub fn export(&self, format: ExportFormat, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let contents = match format {
+            ExportFormat::Json => self.log.to_json(),
+            ExportFormat::Csv => Ok(self.log.to_csv()),
+        };
+        let path = cx.prompt_for_new_path(paths::home_dir());
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let contents = contents?;
+            if let Some(path) = path.await?? {
+                fs.atomic_write(path, contents).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Loads the previously recorded activity, once.
+    fn load(&mut self, cx: &mut ModelContext<Self>) {
+        if self.load_task.is_some() {
+            return;
+        }
+        let fs = self.fs.clone();
+        self.load_task = Some(cx.spawn(|this, mut cx| async move {
+            let path = paths::activity_file();
+            let log = if fs.is_file(path).await {
+                let Some(text) = fs.load(path).await.log_err() else {
+                    return;
+                };
+                // Don't overwrite a log that can't be parsed, so that it can be fixed by hand.
+                let Some(log) = ActivityLog::from_json(&text).log_err() else {
+                    return;
+                };
+                log
+            } else {
+                ActivityLog::default()
+            };
+            this.update(&mut cx, |this, cx| {
+                this.log.merge(log);
+                this.loaded = true;
+                if !this.log.is_empty() {
+                    this.schedule_save(cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn schedule_save(&mut self, cx: &mut ModelContext<Self>) {
+        if self.pending_save.is_some() {
+            return;
+        }
+        self.pending_save = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(SAVE_DEBOUNCE).await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_save = None;
+                this.save(cx).detach_and_log_err(cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn save(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let contents = self.loaded.then(|| self.log.to_json());
+        let fs = self.fs.clone();
+        cx.background_executor().spawn(async move {
+            let Some(contents) = contents else {
+                return Ok(());
+            };
+            let path = paths::activity_file();
+            if let Some(parent) = path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            fs.atomic_write(path.clone(), contents?).await
+        })
+    }
+}
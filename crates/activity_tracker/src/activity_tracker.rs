@@ -0,0 +1,110 @@
+mod activity_log;
+mod activity_panel;
+mod activity_store;
+mod activity_tracker_settings;
+
+use editor::{Editor, EditorEvent, EditorMode};
+use fs::Fs;
+use gpui::{actions, AppContext, PromptLevel, ViewContext};
+use settings::Settings;
+use std::sync::Arc;
+use workspace::Workspace;
+
+pub use activity_log::{ActivityEntry, ActivityKey, ActivityLog};
+pub use activity_panel::{ActivityPanel, ToggleFocus};
+pub use activity_store::{ActivityEvent, ActivityStore, ExportFormat, Heartbeat};
+pub use activity_tracker_settings::ActivityTrackerSettings;
+
+actions!(
+    activity_tracker,
+    [ExportActivityAsJson, ExportActivityAsCsv, ClearActivity]
+);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    ActivityTrackerSettings::register(cx);
+    activity_store::init(fs, cx);
+
+    cx.observe_new_views(|editor: &mut Editor, cx| {
+        if editor.mode() != EditorMode::Full {
+            return;
+        }
+        let this = cx.view().clone();
+        cx.subscribe(&this, |editor, _, event: &EditorEvent, cx| {
+            let is_write = match event {
+                EditorEvent::BufferEdited | EditorEvent::SelectionsChanged { local: true } => false,
+                EditorEvent::Saved => true,
+                _ => return,
+            };
+            if let Some(heartbeat) = heartbeat(editor, is_write, cx) {
+                ActivityStore::global(cx)
+                    .update(cx, |store, cx| store.record_heartbeat(heartbeat, cx));
+            }
+        })
+        .detach();
+    })
+    .detach();
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<ActivityPanel>(cx);
+            })
+            .register_action(|_, _: &ExportActivityAsJson, cx| {
+                ActivityStore::global(cx)
+                    .update(cx, |store, cx| store.export(ExportFormat::Json, cx))
+                    .detach_and_log_err(cx);
+            })
+            .register_action(|_, _: &ExportActivityAsCsv, cx| {
+                ActivityStore::global(cx)
+                    .update(cx, |store, cx| store.export(ExportFormat::Csv, cx))
+                    .detach_and_log_err(cx);
+            })
+            .register_action(|_, _: &ClearActivity, cx| {
+                let answer = cx.prompt(
+                    PromptLevel::Warning,
+                    "Clear all recorded activity?",
+                    None,
+                    &["Clear", "Cancel"],
+                );
+                cx.spawn(|_, mut cx| async move {
+                    if matches!(answer.await, Ok(0)) {
+                        cx.update(|cx| {
+                            ActivityStore::global(cx).update(cx, |store, cx| store.clear(cx))
+                        })?
+                        .await?;
+                    }
+                    anyhow::Ok(())
+                })
+                .detach_and_log_err(cx);
+            });
+    })
+    .detach();
+}
+
+/// The activity at the newest cursor of the editor, in the buffer containing it.
+fn heartbeat(editor: &Editor, is_write: bool, cx: &ViewContext<Editor>) -> Option<Heartbeat> {
+    let cursor = editor.selections.newest_anchor().head();
+    let (buffer, _, _) = editor
+        .buffer()
+        .read(cx)
+        .point_to_buffer_offset(cursor, cx)?;
+    let buffer = buffer.read(cx);
+    let file = buffer.file()?;
+    let project = file
+        .full_path(cx)
+        .components()
+        .next()?
+        .as_os_str()
+        .to_string_lossy()
+        .to_string();
+    let language = buffer.language().map_or_else(
+        || "Plain Text".to_string(),
+        |language| language.name().to_string(),
+    );
+    Some(Heartbeat {
+        abs_path: file.as_local().map(|file| file.abs_path(cx)),
+        project,
+        language,
+        is_write,
+    })
+}
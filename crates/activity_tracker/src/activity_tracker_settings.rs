@@ -0,0 +1,52 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ActivityTrackerSettings {
+    pub enabled: bool,
+    pub idle_timeout_seconds: u64,
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ActivityTrackerSettingsContent {
+    /// Whether to record the time spent editing per project and language. The activity is
+    /// only stored on this machine.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// How long to wait without any activity before considering the time as idle, in seconds.
+    ///
+    /// Default: 120
+    pub idle_timeout_seconds: Option<u64>,
+    /// Whether to show the activity panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the activity panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the activity panel in pixels.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
+impl Settings for ActivityTrackerSettings {
+    const KEY: Option<&'static str> = Some("activity_tracker");
+
+    type FileContent = ActivityTrackerSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
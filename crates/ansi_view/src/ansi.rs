@@ -0,0 +1,227 @@
+use std::{iter::Peekable, ops::Range, str::Chars};
+
+const ESCAPE: char = '\x1b';
+const BELL: char = '\x07';
+
+/// A color set by an SGR escape sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 256 colors of the terminal's palette, the first 16 being the named colors.
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// The text attributes set by SGR ("Select Graphic Rendition") escape sequences.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColor>,
+    pub background: Option<AnsiColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub inverse: bool,
+}
+
+/// A line of text with its escape sequences removed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnsiLine {
+    pub text: String,
+    /// The styled ranges of the text, in order. Text outside of them has the default style.
+    pub spans: Vec<(Range<usize>, AnsiStyle)>,
+}
+
+impl AnsiLine {
+    fn push(&mut self, c: char, style: AnsiStyle) {
+        let start = self.text.len();
+        self.text.push(c);
+        if style == AnsiStyle::default() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some((range, last_style)) if range.end == start && *last_style == style => {
+                range.end = self.text.len();
+            }
+            _ => self.spans.push((start..self.text.len(), style)),
+        }
+    }
+}
+
+/// Splits the text into lines, replacing the escape characters with a visible symbol.
+pub fn raw_lines(text: &str) -> Vec<String> {
+    text.split('\n')
+        .map(|line| line.trim_end_matches('\r').replace(ESCAPE, "␛"))
+        .collect()
+}
+
+/// Splits the text into lines, applying its SGR escape sequences as styles and removing all
+/// escape sequences. Styles carry over from one line to the next, like in a terminal.
+pub fn parse_ansi(text: &str) -> Vec<AnsiLine> {
+    let mut lines = Vec::new();
+    let mut line = AnsiLine::default();
+    let mut style = AnsiStyle::default();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => lines.push(std::mem::take(&mut line)),
+            ESCAPE => match chars.next() {
+                Some('[') => {
+                    let (parameters, command) = read_control_sequence(&mut chars);
+                    if command == Some('m') {
+                        apply_sgr(&parameters, &mut style);
+                    }
+                }
+                Some(']') => skip_operating_system_command(&mut chars),
+                _ => {}
+            },
+            '\t' => line.push(c, style),
+            c if c.is_control() => {}
+            c => line.push(c, style),
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+/// Reads the parameters and final character of a control sequence, following `ESC [`.
+fn read_control_sequence(chars: &mut Peekable<Chars>) -> (String, Option<char>) {
+    let mut parameters = String::new();
+    while let Some(&c) = chars.peek() {
+        if ('\x30'..='\x3f').contains(&c) {
+            parameters.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+    let command = chars.next_if(|c| ('\x40'..='\x7e').contains(c));
+    (parameters, command)
+}
+
+/// Skips an operating system command, such as a hyperlink or window title, which ends with
+/// either a bell or `ESC \`.
+fn skip_operating_system_command(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.next() {
+        match c {
+            BELL => break,
+            ESCAPE => {
+                chars.next_if_eq(&'\\');
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn apply_sgr(parameters: &str, style: &mut AnsiStyle) {
+    let mut parameters = parameters
+        .split([';', ':'])
+        .map(|parameter| match parameter {
+            "" => 0,
+            // Codes out of range are ignored.
+            _ => parameter.parse::<u8>().unwrap_or(u8::MAX),
+        });
+    // An empty sequence resets the style.
+    let mut next = Some(parameters.next().unwrap_or(0));
+    while let Some(parameter) = next {
+        match parameter {
+            0 => *style = AnsiStyle::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            7 => style.inverse = true,
+            9 => style.strikethrough = true,
+            21 | 22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            27 => style.inverse = false,
+            29 => style.strikethrough = false,
+            30..=37 => style.foreground = Some(AnsiColor::Indexed(parameter - 30)),
+            38 => style.foreground = read_extended_color(&mut parameters),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(AnsiColor::Indexed(parameter - 40)),
+            48 => style.background = read_extended_color(&mut parameters),
+            49 => style.background = None,
+            90..=97 => style.foreground = Some(AnsiColor::Indexed(parameter - 90 + 8)),
+            100..=107 => style.background = Some(AnsiColor::Indexed(parameter - 100 + 8)),
+            _ => {}
+        }
+        next = parameters.next();
+    }
+}
+
+/// Reads the `5;n` or `2;r;g;b` parameters following a 38 or 48 parameter.
+fn read_extended_color(parameters: &mut impl Iterator<Item = u8>) -> Option<AnsiColor> {
+    match parameters.next()? {
+        5 => Some(AnsiColor::Indexed(parameters.next()?)),
+        2 => Some(AnsiColor::Rgb(
+            parameters.next()?,
+            parameters.next()?,
+            parameters.next()?,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ansi() {
+        let red = AnsiStyle {
+            foreground: Some(AnsiColor::Indexed(1)),
+            ..Default::default()
+        };
+        let bold_red = AnsiStyle { bold: true, ..red };
+        assert_eq!(
+            parse_ansi("\x1b[31mred \x1b[1mbold\nstill\x1b[0m plain"),
+            vec![
+                AnsiLine {
+                    text: "red bold".into(),
+                    spans: vec![(0..4, red), (4..8, bold_red)],
+                },
+                AnsiLine {
+                    text: "still plain".into(),
+                    spans: vec![(0..5, bold_red)],
+                },
+            ]
+        );
+
+        let extended = AnsiStyle {
+            foreground: Some(AnsiColor::Indexed(208)),
+            background: Some(AnsiColor::Rgb(1, 2, 3)),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_ansi("\x1b[38;5;208;48;2;1;2;3mx\x1b[m y"),
+            vec![AnsiLine {
+                text: "x y".into(),
+                spans: vec![(0..1, extended)],
+            }]
+        );
+
+        // Other escape sequences and control characters are removed.
+        assert_eq!(
+            parse_ansi("\x1b]8;;https://zed.dev\x1b\\link\x1b]8;;\x07\x1b[2K\r\tdone\r"),
+            vec![AnsiLine {
+                text: "link\tdone".into(),
+                spans: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_raw_lines() {
+        assert_eq!(
+            raw_lines("\x1b[31mred\x1b[0m\r\nplain"),
+            vec!["␛[31mred␛[0m".to_string(), "plain".to_string()]
+        );
+    }
+}
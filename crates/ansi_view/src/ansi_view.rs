@@ -0,0 +1,253 @@
+mod ansi;
+
+use ansi::{parse_ansi, raw_lines, AnsiColor, AnsiLine, AnsiStyle};
+use editor::{Editor, EditorEvent};
+use gpui::{
+    actions, list, px, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, FontStyle,
+    FontWeight, HighlightStyle, Hsla, ListAlignment, ListState, StrikethroughStyle, StyledText,
+    Subscription, Task, TextStyle, UnderlineStyle, View, ViewContext,
+};
+use settings::Settings as _;
+use std::time::Duration;
+use theme::{Theme, ThemeSettings};
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    item::{Item, ItemEvent},
+    Workspace,
+};
+
+actions!(ansi_view, [OpenAnsiView, ToggleRawCodes]);
+
+const REPARSE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &OpenAnsiView, cx| {
+            let Some(editor) = workspace
+                .active_item(cx)
+                .and_then(|item| item.act_as::<Editor>(cx))
+            else {
+                return;
+            };
+            let view = cx.new_view(|cx| AnsiView::new(editor, cx));
+            workspace.add_item_to_active_pane(Box::new(view), None, true, cx);
+        });
+    })
+    .detach();
+}
+
+/// A read-only view of an editor's text, with its ANSI escape sequences rendered as styles.
+///
+/// The view is updated as the editor's text changes, and can show the raw escape sequences
+/// instead.
+pub struct AnsiView {
+    editor: View<Editor>,
+    focus_handle: FocusHandle,
+    lines: Vec<AnsiLine>,
+    raw_lines: Vec<SharedString>,
+    show_raw_codes: bool,
+    list_state: ListState,
+    parse_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl AnsiView {
+    pub fn new(editor: View<Editor>, cx: &mut ViewContext<Self>) -> Self {
+        let subscription = cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::BufferEdited = event {
+                this.parse(true, cx);
+            }
+        });
+        let view = cx.view().downgrade();
+        let list_state = ListState::new(0, ListAlignment::Top, px(1000.), move |ix, cx| {
+            view.upgrade()
+                .map(|view| view.update(cx, |this, cx| this.render_line(ix, cx)))
+                .unwrap_or_else(|| div().into_any_element())
+        });
+        let mut this = Self {
+            editor,
+            focus_handle: cx.focus_handle(),
+            lines: Vec::new(),
+            raw_lines: Vec::new(),
+            show_raw_codes: false,
+            list_state,
+            parse_task: Task::ready(()),
+            _subscription: subscription,
+        };
+        this.parse(false, cx);
+        this
+    }
+
+    fn parse(&mut self, debounce: bool, cx: &mut ViewContext<Self>) {
+        let text = self.editor.read(cx).buffer().read(cx).snapshot(cx).text();
+        self.parse_task = cx.spawn(|this, mut cx| async move {
+            if debounce {
+                cx.background_executor().timer(REPARSE_DEBOUNCE).await;
+            }
+            let (lines, raw_lines) = cx
+                .background_executor()
+                .spawn(async move { (parse_ansi(&text), raw_lines(&text)) })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.lines = lines;
+                this.raw_lines = raw_lines.into_iter().map(SharedString::from).collect();
+                this.reset_list();
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn reset_list(&self) {
+        let scroll_top = self.list_state.logical_scroll_top();
+        self.list_state.reset(self.lines.len());
+        self.list_state.scroll_to(scroll_top);
+    }
+
+    fn toggle_raw_codes(&mut self, _: &ToggleRawCodes, cx: &mut ViewContext<Self>) {
+        self.show_raw_codes = !self.show_raw_codes;
+        self.reset_list();
+        cx.notify();
+    }
+
+    fn text_style(cx: &WindowContext) -> TextStyle {
+        let settings = ThemeSettings::get_global(cx);
+        TextStyle {
+            color: cx.theme().colors().editor_foreground,
+            font_family: settings.buffer_font.family.clone(),
+            font_features: settings.buffer_font.features.clone(),
+            font_fallbacks: settings.buffer_font.fallbacks.clone(),
+            font_size: settings.buffer_font_size(cx).into(),
+            font_weight: settings.buffer_font.weight,
+            line_height: relative(settings.buffer_line_height.value()),
+            ..Default::default()
+        }
+    }
+
+    fn render_line(&self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let text_style = Self::text_style(cx);
+        let text = if self.show_raw_codes {
+            let Some(line) = self.raw_lines.get(ix) else {
+                return div().into_any_element();
+            };
+            StyledText::new(line.clone()).with_highlights(&text_style, [])
+        } else {
+            let Some(line) = self.lines.get(ix) else {
+                return div().into_any_element();
+            };
+            let theme = cx.theme();
+            StyledText::new(line.text.clone()).with_highlights(
+                &text_style,
+                line.spans
+                    .iter()
+                    .map(|(range, style)| (range.clone(), highlight_style(style, theme))),
+            )
+        };
+        div()
+            .px_4()
+            .min_h(text_style.line_height_in_pixels(cx.rem_size()))
+            .child(text)
+            .into_any_element()
+    }
+}
+
+fn ansi_color(color: AnsiColor, theme: &Theme) -> Hsla {
+    match color {
+        AnsiColor::Indexed(index) => terminal::get_color_at_index(index as usize, theme),
+        AnsiColor::Rgb(r, g, b) => terminal::rgba_color(r, g, b),
+    }
+}
+
+fn highlight_style(style: &AnsiStyle, theme: &Theme) -> HighlightStyle {
+    let mut foreground = style.foreground.map(|color| ansi_color(color, theme));
+    let mut background = style.background.map(|color| ansi_color(color, theme));
+    if style.inverse {
+        let colors = theme.colors();
+        (foreground, background) = (
+            Some(background.unwrap_or(colors.editor_background)),
+            Some(foreground.unwrap_or(colors.editor_foreground)),
+        );
+    }
+    HighlightStyle {
+        color: foreground,
+        background_color: background,
+        font_weight: style.bold.then_some(FontWeight::BOLD),
+        font_style: style.italic.then_some(FontStyle::Italic),
+        underline: style.underline.then_some(UnderlineStyle {
+            thickness: px(1.),
+            color: None,
+            wavy: false,
+            ..Default::default()
+        }),
+        strikethrough: style.strikethrough.then_some(StrikethroughStyle {
+            thickness: px(1.),
+            color: None,
+        }),
+        fade_out: style.dim.then_some(0.4),
+    }
+}
+
+impl EventEmitter<()> for AnsiView {}
+
+impl FocusableView for AnsiView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for AnsiView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let (icon, tooltip) = if self.show_raw_codes {
+            (IconName::Eye, "Render Escape Codes")
+        } else {
+            (IconName::Code, "Show Raw Escape Codes")
+        };
+        v_flex()
+            .key_context("AnsiView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::toggle_raw_codes))
+            .relative()
+            .size_full()
+            .py_2()
+            .bg(cx.theme().colors().editor_background)
+            .child(list(self.list_state.clone()).size_full())
+            .child(
+                div().absolute().top_2().right_4().child(
+                    IconButton::new("toggle-raw-codes", icon)
+                        .selected(self.show_raw_codes)
+                        .tooltip(move |cx| Tooltip::for_action(tooltip, &ToggleRawCodes, cx))
+                        .on_click(
+                            cx.listener(|this, _, cx| this.toggle_raw_codes(&ToggleRawCodes, cx)),
+                        ),
+                ),
+            )
+    }
+}
+
+impl Item for AnsiView {
+    type Event = ();
+
+    fn tab_icon(&self, _: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::Terminal))
+    }
+
+    fn tab_content_text(&self, cx: &WindowContext) -> Option<SharedString> {
+        let title = self.editor.read(cx).title(cx);
+        Some(format!("{title} (Rendered)").into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(ItemEvent)) {}
+
+    fn clone_on_split(
+        &self,
+        _: Option<workspace::WorkspaceId>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        let editor = self.editor.clone();
+        Some(cx.new_view(|cx| Self::new(editor, cx)))
+    }
+}
@@ -1,11 +1,13 @@
 use super::{diagnostics_command::collect_buffer_diagnostics, SlashCommand, SlashCommandOutput};
 use anyhow::{anyhow, Context as _, Result};
 use assistant_slash_command::{AfterCompletion, ArgumentCompletion, SlashCommandOutputSection};
+use env_file::{is_env_file, EnvFileSettings};
 use fuzzy::PathMatch;
 use gpui::{AppContext, Model, Task, View, WeakView};
 use language::{BufferSnapshot, CodeLabel, HighlightId, LineEnding, LspAdapterDelegate};
 use project::{PathMatchCandidateSet, Project};
 use serde::{Deserialize, Serialize};
+use settings::Settings as _;
 use std::{
     fmt::Write,
     ops::{Range, RangeInclusive},
@@ -210,6 +212,7 @@ fn collect_files(
         return Task::ready(Err(anyhow!("invalid path")));
     };
 
+    let include_env_files = EnvFileSettings::get_global(cx).include_in_assistant_context;
     let project_handle = project.downgrade();
     let snapshots = project
         .read(cx)
@@ -297,6 +300,10 @@ fn collect_files(
                     }
                     output.text.push('\n');
                 } else if entry.is_file() {
+                    // Environment files are likely to contain secrets.
+                    if !include_env_files && is_env_file(&entry.path) {
+                        continue;
+                    }
                     let Some(open_buffer_task) = project_handle
                         .update(&mut cx, |project, cx| {
                             project.open_buffer((worktree_id, &entry.path), cx)
@@ -524,11 +531,12 @@ pub fn append_buffer_to_output(
 
 #[cfg(test)]
 mod test {
+    use env_file::EnvFileSettings;
     use fs::FakeFs;
     use gpui::TestAppContext;
     use project::Project;
     use serde_json::json;
-    use settings::SettingsStore;
+    use settings::{Settings as _, SettingsStore};
 
     use crate::slash_command::file_command::collect_files;
 
@@ -543,6 +551,7 @@ mod test {
             // release_channel::init(SemanticVersion::default(), cx);
             language::init(cx);
             Project::init_settings(cx);
+            EnvFileSettings::register(cx);
         });
     }
 
@@ -719,4 +728,43 @@ mod test {
         // Ensure that the project lasts until after the last await
         drop(project);
     }
+
+    #[gpui::test]
+    async fn test_file_skips_env_files(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        fs.insert_tree(
+            "/root",
+            json!({
+                "app": {
+                    ".env": "API_KEY=secret",
+                    ".env.example": "API_KEY=",
+                    "main.rs": "fn main() {}",
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+
+        let result = cx
+            .update(|cx| collect_files(project.clone(), &["root/app".to_string()], cx))
+            .await
+            .unwrap();
+
+        assert!(!result.text.contains("secret"));
+        let labels = result
+            .sections
+            .iter()
+            .map(|section| section.label.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            ["root/app/.env.example", "root/app/main.rs", "root/app"]
+        );
+
+        // Ensure that the project lasts until after the last await
+        drop(project);
+    }
 }
@@ -5,10 +5,12 @@ use super::{
 };
 use anyhow::Result;
 use assistant_slash_command::{ArgumentCompletion, SlashCommandOutputSection};
+use env_file::{is_env_file, EnvFileSettings};
 use feature_flags::FeatureFlag;
 use gpui::{AppContext, Task, WeakView};
 use language::{CodeLabel, LineEnding, LspAdapterDelegate};
 use semantic_index::{LoadedSearchResult, SemanticDb};
+use settings::Settings as _;
 use std::{
     fmt::Write,
     sync::{atomic::AtomicBool, Arc},
@@ -98,6 +100,7 @@ impl SlashCommand for SearchSlashCommand {
             return Task::ready(Err(anyhow::anyhow!("no project indexer")));
         };
 
+        let include_env_files = EnvFileSettings::get_global(cx).include_in_assistant_context;
         cx.spawn(|cx| async move {
             let results = project_index
                 .read_with(&cx, |project_index, cx| {
@@ -105,7 +108,11 @@ impl SlashCommand for SearchSlashCommand {
                 })?
                 .await?;
 
-            let loaded_results = SemanticDb::load_results(results, &fs, &cx).await?;
+            let mut loaded_results = SemanticDb::load_results(results, &fs, &cx).await?;
+            // Environment files are likely to contain secrets.
+            if !include_env_files {
+                loaded_results.retain(|result| !is_env_file(&result.path));
+            }
 
             let output = cx
                 .background_executor()
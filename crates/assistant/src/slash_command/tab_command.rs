@@ -3,9 +3,11 @@ use anyhow::{Context, Result};
 use assistant_slash_command::{ArgumentCompletion, SlashCommandOutputSection};
 use collections::{HashMap, HashSet};
 use editor::Editor;
+use env_file::{is_env_file, EnvFileSettings};
 use futures::future::join_all;
 use gpui::{Entity, Task, WeakView};
 use language::{BufferSnapshot, CodeLabel, HighlightId, LspAdapterDelegate};
+use settings::Settings as _;
 use std::{
     path::PathBuf,
     sync::{atomic::AtomicBool, Arc},
@@ -141,9 +143,14 @@ impl SlashCommand for TabSlashCommand {
             cx,
         );
 
+        let include_env_files = EnvFileSettings::get_global(cx).include_in_assistant_context;
         cx.background_executor().spawn(async move {
             let mut output = SlashCommandOutput::default();
             for (full_path, buffer, _) in tab_items_search.await? {
+                // Environment files are likely to contain secrets.
+                if !include_env_files && full_path.as_deref().map_or(false, is_env_file) {
+                    continue;
+                }
                 append_buffer_to_output(&buffer, full_path.as_deref(), &mut output).log_err();
             }
             Ok(output)
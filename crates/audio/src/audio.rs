@@ -1,16 +1,25 @@
 use assets::SoundRegistry;
 use derive_more::{Deref, DerefMut};
 use gpui::{AppContext, AssetSource, BorrowAppContext, Global};
-use rodio::{OutputStream, OutputStreamHandle};
+use rodio::{OutputStream, OutputStreamHandle, Source as _};
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::Settings;
 use util::ResultExt;
 
 mod assets;
+pub mod audio_settings;
+
+use audio_settings::AudioSettings;
 
 pub fn init(source: impl AssetSource, cx: &mut AppContext) {
+    AudioSettings::register(cx);
     SoundRegistry::set_global(source, cx);
     cx.set_global(GlobalAudio(Audio::new()));
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum Sound {
     Joined,
     Leave,
@@ -18,17 +27,33 @@ pub enum Sound {
     Unmute,
     StartScreenshare,
     StopScreenshare,
+    TaskFinished,
+    Error,
+}
+
+enum SoundSource {
+    /// A sample bundled with Zed's assets.
+    Bundled(&'static str),
+    /// One of the operating system's named sounds.
+    System(&'static str),
 }
 
 impl Sound {
-    fn file(&self) -> &'static str {
+    fn source(&self) -> SoundSource {
         match self {
-            Self::Joined => "joined_call",
-            Self::Leave => "leave_call",
-            Self::Mute => "mute",
-            Self::Unmute => "unmute",
-            Self::StartScreenshare => "start_screenshare",
-            Self::StopScreenshare => "stop_screenshare",
+            Self::Joined => SoundSource::Bundled("joined_call"),
+            Self::Leave => SoundSource::Bundled("leave_call"),
+            Self::Mute => SoundSource::Bundled("mute"),
+            Self::Unmute => SoundSource::Bundled("unmute"),
+            Self::StartScreenshare => SoundSource::Bundled("start_screenshare"),
+            Self::StopScreenshare => SoundSource::Bundled("stop_screenshare"),
+            // Notifications use the sounds people already know from their operating system.
+            Self::TaskFinished if cfg!(target_os = "windows") => {
+                SoundSource::System("SystemAsterisk")
+            }
+            Self::TaskFinished => SoundSource::System("Glass"),
+            Self::Error if cfg!(target_os = "windows") => SoundSource::System("SystemHand"),
+            Self::Error => SoundSource::System("Basso"),
         }
     }
 }
@@ -64,12 +89,22 @@ impl Audio {
             return;
         }
 
-        cx.update_global::<GlobalAudio, _>(|this, cx| {
-            let output_handle = this.ensure_output_exists()?;
-            let source = SoundRegistry::global(cx).get(sound.file()).log_err()?;
-            output_handle.play_raw(source).log_err()?;
-            Some(())
-        });
+        let volume = AudioSettings::get_global(cx).volume(sound);
+        if volume <= 0. {
+            return;
+        }
+
+        match sound.source() {
+            SoundSource::Bundled(file) => {
+                cx.update_global::<GlobalAudio, _>(|this, cx| {
+                    let output_handle = this.ensure_output_exists()?;
+                    let source = SoundRegistry::global(cx).get(file).log_err()?;
+                    output_handle.play_raw(source.amplify(volume)).log_err()?;
+                    Some(())
+                });
+            }
+            SoundSource::System(name) => cx.play_system_sound(name, volume),
+        }
     }
 
     pub fn end_call(cx: &mut AppContext) {
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use collections::HashMap;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+use crate::Sound;
+
+#[derive(Deserialize, Debug)]
+pub struct AudioSettings {
+    pub mute: bool,
+    pub volume: HashMap<Sound, f32>,
+}
+
+impl AudioSettings {
+    /// The volume to play the given sound at, from 0 to 1.
+    pub fn volume(&self, sound: Sound) -> f32 {
+        if self.mute {
+            return 0.;
+        }
+        self.volume.get(&sound).copied().unwrap_or(1.).clamp(0., 1.)
+    }
+}
+
+/// Configuration of the sound effects Zed plays.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct AudioSettingsContent {
+    /// Whether to mute all sound effects.
+    ///
+    /// Default: false
+    pub mute: Option<bool>,
+
+    /// The volume of each sound effect, from 0 to 1. Sound effects that aren't listed play
+    /// at full volume.
+    ///
+    /// Default: {"task_finished": 0, "error": 0}
+    pub volume: Option<HashMap<Sound, f32>>,
+}
+
+impl Settings for AudioSettings {
+    const KEY: Option<&'static str> = Some("audio");
+
+    type FileContent = AudioSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -9,8 +9,8 @@ use std::{rc::Rc, sync::Arc};
 
 pub use collab_panel::CollabPanel;
 use gpui::{
-    point, AppContext, Pixels, PlatformDisplay, Size, WindowBackgroundAppearance, WindowBounds,
-    WindowDecorations, WindowKind, WindowOptions,
+    point, AppContext, ColorSpace, Pixels, PlatformDisplay, Size, WindowBackgroundAppearance,
+    WindowBounds, WindowDecorations, WindowKind, WindowOptions,
 };
 use panel_settings::MessageEditorSettings;
 pub use panel_settings::{
@@ -65,7 +65,9 @@ fn notification_window_options(
         display_id: Some(screen.id()),
         window_background: WindowBackgroundAppearance::Transparent,
         app_id: Some(app_id.to_owned()),
+        icon: None,
         window_min_size: None,
         window_decorations: Some(WindowDecorations::Client),
+        color_space: ColorSpace::default(),
     }
 }
@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Context as _, Result};
+use std::path::{Path, PathBuf};
+
+use crate::report::{resolve_source_path, CoverageReport};
+
+/// Parses a Cobertura XML report, as produced by `cargo tarpaulin`, `coverage.py xml`,
+/// `istanbul` and most Java and .NET tooling.
+pub(crate) fn parse(text: &str, base_dir: &Path) -> Result<CoverageReport> {
+    let document = roxmltree::Document::parse(text).context("invalid cobertura xml")?;
+    let root = document.root_element();
+    if !root.has_tag_name("coverage") {
+        return Err(anyhow!(
+            "expected a <coverage> root element, found <{}>",
+            root.tag_name().name()
+        ));
+    }
+
+    // Class file names are relative to the first listed source directory.
+    let source_dir = root
+        .descendants()
+        .filter(|node| node.has_tag_name("source"))
+        .filter_map(|node| node.text())
+        .map(|source| resolve_source_path(source, base_dir))
+        .next()
+        .unwrap_or_else(|| PathBuf::from(base_dir));
+
+    let mut report = CoverageReport::default();
+    for class in root.descendants().filter(|node| node.has_tag_name("class")) {
+        let Some(filename) = class.attribute("filename") else {
+            continue;
+        };
+        let file = report.file_mut(resolve_source_path(filename, &source_dir));
+        for line in class.descendants().filter(|node| node.has_tag_name("line")) {
+            let number = line
+                .attribute("number")
+                .ok_or_else(|| anyhow!("<line> element without a number"))?
+                .parse::<u32>()?;
+            let hits = line
+                .attribute("hits")
+                .map(|hits| hits.parse::<u64>())
+                .transpose()?
+                .unwrap_or(0);
+            let row = number.saturating_sub(1);
+            file.record_hits(row, hits);
+            if line.attribute("branch") == Some("true") {
+                if let Some((hit, found)) = line
+                    .attribute("condition-coverage")
+                    .and_then(parse_condition_coverage)
+                {
+                    file.record_branches(row, found, hit);
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Parses the `"50% (1/2)"` format used by the `condition-coverage` attribute.
+fn parse_condition_coverage(value: &str) -> Option<(u32, u32)> {
+    let fraction = value.split_once('(')?.1.strip_suffix(')')?;
+    let (hit, found) = fraction.split_once('/')?;
+    Some((hit.trim().parse().ok()?, found.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::LineStatus;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_cobertura() {
+        let report = parse(
+            indoc! {r#"
+                <?xml version="1.0" ?>
+                <coverage line-rate="0.66" version="1.9">
+                    <sources>
+                        <source>src</source>
+                    </sources>
+                    <packages>
+                        <package name="app">
+                            <classes>
+                                <class name="lib" filename="app/lib.py">
+                                    <lines>
+                                        <line number="1" hits="1"/>
+                                        <line number="2" hits="0"/>
+                                        <line number="4" hits="3" branch="true" condition-coverage="50% (1/2)"/>
+                                    </lines>
+                                </class>
+                            </classes>
+                        </package>
+                    </packages>
+                </coverage>
+            "#},
+            Path::new("/project"),
+        )
+        .unwrap();
+
+        let file = report.file(Path::new("/project/src/app/lib.py")).unwrap();
+        assert_eq!(file.line(0).unwrap().status(), LineStatus::Covered);
+        assert_eq!(file.line(1).unwrap().status(), LineStatus::Uncovered);
+        assert_eq!(file.line(3).unwrap().status(), LineStatus::Partial);
+        assert_eq!(file.instrumented_lines(), 3);
+    }
+
+    #[test]
+    fn test_parse_condition_coverage() {
+        assert_eq!(parse_condition_coverage("50% (1/2)"), Some((1, 2)));
+        assert_eq!(parse_condition_coverage("100%"), None);
+    }
+}
@@ -0,0 +1,63 @@
+mod cobertura;
+mod coverage_panel;
+mod coverage_settings;
+mod coverage_store;
+mod editor_coverage;
+mod lcov;
+mod report;
+
+use fs::Fs;
+use gpui::{actions, AppContext};
+use settings::Settings;
+use std::sync::Arc;
+use workspace::Workspace;
+
+pub use coverage_panel::{CoveragePanel, ToggleFocus};
+pub use coverage_settings::CoverageSettings;
+pub use coverage_store::CoverageStore;
+pub use report::{CoverageReport, FileCoverage, LineCoverage, LineStatus, ReportFormat};
+
+actions!(coverage, [ToggleCoverage, ReloadCoverage]);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    CoverageSettings::register(cx);
+    coverage_store::init(fs, cx);
+
+    cx.observe_new_views(editor_coverage::register).detach();
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        let project = workspace.project().clone();
+        CoverageStore::global(cx).update(cx, |store, cx| store.watch_project(&project, cx));
+        cx.subscribe(&project, |_, project, event, cx| match event {
+            project::Event::WorktreeAdded
+            | project::Event::WorktreeRemoved(_)
+            | project::Event::WorktreeOrderChanged => {
+                CoverageStore::global(cx).update(cx, |store, cx| store.watch_project(&project, cx));
+            }
+            _ => {}
+        })
+        .detach();
+
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<CoveragePanel>(cx);
+            })
+            .register_action(|_, _: &ToggleCoverage, cx| {
+                CoverageStore::global(cx).update(cx, |store, cx| store.toggle_visible(cx));
+            })
+            .register_action(|_, _: &ReloadCoverage, cx| {
+                CoverageStore::global(cx).update(cx, |store, cx| store.reload(cx));
+            });
+    })
+    .detach();
+}
+
+/// Returns the percentage of covered lines for the file at the given absolute path,
+/// if coverage is visible and the file appears in a loaded report.
+pub fn file_percentage(abs_path: &std::path::Path, cx: &AppContext) -> Option<f32> {
+    let store = CoverageStore::try_global(cx)?;
+    let store = store.read(cx);
+    if !store.is_visible() || !CoverageSettings::get_global(cx).project_panel {
+        return None;
+    }
+    store.file_coverage(abs_path)?.percentage()
+}
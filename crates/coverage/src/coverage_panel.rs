@@ -0,0 +1,337 @@
+use crate::{CoverageSettings, CoverageStore, ReloadCoverage, ToggleCoverage};
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use fs::Fs;
+use gpui::{
+    actions, uniform_list, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, Model, Render, Subscription, Task, View, ViewContext, WeakView,
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use std::{path::PathBuf, sync::Arc};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+const COVERAGE_PANEL_KEY: &str = "CoveragePanel";
+
+actions!(coverage_panel, [ToggleFocus]);
+
+#[derive(Serialize, Deserialize)]
+struct SerializedCoveragePanel {
+    width: Option<Pixels>,
+}
+
+struct FileSummary {
+    abs_path: PathBuf,
+    display_path: SharedString,
+    covered_lines: usize,
+    instrumented_lines: usize,
+    percentage: f32,
+}
+
+/// Lists every file in the loaded coverage reports, least covered first.
+pub struct CoveragePanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    store: Model<CoverageStore>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    files: Vec<FileSummary>,
+    total_percentage: Option<f32>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CoveragePanel {
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let worktree_roots = workspace
+            .project()
+            .read(cx)
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path())
+            .collect::<Vec<_>>();
+        let workspace = workspace.weak_handle();
+        cx.new_view(|cx| {
+            let store = CoverageStore::global(cx);
+            let subscriptions = vec![cx.observe(&store, |this, _, cx| this.update_files(cx))];
+            let mut this = Self {
+                fs,
+                workspace,
+                store,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                files: Vec::new(),
+                total_percentage: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            };
+            this.update_files_with_roots(&worktree_roots, cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(COVERAGE_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedCoveragePanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        COVERAGE_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedCoveragePanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_files(&mut self, cx: &mut ViewContext<Self>) {
+        let worktree_roots = self
+            .workspace
+            .upgrade()
+            .map(|workspace| {
+                workspace
+                    .read(cx)
+                    .project()
+                    .read(cx)
+                    .visible_worktrees(cx)
+                    .map(|worktree| worktree.read(cx).abs_path())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        self.update_files_with_roots(&worktree_roots, cx);
+    }
+
+    fn update_files_with_roots(
+        &mut self,
+        worktree_roots: &[Arc<std::path::Path>],
+        cx: &mut ViewContext<Self>,
+    ) {
+        let report = self.store.read(cx).report();
+        self.total_percentage = report.percentage();
+        self.files = report
+            .files()
+            .filter_map(|(abs_path, coverage)| {
+                let display_path = worktree_roots
+                    .iter()
+                    .find_map(|root| abs_path.strip_prefix(root).ok())
+                    .unwrap_or(abs_path);
+                Some(FileSummary {
+                    abs_path: abs_path.to_path_buf(),
+                    display_path: display_path.to_string_lossy().to_string().into(),
+                    covered_lines: coverage.covered_lines(),
+                    instrumented_lines: coverage.instrumented_lines(),
+                    percentage: coverage.percentage()?,
+                })
+            })
+            .collect();
+        self.files.sort_by(|a, b| {
+            a.percentage
+                .total_cmp(&b.percentage)
+                .then_with(|| a.display_path.cmp(&b.display_path))
+        });
+        cx.notify();
+    }
+
+    fn open_file(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(file) = self.files.get(ix) else {
+            return;
+        };
+        let abs_path = file.abs_path.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_abs_path(abs_path, true, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let summary = match self.total_percentage {
+            Some(percentage) => format!("{percentage:.1}% covered"),
+            None => "No coverage data".to_string(),
+        };
+        h_flex()
+            .justify_between()
+            .px_2()
+            .py_1()
+            // Match the height of the tab bar so they line up.
+            .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(summary))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("toggle-coverage", IconName::Eye)
+                            .icon_size(IconSize::Small)
+                            .selected(self.store.read(cx).is_visible())
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Toggle Coverage", &ToggleCoverage, cx)
+                            })
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ToggleCoverage))),
+                    )
+                    .child(
+                        IconButton::new("reload-coverage", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| {
+                                Tooltip::for_action("Reload Coverage", &ReloadCoverage, cx)
+                            })
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(ReloadCoverage))),
+                    ),
+            )
+    }
+
+    fn render_file(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let file = &self.files[ix];
+        let color = if file.percentage >= 80. {
+            Color::Created
+        } else if file.percentage >= 50. {
+            Color::Modified
+        } else {
+            Color::Deleted
+        };
+        ListItem::new(ix)
+            .spacing(ui::ListItemSpacing::Sparse)
+            .child(Label::new(file.display_path.clone()).single_line())
+            .end_slot(
+                Label::new(format!(
+                    "{}/{} · {:.0}%",
+                    file.covered_lines, file.instrumented_lines, file.percentage
+                ))
+                .size(LabelSize::Small)
+                .color(color),
+            )
+            .on_click(cx.listener(move |this, _, cx| this.open_file(ix, cx)))
+    }
+}
+
+impl Render for CoveragePanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CoveragePanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.files.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Run your tests with coverage enabled to see results here.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.view().clone(),
+                            "coverage-files",
+                            self.files.len(),
+                            |this, range, cx| {
+                                range.map(|ix| this.render_file(ix, cx)).collect::<Vec<_>>()
+                            },
+                        )
+                        .size_full(),
+                    )
+                }
+            })
+    }
+}
+
+impl FocusableView for CoveragePanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for CoveragePanel {}
+
+impl Panel for CoveragePanel {
+    fn persistent_name() -> &'static str {
+        "CoveragePanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        CoverageSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<CoverageSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| CoverageSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        let settings = CoverageSettings::get_global(cx);
+        (settings.enabled && settings.button).then_some(IconName::Microscope)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Coverage Panel")
+    }
+
+    fn icon_label(&self, _: &WindowContext) -> Option<String> {
+        self.total_percentage
+            .map(|percentage| format!("{percentage:.0}%"))
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
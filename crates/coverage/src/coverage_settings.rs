@@ -0,0 +1,67 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct CoverageSettings {
+    pub enabled: bool,
+    pub report_paths: Vec<String>,
+    pub gutter: bool,
+    pub line_backgrounds: bool,
+    pub project_panel: bool,
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct CoverageSettingsContent {
+    /// Whether to load coverage reports and show coverage information.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// Paths to lcov or Cobertura reports, relative to each worktree root.
+    /// Reports are reloaded whenever they change on disk.
+    ///
+    /// Default: ["lcov.info", "coverage/lcov.info", "coverage.xml", "cobertura.xml"]
+    pub report_paths: Option<Vec<String>>,
+    /// Whether to mark covered, partially covered and uncovered lines in the gutter.
+    ///
+    /// Default: true
+    pub gutter: Option<bool>,
+    /// Whether to tint the background of uncovered and partially covered lines.
+    ///
+    /// Default: false
+    pub line_backgrounds: Option<bool>,
+    /// Whether to show per-file coverage percentages in the project panel.
+    ///
+    /// Default: true
+    pub project_panel: Option<bool>,
+    /// Whether to show the coverage panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the coverage panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the coverage panel in pixels.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
+impl Settings for CoverageSettings {
+    const KEY: Option<&'static str> = Some("coverage");
+
+    type FileContent = CoverageSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
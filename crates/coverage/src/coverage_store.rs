@@ -0,0 +1,210 @@
+use crate::{
+    report::{CoverageReport, FileCoverage, ReportFormat},
+    CoverageSettings,
+};
+use collections::HashMap;
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{AppContext, Context as _, EntityId, Global, Model, ModelContext, Task};
+use project::Project;
+use settings::{Settings as _, SettingsStore};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use util::ResultExt as _;
+
+const REPORT_WATCH_LATENCY: Duration = Duration::from_millis(250);
+
+pub(crate) fn init(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    let store = cx.new_model(|cx| CoverageStore::new(fs, cx));
+    cx.set_global(GlobalCoverageStore(store));
+}
+
+struct GlobalCoverageStore(Model<CoverageStore>);
+
+impl Global for GlobalCoverageStore {}
+
+/// Loads the coverage reports configured for every open project, keeping them up to date
+/// as test runs rewrite them on disk.
+pub struct CoverageStore {
+    fs: Arc<dyn Fs>,
+    visible: bool,
+    settings: CoverageSettings,
+    projects: HashMap<EntityId, Vec<Arc<Path>>>,
+    watched_reports: HashMap<PathBuf, Task<()>>,
+    loaded_reports: HashMap<PathBuf, CoverageReport>,
+    merged_report: CoverageReport,
+    _settings_subscription: gpui::Subscription,
+}
+
+impl CoverageStore {
+    fn new(fs: Arc<dyn Fs>, cx: &mut ModelContext<Self>) -> Self {
+        let settings = CoverageSettings::get_global(cx).clone();
+        Self {
+            fs,
+            visible: true,
+            settings,
+            projects: HashMap::default(),
+            watched_reports: HashMap::default(),
+            loaded_reports: HashMap::default(),
+            merged_report: CoverageReport::default(),
+            _settings_subscription: cx.observe_global::<SettingsStore>(|this, cx| {
+                let settings = CoverageSettings::get_global(cx);
+                if *settings != this.settings {
+                    this.settings = settings.clone();
+                    this.sync_watched_reports(cx);
+                    cx.notify();
+                }
+            }),
+        }
+    }
+
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalCoverageStore>().0.clone()
+    }
+
+    pub fn try_global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalCoverageStore>()
+            .map(|store| store.0.clone())
+    }
+
+    /// Whether coverage should currently be shown, taking both settings and the
+    /// `coverage::ToggleCoverage` action into account.
+    pub fn is_visible(&self) -> bool {
+        self.settings.enabled && self.visible
+    }
+
+    pub fn toggle_visible(&mut self, cx: &mut ModelContext<Self>) {
+        self.visible = !self.visible;
+        cx.notify();
+    }
+
+    pub fn report(&self) -> &CoverageReport {
+        &self.merged_report
+    }
+
+    pub fn file_coverage(&self, abs_path: &Path) -> Option<&FileCoverage> {
+        self.merged_report.file(abs_path)
+    }
+
+    pub fn reports_paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.loaded_reports.keys().map(PathBuf::as_path)
+    }
+
+    /// Starts watching reports in the local worktrees of the given project.
+    pub fn watch_project(&mut self, project: &Model<Project>, cx: &mut ModelContext<Self>) {
+        let roots = project
+            .read(cx)
+            .visible_worktrees(cx)
+            .filter(|worktree| worktree.read(cx).is_local())
+            .map(|worktree| worktree.read(cx).abs_path())
+            .collect::<Vec<_>>();
+        let project_id = project.entity_id();
+        if self.projects.get(&project_id) == Some(&roots) {
+            return;
+        }
+
+        if !self.projects.contains_key(&project_id) {
+            cx.observe_release(project, move |this, _, cx| {
+                this.projects.remove(&project_id);
+                this.sync_watched_reports(cx);
+            })
+            .detach();
+        }
+        self.projects.insert(project_id, roots);
+        self.sync_watched_reports(cx);
+    }
+
+    /// Reloads every watched report from disk.
+    pub fn reload(&mut self, cx: &mut ModelContext<Self>) {
+        self.watched_reports.clear();
+        self.sync_watched_reports(cx);
+    }
+
+    fn sync_watched_reports(&mut self, cx: &mut ModelContext<Self>) {
+        let report_paths = if self.settings.enabled {
+            self.projects
+                .values()
+                .flatten()
+                .flat_map(|root| {
+                    self.settings
+                        .report_paths
+                        .iter()
+                        .map(|report_path| root.join(report_path))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        self.watched_reports
+            .retain(|path, _| report_paths.contains(path));
+        let previous_report_count = self.loaded_reports.len();
+        self.loaded_reports
+            .retain(|path, _| report_paths.contains(path));
+        if self.loaded_reports.len() != previous_report_count {
+            self.merge_reports(cx);
+        }
+
+        for report_path in report_paths {
+            if !self.watched_reports.contains_key(&report_path) {
+                let task = self.watch_report(report_path.clone(), cx);
+                self.watched_reports.insert(report_path, task);
+            }
+        }
+    }
+
+    fn watch_report(&self, report_path: PathBuf, cx: &mut ModelContext<Self>) -> Task<()> {
+        let fs = self.fs.clone();
+        cx.spawn(|this, mut cx| async move {
+            let (events, _watcher) = fs.watch(&report_path, REPORT_WATCH_LATENCY).await;
+            futures::pin_mut!(events);
+            let base_dir = report_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+
+            loop {
+                let report = match fs.load(&report_path).await {
+                    Ok(text) => {
+                        let format = ReportFormat::detect(&report_path, &text);
+                        let base_dir = base_dir.clone();
+                        cx.background_executor()
+                            .spawn(async move { CoverageReport::parse(&text, format, &base_dir) })
+                            .await
+                            .log_err()
+                    }
+                    Err(_) => None,
+                };
+
+                let updated = this.update(&mut cx, |this, cx| {
+                    match report {
+                        Some(report) => {
+                            this.loaded_reports.insert(report_path.clone(), report);
+                        }
+                        None => {
+                            if this.loaded_reports.remove(&report_path).is_none() {
+                                return;
+                            }
+                        }
+                    }
+                    this.merge_reports(cx);
+                });
+                if updated.is_err() || events.next().await.is_none() {
+                    break;
+                }
+            }
+        })
+    }
+
+    fn merge_reports(&mut self, cx: &mut ModelContext<Self>) {
+        let mut merged_report = CoverageReport::default();
+        for report in self.loaded_reports.values() {
+            merged_report.merge(report);
+        }
+        self.merged_report = merged_report;
+        cx.notify();
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{report::LineStatus, CoverageSettings, CoverageStore};
+use editor::{Anchor, Editor, EditorMode};
+use gpui::{Model, ViewContext};
+use language::Point;
+use settings::Settings as _;
+use std::{ops::Range, path::PathBuf};
+use theme::ActiveTheme as _;
+
+struct CoveredRows;
+struct PartiallyCoveredRows;
+struct UncoveredRows;
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    if editor.mode() != EditorMode::Full {
+        return;
+    }
+    let Some(store) = CoverageStore::try_global(cx) else {
+        return;
+    };
+
+    refresh_coverage(editor, &store, cx);
+    cx.observe(&store, |editor, store, cx| {
+        refresh_coverage(editor, &store, cx);
+    })
+    .detach();
+}
+
+fn refresh_coverage(
+    editor: &mut Editor,
+    store: &Model<CoverageStore>,
+    cx: &mut ViewContext<Editor>,
+) {
+    editor.clear_gutter_highlights::<CoveredRows>(cx);
+    editor.clear_gutter_highlights::<PartiallyCoveredRows>(cx);
+    editor.clear_gutter_highlights::<UncoveredRows>(cx);
+    editor.clear_row_highlights::<PartiallyCoveredRows>();
+    editor.clear_row_highlights::<UncoveredRows>();
+
+    let store = store.read(cx);
+    if !store.is_visible() {
+        return;
+    }
+    let Some(coverage) =
+        editor_abs_path(editor, cx).and_then(|abs_path| store.file_coverage(&abs_path))
+    else {
+        return;
+    };
+
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let max_row = snapshot.max_point().row;
+    let mut covered = Vec::<Range<Anchor>>::new();
+    let mut partially_covered = Vec::new();
+    let mut uncovered = Vec::new();
+    for (row, line) in coverage.lines() {
+        if row > max_row {
+            break;
+        }
+        let anchor = snapshot.anchor_before(Point::new(row, 0));
+        match line.status() {
+            LineStatus::Covered => covered.push(anchor..anchor),
+            LineStatus::Partial => partially_covered.push(anchor..anchor),
+            LineStatus::Uncovered => uncovered.push(anchor..anchor),
+        }
+    }
+
+    let settings = CoverageSettings::get_global(cx);
+    let (gutter, line_backgrounds) = (settings.gutter, settings.line_backgrounds);
+    if gutter {
+        editor.highlight_gutter::<CoveredRows>(&covered, |cx| cx.theme().status().created, cx);
+        editor.highlight_gutter::<PartiallyCoveredRows>(
+            &partially_covered,
+            |cx| cx.theme().status().modified,
+            cx,
+        );
+        editor.highlight_gutter::<UncoveredRows>(&uncovered, |cx| cx.theme().status().deleted, cx);
+    }
+    if line_backgrounds {
+        let partial_background = cx.theme().status().modified.opacity(0.12);
+        for range in partially_covered {
+            editor.highlight_rows::<PartiallyCoveredRows>(
+                range.start..=range.end,
+                Some(partial_background),
+                false,
+                cx,
+            );
+        }
+        let uncovered_background = cx.theme().status().deleted.opacity(0.12);
+        for range in uncovered {
+            editor.highlight_rows::<UncoveredRows>(
+                range.start..=range.end,
+                Some(uncovered_background),
+                false,
+                cx,
+            );
+        }
+    }
+}
+
+fn editor_abs_path(editor: &Editor, cx: &ViewContext<Editor>) -> Option<PathBuf> {
+    let buffer = editor.buffer().read(cx).as_singleton()?;
+    let file = buffer.read(cx).file()?.as_local()?;
+    Some(file.abs_path(cx))
+}
@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Context as _, Result};
+use std::path::{Path, PathBuf};
+
+use crate::report::{resolve_source_path, CoverageReport, FileCoverage};
+
+/// Parses a report in the `lcov` tracefile format, as produced by `genhtml`-compatible tools
+/// such as `cargo llvm-cov --lcov`, `c8`, `nyc` and `coverage.py`.
+pub(crate) fn parse(text: &str, base_dir: &Path) -> Result<CoverageReport> {
+    let mut report = CoverageReport::default();
+    let mut current = None;
+
+    for (ix, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let (tag, value) = line.split_once(':').unwrap_or((line, ""));
+        parse_record(tag, value, base_dir, &mut current, &mut report)
+            .with_context(|| format!("invalid lcov record on line {}", ix + 1))?;
+    }
+
+    if let Some((path, coverage)) = current.take() {
+        report.file_mut(path).merge(&coverage);
+    }
+
+    Ok(report)
+}
+
+fn parse_record(
+    tag: &str,
+    value: &str,
+    base_dir: &Path,
+    current: &mut Option<(PathBuf, FileCoverage)>,
+    report: &mut CoverageReport,
+) -> Result<()> {
+    match tag {
+        "SF" => {
+            if let Some((path, coverage)) = current.take() {
+                report.file_mut(path).merge(&coverage);
+            }
+            *current = Some((
+                resolve_source_path(value, base_dir),
+                FileCoverage::default(),
+            ));
+        }
+        "DA" => {
+            let (_, coverage) = current
+                .as_mut()
+                .ok_or_else(|| anyhow!("DA record outside of a source file"))?;
+            let mut fields = value.split(',');
+            let row = parse_row(fields.next())?;
+            let hits = fields
+                .next()
+                .ok_or_else(|| anyhow!("missing hit count"))?
+                .parse::<i64>()?;
+            coverage.record_hits(row, hits.max(0) as u64);
+        }
+        "BRDA" => {
+            let (_, coverage) = current
+                .as_mut()
+                .ok_or_else(|| anyhow!("BRDA record outside of a source file"))?;
+            let mut fields = value.split(',');
+            let row = parse_row(fields.next())?;
+            let taken = fields
+                .nth(2)
+                .ok_or_else(|| anyhow!("missing branch count"))?;
+            let hit = taken != "-" && taken.parse::<u64>().unwrap_or(0) > 0;
+            coverage.record_branches(row, 1, hit as u32);
+        }
+        "end_of_record" => {
+            if let Some((path, coverage)) = current.take() {
+                report.file_mut(path).merge(&coverage);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn parse_row(field: Option<&str>) -> Result<u32> {
+    let line_number = field
+        .ok_or_else(|| anyhow!("missing line number"))?
+        .parse::<u32>()?;
+    Ok(line_number.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::LineStatus;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_lcov() {
+        let report = parse(
+            indoc! {"
+                TN:
+                SF:src/lib.rs
+                FN:1,add
+                DA:1,4
+                DA:2,0
+                DA:3,2
+                BRDA:3,0,0,1
+                BRDA:3,0,1,-
+                LF:3
+                LH:2
+                end_of_record
+                SF:/abs/main.rs
+                DA:10,1
+                end_of_record
+            "},
+            Path::new("/project"),
+        )
+        .unwrap();
+
+        let lib = report.file(Path::new("/project/src/lib.rs")).unwrap();
+        assert_eq!(lib.line(0).unwrap().status(), LineStatus::Covered);
+        assert_eq!(lib.line(1).unwrap().status(), LineStatus::Uncovered);
+        assert_eq!(lib.line(2).unwrap().status(), LineStatus::Partial);
+
+        let main = report.file(Path::new("/abs/main.rs")).unwrap();
+        assert_eq!(main.line(9).unwrap().hits, 1);
+        assert_eq!(main.percentage(), Some(100.));
+    }
+
+    #[test]
+    fn test_parse_lcov_error() {
+        assert!(parse("DA:1,1\n", Path::new("/project")).is_err());
+    }
+}
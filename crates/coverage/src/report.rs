@@ -0,0 +1,220 @@
+use anyhow::Result;
+use collections::HashMap;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{cobertura, lcov};
+
+/// How well a single line was exercised by the test run that produced a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineStatus {
+    Covered,
+    /// The line was executed, but not every branch on it was taken.
+    Partial,
+    Uncovered,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineCoverage {
+    pub hits: u64,
+    pub branches_found: u32,
+    pub branches_hit: u32,
+}
+
+impl LineCoverage {
+    pub fn status(&self) -> LineStatus {
+        if self.hits == 0 {
+            LineStatus::Uncovered
+        } else if self.branches_hit < self.branches_found {
+            LineStatus::Partial
+        } else {
+            LineStatus::Covered
+        }
+    }
+}
+
+/// Coverage data for a single source file, keyed by zero-based row.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    lines: BTreeMap<u32, LineCoverage>,
+}
+
+impl FileCoverage {
+    pub fn record_hits(&mut self, row: u32, hits: u64) {
+        let line = self.lines.entry(row).or_default();
+        line.hits = line.hits.saturating_add(hits);
+    }
+
+    pub fn record_branches(&mut self, row: u32, found: u32, hit: u32) {
+        let line = self.lines.entry(row).or_default();
+        line.branches_found += found;
+        line.branches_hit += hit.min(found);
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = (u32, &LineCoverage)> + '_ {
+        self.lines.iter().map(|(row, line)| (*row, line))
+    }
+
+    pub fn line(&self, row: u32) -> Option<&LineCoverage> {
+        self.lines.get(&row)
+    }
+
+    pub fn instrumented_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn covered_lines(&self) -> usize {
+        self.lines.values().filter(|line| line.hits > 0).count()
+    }
+
+    /// Returns the percentage of instrumented lines that were executed at least once,
+    /// or `None` if the file has no instrumented lines.
+    pub fn percentage(&self) -> Option<f32> {
+        percentage(self.covered_lines(), self.instrumented_lines())
+    }
+
+    pub fn merge(&mut self, other: &FileCoverage) {
+        for (row, line) in &other.lines {
+            self.record_hits(*row, line.hits);
+            let merged = self.lines.entry(*row).or_default();
+            merged.branches_found = merged.branches_found.max(line.branches_found);
+            merged.branches_hit = merged
+                .branches_hit
+                .max(line.branches_hit)
+                .min(merged.branches_found);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Lcov,
+    Cobertura,
+}
+
+impl ReportFormat {
+    /// Guesses the report format from its path, falling back to sniffing the contents.
+    pub fn detect(path: &Path, text: &str) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("xml") => Self::Cobertura,
+            Some("info") | Some("lcov") => Self::Lcov,
+            _ if text.trim_start().starts_with('<') => Self::Cobertura,
+            _ => Self::Lcov,
+        }
+    }
+}
+
+/// The parsed contents of one or more coverage reports, keyed by absolute source path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    files: HashMap<PathBuf, FileCoverage>,
+}
+
+impl CoverageReport {
+    /// Parses a report, resolving relative source paths against `base_dir`.
+    pub fn parse(text: &str, format: ReportFormat, base_dir: &Path) -> Result<Self> {
+        match format {
+            ReportFormat::Lcov => lcov::parse(text, base_dir),
+            ReportFormat::Cobertura => cobertura::parse(text, base_dir),
+        }
+    }
+
+    pub fn file_mut(&mut self, path: PathBuf) -> &mut FileCoverage {
+        self.files.entry(path).or_default()
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&FileCoverage> {
+        self.files.get(path)
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &FileCoverage)> + '_ {
+        self.files
+            .iter()
+            .map(|(path, coverage)| (path.as_path(), coverage))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn merge(&mut self, other: &CoverageReport) {
+        for (path, coverage) in &other.files {
+            self.files.entry(path.clone()).or_default().merge(coverage);
+        }
+    }
+
+    /// Returns the percentage of instrumented lines covered across every file in the report.
+    pub fn percentage(&self) -> Option<f32> {
+        let (covered, instrumented) =
+            self.files
+                .values()
+                .fold((0, 0), |(covered, instrumented), file| {
+                    (
+                        covered + file.covered_lines(),
+                        instrumented + file.instrumented_lines(),
+                    )
+                });
+        percentage(covered, instrumented)
+    }
+}
+
+fn percentage(covered: usize, instrumented: usize) -> Option<f32> {
+    if instrumented == 0 {
+        None
+    } else {
+        Some(covered as f32 * 100. / instrumented as f32)
+    }
+}
+
+pub(crate) fn resolve_source_path(path: &str, base_dir: &Path) -> PathBuf {
+    let path = Path::new(path.trim());
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_status() {
+        let mut file = FileCoverage::default();
+        file.record_hits(0, 3);
+        file.record_hits(1, 0);
+        file.record_hits(2, 1);
+        file.record_branches(2, 2, 1);
+
+        assert_eq!(file.line(0).unwrap().status(), LineStatus::Covered);
+        assert_eq!(file.line(1).unwrap().status(), LineStatus::Uncovered);
+        assert_eq!(file.line(2).unwrap().status(), LineStatus::Partial);
+        assert_eq!(file.covered_lines(), 2);
+        assert_eq!(file.instrumented_lines(), 3);
+    }
+
+    #[test]
+    fn test_merge_reports() {
+        let mut first = CoverageReport::default();
+        first.file_mut("/a.rs".into()).record_hits(0, 0);
+        first.file_mut("/a.rs".into()).record_hits(1, 2);
+
+        let mut second = CoverageReport::default();
+        second.file_mut("/a.rs".into()).record_hits(0, 1);
+        second.file_mut("/b.rs".into()).record_hits(0, 0);
+
+        first.merge(&second);
+        assert_eq!(
+            first.file(Path::new("/a.rs")).unwrap().percentage(),
+            Some(100.)
+        );
+        assert_eq!(
+            first.file(Path::new("/b.rs")).unwrap().percentage(),
+            Some(0.)
+        );
+        assert_eq!(first.percentage(), Some(200. / 3.));
+    }
+}
@@ -0,0 +1,66 @@
+mod database_panel;
+mod database_settings;
+mod driver;
+mod password_prompt;
+mod postgres;
+mod results_view;
+mod sqlite;
+
+use editor::Editor;
+use gpui::{actions, AppContext};
+use settings::Settings;
+use workspace::Workspace;
+
+pub use database_panel::{DatabasePanel, ToggleFocus};
+pub use database_settings::{ConnectionSettings, DatabaseSettings, DriverSettings};
+pub use driver::{ColumnSchema, DatabaseConnection, QueryResult, TableSchema};
+pub use results_view::QueryResultsView;
+
+actions!(database_explorer, [RunQuery, RefreshSchema]);
+
+pub fn init(cx: &mut AppContext) {
+    DatabaseSettings::register(cx);
+
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, cx| {
+                workspace.toggle_panel_focus::<DatabasePanel>(cx);
+            })
+            .register_action(run_query);
+    })
+    .detach();
+}
+
+/// Runs the selected SQL in the active editor, or all of it if nothing is selected, against
+/// the connection selected in the database panel.
+fn run_query(workspace: &mut Workspace, _: &RunQuery, cx: &mut gpui::ViewContext<Workspace>) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some(panel) = workspace.panel::<DatabasePanel>(cx) else {
+        return;
+    };
+
+    let sql = {
+        let editor = editor.read(cx);
+        let selection = editor.selections.newest::<usize>(cx);
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        if selection.is_empty() {
+            snapshot.text()
+        } else {
+            snapshot
+                .text_for_range(selection.range())
+                .collect::<String>()
+        }
+    };
+    if sql.trim().is_empty() {
+        return;
+    }
+
+    match panel.read(cx).active_connection() {
+        Ok((connection_name, connection)) => {
+            results_view::run_query(workspace, connection_name, connection, sql, cx)
+        }
+        Err(error) => workspace.show_error(&error, cx),
+    }
+}
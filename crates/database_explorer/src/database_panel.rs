@@ -0,0 +1,611 @@
+use crate::{
+    database_settings::{ConnectionSettings, DatabaseSettings},
+    driver::{self, DatabaseConnection, TableSchema},
+    password_prompt::PasswordPrompt,
+    results_view::run_query,
+    RefreshSchema,
+};
+use anyhow::{anyhow, Result};
+use collections::HashSet;
+use db::kvp::KEY_VALUE_STORE;
+use fs::Fs;
+use gpui::{
+    actions, uniform_list, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, Render, SharedString, Subscription, Task, View, ViewContext, WeakView,
+};
+use serde::{Deserialize, Serialize};
+use settings::{Settings as _, SettingsStore};
+use std::{path::Path, sync::Arc};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+const DATABASE_PANEL_KEY: &str = "DatabasePanel";
+
+actions!(database_panel, [ToggleFocus]);
+
+#[derive(Serialize, Deserialize)]
+struct SerializedDatabasePanel {
+    width: Option<Pixels>,
+}
+
+enum ConnectionStatus {
+    Disconnected,
+    Connecting,
+    Connected {
+        connection: Arc<dyn DatabaseConnection>,
+        tables: Vec<TableSchema>,
+    },
+    Failed(SharedString),
+}
+
+struct ConnectionEntry {
+    settings: ConnectionSettings,
+    status: ConnectionStatus,
+    expanded: bool,
+    expanded_tables: HashSet<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum Entry {
+    Connection(usize),
+    Table {
+        connection_ix: usize,
+        table_ix: usize,
+    },
+    Column {
+        connection_ix: usize,
+        table_ix: usize,
+        column_ix: usize,
+    },
+}
+
+/// Lists the configured database connections and lets you browse their schemas.
+pub struct DatabasePanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    connections: Vec<ConnectionEntry>,
+    active_connection: Option<usize>,
+    entries: Vec<Entry>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DatabasePanel {
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        cx.new_view(|cx| {
+            let subscriptions = vec![cx.observe_global::<SettingsStore>(|this, cx| {
+                this.update_connections(cx);
+            })];
+            let mut this = Self {
+                fs,
+                workspace,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                connections: Vec::new(),
+                active_connection: None,
+                entries: Vec::new(),
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            };
+            this.update_connections(cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(DATABASE_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedDatabasePanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        DATABASE_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedDatabasePanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Syncs the connection list with settings, keeping open connections whose settings
+    /// did not change.
+    fn update_connections(&mut self, cx: &mut ViewContext<Self>) {
+        let settings = DatabaseSettings::get_global(cx).connections.clone();
+        let active_name = self
+            .active_connection
+            .and_then(|ix| self.connections.get(ix))
+            .map(|entry| entry.settings.name.clone());
+
+        let mut old_connections = std::mem::take(&mut self.connections);
+        self.connections = settings
+            .into_iter()
+            .map(|settings| {
+                match old_connections
+                    .iter()
+                    .position(|entry| entry.settings == settings)
+                {
+                    Some(ix) => old_connections.remove(ix),
+                    None => ConnectionEntry {
+                        settings,
+                        status: ConnectionStatus::Disconnected,
+                        expanded: false,
+                        expanded_tables: HashSet::default(),
+                    },
+                }
+            })
+            .collect();
+        self.active_connection = active_name.and_then(|name| {
+            self.connections
+                .iter()
+                .position(|entry| entry.settings.name == name)
+        });
+        self.update_entries(cx);
+    }
+
+    fn update_entries(&mut self, cx: &mut ViewContext<Self>) {
+        self.entries.clear();
+        for (connection_ix, connection) in self.connections.iter().enumerate() {
+            self.entries.push(Entry::Connection(connection_ix));
+            let ConnectionStatus::Connected { tables, .. } = &connection.status else {
+                continue;
+            };
+            if !connection.expanded {
+                continue;
+            }
+            for (table_ix, table) in tables.iter().enumerate() {
+                self.entries.push(Entry::Table {
+                    connection_ix,
+                    table_ix,
+                });
+                if connection.expanded_tables.contains(&table_ix) {
+                    self.entries
+                        .extend((0..table.columns.len()).map(|column_ix| Entry::Column {
+                            connection_ix,
+                            table_ix,
+                            column_ix,
+                        }));
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn worktree_root(&self, cx: &AppContext) -> Option<Arc<Path>> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        let worktree = project.visible_worktrees(cx).next()?;
+        Some(worktree.read(cx).abs_path())
+    }
+
+    fn toggle_connection(&mut self, connection_ix: usize, cx: &mut ViewContext<Self>) {
+        self.active_connection = Some(connection_ix);
+        let Some(connection) = self.connections.get_mut(connection_ix) else {
+            return;
+        };
+        match connection.status {
+            ConnectionStatus::Connected { .. } => {
+                connection.expanded = !connection.expanded;
+                self.update_entries(cx);
+            }
+            ConnectionStatus::Connecting => {}
+            ConnectionStatus::Disconnected | ConnectionStatus::Failed(_) => {
+                connection.expanded = true;
+                self.connect(connection_ix, cx);
+            }
+        }
+    }
+
+    fn connect(&mut self, connection_ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(connection) = self.connections.get_mut(connection_ix) else {
+            return;
+        };
+        connection.status = ConnectionStatus::Connecting;
+        cx.notify();
+
+        let settings = connection.settings.clone();
+        let read_credentials =
+            driver::credentials(&settings).map(|(url, _)| cx.read_credentials(&url));
+        cx.spawn(|this, mut cx| async move {
+            let password = match read_credentials {
+                Some(read_credentials) => match read_credentials.await.log_err().flatten() {
+                    Some((_, password)) => Some(String::from_utf8(password)?),
+                    None => {
+                        return this.update(&mut cx, |this, cx| {
+                            this.prompt_for_password(connection_ix, settings, cx)
+                        });
+                    }
+                },
+                None => None,
+            };
+            let root = this.update(&mut cx, |this, cx| this.worktree_root(cx))?;
+            this.update(&mut cx, |this, cx| {
+                this.finish_connecting(connection_ix, settings, root, password, false, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn prompt_for_password(
+        &mut self,
+        connection_ix: usize,
+        settings: ConnectionSettings,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(connection) = self.connections.get_mut(connection_ix) {
+            // Keep showing why the last attempt failed while the password is typed again.
+            if let ConnectionStatus::Connecting = connection.status {
+                connection.status = ConnectionStatus::Disconnected;
+                cx.notify();
+            }
+        }
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let panel = cx.view().downgrade();
+        let connection_name = SharedString::from(settings.name.clone());
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, move |cx| {
+                PasswordPrompt::new(
+                    connection_name,
+                    move |password, cx| {
+                        panel
+                            .update(cx, |panel, cx| {
+                                let root = panel.worktree_root(cx);
+                                panel.finish_connecting(
+                                    connection_ix,
+                                    settings,
+                                    root,
+                                    Some(password),
+                                    true,
+                                    cx,
+                                );
+                            })
+                            .ok();
+                    },
+                    cx,
+                )
+            });
+        });
+    }
+
+    /// Connects with the given password. A password typed into the prompt is only stored in
+    /// the keychain once the connection succeeds. When the server rejects the password, the
+    /// stored one is forgotten and the prompt is shown again.
+    fn finish_connecting(
+        &mut self,
+        connection_ix: usize,
+        settings: ConnectionSettings,
+        root: Option<Arc<Path>>,
+        password: Option<String>,
+        password_was_typed: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(connection) = self.connections.get_mut(connection_ix) {
+            connection.status = ConnectionStatus::Connecting;
+            cx.notify();
+        }
+        let task = cx.background_executor().spawn({
+            let settings = settings.clone();
+            let password = password.clone();
+            async move {
+                let connection = driver::connect(&settings, root.as_deref(), password.as_deref())?;
+                let tables = connection.schema()?;
+                anyhow::Ok((connection, tables))
+            }
+        });
+        cx.spawn(|this, mut cx| async move {
+            let result = task.await;
+            let mut password_rejected = false;
+            if let Some((url, username)) = driver::credentials(&settings) {
+                match (&result, password) {
+                    (Ok(_), Some(password)) if password_was_typed => {
+                        cx.update(|cx| cx.write_credentials(&url, &username, password.as_bytes()))?
+                            .await
+                            .log_err();
+                    }
+                    (Err(error), _) if driver::is_authentication_error(error) => {
+                        if !password_was_typed {
+                            cx.update(|cx| cx.delete_credentials(&url))?.await.log_err();
+                        }
+                        password_rejected = true;
+                    }
+                    _ => {}
+                }
+            }
+            this.update(&mut cx, |this, cx| {
+                let Some(entry) = this.connections.get_mut(connection_ix) else {
+                    return;
+                };
+                entry.status = match result {
+                    Ok((connection, tables)) => ConnectionStatus::Connected { connection, tables },
+                    Err(error) => ConnectionStatus::Failed(format!("{error:#}").into()),
+                };
+                entry.expanded_tables.clear();
+                this.update_entries(cx);
+                if password_rejected {
+                    this.prompt_for_password(connection_ix, settings, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn refresh_schema(&mut self, _: &RefreshSchema, cx: &mut ViewContext<Self>) {
+        if let Some(connection_ix) = self.active_connection {
+            self.connect(connection_ix, cx);
+        }
+    }
+
+    fn toggle_table(&mut self, connection_ix: usize, table_ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(connection) = self.connections.get_mut(connection_ix) {
+            if !connection.expanded_tables.remove(&table_ix) {
+                connection.expanded_tables.insert(table_ix);
+            }
+            self.active_connection = Some(connection_ix);
+            self.update_entries(cx);
+        }
+    }
+
+    fn preview_table(&mut self, connection_ix: usize, table_ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(connection) = self.connections.get(connection_ix) else {
+            return;
+        };
+        let ConnectionStatus::Connected { tables, .. } = &connection.status else {
+            return;
+        };
+        let Some(table) = tables.get(table_ix) else {
+            return;
+        };
+        let sql = driver::preview_query(&table.name, DatabaseSettings::get_global(cx).row_limit);
+        self.active_connection = Some(connection_ix);
+        cx.notify();
+        let Some((connection_name, connection)) = self.active_connection().log_err() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                run_query(workspace, connection_name, connection, sql, cx)
+            })
+            .ok();
+    }
+
+    /// Returns the name of and connection to the database selected in the panel.
+    pub fn active_connection(&self) -> Result<(SharedString, Arc<dyn DatabaseConnection>)> {
+        let connection = self
+            .active_connection
+            .and_then(|ix| self.connections.get(ix))
+            .ok_or_else(|| anyhow!("select a connection in the database panel first"))?;
+        match &connection.status {
+            ConnectionStatus::Connected {
+                connection: database,
+                ..
+            } => Ok((connection.settings.name.clone().into(), database.clone())),
+            _ => Err(anyhow!("{} is not connected", connection.settings.name)),
+        }
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        match self.entries[ix] {
+            Entry::Connection(connection_ix) => {
+                let connection = &self.connections[connection_ix];
+                let (status_label, status_color) = match &connection.status {
+                    ConnectionStatus::Disconnected => (None, Color::Muted),
+                    ConnectionStatus::Connecting => {
+                        (Some(SharedString::from("connecting…")), Color::Muted)
+                    }
+                    ConnectionStatus::Connected { tables, .. } => (
+                        Some(format!("{} tables", tables.len()).into()),
+                        Color::Muted,
+                    ),
+                    ConnectionStatus::Failed(error) => (Some(error.clone()), Color::Error),
+                };
+                let is_connected = matches!(connection.status, ConnectionStatus::Connected { .. });
+                ListItem::new(ix)
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .selected(self.active_connection == Some(connection_ix))
+                    .toggle(is_connected.then_some(connection.expanded))
+                    .start_slot(Icon::new(IconName::DatabaseZap).size(IconSize::Small))
+                    .child(Label::new(connection.settings.name.clone()))
+                    .end_slot::<Label>(status_label.map(|status| {
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(status_color)
+                            .single_line()
+                    }))
+                    .on_click(
+                        cx.listener(move |this, _, cx| this.toggle_connection(connection_ix, cx)),
+                    )
+                    .into_any_element()
+            }
+            Entry::Table {
+                connection_ix,
+                table_ix,
+            } => {
+                let Some(table) = self.table(connection_ix, table_ix) else {
+                    return div().into_any_element();
+                };
+                let expanded = self.connections[connection_ix]
+                    .expanded_tables
+                    .contains(&table_ix);
+                ListItem::new(ix)
+                    .indent_level(1)
+                    .indent_step_size(px(12.))
+                    .toggle(expanded)
+                    .on_toggle(cx.listener(move |this, _, cx| {
+                        this.toggle_table(connection_ix, table_ix, cx)
+                    }))
+                    .child(Label::new(table.name.clone()))
+                    .end_hover_slot(
+                        IconButton::new(("preview-table", ix), IconName::Play)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(|cx| Tooltip::text("Preview Rows", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.preview_table(connection_ix, table_ix, cx)
+                            })),
+                    )
+                    .on_click(cx.listener(move |this, _, cx| {
+                        this.toggle_table(connection_ix, table_ix, cx)
+                    }))
+                    .into_any_element()
+            }
+            Entry::Column {
+                connection_ix,
+                table_ix,
+                column_ix,
+            } => {
+                let Some(column) = self
+                    .table(connection_ix, table_ix)
+                    .and_then(|table| table.columns.get(column_ix))
+                else {
+                    return div().into_any_element();
+                };
+                ListItem::new(ix)
+                    .indent_level(3)
+                    .indent_step_size(px(12.))
+                    .child(Label::new(column.name.clone()).size(LabelSize::Small))
+                    .end_slot(
+                        Label::new(column.data_type.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn table(&self, connection_ix: usize, table_ix: usize) -> Option<&TableSchema> {
+        match &self.connections.get(connection_ix)?.status {
+            ConnectionStatus::Connected { tables, .. } => tables.get(table_ix),
+            _ => None,
+        }
+    }
+}
+
+impl Render for DatabasePanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DatabasePanel")
+            .on_action(cx.listener(Self::refresh_schema))
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .map(|this| {
+                if self.connections.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new(
+                                "Add connections to `database_explorer.connections` in your settings to browse them here.",
+                            )
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.view().clone(),
+                            "database-entries",
+                            self.entries.len(),
+                            |this, range, cx| {
+                                range.map(|ix| this.render_entry(ix, cx)).collect::<Vec<_>>()
+                            },
+                        )
+                        .size_full(),
+                    )
+                }
+            })
+    }
+}
+
+impl FocusableView for DatabasePanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for DatabasePanel {}
+
+impl Panel for DatabasePanel {
+    fn persistent_name() -> &'static str {
+        "DatabasePanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        DatabaseSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<DatabaseSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| DatabaseSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        DatabaseSettings::get_global(cx)
+            .button
+            .then_some(IconName::DatabaseZap)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Database Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -0,0 +1,99 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use std::path::PathBuf;
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DatabaseSettings {
+    pub connections: Vec<ConnectionSettings>,
+    pub row_limit: usize,
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+/// A database that can be browsed and queried from the database panel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConnectionSettings {
+    /// The name shown for this connection in the database panel.
+    pub name: String,
+    #[serde(flatten)]
+    pub driver: DriverSettings,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "driver", rename_all = "snake_case")]
+pub enum DriverSettings {
+    Sqlite {
+        /// Path to the database file, relative to the first worktree root.
+        path: PathBuf,
+    },
+    Postgres {
+        host: String,
+        #[serde(default = "default_postgres_port")]
+        port: u16,
+        user: String,
+        database: String,
+        /// Whether to encrypt the connection with TLS. Server certificates are verified
+        /// against the system's trusted roots.
+        ///
+        /// Default: `require`, or `prefer` for servers on this machine
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ssl_mode: Option<SslMode>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SslMode {
+    /// Never use TLS.
+    Disable,
+    /// Use TLS when the server supports it.
+    Prefer,
+    /// Always use TLS, failing to connect to servers that don't support it.
+    Require,
+}
+
+fn default_postgres_port() -> u16 {
+    5432
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct DatabaseSettingsContent {
+    /// The databases to show in the database panel. Passwords are never read from
+    /// settings; you will be prompted for them and they will be kept in the system keychain.
+    ///
+    /// Default: []
+    pub connections: Option<Vec<ConnectionSettings>>,
+    /// The maximum number of rows to fetch when previewing a table.
+    ///
+    /// Default: 500
+    pub row_limit: Option<usize>,
+    /// Whether to show the database panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the database panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the database panel in pixels.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
+impl Settings for DatabaseSettings {
+    const KEY: Option<&'static str> = Some("database_explorer");
+
+    type FileContent = DatabaseSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
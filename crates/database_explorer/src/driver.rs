@@ -0,0 +1,150 @@
+use crate::{
+    database_settings::{ConnectionSettings, DriverSettings},
+    postgres::{self, PostgresConnection},
+    sqlite::SqliteConnection,
+};
+use anyhow::{anyhow, Result};
+use std::{path::Path, sync::Arc, time::Duration};
+
+/// A live connection to a database. Calls block, so they should be made from a
+/// background thread.
+pub trait DatabaseConnection: Send + Sync {
+    fn schema(&self) -> Result<Vec<TableSchema>>;
+    fn execute(&self, sql: &str) -> Result<QueryResult>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: String,
+}
+
+/// The rows returned by the last statement of a query, with `None` standing in for `NULL`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+    pub rows_affected: Option<u64>,
+    pub elapsed: Duration,
+}
+
+/// Returns the keychain URL and username under which the password for a connection is
+/// stored, or `None` if the driver does not use passwords.
+pub fn credentials(settings: &ConnectionSettings) -> Option<(String, String)> {
+    match &settings.driver {
+        DriverSettings::Sqlite { .. } => None,
+        DriverSettings::Postgres {
+            host,
+            port,
+            user,
+            database,
+            ..
+        } => Some((
+            format!("postgres://{user}@{host}:{port}/{database}"),
+            user.clone(),
+        )),
+    }
+}
+
+/// Whether connecting failed because the server rejected the user or password.
+pub fn is_authentication_error(error: &anyhow::Error) -> bool {
+    postgres::is_authentication_error(error)
+}
+
+/// Opens a connection, resolving relative SQLite paths against `root`.
+pub fn connect(
+    settings: &ConnectionSettings,
+    root: Option<&Path>,
+    password: Option<&str>,
+) -> Result<Arc<dyn DatabaseConnection>> {
+    match &settings.driver {
+        DriverSettings::Sqlite { path } => {
+            let path = match root {
+                Some(root) if path.is_relative() => root.join(path),
+                _ => path.clone(),
+            };
+            Ok(Arc::new(SqliteConnection::open(&path)?))
+        }
+        DriverSettings::Postgres {
+            host,
+            port,
+            user,
+            database,
+            ssl_mode,
+        } => {
+            let password = password.ok_or_else(|| anyhow!("no password provided"))?;
+            let ssl_mode = ssl_mode.unwrap_or_else(|| postgres::default_ssl_mode(host));
+            Ok(Arc::new(PostgresConnection::connect(
+                host, *port, user, database, password, ssl_mode,
+            )?))
+        }
+    }
+}
+
+/// Builds a query that previews the first `limit` rows of a table. Schema-qualified
+/// names such as `audit.events` are quoted part by part.
+pub fn preview_query(table: &str, limit: usize) -> String {
+    let name = table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".");
+    format!("SELECT * FROM {name} LIMIT {limit}")
+}
+
+/// Groups `(table, column, type)` rows, which must be sorted by table, into table schemas.
+pub(crate) fn group_columns(
+    rows: impl IntoIterator<Item = (String, String, String)>,
+) -> Vec<TableSchema> {
+    let mut tables = Vec::<TableSchema>::new();
+    for (table, name, data_type) in rows {
+        let column = ColumnSchema { name, data_type };
+        match tables.last_mut() {
+            Some(last) if last.name == table => last.columns.push(column),
+            _ => tables.push(TableSchema {
+                name: table,
+                columns: vec![column],
+            }),
+        }
+    }
+    tables
+}
+
+/// Quotes an identifier so it can be safely interpolated into a query.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_columns() {
+        let tables = group_columns([
+            ("a".into(), "id".into(), "INTEGER".into()),
+            ("a".into(), "name".into(), "TEXT".into()),
+            ("b".into(), "id".into(), "INTEGER".into()),
+        ]);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "a");
+        assert_eq!(tables[0].columns.len(), 2);
+        assert_eq!(tables[1].columns[0].name, "id");
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("users"), "\"users\"");
+        assert_eq!(quote_identifier("we\"ird"), "\"we\"\"ird\"");
+        assert_eq!(
+            preview_query("audit.events", 10),
+            "SELECT * FROM \"audit\".\"events\" LIMIT 10"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use editor::{Editor, EditorEvent};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, SharedString,
+    Subscription, View, ViewContext,
+};
+use ui::prelude::*;
+use workspace::ModalView;
+
+type OnConfirm = Box<dyn FnOnce(String, &mut WindowContext)>;
+
+/// Asks for the password of a connection that has none stored in the keychain.
+pub struct PasswordPrompt {
+    connection_name: SharedString,
+    password_editor: View<Editor>,
+    on_confirm: Option<OnConfirm>,
+    _subscription: Subscription,
+}
+
+impl PasswordPrompt {
+    pub fn new(
+        connection_name: SharedString,
+        on_confirm: impl FnOnce(String, &mut WindowContext) + 'static,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let password_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_masked(true, cx);
+            editor.set_placeholder_text("Password", cx);
+            editor
+        });
+        let subscription = cx.subscribe(&password_editor, |_, _, event, cx| {
+            if let EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        });
+        Self {
+            connection_name,
+            password_editor,
+            on_confirm: Some(Box::new(on_confirm)),
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let password = self.password_editor.read(cx).text(cx);
+        if let Some(on_confirm) = self.on_confirm.take() {
+            on_confirm(password, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl ModalView for PasswordPrompt {}
+
+impl EventEmitter<DismissEvent> for PasswordPrompt {}
+
+impl FocusableView for PasswordPrompt {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.password_editor.focus_handle(cx)
+    }
+}
+
+impl Render for PasswordPrompt {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .elevation_2(cx)
+            .key_context("PasswordPrompt")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .w_96()
+            .p_2()
+            .gap_2()
+            .child(Label::new(format!(
+                "Enter the password for {}",
+                self.connection_name
+            )))
+            .child(
+                div()
+                    .px_1()
+                    .py_0p5()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(self.password_editor.clone()),
+            )
+            .child(
+                Label::new("The password will be stored in the system keychain once connected.")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+}
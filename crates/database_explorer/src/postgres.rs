@@ -0,0 +1,152 @@
+use crate::{
+    database_settings::SslMode,
+    driver::{group_columns, DatabaseConnection, QueryResult, TableSchema},
+};
+use anyhow::Result;
+use native_tls::TlsConnector;
+use parking_lot::Mutex;
+use postgres::{error::SqlState, Client, NoTls, SimpleQueryMessage};
+use postgres_native_tls::MakeTlsConnector;
+use std::time::Instant;
+
+pub struct PostgresConnection {
+    client: Mutex<Client>,
+}
+
+impl PostgresConnection {
+    pub fn connect(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: &str,
+        ssl_mode: SslMode,
+    ) -> Result<Self> {
+        let mut config = postgres::Config::new();
+        config
+            .host(host)
+            .port(port)
+            .user(user)
+            .dbname(database)
+            .password(password)
+            .application_name("Zed");
+        config.ssl_mode(match ssl_mode {
+            SslMode::Disable => postgres::config::SslMode::Disable,
+            SslMode::Prefer => postgres::config::SslMode::Prefer,
+            SslMode::Require => postgres::config::SslMode::Require,
+        });
+        let client = match ssl_mode {
+            SslMode::Disable => config.connect(NoTls)?,
+            SslMode::Prefer | SslMode::Require => {
+                config.connect(MakeTlsConnector::new(TlsConnector::new()?))?
+            }
+        };
+        Ok(Self {
+            client: Mutex::new(client),
+        })
+    }
+}
+
+/// Connections to servers on this machine, including through Unix sockets, prefer TLS but don't
+/// require it, as local servers are rarely set up with certificates.
+pub fn default_ssl_mode(host: &str) -> SslMode {
+    if host == "localhost"
+        || host.starts_with('/')
+        || host
+            .parse()
+            .map_or(false, |ip: std::net::IpAddr| ip.is_loopback())
+    {
+        SslMode::Prefer
+    } else {
+        SslMode::Require
+    }
+}
+
+/// Whether the server refused the connection because of the user or password.
+pub fn is_authentication_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<postgres::Error>()
+        .and_then(|error| error.code())
+        .map_or(false, |code| {
+            *code == SqlState::INVALID_PASSWORD
+                || *code == SqlState::INVALID_AUTHORIZATION_SPECIFICATION
+        })
+}
+
+impl DatabaseConnection for PostgresConnection {
+    fn schema(&self) -> Result<Vec<TableSchema>> {
+        let rows = self.client.lock().query(
+            "SELECT
+                CASE WHEN table_schema = 'public' THEN table_name::text
+                     ELSE table_schema || '.' || table_name END,
+                column_name::text,
+                data_type::text
+             FROM information_schema.columns
+             WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+             ORDER BY table_schema, table_name, ordinal_position",
+            &[],
+        )?;
+        Ok(group_columns(
+            rows.iter().map(|row| (row.get(0), row.get(1), row.get(2))),
+        ))
+    }
+
+    fn execute(&self, sql: &str) -> Result<QueryResult> {
+        let start = Instant::now();
+        let messages = self.client.lock().simple_query(sql)?;
+
+        // Only the results of the last statement are shown, so start over whenever a
+        // statement completes and another one follows.
+        let mut result = QueryResult::default();
+        let mut statement_complete = false;
+        for message in messages {
+            match message {
+                SimpleQueryMessage::Row(row) => {
+                    if statement_complete {
+                        result = QueryResult::default();
+                        statement_complete = false;
+                    }
+                    if result.columns.is_empty() {
+                        result.columns = row
+                            .columns()
+                            .iter()
+                            .map(|column| column.name().to_string())
+                            .collect();
+                    }
+                    result.rows.push(
+                        (0..row.len())
+                            .map(|ix| row.get(ix).map(str::to_string))
+                            .collect(),
+                    );
+                }
+                SimpleQueryMessage::CommandComplete(count) => {
+                    if statement_complete {
+                        result = QueryResult::default();
+                    }
+                    if result.columns.is_empty() {
+                        result.rows_affected = Some(count);
+                    }
+                    statement_complete = true;
+                }
+                _ => {}
+            }
+        }
+        result.elapsed = start.elapsed();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_ssl_mode() {
+        assert_eq!(default_ssl_mode("localhost"), SslMode::Prefer);
+        assert_eq!(default_ssl_mode("127.0.0.1"), SslMode::Prefer);
+        assert_eq!(default_ssl_mode("::1"), SslMode::Prefer);
+        assert_eq!(default_ssl_mode("/var/run/postgresql"), SslMode::Prefer);
+        assert_eq!(default_ssl_mode("db.example.com"), SslMode::Require);
+        assert_eq!(default_ssl_mode("10.0.0.5"), SslMode::Require);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::driver::{DatabaseConnection, QueryResult};
+use gpui::{
+    uniform_list, AppContext, EventEmitter, FocusHandle, FocusableView, Render, SharedString,
+    UniformListScrollHandle, ViewContext,
+};
+use std::sync::Arc;
+use ui::prelude::*;
+use workspace::{
+    item::{Item, ItemEvent},
+    SplitDirection, Workspace,
+};
+
+/// How many rows are inspected when sizing columns to their contents.
+const COLUMN_WIDTH_SAMPLE_ROWS: usize = 200;
+const MIN_COLUMN_CHARS: usize = 4;
+const MAX_COLUMN_CHARS: usize = 48;
+
+/// Runs `sql` on a background thread, showing the rows it returns in the workspace's query
+/// results view, which is opened in a split if needed.
+pub(crate) fn run_query(
+    workspace: &mut Workspace,
+    connection_name: SharedString,
+    connection: Arc<dyn DatabaseConnection>,
+    sql: String,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let results_view = if let Some(results_view) = workspace.item_of_type::<QueryResultsView>(cx) {
+        workspace.activate_item(&results_view, false, false, cx);
+        results_view
+    } else {
+        let results_view = cx.new_view(QueryResultsView::new);
+        workspace.split_item(SplitDirection::Right, Box::new(results_view.clone()), cx);
+        results_view
+    };
+    results_view.update(cx, |view, cx| {
+        view.set_running(connection_name, sql.clone(), cx)
+    });
+
+    let task = cx
+        .background_executor()
+        .spawn(async move { connection.execute(&sql) });
+    cx.spawn(|_, mut cx| async move {
+        let result = task.await;
+        results_view.update(&mut cx, |view, cx| match result {
+            Ok(result) => view.set_result(result, cx),
+            Err(error) => view.set_error(error, cx),
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+enum QueryState {
+    Running,
+    Finished(QueryResult),
+    Failed(SharedString),
+}
+
+/// Shows the rows returned by a query in a virtualized table.
+pub struct QueryResultsView {
+    connection_name: SharedString,
+    sql: SharedString,
+    state: QueryState,
+    column_widths: Vec<Rems>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl QueryResultsView {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            connection_name: SharedString::default(),
+            sql: SharedString::default(),
+            state: QueryState::Running,
+            column_widths: Vec::new(),
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn set_running(
+        &mut self,
+        connection_name: SharedString,
+        sql: String,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.connection_name = connection_name;
+        self.sql = sql.trim().to_string().into();
+        self.state = QueryState::Running;
+        self.column_widths.clear();
+        cx.emit(ItemEvent::UpdateTab);
+        cx.notify();
+    }
+
+    pub fn set_result(&mut self, result: QueryResult, cx: &mut ViewContext<Self>) {
+        self.column_widths = column_widths(&result);
+        self.state = QueryState::Finished(result);
+        self.scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+
+    pub fn set_error(&mut self, error: anyhow::Error, cx: &mut ViewContext<Self>) {
+        self.state = QueryState::Failed(format!("{error:#}").into());
+        cx.notify();
+    }
+
+    fn render_status(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let status = match &self.state {
+            QueryState::Running => Label::new("Running query…").color(Color::Muted),
+            QueryState::Failed(error) => Label::new(error.clone()).color(Color::Error),
+            QueryState::Finished(result) => {
+                let elapsed = result.elapsed.as_millis();
+                let summary = if !result.columns.is_empty() {
+                    let rows = result.rows.len();
+                    let noun = if rows == 1 { "row" } else { "rows" };
+                    format!("{rows} {noun} in {elapsed} ms")
+                } else if let Some(rows_affected) = result.rows_affected {
+                    format!("{rows_affected} rows affected in {elapsed} ms")
+                } else {
+                    format!("Statement executed in {elapsed} ms")
+                };
+                Label::new(summary).color(Color::Muted)
+            }
+        };
+        v_flex()
+            .p_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(self.sql.clone())
+                    .size(LabelSize::Small)
+                    .single_line(),
+            )
+            .child(status.size(LabelSize::Small))
+    }
+
+    fn render_cell(
+        &self,
+        column_ix: usize,
+        text: Option<&str>,
+        cx: &ViewContext<Self>,
+    ) -> impl IntoElement {
+        let label = match text {
+            Some(text) => Label::new(text.replace('\n', " ")),
+            None => Label::new("NULL").color(Color::Muted).italic(true),
+        };
+        div()
+            .flex_none()
+            .w(self.column_widths[column_ix])
+            .px_2()
+            .overflow_hidden()
+            .border_r_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(label.size(LabelSize::Small).single_line())
+    }
+
+    fn render_rows(
+        &self,
+        range: std::ops::Range<usize>,
+        cx: &ViewContext<Self>,
+    ) -> Vec<gpui::AnyElement> {
+        let QueryState::Finished(result) = &self.state else {
+            return Vec::new();
+        };
+        range
+            .map(|row_ix| {
+                let row = &result.rows[row_ix];
+                h_flex()
+                    .id(row_ix)
+                    .when(row_ix % 2 == 1, |this| {
+                        this.bg(cx.theme().colors().surface_background)
+                    })
+                    .children(row.iter().enumerate().map(|(column_ix, value)| {
+                        self.render_cell(column_ix, value.as_deref(), cx)
+                    }))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_table(&self, result: &QueryResult, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let table_width = rems(self.column_widths.iter().map(|width| width.0).sum());
+        div()
+            .id("query-results")
+            .size_full()
+            .overflow_x_scroll()
+            .child(
+                v_flex()
+                    .h_full()
+                    .w(table_width)
+                    .child(
+                        h_flex()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(cx.theme().colors().title_bar_background)
+                            .children(
+                                result
+                                    .columns
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(ix, column)| self.render_cell(ix, Some(column), cx)),
+                            ),
+                    )
+                    .child(
+                        uniform_list(
+                            cx.view().clone(),
+                            "query-result-rows",
+                            result.rows.len(),
+                            |this, range, cx| this.render_rows(range, cx),
+                        )
+                        .flex_grow()
+                        .track_scroll(self.scroll_handle.clone()),
+                    ),
+            )
+    }
+}
+
+fn column_widths(result: &QueryResult) -> Vec<Rems> {
+    result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(ix, column)| {
+            let widest_value = result
+                .rows
+                .iter()
+                .take(COLUMN_WIDTH_SAMPLE_ROWS)
+                .filter_map(|row| row.get(ix)?.as_ref())
+                .map(|value| value.chars().count())
+                .max()
+                .unwrap_or(0);
+            let chars = widest_value
+                .max(column.chars().count())
+                .clamp(MIN_COLUMN_CHARS, MAX_COLUMN_CHARS);
+            rems(chars as f32 * 0.5 + 1.)
+        })
+        .collect()
+}
+
+impl Render for QueryResultsView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("QueryResultsView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_status(cx))
+            .map(|this| match &self.state {
+                QueryState::Finished(result) if !result.columns.is_empty() => {
+                    this.child(self.render_table(result, cx))
+                }
+                _ => this,
+            })
+    }
+}
+
+impl EventEmitter<ItemEvent> for QueryResultsView {}
+
+impl FocusableView for QueryResultsView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for QueryResultsView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _cx: &WindowContext) -> Option<SharedString> {
+        if self.connection_name.is_empty() {
+            Some("Query Results".into())
+        } else {
+            Some(format!("Results: {}", self.connection_name).into())
+        }
+    }
+
+    fn tab_icon(&self, _cx: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::DatabaseZap))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
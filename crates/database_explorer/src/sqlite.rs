@@ -0,0 +1,127 @@
+use crate::driver::{group_columns, DatabaseConnection, QueryResult, TableSchema};
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use sqlez::{
+    connection::Connection,
+    statement::{SqlType, Statement},
+};
+use std::{path::Path, time::Instant};
+
+pub struct SqliteConnection {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteConnection {
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Err(anyhow!("database file {path:?} does not exist"));
+        }
+        let path = path
+            .to_str()
+            .ok_or_else(|| anyhow!("database path {path:?} is not valid UTF-8"))?;
+        Ok(Self {
+            connection: Mutex::new(Connection::try_open_file(path)?),
+        })
+    }
+
+    #[cfg(test)]
+    fn in_memory(name: &str) -> Self {
+        Self {
+            connection: Mutex::new(Connection::open_memory(Some(name))),
+        }
+    }
+}
+
+impl DatabaseConnection for SqliteConnection {
+    fn schema(&self) -> Result<Vec<TableSchema>> {
+        let connection = self.connection.lock();
+        let mut statement = Statement::prepare(
+            &connection,
+            "SELECT m.name, p.name, p.type
+             FROM sqlite_master m JOIN pragma_table_info(m.name) p
+             WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite_%'
+             ORDER BY m.name, p.cid",
+        )?;
+        let rows = statement.map(|row| {
+            Ok((
+                row.column_text(0)?.to_string(),
+                row.column_text(1)?.to_string(),
+                row.column_text(2)?.to_string(),
+            ))
+        })?;
+        Ok(group_columns(rows))
+    }
+
+    fn execute(&self, sql: &str) -> Result<QueryResult> {
+        let start = Instant::now();
+        let connection = self.connection.lock();
+        let mut statement = Statement::prepare(&connection, sql)?;
+        let mut columns = Vec::new();
+        let rows = statement.map(|row| {
+            let column_count = row.column_count();
+            if columns.len() != column_count as usize {
+                columns = (0..column_count)
+                    .map(|ix| Ok(row.column_name(ix)?.to_string()))
+                    .collect::<Result<_>>()?;
+            }
+            (0..column_count)
+                .map(|ix| {
+                    Ok(match row.column_type(ix)? {
+                        SqlType::Null => None,
+                        SqlType::Integer => Some(row.column_int64(ix)?.to_string()),
+                        SqlType::Float => Some(row.column_double(ix)?.to_string()),
+                        SqlType::Text => Some(row.column_text(ix)?.to_string()),
+                        SqlType::Blob => Some(format!("<{} bytes>", row.column_blob(ix)?.len())),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        Ok(QueryResult {
+            columns,
+            rows,
+            rows_affected: None,
+            elapsed: start.elapsed(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_schema_and_query() {
+        let connection = SqliteConnection::in_memory("test_sqlite_schema_and_query");
+        connection
+            .execute(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL);
+                 INSERT INTO users (name, score) VALUES ('ada', 1.5), (NULL, 2.0);",
+            )
+            .unwrap();
+
+        let schema = connection.schema().unwrap();
+        assert_eq!(schema.len(), 1);
+        assert_eq!(schema[0].name, "users");
+        let columns = schema[0]
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.data_type.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            [("id", "INTEGER"), ("name", "TEXT"), ("score", "REAL")]
+        );
+
+        let result = connection
+            .execute("SELECT id, name, score FROM users ORDER BY id")
+            .unwrap();
+        assert_eq!(result.columns, ["id", "name", "score"]);
+        assert_eq!(
+            result.rows,
+            vec![
+                vec![Some("1".into()), Some("ada".into()), Some("1.5".into())],
+                vec![Some("2".into()), None, Some("2".into())],
+            ]
+        );
+    }
+}
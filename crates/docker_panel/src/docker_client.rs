@@ -0,0 +1,213 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_SOCKET_PATH: &str = "/var/run/docker.sock";
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Container {
+    pub id: String,
+    #[serde(default)]
+    pub names: Vec<String>,
+    pub image: String,
+    /// The lifecycle state, such as `running` or `exited`.
+    pub state: String,
+    /// A human readable status, such as `Up 2 hours`.
+    pub status: String,
+}
+
+impl Container {
+    pub fn name(&self) -> &str {
+        self.names
+            .first()
+            .map(|name| name.trim_start_matches('/'))
+            .unwrap_or_else(|| self.short_id())
+    }
+
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state == "running"
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Image {
+    pub id: String,
+    #[serde(default)]
+    pub repo_tags: Option<Vec<String>>,
+    pub size: u64,
+}
+
+impl Image {
+    pub fn name(&self) -> &str {
+        self.repo_tags
+            .as_ref()
+            .and_then(|tags| tags.first())
+            .map(String::as_str)
+            .unwrap_or("<none>")
+    }
+}
+
+/// A minimal client for the Docker Engine API, spoken over its Unix socket.
+#[derive(Clone, Debug)]
+pub struct DockerClient {
+    socket_path: PathBuf,
+}
+
+impl DockerClient {
+    /// Connects to `socket_path` if given, falling back to a `unix://` `DOCKER_HOST` and
+    /// then to the default socket location.
+    pub fn new(socket_path: Option<&Path>) -> Self {
+        let socket_path = socket_path.map(Path::to_path_buf).unwrap_or_else(|| {
+            std::env::var("DOCKER_HOST")
+                .ok()
+                .and_then(|host| host.strip_prefix("unix://").map(PathBuf::from))
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_SOCKET_PATH))
+        });
+        Self { socket_path }
+    }
+
+    pub async fn containers(&self) -> Result<Vec<Container>> {
+        let body = self.request("GET", "/containers/json?all=1").await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    pub async fn images(&self) -> Result<Vec<Image>> {
+        let body = self.request("GET", "/images/json").await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    pub async fn start(&self, container_id: &str) -> Result<()> {
+        self.request("POST", &format!("/containers/{container_id}/start"))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn stop(&self, container_id: &str) -> Result<()> {
+        self.request("POST", &format!("/containers/{container_id}/stop"))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn restart(&self, container_id: &str) -> Result<()> {
+        self.request("POST", &format!("/containers/{container_id}/restart"))
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    async fn request(&self, method: &str, path: &str) -> Result<Vec<u8>> {
+        use anyhow::Context as _;
+        use futures::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let mut stream = smol::net::unix::UnixStream::connect(&self.socket_path)
+            .await
+            .with_context(|| format!("failed to connect to docker at {:?}", self.socket_path))?;
+        // HTTP/1.0 makes the daemon close the connection after responding, so the
+        // response can simply be read to the end.
+        let request =
+            format!("{method} {path} HTTP/1.0\r\nHost: docker\r\nContent-Length: 0\r\n\r\n");
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        parse_response(&response)
+    }
+
+    #[cfg(not(unix))]
+    async fn request(&self, _method: &str, _path: &str) -> Result<Vec<u8>> {
+        Err(anyhow!(
+            "connecting to docker is only supported on Unix platforms"
+        ))
+    }
+}
+
+/// Returns the body of a raw HTTP response, or an error carrying the daemon's message if
+/// the request did not succeed.
+fn parse_response(response: &[u8]) -> Result<Vec<u8>> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("incomplete response from docker"))?;
+    let head = std::str::from_utf8(&response[..header_end])?;
+    let mut body = response[header_end + 4..].to_vec();
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|status_line| status_line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("invalid status line in docker response"))?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').map_or(false, |(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+    if chunked {
+        body = decode_chunked(&body)?;
+    }
+
+    if (200..300).contains(&status) || status == 304 {
+        Ok(body)
+    } else {
+        #[derive(Deserialize)]
+        struct ErrorMessage {
+            message: String,
+        }
+        let message = serde_json::from_slice::<ErrorMessage>(&body)
+            .map(|error| error.message)
+            .unwrap_or_else(|_| String::from_utf8_lossy(&body).trim().to_string());
+        Err(anyhow!("docker returned {status}: {message}"))
+    }
+}
+
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or_else(|| anyhow!("invalid chunk in docker response"))?;
+        let size = std::str::from_utf8(&body[..line_end])?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk_start = line_end + 2;
+        let chunk = body
+            .get(chunk_start..chunk_start + size)
+            .ok_or_else(|| anyhow!("truncated chunk in docker response"))?;
+        decoded.extend_from_slice(chunk);
+        body = body.get(chunk_start + size + 2..).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let response = b"HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n[{\"Id\":\"0123456789abcdef\",\"Names\":[\"/web\"],\"Image\":\"nginx\",\"State\":\"running\",\"Status\":\"Up 2 hours\"}]";
+        let body = parse_response(response).unwrap();
+        let containers: Vec<Container> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].name(), "web");
+        assert_eq!(containers[0].short_id(), "0123456789ab");
+        assert!(containers[0].is_running());
+
+        let response =
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n[{}]\r\n3\r\n   \r\n0\r\n\r\n";
+        assert_eq!(parse_response(response).unwrap(), b"[{}]   ");
+
+        let response = b"HTTP/1.0 404 Not Found\r\n\r\n{\"message\":\"No such container: abc\"}";
+        assert_eq!(
+            parse_response(response).unwrap_err().to_string(),
+            "docker returned 404: No such container: abc"
+        );
+    }
+}
@@ -0,0 +1,484 @@
+mod docker_client;
+mod docker_panel_settings;
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use docker_client::{Container, DockerClient, Image};
+use fs::Fs;
+use gpui::{
+    actions, uniform_list, AnyElement, AppContext, AsyncWindowContext, EventEmitter, FocusHandle,
+    FocusableView, Render, Task, View, ViewContext, WeakView,
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{future::Future, sync::Arc};
+use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
+use ui::{prelude::*, Indicator, ListHeader, ListItem, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+pub use docker_panel_settings::DockerPanelSettings;
+
+const DOCKER_PANEL_KEY: &str = "DockerPanel";
+
+actions!(docker_panel, [ToggleFocus, RefreshContainers]);
+
+pub fn init(cx: &mut AppContext) {
+    DockerPanelSettings::register(cx);
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<DockerPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedDockerPanel {
+    width: Option<Pixels>,
+}
+
+#[derive(Clone, Copy)]
+enum Entry {
+    ContainersHeader,
+    Container(usize),
+    ImagesHeader,
+    Image(usize),
+}
+
+/// Lists the containers and images known to the local Docker daemon.
+pub struct DockerPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    containers: Vec<Container>,
+    images: Vec<Image>,
+    entries: Vec<Entry>,
+    error: Option<SharedString>,
+    refresh_task: Task<()>,
+    pending_serialization: Task<Option<()>>,
+}
+
+impl DockerPanel {
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        cx.new_view(|cx| {
+            let mut this = Self {
+                fs,
+                workspace,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                containers: Vec::new(),
+                images: Vec::new(),
+                entries: Vec::new(),
+                error: None,
+                refresh_task: Task::ready(()),
+                pending_serialization: Task::ready(None),
+            };
+            this.refresh(cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(DOCKER_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedDockerPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        DOCKER_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedDockerPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn client(cx: &AppContext) -> DockerClient {
+        DockerClient::new(DockerPanelSettings::get_global(cx).socket_path.as_deref())
+    }
+
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        let client = Self::client(cx);
+        self.refresh_task = cx.spawn(|this, mut cx| async move {
+            let result = async {
+                let containers = client.containers().await?;
+                let images = client.images().await?;
+                anyhow::Ok((containers, images))
+            }
+            .await;
+            this.update(&mut cx, |this, cx| {
+                match result {
+                    Ok((containers, images)) => {
+                        this.containers = containers;
+                        this.images = images;
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.containers.clear();
+                        this.images.clear();
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                this.update_entries(cx);
+            })
+            .ok();
+        });
+    }
+
+    fn refresh_containers(&mut self, _: &RefreshContainers, cx: &mut ViewContext<Self>) {
+        self.refresh(cx);
+    }
+
+    fn update_entries(&mut self, cx: &mut ViewContext<Self>) {
+        self.entries.clear();
+        self.entries.push(Entry::ContainersHeader);
+        self.entries
+            .extend((0..self.containers.len()).map(Entry::Container));
+        self.entries.push(Entry::ImagesHeader);
+        self.entries
+            .extend((0..self.images.len()).map(Entry::Image));
+        cx.notify();
+    }
+
+    /// Runs a container operation, then refreshes the list so it reflects the new state.
+    fn run_operation<F, Fut>(&mut self, operation: F, cx: &mut ViewContext<Self>)
+    where
+        F: FnOnce(DockerClient) -> Fut,
+        Fut: 'static + Future<Output = Result<()>>,
+    {
+        let operation = operation(Self::client(cx));
+        cx.spawn(|this, mut cx| async move {
+            let result = operation.await;
+            this.update(&mut cx, |this, cx| {
+                if let Err(error) = result {
+                    this.error = Some(format!("{error:#}").into());
+                }
+                this.refresh(cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn toggle_container(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(container) = self.containers.get(ix) else {
+            return;
+        };
+        let id = container.id.clone();
+        if container.is_running() {
+            self.run_operation(|client| async move { client.stop(&id).await }, cx);
+        } else {
+            self.run_operation(|client| async move { client.start(&id).await }, cx);
+        }
+    }
+
+    fn restart_container(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(container) = self.containers.get(ix) {
+            let id = container.id.clone();
+            self.run_operation(|client| async move { client.restart(&id).await }, cx);
+        }
+    }
+
+    /// Follows the container's logs in a terminal tab.
+    fn show_logs(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(container) = self.containers.get(ix) {
+            let label = format!("Logs: {}", container.name());
+            let args = vec![
+                "logs".into(),
+                "--follow".into(),
+                "--tail".into(),
+                "500".into(),
+                container.id.clone(),
+            ];
+            self.spawn_in_terminal(label, args, cx);
+        }
+    }
+
+    /// Opens an interactive shell inside the container in a terminal tab.
+    fn exec_into(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(container) = self.containers.get(ix) {
+            let label = format!("Shell: {}", container.name());
+            let args = vec![
+                "exec".into(),
+                "-it".into(),
+                container.id.clone(),
+                DockerPanelSettings::get_global(cx).exec_shell.clone(),
+            ];
+            self.spawn_in_terminal(label, args, cx);
+        }
+    }
+
+    fn spawn_in_terminal(&self, label: String, args: Vec<String>, cx: &mut ViewContext<Self>) {
+        let command_label = format!("docker {}", args.join(" "));
+        let spawn_in_terminal = SpawnInTerminal {
+            id: TaskId(format!("docker-{command_label}")),
+            full_label: label.clone(),
+            label,
+            command: "docker".into(),
+            args,
+            command_label,
+            cwd: None,
+            env: Default::default(),
+            use_new_terminal: true,
+            allow_concurrent_runs: true,
+            reveal: RevealStrategy::Always,
+            hide: HideStrategy::Never,
+            shell: Default::default(),
+        };
+        self.workspace
+            .update(cx, |_, cx| {
+                cx.emit(workspace::Event::SpawnTask(Box::new(spawn_in_terminal)));
+            })
+            .ok();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        match self.entries[ix] {
+            Entry::ContainersHeader => ListHeader::new("Containers")
+                .end_slot(
+                    IconButton::new("refresh-containers", IconName::RotateCw)
+                        .icon_size(IconSize::Small)
+                        .tooltip(|cx| Tooltip::for_action("Refresh", &RefreshContainers, cx))
+                        .on_click(cx.listener(|this, _, cx| this.refresh(cx))),
+                )
+                .into_any_element(),
+            Entry::ImagesHeader => ListHeader::new("Images").into_any_element(),
+            Entry::Container(container_ix) => {
+                let container = &self.containers[container_ix];
+                let is_running = container.is_running();
+                ListItem::new(ix)
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .start_slot(Indicator::dot().color(if is_running {
+                        Color::Created
+                    } else {
+                        Color::Muted
+                    }))
+                    .child(
+                        v_flex()
+                            .child(Label::new(container.name().to_string()))
+                            .child(
+                                Label::new(format!("{} · {}", container.image, container.status))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .single_line(),
+                            ),
+                    )
+                    .end_hover_slot(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                IconButton::new(
+                                    ("toggle-container", container_ix),
+                                    if is_running {
+                                        IconName::Stop
+                                    } else {
+                                        IconName::Play
+                                    },
+                                )
+                                .icon_size(IconSize::Small)
+                                .tooltip(move |cx| {
+                                    Tooltip::text(if is_running { "Stop" } else { "Start" }, cx)
+                                })
+                                .on_click(cx.listener(
+                                    move |this, _, cx| this.toggle_container(container_ix, cx),
+                                )),
+                            )
+                            .child(
+                                IconButton::new(
+                                    ("restart-container", container_ix),
+                                    IconName::Rerun,
+                                )
+                                .icon_size(IconSize::Small)
+                                .tooltip(|cx| Tooltip::text("Restart", cx))
+                                .on_click(cx.listener(
+                                    move |this, _, cx| this.restart_container(container_ix, cx),
+                                )),
+                            )
+                            .child(
+                                IconButton::new(
+                                    ("container-logs", container_ix),
+                                    IconName::FileText,
+                                )
+                                .icon_size(IconSize::Small)
+                                .tooltip(|cx| Tooltip::text("Show Logs", cx))
+                                .on_click(
+                                    cx.listener(move |this, _, cx| {
+                                        this.show_logs(container_ix, cx)
+                                    }),
+                                ),
+                            )
+                            .when(is_running, |this| {
+                                this.child(
+                                    IconButton::new(
+                                        ("exec-container", container_ix),
+                                        IconName::Terminal,
+                                    )
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(|cx| Tooltip::text("Open Shell", cx))
+                                    .on_click(cx.listener(
+                                        move |this, _, cx| this.exec_into(container_ix, cx),
+                                    )),
+                                )
+                            }),
+                    )
+                    .into_any_element()
+            }
+            Entry::Image(image_ix) => {
+                let image = &self.images[image_ix];
+                ListItem::new(ix)
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .child(Label::new(image.name().to_string()).single_line())
+                    .end_slot(
+                        Label::new(format_size(image.size))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const MB: f64 = 1000. * 1000.;
+    let megabytes = bytes as f64 / MB;
+    if megabytes >= 1000. {
+        format!("{:.1} GB", megabytes / 1000.)
+    } else {
+        format!("{megabytes:.1} MB")
+    }
+}
+
+impl Render for DockerPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DockerPanel")
+            .on_action(cx.listener(Self::refresh_containers))
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    h_flex()
+                        .p_2()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Muted)),
+                )
+            })
+            .child(
+                uniform_list(
+                    cx.view().clone(),
+                    "docker-entries",
+                    self.entries.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_entry(ix, cx))
+                            .collect::<Vec<_>>()
+                    },
+                )
+                .size_full(),
+            )
+    }
+}
+
+impl FocusableView for DockerPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for DockerPanel {}
+
+impl Panel for DockerPanel {
+    fn persistent_name() -> &'static str {
+        "DockerPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        DockerPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<DockerPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| DockerPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
+        if active {
+            self.refresh(cx);
+        }
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        DockerPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Server)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Docker Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -0,0 +1,53 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use std::path::PathBuf;
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DockerPanelSettings {
+    pub socket_path: Option<PathBuf>,
+    pub exec_shell: String,
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct DockerPanelSettingsContent {
+    /// Path to the Docker daemon's socket. When unset, a `unix://` `DOCKER_HOST` is used,
+    /// falling back to `/var/run/docker.sock`.
+    ///
+    /// Default: null
+    pub socket_path: Option<PathBuf>,
+    /// The shell to run when opening a terminal inside a container.
+    ///
+    /// Default: "sh"
+    pub exec_shell: Option<String>,
+    /// Whether to show the Docker panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the Docker panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the Docker panel in pixels.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
+impl Settings for DockerPanelSettings {
+    const KEY: Option<&'static str> = Some("docker_panel");
+
+    type FileContent = DockerPanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -92,6 +92,20 @@ pub struct UnfoldAt {
     pub buffer_row: MultiBufferRow,
 }
 
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct AddSelectionAbove {
+    /// How many cursors to add, where zero adds one.
+    #[serde(default)]
+    pub(super) count: u32,
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct AddSelectionBelow {
+    /// How many cursors to add, where zero adds one.
+    #[serde(default)]
+    pub(super) count: u32,
+}
+
 #[derive(PartialEq, Clone, Deserialize, Default)]
 pub struct MoveUpByLines {
     #[serde(default)]
@@ -153,9 +167,24 @@ pub struct DeleteToPreviousWordStart {
     pub ignore_newlines: bool,
 }
 
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct AddToWorkingSet {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(PartialEq, Clone, Deserialize, Default)]
+pub struct OpenWorkingSet {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
 impl_actions!(
     editor,
     [
+        AddSelectionAbove,
+        AddSelectionBelow,
+        AddToWorkingSet,
         ComposeCompletion,
         ConfirmCodeAction,
         ConfirmCompletion,
@@ -172,6 +201,7 @@ impl_actions!(
         MoveToBeginningOfLine,
         MoveToEndOfLine,
         MoveUpByLines,
+        OpenWorkingSet,
         SelectDownByLines,
         SelectNext,
         SelectPrevious,
@@ -191,8 +221,7 @@ gpui::actions!(
         AcceptInlineCompletion,
         AcceptPartialCopilotSuggestion,
         AcceptPartialInlineCompletion,
-        AddSelectionAbove,
-        AddSelectionBelow,
+        AddCursorsToSelectedLines,
         Backspace,
         Cancel,
         CancelLanguageServerWork,
@@ -226,6 +255,7 @@ gpui::actions!(
         DisplayCursorNames,
         DuplicateLineDown,
         DuplicateLineUp,
+        DuplicateSelection,
         ExpandAllHunkDiffs,
         ExpandMacroRecursively,
         FindAllReferences,
@@ -283,6 +313,7 @@ gpui::actions!(
         PreviousInlineCompletion,
         Redo,
         RedoSelection,
+        RemoveFromWorkingSet,
         Rename,
         RestartLanguageServer,
         RevealInFileManager,
@@ -305,6 +336,7 @@ gpui::actions!(
         SelectPageUp,
         SelectRight,
         SelectSmallerSyntaxNode,
+        SelectSubword,
         SelectToBeginning,
         SelectToEnd,
         SelectToEndOfParagraph,
@@ -320,6 +352,7 @@ gpui::actions!(
         ShuffleLines,
         SortLinesCaseInsensitive,
         SortLinesCaseSensitive,
+        SplitSelectionIntoLineRanges,
         SplitSelectionIntoLines,
         SwitchSourceHeader,
         Tab,
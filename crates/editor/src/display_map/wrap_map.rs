@@ -3,11 +3,14 @@ use super::{
     tab_map::{self, TabEdit, TabPoint, TabSnapshot},
     Highlights,
 };
-use gpui::{AppContext, Context, Font, LineWrapper, Model, ModelContext, Pixels, Task};
+use collections::HashMap;
+use gpui::{
+    AppContext, Context, Font, LineWrapper, Model, ModelContext, Pixels, Task, WrapOptions,
+};
 use language::{Chunk, Point};
 use multi_buffer::MultiBufferSnapshot;
 use smol::future::yield_now;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Mutex};
 use std::{cmp, collections::VecDeque, mem, ops::Range, time::Duration};
 use sum_tree::{Bias, Cursor, SumTree};
 use text::Patch;
@@ -24,6 +27,7 @@ pub struct WrapMap {
     interpolated_edits: Patch<u32>,
     edits_since_sync: Patch<u32>,
     wrap_width: Option<Pixels>,
+    wrap_options: WrapOptions,
     background_task: Option<Task<()>>,
     font_with_size: (Font, Pixels),
 }
@@ -80,6 +84,7 @@ impl WrapMap {
             let mut this = Self {
                 font_with_size: (font, font_size),
                 wrap_width: None,
+                wrap_options: WrapOptions::default(),
                 pending_edits: Default::default(),
                 interpolated_edits: Default::default(),
                 edits_since_sync: Default::default(),
@@ -149,6 +154,22 @@ impl WrapMap {
         true
     }
 
+    pub fn set_wrap_options(
+        &mut self,
+        wrap_options: WrapOptions,
+        cx: &mut ModelContext<Self>,
+    ) -> bool {
+        if wrap_options == self.wrap_options {
+            return false;
+        }
+
+        self.wrap_options = wrap_options;
+        if self.wrap_width.is_some() {
+            self.rewrap(cx);
+        }
+        true
+    }
+
     fn rewrap(&mut self, cx: &mut ModelContext<Self>) {
         self.background_task.take();
         self.interpolated_edits.clear();
@@ -159,6 +180,7 @@ impl WrapMap {
 
             let text_system = cx.text_system().clone();
             let (font, font_size) = self.font_with_size.clone();
+            let wrap_options = self.wrap_options;
             let task = cx.background_executor().spawn(async move {
                 let mut line_wrapper = text_system.line_wrapper(font, font_size);
                 let tab_snapshot = new_snapshot.tab_snapshot.clone();
@@ -171,6 +193,7 @@ impl WrapMap {
                             new: range.clone(),
                         }],
                         wrap_width,
+                        wrap_options,
                         &mut line_wrapper,
                     )
                     .await;
@@ -243,12 +266,19 @@ impl WrapMap {
                 let mut snapshot = self.snapshot.clone();
                 let text_system = cx.text_system().clone();
                 let (font, font_size) = self.font_with_size.clone();
+                let wrap_options = self.wrap_options;
                 let update_task = cx.background_executor().spawn(async move {
                     let mut edits = Patch::default();
                     let mut line_wrapper = text_system.line_wrapper(font, font_size);
                     for (tab_snapshot, tab_edits) in pending_edits {
                         let wrap_edits = snapshot
-                            .update(tab_snapshot, &tab_edits, wrap_width, &mut line_wrapper)
+                            .update(
+                                tab_snapshot,
+                                &tab_edits,
+                                wrap_width,
+                                wrap_options,
+                                &mut line_wrapper,
+                            )
                             .await;
                         edits = edits.compose(&wrap_edits);
                     }
@@ -390,6 +420,7 @@ impl WrapSnapshot {
         new_tab_snapshot: TabSnapshot,
         tab_edits: &[TabEdit],
         wrap_width: Pixels,
+        wrap_options: WrapOptions,
         line_wrapper: &mut LineWrapper,
     ) -> Patch<u32> {
         #[derive(Debug)]
@@ -398,6 +429,7 @@ impl WrapSnapshot {
             new_rows: Range<u32>,
         }
 
+        let wrap_text = Transform::wrap_text(wrap_options);
         let mut tab_edits_iter = tab_edits.iter().peekable();
         let mut row_edits = Vec::new();
         while let Some(edit) = tab_edits_iter.next() {
@@ -467,10 +499,12 @@ impl WrapSnapshot {
                     }
 
                     let mut prev_boundary_ix = 0;
-                    for boundary in line_wrapper.wrap_line(&line, wrap_width) {
+                    for boundary in
+                        line_wrapper.wrap_line_with_options(&line, wrap_width, wrap_options)
+                    {
                         let wrapped = &line[prev_boundary_ix..boundary.ix];
                         push_isomorphic(&mut edit_transforms, TextSummary::from(wrapped));
-                        edit_transforms.push(Transform::wrap(boundary.next_indent));
+                        edit_transforms.push(Transform::wrap(wrap_text, boundary.next_indent));
                         prev_boundary_ix = boundary.ix;
                     }
 
@@ -797,14 +831,15 @@ impl<'a> Iterator for WrapChunks<'a> {
             let mut start_ix = 0;
             let mut end_ix = display_text.len();
             let mut summary = transform.summary.output.lines;
+            let newline_ix = display_text.find('\n').unwrap_or(0);
 
             if self.output_position > self.transforms.start().0 {
                 // Exclude newline starting prior to the desired row.
-                start_ix = 1;
+                start_ix = newline_ix + 1;
                 summary.row = 0;
             } else if self.output_position.row() + 1 >= self.max_output_row {
                 // Exclude soft indentation ending after the desired row.
-                end_ix = 1;
+                end_ix = newline_ix + 1;
                 summary.column = 0;
             }
 
@@ -886,26 +921,50 @@ impl Transform {
         }
     }
 
-    fn wrap(indent: u32) -> Self {
-        static WRAP_TEXT: LazyLock<String> = LazyLock::new(|| {
-            let mut wrap_text = String::new();
-            wrap_text.push('\n');
-            wrap_text.extend((0..LineWrapper::MAX_INDENT as usize).map(|_| ' '));
-            wrap_text
-        });
+    /// The text displayed for soft wraps with the given options: the end marker, a newline,
+    /// the continuation marker and as many spaces as the maximum indent.
+    fn wrap_text(options: WrapOptions) -> &'static str {
+        static WRAP_TEXTS: LazyLock<Mutex<HashMap<(Option<char>, Option<char>), &'static str>>> =
+            LazyLock::new(Default::default);
+
+        let markers = (options.end_marker, options.continuation_marker);
+        *WRAP_TEXTS
+            .lock()
+            .unwrap()
+            .entry(markers)
+            .or_insert_with(|| {
+                let mut wrap_text = String::new();
+                wrap_text.extend(options.end_marker);
+                wrap_text.push('\n');
+                wrap_text.extend(options.continuation_marker);
+                wrap_text.extend((0..LineWrapper::MAX_INDENT as usize).map(|_| ' '));
+                wrap_text.leak()
+            })
+    }
+
+    fn wrap(wrap_text: &'static str, indent: u32) -> Self {
+        let newline_ix = wrap_text.find('\n').unwrap_or(0);
+        let indent_ix = wrap_text.len() - LineWrapper::MAX_INDENT as usize;
+        let display_text = &wrap_text[..indent_ix + indent as usize];
+        let first_line_chars = wrap_text[..newline_ix].chars().count() as u32;
+        let last_line_chars = display_text[newline_ix + 1..].chars().count() as u32;
 
         Self {
             summary: TransformSummary {
                 input: TextSummary::default(),
                 output: TextSummary {
-                    lines: Point::new(1, indent),
-                    first_line_chars: 0,
-                    last_line_chars: indent,
-                    longest_row: 1,
-                    longest_row_chars: indent,
+                    lines: Point::new(1, (display_text.len() - newline_ix - 1) as u32),
+                    first_line_chars,
+                    last_line_chars,
+                    longest_row: if first_line_chars > last_line_chars {
+                        0
+                    } else {
+                        1
+                    },
+                    longest_row_chars: first_line_chars.max(last_line_chars),
                 },
             },
-            display_text: Some(&WRAP_TEXT[..1 + indent as usize]),
+            display_text: Some(display_text),
         }
     }
 
@@ -39,6 +39,8 @@ mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
 pub mod tasks;
+mod word_completions;
+mod working_set;
 
 #[cfg(test)]
 mod editor_tests;
@@ -60,10 +62,11 @@ use debounced_delay::DebouncedDelay;
 use display_map::*;
 pub use display_map::{DisplayPoint, FoldPlaceholder};
 pub use editor_settings::{
-    CurrentLineHighlight, EditorSettings, ScrollBeyondLastLine, SearchSettings,
+    CurrentLineHighlight, EditorSettings, MultiCursorShortLines, ScrollBeyondLastLine,
+    SearchSettings,
 };
 pub use editor_settings_controls::*;
-use element::LineWithInvisibles;
+use element::{CachedLineLayouts, LineWithInvisibles, TypingEcho};
 pub use element::{
     CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
 };
@@ -100,6 +103,7 @@ use language::{point_to_lsp, BufferRow, CharClassifier, Runnable, RunnableRange}
 use linked_editing_ranges::refresh_linked_ranges;
 use similar::{ChangeTag, TextDiff};
 use task::{ResolvedTask, TaskTemplate, TaskVariables};
+pub use working_set::WorkingSet;
 
 use hover_links::{find_file, HoverLink, HoveredLinkState, InlayHighlight};
 pub use lsp::CompletionContext;
@@ -296,6 +300,7 @@ pub fn init(cx: &mut AppContext) {
     workspace::register_project_item::<Editor>(cx);
     workspace::FollowableViewRegistry::register::<Editor>(cx);
     workspace::register_serializable_item::<Editor>(cx);
+    working_set::init(cx);
 
     cx.observe_new_views(
         |workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>| {
@@ -548,6 +553,7 @@ pub struct Editor {
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
     show_wrap_guides: Option<bool>,
+    wrap_guides_override: Option<SmallVec<[usize; 2]>>,
     show_indent_guides: Option<bool>,
     placeholder_text: Option<Arc<str>>,
     highlight_order: usize,
@@ -619,6 +625,10 @@ pub struct Editor {
     >,
     last_bounds: Option<Bounds<Pixels>>,
     expect_bounds_change: Option<Bounds<Pixels>>,
+    /// Line layouts of the last frame, reused to echo typing before the next full layout.
+    cached_line_layouts: Option<CachedLineLayouts>,
+    /// A keystroke that only changed its own line since the last frame.
+    typing_echo: Option<TypingEcho>,
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     previous_search_ranges: Option<Arc<[Range<Anchor>]>>,
@@ -1228,6 +1238,13 @@ impl CompletionsMenu {
                                         task.detach_and_log_err(cx)
                                     }
                                 }))
+                                .when(word_completions::is_word_completion(completion), |item| {
+                                    item.start_slot(
+                                        Icon::new(IconName::WholeWord)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Muted),
+                                    )
+                                })
                                 .child(h_flex().overflow_hidden().child(completion_label))
                                 .end_slot::<Label>(documentation_label),
                         )
@@ -1786,7 +1803,7 @@ impl Editor {
         };
         let file_header_size = if show_excerpt_controls { 3 } else { 2 };
         let display_map = cx.new_model(|cx| {
-            DisplayMap::new(
+            let display_map = DisplayMap::new(
                 buffer.clone(),
                 style.font(),
                 font_size,
@@ -1797,7 +1814,14 @@ impl Editor {
                 MULTI_BUFFER_EXCERPT_FOOTER_HEIGHT,
                 fold_placeholder,
                 cx,
-            )
+            );
+            display_map.set_wrap_options(
+                EditorSettings::get_global(cx)
+                    .soft_wrap_layout
+                    .wrap_options(),
+                cx,
+            );
+            display_map
         });
 
         let selections = SelectionsCollection::new(display_map.clone(), buffer.clone());
@@ -1893,6 +1917,7 @@ impl Editor {
             show_code_actions: None,
             show_runnables: None,
             show_wrap_guides: None,
+            wrap_guides_override: None,
             show_indent_guides,
             placeholder_text: None,
             highlight_order: 0,
@@ -1942,6 +1967,8 @@ impl Editor {
             pixel_position_of_newest_cursor: None,
             last_bounds: None,
             expect_bounds_change: None,
+            cached_line_layouts: None,
+            typing_echo: None,
             gutter_dimensions: GutterDimensions::default(),
             style: None,
             show_cursor_names: false,
@@ -2729,7 +2756,7 @@ impl Editor {
                 auto_scroll = true;
             }
             2 => {
-                let range = movement::surrounding_word(&display_map, position);
+                let range = Self::surrounding_word(&display_map, position, cx);
                 start = buffer.anchor_before(range.start.to_point(&display_map));
                 end = buffer.anchor_before(range.end.to_point(&display_map));
                 mode = SelectMode::Word(start..end);
@@ -2869,7 +2896,7 @@ impl Editor {
                     if movement::is_inside_word(&display_map, position)
                         || original_display_range.contains(&position)
                     {
-                        let word_range = movement::surrounding_word(&display_map, position);
+                        let word_range = Self::surrounding_word(&display_map, position, cx);
                         if word_range.start < original_display_range.start {
                             head = word_range.start.to_point(&display_map);
                         } else {
@@ -3341,6 +3368,14 @@ impl Editor {
 
         drop(snapshot);
 
+        // A keystroke that only changes its own line can be painted from the
+        // previous frame's line layouts while the rest of the editor catches up.
+        let echoes_typing = linked_edits.is_empty()
+            && matches!(
+                edits.as_slice(),
+                [(range, new_text)] if range.start.row == range.end.row && !new_text.contains('\n')
+            );
+
         self.transact(cx, |this, cx| {
             this.buffer.update(cx, |buffer, cx| {
                 buffer.edit(edits, this.autoindent_mode.clone(), cx);
@@ -3406,6 +3441,12 @@ impl Editor {
             this.change_selections_inner(Some(Autoscroll::fit()), false, cx, |s| {
                 s.select(new_selections)
             });
+            if echoes_typing {
+                this.typing_echo = Some(TypingEcho {
+                    position: this.selections.newest_anchor().head(),
+                    edit_count: this.buffer.read(cx).read(cx).edit_count(),
+                });
+            }
 
             if !bracket_inserted && EditorSettings::get_global(cx).use_on_type_format {
                 if let Some(on_type_format_task) =
@@ -6457,6 +6498,51 @@ impl Editor {
         self.duplicate_line(false, cx);
     }
 
+    pub fn duplicate_selection(&mut self, _: &DuplicateSelection, cx: &mut ViewContext<Self>) {
+        let selections = self.selections.all::<usize>(cx);
+        if self.selections.line_mode || selections.iter().all(|selection| selection.is_empty()) {
+            self.duplicate_line(false, cx);
+            return;
+        }
+
+        // Insert a copy of each selection's text right after it, and select the copy.
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut new_selections = Vec::new();
+        let mut delta = 0;
+        for selection in selections {
+            let end = selection.end + delta;
+            if selection.is_empty() {
+                new_selections.push(Selection {
+                    start: end,
+                    end,
+                    goal: SelectionGoal::None,
+                    ..selection
+                });
+                continue;
+            }
+
+            let text = buffer
+                .text_for_range(selection.start..selection.end)
+                .collect::<String>();
+            delta += text.len();
+            new_selections.push(Selection {
+                start: end,
+                end: end + text.len(),
+                goal: SelectionGoal::None,
+                ..selection
+            });
+            edits.push((selection.end..selection.end, text));
+        }
+
+        self.transact(cx, |this, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(Some(Autoscroll::fit()), cx, |s| s.select(new_selections));
+        });
+    }
+
     pub fn move_line_up(&mut self, _: &MoveLineUp, cx: &mut ViewContext<Self>) {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
@@ -6513,9 +6599,17 @@ impl Editor {
                         buffer.anchor_after(range_to_move.start)
                             ..buffer.anchor_before(range_to_move.end),
                         String::new(),
+                        None,
                     ));
+                    // Reindent the moved lines as a block to fit where they end up.
                     let insertion_anchor = buffer.anchor_after(insertion_point);
-                    edits.push((insertion_anchor..insertion_anchor, text));
+                    edits.push((
+                        insertion_anchor..insertion_anchor,
+                        text,
+                        Some(AutoindentMode::Block {
+                            original_indent_columns: Vec::new(),
+                        }),
+                    ));
 
                     let row_delta = range_to_move.start.row - insertion_point.row + 1;
 
@@ -6550,8 +6644,8 @@ impl Editor {
         self.transact(cx, |this, cx| {
             this.unfold_ranges(unfold_ranges, true, true, cx);
             this.buffer.update(cx, |buffer, cx| {
-                for (range, text) in edits {
-                    buffer.edit([(range, text)], None, cx);
+                for (range, text, autoindent_mode) in edits {
+                    buffer.edit([(range, text)], autoindent_mode, cx);
                 }
             });
             this.fold_ranges(refold_ranges, true, cx);
@@ -6607,9 +6701,17 @@ impl Editor {
                         buffer.anchor_after(range_to_move.start)
                             ..buffer.anchor_before(range_to_move.end),
                         String::new(),
+                        None,
                     ));
+                    // Reindent the moved lines as a block to fit where they end up.
                     let insertion_anchor = buffer.anchor_after(insertion_point);
-                    edits.push((insertion_anchor..insertion_anchor, text));
+                    edits.push((
+                        insertion_anchor..insertion_anchor,
+                        text,
+                        Some(AutoindentMode::Block {
+                            original_indent_columns: Vec::new(),
+                        }),
+                    ));
 
                     let row_delta = insertion_point.row - range_to_move.end.row + 1;
 
@@ -6644,8 +6746,8 @@ impl Editor {
         self.transact(cx, |this, cx| {
             this.unfold_ranges(unfold_ranges, true, true, cx);
             this.buffer.update(cx, |buffer, cx| {
-                for (range, text) in edits {
-                    buffer.edit([(range, text)], None, cx);
+                for (range, text, autoindent_mode) in edits {
+                    buffer.edit([(range, text)], autoindent_mode, cx);
                 }
             });
             this.fold_ranges(refold_ranges, true, cx);
@@ -7940,6 +8042,35 @@ impl Editor {
         });
     }
 
+    pub fn select_subword(&mut self, _: &SelectSubword, cx: &mut ViewContext<Self>) {
+        self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+            s.move_with(|map, selection| {
+                if selection.is_empty() {
+                    let range = movement::surrounding_subword(map, selection.head());
+                    selection.start = range.start;
+                    selection.end = range.end;
+                    selection.reversed = false;
+                    selection.goal = SelectionGoal::None;
+                }
+            });
+        });
+    }
+
+    /// Returns the range of the word surrounding the given position, also treating the
+    /// `word_characters` of the language settings at that position as word characters.
+    fn surrounding_word(
+        display_map: &DisplaySnapshot,
+        position: DisplayPoint,
+        cx: &AppContext,
+    ) -> Range<DisplayPoint> {
+        let word_characters = display_map
+            .buffer_snapshot
+            .settings_at(position.to_point(display_map), cx)
+            .word_characters
+            .clone();
+        movement::surrounding_word_with_characters(display_map, position, word_characters)
+    }
+
     pub fn split_selection_into_lines(
         &mut self,
         _: &SplitSelectionIntoLines,
@@ -7965,115 +8096,211 @@ impl Editor {
         });
     }
 
-    pub fn add_selection_above(&mut self, _: &AddSelectionAbove, cx: &mut ViewContext<Self>) {
-        self.add_selection(true, cx);
-    }
-
-    pub fn add_selection_below(&mut self, _: &AddSelectionBelow, cx: &mut ViewContext<Self>) {
-        self.add_selection(false, cx);
+    pub fn split_selection_into_line_ranges(
+        &mut self,
+        _: &SplitSelectionIntoLineRanges,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let mut to_unfold = Vec::new();
+        let mut new_selection_ranges = Vec::new();
+        {
+            let selections = self.selections.all::<Point>(cx);
+            let buffer = self.buffer.read(cx).read(cx);
+            for selection in selections {
+                let mut end_row = selection.end.row;
+                if selection.end.column == 0 && end_row > selection.start.row {
+                    end_row -= 1;
+                }
+                for row in selection.start.row..=end_row {
+                    let start = if row == selection.start.row {
+                        selection.start
+                    } else {
+                        Point::new(row, 0)
+                    };
+                    let end = if row == selection.end.row {
+                        selection.end
+                    } else {
+                        Point::new(row, buffer.line_len(MultiBufferRow(row)))
+                    };
+                    if selection.reversed {
+                        new_selection_ranges.push(end..start);
+                    } else {
+                        new_selection_ranges.push(start..end);
+                    }
+                }
+                to_unfold.push(selection.start..selection.end);
+            }
+        }
+        self.unfold_ranges(to_unfold, true, true, cx);
+        self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+            s.select_ranges(new_selection_ranges);
+        });
     }
 
-    fn add_selection(&mut self, above: bool, cx: &mut ViewContext<Self>) {
+    pub fn add_cursors_to_selected_lines(
+        &mut self,
+        _: &AddCursorsToSelectedLines,
+        cx: &mut ViewContext<Self>,
+    ) {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
-        let mut selections = self.selections.all::<Point>(cx);
         let text_layout_details = self.text_layout_details(cx);
-        let mut state = self.add_selections_state.take().unwrap_or_else(|| {
-            let oldest_selection = selections.iter().min_by_key(|s| s.id).unwrap().clone();
-            let range = oldest_selection.display_range(&display_map).sorted();
-
-            let start_x = display_map.x_for_display_point(range.start, &text_layout_details);
-            let end_x = display_map.x_for_display_point(range.end, &text_layout_details);
-            let positions = start_x.min(end_x)..start_x.max(end_x);
-
-            selections.clear();
-            let mut stack = Vec::new();
-            for row in range.start.row().0..=range.end.row().0 {
-                if let Some(selection) = self.selections.build_columnar_selection(
+        let clamp_to_line_end =
+            EditorSettings::get_global(cx).multi_cursor_short_lines == MultiCursorShortLines::Clamp;
+        let selections = self.selections.all_display(cx);
+
+        let mut new_selections = Vec::new();
+        for selection in selections {
+            let head = selection.head();
+            let x = display_map.x_for_display_point(head, &text_layout_details);
+            let positions = x..x;
+            let start_row = selection.start.row();
+            let mut end_row = selection.end.row();
+            if selection.end.column() == 0 && end_row > start_row && head != selection.end {
+                end_row.0 -= 1;
+            }
+            for row in start_row.0..=end_row.0 {
+                let row = DisplayRow(row);
+                // The head's own line always reaches its column.
+                new_selections.extend(self.selections.build_columnar_selection(
                     &display_map,
-                    DisplayRow(row),
+                    row,
                     &positions,
-                    oldest_selection.reversed,
+                    false,
+                    clamp_to_line_end || row == head.row(),
                     &text_layout_details,
-                ) {
-                    stack.push(selection.id);
-                    selections.push(selection);
-                }
-            }
-
-            if above {
-                stack.reverse();
+                ));
             }
+        }
 
-            AddSelectionsState { above, stack }
+        self.change_selections(Some(Autoscroll::fit()), cx, |s| {
+            s.select(new_selections);
         });
+    }
 
-        let last_added_selection = *state.stack.last().unwrap();
-        let mut new_selections = Vec::new();
-        if above == state.above {
-            let end_row = if above {
-                DisplayRow(0)
-            } else {
-                display_map.max_point().row()
-            };
-
-            'outer: for selection in selections {
-                if selection.id == last_added_selection {
-                    let range = selection.display_range(&display_map).sorted();
-                    debug_assert_eq!(range.start.row(), range.end.row());
-                    let mut row = range.start.row();
-                    let positions =
-                        if let SelectionGoal::HorizontalRange { start, end } = selection.goal {
-                            px(start)..px(end)
-                        } else {
-                            let start_x =
-                                display_map.x_for_display_point(range.start, &text_layout_details);
-                            let end_x =
-                                display_map.x_for_display_point(range.end, &text_layout_details);
-                            start_x.min(end_x)..start_x.max(end_x)
-                        };
+    pub fn add_selection_above(&mut self, action: &AddSelectionAbove, cx: &mut ViewContext<Self>) {
+        self.add_selection(true, action.count.max(1), cx);
+    }
 
-                    while row != end_row {
-                        if above {
-                            row.0 -= 1;
-                        } else {
-                            row.0 += 1;
-                        }
+    pub fn add_selection_below(&mut self, action: &AddSelectionBelow, cx: &mut ViewContext<Self>) {
+        self.add_selection(false, action.count.max(1), cx);
+    }
 
-                        if let Some(new_selection) = self.selections.build_columnar_selection(
+    fn add_selection(&mut self, above: bool, count: u32, cx: &mut ViewContext<Self>) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let mut selections = self.selections.all::<Point>(cx);
+        let text_layout_details = self.text_layout_details(cx);
+        let clamp_to_line_end =
+            EditorSettings::get_global(cx).multi_cursor_short_lines == MultiCursorShortLines::Clamp;
+        let mut add_selections_state = self.add_selections_state.take();
+
+        for _ in 0..count {
+            let mut state = match add_selections_state.take() {
+                Some(state) => state,
+                None => {
+                    let oldest_selection = selections.iter().min_by_key(|s| s.id).unwrap().clone();
+                    let range = oldest_selection.display_range(&display_map).sorted();
+
+                    let start_x =
+                        display_map.x_for_display_point(range.start, &text_layout_details);
+                    let end_x = display_map.x_for_display_point(range.end, &text_layout_details);
+                    let positions = start_x.min(end_x)..start_x.max(end_x);
+
+                    selections.clear();
+                    let mut stack = Vec::new();
+                    for row in range.start.row().0..=range.end.row().0 {
+                        if let Some(selection) = self.selections.build_columnar_selection(
                             &display_map,
-                            row,
+                            DisplayRow(row),
                             &positions,
-                            selection.reversed,
+                            oldest_selection.reversed,
+                            false,
                             &text_layout_details,
                         ) {
-                            state.stack.push(new_selection.id);
+                            stack.push(selection.id);
+                            selections.push(selection);
+                        }
+                    }
+
+                    if above {
+                        stack.reverse();
+                    }
+
+                    AddSelectionsState { above, stack }
+                }
+            };
+
+            let last_added_selection = *state.stack.last().unwrap();
+            let mut new_selections = Vec::new();
+            if above == state.above {
+                let end_row = if above {
+                    DisplayRow(0)
+                } else {
+                    display_map.max_point().row()
+                };
+
+                'outer: for selection in selections {
+                    if selection.id == last_added_selection {
+                        let range = selection.display_range(&display_map).sorted();
+                        debug_assert_eq!(range.start.row(), range.end.row());
+                        let mut row = range.start.row();
+                        let positions =
+                            if let SelectionGoal::HorizontalRange { start, end } = selection.goal {
+                                px(start)..px(end)
+                            } else {
+                                let start_x = display_map
+                                    .x_for_display_point(range.start, &text_layout_details);
+                                let end_x = display_map
+                                    .x_for_display_point(range.end, &text_layout_details);
+                                start_x.min(end_x)..start_x.max(end_x)
+                            };
+
+                        while row != end_row {
                             if above {
-                                new_selections.push(new_selection);
-                                new_selections.push(selection);
+                                row.0 -= 1;
                             } else {
-                                new_selections.push(selection);
-                                new_selections.push(new_selection);
+                                row.0 += 1;
                             }
 
-                            continue 'outer;
+                            if let Some(new_selection) = self.selections.build_columnar_selection(
+                                &display_map,
+                                row,
+                                &positions,
+                                selection.reversed,
+                                clamp_to_line_end,
+                                &text_layout_details,
+                            ) {
+                                state.stack.push(new_selection.id);
+                                if above {
+                                    new_selections.push(new_selection);
+                                    new_selections.push(selection);
+                                } else {
+                                    new_selections.push(selection);
+                                    new_selections.push(new_selection);
+                                }
+
+                                continue 'outer;
+                            }
                         }
                     }
+
+                    new_selections.push(selection);
                 }
+            } else {
+                new_selections = selections;
+                new_selections.retain(|s| s.id != last_added_selection);
+                state.stack.pop();
+            }
 
-                new_selections.push(selection);
+            selections = new_selections;
+            if state.stack.len() > 1 {
+                add_selections_state = Some(state);
             }
-        } else {
-            new_selections = selections;
-            new_selections.retain(|s| s.id != last_added_selection);
-            state.stack.pop();
         }
 
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
-            s.select(new_selections);
+            s.select(selections);
         });
-        if state.stack.len() > 1 {
-            self.add_selections_state = Some(state);
-        }
+        self.add_selections_state = add_selections_state;
     }
 
     pub fn select_next_match_internal(
@@ -8192,9 +8419,10 @@ impl Editor {
 
             if only_carets {
                 for selection in &mut selections {
-                    let word_range = movement::surrounding_word(
+                    let word_range = Self::surrounding_word(
                         display_map,
                         selection.start.to_display_point(display_map),
+                        cx,
                     );
                     selection.start = word_range.start.to_offset(display_map, Bias::Left);
                     selection.end = word_range.end.to_offset(display_map, Bias::Left);
@@ -8419,9 +8647,10 @@ impl Editor {
 
             if only_carets {
                 for selection in &mut selections {
-                    let word_range = movement::surrounding_word(
+                    let word_range = Self::surrounding_word(
                         &display_map,
                         selection.start.to_display_point(&display_map),
+                        cx,
                     );
                     selection.start = word_range.start.to_offset(&display_map, Bias::Left);
                     selection.end = word_range.end.to_offset(&display_map, Bias::Left);
@@ -10767,6 +10996,11 @@ impl Editor {
             return wrap_guides;
         }
 
+        if let Some(guides) = &self.wrap_guides_override {
+            wrap_guides.extend(guides.iter().map(|guide| (*guide, true)));
+            return wrap_guides;
+        }
+
         let settings = self.buffer.read(cx).settings_at(0, cx);
         if settings.show_wrap_guides {
             if let SoftWrap::Column(soft_wrap) = self.soft_wrap_mode(cx) {
@@ -10940,6 +11174,16 @@ impl Editor {
         cx.notify();
     }
 
+    /// Draws wrap guides at the given columns instead of the ones from the language settings.
+    pub fn set_wrap_guides(
+        &mut self,
+        wrap_guides: impl IntoIterator<Item = usize>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.wrap_guides_override = Some(wrap_guides.into_iter().collect());
+        cx.notify();
+    }
+
     pub fn set_show_indent_guides(&mut self, show_indent_guides: bool, cx: &mut ViewContext<Self>) {
         self.show_indent_guides = Some(show_indent_guides);
         cx.notify();
@@ -11839,6 +12083,9 @@ impl Editor {
         }
         self.scroll_manager.vertical_scroll_margin = editor_settings.vertical_scroll_margin;
         self.show_breadcrumbs = editor_settings.toolbar.breadcrumbs;
+        let wrap_options = editor_settings.soft_wrap_layout.wrap_options();
+        self.display_map
+            .update(cx, |map, cx| map.set_wrap_options(wrap_options, cx));
 
         let project_settings = ProjectSettings::get_global(cx);
         self.serialize_dirty_buffers = project_settings.session.restore_unsaved_buffers;
@@ -12598,11 +12845,46 @@ impl CompletionProvider for Model<Project> {
     ) -> Task<Result<Vec<Completion>>> {
         self.update(cx, |project, cx| {
             let snippets = snippet_completions(project, buffer, buffer_position, cx);
+            let snapshot = buffer.read(cx).snapshot();
+            let word_completions_enabled = EditorSettings::get_global(cx).word_completions;
+            let other_buffers = if word_completions_enabled {
+                project
+                    .opened_buffers(cx)
+                    .into_iter()
+                    .map(|buffer| buffer.read(cx).snapshot())
+                    .collect()
+            } else {
+                Vec::new()
+            };
             let project_completions = project.completions(buffer, buffer_position, options, cx);
             cx.background_executor().spawn(async move {
-                let mut completions = project_completions.await?;
-                //let snippets = snippets.into_iter().;
+                let (mut completions, include_words) = match project_completions.await {
+                    Ok(completions) => (completions, word_completions_enabled),
+                    Err(error) if error.is::<project::LanguageServerTimeout>() => {
+                        log::warn!("{error}, falling back to buffer words");
+                        (Vec::new(), true)
+                    }
+                    Err(error) => return Err(error),
+                };
                 completions.extend(snippets);
+                if include_words {
+                    let offered = completions
+                        .iter()
+                        .map(|completion| {
+                            completion.label.text[completion.label.filter_range.clone()].to_string()
+                        })
+                        .collect::<HashSet<_>>();
+                    let words = word_completions::word_completions(
+                        &snapshot,
+                        buffer_position,
+                        &other_buffers,
+                    );
+                    completions.extend(
+                        words
+                            .into_iter()
+                            .filter(|word| !offered.contains(&word.new_text)),
+                    );
+                }
                 Ok(completions)
             })
         })
@@ -13202,6 +13484,7 @@ impl ViewInputHandler for Editor {
                             thickness: px(1.),
                             color: None,
                             wavy: false,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     },
@@ -1,4 +1,4 @@
-use gpui::AppContext;
+use gpui::{AppContext, WrapBreak, WrapIndent, WrapOptions};
 use language::CursorShape;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,10 +13,12 @@ pub struct EditorSettings {
     pub show_completions_on_input: bool,
     pub show_completion_documentation: bool,
     pub completion_documentation_secondary_query_debounce: u64,
+    pub word_completions: bool,
     pub use_on_type_format: bool,
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
     pub gutter: Gutter,
+    pub soft_wrap_layout: SoftWrapLayout,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f32,
     pub scroll_sensitivity: f32,
@@ -24,6 +26,7 @@ pub struct EditorSettings {
     pub seed_search_query_from_cursor: SeedQuerySetting,
     pub use_smartcase_search: bool,
     pub multi_cursor_modifier: MultiCursorModifier,
+    pub multi_cursor_short_lines: MultiCursorShortLines,
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     pub middle_click_paste: bool,
@@ -116,6 +119,62 @@ pub struct Gutter {
     pub folds: bool,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SoftWrapLayout {
+    pub break_at: SoftWrapBreak,
+    pub indent: SoftWrapIndent,
+    pub extra_indent: u32,
+    pub start_indicator: Option<char>,
+    pub end_indicator: Option<char>,
+}
+
+impl SoftWrapLayout {
+    pub fn wrap_options(&self) -> WrapOptions {
+        WrapOptions {
+            break_at: match self.break_at {
+                SoftWrapBreak::Word => WrapBreak::Word,
+                SoftWrapBreak::Whitespace => WrapBreak::Whitespace,
+                SoftWrapBreak::Anywhere => WrapBreak::Anywhere,
+            },
+            indent: match self.indent {
+                SoftWrapIndent::Aligned => WrapIndent::Aligned,
+                SoftWrapIndent::Extra => WrapIndent::Extra(self.extra_indent),
+                SoftWrapIndent::None => WrapIndent::None,
+            },
+            continuation_marker: self.start_indicator,
+            end_marker: self.end_indicator,
+        }
+    }
+}
+
+/// Where soft wrapped lines can be broken.
+///
+/// Default: word
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftWrapBreak {
+    /// Break between words, splitting words that are longer than a line.
+    Word,
+    /// Break only at whitespace, letting words that are longer than a line overflow it.
+    Whitespace,
+    /// Break at any character.
+    Anywhere,
+}
+
+/// How the continuation lines of soft wrapped lines are indented.
+///
+/// Default: aligned
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftWrapIndent {
+    /// Align continuation lines with the start of the line's text.
+    Aligned,
+    /// Indent continuation lines by `extra_indent` columns more than the line's text.
+    Extra,
+    /// Start continuation lines at the first column.
+    None,
+}
+
 /// When to show the scrollbar in the editor.
 ///
 /// Default: auto
@@ -144,6 +203,21 @@ pub enum MultiCursorModifier {
     CmdOrCtrl,
 }
 
+/// What to do when adding cursors above or below reaches a line that's shorter
+/// than the column the cursors are in.
+///
+/// Default: skip
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiCursorShortLines {
+    /// Skip over the line, adding the cursor to the next line that's long enough.
+    #[default]
+    Skip,
+    /// Add the cursor at the end of the line, keeping the original column for
+    /// cursors added after it.
+    Clamp,
+}
+
 /// Whether the editor will scroll beyond the last line.
 ///
 /// Default: one_page
@@ -209,6 +283,12 @@ pub struct EditorSettingsContent {
     ///
     /// Default: 300 ms
     pub completion_documentation_secondary_query_debounce: Option<u64>,
+    /// Whether to offer the words used in open buffers in the completions menu,
+    /// alongside the completions of language servers. Words are offered when
+    /// language servers don't respond in time either way.
+    ///
+    /// Default: true
+    pub word_completions: Option<bool>,
     /// Whether to use additional LSP queries to format (and amend) the code after
     /// every "trigger" symbol input, defined by LSP server capabilities.
     ///
@@ -220,6 +300,8 @@ pub struct EditorSettingsContent {
     pub scrollbar: Option<ScrollbarContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
+    /// How soft wrapped lines are broken and laid out.
+    pub soft_wrap_layout: Option<SoftWrapLayoutContent>,
     /// Whether the editor will scroll beyond the last line.
     ///
     /// Default: one_page
@@ -246,6 +328,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: alt
     pub multi_cursor_modifier: Option<MultiCursorModifier>,
+    /// What to do when adding cursors above or below reaches a line that's shorter
+    /// than the column the cursors are in.
+    ///
+    /// Default: skip
+    pub multi_cursor_short_lines: Option<MultiCursorShortLines>,
     /// Hide the values of variables in `private` files, as defined by the
     /// private_files setting. This only changes the visual representation,
     /// the values are still present in the file and can be selected / copied / pasted
@@ -360,6 +447,31 @@ pub struct GutterContent {
     pub folds: Option<bool>,
 }
 
+/// Soft wrap layout related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SoftWrapLayoutContent {
+    /// Where soft wrapped lines can be broken.
+    ///
+    /// Default: word
+    pub break_at: Option<SoftWrapBreak>,
+    /// How the continuation lines of soft wrapped lines are indented.
+    ///
+    /// Default: aligned
+    pub indent: Option<SoftWrapIndent>,
+    /// The number of columns continuation lines are indented by when `indent` is `extra`.
+    ///
+    /// Default: 2
+    pub extra_indent: Option<u32>,
+    /// A character to show at the start of continuation lines.
+    ///
+    /// Default: null
+    pub start_indicator: Option<char>,
+    /// A character to show at the end of lines that were soft wrapped.
+    ///
+    /// Default: null
+    pub end_indicator: Option<char>,
+}
+
 impl EditorSettings {
     pub fn jupyter_enabled(cx: &AppContext) -> bool {
         EditorSettings::get_global(cx).jupyter.enabled
@@ -17,6 +17,7 @@ use indoc::indoc;
 use language::{
     language_settings::{
         AllLanguageSettings, AllLanguageSettingsContent, LanguageSettingsContent, PrettierSettings,
+        WordCharacter,
     },
     BracketPairConfig,
     Capability::ReadWrite,
@@ -3757,6 +3758,102 @@ fn test_duplicate_line(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_duplicate_selection(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Each selection is duplicated in place, including column selections and reversed ones.
+    cx.set_state(indoc! {"
+        «abcˇ» def
+        «ghiˇ» jkl
+        m«ˇno» pqr
+    "});
+    cx.update_editor(|e, cx| e.duplicate_selection(&DuplicateSelection, cx));
+    cx.assert_editor_state(indoc! {"
+        abc«abcˇ» def
+        ghi«ghiˇ» jkl
+        mno«ˇno» pqr
+    "});
+
+    // Several selections on one line, and empty ones next to them.
+    cx.set_state(indoc! {"
+        «aˇ» «bˇ» ˇc
+    "});
+    cx.update_editor(|e, cx| e.duplicate_selection(&DuplicateSelection, cx));
+    cx.assert_editor_state(indoc! {"
+        a«aˇ» b«bˇ» ˇc
+    "});
+
+    // Without any selected text, the lines are duplicated.
+    cx.set_state(indoc! {"
+        aˇbc
+        def
+    "});
+    cx.update_editor(|e, cx| e.duplicate_selection(&DuplicateSelection, cx));
+    cx.assert_editor_state(indoc! {"
+        abc
+        aˇbc
+        def
+    "});
+}
+
+#[gpui::test]
+async fn test_move_line_up_down_reindents(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.tab_size = NonZeroU32::new(4)
+    });
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_indents_query(r#"(_ "{" "}" @end) @indent"#)
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {"
+        fn a() {
+            b();
+        }
+        c(ˇ);
+    "});
+    cx.run_until_parked();
+
+    cx.update_editor(|e, cx| e.move_line_up(&MoveLineUp, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            b();
+            c(ˇ);
+        }
+    "});
+
+    cx.update_editor(|e, cx| e.move_line_up(&MoveLineUp, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            c(ˇ);
+            b();
+        }
+    "});
+
+    cx.update_editor(|e, cx| e.move_line_down(&MoveLineDown, cx));
+    cx.run_until_parked();
+    cx.update_editor(|e, cx| e.move_line_down(&MoveLineDown, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            b();
+        }
+        c(ˇ);
+    "});
+}
+
 #[gpui::test]
 fn test_move_line_up_down(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -4841,6 +4938,176 @@ async fn test_add_selection_above_below(cx: &mut TestAppContext) {
     ));
 }
 
+#[gpui::test]
+async fn test_add_selection_above_below_with_count(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc!(
+        r#"abcdefg
+           ab
+
+           abcdeˇfg
+           "#
+    ));
+
+    cx.update_editor(|editor, cx| {
+        editor.add_selection_above(&AddSelectionAbove { count: 2 }, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdeˇfg
+           ab
+
+           abcdeˇfg
+           "#
+    ));
+
+    cx.update_editor(|editor, cx| {
+        editor.add_selection_below(&AddSelectionBelow { count: 2 }, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdefg
+           ab
+
+           abcdeˇfg
+           "#
+    ));
+}
+
+#[gpui::test]
+async fn test_add_selection_above_below_clamped(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.multi_cursor_short_lines = Some(MultiCursorShortLines::Clamp);
+            });
+        });
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc!(
+        r#"abcdefg
+           ab
+
+           abcdeˇfg
+           "#
+    ));
+
+    // Short lines get a cursor at their end.
+    cx.update_editor(|editor, cx| {
+        editor.add_selection_above(&AddSelectionAbove { count: 2 }, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdefg
+           abˇ
+           ˇ
+           abcdeˇfg
+           "#
+    ));
+
+    // Cursors added after them go back to the original column.
+    cx.update_editor(|editor, cx| {
+        editor.add_selection_above(&Default::default(), cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdeˇfg
+           abˇ
+           ˇ
+           abcdeˇfg
+           "#
+    ));
+
+    cx.update_editor(|editor, cx| {
+        editor.add_selection_below(&AddSelectionBelow { count: 2 }, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdefg
+           ab
+           ˇ
+           abcdeˇfg
+           "#
+    ));
+}
+
+#[gpui::test]
+async fn test_add_cursors_to_selected_lines(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc!(
+        r#"a«bcdefg
+           ab
+
+           abcdeˇ»fg
+           "#
+    ));
+    cx.update_editor(|editor, cx| {
+        editor.add_cursors_to_selected_lines(&AddCursorsToSelectedLines, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abcdeˇfg
+           ab
+
+           abcdeˇfg
+           "#
+    ));
+
+    cx.set_state(indoc!(
+        r#"ab«ˇcdefg
+           abcdefg
+           »abcdefg
+           "#
+    ));
+    cx.update_editor(|editor, cx| {
+        editor.add_cursors_to_selected_lines(&AddCursorsToSelectedLines, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"abˇcdefg
+           abˇcdefg
+           abcdefg
+           "#
+    ));
+}
+
+#[gpui::test]
+async fn test_split_selection_into_line_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc!(
+        r#"a«bc
+           defg
+           hiˇ»j
+           "#
+    ));
+    cx.update_editor(|editor, cx| {
+        editor.split_selection_into_line_ranges(&SplitSelectionIntoLineRanges, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"a«bcˇ»
+           «defgˇ»
+           «hiˇ»j
+           "#
+    ));
+
+    cx.set_state(indoc!(
+        r#"a«ˇbc
+           defg
+           »hij
+           "#
+    ));
+    cx.update_editor(|editor, cx| {
+        editor.split_selection_into_line_ranges(&SplitSelectionIntoLineRanges, cx);
+    });
+    cx.assert_editor_state(indoc!(
+        r#"a«ˇbc»
+           «ˇdefg»
+           hij
+           "#
+    ));
+}
+
 #[gpui::test]
 async fn test_select_next(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -4871,6 +5138,35 @@ async fn test_select_next(cx: &mut gpui::TestAppContext) {
     cx.assert_editor_state("«abcˇ»\n«abcˇ» «abcˇ»\ndefabc\n«abcˇ»");
 }
 
+#[gpui::test]
+async fn test_select_next_with_word_characters(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.word_characters =
+            Some(vec![WordCharacter::Char('-'), WordCharacter::Char('$')]);
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇ$foo-bar foo $foo-bar");
+
+    cx.update_editor(|e, cx| e.select_next(&SelectNext::default(), cx))
+        .unwrap();
+    cx.assert_editor_state("«$foo-barˇ» foo $foo-bar");
+
+    cx.update_editor(|e, cx| e.select_next(&SelectNext::default(), cx))
+        .unwrap();
+    cx.assert_editor_state("«$foo-barˇ» foo «$foo-barˇ»");
+}
+
+#[gpui::test]
+async fn test_select_subword(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("let fooBˇarBaz = foo_ˇbar;");
+    cx.update_editor(|e, cx| e.select_subword(&SelectSubword, cx));
+    cx.assert_editor_state("let foo«Barˇ»Baz = foo_«barˇ»;");
+}
+
 #[gpui::test]
 async fn test_select_all_matches(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -8067,6 +8363,44 @@ async fn test_completion(cx: &mut gpui::TestAppContext) {
     apply_additional_edits.await.unwrap();
 }
 
+#[gpui::test]
+fn test_word_completions(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.new_model(|cx| {
+        Buffer::local(
+            "let counter = 1;\nlet count_all = counter + Counted;\ncou",
+            cx,
+        )
+    });
+    let other_buffer = cx.new_model(|cx| Buffer::local("counting country counting counter", cx));
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let other_snapshot = other_buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let position = snapshot.anchor_after(snapshot.len());
+
+    // Words closer to the cursor, or used more often near it, come first, followed by the
+    // words used most often in other buffers.
+    let completions = word_completions::word_completions(
+        &snapshot,
+        position,
+        &[snapshot.clone(), other_snapshot],
+    );
+    assert_eq!(
+        completions
+            .iter()
+            .map(|completion| completion.new_text.as_str())
+            .collect::<Vec<_>>(),
+        ["counter", "Counted", "count_all", "counting", "country"]
+    );
+    assert!(completions.iter().all(|completion| {
+        completion.old_range.start == snapshot.anchor_before(snapshot.len() - 3)
+            && word_completions::is_word_completion(completion)
+    }));
+
+    let position = snapshot.anchor_after(Point::new(0, 4));
+    assert!(word_completions::word_completions(&snapshot, position, &[]).is_empty());
+}
+
 #[gpui::test]
 async fn test_completion_page_up_down_keys(cx: &mut gpui::TestAppContext) {
     init_test(cx, |_| {});
@@ -13762,6 +14096,120 @@ async fn test_goto_definition_with_find_all_references_fallback(cx: &mut gpui::T
     });
 }
 
+#[gpui::test]
+async fn test_working_set(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/a",
+        json!({
+            "one.txt": "alpha\nbeta\ngamma\ndelta\n",
+            "two.txt": "first\nsecond\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, ["/a".as_ref()], cx).await;
+    let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let open_editor = |path: &'static str, cx: &mut VisualTestContext| {
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path((worktree_id, path), None, true, cx)
+            })
+            .unwrap()
+    };
+    let location_texts = |working_set: &View<WorkingSet>, cx: &mut VisualTestContext| {
+        working_set.update(cx, |working_set, cx| {
+            working_set
+                .locations()
+                .iter()
+                .map(|(buffer, range)| {
+                    buffer
+                        .read(cx)
+                        .text_for_range(range.clone())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Empty selections add their line.
+    let one = open_editor("one.txt", cx)
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    one.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |selections| {
+            selections.select_ranges([
+                Point::new(3, 1)..Point::new(3, 3),
+                Point::new(1, 0)..Point::new(1, 0),
+            ])
+        });
+    });
+    cx.dispatch_action(AddToWorkingSet::default());
+    let working_set = workspace
+        .update(cx, |workspace, cx| {
+            workspace.items_of_type::<WorkingSet>(cx).next().unwrap()
+        })
+        .unwrap();
+    assert_eq!(location_texts(&working_set, cx), ["beta", "el"]);
+
+    // The same locations aren't added twice.
+    cx.dispatch_action(AddToWorkingSet::default());
+    let two = open_editor("two.txt", cx)
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    two.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |selections| {
+            selections.select_ranges([Point::new(1, 0)..Point::new(1, 3)])
+        });
+    });
+    cx.dispatch_action(AddToWorkingSet::default());
+    assert_eq!(location_texts(&working_set, cx), ["beta", "el", "sec"]);
+    assert_eq!(
+        workspace
+            .update(cx, |workspace, cx| workspace
+                .items_of_type::<WorkingSet>(cx)
+                .count())
+            .unwrap(),
+        1
+    );
+
+    // Locations follow the edits made to their buffers.
+    one.update(cx, |editor, cx| {
+        editor.buffer().update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(0, 0)..Point::new(0, 0), "zero\n")], None, cx)
+        });
+    });
+    assert_eq!(location_texts(&working_set, cx), ["beta", "el", "sec"]);
+    let first_row = working_set.update(cx, |working_set, cx| {
+        let (buffer, range) = &working_set.locations()[0];
+        buffer
+            .read(cx)
+            .summary_for_anchor::<Point>(&range.start)
+            .row
+    });
+    assert_eq!(first_row, 2);
+
+    // Locations are removed when the cursor is in them.
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.activate_item(&working_set, true, true, cx)
+        })
+        .unwrap();
+    cx.dispatch_action(MoveToEnd);
+    cx.dispatch_action(MoveUp);
+    cx.dispatch_action(RemoveFromWorkingSet);
+    assert_eq!(location_texts(&working_set, cx), ["beta", "el"]);
+}
+
 fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     let point = DisplayPoint::new(DisplayRow(row as u32), column as u32);
     point..point
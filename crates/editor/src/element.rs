@@ -34,7 +34,7 @@ use gpui::{
     anchored, deferred, div, fill, outline, point, px, quad, relative, size, svg,
     transparent_black, Action, AnchorCorner, AnyElement, AvailableSpace, Bounds, ClipboardItem,
     ContentMask, Corners, CursorStyle, DispatchPhase, Edges, Element, ElementInputHandler, Entity,
-    EntityId, FontId, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Length,
+    EntityId, Font, FontId, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Length,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
     ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
     StatefulInteractiveElement, Style, Styled, TextRun, TextStyle, TextStyleRefinement, View,
@@ -213,6 +213,7 @@ impl EditorElement {
         register_action(view, cx, Editor::cut_to_end_of_line);
         register_action(view, cx, Editor::duplicate_line_up);
         register_action(view, cx, Editor::duplicate_line_down);
+        register_action(view, cx, Editor::duplicate_selection);
         register_action(view, cx, Editor::move_line_up);
         register_action(view, cx, Editor::move_line_down);
         register_action(view, cx, Editor::transpose);
@@ -282,9 +283,12 @@ impl EditorElement {
             editor.select_all_matches(action, cx).log_err();
         });
         register_action(view, cx, Editor::select_line);
+        register_action(view, cx, Editor::select_subword);
         register_action(view, cx, Editor::split_selection_into_lines);
+        register_action(view, cx, Editor::split_selection_into_line_ranges);
         register_action(view, cx, Editor::add_selection_above);
         register_action(view, cx, Editor::add_selection_below);
+        register_action(view, cx, Editor::add_cursors_to_selected_lines);
         register_action(view, cx, |editor, action, cx| {
             editor.select_next(action, cx).log_err();
         });
@@ -1922,6 +1926,54 @@ impl EditorElement {
         }
     }
 
+    /// Lays out the visible lines for a frame following a keystroke that only
+    /// changed its own line, reusing the previous frame's layouts for the other
+    /// lines. Returns `None` when those layouts can't be reused.
+    #[allow(clippy::too_many_arguments)]
+    fn echo_typed_line(
+        typing_echo: TypingEcho,
+        cached_line_layouts: CachedLineLayouts,
+        rows: Range<DisplayRow>,
+        buffer_rows: &[Option<MultiBufferRow>],
+        line_number_layouts: &[Option<ShapedLine>],
+        snapshot: &EditorSnapshot,
+        style: &EditorStyle,
+        editor_width: Pixels,
+        cx: &mut WindowContext,
+    ) -> Option<Vec<LineWithInvisibles>> {
+        if snapshot.is_empty()
+            || snapshot.buffer_snapshot.edit_count() != typing_echo.edit_count
+            || !cached_line_layouts.is_valid(rows.clone(), buffer_rows, style, editor_width, cx)
+        {
+            return None;
+        }
+
+        let row = typing_echo
+            .position
+            .to_display_point(&snapshot.display_snapshot)
+            .row();
+        if !rows.contains(&row) {
+            return None;
+        }
+        // Soft-wrapped lines may wrap differently after the edit.
+        let ix = (row.0 - rows.start.0) as usize;
+        if buffer_rows[ix].is_none() || matches!(buffer_rows.get(ix + 1), Some(None)) {
+            return None;
+        }
+
+        let mut line_layouts = cached_line_layouts.lines;
+        line_layouts[ix] = Self::layout_lines(
+            row..row.next_row(),
+            &line_number_layouts[ix..ix + 1],
+            snapshot,
+            style,
+            editor_width,
+            cx,
+        )
+        .pop()?;
+        Some(line_layouts)
+    }
+
     fn prepaint_lines(
         &self,
         start_row: DisplayRow,
@@ -4327,6 +4379,62 @@ pub(crate) struct LineWithInvisibles {
     font_size: Pixels,
 }
 
+/// The line layouts of the last frame, kept so that a keystroke that only
+/// changes one line can be painted without laying out the other lines again.
+pub(crate) struct CachedLineLayouts {
+    rows: Range<DisplayRow>,
+    buffer_rows: Vec<Option<MultiBufferRow>>,
+    font: Font,
+    font_size: Pixels,
+    editor_width: Pixels,
+    lines: Vec<LineWithInvisibles>,
+}
+
+impl CachedLineLayouts {
+    fn new(
+        rows: Range<DisplayRow>,
+        buffer_rows: Vec<Option<MultiBufferRow>>,
+        lines: &[LineWithInvisibles],
+        style: &EditorStyle,
+        editor_width: Pixels,
+        cx: &WindowContext,
+    ) -> Option<Self> {
+        Some(Self {
+            rows,
+            buffer_rows,
+            font: style.text.font(),
+            font_size: style.text.font_size.to_pixels(cx.rem_size()),
+            editor_width,
+            lines: lines
+                .iter()
+                .map(LineWithInvisibles::clone_text)
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    fn is_valid(
+        &self,
+        rows: Range<DisplayRow>,
+        buffer_rows: &[Option<MultiBufferRow>],
+        style: &EditorStyle,
+        editor_width: Pixels,
+        cx: &WindowContext,
+    ) -> bool {
+        self.rows == rows
+            && self.buffer_rows == buffer_rows
+            && self.font == style.text.font()
+            && self.font_size == style.text.font_size.to_pixels(cx.rem_size())
+            && self.editor_width == editor_width
+    }
+}
+
+/// A keystroke that only changed the line it was typed on, along with the
+/// multibuffer's edit count right after it.
+pub(crate) struct TypingEcho {
+    pub position: Anchor,
+    pub edit_count: usize,
+}
+
 #[allow(clippy::large_enum_variant)]
 enum LineFragment {
     Text(ShapedLine),
@@ -4351,6 +4459,26 @@ impl fmt::Debug for LineFragment {
 }
 
 impl LineWithInvisibles {
+    /// Clones a line that only contains text, as elements can't be reused
+    /// across frames.
+    fn clone_text(&self) -> Option<Self> {
+        let fragments = self
+            .fragments
+            .iter()
+            .map(|fragment| match fragment {
+                LineFragment::Text(line) => Some(LineFragment::Text(line.clone())),
+                LineFragment::Element { .. } => None,
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            fragments,
+            invisibles: self.invisibles.clone(),
+            len: self.len,
+            width: self.width,
+            font_size: self.font_size,
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn from_chunks<'a>(
         chunks: impl Iterator<Item = HighlightedChunk<'a>>,
@@ -5161,15 +5289,55 @@ impl Element for EditorElement {
                         cx,
                     );
 
-                    let mut max_visible_line_width = Pixels::ZERO;
-                    let mut line_layouts = Self::layout_lines(
-                        start_row..end_row,
-                        &line_numbers,
-                        &snapshot,
-                        &self.style,
-                        editor_width,
-                        cx,
+                    let (typing_echo, cached_line_layouts) = self.editor.update(cx, |editor, _| {
+                        (editor.typing_echo.take(), editor.cached_line_layouts.take())
+                    });
+                    let echoed_line_layouts = typing_echo.zip(cached_line_layouts).and_then(
+                        |(typing_echo, cached_line_layouts)| {
+                            Self::echo_typed_line(
+                                typing_echo,
+                                cached_line_layouts,
+                                start_row..end_row,
+                                &buffer_rows,
+                                &line_numbers,
+                                &snapshot,
+                                &self.style,
+                                editor_width,
+                                cx,
+                            )
+                        },
                     );
+                    let mut line_layouts = match echoed_line_layouts {
+                        Some(line_layouts) => {
+                            // Highlights of the other lines may be stale, so lay
+                            // everything out again in the next frame.
+                            cx.request_animation_frame();
+                            line_layouts
+                        }
+                        None => Self::layout_lines(
+                            start_row..end_row,
+                            &line_numbers,
+                            &snapshot,
+                            &self.style,
+                            editor_width,
+                            cx,
+                        ),
+                    };
+                    if focus_handle.is_focused(cx) && !snapshot.is_empty() {
+                        let cached_line_layouts = CachedLineLayouts::new(
+                            start_row..end_row,
+                            buffer_rows.clone(),
+                            &line_layouts,
+                            &self.style,
+                            editor_width,
+                            cx,
+                        );
+                        self.editor.update(cx, |editor, _| {
+                            editor.cached_line_layouts = cached_line_layouts;
+                        });
+                    }
+
+                    let mut max_visible_line_width = Pixels::ZERO;
                     for line_with_invisibles in &line_layouts {
                         if line_with_invisibles.width > max_visible_line_width {
                             max_visible_line_width = line_with_invisibles.width;
@@ -6609,6 +6777,79 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_typing_echo(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+
+        let window = cx.add_window(|cx| {
+            let buffer = MultiBuffer::build_simple("one\ntwo\nthree", cx);
+            Editor::new(EditorMode::Full, buffer, None, true, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        window
+            .update(cx, |editor, cx| {
+                cx.focus_self();
+                editor.change_selections(None, cx, |s| {
+                    s.select_ranges([Point::new(1, 3)..Point::new(1, 3)])
+                });
+            })
+            .unwrap();
+
+        let line_texts = |state: &EditorLayout| {
+            state
+                .position_map
+                .line_layouts
+                .iter()
+                .map(|line| {
+                    line.fragments
+                        .iter()
+                        .map(|fragment| match fragment {
+                            LineFragment::Text(line) => line.text.to_string(),
+                            LineFragment::Element { .. } => String::new(),
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style.clone())
+        });
+        assert_eq!(line_texts(&state), ["one", "two", "three"]);
+
+        // Typing within a line echoes it from the previous frame's layouts.
+        window
+            .update(cx, |editor, cx| {
+                editor.handle_input("s", cx);
+                assert!(editor.typing_echo.is_some());
+            })
+            .unwrap();
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style.clone())
+        });
+        assert_eq!(line_texts(&state), ["one", "twos", "three"]);
+        window
+            .update(cx, |editor, _| {
+                assert!(editor.typing_echo.is_none());
+                assert!(editor.cached_line_layouts.is_some());
+            })
+            .unwrap();
+
+        // Inserting a newline changes the other lines, so they are laid out again.
+        window
+            .update(cx, |editor, cx| {
+                editor.handle_input("\n", cx);
+                assert!(editor.typing_echo.is_none());
+            })
+            .unwrap();
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style)
+        });
+        assert_eq!(line_texts(&state), ["one", "twos", "", "three"]);
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
@@ -107,6 +107,7 @@ pub struct GitBlame {
     focused: bool,
     generated: bool,
     changed_while_blurred: bool,
+    changed_while_conserving_power: bool,
     user_triggered: bool,
     regenerate_on_edit_task: Task<Result<()>>,
     _regenerate_subscriptions: Vec<Subscription>,
@@ -151,17 +152,24 @@ impl GitBlame {
                         .any(|(_, entry_id, _)| project_entry_id == Some(*entry_id))
                     {
                         log::debug!("Updated buffers. Regenerating blame data...",);
-                        this.generate(cx);
+                        this.regenerate_on_disk_change(cx);
                     }
                 }
                 project::Event::WorktreeUpdatedGitRepositories => {
                     log::debug!("Status of git repositories updated. Regenerating blame data...",);
-                    this.generate(cx);
+                    this.regenerate_on_disk_change(cx);
                 }
                 _ => {}
             }
         });
 
+        let power_state_subscription = cx.observe_power_state(|this, power_state, cx| {
+            if !power_state.should_conserve_power() && this.changed_while_conserving_power {
+                this.changed_while_conserving_power = false;
+                this.generate(cx);
+            }
+        });
+
         let buffer_snapshot = buffer.read(cx).snapshot();
         let buffer_edits = buffer.update(cx, |buffer, _| buffer.subscribe());
 
@@ -174,11 +182,16 @@ impl GitBlame {
             user_triggered,
             focused,
             changed_while_blurred: false,
+            changed_while_conserving_power: false,
             commit_details: HashMap::default(),
             task: Task::ready(Ok(())),
             generated: false,
             regenerate_on_edit_task: Task::ready(Ok(())),
-            _regenerate_subscriptions: vec![buffer_subscriptions, project_subscription],
+            _regenerate_subscriptions: vec![
+                buffer_subscriptions,
+                project_subscription,
+                power_state_subscription,
+            ],
         };
         this.generate(cx);
         this
@@ -330,6 +343,16 @@ impl GitBlame {
         );
     }
 
+    /// Files and repositories can change on disk often, e.g. while switching branches, so
+    /// regenerating the blame for those changes waits until the machine is plugged in.
+    fn regenerate_on_disk_change(&mut self, cx: &mut ModelContext<Self>) {
+        if cx.power_state().should_conserve_power() {
+            self.changed_while_conserving_power = true;
+        } else {
+            self.generate(cx);
+        }
+    }
+
     fn generate(&mut self, cx: &mut ModelContext<Self>) {
         if !self.focused {
             self.changed_while_blurred = true;
@@ -351,6 +351,7 @@ fn show_hover(
                                     thickness: px(1.),
                                     color: Some(cx.theme().colors().editor_foreground),
                                     wavy: false,
+                                    ..Default::default()
                                 }),
                                 ..Default::default()
                             },
@@ -548,6 +549,7 @@ async fn parse_blocks(
                         thickness: px(1.),
                         color: Some(cx.theme().colors().editor_foreground),
                         wavy: false,
+                        ..Default::default()
                     }),
                     ..Default::default()
                 },
@@ -25,7 +25,7 @@ use project::{
 };
 use rpc::proto::{self, update_view, PeerId};
 use settings::Settings;
-use workspace::item::{Dedup, ItemSettings, SerializableItem, TabContentParams};
+use workspace::item::{Dedup, ItemSettings, SerializableItem, TabContentParams, TabPathDisplay};
 
 use std::{
     any::TypeId,
@@ -622,32 +622,37 @@ impl Item for Editor {
             entry_label_color(params.selected)
         };
 
-        let description = params.detail.and_then(|detail| {
-            let path = path_for_buffer(&self.buffer, detail, false, cx)?;
-            let description = path.to_string_lossy();
-            let description = description.trim();
-
-            if description.is_empty() {
-                return None;
+        let settings = ItemSettings::get_global(cx);
+        let description = params
+            .detail
+            .and_then(|detail| tab_directory(&self.buffer, detail, settings.path_display, cx));
+        let title = self.title(cx);
+        let title = match settings.max_title_length {
+            Some(max_title_length) => {
+                util::truncate_and_remove_middle(&title, max_title_length.max(5))
             }
-
-            Some(util::truncate_and_trailoff(description, MAX_TAB_TITLE_LEN))
-        });
+            None => title.to_string(),
+        };
+        let directory_label = |description: String| {
+            Label::new(description)
+                .size(LabelSize::XSmall)
+                .color(Color::Muted)
+        };
 
         h_flex()
             .gap_2()
-            .child(
-                Label::new(self.title(cx).to_string())
-                    .color(label_color)
-                    .italic(params.preview),
+            .children(
+                description
+                    .clone()
+                    .filter(|_| settings.directory_first)
+                    .map(directory_label),
+            )
+            .child(Label::new(title).color(label_color).italic(params.preview))
+            .children(
+                description
+                    .filter(|_| !settings.directory_first)
+                    .map(directory_label),
             )
-            .when_some(description, |this, description| {
-                this.child(
-                    Label::new(description)
-                        .size(LabelSize::XSmall)
-                        .color(Color::Muted),
-                )
-            })
             .into_any_element()
     }
 
@@ -1499,6 +1504,48 @@ pub fn entry_git_aware_label_color(
     }
 }
 
+/// The directory shown next to the name of the buffer's file in its tab, with `detail` being the
+/// number of directories needed to tell it apart from the other tabs.
+fn tab_directory(
+    buffer: &Model<MultiBuffer>,
+    detail: usize,
+    path_display: TabPathDisplay,
+    cx: &AppContext,
+) -> Option<String> {
+    let directory = match path_display {
+        TabPathDisplay::Minimal => {
+            minimal_tab_directory(&path_for_buffer(buffer, detail, false, cx)?)
+        }
+        TabPathDisplay::Full => {
+            let file = buffer.read(cx).as_singleton()?.read(cx).file()?;
+            file.path().parent()?.to_string_lossy().into_owned()
+        }
+    };
+
+    let directory = directory.trim();
+    if directory.is_empty() {
+        None
+    } else {
+        Some(util::truncate_and_remove_middle(
+            directory,
+            MAX_TAB_TITLE_LEN,
+        ))
+    }
+}
+
+/// The outermost directory of the path is the one telling a file apart from the others with the
+/// same name, so the ones within it are elided.
+fn minimal_tab_directory(path: &Path) -> String {
+    let mut components = path.components();
+    match (components.next(), components.next()) {
+        (Some(outermost), Some(_)) => Path::new(outermost.as_os_str())
+            .join("…")
+            .to_string_lossy()
+            .into_owned(),
+        _ => path.to_string_lossy().into_owned(),
+    }
+}
+
 fn path_for_buffer<'a>(
     buffer: &Model<MultiBuffer>,
     height: usize,
@@ -1569,6 +1616,16 @@ mod tests {
         assert_eq!(path_for_file(&file, 0, false, cx), None);
     }
 
+    #[test]
+    fn test_minimal_tab_directory() {
+        assert_eq!(minimal_tab_directory(Path::new("")), "");
+        assert_eq!(minimal_tab_directory(Path::new("src")), "src");
+        assert_eq!(
+            minimal_tab_directory(Path::new("crates/editor/src")),
+            Path::new("crates/…").to_string_lossy()
+        );
+    }
+
     async fn deserialize_editor(
         item_id: ItemId,
         workspace_id: WorkspaceId,
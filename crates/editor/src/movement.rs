@@ -4,7 +4,7 @@
 use super::{Bias, DisplayPoint, DisplaySnapshot, SelectionGoal, ToDisplayPoint};
 use crate::{scroll::ScrollAnchor, CharKind, DisplayRow, EditorStyle, RowExt, ToOffset, ToPoint};
 use gpui::{px, Pixels, WindowTextSystem};
-use language::Point;
+use language::{language_settings::WordCharacter, CharClassifier, Point};
 use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
 use serde::Deserialize;
 
@@ -554,11 +554,27 @@ pub(crate) fn is_inside_word(map: &DisplaySnapshot, point: DisplayPoint) -> bool
 pub(crate) fn surrounding_word(
     map: &DisplaySnapshot,
     position: DisplayPoint,
+) -> Range<DisplayPoint> {
+    surrounding_word_with_characters(map, position, Vec::new())
+}
+
+/// Returns the range of the word surrounding the given position, treating the given
+/// characters as word characters on top of the language's own.
+pub(crate) fn surrounding_word_with_characters(
+    map: &DisplaySnapshot,
+    position: DisplayPoint,
+    word_characters: Vec<WordCharacter>,
 ) -> Range<DisplayPoint> {
     let position = map
         .clip_point(position, Bias::Left)
         .to_offset(map, Bias::Left);
-    let (range, _) = map.buffer_snapshot.surrounding_word(position, false);
+    let classifier = map
+        .buffer_snapshot
+        .char_classifier_at(position)
+        .extra_word_characters(word_characters);
+    let (range, _) = map
+        .buffer_snapshot
+        .surrounding_word_with_classifier(position, &classifier);
     let start = range
         .start
         .to_point(&map.buffer_snapshot)
@@ -570,6 +586,50 @@ pub(crate) fn surrounding_word(
     start..end
 }
 
+/// Returns the range of the subword surrounding the given position, where subwords are
+/// delimited within a word as in [`previous_subword_start`] and [`next_subword_end`].
+pub fn surrounding_subword(map: &DisplaySnapshot, position: DisplayPoint) -> Range<DisplayPoint> {
+    let word = surrounding_word(map, position);
+    let offset = map
+        .clip_point(position, Bias::Left)
+        .to_offset(map, Bias::Left);
+    let word_start = word.start.to_offset(map, Bias::Left);
+    let word_end = word.end.to_offset(map, Bias::Left);
+    let classifier = map.buffer_snapshot.char_classifier_at(offset);
+
+    let mut start = word_start;
+    let mut end = word_end;
+    let mut ix = word_start;
+    let mut prev_char = None;
+    for c in map.buffer_snapshot.chars_at(word_start) {
+        if ix >= word_end {
+            break;
+        }
+        if prev_char.map_or(false, |prev_char| {
+            is_subword_boundary(&classifier, prev_char, c)
+        }) {
+            if ix <= offset {
+                start = ix;
+            } else {
+                end = ix;
+                break;
+            }
+        }
+        prev_char = Some(c);
+        ix += c.len_utf8();
+    }
+
+    let start = start.to_point(&map.buffer_snapshot).to_display_point(map);
+    let end = end.to_point(&map.buffer_snapshot).to_display_point(map);
+    start..end
+}
+
+fn is_subword_boundary(classifier: &CharClassifier, left: char, right: char) -> bool {
+    classifier.is_word('-') && (left == '-') != (right == '-')
+        || (left == '_') != (right == '_')
+        || left.is_lowercase() && right.is_uppercase()
+}
+
 /// Returns a list of lines (represented as a [`DisplayPoint`] range) contained
 /// within a passed range.
 ///
@@ -911,6 +971,27 @@ mod tests {
         assert("ˇloremˇˇ, ipsum", cx);
     }
 
+    #[gpui::test]
+    fn test_surrounding_subword(cx: &mut gpui::AppContext) {
+        init_test(cx);
+
+        fn assert(marked_text: &str, cx: &mut gpui::AppContext) {
+            let (snapshot, display_points) = marked_display_snapshot(marked_text, cx);
+            assert_eq!(
+                surrounding_subword(&snapshot, display_points[1]),
+                display_points[0]..display_points[2],
+                "{}",
+                marked_text
+            );
+        }
+
+        assert("ˇloˇremˇIpsum", cx);
+        assert("loremˇIpˇsumˇ", cx);
+        assert("loremˇIpsumˇˇ dolor", cx);
+        assert("lorem_ˇipˇsumˇ dolor", cx);
+        assert("loremˇˇ_ˇipsum", cx);
+    }
+
     #[gpui::test]
     async fn test_move_up_and_down_with_excerpts(cx: &mut gpui::TestAppContext) {
         cx.update(|cx| {
@@ -89,6 +89,13 @@ define_connection!(
     //   mtime_seconds: Option<i64>,
     //   mtime_nanos: Option<i32>,
     // )
+    //
+    // working_sets(
+    //   item_id: usize,
+    //   workspace_id: usize,
+    //   name: String,
+    //   locations: String, // JSON-encoded list of paths and point ranges
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> =
         &[sql! (
             CREATE TABLE editors(
@@ -136,6 +143,18 @@ define_connection!(
             ALTER TABLE editors ADD COLUMN mtime_seconds INTEGER DEFAULT NULL;
             ALTER TABLE editors ADD COLUMN mtime_nanos INTEGER DEFAULT NULL;
         ),
+        sql! (
+            CREATE TABLE working_sets(
+                item_id INTEGER NOT NULL,
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                locations TEXT NOT NULL,
+                PRIMARY KEY(item_id, workspace_id),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
         ];
 );
 
@@ -190,10 +209,48 @@ impl EditorDb {
         }
     }
 
+    query! {
+        pub fn get_working_set(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<(String, String)>> {
+            SELECT name, locations FROM working_sets
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+
+    query! {
+        pub async fn save_working_set(item_id: ItemId, workspace_id: WorkspaceId, name: String, locations: String) -> Result<()> {
+            INSERT INTO working_sets
+                (item_id, workspace_id, name, locations)
+            VALUES
+                (?1, ?2, ?3, ?4)
+            ON CONFLICT DO UPDATE SET
+                name = ?3,
+                locations = ?4
+        }
+    }
+
     pub async fn delete_unloaded_items(
         &self,
         workspace: WorkspaceId,
         alive_items: Vec<ItemId>,
+    ) -> Result<()> {
+        self.delete_unloaded_rows("editors", workspace, alive_items)
+            .await
+    }
+
+    pub async fn delete_unloaded_working_sets(
+        &self,
+        workspace: WorkspaceId,
+        alive_items: Vec<ItemId>,
+    ) -> Result<()> {
+        self.delete_unloaded_rows("working_sets", workspace, alive_items)
+            .await
+    }
+
+    async fn delete_unloaded_rows(
+        &self,
+        table: &'static str,
+        workspace: WorkspaceId,
+        alive_items: Vec<ItemId>,
     ) -> Result<()> {
         let placeholders = alive_items
             .iter()
@@ -202,7 +259,7 @@ impl EditorDb {
             .join(", ");
 
         let query = format!(
-            "DELETE FROM editors WHERE workspace_id = ? AND item_id NOT IN ({placeholders})"
+            "DELETE FROM {table} WHERE workspace_id = ? AND item_id NOT IN ({placeholders})"
         );
 
         self.write(move |conn| {
@@ -297,4 +354,30 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_get_working_set() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        DB.save_working_set(1234, workspace_id, "Refactor".into(), "[]".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_working_set(1234, workspace_id).unwrap(),
+            Some(("Refactor".to_string(), "[]".to_string()))
+        );
+
+        DB.save_working_set(1234, workspace_id, "Renamed".into(), "[1]".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_working_set(1234, workspace_id).unwrap(),
+            Some(("Renamed".to_string(), "[1]".to_string()))
+        );
+
+        DB.delete_unloaded_working_sets(workspace_id, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_working_set(1234, workspace_id).unwrap(), None);
+    }
 }
@@ -334,6 +334,7 @@ impl SelectionsCollection {
         row: DisplayRow,
         positions: &Range<Pixels>,
         reversed: bool,
+        clamp_to_line_end: bool,
         text_layout_details: &TextLayoutDetails,
     ) -> Option<Selection<Point>> {
         let is_empty = positions.start == positions.end;
@@ -342,7 +343,8 @@ impl SelectionsCollection {
         let line = display_map.layout_row(row, text_layout_details);
 
         let start_col = line.closest_index_for_x(positions.start) as u32;
-        if start_col < line_len || (is_empty && positions.start == line.width) {
+        let reaches_column = start_col < line_len || (is_empty && positions.start == line.width);
+        if reaches_column || clamp_to_line_end {
             let start = DisplayPoint::new(row, start_col);
             let end_col = line.closest_index_for_x(positions.end) as u32;
             let end = DisplayPoint::new(row, end_col);
@@ -0,0 +1,154 @@
+use std::{cmp::Reverse, iter, ops::Range};
+
+use collections::HashMap;
+use language::{BufferSnapshot, CharClassifier, CodeLabel};
+use lsp::{CompletionItemKind, LanguageServerId};
+use project::Completion;
+use text::{Bias, ToOffset};
+
+/// The most words offered in the completions menu.
+const MAX_WORD_COMPLETIONS: usize = 100;
+
+/// How much text around the cursor, and at the start of other buffers, is searched for words,
+/// so that completing in large files stays fast.
+const MAX_SCANNED_LEN: usize = 512 * 1024;
+
+/// Completions are attributed to this server id when they don't come from a language server.
+const NO_LANGUAGE_SERVER: LanguageServerId = LanguageServerId(usize::MAX);
+
+#[derive(Default)]
+struct WordOccurrences {
+    count: usize,
+    /// The distance to the cursor of the closest occurrence in the buffer being edited.
+    distance: Option<usize>,
+}
+
+/// Completions for the words in the buffer being edited and the other open buffers that start
+/// with the word before the cursor. Words used close to the cursor are offered first, followed
+/// by the ones used most often elsewhere.
+pub(crate) fn word_completions(
+    snapshot: &BufferSnapshot,
+    buffer_position: text::Anchor,
+    other_buffers: &[BufferSnapshot],
+) -> Vec<Completion> {
+    let classifier = snapshot
+        .char_classifier_at(buffer_position)
+        .for_completion(true);
+    let position = buffer_position.to_offset(snapshot);
+    let prefix_len = snapshot
+        .reversed_chars_at(position)
+        .take_while(|c| classifier.is_word(*c))
+        .map(char::len_utf8)
+        .sum::<usize>();
+    if prefix_len == 0 {
+        return Vec::new();
+    }
+    let prefix_start = position - prefix_len;
+    let prefix = snapshot
+        .text_for_range(prefix_start..position)
+        .collect::<String>()
+        .to_lowercase();
+
+    let mut words = HashMap::<String, WordOccurrences>::default();
+    let scan_start = snapshot.clip_offset(position.saturating_sub(MAX_SCANNED_LEN / 2), Bias::Left);
+    let scan_end = snapshot.clip_offset(
+        (position + MAX_SCANNED_LEN / 2).min(snapshot.len()),
+        Bias::Right,
+    );
+    let text = snapshot
+        .text_for_range(scan_start..scan_end)
+        .collect::<String>();
+    for (range, word) in words_in(&text, &classifier) {
+        let start = scan_start + range.start;
+        // Skip the word being completed.
+        if start == prefix_start || !is_completion_of(word, &prefix) {
+            continue;
+        }
+        let distance = if start < position {
+            position - (scan_start + range.end)
+        } else {
+            start - position
+        };
+        let occurrences = words.entry(word.to_string()).or_default();
+        occurrences.count += 1;
+        occurrences.distance = Some(
+            occurrences
+                .distance
+                .map_or(distance, |closest| closest.min(distance)),
+        );
+    }
+
+    for buffer in other_buffers {
+        if buffer.remote_id() == snapshot.remote_id() {
+            continue;
+        }
+        let scan_end = buffer.clip_offset(buffer.len().min(MAX_SCANNED_LEN), Bias::Right);
+        let text = buffer.text_for_range(0..scan_end).collect::<String>();
+        let classifier = buffer.char_classifier_at(0).for_completion(true);
+        for (_, word) in words_in(&text, &classifier) {
+            if is_completion_of(word, &prefix) {
+                words.entry(word.to_string()).or_default().count += 1;
+            }
+        }
+    }
+
+    let mut words = words.into_iter().collect::<Vec<_>>();
+    words.sort_unstable_by(|(word_a, a), (word_b, b)| {
+        let rank = |occurrences: &WordOccurrences| {
+            (
+                occurrences.distance.is_none(),
+                occurrences
+                    .distance
+                    .map_or(0, |distance| distance / occurrences.count),
+                Reverse(occurrences.count),
+            )
+        };
+        rank(a).cmp(&rank(b)).then_with(|| word_a.cmp(word_b))
+    });
+
+    let old_range = snapshot.anchor_before(prefix_start)..buffer_position;
+    words
+        .into_iter()
+        .take(MAX_WORD_COMPLETIONS)
+        .enumerate()
+        .map(|(ix, (word, _))| Completion {
+            old_range: old_range.clone(),
+            new_text: word.clone(),
+            label: CodeLabel::plain(word.clone(), None),
+            server_id: NO_LANGUAGE_SERVER,
+            documentation: None,
+            lsp_completion: lsp::CompletionItem {
+                label: word,
+                kind: Some(CompletionItemKind::TEXT),
+                // Offered after the completions of language servers and snippets.
+                sort_text: Some(format!("{}{ix:05}", char::MAX)),
+                ..Default::default()
+            },
+            confirm: None,
+        })
+        .collect()
+}
+
+/// Whether the completion was offered from the words in open buffers.
+pub(crate) fn is_word_completion(completion: &Completion) -> bool {
+    completion.server_id == NO_LANGUAGE_SERVER
+        && completion.lsp_completion.kind == Some(CompletionItemKind::TEXT)
+}
+
+fn is_completion_of(word: &str, lowercase_prefix: &str) -> bool {
+    word.len() > lowercase_prefix.len() && word.to_lowercase().starts_with(lowercase_prefix)
+}
+
+fn words_in<'a>(
+    text: &'a str,
+    classifier: &'a CharClassifier,
+) -> impl Iterator<Item = (Range<usize>, &'a str)> + 'a {
+    let mut chars = text.char_indices();
+    iter::from_fn(move || {
+        let (start, _) = chars.find(|(_, c)| classifier.is_word(*c))?;
+        let end = chars
+            .find(|(_, c)| !classifier.is_word(*c))
+            .map_or(text.len(), |(offset, _)| offset);
+        Some((start..end, &text[start..end]))
+    })
+}
@@ -0,0 +1,493 @@
+//! Working sets collect locations from anywhere in the project, such as search results,
+//! references or hand-picked selections, into a named multibuffer that's restored with the
+//! workspace. Locations are anchored in their buffers, so they follow the edits made to them.
+
+use crate::{
+    actions::{AddToWorkingSet, OpenWorkingSet, RemoveFromWorkingSet},
+    persistence::DB,
+    Editor, EditorEvent, MultiBuffer, DEFAULT_MULTIBUFFER_CONTEXT,
+};
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{
+    AnyView, AppContext, EventEmitter, FocusHandle, FocusableView, IntoElement, Model,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, View, ViewContext,
+    VisualContext, WeakView, WindowContext,
+};
+use language::{Anchor, Bias, Buffer, OffsetRangeExt as _, Point, ToOffset as _, ToPoint as _};
+use multi_buffer::MultiBufferRow;
+use project::{Item as _, Project, ProjectPath};
+use serde::{Deserialize, Serialize};
+use std::{any::TypeId, ops::Range, path::PathBuf};
+use ui::{prelude::*, Icon, IconName};
+use util::ResultExt;
+use workspace::{
+    item::{BreadcrumbText, Item, ItemEvent, ItemHandle, SerializableItem},
+    ItemId, ItemNavHistory, Pane, ToolbarItemLocation, Workspace, WorkspaceId,
+};
+
+const DEFAULT_WORKING_SET_NAME: &str = "Working Set";
+
+pub struct WorkingSet {
+    name: SharedString,
+    editor: View<Editor>,
+    excerpts: Model<MultiBuffer>,
+    /// The locations in the set, ordered by buffer and then by position.
+    locations: Vec<(Model<Buffer>, Range<Anchor>)>,
+    _subscription: Subscription,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedLocation {
+    path: PathBuf,
+    start: (u32, u32),
+    end: (u32, u32),
+}
+
+pub(crate) fn init(cx: &mut AppContext) {
+    workspace::register_serializable_item::<WorkingSet>(cx);
+    cx.observe_new_views(
+        |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
+            workspace.register_action(WorkingSet::add_to_working_set);
+            workspace.register_action(WorkingSet::open_working_set);
+        },
+    )
+    .detach();
+}
+
+impl WorkingSet {
+    fn new(name: SharedString, project: Model<Project>, cx: &mut ViewContext<Self>) -> Self {
+        let excerpts = cx.new_model(|cx| {
+            let project = project.read(cx);
+            MultiBuffer::new(project.replica_id(), project.capability())
+        });
+        let editor = cx.new_view(|cx| {
+            Editor::for_multibuffer(excerpts.clone(), Some(project.clone()), true, cx)
+        });
+        let subscription = cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone());
+        });
+        Self {
+            name,
+            editor,
+            excerpts,
+            locations: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    pub fn name(&self) -> &SharedString {
+        &self.name
+    }
+
+    pub fn locations(&self) -> &[(Model<Buffer>, Range<Anchor>)] {
+        &self.locations
+    }
+
+    fn add_to_working_set(
+        workspace: &mut Workspace,
+        action: &AddToWorkingSet,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let locations = editor.update(cx, |editor, cx| editor.selected_locations(cx));
+        if locations.is_empty() {
+            return;
+        }
+
+        let working_set = Self::find_or_open(workspace, action.name.as_deref(), false, cx);
+        working_set.update(cx, |working_set, cx| {
+            working_set.add_locations(locations, cx)
+        });
+        // Opening a new working set activates it, but the locations keep being picked from the
+        // editor.
+        workspace.activate_item(&editor, true, true, cx);
+    }
+
+    fn open_working_set(
+        workspace: &mut Workspace,
+        action: &OpenWorkingSet,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        Self::find_or_open(workspace, action.name.as_deref(), true, cx);
+    }
+
+    /// Returns the working set with the given name, opening an empty one in the active pane if
+    /// there isn't one. Existing working sets are only activated if `activate` is set.
+    fn find_or_open(
+        workspace: &mut Workspace,
+        name: Option<&str>,
+        activate: bool,
+        cx: &mut ViewContext<Workspace>,
+    ) -> View<Self> {
+        let name = name.unwrap_or(DEFAULT_WORKING_SET_NAME);
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|working_set| working_set.read(cx).name.as_ref() == name);
+        if let Some(working_set) = existing {
+            if activate {
+                workspace.activate_item(&working_set, true, true, cx);
+            }
+            return working_set;
+        }
+
+        let project = workspace.project().clone();
+        let name = SharedString::from(name.to_string());
+        let working_set = cx.new_view(|cx| Self::new(name, project, cx));
+        workspace.add_item_to_active_pane(Box::new(working_set.clone()), None, activate, cx);
+        working_set
+    }
+
+    /// Adds the locations that aren't in the set yet.
+    pub fn add_locations(
+        &mut self,
+        locations: impl IntoIterator<Item = (Model<Buffer>, Range<Anchor>)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let mut added = false;
+        for (buffer, range) in locations {
+            let snapshot = buffer.read(cx);
+            let offsets = range.to_offset(snapshot);
+            let is_duplicate = self
+                .locations
+                .iter()
+                .any(|(existing_buffer, existing_range)| {
+                    *existing_buffer == buffer && existing_range.to_offset(snapshot) == offsets
+                });
+            if !is_duplicate {
+                self.locations.push((buffer, range));
+                added = true;
+            }
+        }
+        if added {
+            self.refresh_excerpts(cx);
+        }
+    }
+
+    fn remove_from_working_set(&mut self, _: &RemoveFromWorkingSet, cx: &mut ViewContext<Self>) {
+        let selected = self
+            .editor
+            .update(cx, |editor, cx| editor.selected_locations(cx));
+        let location_count = self.locations.len();
+        self.locations.retain(|(buffer, range)| {
+            let snapshot = buffer.read(cx);
+            let range = range.to_offset(snapshot);
+            !selected.iter().any(|(selected_buffer, selected_range)| {
+                if selected_buffer != buffer {
+                    return false;
+                }
+                let selected_range = selected_range.to_offset(snapshot);
+                selected_range.start <= range.end && range.start <= selected_range.end
+            })
+        });
+        if self.locations.len() != location_count {
+            self.refresh_excerpts(cx);
+        }
+    }
+
+    /// Rebuilds the excerpts from the locations, which may have moved since they were added.
+    fn refresh_excerpts(&mut self, cx: &mut ViewContext<Self>) {
+        self.locations.sort_by_cached_key(|(buffer, range)| {
+            let buffer = buffer.read(cx);
+            (buffer.remote_id(), range.start.to_offset(buffer))
+        });
+
+        let locations = &self.locations;
+        let ranges_to_highlight = self.excerpts.update(cx, |excerpts, cx| {
+            excerpts.clear(cx);
+            let mut ranges_to_highlight = Vec::new();
+            let mut locations = locations.iter().peekable();
+            while let Some((buffer, range)) = locations.next() {
+                let mut ranges_for_buffer = vec![range.clone()];
+                while let Some((_, range)) =
+                    locations.next_if(|(next_buffer, _)| next_buffer == buffer)
+                {
+                    ranges_for_buffer.push(range.clone());
+                }
+                ranges_to_highlight.extend(excerpts.push_excerpts_with_context_lines(
+                    buffer.clone(),
+                    ranges_for_buffer,
+                    DEFAULT_MULTIBUFFER_CONTEXT,
+                    cx,
+                ));
+            }
+            ranges_to_highlight
+        });
+        self.editor.update(cx, |editor, cx| {
+            editor.highlight_background::<Self>(
+                &ranges_to_highlight,
+                |theme| theme.editor_highlighted_line_background,
+                cx,
+            );
+        });
+        cx.notify();
+    }
+}
+
+impl Editor {
+    /// The buffer ranges covered by the selections, where empty selections cover their line.
+    fn selected_locations(
+        &mut self,
+        cx: &mut ViewContext<Self>,
+    ) -> Vec<(Model<Buffer>, Range<Anchor>)> {
+        let selections = self.selections.all_adjusted(cx);
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut locations = Vec::new();
+        for selection in selections {
+            let mut range = selection.range();
+            if range.is_empty() {
+                let row = range.start.row;
+                range = Point::new(row, 0)..Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+            }
+            for (buffer, range, _) in multi_buffer.range_to_buffer_ranges(range, cx) {
+                let buffer_snapshot = buffer.read(cx);
+                let range = buffer_snapshot.anchor_after(range.start)
+                    ..buffer_snapshot.anchor_before(range.end);
+                locations.push((buffer, range));
+            }
+        }
+        locations
+    }
+}
+
+impl EventEmitter<EditorEvent> for WorkingSet {}
+
+impl FocusableView for WorkingSet {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for WorkingSet {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .on_action(cx.listener(Self::remove_from_working_set))
+            .child(self.editor.clone())
+    }
+}
+
+impl Item for WorkingSet {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, cx| editor.deactivated(cx));
+    }
+
+    fn navigate(&mut self, data: Box<dyn std::any::Any>, cx: &mut ViewContext<Self>) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(format!("{} ({} locations)", self.name, self.locations.len()).into())
+    }
+
+    fn tab_content_text(&self, _: &WindowContext) -> Option<SharedString> {
+        Some(self.name.clone())
+    }
+
+    fn tab_icon(&self, _: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTree))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("working set")
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &AppContext,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::Item),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn is_singleton(&self, _: &AppContext) -> bool {
+        false
+    }
+
+    fn set_nav_history(&mut self, nav_history: ItemNavHistory, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn is_dirty(&self, cx: &AppContext) -> bool {
+        self.excerpts.read(cx).is_dirty(cx)
+    }
+
+    fn has_conflict(&self, cx: &AppContext) -> bool {
+        self.excerpts.read(cx).has_conflict(cx)
+    }
+
+    fn can_save(&self, _: &AppContext) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.save(format, project, cx)
+    }
+
+    fn save_as(
+        &mut self,
+        _: Model<Project>,
+        _: ProjectPath,
+        _: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(&mut self, project: Model<Project>, cx: &mut ViewContext<Self>) -> Task<Result<()>> {
+        self.editor.reload(project, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a View<Self>,
+        _: &'a AppContext,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn breadcrumb_location(&self) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &AppContext) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.added_to_workspace(workspace, cx));
+    }
+}
+
+impl SerializableItem for WorkingSet {
+    fn serialized_item_kind() -> &'static str {
+        "WorkingSet"
+    }
+
+    fn cleanup(
+        workspace_id: WorkspaceId,
+        alive_items: Vec<ItemId>,
+        cx: &mut WindowContext,
+    ) -> Task<Result<()>> {
+        cx.spawn(|_| DB.delete_unloaded_working_sets(workspace_id, alive_items))
+    }
+
+    fn deserialize(
+        project: Model<Project>,
+        _workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        item_id: ItemId,
+        cx: &mut ViewContext<Pane>,
+    ) -> Task<Result<View<Self>>> {
+        let serialized_working_set = DB
+            .get_working_set(item_id, workspace_id)
+            .context("Failed to query working set")
+            .and_then(|working_set| working_set.ok_or_else(|| anyhow!("No working set found")))
+            .and_then(|(name, locations)| {
+                let locations = serde_json::from_str::<Vec<SerializedLocation>>(&locations)?;
+                Ok((name, locations))
+            });
+        let (name, serialized_locations) = match serialized_working_set {
+            Ok(working_set) => working_set,
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        cx.spawn(|pane, mut cx| async move {
+            let mut locations = Vec::new();
+            for location in serialized_locations {
+                // Skip the locations in files that were removed since.
+                let Some(buffer) = project
+                    .update(&mut cx, |project, cx| {
+                        project.open_local_buffer(&location.path, cx)
+                    })?
+                    .await
+                    .log_err()
+                else {
+                    continue;
+                };
+                let range = buffer.update(&mut cx, |buffer, _| {
+                    let start = Point::new(location.start.0, location.start.1);
+                    let end = Point::new(location.end.0, location.end.1);
+                    buffer.anchor_after(buffer.clip_point(start, Bias::Left))
+                        ..buffer.anchor_before(buffer.clip_point(end, Bias::Right))
+                })?;
+                locations.push((buffer, range));
+            }
+
+            pane.update(&mut cx, |_, cx| {
+                cx.new_view(|cx| {
+                    let mut working_set = Self::new(name.into(), project, cx);
+                    working_set.add_locations(locations, cx);
+                    working_set
+                })
+            })
+        })
+    }
+
+    fn serialize(
+        &mut self,
+        workspace: &mut Workspace,
+        item_id: ItemId,
+        _closing: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let workspace_id = workspace.database_id()?;
+        let project = workspace.project().read(cx);
+        let locations = self
+            .locations
+            .iter()
+            .filter_map(|(buffer, range)| {
+                let buffer = buffer.read(cx);
+                let project_path = buffer.project_path(cx)?;
+                let path = project.absolute_path(&project_path, cx)?;
+                let start = range.start.to_point(buffer);
+                let end = range.end.to_point(buffer);
+                Some(SerializedLocation {
+                    path,
+                    start: (start.row, start.column),
+                    end: (end.row, end.column),
+                })
+            })
+            .collect::<Vec<_>>();
+        let locations = serde_json::to_string(&locations).log_err()?;
+        let name = self.name.to_string();
+
+        Some(cx.background_executor().spawn(async move {
+            DB.save_working_set(item_id, workspace_id, name, locations)
+                .await
+        }))
+    }
+
+    fn should_serialize(&self, event: &Self::Event) -> bool {
+        matches!(
+            event,
+            EditorEvent::ExcerptsAdded { .. }
+                | EditorEvent::ExcerptsRemoved { .. }
+                | EditorEvent::BufferEdited
+                | EditorEvent::Saved
+        )
+    }
+}
@@ -0,0 +1,133 @@
+use std::{ops::Range, path::Path};
+
+/// Suffixes of the environment files that only document the variables, with placeholder
+/// values, and are meant to be committed.
+const TEMPLATE_NAMES: &[&str] = &["example", "sample", "template", "dist", "defaults"];
+
+/// Whether the file is a `.env`-style file, such as `.env`, `.env.local` or `production.env`.
+pub fn is_env_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let variant = if name == ".env" {
+        return true;
+    } else if let Some(suffix) = name.strip_prefix(".env.") {
+        suffix
+    } else if let Some(stem) = name.strip_suffix(".env") {
+        stem.rsplit('.').next().unwrap_or(stem)
+    } else {
+        return false;
+    };
+    !variant.is_empty() && !TEMPLATE_NAMES.contains(&variant.to_lowercase().as_str())
+}
+
+/// A `KEY=value` assignment of an environment file, as byte ranges of the text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvEntry {
+    pub key: Range<usize>,
+    /// The value, including its quotes but not its trailing comment.
+    pub value: Range<usize>,
+}
+
+/// Parses the assignments with a non-empty value, skipping comments and `export` keywords.
+pub fn parse_env_entries(text: &str) -> Vec<EnvEntry> {
+    let mut entries = Vec::new();
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        if let Some(entry) = parse_line(line) {
+            entries.push(EnvEntry {
+                key: line_start + entry.key.start..line_start + entry.key.end,
+                value: line_start + entry.value.start..line_start + entry.value.end,
+            });
+        }
+        line_start += line.len() + 1;
+    }
+    entries
+}
+
+fn parse_line(line: &str) -> Option<EnvEntry> {
+    let assignment = line.trim_start();
+    if assignment.starts_with('#') {
+        return None;
+    }
+    let assignment = assignment.strip_prefix("export ").unwrap_or(assignment);
+    let (key, value) = assignment.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let value = value.trim_start();
+    let value_len = match value.chars().next() {
+        Some(quote @ ('"' | '\'' | '`')) => value[1..]
+            .find(quote)
+            .map_or(value.trim_end().len(), |end| end + 2),
+        _ => {
+            let comment = value
+                .match_indices('#')
+                .find(|(ix, _)| value[..*ix].ends_with([' ', '\t']))
+                .map_or(value.len(), |(ix, _)| ix);
+            value[..comment].trim_end().len()
+        }
+    };
+    if value_len == 0 {
+        return None;
+    }
+    let key_start = offset_in(line, key);
+    let value_start = offset_in(line, value);
+    Some(EnvEntry {
+        key: key_start..key_start + key.len(),
+        value: value_start..value_start + value_len,
+    })
+}
+
+/// The byte offset of a slice of the line.
+fn offset_in(line: &str, slice: &str) -> usize {
+    slice.as_ptr() as usize - line.as_ptr() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_env_file() {
+        for path in [".env", ".env.local", "app/.env.production", "staging.env"] {
+            assert!(is_env_file(Path::new(path)), "{path}");
+        }
+        for path in [
+            ".env.example",
+            ".env.Sample",
+            "defaults.env",
+            ".envrc",
+            "env",
+            "a.txt",
+        ] {
+            assert!(!is_env_file(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_parse_env_entries() {
+        let text = "# comment\n\
+            API_KEY=secret\n\
+            export  TOKEN = \"quoted # value\" # comment\n\
+            EMPTY=\n\
+            URL=http://host/#anchor   # trailing\n\
+            not an assignment\r\n\
+            SINGLE='unterminated\r\n";
+        let entries = parse_env_entries(text)
+            .into_iter()
+            .map(|entry| (&text[entry.key], &text[entry.value]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                ("API_KEY", "secret"),
+                ("TOKEN", "\"quoted # value\""),
+                ("URL", "http://host/#anchor"),
+                ("SINGLE", "'unterminated"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+mod dotenv;
+mod env_file_settings;
+
+use collections::HashSet;
+use editor::{Anchor, Editor, EditorEvent, EditorMode, FoldPlaceholder};
+use gpui::{actions, AppContext, Model, MouseButton, Subscription, ViewContext, WeakView};
+use settings::Settings as _;
+use std::{ops::Range, sync::Arc};
+use ui::{prelude::*, Tooltip};
+
+pub use dotenv::{is_env_file, parse_env_entries, EnvEntry};
+pub use env_file_settings::EnvFileSettings;
+
+actions!(env_file, [ToggleValueMasking]);
+
+const MASKED_VALUE: &str = "••••••••";
+
+pub fn init(cx: &mut AppContext) {
+    EnvFileSettings::register(cx);
+
+    cx.observe_new_views(|editor: &mut Editor, cx| {
+        if editor.mode() != EditorMode::Full {
+            return;
+        }
+
+        editor
+            .register_action(cx.listener(|editor, _: &ToggleValueMasking, cx| {
+                if value_mask(editor).is_some() {
+                    disable(editor, cx);
+                } else {
+                    enable(editor, cx);
+                }
+            }))
+            .detach();
+
+        if EnvFileSettings::get_global(cx).mask_values && is_env_file_editor(editor, cx) {
+            enable(editor, cx);
+        }
+    })
+    .detach();
+}
+
+/// The values hidden in an editor, until they're clicked.
+struct ValueMask {
+    folds: Vec<Range<Anchor>>,
+    /// The keys whose values were revealed or edited, which stay visible.
+    revealed: HashSet<String>,
+}
+
+struct ValueMaskAddon {
+    mask: Model<ValueMask>,
+    _subscription: Subscription,
+}
+
+impl editor::Addon for ValueMaskAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn is_env_file_editor(editor: &Editor, cx: &AppContext) -> bool {
+    editor
+        .buffer()
+        .read(cx)
+        .as_singleton()
+        .and_then(|buffer| buffer.read(cx).file().map(|file| is_env_file(file.path())))
+        .unwrap_or(false)
+}
+
+fn value_mask(editor: &Editor) -> Option<Model<ValueMask>> {
+    editor
+        .addon::<ValueMaskAddon>()
+        .map(|addon| addon.mask.clone())
+}
+
+fn enable(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let mask = cx.new_model(|_| ValueMask {
+        folds: Vec::new(),
+        revealed: HashSet::default(),
+    });
+    let this = cx.view().clone();
+    let subscription = cx.subscribe(&this, |editor, _, event: &EditorEvent, cx| {
+        if let EditorEvent::BufferEdited = event {
+            refresh(editor, cx);
+        }
+    });
+    editor.register_addon(ValueMaskAddon {
+        mask,
+        _subscription: subscription,
+    });
+    refresh(editor, cx);
+}
+
+fn disable(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let Some(mask) = value_mask(editor) else {
+        return;
+    };
+    let folds = mask.update(cx, |mask, _| std::mem::take(&mut mask.folds));
+    editor.unregister_addon::<ValueMaskAddon>();
+    editor.unfold_ranges(folds, true, false, cx);
+    cx.notify();
+}
+
+/// Hides the values that weren't revealed. Values intersecting a selection are being edited,
+/// so they're revealed.
+fn refresh(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let Some(mask) = value_mask(editor) else {
+        return;
+    };
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let text = snapshot.text();
+    let selections = editor.selections.all::<usize>(cx);
+    let entries = parse_env_entries(&text);
+
+    let (old_folds, new_folds) = mask.update(cx, |mask, _| {
+        let mut masked = Vec::new();
+        for entry in entries {
+            let key = &text[entry.key];
+            let is_edited = selections.iter().any(|selection| {
+                selection.start <= entry.value.end && selection.end >= entry.value.start
+            });
+            if is_edited {
+                mask.revealed.insert(key.to_string());
+            }
+            if !mask.revealed.contains(key) {
+                let range = snapshot.anchor_before(entry.value.start)
+                    ..snapshot.anchor_after(entry.value.end);
+                masked.push((range, key.to_string()));
+            }
+        }
+        let new_folds = masked.iter().map(|(range, _)| range.clone()).collect();
+        (std::mem::replace(&mut mask.folds, new_folds), masked)
+    });
+
+    let this = cx.view().downgrade();
+    editor.unfold_ranges(old_folds, true, false, cx);
+    editor.fold_ranges(
+        new_folds
+            .into_iter()
+            .map(|(range, key)| (range, masked_value_placeholder(key, this.clone()))),
+        false,
+        cx,
+    );
+}
+
+fn reveal(editor: &mut Editor, key: String, range: Range<Anchor>, cx: &mut ViewContext<Editor>) {
+    if let Some(mask) = value_mask(editor) {
+        mask.update(cx, |mask, _| mask.revealed.insert(key));
+    }
+    editor.unfold_ranges([range], true, false, cx);
+}
+
+fn masked_value_placeholder(key: String, editor: WeakView<Editor>) -> FoldPlaceholder {
+    FoldPlaceholder {
+        render: Arc::new(move |fold_id, fold_range, cx| {
+            let key = key.clone();
+            let editor = editor.clone();
+            div()
+                .id(fold_id)
+                .px_0p5()
+                .rounded_sm()
+                .cursor_pointer()
+                .text_color(cx.theme().colors().text_muted)
+                .bg(cx.theme().colors().ghost_element_background)
+                .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+                .child(MASKED_VALUE)
+                .tooltip(|cx| Tooltip::text("Click to Reveal", cx))
+                .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+                .on_click(move |_, cx| {
+                    editor
+                        .update(cx, |editor, cx| {
+                            reveal(editor, key.clone(), fold_range.clone(), cx);
+                        })
+                        .ok();
+                    cx.stop_propagation();
+                })
+                .into_any_element()
+        }),
+        constrain_width: false,
+        merge_adjacent: false,
+    }
+}
@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EnvFileSettings {
+    pub mask_values: bool,
+    pub warn_before_commit: bool,
+    pub include_in_assistant_context: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct EnvFileSettingsContent {
+    /// Whether the values of environment files are hidden until they're clicked.
+    ///
+    /// Default: false
+    pub mask_values: Option<bool>,
+    /// Whether to ask for confirmation before committing staged environment files.
+    ///
+    /// Default: true
+    pub warn_before_commit: Option<bool>,
+    /// Whether environment files can be inserted into the assistant's context, by
+    /// slash commands such as `/file`, `/tab` and `/search`.
+    ///
+    /// Default: false
+    pub include_in_assistant_context: Option<bool>,
+}
+
+impl Settings for EnvFileSettings {
+    const KEY: Option<&'static str> = Some("env_files");
+
+    type FileContent = EnvFileSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -80,6 +80,9 @@ pub struct ExtensionManifest {
     pub indexed_docs_providers: BTreeMap<Arc<str>, IndexedDocsProviderEntry>,
     #[serde(default)]
     pub snippets: Option<PathBuf>,
+    /// The translations of Zed's UI the extension provides, keyed by language.
+    #[serde(default)]
+    pub translations: BTreeMap<Arc<str>, PathBuf>,
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -208,5 +211,6 @@ fn manifest_from_old_manifest(
         slash_commands: BTreeMap::default(),
         indexed_docs_providers: BTreeMap::default(),
         snippets: None,
+        translations: BTreeMap::default(),
     }
 }
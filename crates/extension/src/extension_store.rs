@@ -34,6 +34,7 @@ use gpui::{
     WeakModel,
 };
 use http_client::{AsyncBody, HttpClient, HttpClientWithUrl};
+use i18n::TranslationRegistry;
 use indexed_docs::{IndexedDocsRegistry, ProviderId};
 use language::{
     LanguageConfig, LanguageMatcher, LanguageName, LanguageQueries, LanguageRegistry,
@@ -50,6 +51,7 @@ use std::ops::RangeInclusive;
 use std::str::FromStr;
 use std::{
     cmp::Ordering,
+    ffi::OsStr,
     path::{self, Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
@@ -118,6 +120,7 @@ pub struct ExtensionStore {
     slash_command_registry: Arc<SlashCommandRegistry>,
     indexed_docs_registry: Arc<IndexedDocsRegistry>,
     snippet_registry: Arc<SnippetRegistry>,
+    translation_registry: Arc<TranslationRegistry>,
     modified_extensions: HashSet<Arc<str>>,
     wasm_host: Arc<WasmHost>,
     wasm_extensions: Vec<(Arc<ExtensionManifest>, WasmExtension)>,
@@ -198,6 +201,7 @@ pub fn init(
             SlashCommandRegistry::global(cx),
             IndexedDocsRegistry::global(cx),
             SnippetRegistry::global(cx),
+            TranslationRegistry::global(cx),
             cx,
         )
     });
@@ -234,6 +238,7 @@ impl ExtensionStore {
         slash_command_registry: Arc<SlashCommandRegistry>,
         indexed_docs_registry: Arc<IndexedDocsRegistry>,
         snippet_registry: Arc<SnippetRegistry>,
+        translation_registry: Arc<TranslationRegistry>,
         cx: &mut ModelContext<Self>,
     ) -> Self {
         let work_dir = extensions_dir.join("work");
@@ -267,6 +272,7 @@ impl ExtensionStore {
             slash_command_registry,
             indexed_docs_registry,
             snippet_registry,
+            translation_registry,
             reload_tx,
             tasks: Vec::new(),
         };
@@ -315,6 +321,11 @@ impl ExtensionStore {
             }
             this.update(&mut cx, |this, cx| this.auto_install_extensions(cx))
                 .ok();
+            // Updating extensions downloads and recompiles them, which can wait until the
+            // machine is plugged in.
+            if let Ok(wait_for_power) = cx.update(|cx| cx.wait_while_conserving_power()) {
+                wait_for_power.await;
+            }
             this.update(&mut cx, |this, cx| this.check_for_updates(cx))
                 .ok();
         })
@@ -1034,11 +1045,22 @@ impl ExtensionStore {
             })
             .collect::<Vec<_>>();
         let mut grammars_to_remove = Vec::new();
+        let mut translations_to_remove = Vec::new();
         for extension_id in &extensions_to_unload {
             let Some(extension) = old_index.extensions.get(extension_id) else {
                 continue;
             };
             grammars_to_remove.extend(extension.manifest.grammars.keys().cloned());
+            translations_to_remove.extend(extension.manifest.translations.values().map(
+                |translations_path| {
+                    let mut path = self.installed_dir.clone();
+                    path.extend([
+                        Path::new(extension_id.as_ref()),
+                        translations_path.as_path(),
+                    ]);
+                    path
+                },
+            ));
             for (language_server_name, config) in extension.manifest.language_servers.iter() {
                 for language in config.languages() {
                     self.language_registry
@@ -1050,6 +1072,8 @@ impl ExtensionStore {
         self.wasm_extensions
             .retain(|(extension, _)| !extensions_to_unload.contains(&extension.id));
         self.theme_registry.remove_user_themes(&themes_to_remove);
+        self.translation_registry
+            .remove_catalogs(&translations_to_remove);
         self.language_registry
             .remove_languages(&languages_to_remove, &grammars_to_remove);
 
@@ -1061,6 +1085,7 @@ impl ExtensionStore {
         let mut grammars_to_add = Vec::new();
         let mut themes_to_add = Vec::new();
         let mut snippets_to_add = Vec::new();
+        let mut translations_to_add = Vec::new();
         for extension_id in &extensions_to_load {
             let Some(extension) = new_index.extensions.get(extension_id) else {
                 continue;
@@ -1083,6 +1108,16 @@ impl ExtensionStore {
                 path.extend([Path::new(extension_id.as_ref()), snippets_path.as_path()]);
                 path
             }));
+            translations_to_add.extend(extension.manifest.translations.iter().map(
+                |(language, translations_path)| {
+                    let mut path = self.installed_dir.clone();
+                    path.extend([
+                        Path::new(extension_id.as_ref()),
+                        translations_path.as_path(),
+                    ]);
+                    (language.clone(), path)
+                },
+            ));
         }
 
         self.language_registry
@@ -1119,6 +1154,7 @@ impl ExtensionStore {
         let root_dir = self.installed_dir.clone();
         let theme_registry = self.theme_registry.clone();
         let snippet_registry = self.snippet_registry.clone();
+        let translation_registry = self.translation_registry.clone();
         let extension_entries = extensions_to_load
             .iter()
             .filter_map(|name| new_index.extensions.get(name).cloned())
@@ -1148,6 +1184,20 @@ impl ExtensionStore {
                                     .log_err();
                             }
                         }
+
+                        for (language, translations_path) in &translations_to_add {
+                            if let Some(translations_contents) =
+                                fs.load(translations_path).await.log_err()
+                            {
+                                translation_registry
+                                    .register_catalog(
+                                        language,
+                                        translations_path,
+                                        &translations_contents,
+                                    )
+                                    .log_err();
+                            }
+                        }
                     }
                 })
                 .await;
@@ -1246,7 +1296,11 @@ impl ExtensionStore {
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
-                ThemeSettings::reload_current_theme(cx)
+                ThemeSettings::reload_current_theme(cx);
+                // Redraw the UI so that it uses the strings of the loaded translations, mirrored
+                // if they're in a right-to-left language.
+                i18n::update_layout_direction(cx);
+                cx.refresh();
             })
             .ok();
         })
@@ -1375,6 +1429,33 @@ impl ExtensionStore {
             }
         }
 
+        if let Ok(mut translations_paths) = fs.read_dir(&extension_dir.join("translations")).await {
+            while let Some(translations_path) = translations_paths.next().await {
+                let translations_path = translations_path?;
+                if translations_path.extension() != Some(OsStr::new("json")) {
+                    continue;
+                }
+                let (Ok(relative_path), Some(language)) = (
+                    translations_path.strip_prefix(&extension_dir),
+                    translations_path.file_stem().and_then(OsStr::to_str),
+                ) else {
+                    continue;
+                };
+
+                let relative_path = relative_path.to_path_buf();
+                if !extension_manifest
+                    .translations
+                    .values()
+                    .any(|path| path == &relative_path)
+                {
+                    extension_manifest
+                        .translations
+                        .entry(language.into())
+                        .or_insert(relative_path);
+                }
+            }
+        }
+
         let extension_wasm_path = extension_dir.join("extension.wasm");
         if fs.is_file(&extension_wasm_path).await {
             extension_manifest
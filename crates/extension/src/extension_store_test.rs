@@ -12,6 +12,7 @@ use fs::{FakeFs, Fs, RealFs};
 use futures::{io::BufReader, AsyncReadExt, StreamExt};
 use gpui::{Context, SemanticVersion, TestAppContext};
 use http_client::{FakeHttpClient, Response};
+use i18n::{Message, TranslationRegistry};
 use indexed_docs::IndexedDocsRegistry;
 use isahc_http_client::IsahcHttpClient;
 use language::{LanguageMatcher, LanguageRegistry, LanguageServerBinaryStatus, LanguageServerName};
@@ -164,6 +165,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         slash_commands: BTreeMap::default(),
                         indexed_docs_providers: BTreeMap::default(),
                         snippets: None,
+                        translations: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -190,6 +192,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         slash_commands: BTreeMap::default(),
                         indexed_docs_providers: BTreeMap::default(),
                         snippets: None,
+                        translations: BTreeMap::default(),
                     }),
                     dev: false,
                 },
@@ -264,6 +267,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
     let slash_command_registry = SlashCommandRegistry::new();
     let indexed_docs_registry = Arc::new(IndexedDocsRegistry::new(cx.executor()));
     let snippet_registry = Arc::new(SnippetRegistry::new());
+    let translation_registry = Arc::new(TranslationRegistry::new());
     let node_runtime = FakeNodeRuntime::new();
 
     let store = cx.new_model(|cx| {
@@ -280,6 +284,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
             slash_command_registry.clone(),
             indexed_docs_registry.clone(),
             snippet_registry.clone(),
+            translation_registry.clone(),
             cx,
         )
     });
@@ -330,6 +335,11 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         }
                     ]
                 }"#,
+            },
+            "translations": {
+                "de.json": r#"{
+                    "picker.no_matches": "Keine Treffer"
+                }"#,
             }
         }),
     )
@@ -354,6 +364,9 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 slash_commands: BTreeMap::default(),
                 indexed_docs_providers: BTreeMap::default(),
                 snippets: None,
+                translations: [("de".into(), "translations/de.json".into())]
+                    .into_iter()
+                    .collect(),
             }),
             dev: false,
         },
@@ -387,6 +400,11 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 "One Dark",
             ]
         );
+        assert_eq!(translation_registry.languages(), ["de"]);
+        assert_eq!(
+            translation_registry.message("de", "picker.no_matches"),
+            Some(Message::Text("Keine Treffer".into()))
+        );
     });
 
     let prev_fs_metadata_call_count = fs.metadata_call_count();
@@ -408,6 +426,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
             slash_command_registry,
             indexed_docs_registry,
             snippet_registry,
+            translation_registry,
             cx,
         )
     });
@@ -490,6 +509,7 @@ async fn test_extension_store_with_test_extension(cx: &mut TestAppContext) {
     let slash_command_registry = SlashCommandRegistry::new();
     let indexed_docs_registry = Arc::new(IndexedDocsRegistry::new(cx.executor()));
     let snippet_registry = Arc::new(SnippetRegistry::new());
+    let translation_registry = Arc::new(TranslationRegistry::new());
     let node_runtime = FakeNodeRuntime::new();
 
     let mut status_updates = language_registry.language_server_binary_statuses();
@@ -592,6 +612,7 @@ async fn test_extension_store_with_test_extension(cx: &mut TestAppContext) {
             slash_command_registry,
             indexed_docs_registry,
             snippet_registry,
+            translation_registry,
             cx,
         )
     });
@@ -84,6 +84,15 @@ impl FileFinder {
             })
             .map(|(history_path, abs_path)| FoundPath::new(history_path, abs_path))
             .collect::<Vec<_>>();
+        let closed_items = workspace
+            .recently_closed_items(Some(MAX_RECENT_SELECTIONS), cx)
+            .into_iter()
+            .filter(|(_, closed_abs_path)| match closed_abs_path {
+                Some(abs_path) => history_file_exists(abs_path),
+                None => true,
+            })
+            .map(|(closed_path, abs_path)| FoundPath::new(closed_path, abs_path))
+            .collect::<Vec<_>>();
 
         let project = workspace.project().clone();
         let weak_workspace = cx.view().downgrade();
@@ -94,6 +103,7 @@ impl FileFinder {
                 project,
                 currently_opened_path,
                 history_items,
+                closed_items,
                 separate_history,
                 cx,
             );
@@ -164,6 +174,9 @@ pub struct FileFinderDelegate {
     has_changed_selected_index: bool,
     cancel_flag: Arc<AtomicBool>,
     history_items: Vec<FoundPath>,
+    /// The items recently closed in the workspace, listed in a section of their own when the
+    /// query is empty.
+    closed_items: Vec<FoundPath>,
     separate_history: bool,
     first_update: bool,
 }
@@ -214,12 +227,14 @@ enum Match {
         panel_match: Option<ProjectPanelOrdMatch>,
     },
     Search(ProjectPanelOrdMatch),
+    /// An item recently closed in the workspace, only listed when the query is empty.
+    RecentlyClosed(FoundPath),
 }
 
 impl Match {
     fn path(&self) -> &Arc<Path> {
         match self {
-            Match::History { path, .. } => &path.project.path,
+            Match::History { path, .. } | Match::RecentlyClosed(path) => &path.project.path,
             Match::Search(panel_match) => &panel_match.0.path,
         }
     }
@@ -228,6 +243,7 @@ impl Match {
         match self {
             Match::History { panel_match, .. } => panel_match.as_ref(),
             Match::Search(panel_match) => Some(&panel_match),
+            Match::RecentlyClosed(_) => None,
         }
     }
 }
@@ -249,7 +265,8 @@ impl Matches {
         if let Match::History {
             path,
             panel_match: None,
-        } = entry
+        }
+        | Match::RecentlyClosed(path) = entry
         {
             // Slow case: linear search by path. Should not happen actually,
             // since we call `position` only if matches set changed, but the query has not changed.
@@ -271,6 +288,7 @@ impl Matches {
     fn push_new_matches<'a>(
         &'a mut self,
         history_items: impl IntoIterator<Item = &'a FoundPath> + Clone,
+        closed_items: &'a [FoundPath],
         currently_opened: Option<&'a FoundPath>,
         query: Option<&FileSearchQuery>,
         new_search_matches: impl Iterator<Item = ProjectPanelOrdMatch>,
@@ -286,12 +304,20 @@ impl Matches {
             self.matches
                 .extend(currently_opened.into_iter().map(path_to_entry));
 
+            // Recently closed items are listed in their own section after the history.
             self.matches.extend(
                 history_items
                     .into_iter()
-                    .filter(|found_path| Some(*found_path) != currently_opened)
+                    .filter(|found_path| {
+                        Some(*found_path) != currently_opened && !closed_items.contains(found_path)
+                    })
                     .map(path_to_entry),
             );
+            self.matches.extend(
+                closed_items
+                    .iter()
+                    .map(|found_path| Match::RecentlyClosed(found_path.clone())),
+            );
             return;
         };
 
@@ -480,6 +506,7 @@ impl FileFinderDelegate {
         project: Model<Project>,
         currently_opened_path: Option<FoundPath>,
         history_items: Vec<FoundPath>,
+        closed_items: Vec<FoundPath>,
         separate_history: bool,
         cx: &mut ViewContext<FileFinder>,
     ) -> Self {
@@ -498,6 +525,7 @@ impl FileFinderDelegate {
             selected_index: 0,
             cancel_flag: Arc::new(AtomicBool::new(false)),
             history_items,
+            closed_items,
             separate_history,
             first_update: true,
         }
@@ -600,6 +628,7 @@ impl FileFinderDelegate {
 
             self.matches.push_new_matches(
                 &self.history_items,
+                &self.closed_items,
                 self.currently_opened_path.as_ref(),
                 Some(&query),
                 matches.into_iter(),
@@ -630,9 +659,10 @@ impl FileFinderDelegate {
     ) -> (String, Vec<usize>, String, Vec<usize>) {
         let (file_name, file_name_positions, full_path, full_path_positions) = match &path_match {
             Match::History {
-                path: entry_path,
-                panel_match,
-            } => {
+                path: entry_path, ..
+            }
+            | Match::RecentlyClosed(entry_path) => {
+                let panel_match = path_match.panel_match();
                 let worktree_id = entry_path.project.worktree_id;
                 let project_relative_path = &entry_path.project.path;
                 let has_worktree = self
@@ -674,7 +704,7 @@ impl FileFinderDelegate {
                     path_prefix: "".into(),
                     distance_to_relative_ancestor: usize::MAX,
                 };
-                if let Some(found_path_match) = &panel_match {
+                if let Some(found_path_match) = panel_match {
                     path_match
                         .positions
                         .extend(found_path_match.0.positions.iter())
@@ -834,6 +864,17 @@ impl PickerDelegate for FileFinderDelegate {
     }
 
     fn separators_after_indices(&self) -> Vec<usize> {
+        let first_closed_index = self
+            .matches
+            .matches
+            .iter()
+            .position(|m| matches!(m, Match::RecentlyClosed(_)));
+        if let Some(first_closed_index) = first_closed_index {
+            if first_closed_index > 0 {
+                return vec![first_closed_index - 1];
+            }
+        }
+
         if self.separate_history {
             let first_non_history_index = self
                 .matches
@@ -878,6 +919,7 @@ impl PickerDelegate for FileFinderDelegate {
                             .is_some()
                             || (project.is_local_or_ssh() && history_item.absolute.is_some())
                     }),
+                    &self.closed_items,
                     self.currently_opened_path.as_ref(),
                     None,
                     None.into_iter(),
@@ -934,7 +976,7 @@ impl PickerDelegate for FileFinderDelegate {
                             }
                         };
                     match &m {
-                        Match::History { path, .. } => {
+                        Match::History { path, .. } | Match::RecentlyClosed(path) => {
                             let worktree_id = path.project.worktree_id;
                             if workspace
                                 .project()
@@ -1051,6 +1093,10 @@ impl PickerDelegate for FileFinderDelegate {
                 .color(Color::Muted)
                 .size(IconSize::Small)
                 .into_any_element(),
+            Match::RecentlyClosed(_) => Icon::new(IconName::Undo)
+                .color(Color::Muted)
+                .size(IconSize::Small)
+                .into_any_element(),
             Match::Search(_) => v_flex()
                 .flex_none()
                 .size(IconSize::Small.rems())
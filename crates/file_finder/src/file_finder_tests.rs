@@ -1323,6 +1323,57 @@ async fn test_history_items_shown_in_order_of_open(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_recently_closed_items_listed_after_history(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/test",
+            json!({
+                "test": {
+                    "1.txt": "// One",
+                    "2.txt": "// Two",
+                    "3.txt": "// Three",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/test".as_ref()], cx).await;
+    let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+    open_queried_buffer("1", 1, "1.txt", &workspace, cx).await;
+    open_queried_buffer("2", 1, "2.txt", &workspace, cx).await;
+    open_close_queried_buffer("3", 1, "3.txt", &workspace, cx).await;
+
+    let picker = open_file_picker(&workspace, cx);
+    picker.update(cx, |finder, _| {
+        assert_eq!(finder.delegate.matches.len(), 3);
+        assert_match_at_position(finder, 0, "2.txt");
+        assert_match_selection(finder, 1, "1.txt");
+        assert_match_at_position(finder, 2, "3.txt");
+        assert!(matches!(
+            finder.delegate.matches.get(2),
+            Some(Match::RecentlyClosed(_))
+        ));
+        assert_eq!(finder.delegate.separators_after_indices(), vec![1]);
+    });
+
+    // Recently closed items are matched like any other history item.
+    picker
+        .update(cx, |finder, cx| {
+            finder.delegate.update_matches("3".to_string(), cx)
+        })
+        .await;
+    picker.update(cx, |finder, _| {
+        assert_eq!(finder.delegate.matches.len(), 1);
+        assert_match_at_position(finder, 0, "3.txt");
+    });
+}
+
 #[gpui::test]
 async fn test_selected_history_item_stays_selected_on_worktree_updated(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
@@ -2120,6 +2171,16 @@ fn collect_search_matches(picker: &Picker<FileFinderDelegate>) -> SearchEntries
                     .history_found_paths
                     .push(history_path.clone());
             }
+            Match::RecentlyClosed(closed_path) => {
+                search_entries.history.push(
+                    closed_path
+                        .absolute
+                        .as_deref()
+                        .unwrap_or_else(|| &closed_path.project.path)
+                        .to_path_buf(),
+                );
+                search_entries.history_found_paths.push(closed_path.clone());
+            }
             Match::Search(path_match) => {
                 search_entries
                     .search
@@ -2157,7 +2218,9 @@ fn assert_match_at_position(
         .get(match_index)
         .unwrap_or_else(|| panic!("Finder has no match for index {match_index}"));
     let match_file_name = match &match_item {
-        Match::History { path, .. } => path.absolute.as_deref().unwrap().file_name(),
+        Match::History { path, .. } | Match::RecentlyClosed(path) => {
+            path.absolute.as_deref().unwrap().file_name()
+        }
         Match::Search(path_match) => path_match.0.path.file_name(),
     }
     .unwrap()
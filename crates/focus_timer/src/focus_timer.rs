@@ -0,0 +1,151 @@
+mod focus_timer_button;
+mod focus_timer_settings;
+
+use gpui::{
+    actions, AppContext, Context as _, EventEmitter, Global, Model, ModelContext, Subscription,
+    Task,
+};
+use settings::{Settings, SettingsStore};
+use std::time::{Duration, Instant};
+
+pub use focus_timer_button::FocusTimerButton;
+pub use focus_timer_settings::FocusTimerSettings;
+
+actions!(focus_timer, [StartFocus, StartBreak, StopFocusTimer]);
+
+pub fn init(cx: &mut AppContext) {
+    FocusTimerSettings::register(cx);
+
+    let timer = cx.new_model(FocusTimer::new);
+    cx.set_global(GlobalFocusTimer(timer));
+
+    cx.on_action(|_: &StartFocus, cx| {
+        FocusTimer::global(cx).update(cx, |timer, cx| timer.start(FocusPhase::Focus, cx))
+    });
+    cx.on_action(|_: &StartBreak, cx| {
+        FocusTimer::global(cx).update(cx, |timer, cx| timer.start(FocusPhase::Break, cx))
+    });
+    cx.on_action(|_: &StopFocusTimer, cx| {
+        FocusTimer::global(cx).update(cx, |timer, cx| timer.stop(cx))
+    });
+}
+
+struct GlobalFocusTimer(Model<FocusTimer>);
+
+impl Global for GlobalFocusTimer {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusPhase {
+    Focus,
+    Break,
+}
+
+impl FocusPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FocusPhase::Focus => "Focus",
+            FocusPhase::Break => "Break",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusTimerEvent {
+    IntervalEnded(FocusPhase),
+}
+
+/// Times focus sessions and the breaks in between, holding back notifications during focus
+/// sessions when `focus_timer.do_not_disturb` is enabled.
+pub struct FocusTimer {
+    interval: Option<(FocusPhase, Instant)>,
+    /// Refreshes the remaining time every second and ends the interval when it's up.
+    _tick: Option<Task<()>>,
+    _settings_subscription: Subscription,
+}
+
+impl EventEmitter<FocusTimerEvent> for FocusTimer {}
+
+impl FocusTimer {
+    fn new(cx: &mut ModelContext<Self>) -> Self {
+        Self {
+            interval: None,
+            _tick: None,
+            _settings_subscription: cx
+                .observe_global::<SettingsStore>(|this, cx| this.update_do_not_disturb(cx)),
+        }
+    }
+
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalFocusTimer>().0.clone()
+    }
+
+    pub fn try_global(cx: &AppContext) -> Option<Model<Self>> {
+        cx.try_global::<GlobalFocusTimer>()
+            .map(|timer| timer.0.clone())
+    }
+
+    /// The phase of the running interval, if any.
+    pub fn phase(&self) -> Option<FocusPhase> {
+        self.interval.map(|(phase, _)| phase)
+    }
+
+    /// The time left in the running interval, if any.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.interval
+            .map(|(_, ends_at)| ends_at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Starts an interval, replacing the running one.
+    pub fn start(&mut self, phase: FocusPhase, cx: &mut ModelContext<Self>) {
+        let settings = FocusTimerSettings::get_global(cx);
+        let minutes = match phase {
+            FocusPhase::Focus => settings.focus_minutes,
+            FocusPhase::Break => settings.break_minutes,
+        };
+        self.interval = Some((phase, Instant::now() + Duration::from_secs(minutes * 60)));
+        self._tick = Some(cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+                let ended = this.update(&mut cx, |this, cx| {
+                    cx.notify();
+                    if this
+                        .remaining()
+                        .map_or(true, |remaining| remaining.is_zero())
+                    {
+                        this.end_interval(cx);
+                        true
+                    } else {
+                        false
+                    }
+                });
+                if ended.unwrap_or(true) {
+                    break;
+                }
+            }
+        }));
+        self.update_do_not_disturb(cx);
+        cx.notify();
+    }
+
+    /// Stops the running interval without notifying that it ended.
+    pub fn stop(&mut self, cx: &mut ModelContext<Self>) {
+        self.interval = None;
+        self._tick = None;
+        self.update_do_not_disturb(cx);
+        cx.notify();
+    }
+
+    fn end_interval(&mut self, cx: &mut ModelContext<Self>) {
+        if let Some((phase, _)) = self.interval.take() {
+            // The tick task ends by itself once the interval is over.
+            self.update_do_not_disturb(cx);
+            cx.emit(FocusTimerEvent::IntervalEnded(phase));
+        }
+    }
+
+    fn update_do_not_disturb(&mut self, cx: &mut ModelContext<Self>) {
+        let do_not_disturb = FocusTimerSettings::get_global(cx).do_not_disturb
+            && self.phase() == Some(FocusPhase::Focus);
+        workspace::notifications::set_do_not_disturb(do_not_disturb, cx);
+    }
+}
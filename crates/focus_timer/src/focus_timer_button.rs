@@ -0,0 +1,98 @@
+use crate::{
+    FocusPhase, FocusTimer, FocusTimerEvent, FocusTimerSettings, StartBreak, StartFocus,
+    StopFocusTimer,
+};
+use gpui::{Model, Subscription, ViewContext, WeakView};
+use settings::Settings as _;
+use std::time::Duration;
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    item::ItemHandle, notifications::NotificationId, StatusItemView, Toast, Workspace,
+};
+
+/// Shows the time left in the running focus session or break in the status bar.
+pub struct FocusTimerButton {
+    timer: Model<FocusTimer>,
+    workspace: WeakView<Workspace>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FocusTimerButton {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Self>) -> Self {
+        let timer = FocusTimer::global(cx);
+        let subscriptions = vec![
+            cx.observe(&timer, |_, _, cx| cx.notify()),
+            cx.subscribe(&timer, |this, _, event, cx| {
+                this.notify_interval_ended(event, cx)
+            }),
+        ];
+        Self {
+            timer,
+            workspace: workspace.weak_handle(),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn notify_interval_ended(&mut self, event: &FocusTimerEvent, cx: &mut ViewContext<Self>) {
+        struct FocusTimerNotification;
+
+        let FocusTimerEvent::IntervalEnded(phase) = event;
+        let toast = match phase {
+            FocusPhase::Focus => Toast::new(
+                NotificationId::unique::<FocusTimerNotification>(),
+                "Focus session complete. Time for a break!",
+            )
+            .on_click("Start Break", |cx| cx.dispatch_action(Box::new(StartBreak))),
+            FocusPhase::Break => Toast::new(
+                NotificationId::unique::<FocusTimerNotification>(),
+                "Break is over.",
+            )
+            .on_click("Start Focus Session", |cx| {
+                cx.dispatch_action(Box::new(StartFocus))
+            }),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| workspace.show_toast(toast, cx))
+            .ok();
+    }
+}
+
+/// Formats a duration as minutes and seconds, like `24:05`.
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs_f64().ceil() as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl Render for FocusTimerButton {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        if !FocusTimerSettings::get_global(cx).button {
+            return div();
+        }
+
+        let timer = self.timer.read(cx);
+        let button = match timer.phase().zip(timer.remaining()) {
+            Some((phase, remaining)) => Button::new(
+                "focus-timer",
+                format!("{} {}", phase.label(), format_remaining(remaining)),
+            )
+            .icon(IconName::CountdownTimer)
+            .icon_position(IconPosition::Start)
+            .icon_size(IconSize::Small)
+            .icon_color(Color::Muted)
+            .label_size(LabelSize::Small)
+            .on_click(|_, cx| cx.dispatch_action(Box::new(StopFocusTimer)))
+            .tooltip(|cx| Tooltip::for_action("Stop Focus Timer", &StopFocusTimer, cx))
+            .into_any_element(),
+            None => IconButton::new("focus-timer", IconName::CountdownTimer)
+                .icon_size(IconSize::Small)
+                .on_click(|_, cx| cx.dispatch_action(Box::new(StartFocus)))
+                .tooltip(|cx| Tooltip::for_action("Start Focus Session", &StartFocus, cx))
+                .into_any_element(),
+        };
+        div().child(button)
+    }
+}
+
+impl StatusItemView for FocusTimerButton {
+    fn set_active_pane_item(&mut self, _: Option<&dyn ItemHandle>, _: &mut ViewContext<Self>) {}
+}
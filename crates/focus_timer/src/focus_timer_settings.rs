@@ -0,0 +1,44 @@
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FocusTimerSettings {
+    pub focus_minutes: u64,
+    pub break_minutes: u64,
+    pub do_not_disturb: bool,
+    pub button: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct FocusTimerSettingsContent {
+    /// How long a focus session lasts, in minutes.
+    ///
+    /// Default: 25
+    pub focus_minutes: Option<u64>,
+    /// How long a break lasts, in minutes.
+    ///
+    /// Default: 5
+    pub break_minutes: Option<u64>,
+    /// Whether to hold back notifications until the end of a focus session.
+    ///
+    /// Default: false
+    pub do_not_disturb: Option<bool>,
+    /// Whether to show the focus timer button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+}
+
+impl Settings for FocusTimerSettings {
+    const KEY: Option<&'static str> = Some("focus_timer");
+
+    type FileContent = FocusTimerSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
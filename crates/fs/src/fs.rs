@@ -13,8 +13,11 @@ use std::os::unix::fs::MetadataExt;
 use std::os::unix::fs::FileTypeExt;
 
 use async_tar::Archive;
-use futures::{future::BoxFuture, AsyncRead, Stream, StreamExt};
-use git::repository::{GitRepository, RealGitRepository};
+use futures::{channel::mpsc::UnboundedSender, future::BoxFuture, AsyncRead, Stream, StreamExt};
+use git::{
+    clone::{CloneProgress, GitCredentials},
+    repository::{GitRepository, RealGitRepository},
+};
 use gpui::{AppContext, Global, ReadGlobal};
 use rope::Rope;
 use smol::io::AsyncWriteExt;
@@ -32,7 +35,10 @@ use util::ResultExt;
 #[cfg(any(test, feature = "test-support"))]
 use collections::{btree_map, BTreeMap};
 #[cfg(any(test, feature = "test-support"))]
-use git::repository::{FakeGitRepositoryState, GitFileStatus};
+use git::{
+    clone::CloneError,
+    repository::{FakeGitRepositoryState, GitFileStatus},
+};
 #[cfg(any(test, feature = "test-support"))]
 use parking_lot::Mutex;
 #[cfg(any(test, feature = "test-support"))]
@@ -116,6 +122,15 @@ pub trait Fs: Send + Sync {
     );
 
     fn open_repo(&self, abs_dot_git: &Path) -> Option<Arc<dyn GitRepository>>;
+    /// Clones the repository at the given URL into `destination`, sending progress updates as
+    /// they're reported. Fails with a [`git::clone::CloneError`] for errors the user can resolve.
+    async fn git_clone(
+        &self,
+        url: &str,
+        destination: &Path,
+        credentials: Option<GitCredentials>,
+        progress: UnboundedSender<CloneProgress>,
+    ) -> Result<()>;
     fn is_fake(&self) -> bool;
     async fn is_case_sensitive(&self) -> Result<bool>;
 
@@ -707,6 +722,27 @@ impl Fs for RealFs {
         )))
     }
 
+    async fn git_clone(
+        &self,
+        url: &str,
+        destination: &Path,
+        credentials: Option<GitCredentials>,
+        progress: UnboundedSender<CloneProgress>,
+    ) -> Result<()> {
+        let git_binary_path = self
+            .git_binary_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("git"));
+        git::clone::clone_repository(
+            &git_binary_path,
+            url,
+            destination,
+            credentials.as_ref(),
+            progress,
+        )
+        .await
+    }
+
     fn is_fake(&self) -> bool {
         false
     }
@@ -1801,12 +1837,28 @@ impl Fs for FakeFs {
             let state = git_repo_state
                 .get_or_insert_with(|| Arc::new(Mutex::new(FakeGitRepositoryState::default())))
                 .clone();
+            state.lock().path = abs_dot_git.to_path_buf();
             Some(git::repository::FakeGitRepository::open(state))
         } else {
             None
         }
     }
 
+    async fn git_clone(
+        &self,
+        _url: &str,
+        destination: &Path,
+        _credentials: Option<GitCredentials>,
+        _progress: UnboundedSender<CloneProgress>,
+    ) -> Result<()> {
+        if self.is_dir(destination).await {
+            return Err(CloneError::DestinationExists.into());
+        }
+        // Clones are empty repositories, which tests can fill in.
+        self.simulate_random_delay().await;
+        self.create_dir(&destination.join(".git")).await
+    }
+
     fn is_fake(&self) -> bool {
         true
     }
@@ -0,0 +1,243 @@
+use anyhow::{anyhow, Context, Result};
+use futures::channel::mpsc::UnboundedSender;
+use smol::io::AsyncReadExt;
+use std::path::Path;
+use std::process::Stdio;
+use url::Url;
+
+/// The username and password, or access token, to authenticate with when cloning over HTTPS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// A progress update of `git clone`, like "Receiving objects" at 42%.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloneProgress {
+    pub stage: String,
+    pub percentage: Option<u8>,
+}
+
+/// The errors of a clone that the user can do something about.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CloneError {
+    /// The remote asked for credentials, and either none were given or they were rejected.
+    AuthenticationFailed,
+    /// The destination already exists and isn't empty.
+    DestinationExists,
+}
+
+impl std::fmt::Display for CloneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CloneError::AuthenticationFailed => write!(f, "authentication failed"),
+            CloneError::DestinationExists => write!(f, "the destination already exists"),
+        }
+    }
+}
+
+impl std::error::Error for CloneError {}
+
+/// The name of the directory a repository is cloned into by default, which is the last component
+/// of its URL without the `.git` extension.
+pub fn repository_name(url: &str) -> Option<&str> {
+    let path = url.trim().trim_end_matches('/');
+    let name = path.rsplit(['/', ':', '\\']).next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// The key credentials for a repository are stored under, which is the origin of its URL, or
+/// `None` for URLs that aren't authenticated with a username and password, like SSH ones.
+pub fn credentials_url(url: &str) -> Option<String> {
+    let url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    Some(url.origin().ascii_serialization())
+}
+
+/// Clones the repository at the given URL into `destination` with the git binary, reporting its
+/// progress. Credentials are handed to git through a credential helper reading them from the
+/// environment, so that they don't end up in the process list or the repository's config.
+pub async fn clone_repository(
+    git_binary_path: &Path,
+    url: &str,
+    destination: &Path,
+    credentials: Option<&GitCredentials>,
+    progress: UnboundedSender<CloneProgress>,
+) -> Result<()> {
+    let mut command = smol::process::Command::new(git_binary_path);
+    if credentials.is_some() {
+        command.args([
+            "-c",
+            "credential.helper=",
+            "-c",
+            "credential.helper=!f() { test \"$1\" = get && echo \"username=$ZED_GIT_USERNAME\" && echo \"password=$ZED_GIT_PASSWORD\"; }; f",
+        ]);
+    }
+    command
+        .args(["clone", "--progress", "--"])
+        .arg(url.trim())
+        .arg(destination)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        // Dropping the clone's future cancels it.
+        .kill_on_drop(true);
+    if let Some(credentials) = credentials {
+        command
+            .env("ZED_GIT_USERNAME", &credentials.username)
+            .env("ZED_GIT_PASSWORD", &credentials.password);
+    }
+
+    #[cfg(windows)]
+    {
+        use smol::process::windows::CommandExt;
+        command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+    }
+
+    let mut child = command
+        .spawn()
+        .context("failed to start git clone process")?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| anyhow!("failed to capture git clone output"))?;
+
+    // Progress lines are terminated by carriage returns while they're updated in place.
+    let mut output = String::new();
+    let mut line = Vec::new();
+    let mut buffer = [0; 1024];
+    loop {
+        let len = stderr.read(&mut buffer).await?;
+        if len == 0 {
+            break;
+        }
+        for &byte in &buffer[..len] {
+            if byte == b'\r' || byte == b'\n' {
+                let text = String::from_utf8_lossy(&line);
+                if let Some(update) = parse_progress(&text) {
+                    progress.unbounded_send(update).ok();
+                } else if !text.trim().is_empty() {
+                    output.push_str(&text);
+                    output.push('\n');
+                }
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+    }
+    output.push_str(&String::from_utf8_lossy(&line));
+
+    let status = child.status().await?;
+    if status.success() {
+        return Ok(());
+    }
+    if is_authentication_error(&output) {
+        Err(CloneError::AuthenticationFailed.into())
+    } else if output.contains("already exists and is not an empty directory") {
+        Err(CloneError::DestinationExists.into())
+    } else {
+        Err(anyhow!("git clone failed: {}", output.trim()))
+    }
+}
+
+/// Parses a progress line of `git clone`, like `Receiving objects:  42% (420/1000)`.
+fn parse_progress(line: &str) -> Option<CloneProgress> {
+    let line = line.trim().strip_prefix("remote:").unwrap_or(line).trim();
+    let (stage, rest) = line.split_once(':')?;
+    let rest = rest.trim_start();
+    let percentage = match rest.split_once('%') {
+        Some((percentage, _)) => Some(percentage.trim().parse::<u8>().ok()?),
+        None if rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_digit()) => None,
+        None => return None,
+    };
+    Some(CloneProgress {
+        stage: stage.trim().to_string(),
+        percentage,
+    })
+}
+
+fn is_authentication_error(output: &str) -> bool {
+    output.contains("Authentication failed")
+        || output.contains("could not read Username")
+        || output.contains("could not read Password")
+        || output.contains("terminal prompts disabled")
+        || output.contains("The requested URL returned error: 401")
+        || output.contains("The requested URL returned error: 403")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_name() {
+        assert_eq!(
+            repository_name("https://github.com/zed-industries/zed.git"),
+            Some("zed")
+        );
+        assert_eq!(
+            repository_name("https://gitlab.com/group/subgroup/project/"),
+            Some("project")
+        );
+        assert_eq!(
+            repository_name("git@github.com:zed-industries/zed.git"),
+            Some("zed")
+        );
+        assert_eq!(repository_name("git@host:repo"), Some("repo"));
+        assert_eq!(repository_name("https://github.com/"), Some("github.com"));
+        assert_eq!(repository_name(""), None);
+    }
+
+    #[test]
+    fn test_credentials_url() {
+        assert_eq!(
+            credentials_url("https://github.com/zed-industries/zed.git").as_deref(),
+            Some("https://github.com")
+        );
+        assert_eq!(
+            credentials_url("http://example.com:8080/repo.git").as_deref(),
+            Some("http://example.com:8080")
+        );
+        assert_eq!(
+            credentials_url("git@github.com:zed-industries/zed.git"),
+            None
+        );
+        assert_eq!(credentials_url("ssh://git@github.com/zed.git"), None);
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            parse_progress("Receiving objects:  42% (420/1000), 1.20 MiB | 2.00 MiB/s"),
+            Some(CloneProgress {
+                stage: "Receiving objects".into(),
+                percentage: Some(42),
+            })
+        );
+        assert_eq!(
+            parse_progress("remote: Counting objects: 100% (10/10), done."),
+            Some(CloneProgress {
+                stage: "Counting objects".into(),
+                percentage: Some(100),
+            })
+        );
+        assert_eq!(
+            parse_progress("remote: Enumerating objects: 1234, done."),
+            Some(CloneProgress {
+                stage: "Enumerating objects".into(),
+                percentage: None,
+            })
+        );
+        assert_eq!(parse_progress("Cloning into 'zed'..."), None);
+        assert_eq!(
+            parse_progress("fatal: Authentication failed for 'https://github.com/a/b.git/'"),
+            None
+        );
+    }
+}
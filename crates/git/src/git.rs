@@ -12,6 +12,7 @@ pub use git2 as libgit;
 pub use crate::hosting_provider::*;
 
 pub mod blame;
+pub mod clone;
 pub mod commit;
 pub mod diff;
 pub mod repository;
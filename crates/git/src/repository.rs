@@ -8,7 +8,9 @@ use rope::Rope;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
+    io::Write,
     path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
     sync::Arc,
 };
 use sum_tree::MapSeekTarget;
@@ -22,9 +24,44 @@ pub struct Branch {
     pub unix_timestamp: Option<i64>,
 }
 
+/// How a commit should be created, on top of the message.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommitOptions {
+    /// Replace the tip of the current branch instead of creating a new commit.
+    pub amend: bool,
+    /// Add a `Signed-off-by` trailer for the committer.
+    pub sign_off: bool,
+    /// Cryptographically sign the commit. When `None`, the repository's
+    /// `commit.gpgSign` configuration applies.
+    pub signing: Option<CommitSigning>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommitSigning {
+    /// The kind of signature to create, overriding the `gpg.format` configuration.
+    pub format: Option<SigningFormat>,
+    /// The key to sign with, overriding the `user.signingKey` configuration.
+    pub key: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningFormat {
+    Gpg,
+    Ssh,
+}
+
 pub trait GitRepository: Send + Sync {
     fn reload_index(&self);
 
+    /// Returns the path to the repository's git directory. For linked worktrees and
+    /// submodules, this is the directory the `.git` file points to.
+    fn path(&self) -> PathBuf;
+
+    /// Returns the path to the git directory shared by a repository and its linked
+    /// worktrees. This is the same as [`GitRepository::path`] unless the repository is a
+    /// linked worktree.
+    fn main_repository_path(&self) -> PathBuf;
+
     /// Loads a git repository entry's contents.
     /// Note that for symlink entries, this will return the contents of the symlink, not the target.
     fn load_index_text(&self, relative_file_path: &Path) -> Option<String>;
@@ -42,6 +79,35 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, _: &str) -> Result<()>;
     fn create_branch(&self, _: &str) -> Result<()>;
 
+    /// Returns the names of the repository's tags.
+    fn tags(&self) -> Result<Vec<String>>;
+
+    /// Loads the contents of a file as of the given branch, tag or commit.
+    /// Returns `None` if the file does not exist at that revision.
+    fn load_ref_text(&self, reference: &str, relative_file_path: &Path) -> Result<Option<String>>;
+
+    /// Returns the paths whose contents in the working directory differ from the given
+    /// branch, tag or commit, including untracked files.
+    fn changed_paths(&self, reference: &str) -> Result<Vec<RepoPath>>;
+
+    /// Returns the SHA of the best common ancestor of two branches, tags or commits.
+    fn merge_base(&self, first: &str, second: &str) -> Result<String>;
+
+    /// Initializes and updates the repository's submodules, recursively.
+    fn update_submodules(&self) -> Result<()>;
+
+    /// Returns the message of the commit at HEAD.
+    fn head_message(&self) -> Option<String>;
+
+    /// Returns the diff of the staged changes against HEAD, as a patch.
+    fn staged_diff(&self) -> Result<String>;
+
+    /// Returns the paths of the files with staged changes.
+    fn staged_paths(&self) -> Result<Vec<RepoPath>>;
+
+    /// Commits the staged changes with the given message.
+    fn commit(&self, message: &str, options: &CommitOptions) -> Result<()>;
+
     fn blame(&self, path: &Path, content: Rope) -> Result<crate::blame::Blame>;
 }
 
@@ -81,6 +147,14 @@ impl GitRepository for RealGitRepository {
         }
     }
 
+    fn path(&self) -> PathBuf {
+        self.repository.lock().path().to_path_buf()
+    }
+
+    fn main_repository_path(&self) -> PathBuf {
+        self.repository.lock().commondir().to_path_buf()
+    }
+
     fn load_index_text(&self, relative_file_path: &Path) -> Option<String> {
         fn logic(repo: &git2::Repository, relative_file_path: &Path) -> Result<Option<String>> {
             const STAGE_NORMAL: i32 = 0;
@@ -179,6 +253,229 @@ impl GitRepository for RealGitRepository {
         Ok(())
     }
 
+    fn tags(&self) -> Result<Vec<String>> {
+        let repo = self.repository.lock();
+        let tags = repo.tag_names(None)?;
+        Ok(tags.iter().flatten().map(String::from).collect())
+    }
+
+    fn load_ref_text(&self, reference: &str, relative_file_path: &Path) -> Result<Option<String>> {
+        check_path_to_repo_path_errors(relative_file_path)?;
+
+        let repo = self.repository.lock();
+        let tree = repo
+            .revparse_single(reference)
+            .with_context(|| format!("failed to resolve git revision {reference:?}"))?
+            .peel_to_tree()?;
+        let entry = match tree.get_path(relative_file_path) {
+            Ok(entry) => entry,
+            Err(error) if error.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        if entry.filemode() == GIT_MODE_SYMLINK as i32 {
+            return Ok(None);
+        }
+
+        let content = entry.to_object(&repo)?.peel_to_blob()?.content().to_owned();
+        Ok(Some(String::from_utf8(content)?))
+    }
+
+    fn changed_paths(&self, reference: &str) -> Result<Vec<RepoPath>> {
+        let repo = self.repository.lock();
+        let tree = repo
+            .revparse_single(reference)
+            .with_context(|| format!("failed to resolve git revision {reference:?}"))?
+            .peel_to_tree()?;
+        let mut options = git2::DiffOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        let diff = repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))?;
+
+        let mut paths = diff
+            .deltas()
+            .filter_map(|delta| {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())?;
+                Some(RepoPath::new(path.to_path_buf()))
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    fn merge_base(&self, first: &str, second: &str) -> Result<String> {
+        let repo = self.repository.lock();
+        let first = repo
+            .revparse_single(first)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("failed to resolve git revision {first:?}"))?;
+        let second = repo
+            .revparse_single(second)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("failed to resolve git revision {second:?}"))?;
+        let oid = repo.merge_base(first.id(), second.id())?;
+        Ok(oid.to_string())
+    }
+
+    fn update_submodules(&self) -> Result<()> {
+        let working_directory = self
+            .repository
+            .lock()
+            .workdir()
+            .context("failed to read git work directory")?
+            .to_path_buf();
+
+        let mut command = Command::new(&self.git_binary_path);
+        command
+            .current_dir(&working_directory)
+            .args(["submodule", "update", "--init", "--recursive"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+
+        let output = command
+            .output()
+            .context("failed to start git submodule process")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git submodule process failed: {}", stderr);
+        }
+        Ok(())
+    }
+
+    fn head_message(&self) -> Option<String> {
+        let repo = self.repository.lock();
+        let commit = repo.head().ok()?.peel_to_commit().ok()?;
+        let message = String::from_utf8_lossy(commit.message_bytes()).into_owned();
+        Some(message)
+    }
+
+    fn staged_diff(&self) -> Result<String> {
+        let working_directory = self
+            .repository
+            .lock()
+            .workdir()
+            .context("failed to read git work directory")?
+            .to_path_buf();
+
+        let mut command = Command::new(&self.git_binary_path);
+        command
+            .current_dir(&working_directory)
+            .args([
+                "--no-optional-locks",
+                "diff",
+                "--cached",
+                "--no-color",
+                "--no-ext-diff",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+
+        let output = command
+            .output()
+            .context("failed to start git diff process")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git diff process failed: {}", stderr);
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn staged_paths(&self) -> Result<Vec<RepoPath>> {
+        let repo = self.repository.lock();
+        // Everything in the index is staged on a branch without commits.
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+        let paths = diff
+            .deltas()
+            .filter_map(|delta| {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())?;
+                Some(RepoPath::new(path.to_path_buf()))
+            })
+            .collect();
+        Ok(paths)
+    }
+
+    fn commit(&self, message: &str, options: &CommitOptions) -> Result<()> {
+        let working_directory = self
+            .repository
+            .lock()
+            .workdir()
+            .context("failed to read git work directory")?
+            .to_path_buf();
+
+        let mut command = Command::new(&self.git_binary_path);
+        command.current_dir(&working_directory);
+        if let Some(signing) = &options.signing {
+            if let Some(format) = signing.format {
+                let format = match format {
+                    SigningFormat::Gpg => "openpgp",
+                    SigningFormat::Ssh => "ssh",
+                };
+                command.arg("-c").arg(format!("gpg.format={format}"));
+            }
+            if let Some(key) = &signing.key {
+                command.arg("-c").arg(format!("user.signingKey={key}"));
+            }
+        }
+        command.args(["commit", "--file=-"]);
+        if options.amend {
+            command.arg("--amend");
+        }
+        if options.sign_off {
+            command.arg("--signoff");
+        }
+        if options.signing.is_some() {
+            command.arg("--gpg-sign");
+        }
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+
+        let mut child = command
+            .spawn()
+            .context("failed to start git commit process")?;
+        child
+            .stdin
+            .take()
+            .context("failed to open git commit stdin")?
+            .write_all(message.as_bytes())
+            .context("failed to write commit message")?;
+        let output = child
+            .wait_with_output()
+            .context("failed to read git commit output")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git commit process failed: {}", stderr);
+        }
+        Ok(())
+    }
+
     fn blame(&self, path: &Path, content: Rope) -> Result<crate::blame::Blame> {
         let working_directory = self
             .repository
@@ -208,10 +505,17 @@ pub struct FakeGitRepository {
 
 #[derive(Debug, Clone, Default)]
 pub struct FakeGitRepositoryState {
+    pub path: PathBuf,
     pub index_contents: HashMap<PathBuf, String>,
     pub blames: HashMap<PathBuf, Blame>,
     pub worktree_statuses: HashMap<RepoPath, GitFileStatus>,
     pub branch_name: Option<String>,
+    /// File contents at each branch, tag or commit, keyed by the reference's name.
+    pub ref_contents: HashMap<String, HashMap<PathBuf, String>>,
+    /// Messages of the commits made through [`GitRepository::commit`], oldest first.
+    pub commit_messages: Vec<String>,
+    pub staged_diff: String,
+    pub staged_paths: Vec<RepoPath>,
 }
 
 impl FakeGitRepository {
@@ -223,6 +527,14 @@ impl FakeGitRepository {
 impl GitRepository for FakeGitRepository {
     fn reload_index(&self) {}
 
+    fn path(&self) -> PathBuf {
+        self.state.lock().path.clone()
+    }
+
+    fn main_repository_path(&self) -> PathBuf {
+        self.state.lock().path.clone()
+    }
+
     fn load_index_text(&self, path: &Path) -> Option<String> {
         let state = self.state.lock();
         state.index_contents.get(path).cloned()
@@ -279,6 +591,73 @@ impl GitRepository for FakeGitRepository {
         Ok(())
     }
 
+    fn tags(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    fn load_ref_text(&self, reference: &str, relative_file_path: &Path) -> Result<Option<String>> {
+        let state = self.state.lock();
+        let contents = state
+            .ref_contents
+            .get(reference)
+            .with_context(|| format!("failed to resolve git revision {reference:?}"))?;
+        Ok(contents.get(relative_file_path).cloned())
+    }
+
+    fn changed_paths(&self, reference: &str) -> Result<Vec<RepoPath>> {
+        let state = self.state.lock();
+        let contents = state
+            .ref_contents
+            .get(reference)
+            .with_context(|| format!("failed to resolve git revision {reference:?}"))?;
+        let mut paths = contents
+            .keys()
+            .chain(state.index_contents.keys())
+            .filter(|path| contents.get(*path) != state.index_contents.get(*path))
+            .map(|path| RepoPath::new(path.clone()))
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    fn merge_base(&self, _first: &str, second: &str) -> Result<String> {
+        let state = self.state.lock();
+        anyhow::ensure!(
+            state.ref_contents.contains_key(second),
+            "failed to resolve git revision {second:?}"
+        );
+        Ok(second.to_string())
+    }
+
+    fn update_submodules(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn head_message(&self) -> Option<String> {
+        self.state.lock().commit_messages.last().cloned()
+    }
+
+    fn staged_diff(&self) -> Result<String> {
+        Ok(self.state.lock().staged_diff.clone())
+    }
+
+    fn staged_paths(&self) -> Result<Vec<RepoPath>> {
+        Ok(self.state.lock().staged_paths.clone())
+    }
+
+    fn commit(&self, message: &str, options: &CommitOptions) -> Result<()> {
+        let mut state = self.state.lock();
+        if options.amend {
+            state
+                .commit_messages
+                .pop()
+                .context("there is no commit to amend")?;
+        }
+        state.commit_messages.push(message.to_owned());
+        Ok(())
+    }
+
     fn blame(&self, path: &Path, _content: Rope) -> Result<crate::blame::Blame> {
         let state = self.state.lock();
         state
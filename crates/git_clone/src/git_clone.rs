@@ -0,0 +1,337 @@
+use anyhow::Result;
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use futures::{channel::mpsc, StreamExt as _};
+use git::clone::{credentials_url, repository_name, CloneError, CloneProgress, GitCredentials};
+use gpui::{
+    AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView,
+    PathPromptOptions, Render, SharedString, Subscription, Task, View, ViewContext, WeakView,
+};
+use std::{path::PathBuf, sync::Arc};
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+use zed_actions::CloneRepository;
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &CloneRepository, cx| {
+            let fs = workspace.app_state().fs.clone();
+            let workspace_handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, |cx| CloneRepositoryModal::new(fs, workspace_handle, cx));
+        });
+    })
+    .detach();
+}
+
+enum CloneState {
+    EnteringUrl,
+    ChoosingDestination,
+    Cloning {
+        destination: PathBuf,
+        progress: Option<CloneProgress>,
+    },
+    /// The remote asked for credentials, which are entered to retry the clone.
+    Authenticating {
+        destination: PathBuf,
+    },
+    Failed(SharedString),
+}
+
+/// Asks for the URL of a repository and where to clone it, clones it and opens it.
+pub struct CloneRepositoryModal {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    url_editor: View<Editor>,
+    username_editor: View<Editor>,
+    password_editor: View<Editor>,
+    state: CloneState,
+    pending_clone: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CloneRepositoryModal {
+    fn new(fs: Arc<dyn Fs>, workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        let url_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("https://github.com/owner/repository.git", cx);
+            editor
+        });
+        let username_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Username", cx);
+            editor
+        });
+        let password_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_masked(true, cx);
+            editor.set_placeholder_text("Password or access token", cx);
+            editor
+        });
+        let subscriptions = vec![cx.subscribe(&url_editor, |this, _, event, cx| {
+            if let EditorEvent::BufferEdited(_) = event {
+                if let CloneState::Failed(_) = this.state {
+                    this.state = CloneState::EnteringUrl;
+                    cx.notify();
+                }
+            }
+        })];
+        Self {
+            fs,
+            workspace,
+            url_editor,
+            username_editor,
+            password_editor,
+            state: CloneState::EnteringUrl,
+            pending_clone: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn url(&self, cx: &AppContext) -> String {
+        self.url_editor.read(cx).text(cx).trim().to_string()
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        match &self.state {
+            CloneState::EnteringUrl | CloneState::Failed(_) => self.choose_destination(cx),
+            CloneState::Authenticating { destination } => {
+                let destination = destination.clone();
+                let credentials = GitCredentials {
+                    username: self.username_editor.read(cx).text(cx),
+                    password: self.password_editor.read(cx).text(cx),
+                };
+                self.clone_repository(destination, Some(credentials), cx);
+            }
+            CloneState::ChoosingDestination | CloneState::Cloning { .. } => {}
+        }
+    }
+
+    fn choose_destination(&mut self, cx: &mut ViewContext<Self>) {
+        let url = self.url(cx);
+        let Some(name) = repository_name(&url).map(ToString::to_string) else {
+            self.state = CloneState::Failed("Enter the URL of a repository to clone.".into());
+            cx.notify();
+            return;
+        };
+
+        self.state = CloneState::ChoosingDestination;
+        cx.notify();
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+        });
+        self.pending_clone = Some(cx.spawn(|this, mut cx| async move {
+            let parent = match paths.await {
+                Ok(Ok(Some(mut paths))) => paths.pop(),
+                Ok(Ok(None)) | Err(_) => None,
+                Ok(Err(error)) => {
+                    this.update(&mut cx, |this, cx| {
+                        this.state = CloneState::Failed(error.to_string().into());
+                        cx.notify();
+                    })
+                    .ok();
+                    return;
+                }
+            };
+            this.update(&mut cx, |this, cx| match parent {
+                Some(parent) => this.clone_repository(parent.join(name), None, cx),
+                None => {
+                    this.state = CloneState::EnteringUrl;
+                    cx.notify();
+                }
+            })
+            .ok();
+        }));
+    }
+
+    /// Clones the repository into the given directory, authenticating with the given credentials
+    /// or, without any, the ones stored in the keychain for the repository's host.
+    fn clone_repository(
+        &mut self,
+        destination: PathBuf,
+        credentials: Option<GitCredentials>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let url = self.url(cx);
+        let credentials_url = credentials_url(&url);
+        let stored_credentials = match (&credentials, &credentials_url) {
+            (None, Some(credentials_url)) => Some(cx.read_credentials(credentials_url)),
+            _ => None,
+        };
+        let (progress_tx, mut progress_rx) = mpsc::unbounded();
+        let fs = self.fs.clone();
+
+        self.state = CloneState::Cloning {
+            destination: destination.clone(),
+            progress: None,
+        };
+        cx.notify();
+        self.pending_clone = Some(cx.spawn(|this, mut cx| async move {
+            let stored_credentials = match stored_credentials {
+                Some(task) => task
+                    .await
+                    .log_err()
+                    .flatten()
+                    .and_then(|(username, password)| {
+                        Some(GitCredentials {
+                            username,
+                            password: String::from_utf8(password).ok()?,
+                        })
+                    }),
+                None => None,
+            };
+            let clone = fs.git_clone(
+                &url,
+                &destination,
+                credentials.clone().or(stored_credentials),
+                progress_tx,
+            );
+            let mut progress_cx = cx.clone();
+            let progress = async {
+                while let Some(update) = progress_rx.next().await {
+                    this.update(&mut progress_cx, |this, cx| {
+                        if let CloneState::Cloning { progress, .. } = &mut this.state {
+                            *progress = Some(update);
+                            cx.notify();
+                        }
+                    })
+                    .ok();
+                }
+            };
+            let (result, _) = futures::join!(clone, progress);
+
+            this.update(&mut cx, |this, cx| {
+                this.finish_cloning(result, destination, credentials, credentials_url, cx)
+            })
+            .ok();
+        }));
+    }
+
+    fn finish_cloning(
+        &mut self,
+        result: Result<()>,
+        destination: PathBuf,
+        credentials: Option<GitCredentials>,
+        credentials_url: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match result {
+            Ok(()) => {
+                if let Some((credentials, credentials_url)) = credentials.zip(credentials_url) {
+                    cx.write_credentials(
+                        &credentials_url,
+                        &credentials.username,
+                        credentials.password.as_bytes(),
+                    )
+                    .detach_and_log_err(cx);
+                }
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        workspace
+                            .open_workspace_for_paths(false, vec![destination], cx)
+                            .detach_and_log_err(cx);
+                    })
+                    .ok();
+                cx.emit(DismissEvent);
+            }
+            Err(error) => match error.downcast_ref::<CloneError>() {
+                Some(CloneError::AuthenticationFailed) => {
+                    self.state = CloneState::Authenticating { destination };
+                    cx.focus_view(&self.username_editor);
+                }
+                Some(CloneError::DestinationExists) => {
+                    self.state = CloneState::Failed(
+                        format!("{} already exists and isn't empty.", destination.display()).into(),
+                    );
+                }
+                None => self.state = CloneState::Failed(error.to_string().into()),
+            },
+        }
+        cx.notify();
+    }
+
+    fn render_editor(&self, editor: &View<Editor>, cx: &mut ViewContext<Self>) -> Div {
+        div()
+            .px_1()
+            .py_0p5()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_md()
+            .child(editor.clone())
+    }
+
+    fn render_status(&self) -> Option<AnyElement> {
+        let (label, color) = match &self.state {
+            CloneState::EnteringUrl => return None,
+            CloneState::ChoosingDestination => (
+                SharedString::from("Choose where to clone the repository…"),
+                Color::Muted,
+            ),
+            CloneState::Cloning {
+                destination,
+                progress,
+            } => {
+                let label = match progress {
+                    Some(CloneProgress {
+                        stage,
+                        percentage: Some(percentage),
+                    }) => format!("{stage}: {percentage}%"),
+                    Some(CloneProgress { stage, .. }) => format!("{stage}…"),
+                    None => format!("Cloning into {}…", destination.display()),
+                };
+                (SharedString::from(label), Color::Muted)
+            }
+            CloneState::Authenticating { .. } => (
+                SharedString::from(concat!(
+                    "The repository requires authentication. ",
+                    "The credentials will be stored in the system keychain."
+                )),
+                Color::Warning,
+            ),
+            CloneState::Failed(error) => (error.clone(), Color::Error),
+        };
+        Some(
+            Label::new(label)
+                .size(LabelSize::Small)
+                .color(color)
+                .into_any_element(),
+        )
+    }
+}
+
+impl ModalView for CloneRepositoryModal {}
+
+impl EventEmitter<DismissEvent> for CloneRepositoryModal {}
+
+impl FocusableView for CloneRepositoryModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.url_editor.focus_handle(cx)
+    }
+}
+
+impl Render for CloneRepositoryModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let authenticating = matches!(self.state, CloneState::Authenticating { .. });
+        v_flex()
+            .elevation_2(cx)
+            .key_context("CloneRepositoryModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .w(rems(34.))
+            .p_2()
+            .gap_2()
+            .child(Headline::new("Clone Repository").size(HeadlineSize::XSmall))
+            .child(self.render_editor(&self.url_editor, cx))
+            .when(authenticating, |this| {
+                this.child(self.render_editor(&self.username_editor, cx))
+                    .child(self.render_editor(&self.password_editor, cx))
+            })
+            .children(self.render_status())
+    }
+}
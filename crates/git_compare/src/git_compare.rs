@@ -0,0 +1,322 @@
+mod ref_diff_editor;
+mod ref_picker;
+mod review;
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::repository::{GitRepository, RepoPath};
+use gpui::{actions, AppContext, Model, Task, ViewContext, VisualContext as _};
+use language::Buffer;
+use project::{Fs, Project, Worktree};
+use std::sync::Arc;
+use util::ResultExt as _;
+use workspace::{
+    notifications::{NotifyResultExt as _, NotifyTaskExt as _},
+    Workspace,
+};
+
+pub use ref_diff_editor::RefDiffEditor;
+pub use review::ReviewEditor;
+
+use ref_diff_editor::FileDiff;
+use ref_picker::RefPicker;
+
+actions!(
+    git_compare,
+    [
+        CompareActiveFileWithRef,
+        DiffProjectAgainstRef,
+        ReviewBranch
+    ]
+);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &CompareActiveFileWithRef, cx| {
+            compare_active_file_with_ref(workspace, cx).notify_err(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &DiffProjectAgainstRef, cx| {
+            diff_project_against_ref(workspace, CompareTarget::Project, cx)
+                .notify_err(workspace, cx);
+        });
+        workspace.register_action(|workspace, _: &ReviewBranch, cx| {
+            diff_project_against_ref(workspace, CompareTarget::Review, cx)
+                .notify_err(workspace, cx);
+        });
+    })
+    .detach();
+}
+
+/// What to compare against the ref once it has been picked.
+#[derive(Clone)]
+pub(crate) enum CompareTarget {
+    File {
+        buffer: Model<Buffer>,
+        repo: Arc<dyn GitRepository>,
+        repo_path: RepoPath,
+    },
+    Project,
+    /// Review the current branch against its merge base with the picked ref.
+    Review,
+}
+
+fn compare_active_file_with_ref(
+    workspace: &mut Workspace,
+    cx: &mut ViewContext<Workspace>,
+) -> Result<()> {
+    let editor = workspace
+        .active_item_as::<Editor>(cx)
+        .context("no active editor")?;
+    let buffer = editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+        .context("the active editor does not contain a single file")?;
+    let file = project::File::from_dyn(buffer.read(cx).file()).context("the file is not saved")?;
+    let worktree = file
+        .worktree
+        .read(cx)
+        .as_local()
+        .context("comparing files of remote projects is not supported")?
+        .snapshot();
+    let (repo_entry, local_repo_entry) = worktree
+        .repo_for_path(&file.path)
+        .context("the file is not in a git repository")?;
+    let repo_path = repo_entry.relativize(&worktree, &file.path)?;
+    let repo = local_repo_entry.repo().clone();
+
+    let refs = refs(repo.as_ref())?;
+    let target = CompareTarget::File {
+        buffer,
+        repo,
+        repo_path,
+    };
+    let weak_workspace = cx.view().downgrade();
+    workspace.toggle_modal(cx, |cx| RefPicker::new(weak_workspace, target, refs, cx));
+    Ok(())
+}
+
+fn diff_project_against_ref(
+    workspace: &mut Workspace,
+    target: CompareTarget,
+    cx: &mut ViewContext<Workspace>,
+) -> Result<()> {
+    let repo = workspace
+        .project()
+        .read(cx)
+        .get_first_worktree_root_repo(cx)
+        .context("the project's first folder is not a git repository")?;
+    let refs = refs(repo.as_ref())?;
+    let weak_workspace = cx.view().downgrade();
+    workspace.toggle_modal(cx, |cx| RefPicker::new(weak_workspace, target, refs, cx));
+    Ok(())
+}
+
+/// Returns the refs offered by the picker: `HEAD`, then the local branches, then the tags.
+fn refs(repo: &dyn GitRepository) -> Result<Vec<String>> {
+    let mut branches = repo.branches()?;
+    branches.sort_unstable_by(|lhs, rhs| {
+        rhs.is_head
+            .cmp(&lhs.is_head)
+            .then(rhs.unix_timestamp.cmp(&lhs.unix_timestamp))
+    });
+    let mut tags = repo.tags()?;
+    tags.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+
+    Ok(std::iter::once("HEAD".to_string())
+        .chain(branches.into_iter().map(|branch| branch.name.into()))
+        .chain(tags)
+        .collect())
+}
+
+impl CompareTarget {
+    pub(crate) fn open(
+        &self,
+        workspace: &mut Workspace,
+        reference: String,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        match self {
+            CompareTarget::File {
+                buffer,
+                repo,
+                repo_path,
+            } => compare_file_with_ref(workspace, buffer, repo.clone(), repo_path, reference, cx),
+            CompareTarget::Project => diff_worktrees_against_ref(workspace, reference, cx),
+            CompareTarget::Review => review::review_branch(workspace, reference, cx),
+        }
+    }
+}
+
+fn compare_file_with_ref(
+    workspace: &mut Workspace,
+    buffer: &Model<Buffer>,
+    repo: Arc<dyn GitRepository>,
+    repo_path: &RepoPath,
+    reference: String,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let Some(file) = buffer.read(cx).file().cloned() else {
+        return;
+    };
+    let text = buffer.read(cx).text();
+    let project = workspace.project().clone();
+    let repo_path = repo_path.clone();
+    let load_base_text = cx.background_executor().spawn(async move {
+        repo.load_ref_text(&reference, &repo_path)
+            .map(|text| (reference, text))
+    });
+
+    cx.spawn(|workspace, mut cx| async move {
+        let (reference, base_text) = load_base_text.await?;
+        workspace.update(&mut cx, |workspace, cx| {
+            let title = format!("{} ({reference})", file.file_name(cx).to_string_lossy());
+            let files = vec![FileDiff {
+                file,
+                text,
+                base_text: base_text.unwrap_or_default(),
+            }];
+            let view = cx.new_view(|cx| {
+                RefDiffEditor::new(project, reference.into(), title.into(), files, true, cx)
+            });
+            workspace.add_item_to_active_pane(Box::new(view), None, true, cx);
+        })
+    })
+    .detach_and_notify_err(cx);
+}
+
+/// A repository inside one of the project's worktrees.
+pub(crate) struct WorktreeRepository {
+    snapshot: worktree::Snapshot,
+    entry: project::RepositoryEntry,
+    repo: Arc<dyn GitRepository>,
+}
+
+fn diff_worktrees_against_ref(
+    workspace: &mut Workspace,
+    reference: String,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let fs = workspace.app_state().fs.clone();
+    let load_files = load_file_diffs(
+        fs,
+        worktree_repositories(&project, cx),
+        reference.clone(),
+        cx,
+    );
+
+    cx.spawn(|workspace, mut cx| async move {
+        let files = load_files.await;
+        workspace.update(&mut cx, |workspace, cx| {
+            let title = format!("Changes since {reference}");
+            let view = cx.new_view(|cx| {
+                RefDiffEditor::new(project, reference.into(), title.into(), files, false, cx)
+            });
+            workspace.add_item_to_active_pane(Box::new(view), None, true, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Loads the files of the repositories that differ from the reference in the working copy.
+pub(crate) fn load_file_diffs(
+    fs: Arc<dyn Fs>,
+    repositories: Vec<(Model<Worktree>, WorktreeRepository)>,
+    reference: String,
+    cx: &AppContext,
+) -> Task<Vec<FileDiff>> {
+    let (worktrees, repositories): (Vec<_>, Vec<_>) = repositories.into_iter().unzip();
+    let load_files = cx.background_executor().spawn(async move {
+        let mut files = Vec::new();
+        for (ix, repository) in repositories.iter().enumerate() {
+            let Some(changed_paths) = repository.repo.changed_paths(&reference).log_err() else {
+                continue;
+            };
+            for repo_path in changed_paths {
+                let Some(path) = repository
+                    .entry
+                    .unrelativize(&repository.snapshot, &repo_path)
+                else {
+                    continue;
+                };
+                let Some(base_text) = repository
+                    .repo
+                    .load_ref_text(&reference, &repo_path)
+                    .log_err()
+                else {
+                    continue;
+                };
+                let abs_path = repository.snapshot.abs_path().join(&path);
+                let text = if fs.is_file(&abs_path).await {
+                    let Some(text) = fs.load(&abs_path).await.log_err() else {
+                        continue;
+                    };
+                    text
+                } else {
+                    String::new()
+                };
+                let base_text = base_text.unwrap_or_default();
+                if text != base_text {
+                    files.push((ix, path, text, base_text));
+                }
+            }
+        }
+        (repositories, files)
+    });
+
+    cx.spawn(|_| async move {
+        let (repositories, files) = load_files.await;
+        files
+            .into_iter()
+            .map(|(ix, path, text, base_text)| {
+                let repository = &repositories[ix];
+                let file = project::File {
+                    worktree: worktrees[ix].clone(),
+                    entry_id: repository
+                        .snapshot
+                        .entry_for_path(&path)
+                        .map(|entry| entry.id),
+                    path,
+                    mtime: None,
+                    is_local: true,
+                    is_deleted: false,
+                    is_private: false,
+                };
+                FileDiff {
+                    file: Arc::new(file),
+                    text,
+                    base_text,
+                }
+            })
+            .collect()
+    })
+}
+
+fn worktree_repositories(
+    project: &Model<Project>,
+    cx: &AppContext,
+) -> Vec<(Model<Worktree>, WorktreeRepository)> {
+    let mut repositories = Vec::new();
+    for worktree in project.read(cx).visible_worktrees(cx) {
+        let Some(local_worktree) = worktree.read(cx).as_local() else {
+            continue;
+        };
+        let snapshot = local_worktree.snapshot();
+        for (_, entry) in snapshot.repositories() {
+            let Some(local_repo_entry) = local_worktree.get_local_repo(entry) else {
+                continue;
+            };
+            repositories.push((
+                worktree.clone(),
+                WorktreeRepository {
+                    snapshot: (*snapshot).clone(),
+                    entry: entry.clone(),
+                    repo: local_repo_entry.repo().clone(),
+                },
+            ));
+        }
+    }
+    repositories
+}
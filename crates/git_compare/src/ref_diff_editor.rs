@@ -0,0 +1,297 @@
+use editor::{
+    actions::ExpandAllHunkDiffs, Editor, EditorEvent, ExcerptRange, MultiBuffer,
+    DEFAULT_MULTIBUFFER_CONTEXT,
+};
+use gpui::{
+    AnyElement, AnyView, AppContext, AsyncAppContext, EventEmitter, FocusHandle, FocusableView,
+    Model, ModelContext, Render, Task, View, ViewContext,
+};
+use language::{Anchor, Bias, Buffer, Capability, LanguageRegistry, Point, TextBuffer};
+use project::Project;
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::item::{Item, ItemEvent, TabContentParams};
+use workspace::ItemNavHistory;
+
+/// A file's contents in the working copy, along with its contents at the compared revision.
+pub(crate) struct FileDiff {
+    pub file: Arc<dyn language::File>,
+    pub text: String,
+    /// The file's contents at the compared revision, empty if it did not exist there.
+    pub base_text: String,
+}
+
+/// Shows how one or more files differ from a git branch, tag or commit.
+///
+/// The files are read-only snapshots of the working copy, taken when the view is opened,
+/// with the contents at the revision as their diff base.
+pub struct RefDiffEditor {
+    reference: SharedString,
+    title: SharedString,
+    multibuffer: Model<MultiBuffer>,
+    editor: View<Editor>,
+    focus_handle: FocusHandle,
+    loading: bool,
+    _load_task: Task<()>,
+}
+
+impl RefDiffEditor {
+    /// Creates the view. When `whole_files` is false, only the changed hunks of each file are
+    /// shown, with some surrounding context.
+    pub(crate) fn new(
+        project: Model<Project>,
+        reference: SharedString,
+        title: SharedString,
+        files: Vec<FileDiff>,
+        whole_files: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let multibuffer = cx.new_model(|cx| {
+            MultiBuffer::new(project.read(cx).replica_id(), Capability::ReadOnly)
+                .with_title(title.to_string())
+        });
+        let editor = cx.new_view(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), true, cx);
+            editor.set_vertical_scroll_margin(5, cx);
+            editor
+        });
+        cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+        let focus_handle = cx.focus_handle();
+        cx.on_focus_in(&focus_handle, |this, cx| {
+            if !this.loading {
+                this.editor.focus_handle(cx).focus(cx);
+            }
+        })
+        .detach();
+
+        let language_registry = project.read(cx).languages().clone();
+        let buffers = build_diff_buffers(files, &language_registry, cx);
+        let load_task = cx.spawn(|this, mut cx| async move {
+            recalculate_diffs(&buffers, &language_registry, &mut cx).await;
+
+            this.update(&mut cx, |this, cx| {
+                this.multibuffer.update(cx, |multibuffer, cx| {
+                    for buffer in buffers {
+                        if whole_files {
+                            let len = buffer.read(cx).len();
+                            multibuffer.push_excerpts(
+                                buffer,
+                                [ExcerptRange {
+                                    context: 0..len,
+                                    primary: None,
+                                }],
+                                cx,
+                            );
+                        } else {
+                            push_hunk_excerpts(multibuffer, buffer, cx);
+                        }
+                    }
+                });
+                this.editor.update(cx, |editor, cx| {
+                    editor.expand_all_hunk_diffs(&ExpandAllHunkDiffs, cx)
+                });
+                this.loading = false;
+                if this.focus_handle.is_focused(cx) {
+                    this.editor.focus_handle(cx).focus(cx);
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+
+        Self {
+            reference,
+            title,
+            multibuffer,
+            editor,
+            focus_handle,
+            loading: true,
+            _load_task: load_task,
+        }
+    }
+}
+
+/// Creates read-only buffers for the files, with their contents at the revision as the diff base.
+pub(crate) fn build_diff_buffers(
+    files: Vec<FileDiff>,
+    language_registry: &Arc<LanguageRegistry>,
+    cx: &mut AppContext,
+) -> Vec<Model<Buffer>> {
+    files
+        .into_iter()
+        .map(|diff| {
+            cx.new_model(|cx| {
+                let text = TextBuffer::new(0, cx.entity_id().as_non_zero_u64().into(), diff.text);
+                let mut buffer = Buffer::build(
+                    text,
+                    Some(diff.base_text),
+                    Some(diff.file),
+                    Capability::ReadOnly,
+                );
+                buffer.set_language_registry(language_registry.clone());
+                buffer
+            })
+        })
+        .collect()
+}
+
+/// Assigns the buffers' languages and waits for their diffs to be computed.
+pub(crate) async fn recalculate_diffs(
+    buffers: &[Model<Buffer>],
+    language_registry: &Arc<LanguageRegistry>,
+    cx: &mut AsyncAppContext,
+) {
+    for buffer in buffers {
+        let Some(path) = buffer
+            .update(cx, |buffer, _| {
+                buffer.file().map(|file| file.path().clone())
+            })
+            .ok()
+            .flatten()
+        else {
+            continue;
+        };
+        let language = language_registry.language_for_file_path(&path).await.ok();
+        let diff_task = buffer
+            .update(cx, |buffer, cx| {
+                if language.is_some() {
+                    buffer.set_language(language, cx);
+                }
+                buffer.git_diff_recalc(cx)
+            })
+            .ok()
+            .flatten();
+        if let Some(diff_task) = diff_task {
+            diff_task.await;
+        }
+    }
+}
+
+/// Adds excerpts for the changed hunks of the buffer, with some surrounding context.
+pub(crate) fn push_hunk_excerpts(
+    multibuffer: &mut MultiBuffer,
+    buffer: Model<Buffer>,
+    cx: &mut ModelContext<MultiBuffer>,
+) {
+    let snapshot = buffer.read(cx).snapshot();
+    let ranges = snapshot
+        .git_diff_hunks_intersecting_range(Anchor::MIN..Anchor::MAX)
+        .map(|hunk| {
+            let rows = hunk.associated_range;
+            let start = Point::new(rows.start, 0);
+            let end = snapshot.clip_point(Point::new(rows.end, 0), Bias::Left);
+            start..end
+        })
+        .collect::<Vec<_>>();
+    if !ranges.is_empty() {
+        multibuffer.push_excerpts_with_context_lines(
+            buffer,
+            ranges,
+            DEFAULT_MULTIBUFFER_CONTEXT,
+            cx,
+        );
+    }
+}
+
+impl EventEmitter<EditorEvent> for RefDiffEditor {}
+
+impl FocusableView for RefDiffEditor {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RefDiffEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let message = if self.loading {
+            Some("Loading…".to_string())
+        } else if self.multibuffer.read(cx).excerpt_ids().is_empty() {
+            Some(format!("No changes against {}", self.reference))
+        } else {
+            None
+        };
+
+        let child = if let Some(message) = message {
+            div()
+                .bg(cx.theme().colors().editor_background)
+                .flex()
+                .items_center()
+                .justify_center()
+                .size_full()
+                .child(Label::new(message).color(Color::Muted))
+        } else {
+            div().size_full().child(self.editor.clone())
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(child)
+    }
+}
+
+impl Item for RefDiffEditor {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, cx| editor.deactivated(cx));
+    }
+
+    fn navigate(&mut self, data: Box<dyn Any>, cx: &mut ViewContext<Self>) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(format!("Changes against {}", self.reference).into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        h_flex()
+            .gap_1()
+            .child(Icon::new(IconName::FileGit).color(Color::Muted))
+            .child(Label::new(self.title.clone()).color(params.text_color()))
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn is_singleton(&self, _: &AppContext) -> bool {
+        false
+    }
+
+    fn set_nav_history(&mut self, nav_history: ItemNavHistory, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a View<Self>,
+        _: &'a AppContext,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::CompareTarget;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    rems, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Subscription,
+    Task, View, ViewContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+/// A modal for choosing the branch, tag or commit to compare against.
+pub(crate) struct RefPicker {
+    picker: View<Picker<RefPickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl RefPicker {
+    pub(crate) fn new(
+        workspace: WeakView<Workspace>,
+        target: CompareTarget,
+        refs: Vec<String>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let delegate = RefPickerDelegate {
+            workspace,
+            target,
+            refs,
+            matches: Vec::new(),
+            custom_ref: None,
+            selected_index: 0,
+        };
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for RefPicker {}
+impl EventEmitter<DismissEvent> for RefPicker {}
+
+impl FocusableView for RefPicker {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for RefPicker {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+pub(crate) struct RefPickerDelegate {
+    workspace: WeakView<Workspace>,
+    target: CompareTarget,
+    refs: Vec<String>,
+    matches: Vec<StringMatch>,
+    /// The query, when it does not name a known ref, so that commits can be compared against.
+    custom_ref: Option<String>,
+    selected_index: usize,
+}
+
+impl RefPickerDelegate {
+    fn reference_at(&self, ix: usize) -> Option<&str> {
+        match &self.custom_ref {
+            Some(custom_ref) if ix == 0 => Some(custom_ref),
+            Some(_) => self.matches.get(ix - 1).map(|hit| hit.string.as_str()),
+            None => self.matches.get(ix).map(|hit| hit.string.as_str()),
+        }
+    }
+}
+
+impl PickerDelegate for RefPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        match self.target {
+            CompareTarget::Review => "Review the current branch against...".into(),
+            _ => "Compare with branch, tag or commit...".into(),
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.custom_ref.is_some() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let candidates = self
+            .refs
+            .iter()
+            .enumerate()
+            .map(|(id, reference)| StringMatchCandidate::new(id, reference.clone()))
+            .collect::<Vec<_>>();
+        cx.spawn(move |picker, mut cx| async move {
+            let query = query.trim().to_string();
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(&mut cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.custom_ref = (!query.is_empty()
+                        && !matches.iter().any(|hit| hit.string == query))
+                    .then_some(query);
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(reference) = self.reference_at(self.selected_index) else {
+            return;
+        };
+        let reference = reference.to_string();
+        let target = self.target.clone();
+        self.workspace
+            .update(cx, |workspace, cx| target.open(workspace, reference, cx))
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let label = match &self.custom_ref {
+            Some(custom_ref) if ix == 0 => {
+                HighlightedLabel::new(format!("Compare with \"{custom_ref}\""), Vec::new())
+            }
+            _ => {
+                let hit = &self.matches[ix - self.custom_ref.is_some() as usize];
+                HighlightedLabel::new(hit.string.clone(), hit.positions.clone())
+            }
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .start_slot(label),
+        )
+    }
+}
@@ -0,0 +1,923 @@
+use crate::{
+    load_file_diffs,
+    ref_diff_editor::{build_diff_buffers, push_hunk_excerpts, recalculate_diffs},
+    WorktreeRepository,
+};
+use collections::HashSet;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{
+    actions::{Cancel, ExpandAllHunkDiffs},
+    scroll::Autoscroll,
+    Editor, EditorEvent, MultiBuffer,
+};
+use gpui::{
+    actions, AnyElement, AnyView, AppContext, EventEmitter, FocusHandle, FocusableView, Model,
+    Render, Task, View, ViewContext, VisualContext as _, WeakView,
+};
+use language::{Bias, Buffer, Capability, Point};
+use project::{Project, Worktree};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::{Any, TypeId},
+    fmt::Write as _,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
+use ui::{prelude::*, Checkbox, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    item::{Item, ItemEvent, TabContentParams},
+    notifications::NotifyTaskExt as _,
+    ItemNavHistory, Workspace,
+};
+
+actions!(
+    git_compare,
+    [
+        AddReviewComment,
+        SubmitReviewComment,
+        ExportReview,
+        PostReview
+    ]
+);
+
+/// The branch under review and what it is compared against.
+struct ReviewInfo {
+    branch: String,
+    base: String,
+    merge_base: String,
+    work_directory: PathBuf,
+}
+
+struct ReviewFile {
+    path: Arc<Path>,
+    buffer: Model<Buffer>,
+}
+
+/// A local comment on a hunk of the reviewed changes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ReviewComment {
+    path: PathBuf,
+    /// The hunk's rows in the current version of the file.
+    rows: Range<u32>,
+    /// The hunk, in unified diff format.
+    diff: String,
+    body: String,
+}
+
+struct PendingComment {
+    path: PathBuf,
+    rows: Range<u32>,
+    diff: String,
+    editor: View<Editor>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SerializedReview {
+    viewed: Vec<PathBuf>,
+    comments: Vec<ReviewComment>,
+}
+
+/// Opens a review of the current branch's changes since its merge base with `base`.
+pub(crate) fn review_branch(
+    workspace: &mut Workspace,
+    base: String,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let fs = workspace.app_state().fs.clone();
+    let Some((worktree, repository)) = root_repository(&project, cx) else {
+        return;
+    };
+    let branch = repository
+        .entry
+        .branch()
+        .map(|branch| branch.to_string())
+        .unwrap_or_else(|| "HEAD".to_string());
+    let work_directory = repository.snapshot.abs_path().to_path_buf();
+    let repo = repository.repo.clone();
+    let merge_base = cx.background_executor().spawn({
+        let base = base.clone();
+        async move { repo.merge_base("HEAD", &base) }
+    });
+
+    cx.spawn(|workspace, mut cx| async move {
+        let merge_base = merge_base.await?;
+        let files = workspace
+            .update(&mut cx, |_, cx| {
+                load_file_diffs(fs, vec![(worktree, repository)], merge_base.clone(), cx)
+            })?
+            .await;
+        workspace.update(&mut cx, |workspace, cx| {
+            let review = ReviewInfo {
+                branch,
+                base,
+                merge_base,
+                work_directory,
+            };
+            let weak_workspace = cx.view().downgrade();
+            let view =
+                cx.new_view(|cx| ReviewEditor::new(project, weak_workspace, review, files, cx));
+            workspace.add_item_to_active_pane(Box::new(view), None, true, cx);
+        })
+    })
+    .detach_and_notify_err(cx);
+}
+
+fn root_repository(
+    project: &Model<Project>,
+    cx: &AppContext,
+) -> Option<(Model<Worktree>, WorktreeRepository)> {
+    let worktree = project.read(cx).visible_worktrees(cx).next()?;
+    let local_worktree = worktree.read(cx).as_local()?;
+    let entry = local_worktree.root_git_entry()?;
+    let repo = local_worktree.get_local_repo(&entry)?.repo().clone();
+    let snapshot = (*local_worktree.snapshot()).clone();
+    Some((
+        worktree,
+        WorktreeRepository {
+            snapshot,
+            entry,
+            repo,
+        },
+    ))
+}
+
+/// Walks through the changes of a branch, tracking which files have been viewed and
+/// collecting comments on their hunks.
+pub struct ReviewEditor {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    review: ReviewInfo,
+    files: Vec<ReviewFile>,
+    viewed: HashSet<PathBuf>,
+    comments: Vec<ReviewComment>,
+    pending_comment: Option<PendingComment>,
+    multibuffer: Model<MultiBuffer>,
+    editor: View<Editor>,
+    focus_handle: FocusHandle,
+    loading: bool,
+    _load_task: Task<()>,
+    pending_serialization: Task<Option<()>>,
+}
+
+impl ReviewEditor {
+    fn new(
+        project: Model<Project>,
+        workspace: WeakView<Workspace>,
+        review: ReviewInfo,
+        files: Vec<crate::FileDiff>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let multibuffer = cx.new_model(|cx| {
+            MultiBuffer::new(project.read(cx).replica_id(), Capability::ReadOnly)
+                .with_title(format!("Review {}", review.branch))
+        });
+        let editor = cx.new_view(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), true, cx);
+            editor.set_vertical_scroll_margin(5, cx);
+            editor
+        });
+        cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+        let focus_handle = cx.focus_handle();
+        cx.on_focus(&focus_handle, |this, cx| {
+            if !this.loading {
+                this.editor.focus_handle(cx).focus(cx);
+            }
+        })
+        .detach();
+
+        let language_registry = project.read(cx).languages().clone();
+        let paths = files
+            .iter()
+            .map(|diff| diff.file.path().clone())
+            .collect::<Vec<_>>();
+        let buffers = build_diff_buffers(files, &language_registry, cx);
+        let files = paths
+            .into_iter()
+            .zip(buffers.iter().cloned())
+            .map(|(path, buffer)| ReviewFile { path, buffer })
+            .collect();
+
+        let storage_key = storage_key(&review);
+        let load_task = cx.spawn(|this, mut cx| async move {
+            let serialized = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(&storage_key) })
+                .await
+                .log_err()
+                .flatten()
+                .and_then(|review| serde_json::from_str::<SerializedReview>(&review).log_err());
+            recalculate_diffs(&buffers, &language_registry, &mut cx).await;
+
+            this.update(&mut cx, |this, cx| {
+                if let Some(serialized) = serialized {
+                    this.viewed = serialized.viewed.into_iter().collect();
+                    this.comments = serialized.comments;
+                }
+                this.multibuffer.update(cx, |multibuffer, cx| {
+                    for buffer in buffers {
+                        push_hunk_excerpts(multibuffer, buffer, cx);
+                    }
+                });
+                this.editor.update(cx, |editor, cx| {
+                    editor.expand_all_hunk_diffs(&ExpandAllHunkDiffs, cx)
+                });
+                this.loading = false;
+                if this.focus_handle.is_focused(cx) {
+                    this.editor.focus_handle(cx).focus(cx);
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+
+        Self {
+            project,
+            workspace,
+            review,
+            files,
+            viewed: HashSet::default(),
+            comments: Vec::new(),
+            pending_comment: None,
+            multibuffer,
+            editor,
+            focus_handle,
+            loading: true,
+            _load_task: load_task,
+            pending_serialization: Task::ready(None),
+        }
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let key = storage_key(&self.review);
+        let mut viewed = self.viewed.iter().cloned().collect::<Vec<_>>();
+        viewed.sort();
+        let review = SerializedReview {
+            viewed,
+            comments: self.comments.clone(),
+        };
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(key, serde_json::to_string(&review)?)
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn toggle_viewed(&mut self, path: &Path, cx: &mut ViewContext<Self>) {
+        if !self.viewed.remove(path) {
+            self.viewed.insert(path.to_path_buf());
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    /// Scrolls to the first hunk of the file, or to the hunk containing `row`.
+    fn jump_to(&mut self, path: &Path, row: Option<u32>, cx: &mut ViewContext<Self>) {
+        let Some(file) = self.files.iter().find(|file| file.path.as_ref() == path) else {
+            return;
+        };
+        let multibuffer = self.multibuffer.read(cx);
+        let buffer_snapshot = file.buffer.read(cx).snapshot();
+        let excerpts = multibuffer.excerpts_for_buffer(&file.buffer, cx);
+        let target = row.map(|row| buffer_snapshot.anchor_before(Point::new(row, 0)));
+        let excerpt = target
+            .and_then(|target| {
+                excerpts.iter().find(|(_, range)| {
+                    range.context.start.cmp(&target, &buffer_snapshot).is_le()
+                        && range.context.end.cmp(&target, &buffer_snapshot).is_ge()
+                })
+            })
+            .or_else(|| excerpts.first());
+        let Some((excerpt_id, range)) = excerpt else {
+            return;
+        };
+        let position = target.unwrap_or(range.context.start);
+        let Some(anchor) = multibuffer
+            .snapshot(cx)
+            .anchor_in_excerpt(*excerpt_id, position)
+        else {
+            return;
+        };
+        self.editor.update(cx, |editor, cx| {
+            editor.change_selections(Some(Autoscroll::top_relative(3)), cx, |selections| {
+                selections.select_anchor_ranges([anchor..anchor])
+            });
+            editor.focus(cx);
+        });
+    }
+
+    /// Starts a comment on the hunk under the cursor.
+    fn add_comment(&mut self, _: &AddReviewComment, cx: &mut ViewContext<Self>) {
+        let head = self.editor.read(cx).selections.newest::<Point>(cx).head();
+        let Some((buffer, point, _)) = self.multibuffer.read(cx).point_to_buffer_point(head, cx)
+        else {
+            return;
+        };
+        let Some(path) = self
+            .files
+            .iter()
+            .find(|file| file.buffer == buffer)
+            .map(|file| file.path.to_path_buf())
+        else {
+            return;
+        };
+
+        let buffer = buffer.read(cx);
+        let snapshot = buffer.snapshot();
+        let anchor = snapshot.anchor_before(point);
+        let hunk = snapshot
+            .git_diff_hunks_intersecting_range(anchor..anchor)
+            .next();
+        let (rows, diff) = match hunk {
+            Some(hunk) => {
+                let removed = buffer
+                    .diff_base()
+                    .map(|base| {
+                        base.chunks_in_range(hunk.diff_base_byte_range.clone())
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+                let rows = hunk.associated_range;
+                let added_range = Point::new(rows.start, 0)
+                    ..snapshot.clip_point(Point::new(rows.end, 0), Bias::Left);
+                let added = snapshot.text_for_range(added_range).collect::<String>();
+                (rows, format_hunk(&removed, &added))
+            }
+            None => {
+                let line =
+                    Point::new(point.row, 0)..Point::new(point.row, snapshot.line_len(point.row));
+                let line = snapshot.text_for_range(line).collect::<String>();
+                (point.row..point.row + 1, format!(" {line}\n"))
+            }
+        };
+
+        let placeholder = format!("Comment on {}, {}", path.display(), lines_label(&rows));
+        let editor = cx.new_view(|cx| {
+            let mut editor = Editor::auto_height(8, cx);
+            editor.set_placeholder_text(placeholder, cx);
+            editor
+        });
+        editor.focus_handle(cx).focus(cx);
+        self.pending_comment = Some(PendingComment {
+            path,
+            rows,
+            diff,
+            editor,
+        });
+        cx.notify();
+    }
+
+    fn submit_comment(&mut self, _: &SubmitReviewComment, cx: &mut ViewContext<Self>) {
+        let Some(pending) = self.pending_comment.take() else {
+            return;
+        };
+        let body = pending.editor.read(cx).text(cx).trim().to_string();
+        if !body.is_empty() {
+            self.comments.push(ReviewComment {
+                path: pending.path,
+                rows: pending.rows,
+                diff: pending.diff,
+                body,
+            });
+            self.serialize(cx);
+        }
+        self.editor.focus_handle(cx).focus(cx);
+        cx.notify();
+    }
+
+    fn cancel_comment(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+        if self.pending_comment.take().is_some() {
+            self.editor.focus_handle(cx).focus(cx);
+            cx.notify();
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn delete_comment(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix < self.comments.len() {
+            self.comments.remove(ix);
+            self.serialize(cx);
+            cx.notify();
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let files = self
+            .files
+            .iter()
+            .map(|file| (file.path.as_ref(), self.viewed.contains(file.path.as_ref())))
+            .collect::<Vec<_>>();
+        review_markdown(&self.review, &files, &self.comments)
+    }
+
+    /// Opens the review as a Markdown document.
+    fn export_review(&mut self, _: &ExportReview, cx: &mut ViewContext<Self>) {
+        let markdown = self.markdown();
+        let project = self.project.clone();
+        let language = project.read(cx).languages().language_for_name("Markdown");
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            let language = language.await.log_err();
+            workspace.update(&mut cx, |workspace, cx| {
+                let buffer = project.update(cx, |project, cx| {
+                    project.create_local_buffer(&markdown, language, cx)
+                });
+                let editor = cx.new_view(|cx| Editor::for_buffer(buffer, Some(project), cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Posts the review as a comment on the branch's pull request, using the GitHub CLI.
+    fn post_review(&mut self, _: &PostReview, cx: &mut ViewContext<Self>) {
+        let markdown = self.markdown();
+        let file_name = format!(
+            "zed-review-{}.md",
+            self.review
+                .branch
+                .replace(|c: char| !c.is_alphanumeric() && c != '-', "_")
+        );
+        let path = std::env::temp_dir().join(file_name);
+        let fs = self.project.read(cx).fs().clone();
+        let work_directory = self.review.work_directory.clone();
+        let branch = self.review.branch.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            fs.atomic_write(path.clone(), markdown).await?;
+            let args = vec![
+                "pr".to_string(),
+                "review".to_string(),
+                "--comment".to_string(),
+                "--body-file".to_string(),
+                path.to_string_lossy().into_owned(),
+            ];
+            let command_label = format!("gh {}", args.join(" "));
+            let label = format!("Post review of {branch}");
+            let spawn_in_terminal = SpawnInTerminal {
+                id: TaskId(format!("gh-pr-review-{branch}")),
+                full_label: label.clone(),
+                label,
+                command: "gh".into(),
+                args,
+                command_label,
+                cwd: Some(work_directory),
+                env: Default::default(),
+                use_new_terminal: true,
+                allow_concurrent_runs: false,
+                reveal: RevealStrategy::Always,
+                hide: HideStrategy::Never,
+                shell: Default::default(),
+            };
+            workspace.update(&mut cx, |_, cx| {
+                cx.emit(workspace::Event::SpawnTask(Box::new(spawn_in_terminal)));
+            })
+        })
+        .detach_and_notify_err(cx);
+    }
+
+    fn render_sidebar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let viewed_count = self
+            .files
+            .iter()
+            .filter(|file| self.viewed.contains(file.path.as_ref()))
+            .count();
+        let has_comments = !self.comments.is_empty();
+
+        v_flex()
+            .w(px(280.))
+            .h_full()
+            .flex_none()
+            .border_r_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().panel_background)
+            .child(
+                v_flex()
+                    .p_2()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(format!(
+                        "{} → {}",
+                        self.review.branch, self.review.base
+                    )))
+                    .child(
+                        h_flex()
+                            .justify_between()
+                            .child(
+                                Label::new(format!(
+                                    "{viewed_count} of {} files viewed",
+                                    self.files.len()
+                                ))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        IconButton::new("export-review", IconName::FileText)
+                                            .icon_size(IconSize::Small)
+                                            .tooltip(|cx| {
+                                                Tooltip::for_action(
+                                                    "Export as Markdown",
+                                                    &ExportReview,
+                                                    cx,
+                                                )
+                                            })
+                                            .on_click(cx.listener(|this, _, cx| {
+                                                this.export_review(&ExportReview, cx)
+                                            })),
+                                    )
+                                    .child(
+                                        IconButton::new("post-review", IconName::Github)
+                                            .icon_size(IconSize::Small)
+                                            .disabled(!has_comments)
+                                            .tooltip(|cx| {
+                                                Tooltip::for_action(
+                                                    "Post to Pull Request",
+                                                    &PostReview,
+                                                    cx,
+                                                )
+                                            })
+                                            .on_click(cx.listener(|this, _, cx| {
+                                                this.post_review(&PostReview, cx)
+                                            })),
+                                    ),
+                            ),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("review-files")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .children(
+                        self.files
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, file)| self.render_file(ix, file, cx)),
+                    ),
+            )
+    }
+
+    fn render_file(&self, ix: usize, file: &ReviewFile, cx: &mut ViewContext<Self>) -> AnyElement {
+        let viewed = self.viewed.contains(file.path.as_ref());
+        let selection = if viewed {
+            Selection::Selected
+        } else {
+            Selection::Unselected
+        };
+        let path = file.path.clone();
+        let comments = self
+            .comments
+            .iter()
+            .enumerate()
+            .filter(|(_, comment)| comment.path.as_path() == path.as_ref())
+            .map(|(comment_ix, comment)| {
+                let path = path.clone();
+                let row = comment.rows.start;
+                h_flex()
+                    .id(("review-comment", comment_ix))
+                    .group("review-comment")
+                    .pl_7()
+                    .pr_2()
+                    .gap_1()
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        Icon::new(IconName::MessageBubbles)
+                            .size(IconSize::XSmall)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(format!(
+                            "{}: {}",
+                            lines_label(&comment.rows),
+                            comment.body.lines().next().unwrap_or_default()
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .single_line(),
+                    )
+                    .child(
+                        div().ml_auto().visible_on_hover("review-comment").child(
+                            IconButton::new(("delete-review-comment", comment_ix), IconName::Trash)
+                                .icon_size(IconSize::XSmall)
+                                .tooltip(|cx| Tooltip::text("Delete Comment", cx))
+                                .on_click(cx.listener(move |this, _, cx| {
+                                    this.delete_comment(comment_ix, cx)
+                                })),
+                        ),
+                    )
+                    .on_click(cx.listener(move |this, _, cx| this.jump_to(&path, Some(row), cx)))
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .child(
+                h_flex()
+                    .id(("review-file", ix))
+                    .px_2()
+                    .py_0p5()
+                    .gap_2()
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        Checkbox::new(("review-file-viewed", ix), selection).on_click(cx.listener(
+                            {
+                                let path = path.clone();
+                                move |this, _, cx| this.toggle_viewed(&path, cx)
+                            },
+                        )),
+                    )
+                    .child(
+                        Label::new(path.to_string_lossy().to_string())
+                            .size(LabelSize::Small)
+                            .color(if viewed { Color::Muted } else { Color::Default })
+                            .single_line(),
+                    )
+                    .on_click(cx.listener({
+                        let path = path.clone();
+                        move |this, _, cx| this.jump_to(&path, None, cx)
+                    })),
+            )
+            .children(comments)
+            .into_any_element()
+    }
+
+    fn render_comment_composer(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let pending = self.pending_comment.as_ref()?;
+        Some(
+            v_flex()
+                .key_context("ReviewComment")
+                .on_action(cx.listener(Self::submit_comment))
+                .on_action(cx.listener(Self::cancel_comment))
+                .p_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().colors().panel_background)
+                .child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .bg(cx.theme().colors().editor_background)
+                        .child(pending.editor.clone()),
+                )
+                .child(
+                    h_flex()
+                        .justify_end()
+                        .gap_1()
+                        .child(
+                            Button::new("cancel-review-comment", "Cancel").on_click(
+                                cx.listener(|this, _, cx| this.cancel_comment(&Cancel, cx)),
+                            ),
+                        )
+                        .child(
+                            Button::new("submit-review-comment", "Add Comment")
+                                .style(ButtonStyle::Filled)
+                                .on_click(cx.listener(|this, _, cx| {
+                                    this.submit_comment(&SubmitReviewComment, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+}
+
+fn storage_key(review: &ReviewInfo) -> String {
+    format!(
+        "git-review:{}:{}:{}",
+        review.work_directory.display(),
+        review.branch,
+        review.base
+    )
+}
+
+/// Describes 0-based rows as 1-based line numbers.
+fn lines_label(rows: &Range<u32>) -> String {
+    if rows.end > rows.start + 1 {
+        format!("lines {}–{}", rows.start + 1, rows.end)
+    } else {
+        format!("line {}", rows.start + 1)
+    }
+}
+
+fn format_hunk(removed: &str, added: &str) -> String {
+    let mut diff = String::new();
+    for line in removed.lines() {
+        writeln!(diff, "-{line}").ok();
+    }
+    for line in added.lines() {
+        writeln!(diff, "+{line}").ok();
+    }
+    diff
+}
+
+fn review_markdown(
+    review: &ReviewInfo,
+    files: &[(&Path, bool)],
+    comments: &[ReviewComment],
+) -> String {
+    let mut markdown = format!(
+        "# Review of `{}` against `{}`\n\nChanges since {}.\n\n## Files\n\n",
+        review.branch, review.base, review.merge_base
+    );
+    for (path, viewed) in files {
+        let checkbox = if *viewed { "x" } else { " " };
+        writeln!(markdown, "- [{checkbox}] {}", path.display()).ok();
+    }
+
+    if !comments.is_empty() {
+        markdown.push_str("\n## Comments\n");
+        for (path, _) in files {
+            for comment in comments.iter().filter(|comment| comment.path == *path) {
+                write!(
+                    markdown,
+                    "\n### {}, {}\n\n```diff\n{}```\n\n{}\n",
+                    path.display(),
+                    lines_label(&comment.rows),
+                    comment.diff,
+                    comment.body
+                )
+                .ok();
+            }
+        }
+    }
+    markdown
+}
+
+impl EventEmitter<EditorEvent> for ReviewEditor {}
+
+impl FocusableView for ReviewEditor {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ReviewEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let message = if self.loading {
+            Some("Loading…".to_string())
+        } else if self.files.is_empty() {
+            Some(format!("No changes since {}", self.review.base))
+        } else {
+            None
+        };
+        let content = if let Some(message) = message {
+            div()
+                .bg(cx.theme().colors().editor_background)
+                .flex()
+                .items_center()
+                .justify_center()
+                .size_full()
+                .child(Label::new(message).color(Color::Muted))
+        } else {
+            div().size_full().child(self.editor.clone())
+        };
+
+        h_flex()
+            .key_context("ReviewEditor")
+            .on_action(cx.listener(Self::add_comment))
+            .on_action(cx.listener(Self::export_review))
+            .on_action(cx.listener(Self::post_review))
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_sidebar(cx))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .h_full()
+                    .child(content)
+                    .children(self.render_comment_composer(cx)),
+            )
+    }
+}
+
+impl Item for ReviewEditor {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, cx| editor.deactivated(cx));
+    }
+
+    fn navigate(&mut self, data: Box<dyn Any>, cx: &mut ViewContext<Self>) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(
+            format!(
+                "Review of {} against {}",
+                self.review.branch, self.review.base
+            )
+            .into(),
+        )
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        h_flex()
+            .gap_1()
+            .child(Icon::new(IconName::FileGit).color(Color::Muted))
+            .child(Label::new(format!("Review {}", self.review.branch)).color(params.text_color()))
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn is_singleton(&self, _: &AppContext) -> bool {
+        false
+    }
+
+    fn set_nav_history(&mut self, nav_history: ItemNavHistory, cx: &mut ViewContext<Self>) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a View<Self>,
+        _: &'a AppContext,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_markdown() {
+        let review = ReviewInfo {
+            branch: "feature".into(),
+            base: "main".into(),
+            merge_base: "abc123".into(),
+            work_directory: PathBuf::from("/repo"),
+        };
+        let comments = vec![ReviewComment {
+            path: PathBuf::from("src/lib.rs"),
+            rows: 2..4,
+            diff: format_hunk("old\n", "new\nnewer\n"),
+            body: "Why the extra line?".into(),
+        }];
+        let files = [
+            (Path::new("src/lib.rs"), true),
+            (Path::new("src/main.rs"), false),
+        ];
+        assert_eq!(
+            review_markdown(&review, &files, &comments),
+            "\
+# Review of `feature` against `main`
+
+Changes since abc123.
+
+## Files
+
+- [x] src/lib.rs
+- [ ] src/main.rs
+
+## Comments
+
+### src/lib.rs, lines 3–4
+
+```diff
+-old
++new
++newer
+```
+
+Why the extra line?
+"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::ops::Range;
+
+/// The commit types suggested by the Conventional Commits template, with a short description.
+pub const CONVENTIONAL_COMMIT_TYPES: &[(&str, &str)] = &[
+    ("feat", "A new feature"),
+    ("fix", "A bug fix"),
+    ("docs", "Documentation only changes"),
+    (
+        "style",
+        "Formatting, whitespace and other non-functional changes",
+    ),
+    (
+        "refactor",
+        "A change that neither fixes a bug nor adds a feature",
+    ),
+    ("perf", "A change that improves performance"),
+    ("test", "Adding or correcting tests"),
+    ("build", "Changes to the build system or dependencies"),
+    ("ci", "Changes to the CI configuration"),
+    (
+        "chore",
+        "Other changes that don't modify source or test files",
+    ),
+    ("revert", "Reverts a previous commit"),
+];
+
+/// The limits that a commit message is checked against.
+#[derive(Clone, Copy, Debug)]
+pub struct CommitMessageRules {
+    pub subject_line_length: usize,
+    pub body_line_length: usize,
+    pub conventional_commits: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommitLint {
+    EmptySubject,
+    SubjectTooLong {
+        length: usize,
+        max: usize,
+    },
+    SubjectEndsWithPeriod,
+    NotConventional,
+    BodyLineTooLong {
+        line: usize,
+        length: usize,
+        max: usize,
+    },
+}
+
+impl CommitLint {
+    /// Whether the lint prevents the message from being committed.
+    pub fn is_error(&self) -> bool {
+        matches!(self, CommitLint::EmptySubject)
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            CommitLint::EmptySubject => "The subject is empty".into(),
+            CommitLint::SubjectTooLong { length, max } => {
+                format!("The subject is {length} characters long, keep it under {max}")
+            }
+            CommitLint::SubjectEndsWithPeriod => "The subject ends with a period".into(),
+            CommitLint::NotConventional => {
+                "The subject doesn't follow the \"type(scope): description\" format".into()
+            }
+            CommitLint::BodyLineTooLong { line, length, max } => {
+                format!("Body line {line} is {length} characters long, wrap it at {max}")
+            }
+        }
+    }
+}
+
+/// Checks a commit message's subject and body against the rules.
+pub fn lint_commit_message(
+    subject: &str,
+    body: &str,
+    rules: CommitMessageRules,
+) -> Vec<CommitLint> {
+    let mut lints = Vec::new();
+    let subject = subject.trim();
+    if subject.is_empty() {
+        lints.push(CommitLint::EmptySubject);
+    } else {
+        let length = subject.chars().count();
+        if length > rules.subject_line_length {
+            lints.push(CommitLint::SubjectTooLong {
+                length,
+                max: rules.subject_line_length,
+            });
+        }
+        if subject.ends_with('.') && !subject.ends_with("...") {
+            lints.push(CommitLint::SubjectEndsWithPeriod);
+        }
+        if rules.conventional_commits && ConventionalSubject::parse(subject).is_none() {
+            lints.push(CommitLint::NotConventional);
+        }
+    }
+
+    for (ix, line) in body.trim_end().lines().enumerate() {
+        let length = line.chars().count();
+        // Long URLs can't be wrapped.
+        if length > rules.body_line_length && line.contains(char::is_whitespace) {
+            lints.push(CommitLint::BodyLineTooLong {
+                line: ix + 1,
+                length,
+                max: rules.body_line_length,
+            });
+        }
+    }
+    lints
+}
+
+/// Joins a subject and body into a commit message, separated by a blank line.
+pub fn compose_commit_message(subject: &str, body: &str) -> String {
+    let subject = subject.trim();
+    let body = body.trim();
+    if body.is_empty() {
+        format!("{subject}\n")
+    } else {
+        format!("{subject}\n\n{body}\n")
+    }
+}
+
+/// Splits a commit message into its subject and body.
+pub fn split_commit_message(message: &str) -> (String, String) {
+    let message = message.trim();
+    match message.split_once('\n') {
+        Some((subject, body)) => (subject.trim().to_string(), body.trim().to_string()),
+        None => (message.to_string(), String::new()),
+    }
+}
+
+/// A subject in the `type(scope)!: description` format of Conventional Commits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConventionalSubject<'a> {
+    pub commit_type: &'a str,
+    pub scope: Option<&'a str>,
+    pub breaking: bool,
+    /// The byte range of the `type(scope)!: ` prefix.
+    pub prefix_range: Range<usize>,
+}
+
+impl<'a> ConventionalSubject<'a> {
+    pub fn parse(subject: &'a str) -> Option<Self> {
+        let (prefix, description) = subject.split_once(": ")?;
+        if description.trim().is_empty() {
+            return None;
+        }
+        let (prefix, breaking) = match prefix.strip_suffix('!') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (commit_type, scope) = match prefix.split_once('(') {
+            Some((commit_type, scope)) => (commit_type, Some(scope.strip_suffix(')')?)),
+            None => (prefix, None),
+        };
+        let is_word = |text: &str| {
+            !text.is_empty()
+                && text
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        };
+        if !is_word(commit_type) || scope.is_some_and(|scope| scope.trim().is_empty()) {
+            return None;
+        }
+        Some(Self {
+            commit_type,
+            scope,
+            breaking,
+            prefix_range: 0..subject.len() - description.len(),
+        })
+    }
+}
+
+/// Replaces the subject's Conventional Commits type, keeping its scope and description.
+pub fn apply_conventional_type(subject: &str, commit_type: &str) -> String {
+    match ConventionalSubject::parse(subject) {
+        Some(conventional) => {
+            let scope = conventional
+                .scope
+                .map(|scope| format!("({scope})"))
+                .unwrap_or_default();
+            let breaking = if conventional.breaking { "!" } else { "" };
+            let description = &subject[conventional.prefix_range.end..];
+            format!("{commit_type}{scope}{breaking}: {description}")
+        }
+        None => format!("{commit_type}: {}", subject.trim_start()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: CommitMessageRules = CommitMessageRules {
+        subject_line_length: 50,
+        body_line_length: 72,
+        conventional_commits: false,
+    };
+
+    #[test]
+    fn test_lint_commit_message() {
+        assert_eq!(lint_commit_message("Fix the build", "", RULES), vec![]);
+        assert_eq!(
+            lint_commit_message("  ", "Some body", RULES),
+            vec![CommitLint::EmptySubject]
+        );
+        assert_eq!(
+            lint_commit_message("Fix the build.", "", RULES),
+            vec![CommitLint::SubjectEndsWithPeriod]
+        );
+        assert_eq!(
+            lint_commit_message(&"a".repeat(51), "", RULES),
+            vec![CommitLint::SubjectTooLong {
+                length: 51,
+                max: 50
+            }]
+        );
+
+        let long_line = "word ".repeat(15);
+        let long_line = long_line.trim_end();
+        let body = format!(
+            "First line\n{long_line}\nhttps://example.com/{}",
+            "a".repeat(80)
+        );
+        assert_eq!(
+            lint_commit_message("Fix the build", &body, RULES),
+            vec![CommitLint::BodyLineTooLong {
+                line: 2,
+                length: 74,
+                max: 72
+            }]
+        );
+
+        let rules = CommitMessageRules {
+            conventional_commits: true,
+            ..RULES
+        };
+        assert_eq!(lint_commit_message("fix: the build", "", rules), vec![]);
+        assert_eq!(
+            lint_commit_message("Fix the build", "", rules),
+            vec![CommitLint::NotConventional]
+        );
+    }
+
+    #[test]
+    fn test_compose_and_split_commit_message() {
+        assert_eq!(compose_commit_message(" Subject ", "\n"), "Subject\n");
+        assert_eq!(
+            compose_commit_message("Subject", "\nBody\n\nMore\n"),
+            "Subject\n\nBody\n\nMore\n"
+        );
+        assert_eq!(
+            split_commit_message("Subject\n\nBody\n\nMore\n"),
+            ("Subject".to_string(), "Body\n\nMore".to_string())
+        );
+        assert_eq!(
+            split_commit_message("Subject\n"),
+            ("Subject".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_conventional_subject() {
+        assert_eq!(
+            ConventionalSubject::parse("feat(editor)!: add a thing"),
+            Some(ConventionalSubject {
+                commit_type: "feat",
+                scope: Some("editor"),
+                breaking: true,
+                prefix_range: 0..15,
+            })
+        );
+        assert_eq!(ConventionalSubject::parse("feat: "), None);
+        assert_eq!(ConventionalSubject::parse("Fix the build"), None);
+        assert_eq!(ConventionalSubject::parse("some thing: else"), None);
+
+        assert_eq!(
+            apply_conventional_type("add a thing", "feat"),
+            "feat: add a thing"
+        );
+        assert_eq!(
+            apply_conventional_type("feat(editor)!: add a thing", "fix"),
+            "fix(editor)!: add a thing"
+        );
+    }
+}
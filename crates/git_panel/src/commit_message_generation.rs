@@ -0,0 +1,191 @@
+use crate::{
+    commit_message::CommitMessageRules, git_panel_settings::CommitMessageGenerationSettings,
+};
+use anyhow::{Context as _, Result};
+use regex::Regex;
+use std::path::Path;
+use util::paths::PathMatcher;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Decides which parts of the staged diff are sent to the language model.
+pub struct DiffFilter {
+    max_size: usize,
+    excluded_files: PathMatcher,
+    redactions: Vec<Regex>,
+}
+
+/// A staged diff, ready to be included in the prompt.
+#[derive(Debug, PartialEq)]
+pub struct PreparedDiff {
+    pub text: String,
+    /// Whether the diff exceeded the maximum size and was cut short.
+    pub truncated: bool,
+}
+
+impl DiffFilter {
+    pub fn new(settings: &CommitMessageGenerationSettings) -> Result<Self> {
+        let excluded_files = PathMatcher::new(&settings.excluded_files)
+            .context("invalid pattern in `commit_message_generation.excluded_files`")?;
+        let redactions = settings
+            .redact_patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).with_context(|| {
+                    format!("invalid pattern {pattern:?} in `commit_message_generation.redact_patterns`")
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            max_size: settings.max_diff_size,
+            excluded_files,
+            redactions,
+        })
+    }
+
+    /// Drops the changes to excluded files, redacts the remaining text and truncates it.
+    pub fn prepare(&self, diff: &str) -> PreparedDiff {
+        let mut text = String::new();
+        let mut include_file = true;
+        for line in diff.split_inclusive('\n') {
+            if let Some(paths) = line.strip_prefix("diff --git a/") {
+                include_file = match paths.trim_end().rsplit_once(" b/") {
+                    Some((_, path)) => !self.excluded_files.is_match(Path::new(path)),
+                    None => true,
+                };
+            }
+            if include_file {
+                text.push_str(line);
+            }
+        }
+
+        for redaction in &self.redactions {
+            text = redaction.replace_all(&text, REDACTED).into_owned();
+        }
+
+        let truncated = text.len() > self.max_size;
+        if truncated {
+            let mut end = self.max_size;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            end = text[..end].rfind('\n').map_or(end, |ix| ix + 1);
+            text.truncate(end);
+        }
+        PreparedDiff { text, truncated }
+    }
+}
+
+/// Builds the prompt asking the model for a message describing the diff.
+pub fn build_prompt(diff: &PreparedDiff, rules: CommitMessageRules) -> String {
+    let mut prompt = format!(
+        "Write a git commit message for the staged changes below.\n\n\
+         The first line is a summary of at most {} characters, in the imperative mood and \
+         without a trailing period. If the change needs explaining, follow it with a blank \
+         line and a body saying what changed and why, wrapped at {} characters.",
+        rules.subject_line_length, rules.body_line_length
+    );
+    if rules.conventional_commits {
+        prompt.push_str(
+            " Start the summary with a Conventional Commits type, such as \"feat: \" or \
+             \"fix(scope): \".",
+        );
+    }
+    prompt.push_str(
+        " Respond with the commit message only, without quotes or code fences.\n\n```diff\n",
+    );
+    prompt.push_str(&diff.text);
+    if !diff.text.ends_with('\n') {
+        prompt.push('\n');
+    }
+    prompt.push_str("```\n");
+    if diff.truncated {
+        prompt.push_str("\nThe diff was truncated, so describe the changes that are shown.\n");
+    }
+    prompt
+}
+
+/// Strips the code fences models sometimes wrap their answers in.
+pub fn clean_generated_message(message: &str) -> &str {
+    let mut message = message.trim();
+    if message.starts_with("```") {
+        message = message
+            .split_once('\n')
+            .map_or("", |(_, rest)| rest)
+            .trim_start();
+    }
+    message.strip_suffix("```").unwrap_or(message).trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-let api_key = \"abc\";
++let api_key = \"def\";
+diff --git a/.env b/.env
+index 3333333..4444444 100644
+--- a/.env
++++ b/.env
+@@ -1 +1 @@
+-TOKEN=abc
++TOKEN=def
+";
+
+    fn filter(max_diff_size: usize) -> DiffFilter {
+        DiffFilter::new(&CommitMessageGenerationSettings {
+            max_diff_size,
+            excluded_files: vec!["**/.env*".into()],
+            redact_patterns: vec!["(?i)api_key = \\S+".into()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prepare_diff() {
+        assert_eq!(
+            filter(10_000).prepare(DIFF),
+            PreparedDiff {
+                text: "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-let [REDACTED]
++let [REDACTED]
+"
+                .into(),
+                truncated: false,
+            }
+        );
+
+        // Truncation happens at line boundaries.
+        assert_eq!(
+            filter(45).prepare(DIFF),
+            PreparedDiff {
+                text: "diff --git a/src/main.rs b/src/main.rs\n".into(),
+                truncated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_clean_generated_message() {
+        assert_eq!(
+            clean_generated_message("  Fix the build\n"),
+            "Fix the build"
+        );
+        assert_eq!(
+            clean_generated_message("```\nFix the build\n\nBody\n```"),
+            "Fix the build\n\nBody"
+        );
+        assert_eq!(clean_generated_message("```text\nFix"), "Fix");
+    }
+}
@@ -0,0 +1,836 @@
+mod commit_message;
+mod commit_message_generation;
+mod git_panel_settings;
+
+use anyhow::{anyhow, Result};
+use commit_message::{
+    apply_conventional_type, compose_commit_message, lint_commit_message, split_commit_message,
+    CommitLint, CommitMessageRules, CONVENTIONAL_COMMIT_TYPES,
+};
+use commit_message_generation::{build_prompt, clean_generated_message, DiffFilter};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{actions::MoveToEnd, Editor, EditorEvent};
+use env_file::{is_env_file, EnvFileSettings};
+use fs::Fs;
+use futures::StreamExt as _;
+use git::repository::{CommitOptions, CommitSigning, GitFileStatus, GitRepository, RepoPath};
+use gpui::{
+    actions, uniform_list, AnchorCorner, AnyElement, AppContext, AsyncWindowContext, EventEmitter,
+    FocusHandle, FocusableView, Model, PromptLevel, Render, Subscription, Task, View, ViewContext,
+    WeakView,
+};
+use language_model::{
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, Role,
+};
+use project::{Project, ProjectPath, WorktreeId};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::{path::PathBuf, sync::Arc};
+use ui::{prelude::*, Checkbox, ContextMenu, ListItem, PopoverMenu, Tooltip};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+pub use git_panel_settings::GitPanelSettings;
+
+const GIT_PANEL_KEY: &str = "GitPanel";
+
+actions!(
+    git_panel,
+    [
+        ToggleFocus,
+        Commit,
+        GenerateCommitMessage,
+        ToggleAmend,
+        ToggleSignOff,
+        ToggleSigning
+    ]
+);
+
+pub fn init(cx: &mut AppContext) {
+    GitPanelSettings::register(cx);
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, cx| {
+            workspace.toggle_panel_focus::<GitPanel>(cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedGitPanel {
+    width: Option<Pixels>,
+}
+
+/// The repository at the root of the project's first folder, which the panel commits to.
+struct ActiveRepository {
+    worktree_id: WorktreeId,
+    branch: Option<SharedString>,
+    repo: Arc<dyn GitRepository>,
+}
+
+/// Lists the changes in the project's repository and composes commits for it.
+pub struct GitPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakView<Workspace>,
+    project: Model<Project>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    repository: Option<ActiveRepository>,
+    changes: Vec<(RepoPath, GitFileStatus)>,
+    subject_editor: View<Editor>,
+    body_editor: View<Editor>,
+    lints: Vec<CommitLint>,
+    amend: bool,
+    sign_off: bool,
+    sign: bool,
+    error: Option<SharedString>,
+    pending_commit: Option<Task<()>>,
+    pending_generation: Option<Task<()>>,
+    refresh_task: Task<()>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl GitPanel {
+    fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let workspace = workspace.weak_handle();
+        cx.new_view(|cx| {
+            let subject_editor = cx.new_view(|cx| {
+                let mut editor = Editor::single_line(cx);
+                editor.set_placeholder_text("Commit subject", cx);
+                editor
+            });
+            let body_editor = cx.new_view(|cx| {
+                let mut editor = Editor::auto_height(12, cx);
+                editor.set_placeholder_text("Extended description", cx);
+                editor
+            });
+            let focus_handle = cx.focus_handle();
+            let subscriptions = vec![
+                cx.subscribe(&subject_editor, Self::handle_editor_event),
+                cx.subscribe(&body_editor, Self::handle_editor_event),
+                cx.subscribe(
+                    &project,
+                    |this, _, event: &project::Event, cx| match event {
+                        project::Event::WorktreeAdded
+                        | project::Event::WorktreeRemoved(_)
+                        | project::Event::WorktreeUpdatedGitRepositories => this.refresh(cx),
+                        _ => {}
+                    },
+                ),
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    this.update_wrap_guides(cx);
+                    this.update_lints(cx);
+                }),
+                cx.on_focus(&focus_handle, |this, cx| {
+                    this.subject_editor.focus_handle(cx).focus(cx);
+                }),
+            ];
+
+            let settings = GitPanelSettings::get_global(cx);
+            let mut this = Self {
+                fs,
+                workspace,
+                project,
+                focus_handle,
+                width: None,
+                repository: None,
+                changes: Vec::new(),
+                subject_editor,
+                body_editor,
+                lints: Vec::new(),
+                amend: false,
+                sign_off: settings.sign_off,
+                sign: settings.sign_commits,
+                error: None,
+                pending_commit: None,
+                pending_generation: None,
+                refresh_task: Task::ready(()),
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            };
+            this.update_wrap_guides(cx);
+            this.update_lints(cx);
+            this.refresh(cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakView<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<View<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let serialized_panel = if let Some(panel) = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(GIT_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedGitPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(&mut cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_executor().spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        GIT_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedGitPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn handle_editor_event(
+        &mut self,
+        _: View<Editor>,
+        event: &EditorEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let EditorEvent::BufferEdited = event {
+            self.update_lints(cx);
+        }
+    }
+
+    fn active_repository(&self, cx: &AppContext) -> Option<ActiveRepository> {
+        let worktree = self.project.read(cx).visible_worktrees(cx).next()?;
+        let worktree = worktree.read(cx).as_local()?;
+        let entry = worktree.root_git_entry()?;
+        let repo = worktree.get_local_repo(&entry)?.repo().clone();
+        Some(ActiveRepository {
+            worktree_id: worktree.id(),
+            branch: entry.branch().map(|branch| branch.to_string().into()),
+            repo,
+        })
+    }
+
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        self.repository = self.active_repository(cx);
+        let Some(repo) = self
+            .repository
+            .as_ref()
+            .map(|repository| repository.repo.clone())
+        else {
+            self.changes.clear();
+            cx.notify();
+            return;
+        };
+        self.refresh_task = cx.spawn(|this, mut cx| async move {
+            let status = cx
+                .background_executor()
+                .spawn(async move { repo.status(&[PathBuf::new()]) })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                match status {
+                    Ok(status) => this.changes = status.entries.to_vec(),
+                    Err(error) => {
+                        this.changes.clear();
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn rules(cx: &AppContext) -> CommitMessageRules {
+        let settings = GitPanelSettings::get_global(cx);
+        CommitMessageRules {
+            subject_line_length: settings.subject_line_length,
+            body_line_length: settings.body_line_length,
+            conventional_commits: settings.conventional_commits,
+        }
+    }
+
+    fn update_wrap_guides(&mut self, cx: &mut ViewContext<Self>) {
+        let rules = Self::rules(cx);
+        self.subject_editor.update(cx, |editor, cx| {
+            editor.set_wrap_guides([rules.subject_line_length], cx)
+        });
+        self.body_editor.update(cx, |editor, cx| {
+            editor.set_wrap_guides([rules.body_line_length], cx)
+        });
+    }
+
+    fn update_lints(&mut self, cx: &mut ViewContext<Self>) {
+        let subject = self.subject_editor.read(cx).text(cx);
+        let body = self.body_editor.read(cx).text(cx);
+        self.lints = lint_commit_message(&subject, &body, Self::rules(cx));
+        cx.notify();
+    }
+
+    fn can_commit(&self) -> bool {
+        self.repository.is_some()
+            && self.pending_commit.is_none()
+            && !self.lints.iter().any(CommitLint::is_error)
+    }
+
+    fn commit(&mut self, _: &Commit, cx: &mut ViewContext<Self>) {
+        if !self.can_commit() {
+            return;
+        }
+        let Some(repo) = self
+            .repository
+            .as_ref()
+            .map(|repository| repository.repo.clone())
+        else {
+            return;
+        };
+
+        let message = compose_commit_message(
+            &self.subject_editor.read(cx).text(cx),
+            &self.body_editor.read(cx).text(cx),
+        );
+        let settings = GitPanelSettings::get_global(cx);
+        let options = CommitOptions {
+            amend: self.amend,
+            sign_off: self.sign_off,
+            signing: self.sign.then(|| CommitSigning {
+                format: settings.signing_format.map(Into::into),
+                key: settings.signing_key.clone(),
+            }),
+        };
+        let warn_about_env_files = EnvFileSettings::get_global(cx).warn_before_commit;
+        self.pending_commit = Some(cx.spawn(|this, mut cx| async move {
+            if warn_about_env_files && !Self::confirm_env_files(repo.clone(), &this, &mut cx).await
+            {
+                this.update(&mut cx, |this, cx| {
+                    this.pending_commit = None;
+                    cx.notify();
+                })
+                .ok();
+                return;
+            }
+            let result = cx
+                .background_executor()
+                .spawn(async move { repo.commit(&message, &options) })
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_commit = None;
+                match result {
+                    Ok(()) => {
+                        this.error = None;
+                        this.amend = false;
+                        this.set_message("", "", cx);
+                    }
+                    Err(error) => this.error = Some(format!("{error:#}").into()),
+                }
+                this.refresh(cx);
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Asks whether to commit the staged environment files, which may contain secrets.
+    async fn confirm_env_files(
+        repo: Arc<dyn GitRepository>,
+        this: &WeakView<Self>,
+        cx: &mut AsyncWindowContext,
+    ) -> bool {
+        let staged_paths = cx
+            .background_executor()
+            .spawn(async move { repo.staged_paths() })
+            .await;
+        let env_files = staged_paths
+            .log_err()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|path| is_env_file(&path.0))
+            .map(|path| path.0.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        if env_files.is_empty() {
+            return true;
+        }
+        let detail = format!(
+            "These staged files may contain secrets:\n{}",
+            env_files.join("\n")
+        );
+        let Ok(answer) = this.update(cx, |_, cx| {
+            cx.prompt(
+                PromptLevel::Warning,
+                "Commit environment files?",
+                Some(&detail),
+                &["Commit", "Cancel"],
+            )
+        }) else {
+            return false;
+        };
+        matches!(answer.await, Ok(0))
+    }
+
+    /// Toggles amending the last commit, filling in its message if none has been written.
+    fn toggle_amend(&mut self, _: &ToggleAmend, cx: &mut ViewContext<Self>) {
+        self.amend = !self.amend;
+        let is_empty = self.subject_editor.read(cx).text(cx).trim().is_empty()
+            && self.body_editor.read(cx).text(cx).trim().is_empty();
+        if self.amend && is_empty {
+            let message = self
+                .repository
+                .as_ref()
+                .and_then(|repository| repository.repo.head_message());
+            if let Some(message) = message {
+                let (subject, body) = split_commit_message(&message);
+                self.set_message(&subject, &body, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn set_message(&mut self, subject: &str, body: &str, cx: &mut ViewContext<Self>) {
+        self.subject_editor
+            .update(cx, |editor, cx| editor.set_text(subject, cx));
+        self.body_editor
+            .update(cx, |editor, cx| editor.set_text(body, cx));
+    }
+
+    /// Streams a message describing the staged changes from the active language model into
+    /// the editors, or stops the generation in progress.
+    fn generate_commit_message(&mut self, _: &GenerateCommitMessage, cx: &mut ViewContext<Self>) {
+        if self.pending_generation.take().is_some() {
+            cx.notify();
+            return;
+        }
+        let Some(repo) = self
+            .repository
+            .as_ref()
+            .map(|repository| repository.repo.clone())
+        else {
+            return;
+        };
+        let registry = LanguageModelRegistry::read_global(cx);
+        let Some(model) = registry.active_model().filter(|_| {
+            registry
+                .active_provider()
+                .map_or(false, |provider| provider.is_authenticated(cx))
+        }) else {
+            self.error = Some("Configure a language model in the assistant panel first".into());
+            cx.notify();
+            return;
+        };
+        let filter =
+            match DiffFilter::new(&GitPanelSettings::get_global(cx).commit_message_generation) {
+                Ok(filter) => filter,
+                Err(error) => {
+                    self.error = Some(format!("{error:#}").into());
+                    cx.notify();
+                    return;
+                }
+            };
+        let rules = Self::rules(cx);
+
+        self.error = None;
+        self.pending_generation = Some(cx.spawn(|this, mut cx| async move {
+            let result = async {
+                let diff = cx
+                    .background_executor()
+                    .spawn(async move { repo.staged_diff() })
+                    .await?;
+                let diff = filter.prepare(&diff);
+                if diff.text.trim().is_empty() {
+                    return Err(anyhow!("there are no staged changes to describe"));
+                }
+                let request = LanguageModelRequest {
+                    messages: vec![LanguageModelRequestMessage {
+                        role: Role::User,
+                        content: vec![build_prompt(&diff, rules).into()],
+                        cache: false,
+                    }],
+                    tools: Vec::new(),
+                    stop: Vec::new(),
+                    temperature: 1.0,
+                };
+                let mut chunks = model.stream_completion_text(request, &cx).await?;
+                let mut message = String::new();
+                while let Some(chunk) = chunks.next().await {
+                    message.push_str(&chunk?);
+                    let (subject, body) = split_commit_message(clean_generated_message(&message));
+                    this.update(&mut cx, |this, cx| this.set_message(&subject, &body, cx))?;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_generation = None;
+                if let Err(error) = result {
+                    this.error = Some(format!("{error:#}").into());
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn toggle_sign_off(&mut self, _: &ToggleSignOff, cx: &mut ViewContext<Self>) {
+        self.sign_off = !self.sign_off;
+        cx.notify();
+    }
+
+    fn toggle_signing(&mut self, _: &ToggleSigning, cx: &mut ViewContext<Self>) {
+        self.sign = !self.sign;
+        cx.notify();
+    }
+
+    fn apply_commit_type(&mut self, commit_type: &str, cx: &mut ViewContext<Self>) {
+        self.subject_editor.update(cx, |editor, cx| {
+            let subject = apply_conventional_type(&editor.text(cx), commit_type);
+            editor.set_text(subject, cx);
+            editor.move_to_end(&MoveToEnd, cx);
+            editor.focus(cx);
+        });
+    }
+
+    fn open_change(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(worktree_id) = self
+            .repository
+            .as_ref()
+            .map(|repository| repository.worktree_id)
+        else {
+            return;
+        };
+        let Some((repo_path, _)) = self.changes.get(ix) else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id,
+            path: repo_path.0.as_path().into(),
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_path(project_path, None, true, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    fn render_change(&self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let (repo_path, status) = &self.changes[ix];
+        let (indicator, color) = match status {
+            GitFileStatus::Added => ("A", Color::Created),
+            GitFileStatus::Modified => ("M", Color::Modified),
+            GitFileStatus::Conflict => ("C", Color::Conflict),
+        };
+        ListItem::new(ix)
+            .spacing(ui::ListItemSpacing::Sparse)
+            .start_slot(Label::new(indicator).size(LabelSize::Small).color(color))
+            .child(Label::new(repo_path.to_string_lossy().to_string()).single_line())
+            .on_click(cx.listener(move |this, _, cx| this.open_change(ix, cx)))
+            .into_any_element()
+    }
+
+    fn render_editor(&self, editor: &View<Editor>, cx: &mut ViewContext<Self>) -> Div {
+        div()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().editor_background)
+            .child(editor.clone())
+    }
+
+    fn render_toggle(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        checked: bool,
+        on_click: impl Fn(&mut Self, &mut ViewContext<Self>) + 'static,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let selection = if checked {
+            Selection::Selected
+        } else {
+            Selection::Unselected
+        };
+        h_flex()
+            .gap_1()
+            .child(
+                Checkbox::new(id, selection)
+                    .on_click(cx.listener(move |this, _, cx| on_click(this, cx))),
+            )
+            .child(Label::new(label).size(LabelSize::Small))
+    }
+
+    fn render_commit_type_menu(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let panel = cx.view().downgrade();
+        PopoverMenu::new("conventional-commit-type")
+            .trigger(
+                Button::new("commit-type", "Type")
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::ChevronDown)
+                    .icon_size(IconSize::XSmall)
+                    .icon_position(IconPosition::End)
+                    .tooltip(|cx| Tooltip::text("Insert a Conventional Commits type", cx)),
+            )
+            .anchor(AnchorCorner::BottomLeft)
+            .menu(move |cx| {
+                let panel = panel.clone();
+                Some(ContextMenu::build(cx, move |mut menu, _| {
+                    for (commit_type, description) in CONVENTIONAL_COMMIT_TYPES {
+                        let panel = panel.clone();
+                        menu = menu.entry(format!("{commit_type}: {description}"), None, {
+                            move |cx| {
+                                panel
+                                    .update(cx, |panel, cx| {
+                                        panel.apply_commit_type(commit_type, cx)
+                                    })
+                                    .ok();
+                            }
+                        });
+                    }
+                    menu
+                }))
+            })
+    }
+
+    fn render_generate_button(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_generating = self.pending_generation.is_some();
+        IconButton::new(
+            "generate-commit-message",
+            if is_generating {
+                IconName::Stop
+            } else {
+                IconName::Sparkle
+            },
+        )
+        .icon_size(IconSize::Small)
+        .tooltip(move |cx| {
+            Tooltip::for_action(
+                if is_generating {
+                    "Stop Generating"
+                } else {
+                    "Generate Commit Message"
+                },
+                &GenerateCommitMessage,
+                cx,
+            )
+        })
+        .on_click(
+            cx.listener(|this, _, cx| this.generate_commit_message(&GenerateCommitMessage, cx)),
+        )
+    }
+
+    fn render_composer(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let can_commit = self.can_commit();
+        v_flex()
+            .p_2()
+            .gap_1p5()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(self.render_editor(&self.subject_editor, cx))
+            .child(self.render_editor(&self.body_editor, cx))
+            .children(
+                self.lints
+                    .iter()
+                    .filter(|lint| **lint != CommitLint::EmptySubject)
+                    .map(|lint| {
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Icon::new(IconName::Warning)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Warning),
+                            )
+                            .child(
+                                Label::new(lint.message())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_3()
+                    .child(self.render_toggle(
+                        "amend",
+                        "Amend",
+                        self.amend,
+                        |this, cx| this.toggle_amend(&ToggleAmend, cx),
+                        cx,
+                    ))
+                    .child(self.render_toggle(
+                        "sign-off",
+                        "Sign-off",
+                        self.sign_off,
+                        |this, cx| this.toggle_sign_off(&ToggleSignOff, cx),
+                        cx,
+                    ))
+                    .child(self.render_toggle(
+                        "sign",
+                        "Sign",
+                        self.sign,
+                        |this, cx| this.toggle_signing(&ToggleSigning, cx),
+                        cx,
+                    )),
+            )
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(self.render_commit_type_menu(cx))
+                            .child(self.render_generate_button(cx)),
+                    )
+                    .child(
+                        Button::new("commit", if self.amend { "Amend" } else { "Commit" })
+                            .style(ButtonStyle::Filled)
+                            .disabled(!can_commit)
+                            .tooltip(|cx| Tooltip::for_action("Commit Staged Changes", &Commit, cx))
+                            .on_click(cx.listener(|this, _, cx| this.commit(&Commit, cx))),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::XCircle)
+                                .size(IconSize::Small)
+                                .color(Color::Error),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Muted)),
+                )
+            })
+    }
+}
+
+impl Render for GitPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let header = match &self.repository {
+            Some(repository) => repository
+                .branch
+                .clone()
+                .unwrap_or_else(|| "Detached HEAD".into()),
+            None => "No Repository".into(),
+        };
+
+        v_flex()
+            .key_context("GitPanel")
+            .on_action(cx.listener(Self::commit))
+            .on_action(cx.listener(Self::generate_commit_message))
+            .on_action(cx.listener(Self::toggle_amend))
+            .on_action(cx.listener(Self::toggle_sign_off))
+            .on_action(cx.listener(Self::toggle_signing))
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .child(Label::new(header))
+                    .child(
+                        Label::new(format!("{} changes", self.changes.len()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                uniform_list(
+                    cx.view().clone(),
+                    "git-changes",
+                    self.changes.len(),
+                    |this, range, cx| {
+                        range
+                            .map(|ix| this.render_change(ix, cx))
+                            .collect::<Vec<_>>()
+                    },
+                )
+                .flex_grow(),
+            )
+            .when(self.repository.is_some(), |this| {
+                this.child(self.render_composer(cx))
+            })
+    }
+}
+
+impl FocusableView for GitPanel {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for GitPanel {}
+
+impl Panel for GitPanel {
+    fn persistent_name() -> &'static str {
+        "GitPanel"
+    }
+
+    fn position(&self, cx: &WindowContext) -> DockPosition {
+        GitPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
+        settings::update_settings_file::<GitPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, cx: &WindowContext) -> Pixels {
+        self.width
+            .unwrap_or_else(|| GitPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, cx: &mut ViewContext<Self>) {
+        if active {
+            self.refresh(cx);
+        }
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<IconName> {
+        GitPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::FileGit)
+    }
+
+    fn icon_tooltip(&self, _: &WindowContext) -> Option<&'static str> {
+        Some("Git Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+}
@@ -0,0 +1,125 @@
+use git::repository::SigningFormat;
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct GitPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub subject_line_length: usize,
+    pub body_line_length: usize,
+    pub conventional_commits: bool,
+    pub sign_commits: bool,
+    pub signing_format: Option<CommitSigningFormat>,
+    pub signing_key: Option<String>,
+    pub sign_off: bool,
+    pub commit_message_generation: CommitMessageGenerationSettings,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct CommitMessageGenerationSettings {
+    pub max_diff_size: usize,
+    pub excluded_files: Vec<String>,
+    pub redact_patterns: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitSigningFormat {
+    /// Sign with GnuPG.
+    Gpg,
+    /// Sign with an SSH key.
+    Ssh,
+}
+
+impl From<CommitSigningFormat> for SigningFormat {
+    fn from(format: CommitSigningFormat) -> Self {
+        match format {
+            CommitSigningFormat::Gpg => SigningFormat::Gpg,
+            CommitSigningFormat::Ssh => SigningFormat::Ssh,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct GitPanelSettingsContent {
+    /// Whether to show the git panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the git panel.
+    ///
+    /// Default: left
+    pub dock: Option<DockPosition>,
+    /// Default width of the git panel in pixels.
+    ///
+    /// Default: 320
+    pub default_width: Option<f32>,
+    /// The column of the guide shown in the commit subject, past which the subject is
+    /// reported as too long.
+    ///
+    /// Default: 50
+    pub subject_line_length: Option<usize>,
+    /// The column of the guide shown in the commit body, past which lines are reported
+    /// as too long.
+    ///
+    /// Default: 72
+    pub body_line_length: Option<usize>,
+    /// Whether to check that commit subjects follow the Conventional Commits format.
+    ///
+    /// Default: false
+    pub conventional_commits: Option<bool>,
+    /// Whether commits are signed by default.
+    ///
+    /// Default: false
+    pub sign_commits: Option<bool>,
+    /// The kind of signature to create, overriding git's `gpg.format` configuration.
+    ///
+    /// Default: null
+    pub signing_format: Option<CommitSigningFormat>,
+    /// The key to sign commits with, overriding git's `user.signingKey` configuration.
+    ///
+    /// Default: null
+    pub signing_key: Option<String>,
+    /// Whether to add a `Signed-off-by` trailer to commits by default.
+    ///
+    /// Default: false
+    pub sign_off: Option<bool>,
+    /// What is sent to the language model when generating commit messages.
+    pub commit_message_generation: Option<CommitMessageGenerationSettingsContent>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct CommitMessageGenerationSettingsContent {
+    /// The maximum size of the staged diff sent to the model, in bytes. Larger diffs
+    /// are truncated.
+    ///
+    /// Default: 20000
+    pub max_diff_size: Option<usize>,
+    /// Globs matching the files whose changes are never sent to the model.
+    ///
+    /// Default: ["**/.env*", "**/*.pem", "**/*.key", "**/*.lock", "**/package-lock.json"]
+    pub excluded_files: Option<Vec<String>>,
+    /// Regular expressions whose matches in the diff are replaced with `[REDACTED]`
+    /// before it is sent to the model.
+    ///
+    /// Default: ["(?i)(api[_-]?key|secret|token|password)\\s*[:=]\\s*\\S+"]
+    pub redact_patterns: Option<Vec<String>>,
+}
+
+impl Settings for GitPanelSettings {
+    const KEY: Option<&'static str> = Some("git_panel");
+
+    type FileContent = GitPanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,90 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gpui::{
+    BlendMode, RecordedFrame, RecordedOperation, RecordedPrimitive, SceneBenchmark,
+    SerializedBackground, SerializedBounds,
+};
+
+const CELL_SIZE: f32 = 8.;
+
+/// A frame painting a square grid of about `quad_count` quads, like the cells of a large table.
+/// Every other quad has a border, so that not every quad is drawn as a solid one. The grid is
+/// offset vertically by `scroll_top`.
+fn grid_frame(quad_count: usize, scroll_top: f32) -> RecordedFrame {
+    let columns = (quad_count as f32).sqrt().ceil() as usize;
+    let viewport_size = columns as f32 * CELL_SIZE;
+    let operations = (0..quad_count)
+        .map(|ix| {
+            let bounds = SerializedBounds {
+                x: (ix % columns) as f32 * CELL_SIZE,
+                y: (ix / columns) as f32 * CELL_SIZE - scroll_top,
+                width: CELL_SIZE,
+                height: CELL_SIZE,
+            };
+            let border_width = if ix % 2 == 0 { 0. } else { 1. };
+            RecordedOperation::Primitive(RecordedPrimitive::Quad {
+                bounds,
+                content_mask: bounds,
+                transformation: None,
+                background: SerializedBackground::Solid {
+                    color: [(ix % 360) as f32 / 360., 0.5, 0.5, 1.],
+                },
+                border_color: [0., 0., 0., 1.],
+                border_widths: [border_width; 4],
+                corner_radii: [0.; 4],
+                blend_mode: BlendMode::Normal,
+            })
+        })
+        .collect();
+    RecordedFrame {
+        viewport_size: [viewport_size, viewport_size],
+        scale_factor: 1.,
+        operations,
+    }
+}
+
+fn scene_benchmarks(c: &mut Criterion) {
+    static QUAD_COUNTS: &[usize] = &[1_000, 10_000, 62_500];
+
+    let mut group = c.benchmark_group("paint");
+    for quad_count in QUAD_COUNTS {
+        let frame = grid_frame(*quad_count, 0.);
+        let mut benchmark = SceneBenchmark::default();
+        group.throughput(Throughput::Elements(*quad_count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(quad_count),
+            &frame,
+            |b, frame| b.iter(|| benchmark.paint(black_box(frame)).unwrap()),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("diff_unchanged");
+    for quad_count in QUAD_COUNTS {
+        let frame = grid_frame(*quad_count, 0.);
+        let mut benchmark = SceneBenchmark::default();
+        benchmark.paint(&frame).unwrap();
+        benchmark.paint(&frame).unwrap();
+        group.throughput(Throughput::Elements(*quad_count as u64));
+        group.bench_function(BenchmarkId::from_parameter(quad_count), |b| {
+            b.iter(|| black_box(benchmark.diff()))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("diff_scrolled");
+    for quad_count in QUAD_COUNTS {
+        let mut benchmark = SceneBenchmark::default();
+        benchmark.paint(&grid_frame(*quad_count, 0.)).unwrap();
+        benchmark
+            .paint(&grid_frame(*quad_count, CELL_SIZE / 2.))
+            .unwrap();
+        group.throughput(Throughput::Elements(*quad_count as u64));
+        group.bench_function(BenchmarkId::from_parameter(quad_count), |b| {
+            b.iter(|| black_box(benchmark.diff()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, scene_benchmarks);
+criterion_main!(benches);
@@ -95,17 +95,27 @@ mod macos {
             "Pixels".into(),
             "PointF".into(),
             "Hsla".into(),
+            "LinearColorStop".into(),
+            "Background".into(),
             "ContentMask".into(),
             "Uniforms".into(),
             "AtlasTile".into(),
             "PathRasterizationInputIndex".into(),
             "PathVertex_ScaledPixels".into(),
+            "GlyphRasterizationInputIndex".into(),
+            "GlyphRasterizationJob".into(),
             "ShadowInputIndex".into(),
             "Shadow".into(),
+            "BackdropBlurInputIndex".into(),
+            "BackdropBlur".into(),
             "QuadInputIndex".into(),
             "Underline".into(),
+            "UnderlineKind".into(),
+            "BlendMode".into(),
             "UnderlineInputIndex".into(),
             "Quad".into(),
+            "SolidQuadInputIndex".into(),
+            "SolidQuad".into(),
             "SpriteInputIndex".into(),
             "MonochromeSprite".into(),
             "PolychromeSprite".into(),
@@ -123,6 +133,7 @@ mod macos {
             crate_dir.join("src/scene.rs"),
             crate_dir.join("src/geometry.rs"),
             crate_dir.join("src/color.rs"),
+            crate_dir.join("src/style.rs"),
             crate_dir.join("src/window.rs"),
             crate_dir.join("src/platform.rs"),
             crate_dir.join("src/platform/mac/metal_renderer.rs"),
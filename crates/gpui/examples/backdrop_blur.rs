@@ -0,0 +1,50 @@
+use gpui::*;
+
+struct BackdropBlur {}
+
+impl Render for BackdropBlur {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let colors = [0xf38ba8, 0xfab387, 0xf9e2af, 0xa6e3a1, 0x89b4fa, 0xcba6f7];
+        div()
+            .relative()
+            .size_full()
+            .bg(rgb(0xffffff))
+            .child(
+                div()
+                    .flex()
+                    .size_full()
+                    .children(colors.map(|color| div().flex_1().h_full().bg(rgb(color)))),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .top(px(75.))
+                    .left(px(50.))
+                    .w(px(200.))
+                    .h(px(150.))
+                    .rounded_lg()
+                    .backdrop_blur(px(16.))
+                    .bg(hsla(0., 0., 1., 0.2))
+                    .border_1()
+                    .border_color(hsla(0., 0., 1., 0.5))
+                    .flex()
+                    .justify_center()
+                    .items_center()
+                    .child("Frosted glass"),
+            )
+    }
+}
+
+fn main() {
+    App::new().run(|cx: &mut AppContext| {
+        let bounds = Bounds::centered(None, size(px(300.0), px(300.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |cx| cx.new_view(|_cx| BackdropBlur {}),
+        )
+        .unwrap();
+    });
+}
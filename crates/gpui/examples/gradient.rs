@@ -0,0 +1,74 @@
+use gpui::*;
+
+struct GradientViewer;
+
+impl Render for GradientViewer {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .size_full()
+            .p_4()
+            .gap_4()
+            .bg(rgb(0xffffff))
+            .child(
+                div()
+                    .h_24()
+                    .bg_gradient(90., [(rgb(0xff0000), 0.), (rgb(0x0000ff), 1.)]),
+            )
+            .child(div().h_24().rounded_lg().bg_gradient(
+                180.,
+                [
+                    (rgb(0xfde047), 0.),
+                    (rgb(0xf97316), 0.5),
+                    (rgb(0xdb2777), 1.),
+                ],
+            ))
+            .child(
+                div()
+                    .h_24()
+                    .border_4()
+                    .border_color(rgb(0x000000))
+                    .bg(linear_gradient(
+                        45.,
+                        [
+                            linear_color_stop(rgb(0x22c55e), 0.),
+                            linear_color_stop(rgba(0x22c55e00), 1.),
+                        ],
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_4()
+                    .child(div().size_24().rounded_full().bg(radial_gradient(
+                        point(0.3, 0.3),
+                        [(rgb(0xffffff), 0.), (rgb(0x2563eb), 0.6)],
+                    )))
+                    .child(div().size_24().rounded_full().bg(conic_gradient(
+                        0.,
+                        point(0.5, 0.5),
+                        [
+                            (rgb(0xef4444), 0.),
+                            (rgb(0x22c55e), 0.33),
+                            (rgb(0x3b82f6), 0.67),
+                            (rgb(0xef4444), 1.),
+                        ],
+                    ))),
+            )
+    }
+}
+
+fn main() {
+    App::new().run(|cx: &mut AppContext| {
+        let bounds = Bounds::centered(None, size(px(400.0), px(400.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |cx| cx.new_view(|_cx| GradientViewer),
+        )
+        .unwrap();
+    });
+}
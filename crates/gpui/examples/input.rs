@@ -389,7 +389,7 @@ impl Element for TextElement {
                     underline: Some(UnderlineStyle {
                         color: Some(run.color),
                         thickness: px(1.0),
-                        wavy: false,
+                        ..Default::default()
                     }),
                     ..run.clone()
                 },
@@ -0,0 +1,105 @@
+//! Replays the frames of a scene recording, saved from `WindowContext::record_scenes`, through
+//! the renderer without the application that drew them:
+//!
+//! ```sh
+//! cargo run -p gpui --example replay_scenes -- recording.json [frame]
+//! ```
+//!
+//! The frames are played in a loop, unless a frame is given, in which case only it is shown.
+
+use std::{path::PathBuf, rc::Rc, time::Duration};
+
+use gpui::*;
+
+struct ReplayScenes {
+    recording: Rc<SceneRecording>,
+    frame_index: usize,
+    _playback: Option<Task<()>>,
+}
+
+impl ReplayScenes {
+    fn new(recording: SceneRecording, frame: Option<usize>, cx: &mut ViewContext<Self>) -> Self {
+        let playback = frame.is_none().then(|| {
+            cx.spawn(|view, mut cx| async move {
+                loop {
+                    Timer::after(Duration::from_millis(16)).await;
+                    let advanced = view.update(&mut cx, |view, cx| {
+                        view.frame_index = (view.frame_index + 1) % view.recording.frames.len();
+                        cx.notify();
+                    });
+                    if advanced.is_err() {
+                        break;
+                    }
+                }
+            })
+        });
+        Self {
+            recording: Rc::new(recording),
+            frame_index: frame.unwrap_or(0),
+            _playback: playback,
+        }
+    }
+}
+
+impl Render for ReplayScenes {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let recording = self.recording.clone();
+        let frame_index = self.frame_index;
+        canvas(
+            |_, _| {},
+            move |_, _, cx| {
+                if let Err(error) = cx.paint_recorded_frame(&recording, frame_index) {
+                    eprintln!("failed to replay frame {frame_index}: {error:#}");
+                }
+            },
+        )
+        .size_full()
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next().map(PathBuf::from) else {
+        eprintln!("usage: replay_scenes <recording> [frame]");
+        std::process::exit(1);
+    };
+    let frame = args.next().map(|frame| {
+        frame.parse::<usize>().unwrap_or_else(|_| {
+            eprintln!("invalid frame index: {frame}");
+            std::process::exit(1);
+        })
+    });
+    let recording = match SceneRecording::load(&path) {
+        Ok(recording) if !recording.frames.is_empty() => recording,
+        Ok(_) => {
+            eprintln!("{} has no frames", path.display());
+            std::process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            std::process::exit(1);
+        }
+    };
+    if frame.map_or(false, |frame| frame >= recording.frames.len()) {
+        eprintln!("the recording only has {} frames", recording.frames.len());
+        std::process::exit(1);
+    }
+
+    App::new().run(move |cx: &mut AppContext| {
+        // Primitives are recorded in device pixels, so the window is opened at the size it was
+        // recorded at.
+        let first_frame = &recording.frames[0];
+        let [width, height] = first_frame
+            .viewport_size
+            .map(|extent| extent / first_frame.scale_factor);
+        let bounds = Bounds::centered(None, size(px(width), px(height)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |cx| cx.new_view(|cx| ReplayScenes::new(recording, frame, cx)),
+        )
+        .unwrap();
+    });
+}
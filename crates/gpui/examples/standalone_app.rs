@@ -0,0 +1,89 @@
+//! An app packaged the way a standalone GPUI application would be: with an app id, a window icon,
+//! assets loaded from disk next to the executable, and a single running instance.
+//!
+//! Run it a second time with some arguments to see them forwarded to the first instance.
+
+use gpui::*;
+use std::sync::Arc;
+
+const APP_ID: &str = "dev.gpui.StandaloneApp";
+
+struct Launches {
+    args: Vec<Vec<String>>,
+}
+
+impl Render for Launches {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .size_full()
+            .p_4()
+            .bg(rgb(0x1e1e1e))
+            .text_color(rgb(0xffffff))
+            .child("Launched with:")
+            .children(
+                self.args
+                    .iter()
+                    .map(|args| div().child(format!("{args:?}")).text_color(rgb(0xa0a0a0))),
+            )
+    }
+}
+
+fn main() {
+    env_logger::init();
+
+    // Assets are read from the `examples/image` directory here, a packaged app would use
+    // `DirectoryAssets::beside_executable("assets")` instead.
+    let assets = DirectoryAssets::new(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/image"));
+    let icon = assets
+        .load("app-icon.png")
+        .ok()
+        .flatten()
+        .map(|bytes| Arc::new(Image::from_bytes(ImageFormat::Png, bytes.into_owned())));
+
+    let mut app = App::new().with_app_id(APP_ID).with_assets(assets);
+    if let Some(icon) = icon {
+        app = app.with_window_icon(icon);
+    }
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let launches = std::rc::Rc::new(std::cell::RefCell::new(None::<WindowHandle<Launches>>));
+    let is_first_instance = app
+        .ensure_single_instance(args.clone(), {
+            let launches = launches.clone();
+            move |args, cx| {
+                if let Some(window) = *launches.borrow() {
+                    window
+                        .update(cx, |launches, cx| {
+                            launches.args.push(args);
+                            cx.activate_window();
+                            cx.notify();
+                        })
+                        .ok();
+                }
+            }
+        })
+        .unwrap_or(true);
+    if !is_first_instance {
+        println!("{APP_ID} is already running, forwarded the arguments to it");
+        return;
+    }
+
+    app.run(move |cx: &mut AppContext| {
+        cx.load_fonts("fonts").ok();
+
+        let bounds = Bounds::centered(None, size(px(500.0), px(300.0)), cx);
+        let window = cx
+            .open_window(
+                WindowOptions {
+                    window_bounds: Some(WindowBounds::Windowed(bounds)),
+                    ..Default::default()
+                },
+                |cx| cx.new_view(|_cx| Launches { args: vec![args] }),
+            )
+            .unwrap();
+        *launches.borrow_mut() = Some(window);
+    });
+}
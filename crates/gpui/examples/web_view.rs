@@ -0,0 +1,146 @@
+use gpui::*;
+use prelude::FluentBuilder;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <body style="font-family: -apple-system, sans-serif">
+    <h1>Hello from a web view</h1>
+    <p><a href="https://zed.dev">Visit zed.dev</a>, then go back.</p>
+    <button onclick="window.gpui.postMessage({ clicked: new Date().toLocaleTimeString() })">
+      Send a message to the app
+    </button>
+  </body>
+</html>"#;
+
+struct Browser {
+    web_view: Option<Model<WebView>>,
+    status: SharedString,
+    _subscription: Option<Subscription>,
+}
+
+impl Browser {
+    fn new(cx: &mut ViewContext<Self>) -> Self {
+        let web_view = match cx.new_web_view() {
+            Ok(web_view) => web_view,
+            Err(error) => {
+                return Self {
+                    web_view: None,
+                    status: error.to_string().into(),
+                    _subscription: None,
+                }
+            }
+        };
+        web_view.update(cx, |web_view, _| web_view.load_html(PAGE, None));
+        let subscription = cx.subscribe(&web_view, |this, _, event, cx| {
+            this.status = match event {
+                WebViewEvent::NavigationStarted => "Loading…".into(),
+                WebViewEvent::NavigationFinished => "Loaded".into(),
+                WebViewEvent::NavigationFailed(error) => error.clone(),
+                WebViewEvent::Message(message) => format!("Message: {message}").into(),
+            };
+            cx.notify();
+        });
+
+        Self {
+            web_view: Some(web_view),
+            status: "Loading…".into(),
+            _subscription: Some(subscription),
+        }
+    }
+
+    fn button(
+        id: &'static str,
+        label: &'static str,
+        enabled: bool,
+        on_click: impl Fn(&mut WebView) + 'static,
+        web_view: &Model<WebView>,
+    ) -> impl IntoElement {
+        let web_view = web_view.clone();
+        div()
+            .id(id)
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(rgb(0x3a3a3a))
+            .when(!enabled, |this| this.opacity(0.5))
+            .when(enabled, |this| {
+                this.hover(|style| style.bg(rgb(0x505050)))
+                    .on_click(move |_, cx| web_view.update(cx, |web_view, _| on_click(web_view)))
+            })
+            .child(label)
+    }
+}
+
+impl Render for Browser {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let Some(web_view) = self.web_view.clone() else {
+            return div()
+                .size_full()
+                .flex()
+                .justify_center()
+                .items_center()
+                .bg(rgb(0x1e1e1e))
+                .text_color(rgb(0xffffff))
+                .child(self.status.clone());
+        };
+        let navigation = web_view.read(cx).navigation().clone();
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(rgb(0x1e1e1e))
+            .text_color(rgb(0xffffff))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .p_2()
+                    .child(Self::button(
+                        "back",
+                        "Back",
+                        navigation.can_go_back,
+                        WebView::go_back,
+                        &web_view,
+                    ))
+                    .child(Self::button(
+                        "forward",
+                        "Forward",
+                        navigation.can_go_forward,
+                        WebView::go_forward,
+                        &web_view,
+                    ))
+                    .child(Self::button(
+                        "reload",
+                        "Reload",
+                        true,
+                        WebView::reload,
+                        &web_view,
+                    ))
+                    .child(
+                        navigation
+                            .title
+                            .or(navigation.url)
+                            .unwrap_or_else(|| "Untitled".into()),
+                    ),
+            )
+            .child(gpui::web_view(web_view).flex_1())
+            .child(div().p_2().child(self.status.clone()))
+    }
+}
+
+fn main() {
+    App::new().run(|cx: &mut AppContext| {
+        let bounds = Bounds::centered(None, size(px(800.0), px(600.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |cx| cx.new_view(Browser::new),
+        )
+        .unwrap();
+        cx.activate(true);
+    });
+}
@@ -56,8 +56,10 @@ fn build_window_options(display_id: DisplayId, bounds: Bounds<Pixels>) -> Window
         kind: WindowKind::PopUp,
         is_movable: false,
         app_id: None,
+        icon: None,
         window_min_size: None,
         window_decorations: None,
+        color_space: ColorSpace::default(),
     }
 }
 
@@ -5,16 +5,19 @@ use std::{
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     rc::{Rc, Weak},
-    sync::{atomic::Ordering::SeqCst, Arc},
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
     time::Duration,
 };
 
 use anyhow::{anyhow, Result};
 use derive_more::{Deref, DerefMut};
 use futures::{
-    channel::oneshot,
+    channel::{mpsc, oneshot},
     future::{LocalBoxFuture, Shared},
-    Future, FutureExt,
+    Future, FutureExt, StreamExt,
 };
 use slotmap::SlotMap;
 
@@ -22,23 +25,30 @@ pub use async_context::*;
 use collections::{FxHashMap, FxHashSet, VecDeque};
 pub use entity_map::*;
 use http_client::HttpClient;
+#[cfg(any(test, feature = "test-support"))]
+pub use input_script::*;
 pub use model_context::*;
 #[cfg(any(test, feature = "test-support"))]
 pub use test_context::*;
 use util::ResultExt;
 
 use crate::{
-    current_platform, hash, init_app_menus, Action, ActionRegistry, Any, AnyView, AnyWindowHandle,
-    Asset, AssetSource, BackgroundExecutor, ClipboardItem, Context, DispatchPhase, DisplayId,
-    Entity, EventEmitter, ForegroundExecutor, Global, KeyBinding, Keymap, Keystroke, LayoutId,
+    claim_instance, current_platform, hash, init_app_menus, register_custom_shader,
+    AccessibilityOverrides, AccessibilityPreferences, Action, ActionRegistry, Any, AnyView,
+    AnyWindowHandle, Asset, AssetSource, BackgroundExecutor, ClipboardItem, Context, CustomCursor,
+    CustomShaderId, CustomShaderSource, DispatchPhase, DisplayId, Entity, EventEmitter,
+    ForegroundExecutor, Global, Image, KeyBinding, Keymap, Keystroke, LayoutDirection, LayoutId,
     Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, Point,
-    PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
-    SharedString, SubscriberSet, Subscription, SvgRenderer, Task, TextSystem, View, ViewContext,
-    Window, WindowAppearance, WindowContext, WindowHandle, WindowId,
+    PowerState, PromptBuilder, PromptHandle, PromptLevel, Render, RenderImage,
+    RenderablePromptHandle, Reservation, SharedString, SubscriberSet, Subscription, SvgRenderer,
+    Task, TextSystem, View, ViewContext, Window, WindowAppearance, WindowContext, WindowHandle,
+    WindowId,
 };
 
 mod async_context;
 mod entity_map;
+#[cfg(any(test, feature = "test-support"))]
+mod input_script;
 mod model_context;
 #[cfg(any(test, feature = "test-support"))]
 mod test_context;
@@ -46,6 +56,8 @@ mod test_context;
 /// The duration for which futures returned from [AppContext::on_app_context] or [ModelContext::on_app_quit] can run before the application fully quits.
 pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(100);
 
+const POWER_STATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Temporary(?) wrapper around [`RefCell<AppContext>`] to help us debug any double borrows.
 /// Strongly consider removing after stabilization.
 #[doc(hidden)]
@@ -150,6 +162,51 @@ impl App {
         self
     }
 
+    /// Set the application identifier, used as the default [`crate::WindowOptions::app_id`] and to find
+    /// other instances in [`App::ensure_single_instance`].
+    pub fn with_app_id(self, app_id: impl Into<SharedString>) -> Self {
+        self.0.borrow_mut().app_id = Some(app_id.into());
+        self
+    }
+
+    /// Set the icon used for windows that don't specify [`crate::WindowOptions::icon`].
+    pub fn with_window_icon(self, icon: Arc<Image>) -> Self {
+        self.0.borrow_mut().window_icon = Some(icon);
+        self
+    }
+
+    /// Ensure that only one instance of the application runs at a time, based on the app id
+    /// configured with [`App::with_app_id`].
+    ///
+    /// Returns `false` if another instance is already running, in which case `args` were forwarded
+    /// to it and this process should exit instead of calling [`App::run`]. Otherwise, `on_args`
+    /// is called with the arguments of every instance launched afterwards.
+    pub fn ensure_single_instance<F>(&self, args: Vec<String>, mut on_args: F) -> Result<bool>
+    where
+        F: 'static + FnMut(Vec<String>, &mut AppContext),
+    {
+        let cx = self.0.borrow();
+        let app_id = cx
+            .app_id
+            .clone()
+            .ok_or_else(|| anyhow!("an app id is required to ensure a single instance"))?;
+        let Some(listener) = claim_instance(&app_id, &args)? else {
+            return Ok(false);
+        };
+
+        let (args_tx, mut args_rx) = mpsc::unbounded();
+        std::thread::spawn(move || listener.listen(|args| args_tx.unbounded_send(args).is_ok()));
+        cx.spawn(|cx| async move {
+            while let Some(args) = args_rx.next().await {
+                if cx.update(|cx| on_args(args, cx)).is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+        Ok(true)
+    }
+
     /// Start the application. The provided callback will be called once the
     /// app is fully launched.
     pub fn run<F>(self, on_finish_launching: F)
@@ -214,6 +271,8 @@ type Handler = Box<dyn FnMut(&mut AppContext) -> bool + 'static>;
 type Listener = Box<dyn FnMut(&dyn Any, &mut AppContext) -> bool + 'static>;
 pub(crate) type KeystrokeObserver =
     Box<dyn FnMut(&KeystrokeEvent, &mut WindowContext) -> bool + 'static>;
+pub(crate) type ClickedActionObserver =
+    Box<dyn FnMut(&dyn Action, &mut WindowContext) -> bool + 'static>;
 type QuitHandler = Box<dyn FnOnce(&mut AppContext) -> LocalBoxFuture<'static, ()> + 'static>;
 type ReleaseListener = Box<dyn FnOnce(&mut dyn Any, &mut AppContext) + 'static>;
 type NewViewListener = Box<dyn FnMut(AnyView, &mut WindowContext) + 'static>;
@@ -235,6 +294,8 @@ pub struct AppContext {
     asset_source: Arc<dyn AssetSource>,
     pub(crate) svg_renderer: SvgRenderer,
     http_client: Arc<dyn HttpClient>,
+    pub(crate) app_id: Option<SharedString>,
+    pub(crate) window_icon: Option<Arc<Image>>,
     pub(crate) globals_by_type: FxHashMap<TypeId, Box<dyn Any>>,
     pub(crate) entities: EntityMap,
     pub(crate) new_view_observers: SubscriberSet<TypeId, NewViewListener>,
@@ -250,12 +311,18 @@ pub struct AppContext {
     // TypeId is the type of the event that the listener callback expects
     pub(crate) event_listeners: SubscriberSet<EntityId, (TypeId, Listener)>,
     pub(crate) keystroke_observers: SubscriberSet<(), KeystrokeObserver>,
+    pub(crate) clicked_action_observers: SubscriberSet<(), ClickedActionObserver>,
     pub(crate) release_listeners: SubscriberSet<EntityId, ReleaseListener>,
     pub(crate) global_observers: SubscriberSet<TypeId, Handler>,
     pub(crate) quit_observers: SubscriberSet<(), QuitHandler>,
     pub(crate) layout_id_buffer: Vec<LayoutId>, // We recycle this memory across layout requests.
     pub(crate) propagate_event: bool,
     pub(crate) prompt_builder: Option<PromptBuilder>,
+    accessibility_overrides: AccessibilityOverrides,
+    default_layout_direction: LayoutDirection,
+    power_state: PowerState,
+    power_state_observers: SubscriberSet<(), Handler>,
+    power_state_poll_task: Option<Task<()>>,
 }
 
 impl AppContext {
@@ -290,6 +357,8 @@ impl AppContext {
                 loading_assets: Default::default(),
                 asset_source,
                 http_client,
+                app_id: None,
+                window_icon: None,
                 globals_by_type: FxHashMap::default(),
                 entities,
                 new_view_observers: SubscriberSet::new(),
@@ -304,11 +373,17 @@ impl AppContext {
                 event_listeners: SubscriberSet::new(),
                 release_listeners: SubscriberSet::new(),
                 keystroke_observers: SubscriberSet::new(),
+                clicked_action_observers: SubscriberSet::new(),
                 global_observers: SubscriberSet::new(),
                 quit_observers: SubscriberSet::new(),
                 layout_id_buffer: Default::default(),
                 propagate_event: true,
                 prompt_builder: Some(PromptBuilder::Default),
+                accessibility_overrides: AccessibilityOverrides::default(),
+                default_layout_direction: LayoutDirection::default(),
+                power_state: platform.power_state(),
+                power_state_observers: SubscriberSet::new(),
+                power_state_poll_task: None,
             }),
         });
 
@@ -676,6 +751,119 @@ impl AppContext {
         self.platform.should_auto_hide_scrollbars()
     }
 
+    /// Returns the accessibility preferences to respect, which are the ones configured in the
+    /// operating system with the overrides set by [`AppContext::set_accessibility_overrides`].
+    pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        self.accessibility_overrides
+            .apply(self.platform.accessibility_preferences())
+    }
+
+    /// Returns the accessibility preferences configured in the operating system, ignoring
+    /// overrides.
+    pub fn system_accessibility_preferences(&self) -> AccessibilityPreferences {
+        self.platform.accessibility_preferences()
+    }
+
+    /// Returns where the machine is drawing its power from and whether the user asked the
+    /// operating system to save power.
+    pub fn power_state(&self) -> PowerState {
+        if self.power_state_poll_task.is_some() {
+            self.power_state
+        } else {
+            self.platform.power_state()
+        }
+    }
+
+    /// Invokes the given callback whenever the [`PowerState`] changes, e.g. when the machine is
+    /// unplugged. Changes are detected by polling, so they're reported with a delay of up to
+    /// a few seconds.
+    pub fn observe_power_state(
+        &mut self,
+        mut f: impl FnMut(PowerState, &mut AppContext) + 'static,
+    ) -> Subscription {
+        if self.power_state_poll_task.is_none() {
+            self.power_state = self.platform.power_state();
+            self.power_state_poll_task = Some(self.spawn(|mut cx| async move {
+                loop {
+                    cx.background_executor()
+                        .timer(POWER_STATE_POLL_INTERVAL)
+                        .await;
+                    if cx.update(|cx| cx.refresh_power_state()).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+
+        let (subscription, activate) = self.power_state_observers.insert(
+            (),
+            Box::new(move |cx| {
+                f(cx.power_state, cx);
+                true
+            }),
+        );
+        activate();
+        subscription
+    }
+
+    /// Returns a future that resolves once the machine no longer has to conserve power, so that
+    /// work that can wait, such as downloading updates, doesn't drain the battery.
+    pub fn wait_while_conserving_power(&mut self) -> impl Future<Output = ()> {
+        let (tx, rx) = oneshot::channel();
+        let subscription = if self.power_state().should_conserve_power() {
+            let mut tx = Some(tx);
+            Some(self.observe_power_state(move |power_state, _| {
+                if !power_state.should_conserve_power() {
+                    if let Some(tx) = tx.take() {
+                        tx.send(()).ok();
+                    }
+                }
+            }))
+        } else {
+            tx.send(()).ok();
+            None
+        };
+
+        async move {
+            rx.await.ok();
+            drop(subscription);
+        }
+    }
+
+    pub(crate) fn refresh_power_state(&mut self) {
+        let power_state = self.platform.power_state();
+        if self.power_state != power_state {
+            self.power_state = power_state;
+            self.power_state_observers
+                .clone()
+                .retain(&(), |observer| observer(self));
+        }
+    }
+
+    /// Overrides the accessibility preferences read from the operating system, e.g. with the
+    /// ones configured in the application's settings, and redraws all windows.
+    pub fn set_accessibility_overrides(&mut self, overrides: AccessibilityOverrides) {
+        if self.accessibility_overrides != overrides {
+            self.accessibility_overrides = overrides;
+            self.refresh();
+        }
+    }
+
+    /// The direction in which the elements of every window are laid out, unless an element
+    /// overrides it for its subtree. See [`WindowContext::layout_direction`].
+    pub fn default_layout_direction(&self) -> LayoutDirection {
+        self.default_layout_direction
+    }
+
+    /// Sets the direction in which the elements of every window are laid out, e.g. to mirror the
+    /// UI when it's translated to a right-to-left language, and redraws all windows.
+    pub fn set_default_layout_direction(&mut self, direction: LayoutDirection) {
+        if self.default_layout_direction != direction {
+            self.default_layout_direction = direction;
+            self.refresh();
+        }
+    }
+
     /// Restart the application.
     pub fn restart(&self, binary_path: Option<PathBuf>) {
         self.platform.restart(binary_path)
@@ -691,6 +879,11 @@ impl AppContext {
         self.http_client.clone()
     }
 
+    /// Returns the application identifier configured with [`App::with_app_id`].
+    pub fn app_id(&self) -> Option<&SharedString> {
+        self.app_id.as_ref()
+    }
+
     /// Returns the SVG renderer GPUI uses
     pub(crate) fn svg_renderer(&self) -> SvgRenderer {
         self.svg_renderer.clone()
@@ -816,6 +1009,24 @@ impl AppContext {
     fn apply_notify_effect(&mut self, emitter: EntityId) {
         self.pending_notifications.remove(&emitter);
 
+        // Views can be rendered in windows other than the one they're updated in, such as a
+        // panel that has been popped out into a window of its own.
+        for window in self.windows.values_mut().flatten() {
+            if window.dirty_views.contains(&emitter) {
+                continue;
+            }
+            let view_path = window.rendered_frame.dispatch_tree.view_path(emitter);
+            if view_path.is_empty() {
+                continue;
+            }
+            for view_id in view_path.into_iter().rev() {
+                if !window.dirty_views.insert(view_id) {
+                    break;
+                }
+            }
+            window.dirty.set(true);
+        }
+
         self.observers
             .clone()
             .retain(&emitter, |handler| handler(self));
@@ -874,6 +1085,7 @@ impl AppContext {
 
     /// Spawns the future returned by the given function on the thread pool. The closure will be invoked
     /// with [AsyncAppContext], which allows the application state to be accessed across await points.
+    #[track_caller]
     pub fn spawn<Fut, R>(&self, f: impl FnOnce(AsyncAppContext) -> Fut) -> Task<R>
     where
         Fut: Future<Output = R> + 'static,
@@ -895,11 +1107,31 @@ impl AppContext {
         &self.asset_source
     }
 
+    /// Add all `.ttf` and `.otf` fonts in the given directory of the asset source to the text system.
+    pub fn load_fonts(&self, path: &str) -> Result<()> {
+        let mut fonts = Vec::new();
+        for font_path in self.asset_source.list(path)? {
+            if font_path.ends_with(".ttf") || font_path.ends_with(".otf") {
+                if let Some(font) = self.asset_source.load(&font_path)? {
+                    fonts.push(font);
+                }
+            }
+        }
+        self.text_system.add_fonts(fonts)
+    }
+
     /// Accessor for the text system.
     pub fn text_system(&self) -> &Arc<TextSystem> {
         &self.text_system
     }
 
+    /// Registers a shader for elements to paint with [`WindowContext::paint_custom_shader`].
+    /// Shaders are compiled by each window's renderer the first time they're drawn, so this is
+    /// best done once at startup. See [`CustomShaderSource`] for what the source has to define.
+    pub fn register_custom_shader(&mut self, source: CustomShaderSource) -> CustomShaderId {
+        register_custom_shader(source)
+    }
+
     /// Check whether a global of the given type has been assigned.
     pub fn has_global<G: Global>(&self) -> bool {
         self.globals_by_type.contains_key(&TypeId::of::<G>())
@@ -1079,6 +1311,23 @@ impl AppContext {
         )
     }
 
+    /// Register a callback to be invoked when an action is dispatched in any window in response to
+    /// a mouse event, such as clicking a button, rather than a keystroke.
+    pub fn observe_clicked_actions(
+        &mut self,
+        mut f: impl FnMut(&dyn Action, &mut WindowContext) + 'static,
+    ) -> Subscription {
+        let (subscription, activate) = self.clicked_action_observers.insert(
+            (),
+            Box::new(move |action, cx| {
+                f(action, cx);
+                true
+            }),
+        );
+        activate();
+        subscription
+    }
+
     /// Register key bindings.
     pub fn bind_keys(&mut self, bindings: impl IntoIterator<Item = KeyBinding>) {
         self.keymap.borrow_mut().add_bindings(bindings);
@@ -1185,6 +1434,27 @@ impl AppContext {
                 .contains_key(&action.as_any().type_id())
     }
 
+    /// Creates a cursor drawn from the first frame of the given image, with one image pixel per
+    /// logical pixel, and its hotspot at the given offset from the image's top left corner.
+    /// Set it on elements with [`CursorStyle::Custom`](crate::CursorStyle::Custom).
+    pub fn create_cursor(
+        &mut self,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    ) -> CustomCursor {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let cursor = CustomCursor(NEXT_ID.fetch_add(1, SeqCst));
+        self.platform.register_cursor(cursor, image, hotspot);
+        cursor
+    }
+
+    /// Plays one of the operating system's named sounds, such as `"Glass"` on macOS or
+    /// `"SystemAsterisk"` on Windows, at a volume between 0 and 1. Platforms without named
+    /// system sounds ignore this.
+    pub fn play_system_sound(&self, name: &str, volume: f32) {
+        self.platform.play_system_sound(name, volume.clamp(0., 1.));
+    }
+
     /// Sets the menu bar for this application. This will replace any existing menu bar.
     pub fn set_menus(&mut self, menus: Vec<Menu>) {
         self.platform.set_menus(menus, &self.keymap.borrow());
@@ -1403,6 +1673,8 @@ impl Context for AppContext {
             if window.removed {
                 cx.window_handles.remove(&handle.id);
                 cx.windows.remove(handle.id);
+                #[cfg(any(test, feature = "test-support"))]
+                cx.entities.window_closed(handle.id);
             } else {
                 cx.windows
                     .get_mut(handle.id)
@@ -1,3 +1,5 @@
+#[cfg(any(test, feature = "test-support"))]
+use crate::WindowId;
 use crate::{seal::Sealed, AppContext, Context, Entity, ModelContext};
 use anyhow::{anyhow, Result};
 use derive_more::{Deref, DerefMut};
@@ -72,6 +74,10 @@ impl EntityMap {
                 leak_detector: LeakDetector {
                     next_handle_id: 0,
                     entity_handles: HashMap::default(),
+                    entity_types: HashMap::default(),
+                    holders: Vec::new(),
+                    window_views: None,
+                    closed_window_views: Vec::new(),
                 },
             })),
         }
@@ -80,6 +86,12 @@ impl EntityMap {
     /// Reserve a slot for an entity, which you can subsequently use with `insert`.
     pub fn reserve<T: 'static>(&self) -> Slot<T> {
         let id = self.ref_counts.write().counts.insert(1.into());
+        #[cfg(any(test, feature = "test-support"))]
+        self.ref_counts
+            .write()
+            .leak_detector
+            .entity_types
+            .insert(id, type_name::<T>());
         Slot(Model::new(id, Arc::downgrade(&self.ref_counts)))
     }
 
@@ -102,6 +114,8 @@ impl EntityMap {
                 .remove(model.entity_id)
                 .unwrap_or_else(|| double_lease_panic::<T>("update")),
         );
+        #[cfg(any(test, feature = "test-support"))]
+        self.push_holder(model.entity_id);
         Lease {
             model,
             entity,
@@ -113,6 +127,8 @@ impl EntityMap {
     pub fn end_lease<T>(&mut self, mut lease: Lease<T>) {
         self.entities
             .insert(lease.model.entity_id, lease.entity.take().unwrap());
+        #[cfg(any(test, feature = "test-support"))]
+        self.pop_holder();
     }
 
     pub fn read<T: 'static>(&self, model: &Model<T>) -> &T {
@@ -137,6 +153,8 @@ impl EntityMap {
             .into_iter()
             .filter_map(|entity_id| {
                 let count = ref_counts.counts.remove(entity_id).unwrap();
+                #[cfg(any(test, feature = "test-support"))]
+                ref_counts.leak_detector.entity_types.remove(&entity_id);
                 debug_assert_eq!(
                     count.load(SeqCst),
                     0,
@@ -148,6 +166,61 @@ impl EntityMap {
             })
             .collect()
     }
+
+    /// Attributes the handles created from now on to the given entity, until the matching
+    /// call to `pop_holder`.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn push_holder(&self, entity_id: EntityId) {
+        self.ref_counts
+            .write()
+            .leak_detector
+            .holders
+            .push(entity_id);
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn pop_holder(&self) {
+        self.ref_counts.write().leak_detector.holders.pop();
+    }
+
+    /// Starts recording the views created in each window, so that the ones still alive after
+    /// their window closes can be reported by `assert_closed_window_views_released`.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn detect_window_leaks(&self) {
+        self.ref_counts
+            .write()
+            .leak_detector
+            .window_views
+            .get_or_insert_with(HashMap::default);
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn view_created(&self, window_id: WindowId, view_id: EntityId) {
+        if let Some(window_views) = self.ref_counts.write().leak_detector.window_views.as_mut() {
+            window_views.entry(window_id).or_default().push(view_id);
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn window_closed(&self, window_id: WindowId) {
+        let mut ref_counts = self.ref_counts.write();
+        let detector = &mut ref_counts.leak_detector;
+        if let Some(views) = detector
+            .window_views
+            .as_mut()
+            .and_then(|window_views| window_views.remove(&window_id))
+        {
+            detector.closed_window_views.extend(views);
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn assert_closed_window_views_released(&self) {
+        self.ref_counts
+            .write()
+            .leak_detector
+            .assert_closed_window_views_released();
+    }
 }
 
 fn double_lease_panic<T>(operation: &str) -> ! {
@@ -654,7 +727,20 @@ pub(crate) struct HandleId {
 #[cfg(any(test, feature = "test-support"))]
 pub(crate) struct LeakDetector {
     next_handle_id: u64,
-    entity_handles: HashMap<EntityId, HashMap<HandleId, Option<backtrace::Backtrace>>>,
+    entity_handles: HashMap<EntityId, HashMap<HandleId, HandleInfo>>,
+    entity_types: HashMap<EntityId, &'static str>,
+    /// The entities being created or updated, innermost last. New handles are attributed to the
+    /// innermost one, as that's usually the entity that ends up storing them.
+    holders: Vec<EntityId>,
+    /// The views created in each open window, if window leak detection is enabled.
+    window_views: Option<HashMap<WindowId, Vec<EntityId>>>,
+    closed_window_views: Vec<EntityId>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+struct HandleInfo {
+    backtrace: Option<backtrace::Backtrace>,
+    holder: Option<EntityId>,
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -663,10 +749,14 @@ impl LeakDetector {
     pub fn handle_created(&mut self, entity_id: EntityId) -> HandleId {
         let id = util::post_inc(&mut self.next_handle_id);
         let handle_id = HandleId { id };
+        let holder = self.holders.last().copied();
         let handles = self.entity_handles.entry(entity_id).or_default();
         handles.insert(
             handle_id,
-            LEAK_BACKTRACE.then(backtrace::Backtrace::new_unresolved),
+            HandleInfo {
+                backtrace: LEAK_BACKTRACE.then(backtrace::Backtrace::new_unresolved),
+                holder,
+            },
         );
         handle_id
     }
@@ -679,8 +769,8 @@ impl LeakDetector {
     pub fn assert_released(&mut self, entity_id: EntityId) {
         let handles = self.entity_handles.entry(entity_id).or_default();
         if !handles.is_empty() {
-            for backtrace in handles.values_mut() {
-                if let Some(mut backtrace) = backtrace.take() {
+            for handle in handles.values_mut() {
+                if let Some(mut backtrace) = handle.backtrace.take() {
                     backtrace.resolve();
                     eprintln!("Leaked handle: {:#?}", backtrace);
                 } else {
@@ -690,6 +780,143 @@ impl LeakDetector {
             panic!();
         }
     }
+
+    fn is_alive(&self, entity_id: EntityId) -> bool {
+        self.entity_handles
+            .get(&entity_id)
+            .map_or(false, |handles| !handles.is_empty())
+    }
+
+    fn describe(&self, entity_id: EntityId) -> String {
+        let type_name = self
+            .entity_types
+            .get(&entity_id)
+            .copied()
+            .unwrap_or("<released entity>");
+        format!("{type_name} ({entity_id})")
+    }
+
+    /// The entities holding a strong handle to the given one, sorted and deduplicated. `None`
+    /// stands for handles created outside of any entity, e.g. by the test itself or by a task.
+    fn holders_of(&self, entity_id: EntityId) -> Vec<Option<EntityId>> {
+        let mut holders = self
+            .entity_handles
+            .get(&entity_id)
+            .into_iter()
+            .flat_map(|handles| handles.values().map(|handle| handle.holder))
+            .collect::<Vec<_>>();
+        holders.sort();
+        holders.dedup();
+        holders
+    }
+
+    /// Panics with a report of the views that are still alive after their window was closed,
+    /// listing what holds on to each of them and the retain cycles they're part of.
+    pub fn assert_closed_window_views_released(&mut self) {
+        let closed_window_views = mem::take(&mut self.closed_window_views);
+        let leaked_views = closed_window_views
+            .into_iter()
+            .filter(|view_id| self.is_alive(*view_id))
+            .collect::<Vec<_>>();
+        if leaked_views.is_empty() {
+            return;
+        }
+
+        let mut report = format!(
+            "{} views are still alive after their window was closed:\n",
+            leaked_views.len()
+        );
+        for view_id in &leaked_views {
+            report.push_str(&format!("  {} is held by:\n", self.describe(*view_id)));
+            for holder in self.holders_of(*view_id) {
+                match holder {
+                    Some(holder) => report.push_str(&format!("    {}\n", self.describe(holder))),
+                    None => report.push_str("    a handle created outside of any entity\n"),
+                }
+            }
+        }
+
+        for view_id in &leaked_views {
+            let description = self.describe(*view_id);
+            for handle in self
+                .entity_handles
+                .get_mut(view_id)
+                .into_iter()
+                .flat_map(|handles| handles.values_mut())
+            {
+                if let Some(mut backtrace) = handle.backtrace.take() {
+                    backtrace.resolve();
+                    eprintln!("Leaked handle to {description}: {:#?}", backtrace);
+                }
+            }
+        }
+
+        let cycles = self.retain_cycles(&leaked_views);
+        if !cycles.is_empty() {
+            report.push_str("retain cycles:\n");
+            for cycle in cycles {
+                let path = cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|entity_id| self.describe(*entity_id))
+                    .collect::<Vec<_>>()
+                    .join(" is held by ");
+                report.push_str(&format!("  {path}\n"));
+            }
+            report.push_str("break them by holding a weak handle on one side of each cycle\n");
+        }
+        if !*LEAK_BACKTRACE {
+            report
+                .push_str("export LEAK_BACKTRACE to print where the leaked handles were created\n");
+        }
+        panic!("{report}");
+    }
+
+    /// Finds the cycles of entities holding each other that the given entities are part of.
+    /// Each cycle is rotated to start at its lowest entity id, so that it's only reported once.
+    fn retain_cycles(&self, entity_ids: &[EntityId]) -> Vec<Vec<EntityId>> {
+        fn visit(
+            detector: &LeakDetector,
+            entity_id: EntityId,
+            path: &mut Vec<EntityId>,
+            visited: &mut Vec<EntityId>,
+            cycles: &mut Vec<Vec<EntityId>>,
+        ) {
+            if let Some(start) = path.iter().position(|id| *id == entity_id) {
+                let mut cycle = path[start..].to_vec();
+                let min_ix = (0..cycle.len()).min_by_key(|ix| cycle[*ix]).unwrap();
+                cycle.rotate_left(min_ix);
+                if !cycles.contains(&cycle) {
+                    cycles.push(cycle);
+                }
+                return;
+            }
+            if visited.contains(&entity_id) {
+                return;
+            }
+            visited.push(entity_id);
+
+            path.push(entity_id);
+            for holder in detector.holders_of(entity_id).into_iter().flatten() {
+                if detector.is_alive(holder) {
+                    visit(detector, holder, path, visited, cycles);
+                }
+            }
+            path.pop();
+        }
+
+        let mut cycles = Vec::new();
+        for entity_id in entity_ids {
+            visit(
+                self,
+                *entity_id,
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut cycles,
+            );
+        }
+        cycles
+    }
 }
 
 #[cfg(test)]
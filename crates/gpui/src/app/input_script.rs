@@ -0,0 +1,289 @@
+use crate::{
+    Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point,
+    ScrollDelta, ScrollWheelEvent, TouchPhase, VisualTestContext,
+};
+use std::time::Duration;
+
+/// A sequence of simulated user input, for testing interactions end-to-end.
+/// Scripts are built up step by step and then played back in a window with
+/// [`VisualTestContext::simulate_input_script`]:
+///
+/// ```ignore
+/// cx.simulate_input_script(
+///     InputScript::new()
+///         .move_mouse_to(point(px(10.), px(10.)), 1)
+///         .mouse_down(MouseButton::Left)
+///         .move_mouse_to(point(px(100.), px(10.)), 10)
+///         .mouse_up(MouseButton::Left)
+///         .keystrokes("cmd-k cmd-s")
+///         .type_text("hello")
+///         .frames(3),
+/// );
+/// ```
+///
+/// Playback keeps track of the mouse position, the pressed button and the modifiers, so that
+/// every event carries the state a real platform would report. Time only passes when a script
+/// waits or steps frames, which makes scripts deterministic.
+#[derive(Clone, Debug, Default)]
+pub struct InputScript {
+    steps: Vec<InputStep>,
+}
+
+#[derive(Clone, Debug)]
+enum InputStep {
+    Keystrokes(String),
+    Type(String),
+    MoveMouse { to: Point<Pixels>, steps: usize },
+    MouseDown(MouseButton),
+    MouseUp(MouseButton),
+    Scroll { delta: Point<Pixels>, steps: usize },
+    Modifiers(Modifiers),
+    Wait(Duration),
+    Frames(usize),
+}
+
+impl InputScript {
+    /// Creates an empty script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Simulates a space-separated sequence of keystrokes, such as the chord `"cmd-k cmd-s"`.
+    pub fn keystrokes(mut self, keystrokes: impl Into<String>) -> Self {
+        self.steps.push(InputStep::Keystrokes(keystrokes.into()));
+        self
+    }
+
+    /// Simulates typing the given text, one character at a time.
+    pub fn type_text(mut self, text: impl Into<String>) -> Self {
+        self.steps.push(InputStep::Type(text.into()));
+        self
+    }
+
+    /// Moves the mouse in a straight line to the given position, in `steps` evenly spaced
+    /// mouse move events.
+    pub fn move_mouse_to(mut self, position: Point<Pixels>, steps: usize) -> Self {
+        self.steps.push(InputStep::MoveMouse {
+            to: position,
+            steps: steps.max(1),
+        });
+        self
+    }
+
+    /// Moves the mouse through each of the given positions in turn, with `steps_per_segment`
+    /// mouse move events between consecutive positions.
+    pub fn move_mouse_along(
+        mut self,
+        path: impl IntoIterator<Item = Point<Pixels>>,
+        steps_per_segment: usize,
+    ) -> Self {
+        for position in path {
+            self = self.move_mouse_to(position, steps_per_segment);
+        }
+        self
+    }
+
+    /// Presses the given mouse button at the current mouse position.
+    pub fn mouse_down(mut self, button: MouseButton) -> Self {
+        self.steps.push(InputStep::MouseDown(button));
+        self
+    }
+
+    /// Releases the given mouse button at the current mouse position.
+    pub fn mouse_up(mut self, button: MouseButton) -> Self {
+        self.steps.push(InputStep::MouseUp(button));
+        self
+    }
+
+    /// Presses and releases the given mouse button at the current mouse position.
+    pub fn click(self, button: MouseButton) -> Self {
+        self.mouse_down(button).mouse_up(button)
+    }
+
+    /// Scrolls by `delta` pixels at the current mouse position, as a trackpad gesture of `steps`
+    /// events: the first has the `Started` phase, and a final event with no delta ends it.
+    pub fn scroll(mut self, delta: Point<Pixels>, steps: usize) -> Self {
+        self.steps.push(InputStep::Scroll {
+            delta,
+            steps: steps.max(1),
+        });
+        self
+    }
+
+    /// Changes the modifiers held down for subsequent mouse events.
+    pub fn modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.steps.push(InputStep::Modifiers(modifiers));
+        self
+    }
+
+    /// Advances the clock by the given duration, without rendering any frames.
+    pub fn wait(mut self, duration: Duration) -> Self {
+        self.steps.push(InputStep::Wait(duration));
+        self
+    }
+
+    /// Renders the given number of frames, advancing the clock by one frame interval before each.
+    pub fn frames(mut self, count: usize) -> Self {
+        self.steps.push(InputStep::Frames(count));
+        self
+    }
+}
+
+impl VisualTestContext {
+    /// Plays back the given script in this window, running until parked after every event.
+    pub fn simulate_input_script(&mut self, script: InputScript) {
+        let (mut position, mut modifiers) = self.update(|cx| (cx.mouse_position(), cx.modifiers()));
+        let mut pressed_button = None;
+
+        for step in script.steps {
+            match step {
+                InputStep::Keystrokes(keystrokes) => self.simulate_keystrokes(&keystrokes),
+                InputStep::Type(text) => self.simulate_input(&text),
+                InputStep::MoveMouse { to, steps } => {
+                    let from = position;
+                    for step in 1..=steps {
+                        position = from + (to - from) * (step as f32 / steps as f32);
+                        self.simulate_event(MouseMoveEvent {
+                            position,
+                            pressed_button,
+                            modifiers,
+                        });
+                    }
+                    position = to;
+                }
+                InputStep::MouseDown(button) => {
+                    pressed_button = Some(button);
+                    self.simulate_event(MouseDownEvent {
+                        button,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                        first_mouse: false,
+                    });
+                }
+                InputStep::MouseUp(button) => {
+                    pressed_button = None;
+                    self.simulate_event(MouseUpEvent {
+                        button,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                    });
+                }
+                InputStep::Scroll { delta, steps } => {
+                    let step_delta = delta * (1. / steps as f32);
+                    for step in 0..steps {
+                        self.simulate_event(ScrollWheelEvent {
+                            position,
+                            delta: ScrollDelta::Pixels(step_delta),
+                            modifiers,
+                            touch_phase: if step == 0 {
+                                TouchPhase::Started
+                            } else {
+                                TouchPhase::Moved
+                            },
+                        });
+                    }
+                    self.simulate_event(ScrollWheelEvent {
+                        position,
+                        delta: ScrollDelta::Pixels(Point::default()),
+                        modifiers,
+                        touch_phase: TouchPhase::Ended,
+                    });
+                }
+                InputStep::Modifiers(new_modifiers) => {
+                    modifiers = new_modifiers;
+                    self.simulate_modifiers_change(modifiers);
+                }
+                InputStep::Wait(duration) => self.background_executor.advance_clock(duration),
+                InputStep::Frames(count) => self.advance_frames(count),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, app::test_context::TEST_FRAME_INTERVAL, div, point, px, InteractiveElement,
+        IntoElement, Render, Styled, TestAppContext, ViewContext,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Default)]
+    struct Events {
+        moves: Vec<(Point<Pixels>, Option<MouseButton>)>,
+        scroll_phases: Vec<String>,
+    }
+
+    struct TestView {
+        events: Rc<RefCell<Events>>,
+        renders: usize,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            self.renders += 1;
+            cx.request_animation_frame();
+
+            let events = self.events.clone();
+            div()
+                .size_full()
+                .on_mouse_move({
+                    let events = events.clone();
+                    move |event, _| {
+                        events
+                            .borrow_mut()
+                            .moves
+                            .push((event.position, event.pressed_button))
+                    }
+                })
+                .on_scroll_wheel(move |event, _| {
+                    events
+                        .borrow_mut()
+                        .scroll_phases
+                        .push(format!("{:?}", event.touch_phase))
+                })
+        }
+    }
+
+    #[gpui::test]
+    fn test_input_script(cx: &mut TestAppContext) {
+        let events = Rc::new(RefCell::new(Events::default()));
+        let (view, cx) = cx.add_window_view(|_| TestView {
+            events: events.clone(),
+            renders: 0,
+        });
+
+        cx.simulate_input_script(
+            InputScript::new()
+                .move_mouse_to(point(px(10.), px(0.)), 2)
+                .mouse_down(MouseButton::Left)
+                .move_mouse_to(point(px(10.), px(10.)), 1)
+                .mouse_up(MouseButton::Left)
+                .scroll(point(px(0.), px(-30.)), 3),
+        );
+
+        let events = events.borrow();
+        assert_eq!(
+            events.moves,
+            [
+                (point(px(5.), px(0.)), None),
+                (point(px(10.), px(0.)), None),
+                (point(px(10.), px(10.)), Some(MouseButton::Left)),
+            ]
+        );
+        assert_eq!(events.scroll_phases, ["Started", "Moved", "Moved", "Ended"]);
+
+        // Each frame runs the view's animation frame request, rendering it once more.
+        let start = cx.background_executor.now();
+        let renders = view.read_with(cx, |view, _| view.renders);
+        cx.advance_frames(3);
+        assert_eq!(view.read_with(cx, |view, _| view.renders), renders + 3);
+        assert_eq!(
+            cx.background_executor.now() - start,
+            TEST_FRAME_INTERVAL * 3
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    AnyView, AnyWindowHandle, AppContext, AsyncAppContext, Context, Effect, Entity, EntityId,
-    EventEmitter, Model, Reservation, Subscription, Task, View, WeakModel, WindowContext,
-    WindowHandle,
+    task_tracking_enabled, AnyView, AnyWindowHandle, AppContext, AsyncAppContext, Context, Effect,
+    Entity, EntityId, EventEmitter, Model, PowerState, Reservation, Subscription, Task, TaskOwner,
+    View, WeakModel, WindowContext, WindowHandle,
 };
 use anyhow::Result;
 use derive_more::{Deref, DerefMut};
@@ -151,6 +151,22 @@ impl<'a, T: 'static> ModelContext<'a, T> {
         subscription
     }
 
+    /// Arrange for the given function to be invoked whenever the [`PowerState`] changes.
+    pub fn observe_power_state(
+        &mut self,
+        mut f: impl FnMut(&mut T, PowerState, &mut ModelContext<'_, T>) + 'static,
+    ) -> Subscription
+    where
+        T: 'static,
+    {
+        let handle = self.weak_model();
+        self.app.observe_power_state(move |power_state, cx| {
+            handle
+                .update(cx, |model, cx| f(model, power_state, cx))
+                .ok();
+        })
+    }
+
     /// Arrange for the given function to be invoked whenever the application is quit.
     /// The future returned from this callback will be polled for up to [crate::SHUTDOWN_TIMEOUT] until the app fully quits.
     pub fn on_app_quit<Fut>(
@@ -194,6 +210,7 @@ impl<'a, T: 'static> ModelContext<'a, T> {
     /// Spawn the future returned by the given function.
     /// The function is provided a weak handle to the model owned by this context and a context that can be held across await points.
     /// The returned task must be held or detached.
+    #[track_caller]
     pub fn spawn<Fut, R>(&self, f: impl FnOnce(WeakModel<T>, AsyncAppContext) -> Fut) -> Task<R>
     where
         T: 'static,
@@ -201,7 +218,13 @@ impl<'a, T: 'static> ModelContext<'a, T> {
         R: 'static,
     {
         let this = self.weak_model();
-        self.app.spawn(|cx| f(this, cx))
+        let owner = task_tracking_enabled().then(|| TaskOwner {
+            entity: this.clone().into(),
+            type_name: std::any::type_name::<T>(),
+        });
+        self.app
+            .foreground_executor
+            .spawn_tracked(f(this, self.app.to_async()), None, owner)
     }
 }
 
@@ -1,11 +1,11 @@
 use crate::{
-    Action, AnyView, AnyWindowHandle, AppCell, AppContext, AsyncAppContext, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context, DrawPhase, Drawable,
-    Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Model,
-    ModelContext, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Platform, Point, Render, Result, Size, Task, TestDispatcher,
-    TestPlatform, TestWindow, TextSystem, View, ViewContext, VisualContext, WindowBounds,
-    WindowContext, WindowHandle, WindowOptions,
+    AccessibilityPreferences, Action, AnyView, AnyWindowHandle, AppCell, AppContext,
+    AsyncAppContext, AvailableSpace, BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem,
+    Context, DrawPhase, Drawable, Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global,
+    InputEvent, Keystroke, Model, ModelContext, Modifiers, ModifiersChangedEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Platform, Point, PowerState, Render,
+    Result, Size, Task, TestDispatcher, TestPlatform, TestWindow, TextSystem, View, ViewContext,
+    VisualContext, WindowBounds, WindowContext, WindowHandle, WindowOptions,
 };
 use anyhow::{anyhow, bail};
 use futures::{channel::oneshot, Stream, StreamExt};
@@ -146,6 +146,25 @@ impl TestAppContext {
         self.on_quit.borrow_mut().push(Box::new(f));
     }
 
+    /// Fails the test if any view created from now on is still alive after its window is
+    /// closed, with a report of the entities holding on to the leaked views and the retain
+    /// cycles between them. Windows still open when the test ends are not checked.
+    pub fn detect_window_leaks(&mut self) {
+        self.app.borrow().entities.detect_window_leaks();
+        let app = self.app.clone();
+        self.on_quit(move || app.borrow().entities.assert_closed_window_views_released());
+    }
+
+    /// Panics if any view of a window closed since the last check is still alive, once all
+    /// pending work has run. Requires [`Self::detect_window_leaks`] to have been called.
+    pub fn assert_closed_window_views_released(&mut self) {
+        self.run_until_parked();
+        self.app
+            .borrow()
+            .entities
+            .assert_closed_window_views_released();
+    }
+
     /// Schedules all windows to be redrawn on the next effect cycle.
     pub fn refresh(&mut self) -> Result<()> {
         let mut app = self.app.borrow_mut();
@@ -282,17 +301,46 @@ impl TestAppContext {
         self.test_platform.opened_url.borrow().clone()
     }
 
+    /// The names and volumes of the system sounds played with cx.play_system_sound() during
+    /// this test, in the order they were played.
+    pub fn played_system_sounds(&self) -> Vec<(String, f32)> {
+        self.test_platform.played_system_sounds.borrow().clone()
+    }
+
+    /// Simulates the user changing the accessibility preferences of the operating system.
+    pub fn simulate_accessibility_preferences(&mut self, preferences: AccessibilityPreferences) {
+        *self.test_platform.accessibility_preferences.borrow_mut() = preferences;
+        self.update(|cx| cx.refresh());
+    }
+
+    /// Simulates the machine switching power sources or entering a power saving mode.
+    pub fn simulate_power_state(&mut self, power_state: PowerState) {
+        *self.test_platform.power_state.borrow_mut() = power_state;
+        self.update(|cx| cx.refresh_power_state());
+    }
+
     /// Simulates the user resizing the window to the new size.
     pub fn simulate_window_resize(&self, window_handle: AnyWindowHandle, size: Size<Pixels>) {
         self.test_window(window_handle).simulate_resize(size);
     }
 
+    /// Simulates the window moving to a display with a different scale factor.
+    pub fn simulate_window_scale_factor_change(
+        &self,
+        window_handle: AnyWindowHandle,
+        scale_factor: f32,
+    ) {
+        self.test_window(window_handle)
+            .simulate_scale_factor_change(scale_factor);
+    }
+
     /// Returns all windows open in the test.
     pub fn windows(&self) -> Vec<AnyWindowHandle> {
         self.app.borrow().windows().clone()
     }
 
     /// Run the given task on the main thread.
+    #[track_caller]
     pub fn spawn<Fut, R>(&self, f: impl FnOnce(AsyncAppContext) -> Fut) -> Task<R>
     where
         Fut: Future<Output = R> + 'static,
@@ -628,6 +676,10 @@ impl<V> View<V> {
 }
 
 use derive_more::{Deref, DerefMut};
+
+/// The time between the frames rendered by [VisualTestContext::advance_frame], as on a 60Hz display.
+pub(crate) const TEST_FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
 #[derive(Deref, DerefMut, Clone)]
 /// A VisualTestContext is the test-equivalent of a `WindowContext`. It allows you to
 /// run window-specific test code.
@@ -762,6 +814,43 @@ impl VisualTestContext {
         self.simulate_window_resize(self.window, size)
     }
 
+    /// Simulates the window moving to a display with a different scale factor.
+    pub fn simulate_scale_factor_change(&mut self, scale_factor: f32) {
+        self.simulate_window_scale_factor_change(self.window, scale_factor);
+        self.background_executor.run_until_parked();
+    }
+
+    /// Simulates the window being hidden from the user, or becoming visible again.
+    pub fn simulate_occlusion_change(&mut self, occluded: bool) {
+        self.test_window(self.window)
+            .simulate_occlusion_change(occluded);
+        self.background_executor.run_until_parked();
+    }
+
+    /// Advances the clock by one frame interval and renders the window as the platform would on
+    /// its next display refresh, running the callbacks registered with `on_next_frame`.
+    /// This is what drives animations and `request_animation_frame` in tests.
+    pub fn advance_frame(&mut self) {
+        self.advance_frames(1)
+    }
+
+    /// Renders the given number of frames, see [VisualTestContext::advance_frame].
+    pub fn advance_frames(&mut self, count: usize) {
+        let window = self.test_window(self.window);
+        for _ in 0..count {
+            self.background_executor.advance_clock(TEST_FRAME_INTERVAL);
+            window.simulate_frame();
+            self.background_executor.run_until_parked();
+        }
+    }
+
+    /// Renders frames until at least the given duration has passed, e.g. to run an
+    /// animation to completion.
+    pub fn advance_frames_for(&mut self, duration: Duration) {
+        let count = duration.as_nanos().div_ceil(TEST_FRAME_INTERVAL.as_nanos());
+        self.advance_frames(count as usize)
+    }
+
     /// debug_bounds returns the bounds of the element with the given selector.
     pub fn debug_bounds(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
         self.update(|cx| cx.window.rendered_frame.debug_bounds.get(selector).copied())
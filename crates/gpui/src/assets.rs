@@ -1,11 +1,13 @@
 use crate::{size, DevicePixels, Result, SharedString, Size};
+use anyhow::Context as _;
 use smallvec::SmallVec;
 
-use image::{Delay, Frame};
+use image::{imageops, Delay, Frame};
 use std::{
     borrow::Cow,
-    fmt,
+    fmt, fs,
     hash::Hash,
+    path::{Path, PathBuf},
     sync::atomic::{AtomicUsize, Ordering::SeqCst},
 };
 
@@ -28,6 +30,72 @@ impl AssetSource for () {
     }
 }
 
+/// An asset source reading files from a directory on disk, for apps that ship their assets
+/// alongside the executable instead of embedding them.
+pub struct DirectoryAssets {
+    root: PathBuf,
+}
+
+impl DirectoryAssets {
+    /// Read assets from the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Read assets from the directory with the given name next to the executable, or in the
+    /// `Resources` directory of the application bundle on macOS.
+    pub fn beside_executable(name: &str) -> Result<Self> {
+        let executable = std::env::current_exe().context("locating the executable")?;
+        let mut directory = executable
+            .parent()
+            .context("the executable has no parent directory")?
+            .to_path_buf();
+        if cfg!(target_os = "macos") && directory.ends_with("Contents/MacOS") {
+            directory.pop();
+            directory.push("Resources");
+        }
+        Ok(Self::new(directory.join(name)))
+    }
+
+    fn list_recursive(&self, directory: &Path, paths: &mut Vec<SharedString>) -> Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.list_recursive(&path, paths)?;
+            } else if let Ok(relative_path) = path.strip_prefix(&self.root) {
+                let components = relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                paths.push(components.join("/").into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AssetSource for DirectoryAssets {
+    fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+        match fs::read(self.root.join(path)) {
+            Ok(bytes) => Ok(Some(Cow::Owned(bytes))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("loading asset at {path:?}")),
+        }
+    }
+
+    /// Lists all files within the given directory and its subdirectories, with paths relative
+    /// to the root and separated by `/` on all platforms.
+    fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+        let mut paths = Vec::new();
+        let directory = self.root.join(path);
+        if directory.is_dir() {
+            self.list_recursive(&directory, &mut paths)?;
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
+
 /// A unique identifier for the image cache
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ImageId(pub usize);
@@ -36,8 +104,13 @@ pub struct ImageId(pub usize);
 pub(crate) struct RenderImageParams {
     pub(crate) image_id: ImageId,
     pub(crate) frame_index: usize,
+    /// How many times the frame is halved in size before it's drawn. See [`RenderImage::mip_level`].
+    pub(crate) mip_level: u32,
 }
 
+/// The smallest mip level images are downscaled to, a 256th of their size.
+const MAX_MIP_LEVEL: u32 = 8;
+
 /// A cached and processed image, in BGRA format
 pub struct RenderImage {
     /// The ID associated with this image
@@ -86,6 +159,51 @@ impl RenderImage {
     pub fn frame_count(&self) -> usize {
         self.data.len()
     }
+
+    /// The number of times to halve the size of a frame before drawing it at the given size.
+    /// Sprites are sampled bilinearly, which aliases when an image is drawn at less than half
+    /// its size, so such images are downscaled ahead of time instead. Halving the size at each
+    /// level keeps the number of downscaled copies small while an image is resized.
+    pub(crate) fn mip_level(&self, frame_index: usize, target_size: Size<DevicePixels>) -> u32 {
+        if target_size.width.0 <= 0 || target_size.height.0 <= 0 {
+            return 0;
+        }
+        let size = self.size(frame_index);
+        let ratio = (size.width.0 as f32 / target_size.width.0 as f32)
+            .min(size.height.0 as f32 / target_size.height.0 as f32);
+        if ratio < 2. {
+            0
+        } else {
+            (ratio.log2().floor() as u32).min(MAX_MIP_LEVEL)
+        }
+    }
+
+    /// Get the size of a frame downscaled to the given mip level, in pixels.
+    pub(crate) fn mip_size(&self, frame_index: usize, mip_level: u32) -> Size<DevicePixels> {
+        let full_size = self.size(frame_index);
+        let divisor = 1 << mip_level;
+        size(
+            ((full_size.width.0 + divisor - 1) / divisor).max(1).into(),
+            ((full_size.height.0 + divisor - 1) / divisor).max(1).into(),
+        )
+    }
+
+    /// Get the pixels of a frame downscaled to the given mip level, filtered with a Lanczos
+    /// kernel so details don't alias.
+    pub(crate) fn mip_bytes(&self, frame_index: usize, mip_level: u32) -> Option<Cow<[u8]>> {
+        if mip_level == 0 {
+            return self.as_bytes(frame_index).map(Cow::Borrowed);
+        }
+        let frame = self.data.get(frame_index)?;
+        let size = self.mip_size(frame_index, mip_level);
+        let downscaled = imageops::resize(
+            frame.buffer(),
+            size.width.0 as u32,
+            size.height.0 as u32,
+            imageops::FilterType::Lanczos3,
+        );
+        Some(Cow::Owned(downscaled.into_raw()))
+    }
 }
 
 impl fmt::Debug for RenderImage {
@@ -1,4 +1,4 @@
-use crate::{Bounds, Half};
+use crate::{Bounds, Half, Point};
 use std::{
     cmp,
     fmt::Debug,
@@ -25,12 +25,77 @@ where
         self.stack.clear();
     }
 
+    /// Inserts the bounds, returning an order higher than that of all the bounds it intersects.
     pub fn insert(&mut self, new_bounds: Bounds<U>) -> u32 {
+        self.insert_leaf(new_bounds, None)
+    }
+
+    /// Inserts the bounds with the given order, for trees that are only queried with
+    /// [`BoundsTree::max_order_containing`].
+    pub fn insert_with_order(&mut self, new_bounds: Bounds<U>, order: u32) {
+        self.insert_leaf(new_bounds, Some(order));
+    }
+
+    /// Returns the highest order of the bounds that fully contain the given ones, if any.
+    pub fn max_order_containing(&self, bounds: &Bounds<U>) -> Option<u32> {
+        let root = self.root?;
+        let max_order = self.find_max_order_containing(root, bounds, 0);
+        (max_order > 0).then_some(max_order)
+    }
+
+    /// Returns the orders of the bounds that contain the given point, in no particular order.
+    pub fn orders_containing_point(&self, point: &Point<U>) -> Vec<u32> {
+        let mut orders = Vec::new();
+        let mut stack = self.root.into_iter().collect::<Vec<_>>();
+        while let Some(index) = stack.pop() {
+            match &self.nodes[index] {
+                Node::Leaf { bounds, order } => {
+                    if bounds.contains(point) {
+                        orders.push(*order);
+                    }
+                }
+                Node::Internal {
+                    left,
+                    right,
+                    bounds,
+                    ..
+                } => {
+                    // The bounds of internal nodes contain those of their descendants.
+                    if bounds.contains(point) {
+                        stack.push(*left);
+                        stack.push(*right);
+                    }
+                }
+            }
+        }
+        orders
+    }
+
+    /// The number of nodes on the longest path from the root to a leaf.
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = self
+            .root
+            .map(|root| (root, 1))
+            .into_iter()
+            .collect::<Vec<_>>();
+        while let Some((index, depth)) = stack.pop() {
+            max_depth = cmp::max(max_depth, depth);
+            if let Node::Internal { left, right, .. } = &self.nodes[index] {
+                stack.push((*left, depth + 1));
+                stack.push((*right, depth + 1));
+            }
+        }
+        max_depth
+    }
+
+    fn insert_leaf(&mut self, new_bounds: Bounds<U>, order: Option<u32>) -> u32 {
         // If the tree is empty, make the root the new leaf.
         if self.root.is_none() {
-            let new_node = self.push_leaf(new_bounds, 1);
+            let ordering = order.unwrap_or(1);
+            let new_node = self.push_leaf(new_bounds, ordering);
             self.root = Some(new_node);
-            return 1;
+            return ordering;
         }
 
         // Search for the best place to add the new leaf based on heuristics.
@@ -56,15 +121,16 @@ where
             let right_cost = new_bounds
                 .union(self.nodes[right].bounds())
                 .half_perimeter();
-            if left_cost < right_cost {
-                max_intersecting_ordering =
-                    self.find_max_ordering(right, &new_bounds, max_intersecting_ordering);
-                index = left;
+            let (best, other) = if left_cost < right_cost {
+                (left, right)
             } else {
+                (right, left)
+            };
+            if order.is_none() {
                 max_intersecting_ordering =
-                    self.find_max_ordering(left, &new_bounds, max_intersecting_ordering);
-                index = right;
+                    self.find_max_ordering(other, &new_bounds, max_intersecting_ordering);
             }
+            index = best;
         }
 
         // We've found a leaf ('index' now refers to a leaf node).
@@ -84,7 +150,7 @@ where
             max_intersecting_ordering = cmp::max(max_intersecting_ordering, *sibling_ordering);
         }
 
-        let ordering = max_intersecting_ordering + 1;
+        let ordering = order.unwrap_or(max_intersecting_ordering + 1);
         let new_node = self.push_leaf(new_bounds, ordering);
         let new_parent = self.push_internal(sibling, new_node);
 
@@ -152,6 +218,37 @@ where
         max_ordering
     }
 
+    fn find_max_order_containing(
+        &self,
+        index: usize,
+        bounds: &Bounds<U>,
+        mut max_order: u32,
+    ) -> u32 {
+        match &self.nodes[index] {
+            Node::Leaf {
+                bounds: node_bounds,
+                order,
+            } => {
+                if contains(node_bounds, bounds) {
+                    max_order = cmp::max(*order, max_order);
+                }
+            }
+            Node::Internal {
+                left,
+                right,
+                bounds: node_bounds,
+                max_order: node_max_order,
+            } => {
+                // The bounds of internal nodes contain those of their descendants.
+                if max_order < *node_max_order && contains(node_bounds, bounds) {
+                    max_order = self.find_max_order_containing(*left, bounds, max_order);
+                    max_order = self.find_max_order_containing(*right, bounds, max_order);
+                }
+            }
+        }
+        max_order
+    }
+
     fn push_leaf(&mut self, bounds: Bounds<U>, order: u32) -> usize {
         self.nodes.push(Node::Leaf { bounds, order });
         self.nodes.len() - 1
@@ -172,6 +269,18 @@ where
     }
 }
 
+fn contains<U>(outer: &Bounds<U>, inner: &Bounds<U>) -> bool
+where
+    U: Clone + Debug + PartialOrd + Add<U, Output = U> + Default,
+{
+    let outer_lower_right = outer.lower_right();
+    let inner_lower_right = inner.lower_right();
+    outer.origin.x <= inner.origin.x
+        && outer.origin.y <= inner.origin.y
+        && inner_lower_right.x <= outer_lower_right.x
+        && inner_lower_right.y <= outer_lower_right.y
+}
+
 impl<U> Default for BoundsTree<U>
 where
     U: Default + Clone + Debug,
@@ -287,4 +396,54 @@ mod tests {
         assert_eq!(tree.insert(bounds5), 1); // bounds5 does not overlap with any other bounds
         assert_eq!(tree.insert(bounds6), 2); // bounds6 overlaps with bounds4, so it should have a different order
     }
+
+    #[test]
+    fn test_max_order_containing() {
+        let mut tree = BoundsTree::<f32>::default();
+        let bounds = |x: f32, y: f32, width: f32, height: f32| Bounds {
+            origin: Point { x, y },
+            size: Size { width, height },
+        };
+        let max_order = |tree: &BoundsTree<f32>, x, y, width, height| {
+            tree.max_order_containing(&bounds(x, y, width, height))
+        };
+        assert_eq!(max_order(&tree, 0., 0., 1., 1.), None);
+
+        tree.insert_with_order(bounds(0., 0., 100., 100.), 2);
+        tree.insert_with_order(bounds(10., 10., 20., 20.), 5);
+        tree.insert_with_order(bounds(50., 50., 100., 100.), 7);
+
+        assert_eq!(max_order(&tree, 15., 15., 5., 5.), Some(5));
+        assert_eq!(max_order(&tree, 5., 5., 10., 10.), Some(2));
+        assert_eq!(max_order(&tree, 60., 60., 10., 10.), Some(7));
+        assert_eq!(max_order(&tree, 90., 90., 20., 20.), Some(7));
+        assert_eq!(max_order(&tree, 40., 40., 20., 20.), Some(2));
+        assert_eq!(max_order(&tree, -5., 0., 10., 10.), None);
+    }
+
+    #[test]
+    fn test_orders_containing_point() {
+        let mut tree = BoundsTree::<f32>::default();
+        let bounds = |x: f32, y: f32, width: f32, height: f32| Bounds {
+            origin: Point { x, y },
+            size: Size { width, height },
+        };
+        let orders = |tree: &BoundsTree<f32>, x, y| {
+            let mut orders = tree.orders_containing_point(&Point { x, y });
+            orders.sort();
+            orders
+        };
+        assert_eq!(orders(&tree, 0., 0.), Vec::<u32>::new());
+
+        assert_eq!(tree.insert(bounds(0., 0., 100., 100.)), 1);
+        assert_eq!(tree.insert(bounds(10., 10., 20., 20.)), 2);
+        assert_eq!(tree.insert(bounds(50., 50., 100., 100.)), 2);
+        assert_eq!(tree.insert(bounds(200., 0., 10., 10.)), 1);
+
+        assert_eq!(orders(&tree, 15., 15.), vec![1, 2]);
+        assert_eq!(orders(&tree, 40., 40.), vec![1]);
+        assert_eq!(orders(&tree, 120., 120.), vec![2]);
+        assert_eq!(orders(&tree, 205., 5.), vec![1]);
+        assert_eq!(orders(&tree, 300., 300.), Vec::<u32>::new());
+    }
 }
@@ -1,3 +1,4 @@
+use crate::{point, Point};
 use anyhow::{bail, Context};
 use serde::de::{self, Deserialize, Deserializer, Visitor};
 use std::{
@@ -57,14 +58,113 @@ impl Rgba {
             };
         }
     }
+
+    /// Converts this color, given in sRGB, to the given color space. Both spaces share the sRGB
+    /// transfer function, so the color is decoded to linear light, mapped to the primaries of
+    /// the other space, and encoded again.
+    pub fn to_color_space(self, color_space: ColorSpace) -> Rgba {
+        if !color_space.is_wide_gamut() {
+            return self;
+        }
+
+        let [r, g, b] = [self.r, self.g, self.b].map(srgb_to_linear);
+        let [r, g, b] = SRGB_TO_DISPLAY_P3.map(|[x, y, z]| x * r + y * g + z * b);
+        Rgba {
+            r: linear_to_srgb(r),
+            g: linear_to_srgb(g),
+            b: linear_to_srgb(b),
+            a: self.a,
+        }
+    }
+
+    /// Creates a color from components in the Display P3 color space. Colors are drawn in
+    /// extended sRGB, so the components of colors outside of sRGB's gamut fall below 0 or above 1.
+    /// They're shown as given in windows with a wide-gamut [`ColorSpace`], and clipped to the
+    /// nearest sRGB color elsewhere.
+    pub fn from_display_p3(r: f32, g: f32, b: f32, a: f32) -> Rgba {
+        let [r, g, b] = [r, g, b].map(srgb_to_linear);
+        let [r, g, b] = DISPLAY_P3_TO_SRGB.map(|[x, y, z]| x * r + y * g + z * b);
+        Rgba {
+            r: linear_to_srgb(r),
+            g: linear_to_srgb(g),
+            b: linear_to_srgb(b),
+            a,
+        }
+    }
+}
+
+/// The color space a window's surface is tagged with, which tells the system how to map the
+/// colors drawn to the window onto the display. Colors are always given in extended sRGB, see
+/// [`Rgba::from_display_p3`], and are converted to the window's color space when drawn.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// The sRGB color space. On displays with a wider gamut, the system maps colors to the
+    /// display, so they aren't oversaturated.
+    #[default]
+    Srgb,
+    /// The Display P3 color space, which covers the gamut of most wide-gamut displays.
+    DisplayP3,
+    /// The Display P3 color space, extended to colors brighter than white on displays that
+    /// support high dynamic range. Colors are still drawn in the standard range, but the
+    /// surface can hold brighter ones.
+    ExtendedDisplayP3,
+}
+
+impl ColorSpace {
+    /// Whether this color space has a wider gamut than sRGB.
+    pub fn is_wide_gamut(&self) -> bool {
+        matches!(self, ColorSpace::DisplayP3 | ColorSpace::ExtendedDisplayP3)
+    }
+
+    /// Whether this color space can hold colors brighter than white.
+    pub fn is_extended_range(&self) -> bool {
+        matches!(self, ColorSpace::ExtendedDisplayP3)
+    }
+}
+
+/// Maps linear sRGB to linear Display P3, one row per output channel. The shaders mirror this.
+const SRGB_TO_DISPLAY_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.],
+    [0.033_194_1, 0.966_805_8, 0.],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+/// Maps linear Display P3 to linear sRGB, the inverse of [`SRGB_TO_DISPLAY_P3`].
+const DISPLAY_P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_94, -0.224_940_1, 0.],
+    [-0.042_056_8, 1.042_056_9, 0.],
+    [-0.019_637_7, -0.078_636, 1.098_273_6],
+];
+
+/// Decodes an sRGB component to linear light. Components outside of the range 0 to 1, which
+/// extended sRGB colors have, are mirrored around 0.
+fn srgb_to_linear(component: f32) -> f32 {
+    let magnitude = component.abs();
+    let linear = if magnitude <= 0.04045 {
+        magnitude / 12.92
+    } else {
+        ((magnitude + 0.055) / 1.055).powf(2.4)
+    };
+    linear.copysign(component)
+}
+
+fn linear_to_srgb(component: f32) -> f32 {
+    let magnitude = component.abs();
+    let encoded = if magnitude <= 0.003_130_8 {
+        magnitude * 12.92
+    } else {
+        1.055 * magnitude.powf(1. / 2.4) - 0.055
+    };
+    encoded.copysign(component)
 }
 
 impl From<Rgba> for u32 {
     fn from(rgba: Rgba) -> Self {
-        let r = (rgba.r * 255.0) as u32;
-        let g = (rgba.g * 255.0) as u32;
-        let b = (rgba.b * 255.0) as u32;
-        let a = (rgba.a * 255.0) as u32;
+        // Extended sRGB colors are clipped, so their components don't spill into each other.
+        let r = (rgba.r.clamp(0., 1.) * 255.0) as u32;
+        let g = (rgba.g.clamp(0., 1.) * 255.0) as u32;
+        let b = (rgba.b.clamp(0., 1.) * 255.0) as u32;
+        let a = (rgba.a.clamp(0., 1.) * 255.0) as u32;
         (r << 24) | (g << 16) | (b << 8) | a
     }
 }
@@ -75,7 +175,10 @@ impl<'de> Visitor<'de> for RgbaVisitor {
     type Value = Rgba;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a string in the format #rrggbb or #rrggbbaa")
+        formatter.write_str(
+            "a string in the format #rrggbb, #rrggbbaa or color(display-p3 r g b), with an \
+             optional / a after the components",
+        )
     }
 
     fn visit_str<E: de::Error>(self, value: &str) -> Result<Rgba, E> {
@@ -128,9 +231,14 @@ impl TryFrom<&'_ str> for Rgba {
         const RRGGBB: usize = "rrggbb".len();
         const RRGGBBAA: usize = "rrggbbaa".len();
 
-        const EXPECTED_FORMATS: &str = "Expected #rgb, #rgba, #rrggbb, or #rrggbbaa";
+        const EXPECTED_FORMATS: &str =
+            "Expected #rgb, #rgba, #rrggbb, #rrggbbaa, or color(display-p3 r g b)";
         const INVALID_UNICODE: &str = "invalid unicode characters in color";
 
+        if value.trim().starts_with("color(") {
+            return parse_color_function(value.trim());
+        }
+
         let Some(("", hex)) = value.trim().split_once('#') else {
             bail!("invalid RGBA hex color: '{value}'. {EXPECTED_FORMATS}");
         };
@@ -226,6 +334,45 @@ impl TryFrom<&'_ str> for Rgba {
     }
 }
 
+/// Parses a color given with the CSS `color()` function in the `srgb` or `display-p3` color
+/// space, like `color(display-p3 1 0.5 0)` or `color(display-p3 100% 50% 0% / 0.8)`.
+fn parse_color_function(value: &str) -> anyhow::Result<Rgba> {
+    let arguments = value
+        .strip_prefix("color(")
+        .and_then(|arguments| arguments.strip_suffix(')'))
+        .with_context(|| format!("invalid color function: '{value}'"))?;
+    let (components, alpha) = match arguments.split_once('/') {
+        Some((components, alpha)) => (components, Some(alpha)),
+        None => (arguments, None),
+    };
+    let mut components = components.split_whitespace();
+    let color_space = components
+        .next()
+        .with_context(|| format!("missing color space in '{value}'"))?;
+    let components = components
+        .map(parse_color_component)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let [r, g, b] = components[..] else {
+        bail!("expected 3 components in '{value}'");
+    };
+    let a = alpha.map(parse_color_component).transpose()?.unwrap_or(1.);
+
+    match color_space {
+        "srgb" => Ok(Rgba { r, g, b, a }),
+        "display-p3" => Ok(Rgba::from_display_p3(r, g, b, a)),
+        _ => bail!("unsupported color space '{color_space}' in '{value}'"),
+    }
+}
+
+fn parse_color_component(component: &str) -> anyhow::Result<f32> {
+    let component = component.trim();
+    let value = match component.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>()? / 100.,
+        None => component.parse::<f32>()?,
+    };
+    Ok(value)
+}
+
 /// An HSLA color
 #[derive(Default, Copy, Clone, Debug)]
 #[repr(C)]
@@ -420,6 +567,11 @@ impl Hsla {
         self.a == 0.0
     }
 
+    /// Returns true if the HSLA color is fully opaque, false otherwise.
+    pub fn is_opaque(&self) -> bool {
+        self.a >= 1.0
+    }
+
     /// Blends `other` on top of `self` based on `other`'s alpha value. The resulting color is a combination of `self`'s and `other`'s colors.
     ///
     /// If `other`'s alpha value is 1.0 or greater, `other` color is fully opaque, thus `other` is returned as the output color.
@@ -446,6 +598,20 @@ impl Hsla {
         }
     }
 
+    /// Linearly interpolates from this color to `other` in RGB space. A `t` of 0.0 returns this
+    /// color, and a `t` of 1.0 returns `other`.
+    pub fn interpolate(self, other: Hsla, t: f32) -> Hsla {
+        let t = t.clamp(0., 1.);
+        let from = Rgba::from(self);
+        let to = Rgba::from(other);
+        Hsla::from(Rgba {
+            r: from.r + (to.r - from.r) * t,
+            g: from.g + (to.g - from.g) * t,
+            b: from.b + (to.b - from.b) * t,
+            a: from.a + (to.a - from.a) * t,
+        })
+    }
+
     /// Returns a new HSLA color with the same hue, and lightness, but with no saturation.
     pub fn grayscale(&self) -> Self {
         Hsla {
@@ -524,6 +690,196 @@ impl<'de> Deserialize<'de> for Hsla {
     }
 }
 
+/// The maximum number of color stops in a gradient.
+pub const MAX_GRADIENT_STOPS: usize = 4;
+
+/// A color at a position along a gradient.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+pub struct LinearColorStop {
+    /// The color at this stop.
+    pub color: Hsla,
+    /// The position of this stop along the gradient, in a range from 0 to 1.
+    pub percentage: f32,
+}
+
+impl Eq for LinearColorStop {}
+
+/// Construct a [`LinearColorStop`] from a color and its position along the gradient, in a range
+/// from 0 to 1.
+pub fn linear_color_stop(color: impl Into<Hsla>, percentage: f32) -> LinearColorStop {
+    LinearColorStop {
+        color: color.into(),
+        percentage: percentage.clamp(0., 1.),
+    }
+}
+
+impl<C: Into<Hsla>> From<(C, f32)> for LinearColorStop {
+    fn from((color, percentage): (C, f32)) -> Self {
+        linear_color_stop(color, percentage)
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub(crate) enum BackgroundTag {
+    #[default]
+    Solid = 0,
+    LinearGradient = 1,
+    RadialGradient = 2,
+    ConicGradient = 3,
+}
+
+/// The fill of a quad or path: either a solid color, or a gradient created with
+/// [`linear_gradient`], [`radial_gradient`] or [`conic_gradient`].
+///
+/// Its layout is shared with the shaders, which compute the color of gradients per pixel.
+/// Gradients are interpolated in the Oklab color space, with premultiplied alpha, like in CSS.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+pub struct Background {
+    pub(crate) tag: BackgroundTag,
+    pub(crate) stop_count: u32,
+    /// The direction of linear gradients and the starting angle of conic gradients, in
+    /// degrees, where 0 points up and angles increase clockwise.
+    pub(crate) angle: f32,
+    /// The center of radial and conic gradients, relative to the bounds of the shape.
+    pub(crate) center_x: f32,
+    pub(crate) center_y: f32,
+    pub(crate) pad: u32, // keep quads 8-byte aligned
+    pub(crate) solid: Hsla,
+    pub(crate) stops: [LinearColorStop; MAX_GRADIENT_STOPS],
+}
+
+impl Eq for Background {}
+
+/// Construct a linear gradient [`Background`], like CSS's `linear-gradient`.
+///
+/// The angle is in degrees: 0 draws the gradient from the bottom to the top of the quad, and
+/// 90 from its left to its right. The stops are sorted by their position, and only the first
+/// [`MAX_GRADIENT_STOPS`] of them are used. A gradient without stops is transparent.
+pub fn linear_gradient(
+    angle: f32,
+    stops: impl IntoIterator<Item = impl Into<LinearColorStop>>,
+) -> Background {
+    gradient(BackgroundTag::LinearGradient, angle, point(0.5, 0.5), stops)
+}
+
+/// Construct a radial gradient [`Background`], like CSS's `radial-gradient`.
+///
+/// The center is relative to the bounds of the shape, so `point(0.5, 0.5)` is its middle. The
+/// gradient is an ellipse reaching the corner that is the farthest from the center, with the
+/// stop at 0 in its center and the stop at 1 on its edge.
+pub fn radial_gradient(
+    center: Point<f32>,
+    stops: impl IntoIterator<Item = impl Into<LinearColorStop>>,
+) -> Background {
+    gradient(BackgroundTag::RadialGradient, 0., center, stops)
+}
+
+/// Construct a conic gradient [`Background`], like CSS's `conic-gradient`.
+///
+/// The stops go clockwise around the center, starting from the angle in degrees, where 0
+/// points up. The center is relative to the bounds of the shape, so `point(0.5, 0.5)` is its
+/// middle.
+pub fn conic_gradient(
+    angle: f32,
+    center: Point<f32>,
+    stops: impl IntoIterator<Item = impl Into<LinearColorStop>>,
+) -> Background {
+    gradient(BackgroundTag::ConicGradient, angle, center, stops)
+}
+
+fn gradient(
+    tag: BackgroundTag,
+    angle: f32,
+    center: Point<f32>,
+    stops: impl IntoIterator<Item = impl Into<LinearColorStop>>,
+) -> Background {
+    let mut background = Background {
+        tag,
+        angle: angle.rem_euclid(360.),
+        center_x: center.x,
+        center_y: center.y,
+        ..Default::default()
+    };
+    for stop in stops.into_iter().take(MAX_GRADIENT_STOPS) {
+        background.stops[background.stop_count as usize] = stop.into();
+        background.stop_count += 1;
+    }
+    background.stops[..background.stop_count as usize]
+        .sort_by(|a, b| a.percentage.total_cmp(&b.percentage));
+    background
+}
+
+impl Background {
+    /// Returns the color of this background, if it is a solid color.
+    pub fn color(&self) -> Option<Hsla> {
+        match self.tag {
+            BackgroundTag::Solid => Some(self.solid),
+            _ => None,
+        }
+    }
+
+    /// Returns true if every color of this background is fully transparent.
+    pub fn is_transparent(&self) -> bool {
+        match self.tag {
+            BackgroundTag::Solid => self.solid.is_transparent(),
+            _ => self.stops[..self.stop_count as usize]
+                .iter()
+                .all(|stop| stop.color.is_transparent()),
+        }
+    }
+
+    /// Returns true if every color of this background is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        match self.tag {
+            BackgroundTag::Solid => self.solid.is_opaque(),
+            _ => {
+                self.stop_count > 0
+                    && self.stops[..self.stop_count as usize]
+                        .iter()
+                        .all(|stop| stop.color.is_opaque())
+            }
+        }
+    }
+
+    /// Returns a new background with the alpha of each of its colors multiplied by the factor.
+    pub fn opacity(&self, factor: f32) -> Self {
+        let mut background = *self;
+        background.solid = background.solid.opacity(factor);
+        for stop in &mut background.stops {
+            stop.color = stop.color.opacity(factor);
+        }
+        background
+    }
+
+    /// Returns a new background with each of its colors made fully opaque.
+    pub fn opaque(&self) -> Self {
+        let mut background = *self;
+        background.solid.a = 1.;
+        for stop in &mut background.stops {
+            stop.color.a = 1.;
+        }
+        background
+    }
+}
+
+impl From<Hsla> for Background {
+    fn from(color: Hsla) -> Self {
+        Background {
+            solid: color,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<Rgba> for Background {
+    fn from(color: Rgba) -> Self {
+        Hsla::from(color).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -571,4 +927,94 @@ mod tests {
 
         assert_eq!(actual, rgba(0xdeadbeef))
     }
+
+    #[test]
+    fn test_linear_gradient() {
+        let gradient = linear_gradient(
+            -90.,
+            [
+                (red(), 1.),
+                (blue(), 0.),
+                (green(), 0.5),
+                (white(), 0.2),
+                (black(), 0.7),
+            ],
+        );
+        assert_eq!(gradient.angle, 270.);
+        assert_eq!(gradient.color(), None);
+        assert_eq!(
+            gradient.stops.map(|stop| stop.color),
+            [blue(), white(), green(), red()]
+        );
+        assert!(gradient.opacity(0.).is_transparent());
+
+        assert!(linear_gradient(0., [] as [LinearColorStop; 0]).is_transparent());
+        assert!(radial_gradient(point(0., 1.), [(red(), 0.)])
+            .color()
+            .is_none());
+        assert_eq!(Background::from(red()).color(), Some(red()));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let black = Hsla::from(rgb(0x000000));
+        let white = Hsla::from(rgb(0xffffff));
+        assert_eq!(black.interpolate(white, 0.), black);
+        assert_eq!(black.interpolate(white, 1.), white);
+
+        let grey = Rgba::from(black.interpolate(white, 0.5));
+        assert!((grey.r - 0.5).abs() < 1e-4 && (grey.g - 0.5).abs() < 1e-4);
+        assert_eq!(grey.a, 1.);
+    }
+
+    #[test]
+    fn test_color_space_conversion() {
+        let red = rgb(0xff0000);
+        assert_eq!(red.to_color_space(ColorSpace::Srgb), red);
+
+        let converted = red.to_color_space(ColorSpace::DisplayP3);
+        for (actual, expected) in [
+            (converted.r, 0.9175),
+            (converted.g, 0.2003),
+            (converted.b, 0.1386),
+        ] {
+            assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+        }
+        assert_eq!(converted, red.to_color_space(ColorSpace::ExtendedDisplayP3));
+
+        // Neutral colors have the same white point in both spaces.
+        let grey = rgb(0x808080).to_color_space(ColorSpace::DisplayP3);
+        assert!((grey.r - grey.g).abs() < 1e-4 && (grey.g - grey.b).abs() < 1e-4);
+        assert!((grey.r - 0x80 as f32 / 255.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_display_p3_colors() {
+        // Display P3's red is outside of sRGB's gamut, so it's given in extended sRGB.
+        let red = Rgba::try_from("color(display-p3 1 0 0)").unwrap();
+        assert!(red.r > 1. && red.g < 0. && red.b < 0.);
+        let drawn = red.to_color_space(ColorSpace::DisplayP3);
+        for (actual, expected) in [(drawn.r, 1.), (drawn.g, 0.), (drawn.b, 0.)] {
+            assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+        }
+
+        // Extended colors survive the round trip through HSLA that themes take.
+        let rgba = Rgba::from(Hsla::from(red));
+        for (actual, expected) in [(rgba.r, red.r), (rgba.g, red.g), (rgba.b, red.b)] {
+            assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+        }
+
+        let translucent = Rgba::try_from("color(srgb 100% 50% 0% / 0.5)").unwrap();
+        assert_eq!(
+            translucent,
+            Rgba {
+                r: 1.,
+                g: 0.5,
+                b: 0.,
+                a: 0.5
+            }
+        );
+        assert!(Rgba::try_from("color(rec2020 1 0 0)").is_err());
+        assert!(Rgba::try_from("color(display-p3 1 0)").is_err());
+    }
 }
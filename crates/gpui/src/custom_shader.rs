@@ -0,0 +1,60 @@
+//! Shaders supplied by the application, for effects the built-in primitives can't express, such
+//! as animated backgrounds. Shaders are registered once, usually at startup, and painted by
+//! elements with [`WindowContext::paint_custom_shader`](crate::WindowContext::paint_custom_shader).
+//!
+//! A custom shader only provides the color of each pixel of the bounds it's painted in. GPUI
+//! positions and clips the primitive, so the shader is a single function, `custom_shader_main`,
+//! written in the shading language of each renderer it should draw with:
+//!
+//! - WGSL, for the Blade renderer used on Linux and Windows:
+//!   `fn custom_shader_main(input: CustomShaderInput) -> vec4<f32>`
+//! - Metal Shading Language, for the Metal renderer used on macOS:
+//!   `float4 custom_shader_main(CustomShaderInput input)`
+//!
+//! In both languages, `CustomShaderInput` has the following fields:
+//!
+//! - `position`: the position of the pixel in the frame, in device pixels.
+//! - `uv`: the position of the pixel within the primitive's bounds, from 0 to 1.
+//! - `size`: the size of the primitive's bounds, in device pixels.
+//! - `uniforms`: the [`MAX_CUSTOM_SHADER_UNIFORMS`] floats the primitive was painted with.
+//!
+//! The returned color is linear RGBA with straight (not premultiplied) alpha.
+
+use crate::SharedString;
+use parking_lot::{const_rwlock, RwLock};
+
+/// The number of floats custom shader primitives are painted with. Primitives painted with fewer
+/// uniforms have the rest set to zero.
+pub const MAX_CUSTOM_SHADER_UNIFORMS: usize = 16;
+
+static CUSTOM_SHADERS: RwLock<Vec<CustomShaderSource>> = const_rwlock(Vec::new());
+
+/// Identifies a shader registered with
+/// [`AppContext::register_custom_shader`](crate::AppContext::register_custom_shader).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct CustomShaderId(pub(crate) u32);
+
+/// The source of a custom shader, in the shading language of each renderer it should draw with.
+/// Primitives painted with a shader that has no source for the current renderer, or whose source
+/// fails to compile, aren't drawn.
+#[derive(Clone, Debug)]
+pub struct CustomShaderSource {
+    /// A name for the shader, used in error messages and GPU debugging tools.
+    pub label: SharedString,
+    /// WGSL defining `custom_shader_main`, for the Blade renderer.
+    pub wgsl: Option<SharedString>,
+    /// Metal Shading Language defining `custom_shader_main`, for the Metal renderer.
+    pub msl: Option<SharedString>,
+}
+
+pub(crate) fn register_custom_shader(source: CustomShaderSource) -> CustomShaderId {
+    let mut shaders = CUSTOM_SHADERS.write();
+    shaders.push(source);
+    CustomShaderId(shaders.len() as u32 - 1)
+}
+
+/// The source of a registered shader. Renderers compile it the first time they draw with it.
+pub(crate) fn custom_shader_source(id: CustomShaderId) -> Option<CustomShaderSource> {
+    CUSTOM_SHADERS.read().get(id.0 as usize).cloned()
+}
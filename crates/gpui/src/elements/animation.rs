@@ -18,6 +18,7 @@ pub struct Animation {
 impl Animation {
     /// Create a new animation with the given duration.
     /// By default the animation will only run once and will use a linear easing function.
+    /// Animations that run once are skipped when the user prefers reduced motion.
     pub fn new(duration: Duration) -> Self {
         Self {
             duration,
@@ -107,14 +108,19 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
         cx: &mut crate::WindowContext,
     ) -> (crate::LayoutId, Self::RequestLayoutState) {
         cx.with_element_state(global_id.unwrap(), |state, cx| {
-            let state = state.unwrap_or_else(|| AnimationState {
-                start: Instant::now(),
-            });
-            let mut delta =
-                state.start.elapsed().as_secs_f32() / self.animation.duration.as_secs_f32();
+            // Use the executor's clock so tests can advance animations deterministically.
+            let now = cx.background_executor().now();
+            let state = state.unwrap_or_else(|| AnimationState { start: now });
+            let mut delta = now.saturating_duration_since(state.start).as_secs_f32()
+                / self.animation.duration.as_secs_f32();
+
+            // One-shot animations are purely decorative, so users who prefer reduced motion
+            // see them in their final state right away.
+            let skip_to_end =
+                self.animation.oneshot && cx.accessibility_preferences().reduce_motion;
 
             let mut done = false;
-            if delta > 1.0 {
+            if delta > 1.0 || skip_to_end {
                 if self.animation.oneshot {
                     done = true;
                     delta = 1.0;
@@ -18,7 +18,7 @@
 use crate::{
     point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Bounds,
     ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
+    HitboxId, HoverDelays, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
     ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
     StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
@@ -35,7 +35,7 @@ use std::{
     mem,
     ops::DerefMut,
     rc::Rc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use taffy::style::Overflow;
 use util::ResultExt;
@@ -568,6 +568,24 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Fade between this element's base style and its hover style over the given duration,
+    /// instead of switching at once. The background, border and text colors are blended, while
+    /// other properties of the hover style apply for as long as the fade lasts. The element
+    /// needs an id to keep track of the fade.
+    fn hover_transition(mut self, duration: Duration) -> Self {
+        self.interactivity().hover_transition = Some(duration);
+        self
+    }
+
+    /// Wait for the mouse to stay over this element for `enter`, or away from it for `leave`,
+    /// before changing its hover state. This applies to its hover style and to the group hover
+    /// styles of its children when it's a group, so they don't flicker when the mouse skims
+    /// across the element. The element needs an id.
+    fn hover_delays(mut self, enter: Duration, leave: Duration) -> Self {
+        self.interactivity().hover_delays = Some(HoverDelays { enter, leave });
+        self
+    }
+
     /// Apply the given style to this element when the mouse hovers over a group member
     fn group_hover(
         mut self,
@@ -581,6 +599,20 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Apply the given style to this element when focus is within a group member
+    /// that tracks focus
+    fn group_focus(
+        mut self,
+        group_name: impl Into<SharedString>,
+        f: impl FnOnce(StyleRefinement) -> StyleRefinement,
+    ) -> Self {
+        self.interactivity().group_focus_style = Some(GroupStyle {
+            group: group_name.into(),
+            style: Box::new(f(StyleRefinement::default())),
+        });
+        self
+    }
+
     /// Bind the given callback to the mouse down event for the given mouse button,
     /// the fluent API equivalent to [`Interactivity::on_mouse_down`]
     ///
@@ -915,7 +947,8 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
-    /// Set the given styles to be applied when this element is active.
+    /// Set the given styles to be applied when this element is active, while the mouse is pressed
+    /// on it. The view containing this element renders again when it's pressed and released.
     fn active(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self
     where
         Self: Sized,
@@ -924,7 +957,9 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
-    /// Set the given styles to be applied when this element's group is active.
+    /// Set the given styles to be applied when this element's group is active, while the mouse is
+    /// pressed on the group's hitbox. Like [`StatefulInteractiveElement::active`], the view
+    /// containing this element renders again when it's pressed and released.
     fn group_active(
         mut self,
         group_name: impl Into<SharedString>,
@@ -1253,7 +1288,10 @@ pub struct Interactivity {
     pub(crate) focus_style: Option<Box<StyleRefinement>>,
     pub(crate) in_focus_style: Option<Box<StyleRefinement>>,
     pub(crate) hover_style: Option<Box<StyleRefinement>>,
+    pub(crate) hover_transition: Option<Duration>,
+    pub(crate) hover_delays: Option<HoverDelays>,
     pub(crate) group_hover_style: Option<GroupStyle>,
+    pub(crate) group_focus_style: Option<GroupStyle>,
     pub(crate) active_style: Option<Box<StyleRefinement>>,
     pub(crate) group_active_style: Option<GroupStyle>,
     pub(crate) drag_over_styles: Vec<(
@@ -1341,7 +1379,9 @@ impl Interactivity {
                 }
 
                 let style = self.compute_style_internal(None, element_state.as_mut(), cx);
-                let layout_id = f(style, cx);
+                let layout_id = self.with_group_focus_handle(cx, |cx| {
+                    cx.with_layout_direction(style.layout_direction, |cx| f(style, cx))
+                });
                 (layout_id, element_state)
             },
         )
@@ -1382,23 +1422,53 @@ impl Interactivity {
                     }
                 }
 
-                cx.with_text_style(style.text_style().cloned(), |cx| {
-                    cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
-                        let hitbox = if self.should_insert_hitbox(&style) {
-                            Some(cx.insert_hitbox(bounds, self.occlude_mouse))
-                        } else {
-                            None
-                        };
+                cx.with_z_index(style.z_index.unwrap_or(0), |cx| {
+                    cx.with_text_style(style.text_style().cloned(), |cx| {
+                        cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
+                            let hitbox = if self.should_insert_hitbox(&style) {
+                                let hitbox = cx.insert_hitbox(bounds, self.occlude_mouse);
+                                if let Some((hover_delays, global_id)) =
+                                    self.hover_delays.zip(global_id)
+                                {
+                                    cx.set_hover_delays(&hitbox, global_id, hover_delays);
+                                }
+                                Some(hitbox)
+                            } else {
+                                None
+                            };
 
-                        let scroll_offset = self.clamp_scroll_position(bounds, &style, cx);
-                        let result = f(&style, scroll_offset, hitbox, cx);
-                        (result, element_state)
+                            let scroll_offset = self.clamp_scroll_position(bounds, &style, cx);
+                            let result = self.with_group_focus_handle(cx, |cx| {
+                                cx.with_layout_direction(style.layout_direction, |cx| {
+                                    f(&style, scroll_offset, hitbox, cx)
+                                })
+                            });
+                            (result, element_state)
+                        })
                     })
                 })
             },
         )
     }
 
+    /// Makes this element's focus handle available to the `group_focus` styles of its children
+    /// while they're laid out, prepainted and painted by the given function, so those styles
+    /// apply in every phase.
+    fn with_group_focus_handle<R>(
+        &self,
+        cx: &mut WindowContext,
+        f: impl FnOnce(&mut WindowContext) -> R,
+    ) -> R {
+        let Some((group, focus_handle)) = self.group.clone().zip(self.tracked_focus_handle.clone())
+        else {
+            return f(cx);
+        };
+        GroupFocusHandles::push(group.clone(), focus_handle, cx);
+        let result = f(cx);
+        GroupFocusHandles::pop(&group, cx);
+        result
+    }
+
     fn should_insert_hitbox(&self, style: &Style) -> bool {
         self.occlude_mouse
             || style.mouse_cursor.is_some()
@@ -1448,6 +1518,9 @@ impl Interactivity {
                         .to_pixels(bounds.size.height.into(), rem_size),
             );
             let scroll_max = (self.content_size + padding_size - bounds.size).max(&Size::default());
+            if let Some(scroll_handle) = &self.tracked_scroll_handle {
+                scroll_handle.0.borrow_mut().max_offset = scroll_max;
+            }
             // Clamp scroll offset in case scroll max is smaller now (e.g., if children
             // were removed or the bounds became larger).
             let mut scroll_offset = scroll_offset.borrow_mut();
@@ -1498,44 +1571,82 @@ impl Interactivity {
                     return ((), element_state);
                 }
 
-                cx.with_element_opacity(style.opacity, |cx| {
-                    style.paint(bounds, cx, |cx: &mut WindowContext| {
-                        cx.with_text_style(style.text_style().cloned(), |cx| {
-                            cx.with_content_mask(
-                                style.overflow_mask(bounds, cx.rem_size()),
-                                |cx| {
-                                    if let Some(hitbox) = hitbox {
-                                        #[cfg(debug_assertions)]
-                                        self.paint_debug_info(global_id, hitbox, &style, cx);
-
-                                        if !cx.has_active_drag() {
-                                            if let Some(mouse_cursor) = style.mouse_cursor {
-                                                cx.set_cursor_style(mouse_cursor, hitbox);
-                                            }
-                                        }
-
-                                        if let Some(group) = self.group.clone() {
-                                            GroupHitboxes::push(group, hitbox.id, cx);
-                                        }
-
-                                        self.paint_mouse_listeners(
-                                            hitbox,
-                                            element_state.as_mut(),
-                                            cx,
+                let (group_opacity, element_opacity) = if style.opacity_group {
+                    (style.opacity.unwrap_or(1.), None)
+                } else {
+                    (1., style.opacity)
+                };
+
+                cx.with_z_index(style.z_index.unwrap_or(0), |cx| {
+                    cx.with_opacity(group_opacity, |cx| {
+                        cx.with_element_opacity(element_opacity, |cx| {
+                            cx.with_blend_mode(style.blend_mode, |cx| {
+                                style.paint(bounds, cx, |cx: &mut WindowContext| {
+                                    cx.with_text_style(style.text_style().cloned(), |cx| {
+                                        cx.with_content_mask(
+                                            style.overflow_mask(bounds, cx.rem_size()),
+                                            |cx| {
+                                                if let Some(hitbox) = hitbox {
+                                                    #[cfg(debug_assertions)]
+                                                    self.paint_debug_info(
+                                                        global_id, hitbox, &style, cx,
+                                                    );
+
+                                                    if !cx.has_active_drag() {
+                                                        // Cursor requests only apply while their hitbox
+                                                        // is hovered, so the hover style's cursor can be
+                                                        // requested up front. The cursor then changes as
+                                                        // soon as the element is hovered, without waiting
+                                                        // for a redraw.
+                                                        let mouse_cursor = if hitbox.is_hovered(cx)
+                                                        {
+                                                            style.mouse_cursor
+                                                        } else {
+                                                            self.hover_style
+                                                                .as_ref()
+                                                                .and_then(|hover| {
+                                                                    hover.mouse_cursor
+                                                                })
+                                                                .or(style.mouse_cursor)
+                                                        };
+                                                        if let Some(mouse_cursor) = mouse_cursor {
+                                                            cx.set_cursor_style(
+                                                                mouse_cursor,
+                                                                hitbox,
+                                                            );
+                                                        }
+                                                    }
+
+                                                    if let Some(group) = self.group.clone() {
+                                                        GroupHitboxes::push(group, hitbox.id, cx);
+                                                    }
+
+                                                    self.paint_mouse_listeners(
+                                                        hitbox,
+                                                        element_state.as_mut(),
+                                                        cx,
+                                                    );
+                                                    self.paint_scroll_listener(hitbox, &style, cx);
+                                                }
+
+                                                self.paint_keyboard_listeners(cx);
+                                                self.with_group_focus_handle(cx, |cx| {
+                                                    cx.with_layout_direction(
+                                                        style.layout_direction,
+                                                        |cx| f(&style, cx),
+                                                    )
+                                                });
+
+                                                if hitbox.is_some() {
+                                                    if let Some(group) = self.group.as_ref() {
+                                                        GroupHitboxes::pop(group, cx);
+                                                    }
+                                                }
+                                            },
                                         );
-                                        self.paint_scroll_listener(hitbox, &style, cx);
-                                    }
-
-                                    self.paint_keyboard_listeners(cx);
-                                    f(&style, cx);
-
-                                    if hitbox.is_some() {
-                                        if let Some(group) = self.group.as_ref() {
-                                            GroupHitboxes::pop(group, cx);
-                                        }
-                                    }
-                                },
-                            );
+                                    });
+                                });
+                            });
                         });
                     });
                 });
@@ -1709,7 +1820,6 @@ impl Interactivity {
         }
 
         if self.hover_style.is_some()
-            || self.base_style.mouse_cursor.is_some()
             || cx.active_drag.is_some() && !self.drag_over_styles.is_empty()
         {
             let hitbox = hitbox.clone();
@@ -1974,11 +2084,18 @@ impl Interactivity {
                 .clicked_state
                 .get_or_insert_with(Default::default)
                 .clone();
+            // Only the view containing this element needs to render again to
+            // apply or remove its active styles.
+            let parent_id = cx.parent_view_id();
             if active_state.borrow().is_clicked() {
                 cx.on_mouse_event(move |_: &MouseUpEvent, phase, cx| {
                     if phase == DispatchPhase::Capture {
                         *active_state.borrow_mut() = ElementClickedState::default();
-                        cx.refresh();
+                        if let Some(parent_id) = parent_id {
+                            cx.notify(parent_id)
+                        } else {
+                            cx.refresh()
+                        }
                     }
                 });
             } else {
@@ -1997,7 +2114,11 @@ impl Interactivity {
                                 group: group_hovered,
                                 element: element_hovered,
                             };
-                            cx.refresh();
+                            if let Some(parent_id) = parent_id {
+                                cx.notify(parent_id)
+                            } else {
+                                cx.refresh()
+                            }
                         }
                     }
                 });
@@ -2115,7 +2236,7 @@ impl Interactivity {
     fn compute_style_internal(
         &self,
         hitbox: Option<&Hitbox>,
-        element_state: Option<&mut InteractiveElementState>,
+        mut element_state: Option<&mut InteractiveElementState>,
         cx: &mut WindowContext,
     ) -> Style {
         let mut style = Style::default();
@@ -2135,6 +2256,16 @@ impl Interactivity {
             }
         }
 
+        if let Some(group_focus) = self.group_focus_style.as_ref() {
+            if let Some(group_focus_handle) =
+                GroupFocusHandles::get(&group_focus.group, cx.deref_mut())
+            {
+                if group_focus_handle.within_focused(cx) {
+                    style.refine(&group_focus.style);
+                }
+            }
+        }
+
         if let Some(hitbox) = hitbox {
             if !cx.has_active_drag() {
                 if let Some(group_hover) = self.group_hover_style.as_ref() {
@@ -2148,8 +2279,35 @@ impl Interactivity {
                 }
 
                 if let Some(hover_style) = self.hover_style.as_ref() {
-                    if hitbox.is_hovered(cx) {
+                    let hovered = hitbox.is_hovered(cx);
+                    let progress = match (self.hover_transition, element_state.as_deref_mut()) {
+                        (Some(duration), Some(element_state))
+                            if !cx.accessibility_preferences().reduce_motion =>
+                        {
+                            let progress = element_state
+                                .hover_transition
+                                .get_or_insert_with(|| HoverTransition::new(hovered))
+                                .progress(hovered, duration, Instant::now());
+                            if progress > 0. && progress < 1. {
+                                cx.request_animation_frame();
+                            }
+                            progress
+                        }
+                        _ => {
+                            if hovered {
+                                1.
+                            } else {
+                                0.
+                            }
+                        }
+                    };
+
+                    if progress >= 1. {
+                        style.refine(hover_style);
+                    } else if progress > 0. {
+                        let base_style = style.clone();
                         style.refine(hover_style);
+                        style.blend_colors_from(&base_style, progress);
                     }
                 }
             }
@@ -2202,6 +2360,14 @@ impl Interactivity {
             }
         }
 
+        if style
+            .layout_direction
+            .unwrap_or_else(|| cx.layout_direction())
+            .is_rtl()
+        {
+            style.mirror_horizontally();
+        }
+
         style
     }
 }
@@ -2213,11 +2379,54 @@ pub struct InteractiveElementState {
     pub(crate) focus_handle: Option<FocusHandle>,
     pub(crate) clicked_state: Option<Rc<RefCell<ElementClickedState>>>,
     pub(crate) hover_state: Option<Rc<RefCell<bool>>>,
+    pub(crate) hover_transition: Option<HoverTransition>,
     pub(crate) pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
     pub(crate) active_tooltip: Option<Rc<RefCell<Option<ActiveTooltip>>>>,
 }
 
+/// How far an element has faded between its base style and its hover style.
+#[derive(Clone, Copy)]
+pub(crate) struct HoverTransition {
+    hovered: bool,
+    changed_at: Instant,
+    progress_at_change: f32,
+}
+
+impl HoverTransition {
+    /// Starts out fully in the style for the given hover state, without fading into it.
+    fn new(hovered: bool) -> Self {
+        Self {
+            hovered,
+            changed_at: Instant::now(),
+            progress_at_change: if hovered { 1. } else { 0. },
+        }
+    }
+
+    /// Returns how far the element is toward its hover style, from 0.0 to 1.0, starting a fade
+    /// from the current progress when the hover state changes.
+    fn progress(&mut self, hovered: bool, duration: Duration, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.changed_at);
+        let delta = if duration.is_zero() {
+            1.
+        } else {
+            elapsed.as_secs_f32() / duration.as_secs_f32()
+        };
+        let progress = if self.hovered {
+            (self.progress_at_change + delta).min(1.)
+        } else {
+            (self.progress_at_change - delta).max(0.)
+        };
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.changed_at = now;
+            self.progress_at_change = progress;
+        }
+        progress
+    }
+}
+
 /// The current active tooltip
 pub struct ActiveTooltip {
     pub(crate) tooltip: Option<AnyTooltip>,
@@ -2267,6 +2476,33 @@ impl GroupHitboxes {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct GroupFocusHandles(HashMap<SharedString, SmallVec<[FocusHandle; 1]>>);
+
+impl Global for GroupFocusHandles {}
+
+impl GroupFocusHandles {
+    pub fn get(name: &SharedString, cx: &mut AppContext) -> Option<FocusHandle> {
+        cx.default_global::<Self>()
+            .0
+            .get(name)
+            .and_then(|focus_handles| focus_handles.last())
+            .cloned()
+    }
+
+    pub fn push(name: SharedString, focus_handle: FocusHandle, cx: &mut AppContext) {
+        cx.default_global::<Self>()
+            .0
+            .entry(name)
+            .or_default()
+            .push(focus_handle);
+    }
+
+    pub fn pop(name: &SharedString, cx: &mut AppContext) {
+        cx.default_global::<Self>().0.get_mut(name).unwrap().pop();
+    }
+}
+
 /// A wrapper around an element that can be focused.
 pub struct Focusable<E> {
     /// The element that is focusable
@@ -2456,6 +2692,7 @@ struct ScrollHandleState {
     child_bounds: Vec<Bounds<Pixels>>,
     requested_scroll_top: Option<(usize, Pixels)>,
     overflow: Point<Overflow>,
+    max_offset: Size<Pixels>,
 }
 
 /// A handle to the scrollable aspects of an element.
@@ -2505,6 +2742,11 @@ impl ScrollHandle {
         self.0.borrow().bounds
     }
 
+    /// Get how far the content can be scrolled along each axis, as of the last frame.
+    pub fn max_offset(&self) -> Size<Pixels> {
+        self.0.borrow().max_offset
+    }
+
     /// Set the bounds into which this child is painted
     pub(super) fn set_bounds(&self, bounds: Bounds<Pixels>) {
         self.0.borrow_mut().bounds = bounds;
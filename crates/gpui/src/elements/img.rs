@@ -1,8 +1,8 @@
 use crate::{
-    px, AbsoluteLength, AppContext, Asset, Bounds, DefiniteLength, Element, ElementId,
-    GlobalElementId, Hitbox, Image, InteractiveElement, Interactivity, IntoElement, LayoutId,
-    Length, ObjectFit, Pixels, RenderImage, SharedString, SharedUri, Size, StyleRefinement, Styled,
-    SvgSize, UriOrPath, WindowContext,
+    px, AbsoluteLength, AppContext, Asset, Bounds, ColorFilter, DefiniteLength, Edges, Element,
+    ElementId, GlobalElementId, Hitbox, Image, ImageRepeat, InteractiveElement, Interactivity,
+    IntoElement, LayoutId, Length, ObjectFit, Pixels, RenderImage, SharedString, SharedUri, Size,
+    StyleRefinement, Styled, SvgSize, UriOrPath, WindowContext,
 };
 use futures::{AsyncReadExt, Future};
 use image::{
@@ -96,8 +96,10 @@ impl From<Arc<RenderImage>> for ImageSource {
 pub struct Img {
     interactivity: Interactivity,
     source: ImageSource,
-    grayscale: bool,
+    filter: ColorFilter,
     object_fit: ObjectFit,
+    nine_slice: Option<Edges<Pixels>>,
+    repeat: Option<ImageRepeat>,
 }
 
 /// Create a new image element.
@@ -105,8 +107,10 @@ pub fn img(source: impl Into<ImageSource>) -> Img {
     Img {
         interactivity: Interactivity::default(),
         source: source.into(),
-        grayscale: false,
+        filter: ColorFilter::default(),
         object_fit: ObjectFit::Contain,
+        nine_slice: None,
+        repeat: None,
     }
 }
 
@@ -122,14 +126,38 @@ impl Img {
 
     /// Set the image to be displayed in grayscale.
     pub fn grayscale(mut self, grayscale: bool) -> Self {
-        self.grayscale = grayscale;
+        self.filter.grayscale = if grayscale { 1. } else { 0. };
         self
     }
+
+    /// Set the color adjustments applied to the image as it's drawn, e.g. to dim it while it's
+    /// disabled, or to desaturate it in an inactive pane.
+    pub fn color_filter(mut self, filter: ColorFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
     /// Set the object fit for the image.
     pub fn object_fit(mut self, object_fit: ObjectFit) -> Self {
         self.object_fit = object_fit;
         self
     }
+
+    /// Stretch the image to fill its bounds as a nine-slice, keeping the borders given by
+    /// `insets`, in the image's pixels, at a fixed size. Useful for skinned panels and buttons
+    /// drawn from a single small image. The object fit is ignored for nine-slice images.
+    pub fn nine_slice(mut self, insets: impl Into<Edges<Pixels>>) -> Self {
+        self.nine_slice = Some(insets.into());
+        self
+    }
+
+    /// Repeat the image to fill its bounds, drawing all the copies as a single primitive. Useful
+    /// for patterned backgrounds. The object fit and nine-slice insets are ignored for repeated
+    /// images.
+    pub fn repeat(mut self, repeat: ImageRepeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
 }
 
 /// The image state between frames
@@ -168,7 +196,7 @@ impl Element for Img {
                         if let Some(state) = &mut state {
                             let frame_count = data.frame_count();
                             if frame_count > 1 {
-                                let current_time = Instant::now();
+                                let current_time = cx.background_executor().now();
                                 if let Some(last_frame_time) = state.last_frame_time {
                                     let elapsed = current_time - last_frame_time;
                                     let frame_duration =
@@ -236,15 +264,38 @@ impl Element for Img {
                 let corner_radii = style.corner_radii.to_pixels(bounds.size, cx.rem_size());
 
                 if let Some(data) = source.use_data(cx) {
-                    let new_bounds = self.object_fit.get_bounds(bounds, data.size(*frame_index));
-                    cx.paint_image(
-                        new_bounds,
-                        corner_radii,
-                        data.clone(),
-                        *frame_index,
-                        self.grayscale,
-                    )
-                    .log_err();
+                    if let Some(repeat) = self.repeat {
+                        cx.paint_repeated_image(
+                            bounds,
+                            corner_radii,
+                            data.clone(),
+                            *frame_index,
+                            repeat,
+                            self.filter,
+                        )
+                        .log_err();
+                    } else if let Some(insets) = self.nine_slice.clone() {
+                        cx.paint_nine_slice_image(
+                            bounds,
+                            corner_radii,
+                            data.clone(),
+                            *frame_index,
+                            insets,
+                            self.filter,
+                        )
+                        .log_err();
+                    } else {
+                        let new_bounds =
+                            self.object_fit.get_bounds(bounds, data.size(*frame_index));
+                        cx.paint_image(
+                            new_bounds,
+                            corner_radii,
+                            data.clone(),
+                            *frame_index,
+                            self.filter,
+                        )
+                        .log_err();
+                    }
                 }
             })
     }
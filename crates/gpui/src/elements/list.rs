@@ -271,6 +271,11 @@ impl ListState {
         self.0.borrow().logical_scroll_top()
     }
 
+    /// Get the bounds the list was last laid out in, in window coordinates.
+    pub fn viewport_bounds(&self) -> Bounds<Pixels> {
+        self.0.borrow().last_layout_bounds.unwrap_or_default()
+    }
+
     /// Scroll the list to the given offset
     pub fn scroll_to(&self, mut scroll_top: ListOffset) {
         let state = &mut *self.0.borrow_mut();
@@ -6,10 +6,13 @@ mod deferred;
 mod div;
 mod img;
 mod list;
+mod scene_stats_hud;
+mod scrollbar;
 mod surface;
 mod svg;
 mod text;
 mod uniform_list;
+mod web_view;
 
 pub use anchored::*;
 pub use animation::*;
@@ -19,7 +22,10 @@ pub use deferred::*;
 pub use div::*;
 pub use img::*;
 pub use list::*;
+pub use scene_stats_hud::*;
+pub use scrollbar::*;
 pub use surface::*;
 pub use svg::*;
 pub use text::*;
 pub use uniform_list::*;
+pub use web_view::*;
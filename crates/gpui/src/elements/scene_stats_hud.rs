@@ -0,0 +1,85 @@
+use crate::{
+    div, rgb, rgba, Component, IntoElement, ParentElement, RenderOnce, Styled, WindowContext,
+};
+
+/// Builds a [`SceneStatsHud`], which lists the primitives and draw calls of the last frame
+/// drawn to the window, and how much of its atlas is in use.
+pub fn scene_stats_hud() -> SceneStatsHud {
+    SceneStatsHud {}
+}
+
+/// An overlay in the top right corner of its nearest positioned ancestor, showing the
+/// [`SceneStats`](crate::SceneStats) of the last frame drawn to the window and its
+/// [`AtlasStats`](crate::AtlasStats). Render it from the root view to see why frames take as
+/// many draw calls as they do.
+pub struct SceneStatsHud {}
+
+impl RenderOnce for SceneStatsHud {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let stats = cx.scene_stats();
+        let atlas = cx.atlas_stats();
+        let counts = &stats.primitive_counts;
+        let breaks = &stats.batch_breaks;
+        let lines = [
+            format!(
+                "{} batches, {} primitives, {:.1} KiB",
+                stats.batch_count,
+                counts.total(),
+                stats.instance_bytes as f32 / 1024.
+            ),
+            format!(
+                "breaks: {} kind, {} texture, {} shader, {} solid quad run",
+                breaks.kind_change,
+                breaks.texture_change,
+                breaks.shader_change,
+                breaks.solid_quad_run
+            ),
+            format!(
+                "quads: {} ({} solid), shadows: {}, blurs: {}",
+                counts.quads + counts.solid_quads,
+                counts.solid_quads,
+                counts.shadows,
+                counts.backdrop_blurs
+            ),
+            format!(
+                "sprites: {} mono, {} poly, paths: {}, underlines: {}",
+                counts.monochrome_sprites,
+                counts.polychrome_sprites,
+                counts.paths,
+                counts.underlines
+            ),
+            format!(
+                "surfaces: {}, custom: {}, opacity groups: {}, bounds tree depth: {}",
+                counts.surfaces, counts.custom, counts.opacity_groups, stats.bounds_tree_depth
+            ),
+            format!(
+                "atlas: {} textures, {} tiles, {:.0}% used, {} evicted",
+                atlas.texture_count,
+                atlas.tile_count,
+                atlas.tile_area as f32 / atlas.texture_area.max(1) as f32 * 100.,
+                atlas.evicted_tile_count
+            ),
+        ];
+
+        div()
+            .absolute()
+            .top_2()
+            .right_2()
+            .flex()
+            .flex_col()
+            .p_2()
+            .rounded_md()
+            .bg(rgba(0x000000c0))
+            .text_color(rgb(0xffffff))
+            .text_xs()
+            .children(lines)
+    }
+}
+
+impl IntoElement for SceneStatsHud {
+    type Element = Component<Self>;
+
+    fn into_element(self) -> Self::Element {
+        Component::new(self)
+    }
+}
@@ -0,0 +1,515 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    fill, point, px, relative, size, Axis, Bounds, ContentMask, CursorStyle, DispatchPhase,
+    Element, ElementId, GlobalElementId, Hitbox, Hsla, IntoElement, LayoutId, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Position, ScrollHandle, Size,
+    Style, Task, WindowContext,
+};
+
+/// How long an overlay scrollbar stays visible after its content stops scrolling.
+const OVERLAY_VISIBLE_DURATION: Duration = Duration::from_millis(1000);
+/// How long an overlay scrollbar takes to fade out.
+const OVERLAY_FADE_DURATION: Duration = Duration::from_millis(250);
+/// The width of an overlay scrollbar's thumb while it isn't hovered.
+const OVERLAY_THIN_WIDTH: Pixels = px(6.);
+/// The width of a scrollbar, and of an overlay scrollbar's hit area and hovered thumb.
+const SCROLLBAR_WIDTH: Pixels = px(12.);
+/// The shortest a thumb gets, however long the content is.
+const MIN_THUMB_LENGTH: Pixels = px(24.);
+
+/// Constructs a [`Scrollbar`] along the given axis, for the content scrolled by the given state's
+/// handle.
+pub fn scrollbar(state: &ScrollbarState, axis: Axis) -> Scrollbar {
+    Scrollbar {
+        state: state.clone(),
+        axis,
+        overlay: false,
+        track_color: Hsla::transparent_black(),
+        thumb_color: Hsla {
+            h: 0.,
+            s: 0.,
+            l: 0.5,
+            a: 0.5,
+        },
+        thumb_hover_color: None,
+    }
+}
+
+/// The state of a scrollbar that needs to outlive a frame, such as an ongoing drag of its thumb
+/// or when its content last scrolled. Views keep one for each of their scrollbars.
+#[derive(Clone)]
+pub struct ScrollbarState(Rc<RefCell<ScrollbarStateInner>>);
+
+struct ScrollbarStateInner {
+    scroll_handle: ScrollHandle,
+    /// The distance from the start of the thumb to the mouse, while the thumb is being dragged.
+    drag_offset: Option<Pixels>,
+    hovered: bool,
+    last_offset: Point<Pixels>,
+    last_activity: Option<Instant>,
+    /// When an overlay scrollbar starts fading out, along with the timer that redraws it then.
+    fade_timer: Option<(Instant, Task<()>)>,
+}
+
+impl ScrollbarState {
+    /// Creates the state of a scrollbar for the content tracking the given handle. For a
+    /// [`UniformList`](crate::UniformList), pass the `base_handle` of its scroll handle.
+    pub fn new(scroll_handle: ScrollHandle) -> Self {
+        let last_offset = scroll_handle.offset();
+        Self(Rc::new(RefCell::new(ScrollbarStateInner {
+            scroll_handle,
+            drag_offset: None,
+            hovered: false,
+            last_offset,
+            last_activity: None,
+            fade_timer: None,
+        })))
+    }
+
+    /// The handle of the content this scrollbar scrolls.
+    pub fn scroll_handle(&self) -> ScrollHandle {
+        self.0.borrow().scroll_handle.clone()
+    }
+
+    /// Shows an overlay scrollbar as if its content had just scrolled, such as after navigating to
+    /// another part of the content with the keyboard. The view must be redrawn for the scrollbar to
+    /// appear.
+    pub fn flash(&self) {
+        self.0.borrow_mut().last_activity = Some(Instant::now());
+    }
+
+    /// Whether the thumb is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.0.borrow().drag_offset.is_some()
+    }
+
+    /// The opacity of an overlay scrollbar at the given time, and how long until it changes
+    /// without any further activity.
+    fn overlay_opacity(&self, now: Instant) -> (f32, Option<Duration>) {
+        let mut state = self.0.borrow_mut();
+        let offset = state.scroll_handle.offset();
+        if offset != state.last_offset {
+            state.last_offset = offset;
+            state.last_activity = Some(now);
+        }
+
+        // Hovering and dragging end with mouse events, which redraw the scrollbar.
+        if state.drag_offset.is_some() || state.hovered {
+            state.last_activity = Some(now);
+            return (1., None);
+        }
+        let Some(last_activity) = state.last_activity else {
+            return (0., None);
+        };
+        let elapsed = now.saturating_duration_since(last_activity);
+        if elapsed < OVERLAY_VISIBLE_DURATION {
+            (1., Some(OVERLAY_VISIBLE_DURATION - elapsed))
+        } else if elapsed < OVERLAY_VISIBLE_DURATION + OVERLAY_FADE_DURATION {
+            let fade = (elapsed - OVERLAY_VISIBLE_DURATION).as_secs_f32()
+                / OVERLAY_FADE_DURATION.as_secs_f32();
+            (1. - fade, Some(Duration::ZERO))
+        } else {
+            state.last_activity = None;
+            (0., None)
+        }
+    }
+}
+
+/// A scrollbar for the content tracking a [`ScrollHandle`]. Clicking its track scrolls to that
+/// position, and dragging its thumb scrolls the content along.
+///
+/// By default, the scrollbar takes up space in its parent's layout. An [overlay](Self::overlay)
+/// scrollbar instead lies over the edge of its parent, and is only shown while the content
+/// scrolls, while it's hovered, or when its state [flashes](ScrollbarState::flash). Its thumb
+/// gets thicker while hovered.
+pub struct Scrollbar {
+    state: ScrollbarState,
+    axis: Axis,
+    overlay: bool,
+    track_color: Hsla,
+    thumb_color: Hsla,
+    thumb_hover_color: Option<Hsla>,
+}
+
+impl Scrollbar {
+    /// Lays the scrollbar over the edge of its parent, which needs to be relatively positioned,
+    /// and hides it while it isn't in use.
+    pub fn overlay(mut self, overlay: bool) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
+    /// Sets the color of the track behind the thumb. Overlay scrollbars only paint their track
+    /// while hovered.
+    pub fn track_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.track_color = color.into();
+        self
+    }
+
+    /// Sets the color of the thumb.
+    pub fn thumb_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.thumb_color = color.into();
+        self
+    }
+
+    /// Sets the color of the thumb while it's hovered or dragged.
+    pub fn thumb_hover_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.thumb_hover_color = Some(color.into());
+        self
+    }
+
+    fn length(&self, size: Size<Pixels>) -> Pixels {
+        match self.axis {
+            Axis::Vertical => size.height,
+            Axis::Horizontal => size.width,
+        }
+    }
+
+    fn coordinate(&self, point: Point<Pixels>) -> Pixels {
+        match self.axis {
+            Axis::Vertical => point.y,
+            Axis::Horizontal => point.x,
+        }
+    }
+}
+
+/// Where a scrollbar's thumb lies along its track.
+#[derive(Clone, Copy)]
+struct ThumbLayout {
+    /// The distance from the start of the track to the start of the thumb.
+    start: Pixels,
+    length: Pixels,
+    track_length: Pixels,
+    max_offset: Pixels,
+}
+
+impl ThumbLayout {
+    /// The scroll offset at which the thumb starts at the given distance from the start of the
+    /// track.
+    fn offset_for_thumb_start(&self, thumb_start: Pixels) -> Pixels {
+        let travel = self.track_length - self.length;
+        if travel <= Pixels::ZERO {
+            return Pixels::ZERO;
+        }
+        -self.max_offset * (thumb_start.clamp(Pixels::ZERO, travel) / travel)
+    }
+}
+
+#[doc(hidden)]
+pub struct ScrollbarPrepaintState {
+    hitbox: Hitbox,
+    thumb: Option<ThumbLayout>,
+    opacity: f32,
+}
+
+impl Element for Scrollbar {
+    type RequestLayoutState = ();
+    type PrepaintState = ScrollbarPrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        match self.axis {
+            Axis::Vertical => {
+                style.size.width = SCROLLBAR_WIDTH.into();
+                style.size.height = relative(1.).into();
+            }
+            Axis::Horizontal => {
+                style.size.width = relative(1.).into();
+                style.size.height = SCROLLBAR_WIDTH.into();
+            }
+        }
+        if self.overlay {
+            style.position = Position::Absolute;
+            style.inset.right = px(0.).into();
+            style.inset.bottom = px(0.).into();
+            match self.axis {
+                Axis::Vertical => style.inset.top = px(0.).into(),
+                Axis::Horizontal => style.inset.left = px(0.).into(),
+            }
+        } else {
+            style.flex_shrink = 0.;
+        }
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let scroll_handle = self.state.scroll_handle();
+        let max_offset = self.length(scroll_handle.max_offset());
+        let viewport_length = self.length(scroll_handle.bounds().size);
+        let track_length = self.length(bounds.size);
+        let thumb = (max_offset > Pixels::ZERO && viewport_length > Pixels::ZERO).then(|| {
+            let content_length = viewport_length + max_offset;
+            let length = (track_length * (viewport_length / content_length))
+                .max(MIN_THUMB_LENGTH)
+                .min(track_length);
+            let progress = (-self.coordinate(scroll_handle.offset()) / max_offset).clamp(0., 1.);
+            ThumbLayout {
+                start: (track_length - length) * progress,
+                length,
+                track_length,
+                max_offset,
+            }
+        });
+
+        let opacity = if self.overlay && thumb.is_some() {
+            let now = Instant::now();
+            let (opacity, next_change) = self.state.overlay_opacity(now);
+            match next_change {
+                Some(delay) if delay.is_zero() => {
+                    self.state.0.borrow_mut().fade_timer = None;
+                    cx.request_animation_frame();
+                }
+                Some(delay) => {
+                    // Every frame drawn until the fade starts gets here, so only schedule another
+                    // redraw when the fade was pushed back by further activity.
+                    let fade_start = now + delay;
+                    let mut state = self.state.0.borrow_mut();
+                    if state.fade_timer.as_ref().map(|(start, _)| *start) != Some(fade_start) {
+                        let parent_id = cx.parent_view_id();
+                        let timer = cx.spawn(|mut cx| async move {
+                            cx.background_executor().timer(delay).await;
+                            cx.on_next_frame(move |cx| {
+                                if let Some(parent_id) = parent_id {
+                                    cx.notify(parent_id)
+                                } else {
+                                    cx.refresh()
+                                }
+                            });
+                        });
+                        state.fade_timer = Some((fade_start, timer));
+                    }
+                }
+                None => self.state.0.borrow_mut().fade_timer = None,
+            }
+            opacity
+        } else {
+            1.
+        };
+
+        let hitbox = cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+            cx.insert_hitbox(bounds, false)
+        });
+        ScrollbarPrepaintState {
+            hitbox,
+            thumb,
+            opacity,
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let Some(thumb) = prepaint.thumb else {
+            self.state.0.borrow_mut().hovered = false;
+            return;
+        };
+        let hitbox = prepaint.hitbox.clone();
+        let is_hovered = hitbox.is_hovered(cx);
+        let is_dragging = self.state.is_dragging();
+        let is_visible = prepaint.opacity > 0.;
+
+        // An overlay scrollbar that isn't visible stays out of the way of the content below it.
+        if is_visible {
+            let thickness = if !self.overlay || is_hovered || is_dragging {
+                SCROLLBAR_WIDTH
+            } else {
+                OVERLAY_THIN_WIDTH
+            };
+            let thumb_bounds = match self.axis {
+                Axis::Vertical => Bounds {
+                    origin: point(bounds.right() - thickness, bounds.origin.y + thumb.start),
+                    size: size(thickness, thumb.length),
+                },
+                Axis::Horizontal => Bounds {
+                    origin: point(bounds.origin.x + thumb.start, bounds.bottom() - thickness),
+                    size: size(thumb.length, thickness),
+                },
+            };
+            let thumb_color = if is_hovered || is_dragging {
+                self.thumb_hover_color.unwrap_or(self.thumb_color)
+            } else {
+                self.thumb_color
+            };
+
+            cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+                if !self.overlay || is_hovered || is_dragging {
+                    cx.paint_quad(fill(bounds, self.track_color.opacity(prepaint.opacity)));
+                }
+                let corner_radius = if self.overlay {
+                    thickness / 2.
+                } else {
+                    Pixels::ZERO
+                };
+                cx.paint_quad(
+                    fill(thumb_bounds, thumb_color.opacity(prepaint.opacity))
+                        .corner_radii(corner_radius),
+                );
+            });
+            cx.set_cursor_style(CursorStyle::Arrow, &hitbox);
+        }
+
+        let parent_id = cx.parent_view_id();
+        let notify = move |cx: &mut WindowContext| {
+            if let Some(parent_id) = parent_id {
+                cx.notify(parent_id)
+            } else {
+                cx.refresh()
+            }
+        };
+        let axis = self.axis;
+        let coordinate = move |position: Point<Pixels>| match axis {
+            Axis::Vertical => position.y - bounds.origin.y,
+            Axis::Horizontal => position.x - bounds.origin.x,
+        };
+        let set_offset = {
+            let scroll_handle = self.state.scroll_handle();
+            move |thumb_start: Pixels| {
+                let offset = thumb.offset_for_thumb_start(thumb_start);
+                let mut current_offset = scroll_handle.offset();
+                match axis {
+                    Axis::Vertical => current_offset.y = offset,
+                    Axis::Horizontal => current_offset.x = offset,
+                }
+                scroll_handle.set_offset(current_offset);
+            }
+        };
+
+        if is_visible {
+            cx.on_mouse_event({
+                let state = self.state.clone();
+                let hitbox = hitbox.clone();
+                let set_offset = set_offset.clone();
+                move |event: &MouseDownEvent, phase, cx| {
+                    if phase == DispatchPhase::Bubble
+                        && event.button == MouseButton::Left
+                        && hitbox.is_hovered(cx)
+                    {
+                        let position = coordinate(event.position);
+                        let drag_offset =
+                            if position >= thumb.start && position <= thumb.start + thumb.length {
+                                position - thumb.start
+                            } else {
+                                // Clicking the track centers the thumb on the click.
+                                let drag_offset = thumb.length / 2.;
+                                set_offset(position - drag_offset);
+                                drag_offset
+                            };
+                        state.0.borrow_mut().drag_offset = Some(drag_offset);
+                        cx.stop_propagation();
+                        notify(cx);
+                    }
+                }
+            });
+        }
+
+        cx.on_mouse_event({
+            let state = self.state.clone();
+            move |event: &MouseMoveEvent, phase, cx| {
+                if phase != DispatchPhase::Bubble {
+                    return;
+                }
+                let drag_offset = state.0.borrow().drag_offset;
+                if let Some(drag_offset) = drag_offset {
+                    if event.dragging() {
+                        set_offset(coordinate(event.position) - drag_offset);
+                    } else {
+                        state.0.borrow_mut().drag_offset = None;
+                    }
+                    notify(cx);
+                } else if hitbox.is_hovered(cx) != is_hovered {
+                    // Hovering shows an overlay scrollbar and thickens its thumb.
+                    state.0.borrow_mut().hovered = !is_hovered;
+                    notify(cx);
+                }
+            }
+        });
+
+        if is_dragging {
+            let state = self.state.clone();
+            cx.on_mouse_event(move |_: &MouseUpEvent, phase, cx| {
+                if phase == DispatchPhase::Capture {
+                    state.0.borrow_mut().drag_offset = None;
+                    notify(cx);
+                }
+            });
+        }
+    }
+}
+
+impl IntoElement for Scrollbar {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_offset_for_thumb_start() {
+        let thumb = ThumbLayout {
+            start: px(0.),
+            length: px(20.),
+            track_length: px(100.),
+            max_offset: px(400.),
+        };
+        assert_eq!(thumb.offset_for_thumb_start(px(0.)), px(0.));
+        assert_eq!(thumb.offset_for_thumb_start(px(40.)), px(-200.));
+        assert_eq!(thumb.offset_for_thumb_start(px(80.)), px(-400.));
+        // The thumb can't be dragged past either end of the track.
+        assert_eq!(thumb.offset_for_thumb_start(px(-10.)), px(0.));
+        assert_eq!(thumb.offset_for_thumb_start(px(95.)), px(-400.));
+    }
+
+    #[test]
+    fn test_overlay_opacity() {
+        let scroll_handle = ScrollHandle::new();
+        let state = ScrollbarState::new(scroll_handle.clone());
+        let start = Instant::now();
+        assert_eq!(state.overlay_opacity(start), (0., None));
+
+        // Scrolling shows the scrollbar until it fades out.
+        scroll_handle.set_offset(point(px(0.), px(-10.)));
+        assert_eq!(
+            state.overlay_opacity(start),
+            (1., Some(OVERLAY_VISIBLE_DURATION))
+        );
+        let (opacity, next_change) =
+            state.overlay_opacity(start + OVERLAY_VISIBLE_DURATION + OVERLAY_FADE_DURATION / 2);
+        assert!(opacity > 0. && opacity < 1.);
+        assert_eq!(next_change, Some(Duration::ZERO));
+        let end = start + OVERLAY_VISIBLE_DURATION + OVERLAY_FADE_DURATION;
+        assert_eq!(state.overlay_opacity(end), (0., None));
+
+        // Flashing shows it again without scrolling.
+        state.flash();
+        assert_eq!(state.overlay_opacity(Instant::now()).0, 1.);
+    }
+}
@@ -3,8 +3,12 @@ use crate::{
     StyleRefinement, Styled, WindowContext,
 };
 #[cfg(target_os = "macos")]
+use crate::{DevicePixels, Size};
+#[cfg(target_os = "macos")]
 use media::core_video::CVImageBuffer;
 use refineable::Refineable;
+#[cfg(target_os = "macos")]
+use std::fmt;
 
 /// A source of a surface's content.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,6 +16,9 @@ pub enum SurfaceSource {
     /// A macOS image buffer from CoreVideo
     #[cfg(target_os = "macos")]
     Surface(CVImageBuffer),
+    /// A texture drawn by another renderer on the GPU.
+    #[cfg(target_os = "macos")]
+    Texture(ExternalTexture),
 }
 
 #[cfg(target_os = "macos")]
@@ -21,6 +28,63 @@ impl From<CVImageBuffer> for SurfaceSource {
     }
 }
 
+#[cfg(target_os = "macos")]
+impl From<ExternalTexture> for SurfaceSource {
+    fn from(value: ExternalTexture) -> Self {
+        SurfaceSource::Texture(value)
+    }
+}
+
+/// A texture another renderer draws into, such as the render target of an application embedding
+/// gpui, which is composited into the scene without being copied.
+///
+/// The texture must be created on the same device as the window's renderer, in an 8-bit RGBA or
+/// BGRA format whose colors are sRGB with straight alpha. It's sampled when the frame is drawn,
+/// so the other renderer must have finished drawing into it by then.
+#[cfg(target_os = "macos")]
+#[derive(Clone)]
+pub struct ExternalTexture(metal::Texture);
+
+#[cfg(target_os = "macos")]
+impl ExternalTexture {
+    /// Wrap a Metal texture.
+    pub fn from_metal(texture: metal::Texture) -> Self {
+        Self(texture)
+    }
+
+    /// The Metal texture this wraps.
+    pub fn metal_texture(&self) -> &metal::TextureRef {
+        &self.0
+    }
+
+    /// The size of the texture, in device pixels.
+    pub fn size(&self) -> Size<DevicePixels> {
+        crate::size(
+            DevicePixels(self.0.width() as i32),
+            DevicePixels(self.0.height() as i32),
+        )
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl PartialEq for ExternalTexture {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ptr() == other.0.as_ptr()
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Eq for ExternalTexture {}
+
+#[cfg(target_os = "macos")]
+impl fmt::Debug for ExternalTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalTexture")
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
 /// A surface element.
 pub struct Surface {
     source: SurfaceSource,
@@ -89,6 +153,11 @@ impl Element for Surface {
                 // TODO: Add support for corner_radii
                 cx.paint_surface(new_bounds, surface.clone());
             }
+            #[cfg(target_os = "macos")]
+            SurfaceSource::Texture(texture) => {
+                let new_bounds = self.object_fit.get_bounds(bounds, texture.size());
+                cx.paint_surface(new_bounds, texture.clone());
+            }
             #[allow(unreachable_patterns)]
             _ => {}
         }
@@ -179,7 +179,18 @@ impl Transformation {
         self
     }
 
-    fn into_matrix(self, center: Point<Pixels>, scale_factor: f32) -> TransformationMatrix {
+    /// Mirror this transformation horizontally, e.g. to flip an icon with a directional meaning
+    /// in a right-to-left layout.
+    pub fn mirrored_horizontally(mut self) -> Self {
+        self.scale.width = -self.scale.width;
+        self.translate.x = -self.translate.x;
+        self.rotate = radians(-self.rotate.0);
+        self
+    }
+
+    /// Convert this transformation into a matrix in scaled pixels that scales and rotates
+    /// around the given center, e.g. to pass to [`WindowContext::with_transformation`].
+    pub fn into_matrix(self, center: Point<Pixels>, scale_factor: f32) -> TransformationMatrix {
         //Note: if you read this as a sequence of matrix multiplications, start from the bottom
         TransformationMatrix::unit()
             .translate(center.scale(scale_factor) + self.translate.scale(scale_factor))
@@ -0,0 +1,193 @@
+use crate::{
+    Bounds, Element, ElementId, EventEmitter, GlobalElementId, Hitbox, IntoElement, LayoutId,
+    Model, ModelContext, Pixels, PlatformWebView, SharedString, Style, StyleRefinement, Styled,
+    Task, WindowContext,
+};
+use futures::{channel::mpsc, StreamExt as _};
+use refineable::Refineable;
+use std::rc::Rc;
+
+/// The events a [`WebView`] emits as it navigates and as its pages send messages to the app.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebViewEvent {
+    /// The web view started loading a page.
+    NavigationStarted,
+    /// The web view finished loading a page.
+    NavigationFinished,
+    /// Loading a page failed, with a description of the error.
+    NavigationFailed(SharedString),
+    /// A page sent a message with `window.gpui.postMessage(message)`. The message is serialized
+    /// as JSON.
+    Message(String),
+}
+
+/// Where a [`WebView`] is in its history, and what it's showing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WebViewNavigation {
+    /// The URL of the current page.
+    pub url: Option<SharedString>,
+    /// The title of the current page.
+    pub title: Option<SharedString>,
+    /// Whether a page is being loaded.
+    pub loading: bool,
+    /// Whether there's a page to go back to.
+    pub can_go_back: bool,
+    /// Whether there's a page to go forward to.
+    pub can_go_forward: bool,
+}
+
+/// A native web view, like a WKWebView on macOS, which is shown in a window by painting a
+/// [`web_view`] element for it.
+///
+/// Web views are drawn by the platform above the rest of the window, so elements painted over
+/// them, like popovers, are hidden behind them. Web views are only supported on macOS for now,
+/// and [`WindowContext::new_web_view`] fails on other platforms.
+pub struct WebView {
+    pub(crate) platform: Rc<dyn PlatformWebView>,
+    navigation: WebViewNavigation,
+    _events: Task<()>,
+}
+
+impl EventEmitter<WebViewEvent> for WebView {}
+
+impl WebView {
+    pub(crate) fn new(platform: Rc<dyn PlatformWebView>, cx: &mut ModelContext<Self>) -> Self {
+        // The platform reports events while it's being driven, so they're handled after the fact
+        // to not update the model while it's borrowed.
+        let (events_tx, mut events_rx) = mpsc::unbounded();
+        platform.on_event(Box::new(move |event| {
+            events_tx.unbounded_send(event).ok();
+        }));
+        let events = cx.spawn(|this, mut cx| async move {
+            while let Some(event) = events_rx.next().await {
+                let updated = this.update(&mut cx, |this, cx| {
+                    this.navigation = this.platform.navigation();
+                    cx.emit(event);
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            navigation: platform.navigation(),
+            platform,
+            _events: events,
+        }
+    }
+
+    /// Where the web view is in its history, and what it's showing.
+    pub fn navigation(&self) -> &WebViewNavigation {
+        &self.navigation
+    }
+
+    /// Load the page at the given URL. Local files are loaded with `file://` URLs, and may
+    /// access the other files in their directory.
+    pub fn load_url(&mut self, url: &str) {
+        self.platform.load_url(url);
+    }
+
+    /// Show the given HTML, resolving relative URLs in it against the base URL.
+    pub fn load_html(&mut self, html: &str, base_url: Option<&str>) {
+        self.platform.load_html(html, base_url);
+    }
+
+    /// Go back to the previous page in the history.
+    pub fn go_back(&mut self) {
+        self.platform.go_back();
+    }
+
+    /// Go forward to the next page in the history.
+    pub fn go_forward(&mut self) {
+        self.platform.go_forward();
+    }
+
+    /// Reload the current page.
+    pub fn reload(&mut self) {
+        self.platform.reload();
+    }
+
+    /// Stop loading the current page.
+    pub fn stop_loading(&mut self) {
+        self.platform.stop_loading();
+    }
+
+    /// Run the given JavaScript in the current page. Results are sent back to the app with
+    /// `window.gpui.postMessage`.
+    pub fn evaluate_script(&mut self, script: &str) {
+        self.platform.evaluate_script(script);
+    }
+}
+
+/// An element showing a [`WebView`] within its bounds.
+pub struct WebViewElement {
+    web_view: Model<WebView>,
+    style: StyleRefinement,
+}
+
+/// Create an element showing the given web view. A web view should only be shown by one element
+/// at a time, and is hidden on frames that don't paint an element for it.
+pub fn web_view(web_view: Model<WebView>) -> WebViewElement {
+    WebViewElement {
+        web_view,
+        style: Default::default(),
+    }
+}
+
+impl Element for WebViewElement {
+    type RequestLayoutState = ();
+    type PrepaintState = Hitbox;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.refine(&self.style);
+        let layout_id = cx.request_layout(style, []);
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        // The web view handles the mouse itself, so nothing below it should be hovered.
+        cx.insert_hitbox(bounds, true)
+    }
+
+    fn paint(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.paint_web_view(bounds, &self.web_view);
+    }
+}
+
+impl IntoElement for WebViewElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Styled for WebViewElement {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{AppContext, PlatformDispatcher};
+use crate::{
+    task_tracker::{self, TaskExecutor, TaskGuard, TaskOwner},
+    AppContext, PlatformDispatcher, SharedString,
+};
 use futures::channel::mpsc;
 use smol::prelude::*;
 use std::{
@@ -6,6 +9,7 @@ use std::{
     marker::PhantomData,
     mem,
     num::NonZeroUsize,
+    panic::Location,
     pin::Pin,
     rc::Rc,
     sync::{
@@ -130,15 +134,36 @@ impl BackgroundExecutor {
     }
 
     /// Enqueues the given future to be run to completion on a background thread.
+    #[track_caller]
     pub fn spawn<R>(&self, future: impl Future<Output = R> + Send + 'static) -> Task<R>
     where
         R: Send + 'static,
     {
-        self.spawn_internal::<R>(Box::pin(future), None)
+        self.spawn_internal::<R>(Box::pin(future), None, None, Location::caller())
+    }
+
+    /// Enqueues the given future to be run to completion on a background thread.
+    /// The given name identifies the task when task tracking is enabled.
+    #[track_caller]
+    pub fn spawn_named<R>(
+        &self,
+        name: impl Into<SharedString>,
+        future: impl Future<Output = R> + Send + 'static,
+    ) -> Task<R>
+    where
+        R: Send + 'static,
+    {
+        self.spawn_internal::<R>(
+            Box::pin(future),
+            None,
+            Some(name.into()),
+            Location::caller(),
+        )
     }
 
     /// Enqueues the given future to be run to completion on a background thread.
     /// The given label can be used to control the priority of the task in tests.
+    #[track_caller]
     pub fn spawn_labeled<R>(
         &self,
         label: TaskLabel,
@@ -147,14 +172,21 @@ impl BackgroundExecutor {
     where
         R: Send + 'static,
     {
-        self.spawn_internal::<R>(Box::pin(future), Some(label))
+        self.spawn_internal::<R>(Box::pin(future), Some(label), None, Location::caller())
     }
 
     fn spawn_internal<R: Send + 'static>(
         &self,
         future: AnyFuture<R>,
         label: Option<TaskLabel>,
+        name: Option<SharedString>,
+        location: &'static Location<'static>,
     ) -> Task<R> {
+        let future: AnyFuture<R> =
+            match task_tracker::track(name, location, TaskExecutor::Background, None) {
+                Some(guard) => Box::pin(run_tracked(guard, future)),
+                None => future,
+            };
         let dispatcher = self.dispatcher.clone();
         let (runnable, task) =
             async_task::spawn(future, move |runnable| dispatcher.dispatch(runnable, label));
@@ -433,7 +465,35 @@ impl ForegroundExecutor {
     }
 
     /// Enqueues the given Task to run on the main thread at some point in the future.
+    #[track_caller]
     pub fn spawn<R>(&self, future: impl Future<Output = R> + 'static) -> Task<R>
+    where
+        R: 'static,
+    {
+        self.spawn_tracked(future, None, None)
+    }
+
+    /// Enqueues the given Task to run on the main thread at some point in the future.
+    /// The given name identifies the task when task tracking is enabled.
+    #[track_caller]
+    pub fn spawn_named<R>(
+        &self,
+        name: impl Into<SharedString>,
+        future: impl Future<Output = R> + 'static,
+    ) -> Task<R>
+    where
+        R: 'static,
+    {
+        self.spawn_tracked(future, Some(name.into()), None)
+    }
+
+    #[track_caller]
+    pub(crate) fn spawn_tracked<R>(
+        &self,
+        future: impl Future<Output = R> + 'static,
+        name: Option<SharedString>,
+        owner: Option<TaskOwner>,
+    ) -> Task<R>
     where
         R: 'static,
     {
@@ -441,17 +501,29 @@ impl ForegroundExecutor {
         fn inner<R: 'static>(
             dispatcher: Arc<dyn PlatformDispatcher>,
             future: AnyLocalFuture<R>,
+            guard: Option<TaskGuard>,
         ) -> Task<R> {
+            let future: AnyLocalFuture<R> = match guard {
+                Some(guard) => Box::pin(run_tracked(guard, future)),
+                None => future,
+            };
             let (runnable, task) = async_task::spawn_local(future, move |runnable| {
                 dispatcher.dispatch_on_main_thread(runnable)
             });
             runnable.schedule();
             Task::Spawned(task)
         }
-        inner::<R>(dispatcher, Box::pin(future))
+        let guard = task_tracker::track(name, Location::caller(), TaskExecutor::Foreground, owner);
+        inner::<R>(dispatcher, Box::pin(future), guard)
     }
 }
 
+/// Keeps a task listed as live until its future completes or is dropped.
+async fn run_tracked<R>(guard: TaskGuard, future: impl Future<Output = R>) -> R {
+    let _guard = guard;
+    future.await
+}
+
 /// Scope manages a set of tasks that are enqueued and waited on together. See [`BackgroundExecutor::scoped`].
 pub struct Scope<'a> {
     executor: BackgroundExecutor,
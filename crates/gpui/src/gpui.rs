@@ -73,6 +73,7 @@ mod asset_cache;
 mod assets;
 mod bounds_tree;
 mod color;
+mod custom_shader;
 mod element;
 mod elements;
 mod executor;
@@ -82,16 +83,20 @@ mod input;
 mod interactive;
 mod key_dispatch;
 mod keymap;
+mod path_builder;
 mod platform;
 pub mod prelude;
 mod scene;
 mod shared_string;
 mod shared_uri;
+mod single_instance;
+mod stroke;
 mod style;
 mod styled;
 mod subscription;
 mod svg_renderer;
 mod taffy;
+mod task_tracker;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
 mod text_system;
@@ -122,6 +127,8 @@ pub use asset_cache::*;
 pub use assets::*;
 pub use color::*;
 pub use ctor::ctor;
+pub(crate) use custom_shader::{custom_shader_source, register_custom_shader};
+pub use custom_shader::{CustomShaderId, CustomShaderSource, MAX_CUSTOM_SHADER_UNIFORMS};
 pub use element::*;
 pub use elements::*;
 pub use executor::*;
@@ -133,18 +140,25 @@ pub use input::*;
 pub use interactive::*;
 use key_dispatch::*;
 pub use keymap::*;
+pub use path_builder::*;
 pub use platform::*;
 pub use refineable::*;
 pub use scene::*;
 use seal::Sealed;
 pub use shared_string::*;
 pub use shared_uri::*;
+use single_instance::*;
 pub use smol::Timer;
+pub use stroke::*;
 pub use style::*;
 pub use styled::*;
 pub use subscription::*;
 use svg_renderer::*;
 pub use taffy::{AvailableSpace, LayoutId};
+pub(crate) use task_tracker::TaskOwner;
+pub use task_tracker::{
+    live_tasks, set_task_tracking_enabled, task_tracking_enabled, TaskExecutor, TrackedTask,
+};
 #[cfg(any(test, feature = "test-support"))]
 pub use test::*;
 pub use text_system::*;
@@ -120,6 +120,15 @@ impl KeyContext {
         self.0.iter().any(|entry| entry.key.as_ref() == key)
     }
 
+    /// Get the first identifier in this context that isn't a key value pair, such as `Editor` in
+    /// `Editor mode = full`.
+    pub fn primary(&self) -> Option<&SharedString> {
+        self.0
+            .iter()
+            .find(|entry| entry.value.is_none())
+            .map(|entry| &entry.key)
+    }
+
     /// Get the associated value for a given identifier or key.
     pub fn get(&self, key: &str) -> Option<&SharedString> {
         self.0
@@ -0,0 +1,792 @@
+use crate::{point, px, Path, Pixels, Point, Radians, Size};
+use std::f32::consts::{PI, TAU};
+
+/// How far curves may stray from their exact shape, in device pixels.
+const TOLERANCE: f32 = 0.1;
+const MAX_CURVE_SEGMENTS: usize = 64;
+const EPSILON: f32 = 1e-4;
+
+/// The rule deciding which regions of a path are filled, where its subpaths overlap or cross
+/// themselves. Both count how many times a ray from a point to infinity crosses the path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /// Points with an odd number of crossings are filled, so overlapping subpaths cut holes in
+    /// each other.
+    #[default]
+    EvenOdd,
+    /// Points the path winds around are filled, counting crossings where it goes one way and
+    /// discounting the ones where it goes the other way.
+    NonZero,
+}
+
+impl FillRule {
+    fn contains(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// A boolean operation combining the areas of two paths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathOperation {
+    /// The area covered by either path.
+    Union,
+    /// The area covered by both paths.
+    Intersect,
+    /// The area covered by the first path but not the second.
+    Subtract,
+    /// The area covered by exactly one of the paths.
+    Xor,
+}
+
+impl PathOperation {
+    fn contains(&self, a: bool, b: bool) -> bool {
+        match self {
+            PathOperation::Union => a || b,
+            PathOperation::Intersect => a && b,
+            PathOperation::Subtract => a && !b,
+            PathOperation::Xor => a != b,
+        }
+    }
+}
+
+/// Builds a filled [`Path`] out of lines, curves and arcs, which can be painted with
+/// [`WindowContext::paint_path`](crate::WindowContext::paint_path).
+///
+/// Quadratic curves are drawn exactly. Cubic curves and arcs are approximated by as many
+/// quadratic curves as it takes to keep them within a tenth of a device pixel of their exact
+/// shape, which is why building a path takes the scale factor of the window it's drawn to.
+///
+/// ```ignore
+/// // A slice of a pie chart, a quarter of the way round.
+/// let center = point(px(50.), px(50.));
+/// let radii = size(px(40.), px(40.));
+/// let mut builder = PathBuilder::new(center);
+/// builder.line_to(point(px(50.), px(10.)));
+/// builder.arc_to(point(px(90.), px(50.)), radii, radians(0.), false, true);
+/// builder.close();
+/// cx.paint_path(builder.build(cx.scale_factor()), red());
+/// ```
+///
+/// Paths can be combined with boolean operations, to cut a notch out of a badge for instance.
+/// The result is made of straight lines, with curves approximated as finely as when building.
+#[derive(Clone, Debug)]
+pub struct PathBuilder {
+    start: Point<Pixels>,
+    commands: Vec<PathCommand>,
+    fill_rule: FillRule,
+}
+
+#[derive(Clone, Debug)]
+enum PathCommand {
+    MoveTo(Point<Pixels>),
+    LineTo(Point<Pixels>),
+    CurveTo {
+        to: Point<Pixels>,
+        ctrl: Point<Pixels>,
+    },
+    CubicBezierTo {
+        to: Point<Pixels>,
+        control_a: Point<Pixels>,
+        control_b: Point<Pixels>,
+    },
+    ArcTo {
+        to: Point<Pixels>,
+        radii: Size<Pixels>,
+        x_rotation: Radians,
+        large_arc: bool,
+        sweep: bool,
+    },
+    Close,
+}
+
+impl PathBuilder {
+    /// Starts a path at the given point.
+    pub fn new(start: Point<Pixels>) -> Self {
+        Self {
+            start,
+            commands: Vec::new(),
+            fill_rule: FillRule::default(),
+        }
+    }
+
+    /// Sets the rule deciding which regions of the path are filled. Paths filled with the
+    /// [`FillRule::NonZero`] rule are made of straight lines when built, like combined paths.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
+    /// Starts a new subpath at the given point, without drawing a line to it.
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.commands.push(PathCommand::MoveTo(to));
+    }
+
+    /// Draws a straight line from the current point to the given point.
+    pub fn line_to(&mut self, to: Point<Pixels>) {
+        self.commands.push(PathCommand::LineTo(to));
+    }
+
+    /// Draws a quadratic curve from the current point to the given point, using the given
+    /// control point.
+    pub fn curve_to(&mut self, to: Point<Pixels>, ctrl: Point<Pixels>) {
+        self.commands.push(PathCommand::CurveTo { to, ctrl });
+    }
+
+    /// Draws a cubic Bézier curve from the current point to the given point, leaving the current
+    /// point towards `control_a` and arriving from `control_b`.
+    pub fn cubic_bezier_to(
+        &mut self,
+        to: Point<Pixels>,
+        control_a: Point<Pixels>,
+        control_b: Point<Pixels>,
+    ) {
+        self.commands.push(PathCommand::CubicBezierTo {
+            to,
+            control_a,
+            control_b,
+        });
+    }
+
+    /// Draws an arc of an ellipse with the given radii, rotated by `x_rotation`, from the current
+    /// point to the given point, like the SVG arc command. Of the four arcs that connect the
+    /// points, `large_arc` picks one of the two spanning more than 180°, and `sweep` one going
+    /// clockwise. Radii too small to reach the point are scaled up until they do.
+    pub fn arc_to(
+        &mut self,
+        to: Point<Pixels>,
+        radii: Size<Pixels>,
+        x_rotation: Radians,
+        large_arc: bool,
+        sweep: bool,
+    ) {
+        self.commands.push(PathCommand::ArcTo {
+            to,
+            radii,
+            x_rotation,
+            large_arc,
+            sweep,
+        });
+    }
+
+    /// Adds a whole ellipse with the given center and radii as a subpath of its own. The current
+    /// point is left on its right-hand side.
+    pub fn ellipse(&mut self, center: Point<Pixels>, radii: Size<Pixels>) {
+        let right = point(center.x + radii.width, center.y);
+        let left = point(center.x - radii.width, center.y);
+        self.move_to(right);
+        self.arc_to(left, radii, Radians(0.), false, true);
+        self.arc_to(right, radii, Radians(0.), false, true);
+        self.close();
+    }
+
+    /// Closes the current subpath, and starts a new one at its start.
+    pub fn close(&mut self) {
+        self.commands.push(PathCommand::Close);
+    }
+
+    /// Returns the area covered by either this path or the other one.
+    pub fn union(&self, other: &PathBuilder, scale_factor: f32) -> PathBuilder {
+        self.combine(other, PathOperation::Union, scale_factor)
+    }
+
+    /// Returns the area covered by both this path and the other one.
+    pub fn intersect(&self, other: &PathBuilder, scale_factor: f32) -> PathBuilder {
+        self.combine(other, PathOperation::Intersect, scale_factor)
+    }
+
+    /// Returns the area covered by this path but not the other one.
+    pub fn subtract(&self, other: &PathBuilder, scale_factor: f32) -> PathBuilder {
+        self.combine(other, PathOperation::Subtract, scale_factor)
+    }
+
+    /// Combines this path with another one, each filled with its own fill rule, approximating
+    /// curves finely enough for a window with the given scale factor.
+    pub fn combine(
+        &self,
+        other: &PathBuilder,
+        operation: PathOperation,
+        scale_factor: f32,
+    ) -> PathBuilder {
+        let tolerance = TOLERANCE / scale_factor.max(f32::EPSILON);
+        let operands = [self, other].map(|path| (path.contours(tolerance), path.fill_rule));
+        let trapezoids = combine_contours(&operands, operation);
+
+        let start = trapezoids
+            .first()
+            .map_or(self.start, |trapezoid| trapezoid[0]);
+        let mut result = PathBuilder::new(start);
+        for trapezoid in trapezoids {
+            result.move_to(trapezoid[0]);
+            for corner in &trapezoid[1..] {
+                result.line_to(*corner);
+            }
+            result.close();
+        }
+        result
+    }
+
+    /// Builds the path, approximating curves finely enough for a window with the given scale
+    /// factor.
+    pub fn build(self, scale_factor: f32) -> Path<Pixels> {
+        // Paths are rasterized by counting how many triangles cover each pixel, which can't tell
+        // which way the path goes, so paths filled with the nonzero rule are split up instead.
+        if self.fill_rule == FillRule::NonZero {
+            let empty = PathBuilder::new(self.start);
+            return self
+                .combine(&empty, PathOperation::Union, scale_factor)
+                .build(scale_factor);
+        }
+
+        let tolerance = TOLERANCE / scale_factor.max(f32::EPSILON);
+        let mut path = Path::new(self.start);
+        for segment in self.segments(tolerance) {
+            match segment {
+                Segment::Move(to) => path.move_to(to_pixels(to)),
+                Segment::Line(to) => path.line_to(to_pixels(to)),
+                Segment::Curve { to, ctrl } => path.curve_to(to_pixels(to), to_pixels(ctrl)),
+            }
+        }
+        path
+    }
+
+    /// The path as lines and quadratic curves, with cubic curves and arcs approximated within
+    /// the given tolerance.
+    fn segments(&self, tolerance: f32) -> Vec<Segment> {
+        let mut segments = Vec::with_capacity(self.commands.len());
+        let mut subpath_start = to_f32(self.start);
+        let mut current = subpath_start;
+
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    subpath_start = to_f32(to);
+                    current = subpath_start;
+                    segments.push(Segment::Move(current));
+                }
+                PathCommand::LineTo(to) => {
+                    current = to_f32(to);
+                    segments.push(Segment::Line(current));
+                }
+                PathCommand::CurveTo { to, ctrl } => {
+                    current = to_f32(to);
+                    segments.push(Segment::Curve {
+                        to: current,
+                        ctrl: to_f32(ctrl),
+                    });
+                }
+                PathCommand::CubicBezierTo {
+                    to,
+                    control_a,
+                    control_b,
+                } => {
+                    let quadratics = cubic_to_quadratics(
+                        [current, to_f32(control_a), to_f32(control_b), to_f32(to)],
+                        tolerance,
+                    );
+                    segments.extend(
+                        quadratics
+                            .into_iter()
+                            .map(|(to, ctrl)| Segment::Curve { to, ctrl }),
+                    );
+                    current = to_f32(to);
+                }
+                PathCommand::ArcTo {
+                    to,
+                    radii,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                } => {
+                    let quadratics = arc_to_quadratics(
+                        current,
+                        to_f32(to),
+                        radii,
+                        x_rotation,
+                        large_arc,
+                        sweep,
+                        tolerance,
+                    );
+                    match quadratics {
+                        Some(quadratics) => segments.extend(
+                            quadratics
+                                .into_iter()
+                                .map(|(to, ctrl)| Segment::Curve { to, ctrl }),
+                        ),
+                        None => segments.push(Segment::Line(to_f32(to))),
+                    }
+                    current = to_f32(to);
+                }
+                // Paths are filled as if every subpath was closed, so there's nothing to draw.
+                PathCommand::Close => {
+                    current = subpath_start;
+                    segments.push(Segment::Move(current));
+                }
+            }
+        }
+        segments
+    }
+
+    /// The subpaths of the path as polygons, with curves flattened within the given tolerance.
+    fn contours(&self, tolerance: f32) -> Vec<Vec<Point<f32>>> {
+        let mut contours = vec![vec![to_f32(self.start)]];
+        for segment in self.segments(tolerance) {
+            match segment {
+                Segment::Move(to) => contours.push(vec![to]),
+                Segment::Line(to) => contours.last_mut().unwrap().push(to),
+                Segment::Curve { to, ctrl } => {
+                    let points = contours.last_mut().unwrap();
+                    let from = *points.last().unwrap();
+                    // The distance between a quadratic curve and its flattening shrinks with the
+                    // square of the number of segments.
+                    let deviation = length(from - ctrl * 2. + to);
+                    let segment_count = ((deviation / (8. * tolerance)).sqrt().ceil() as usize)
+                        .clamp(1, MAX_CURVE_SEGMENTS);
+                    points.extend((1..=segment_count).map(|i| {
+                        let t = i as f32 / segment_count as f32;
+                        from * ((1. - t) * (1. - t)) + ctrl * (2. * t * (1. - t)) + to * (t * t)
+                    }));
+                }
+            }
+        }
+        contours.retain(|contour| contour.len() > 2);
+        contours
+    }
+}
+
+enum Segment {
+    Move(Point<f32>),
+    Line(Point<f32>),
+    Curve { to: Point<f32>, ctrl: Point<f32> },
+}
+
+/// An edge of a polygon being combined, pointing down.
+struct Edge {
+    top: Point<f32>,
+    bottom: Point<f32>,
+    /// 1 when the polygon goes down along the edge, and -1 when it goes up.
+    winding: i32,
+    operand: usize,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        self.top.x + (self.bottom.x - self.top.x) * t
+    }
+
+    fn intersection_y(&self, other: &Edge) -> Option<f32> {
+        let direction = self.bottom - self.top;
+        let other_direction = other.bottom - other.top;
+        let denominator = cross(direction, other_direction);
+        if denominator.abs() < EPSILON {
+            return None;
+        }
+        let offset = other.top - self.top;
+        let t = cross(offset, other_direction) / denominator;
+        let u = cross(offset, direction) / denominator;
+        ((0. ..=1.).contains(&t) && (0. ..=1.).contains(&u)).then(|| self.top.y + direction.y * t)
+    }
+}
+
+/// Combines two sets of polygons, returning the result as trapezoids that don't overlap.
+///
+/// The plane is cut into horizontal bands at every vertex and every point where edges cross, so
+/// that edges don't cross within a band. The edges spanning each band are then sorted from left
+/// to right, and the winding number of each polygon is tracked between them to find the spans
+/// of the band inside the result. This handles polygons that cross themselves or each other
+/// with either fill rule, at a quadratic cost in the number of edges.
+fn combine_contours(
+    operands: &[(Vec<Vec<Point<f32>>>, FillRule); 2],
+    operation: PathOperation,
+) -> Vec<[Point<Pixels>; 4]> {
+    let mut edges = Vec::new();
+    for (operand, (contours, _)) in operands.iter().enumerate() {
+        for contour in contours {
+            for (i, &from) in contour.iter().enumerate() {
+                let to = contour[(i + 1) % contour.len()];
+                if (from.y - to.y).abs() < EPSILON {
+                    continue;
+                }
+                let (top, bottom, winding) = if from.y < to.y {
+                    (from, to, 1)
+                } else {
+                    (to, from, -1)
+                };
+                edges.push(Edge {
+                    top,
+                    bottom,
+                    winding,
+                    operand,
+                });
+            }
+        }
+    }
+
+    let mut ys = edges
+        .iter()
+        .flat_map(|edge| [edge.top.y, edge.bottom.y])
+        .collect::<Vec<_>>();
+    for (i, edge) in edges.iter().enumerate() {
+        ys.extend(
+            edges[i + 1..]
+                .iter()
+                .filter_map(|other| edge.intersection_y(other)),
+        );
+    }
+    ys.sort_by(f32::total_cmp);
+    ys.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+
+    let mut trapezoids = Vec::new();
+    let mut crossing_edges = Vec::new();
+    for band in ys.windows(2) {
+        let (top, bottom) = (band[0], band[1]);
+        let middle = (top + bottom) / 2.;
+        crossing_edges.clear();
+        crossing_edges.extend(
+            edges
+                .iter()
+                .filter(|edge| edge.top.y <= middle && edge.bottom.y >= middle),
+        );
+        crossing_edges.sort_by(|a, b| a.x_at(middle).total_cmp(&b.x_at(middle)));
+
+        let mut windings = [0; 2];
+        let mut span_start = None;
+        for edge in &crossing_edges {
+            windings[edge.operand] += edge.winding;
+            let inside = operation.contains(
+                operands[0].1.contains(windings[0]),
+                operands[1].1.contains(windings[1]),
+            );
+            match span_start {
+                None if inside => span_start = Some(edge),
+                Some(start) if !inside => {
+                    trapezoids.push(
+                        [
+                            point(start.x_at(top), top),
+                            point(edge.x_at(top), top),
+                            point(edge.x_at(bottom), bottom),
+                            point(start.x_at(bottom), bottom),
+                        ]
+                        .map(to_pixels),
+                    );
+                    span_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    trapezoids
+}
+
+/// Splits a cubic curve into quadratic ones, returning the end and control point of each.
+fn cubic_to_quadratics(
+    [from, control_a, control_b, to]: [Point<f32>; 4],
+    tolerance: f32,
+) -> Vec<(Point<f32>, Point<f32>)> {
+    // Approximating a cubic curve with quadratic ones strays from it by at most this much, over
+    // the cube of the number of quadratics.
+    let third_difference = to - control_b * 3. + control_a * 3. - from;
+    let deviation = 3f32.sqrt() / 36. * length(third_difference);
+    let segment_count =
+        ((deviation / tolerance).cbrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS);
+
+    let at = |t: f32| {
+        let s = 1. - t;
+        from * (s * s * s)
+            + control_a * (3. * s * s * t)
+            + control_b * (3. * s * t * t)
+            + to * (t * t * t)
+    };
+    let derivative_at = |t: f32| {
+        let s = 1. - t;
+        (control_a - from) * (3. * s * s)
+            + (control_b - control_a) * (6. * s * t)
+            + (to - control_b) * (3. * t * t)
+    };
+
+    (0..segment_count)
+        .map(|i| {
+            let t0 = i as f32 / segment_count as f32;
+            let t1 = (i + 1) as f32 / segment_count as f32;
+            // The control points of the piece of the cubic between t0 and t1, averaged into
+            // a quadratic control point.
+            let (start, end) = (at(t0), at(t1));
+            let span = t1 - t0;
+            let control_a = start + derivative_at(t0) * (span / 3.);
+            let control_b = end - derivative_at(t1) * (span / 3.);
+            let ctrl = (control_a * 3. - start + control_b * 3. - end) * 0.25;
+            (end, ctrl)
+        })
+        .collect()
+}
+
+/// Approximates an SVG arc with quadratic curves, returning the end and control point of each,
+/// or `None` when the arc is a straight line. See
+/// https://www.w3.org/TR/SVG2/implnote.html#ArcConversionEndpointToCenter.
+fn arc_to_quadratics(
+    from: Point<f32>,
+    to: Point<f32>,
+    radii: Size<Pixels>,
+    x_rotation: Radians,
+    large_arc: bool,
+    sweep: bool,
+    tolerance: f32,
+) -> Option<Vec<(Point<f32>, Point<f32>)>> {
+    let (mut rx, mut ry) = (radii.width.0.abs(), radii.height.0.abs());
+    if rx < f32::EPSILON || ry < f32::EPSILON || length(to - from) < f32::EPSILON {
+        return None;
+    }
+
+    // Find the center in a space where the ellipse's axes are aligned with the coordinate axes.
+    let (sin, cos) = x_rotation.0.sin_cos();
+    let rotate = |p: Point<f32>| point(cos * p.x - sin * p.y, sin * p.x + cos * p.y);
+    let unrotate = |p: Point<f32>| point(cos * p.x + sin * p.y, -sin * p.x + cos * p.y);
+    let half_chord = unrotate((from - to) * 0.5);
+    let scale = (half_chord.x / rx).powi(2) + (half_chord.y / ry).powi(2);
+    if scale > 1. {
+        rx *= scale.sqrt();
+        ry *= scale.sqrt();
+    }
+    let numerator = rx * rx * ry * ry
+        - rx * rx * half_chord.y * half_chord.y
+        - ry * ry * half_chord.x * half_chord.x;
+    let denominator = rx * rx * half_chord.y * half_chord.y + ry * ry * half_chord.x * half_chord.x;
+    let sign = if large_arc == sweep { -1. } else { 1. };
+    let coefficient = sign * (numerator / denominator).max(0.).sqrt();
+    let center = point(
+        coefficient * rx * half_chord.y / ry,
+        -coefficient * ry * half_chord.x / rx,
+    );
+
+    let start_vector = point(
+        (half_chord.x - center.x) / rx,
+        (half_chord.y - center.y) / ry,
+    );
+    let end_vector = point(
+        (-half_chord.x - center.x) / rx,
+        (-half_chord.y - center.y) / ry,
+    );
+    let start_angle = start_vector.y.atan2(start_vector.x);
+    let mut sweep_angle = (start_vector.x * end_vector.y - start_vector.y * end_vector.x)
+        .atan2(start_vector.x * end_vector.x + start_vector.y * end_vector.y);
+    if sweep && sweep_angle < 0. {
+        sweep_angle += TAU;
+    } else if !sweep && sweep_angle > 0. {
+        sweep_angle -= TAU;
+    }
+
+    // A quadratic spanning an angle θ of a circle of radius r strays from it by about rθ⁴/128.
+    let max_segment_angle = (128. * tolerance / rx.max(ry)).powf(0.25).min(PI / 2.);
+    let segment_count =
+        ((sweep_angle.abs() / max_segment_angle).ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS);
+    let segment_angle = sweep_angle / segment_count as f32;
+
+    let midpoint = (from + to) * 0.5;
+    let to_path = |p: Point<f32>| rotate(point(p.x * rx, p.y * ry) + center) + midpoint;
+    Some(
+        (1..=segment_count)
+            .map(|i| {
+                let angle = start_angle + segment_angle * i as f32;
+                let ctrl_angle = angle - segment_angle / 2.;
+                // The control point is where the tangents at both ends meet.
+                let ctrl_distance = 1. / (segment_angle / 2.).cos();
+                let end = if i == segment_count {
+                    to
+                } else {
+                    to_path(point(angle.cos(), angle.sin()))
+                };
+                let ctrl = to_path(point(ctrl_angle.cos(), ctrl_angle.sin()) * ctrl_distance);
+                (end, ctrl)
+            })
+            .collect(),
+    )
+}
+
+fn to_f32(point: Point<Pixels>) -> Point<f32> {
+    point.map(|coordinate| coordinate.0)
+}
+
+fn to_pixels(point: Point<f32>) -> Point<Pixels> {
+    point.map(px)
+}
+
+fn length(a: Point<f32>) -> f32 {
+    a.x.hypot(a.y)
+}
+
+fn cross(a: Point<f32>, b: Point<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::size;
+
+    /// The start, control and end points of the quadratic curves in a path.
+    fn curves(path: &Path<Pixels>) -> Vec<[Point<f32>; 3]> {
+        path.vertices
+            .chunks(3)
+            .filter(|triangle| triangle[1].st_position == point(0.5, 0.))
+            .map(|triangle| {
+                [&triangle[0], &triangle[1], &triangle[2]].map(|vertex| to_f32(vertex.xy_position))
+            })
+            .collect()
+    }
+
+    /// The y coordinate furthest from zero among the midpoints and ends of the curves.
+    fn vertical_extent(curves: &[[Point<f32>; 3]]) -> f32 {
+        curves
+            .iter()
+            .flat_map(|&[from, ctrl, to]| [(from + ctrl * 2. + to) * 0.25, to])
+            .map(|point| point.y)
+            .fold(
+                0.,
+                |extent, y| if y.abs() > extent.abs() { y } else { extent },
+            )
+    }
+
+    #[test]
+    fn test_ellipse() {
+        for scale_factor in [1., 2.] {
+            let mut builder = PathBuilder::new(point(px(0.), px(0.)));
+            builder.ellipse(point(px(50.), px(50.)), size(px(100.), px(100.)));
+            let curves = curves(&builder.build(scale_factor));
+
+            // The curves meet on the circle, and stray from it by less than a tenth of a device
+            // pixel in between.
+            for [from, ctrl, to] in curves {
+                assert!((length(to - point(50., 50.)) - 100.).abs() < 1e-2);
+                let midpoint = (from + ctrl * 2. + to) * 0.25;
+                let deviation = (length(midpoint - point(50., 50.)) - 100.).abs();
+                assert!(deviation < TOLERANCE / scale_factor, "{deviation}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_arc_to() {
+        let arc = |radius: f32, large_arc, sweep| {
+            let mut builder = PathBuilder::new(point(px(0.), px(0.)));
+            builder.arc_to(
+                point(px(20.), px(0.)),
+                size(px(radius), px(radius)),
+                Radians(0.),
+                large_arc,
+                sweep,
+            );
+            let curves = curves(&builder.build(1.));
+            assert_eq!(curves.last().unwrap()[2], point(20., 0.));
+            vertical_extent(&curves)
+        };
+
+        // Going clockwise from the left end of a half circle passes over the top.
+        assert!((arc(10., false, true) + 10.).abs() < TOLERANCE);
+        assert!((arc(10., false, false) - 10.).abs() < TOLERANCE);
+        // Of the arcs of a larger circle, the large ones reach further from the chord.
+        assert!(arc(20., true, true).abs() > 30.);
+        assert!(arc(20., false, true).abs() < 3.);
+        // Radii that are too small are scaled up to reach the end point.
+        assert!((arc(1., false, true) + 10.).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn test_cubic_bezier_to() {
+        let curves_at = |scale_factor| {
+            let mut builder = PathBuilder::new(point(px(0.), px(0.)));
+            builder.cubic_bezier_to(
+                point(px(100.), px(0.)),
+                point(px(0.), px(100.)),
+                point(px(100.), px(100.)),
+            );
+            curves(&builder.build(scale_factor))
+        };
+
+        let curves = curves_at(1.);
+        assert_eq!(curves[0][0], point(0., 0.));
+        assert_eq!(curves.last().unwrap()[2], point(100., 0.));
+        // The curve peaks halfway, at 3/4 of the height of its control points.
+        assert!((vertical_extent(&curves) - 75.).abs() < TOLERANCE);
+
+        assert!(curves_at(2.).len() > curves.len());
+    }
+
+    fn polygon(points: &[(f32, f32)]) -> PathBuilder {
+        let mut builder = PathBuilder::new(point(px(points[0].0), px(points[0].1)));
+        for &(x, y) in &points[1..] {
+            builder.line_to(point(px(x), px(y)));
+        }
+        builder.close();
+        builder
+    }
+
+    fn area(builder: &PathBuilder) -> f32 {
+        builder
+            .contours(TOLERANCE)
+            .iter()
+            .map(|contour| {
+                let doubled_area = (0..contour.len())
+                    .map(|i| cross(contour[i], contour[(i + 1) % contour.len()]))
+                    .sum::<f32>();
+                doubled_area.abs() / 2.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_path_operations() {
+        let a = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        let b = polygon(&[(5., 0.), (15., 0.), (15., 10.), (5., 10.)]);
+
+        assert!((area(&a.union(&b, 1.)) - 150.).abs() < 1e-3);
+        assert!((area(&a.intersect(&b, 1.)) - 50.).abs() < 1e-3);
+        assert!((area(&a.subtract(&b, 1.)) - 50.).abs() < 1e-3);
+        assert!((area(&b.subtract(&a, 1.)) - 50.).abs() < 1e-3);
+        assert!((area(&a.combine(&b, PathOperation::Xor, 1.)) - 100.).abs() < 1e-3);
+
+        // Cutting a circle out of a bigger one leaves a ring.
+        let mut outer = PathBuilder::new(point(px(0.), px(0.)));
+        outer.ellipse(point(px(0.), px(0.)), size(px(20.), px(20.)));
+        let mut inner = PathBuilder::new(point(px(0.), px(0.)));
+        inner.ellipse(point(px(0.), px(0.)), size(px(10.), px(10.)));
+        let ring = area(&outer.subtract(&inner, 1.));
+        assert!((ring - (area(&outer) - area(&inner))).abs() < 1e-2);
+        assert!((ring - PI * (400. - 100.)).abs() < 10.);
+    }
+
+    #[test]
+    fn test_fill_rules() {
+        // Two squares going the same way, one inside the other.
+        let mut nested = polygon(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        nested.move_to(point(px(2.), px(2.)));
+        nested.line_to(point(px(8.), px(2.)));
+        nested.line_to(point(px(8.), px(8.)));
+        nested.line_to(point(px(2.), px(8.)));
+        nested.close();
+        let empty = PathBuilder::new(point(px(0.), px(0.)));
+
+        assert!((area(&nested.union(&empty, 1.)) - 64.).abs() < 1e-3);
+        nested.set_fill_rule(FillRule::NonZero);
+        assert!((area(&nested.union(&empty, 1.)) - 100.).abs() < 1e-3);
+
+        // A pentagram crosses itself, and only fills its center with the nonzero rule.
+        let mut star = polygon(
+            &(0..5)
+                .map(|i| {
+                    let angle = i as f32 * 4. * PI / 5.;
+                    (10. * angle.sin(), -10. * angle.cos())
+                })
+                .collect::<Vec<_>>(),
+        );
+        let even_odd = area(&star.union(&empty, 1.));
+        star.set_fill_rule(FillRule::NonZero);
+        let non_zero = area(&star.union(&empty, 1.));
+        assert!(
+            even_odd > 0. && non_zero > even_odd,
+            "{even_odd} {non_zero}"
+        );
+        assert!(!star.build(1.).vertices.is_empty());
+    }
+}
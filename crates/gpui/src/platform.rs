@@ -2,6 +2,7 @@
 #![cfg_attr(windows, allow(dead_code))]
 
 mod app_menu;
+mod atlas_lru;
 mod keystroke;
 
 #[cfg(target_os = "linux")]
@@ -20,13 +21,14 @@ mod test;
 mod windows;
 
 use crate::{
-    point, Action, AnyWindowHandle, AppContext, AsyncWindowContext, BackgroundExecutor, Bounds,
-    DevicePixels, DispatchEventResult, Font, FontId, FontMetrics, FontRun, ForegroundExecutor,
-    GPUSpecs, GlyphId, ImageSource, Keymap, LineLayout, Pixels, PlatformInput, Point,
-    RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Scene, SharedString, Size,
-    SvgSize, Task, TaskLabel, WindowContext, DEFAULT_WINDOW_SIZE,
+    hash, point, Action, AnyWindowHandle, AppContext, AsyncWindowContext, BackgroundExecutor,
+    Bounds, ColorSpace, DevicePixels, DispatchEventResult, Font, FontId, FontMetrics, FontRun,
+    ForegroundExecutor, GPUSpecs, GlyphId, GlyphOutline, ImageSource, Keymap, LineLayout, Pixels,
+    PlatformInput, Point, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
+    Scene, SharedString, Size, SvgSize, Task, TaskLabel, WebViewEvent, WebViewNavigation,
+    WindowContext, DEFAULT_WINDOW_SIZE,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_task::Runnable;
 use futures::channel::oneshot;
 use image::codecs::gif::GifDecoder;
@@ -51,6 +53,7 @@ use strum::EnumIter;
 use uuid::Uuid;
 
 pub use app_menu::*;
+pub(crate) use atlas_lru::*;
 pub use keystroke::*;
 
 #[cfg(target_os = "linux")]
@@ -172,7 +175,16 @@ pub(crate) trait Platform: 'static {
     fn path_for_auxiliary_executable(&self, name: &str) -> Result<PathBuf>;
 
     fn set_cursor_style(&self, style: CursorStyle);
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    );
     fn should_auto_hide_scrollbars(&self) -> bool;
+    fn accessibility_preferences(&self) -> AccessibilityPreferences;
+    fn power_state(&self) -> PowerState;
+    fn play_system_sound(&self, _name: &str, _volume: f32) {}
 
     #[cfg(target_os = "linux")]
     fn write_to_primary(&self, item: ClipboardItem);
@@ -205,6 +217,12 @@ pub trait PlatformDisplay: Send + Sync + Debug {
         let origin = point(center.x - offset.width, center.y - offset.height);
         Bounds::new(origin, DEFAULT_WINDOW_SIZE)
     }
+
+    /// The widest color space the display can show, which windows opened on it can be tagged
+    /// with so that colors outside of sRGB's gamut, or brighter than white, are shown as given.
+    fn color_space(&self) -> ColorSpace {
+        ColorSpace::Srgb
+    }
 }
 
 /// An opaque identifier for a hardware display
@@ -355,7 +373,20 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn on_should_close(&self, callback: Box<dyn FnMut() -> bool>);
     fn on_close(&self, callback: Box<dyn FnOnce()>);
     fn on_appearance_changed(&self, callback: Box<dyn FnMut()>);
+    fn on_occlusion_change(&self, _callback: Box<dyn FnMut(bool)>) {}
     fn draw(&self, scene: &Scene);
+    /// Whether drawing reports the scene's [`Scene::damage`] to the platform. Damage is only
+    /// computed for the windows that use it, since diffing scenes takes time every frame.
+    fn uses_damage(&self) -> bool {
+        false
+    }
+    /// Renders the scene offscreen, the same way it's drawn to the window, and reads back its
+    /// pixels.
+    fn render_to_image(&self, _scene: &Scene) -> Result<image::RgbaImage> {
+        Err(anyhow!(
+            "reading back frames isn't supported on this platform"
+        ))
+    }
     fn completed_frame(&self) {}
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas>;
 
@@ -375,6 +406,7 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
         Decorations::Server
     }
     fn set_app_id(&mut self, _app_id: &str) {}
+    fn set_icon(&mut self, _icon: &RenderImage) {}
     fn window_controls(&self) -> WindowControls {
         WindowControls::default()
     }
@@ -383,12 +415,33 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
 
     fn update_ime_position(&self, _bounds: Bounds<Pixels>);
 
+    fn create_web_view(&self) -> Option<Box<dyn PlatformWebView>> {
+        None
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         None
     }
 }
 
+/// A native web view embedded in a window, which the platform draws above the window's contents.
+pub(crate) trait PlatformWebView {
+    /// Position the web view at the given bounds within the window, showing only the part of it
+    /// within the clip bounds.
+    fn set_frame(&self, bounds: Bounds<Pixels>, clip: Bounds<Pixels>);
+    fn hide(&self);
+    fn navigation(&self) -> WebViewNavigation;
+    fn load_url(&self, url: &str);
+    fn load_html(&self, html: &str, base_url: Option<&str>);
+    fn go_back(&self);
+    fn go_forward(&self);
+    fn reload(&self);
+    fn stop_loading(&self);
+    fn evaluate_script(&self, script: &str);
+    fn on_event(&self, callback: Box<dyn FnMut(WebViewEvent)>);
+}
+
 /// This type is public so that our test macro can generate and use it, but it should not
 /// be considered part of our public API.
 #[doc(hidden)]
@@ -423,6 +476,15 @@ pub(crate) trait PlatformTextSystem: Send + Sync {
         params: &RenderGlyphParams,
         raster_bounds: Bounds<DevicePixels>,
     ) -> Result<(Size<DevicePixels>, Vec<u8>)>;
+    /// The outline of a monochrome glyph within the given raster bounds, or `None` if the
+    /// platform can't provide it.
+    fn glyph_outline(
+        &self,
+        _params: &RenderGlyphParams,
+        _raster_bounds: Bounds<DevicePixels>,
+    ) -> Result<Option<GlyphOutline>> {
+        Ok(None)
+    }
     fn layout_line(&self, text: &str, font_size: Pixels, runs: &[FontRun]) -> LineLayout;
 }
 
@@ -431,6 +493,12 @@ pub(crate) enum AtlasKey {
     Glyph(RenderGlyphParams),
     Svg(RenderSvgParams),
     Image(RenderImageParams),
+    /// A tile replayed from a [`SceneRecording`](crate::SceneRecording), identified by a hash
+    /// of its pixels.
+    Recorded {
+        kind: AtlasTextureKind,
+        content_hash: u64,
+    },
 }
 
 impl AtlasKey {
@@ -445,6 +513,7 @@ impl AtlasKey {
             }
             AtlasKey::Svg(_) => AtlasTextureKind::Monochrome,
             AtlasKey::Image(_) => AtlasTextureKind::Polychrome,
+            AtlasKey::Recorded { kind, .. } => *kind,
         }
     }
 }
@@ -473,6 +542,42 @@ pub(crate) trait PlatformAtlas: Send + Sync {
         key: &AtlasKey,
         build: &mut dyn FnMut() -> Result<Option<(Size<DevicePixels>, Cow<'a, [u8]>)>>,
     ) -> Result<Option<AtlasTile>>;
+
+    /// Get or insert a monochrome tile that the renderer rasterizes from the outline returned by
+    /// `build` before drawing the next frame. Returns `None` without calling `build` if the atlas
+    /// can't rasterize outlines, and when `build` returns `None`, leaving the tile to be rasterized
+    /// on the CPU through [`Self::get_or_insert_with`].
+    fn get_or_insert_outline(
+        &self,
+        key: &AtlasKey,
+        build: &mut dyn FnMut() -> Result<Option<GlyphOutline>>,
+    ) -> Result<Option<AtlasTile>>;
+
+    /// Drops every glyph, SVG and image in the atlas, so that they're rasterized again the next
+    /// time they're drawn.
+    fn clear(&self);
+
+    /// Marks the tiles drawn in a frame as used, evicting the ones that haven't been drawn for a
+    /// while and releasing the textures left empty.
+    fn finish_frame(&self, drawn_tiles: &mut dyn Iterator<Item = &AtlasTile>);
+
+    fn stats(&self) -> AtlasStats;
+}
+
+/// How much of a window's sprite atlas, where glyphs, SVGs and images are rasterized, is in use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AtlasStats {
+    /// The number of textures glyphs, SVGs and images are rasterized into.
+    pub texture_count: usize,
+    /// The area of those textures, in device pixels.
+    pub texture_area: u64,
+    /// The number of glyphs, SVGs and images in the atlas.
+    pub tile_count: usize,
+    /// The area they cover, in device pixels.
+    pub tile_area: u64,
+    /// The number of glyphs, SVGs and images evicted since the window was opened, for not having
+    /// been drawn for a while.
+    pub evicted_tile_count: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -492,6 +597,30 @@ pub(crate) struct AtlasTextureId {
     pub(crate) kind: AtlasTextureKind,
 }
 
+impl AtlasTextureId {
+    /// How many textures of the same kind a sprite batch can sample from. Textures are bound in
+    /// groups of this many consecutive indices, so sprites can share a batch as long as their
+    /// textures are in the same group.
+    ///
+    /// Only the Metal renderer binds a group of textures at once, and this must match
+    /// `ATLAS_TEXTURES_PER_BATCH` in its shaders. The Blade renderer used on Linux and Windows
+    /// binds one texture per draw, so it still splits these batches where the texture changes.
+    pub(crate) const PER_BATCH: u32 = 8;
+
+    /// The first texture of the group this texture is bound with.
+    pub(crate) fn batch_base(self) -> Self {
+        Self {
+            index: self.index - self.batch_slot(),
+            kind: self.kind,
+        }
+    }
+
+    /// The position of this texture in the group it's bound with.
+    pub(crate) fn batch_slot(self) -> u32 {
+        self.index % Self::PER_BATCH
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub(crate) enum AtlasTextureKind {
@@ -500,7 +629,7 @@ pub(crate) enum AtlasTextureKind {
     Path = 2,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(C)]
 pub(crate) struct TileId(pub(crate) u32);
 
@@ -718,12 +847,20 @@ pub struct WindowOptions {
     /// Application identifier of the window. Can by used by desktop environments to group applications together.
     pub app_id: Option<String>,
 
+    /// The icon of the window. X11 only, other platforms take the icon from the application
+    /// bundle or desktop entry.
+    pub icon: Option<Arc<Image>>,
+
     /// Window minimum size
     pub window_min_size: Option<Size<Pixels>>,
 
     /// Whether to use client or server side decorations. Wayland only
     /// Note that this may be ignored.
     pub window_decorations: Option<WindowDecorations>,
+
+    /// The color space to tag the window's surface with. Systems without color management,
+    /// such as Linux, present colors as is, so the color space should match the display.
+    pub color_space: ColorSpace,
 }
 
 /// The variables that can be configured when creating a new window
@@ -751,6 +888,8 @@ pub(crate) struct WindowParams {
     pub display_id: Option<DisplayId>,
 
     pub window_min_size: Option<Size<Pixels>>,
+
+    pub color_space: ColorSpace,
 }
 
 /// Represents the status of how a window should be opened.
@@ -799,8 +938,10 @@ impl Default for WindowOptions {
             display_id: None,
             window_background: WindowBackgroundAppearance::default(),
             app_id: None,
+            icon: None,
             window_min_size: None,
             window_decorations: None,
+            color_space: ColorSpace::default(),
         }
     }
 }
@@ -863,6 +1004,63 @@ impl Default for WindowAppearance {
     }
 }
 
+/// The accessibility preferences the user configured in the operating system.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessibilityPreferences {
+    /// Whether to minimize non-essential motion, such as animations.
+    pub reduce_motion: bool,
+    /// Whether to draw the interface with more contrast between colors.
+    pub increase_contrast: bool,
+    /// Whether to replace translucent and blurred surfaces with opaque ones.
+    pub reduce_transparency: bool,
+}
+
+/// Overrides for the [`AccessibilityPreferences`] read from the operating system, such as the
+/// ones an application lets its users configure. Preferences set to `None` follow the system.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessibilityOverrides {
+    /// Overrides [`AccessibilityPreferences::reduce_motion`].
+    pub reduce_motion: Option<bool>,
+    /// Overrides [`AccessibilityPreferences::increase_contrast`].
+    pub increase_contrast: Option<bool>,
+    /// Overrides [`AccessibilityPreferences::reduce_transparency`].
+    pub reduce_transparency: Option<bool>,
+}
+
+impl AccessibilityOverrides {
+    /// Returns the given preferences with these overrides applied.
+    pub fn apply(&self, preferences: AccessibilityPreferences) -> AccessibilityPreferences {
+        AccessibilityPreferences {
+            reduce_motion: self.reduce_motion.unwrap_or(preferences.reduce_motion),
+            increase_contrast: self
+                .increase_contrast
+                .unwrap_or(preferences.increase_contrast),
+            reduce_transparency: self
+                .reduce_transparency
+                .unwrap_or(preferences.reduce_transparency),
+        }
+    }
+}
+
+/// Where the machine is drawing its power from, and whether the user asked the operating system
+/// to save power.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PowerState {
+    /// Whether the machine is running on battery rather than on external power.
+    pub on_battery: bool,
+    /// Whether a power saving mode is enabled, such as Low Power Mode on macOS or battery saver
+    /// on Windows.
+    pub low_power_mode: bool,
+}
+
+impl PowerState {
+    /// Returns whether work that can wait, such as indexing in the background, should be
+    /// deferred or done less often.
+    pub fn should_conserve_power(&self) -> bool {
+        self.on_battery || self.low_power_mode
+    }
+}
+
 /// The appearance of the background of the window itself, when there is
 /// no content or the content is transparent.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
@@ -993,8 +1191,41 @@ pub enum CursorStyle {
     /// A cursor indicating that the operation will result in a context menu
     /// corresponds to the CSS cursor value `context-menu`
     ContextualMenu,
+
+    /// A cursor indicating that something can be moved in any direction
+    /// corresponds to the CSS cursor value `move`
+    Move,
+
+    /// A cursor indicating that the application is busy and can't be interacted with
+    /// corresponds to the CSS cursor value `wait`
+    Wait,
+
+    /// A cursor indicating that the application is busy, but can still be interacted with
+    /// corresponds to the CSS cursor value `progress`
+    Progress,
+
+    /// A cursor indicating that help is available
+    /// corresponds to the CSS cursor value `help`
+    Help,
+
+    /// A cursor indicating that something can be zoomed in
+    /// corresponds to the CSS cursor value `zoom-in`
+    ZoomIn,
+
+    /// A cursor indicating that something can be zoomed out
+    /// corresponds to the CSS cursor value `zoom-out`
+    ZoomOut,
+
+    /// A cursor drawn from an image, created with [`AppContext::create_cursor`]
+    /// corresponds to the CSS cursor value `url(...)`
+    Custom(CustomCursor),
 }
 
+/// A cursor drawn from an image, created with [`AppContext::create_cursor`]. Platforms that
+/// can't draw custom cursors show the arrow cursor instead.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CustomCursor(pub(crate) usize);
+
 impl Default for CursorStyle {
     fn default() -> Self {
         Self::Arrow
@@ -1132,6 +1363,15 @@ impl Hash for Image {
 }
 
 impl Image {
+    /// Create an image from the given encoded bytes, identified by their hash.
+    pub fn from_bytes(format: ImageFormat, bytes: Vec<u8>) -> Self {
+        Self {
+            id: hash(&bytes),
+            format,
+            bytes,
+        }
+    }
+
     /// Get this image's ID
     pub fn id(&self) -> u64 {
         self.id
@@ -0,0 +1,193 @@
+use crate::{
+    AtlasKey, AtlasStats, AtlasTextureId, AtlasTextureKind, AtlasTile, DevicePixels, Size,
+};
+use collections::FxHashMap;
+
+/// How many frames pass between looking for tiles to evict.
+const EVICTION_INTERVAL: u64 = 60;
+
+/// How many frames a tile can go without being drawn before it's evicted.
+const MAX_TILE_AGE: u64 = 3600;
+
+/// How many frames a tile can go without being drawn before it's evicted from a mostly empty
+/// texture, so that the texture empties out and can be released.
+const MAX_SPARSE_TILE_AGE: u64 = 120;
+
+/// The fraction of a texture's area that tiles cover, below which the texture is mostly empty.
+/// A kind's only texture is never considered so.
+const SPARSE_TEXTURE_COVERAGE: f32 = 0.25;
+
+/// The kinds of textures whose tiles are tracked, and which are released when they're empty.
+/// Path textures are cleared every frame instead.
+pub(crate) const LRU_TEXTURE_KINDS: [AtlasTextureKind; 2] =
+    [AtlasTextureKind::Monochrome, AtlasTextureKind::Polychrome];
+
+/// Tracks the last frame each glyph, SVG and image in an atlas was drawn in, to find the ones that
+/// can be evicted.
+#[derive(Default)]
+pub(crate) struct AtlasLru {
+    frame: u64,
+    tiles: FxHashMap<(AtlasTextureId, u32), LruTile>,
+    evicted_tile_count: usize,
+}
+
+struct LruTile {
+    key: AtlasKey,
+    area: u64,
+    last_drawn: u64,
+}
+
+impl AtlasLru {
+    pub fn insert(&mut self, key: &AtlasKey, tile: &AtlasTile) {
+        self.tiles.insert(
+            (tile.texture_id, tile.tile_id.0),
+            LruTile {
+                key: key.clone(),
+                area: tile_area(tile.bounds.size),
+                last_drawn: self.frame,
+            },
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+    }
+
+    /// Whether any tile is allocated from the given texture.
+    pub fn has_tiles(&self, texture_id: AtlasTextureId) -> bool {
+        self.tiles
+            .keys()
+            .any(|(tile_texture_id, _)| *tile_texture_id == texture_id)
+    }
+
+    /// Finishes a frame that drew the given tiles, returning the keys of the tiles to evict every
+    /// [`EVICTION_INTERVAL`] frames. Those are the tiles that haven't been drawn for
+    /// [`MAX_TILE_AGE`] frames, and the ones on mostly empty textures that haven't been drawn for
+    /// [`MAX_SPARSE_TILE_AGE`].
+    pub fn finish_frame<'a>(
+        &mut self,
+        drawn_tiles: &mut dyn Iterator<Item = &'a AtlasTile>,
+        texture_sizes: impl FnOnce() -> Vec<(AtlasTextureId, Size<DevicePixels>)>,
+    ) -> Vec<AtlasKey> {
+        for tile in drawn_tiles {
+            if let Some(lru_tile) = self.tiles.get_mut(&(tile.texture_id, tile.tile_id.0)) {
+                lru_tile.last_drawn = self.frame;
+            }
+        }
+        self.frame += 1;
+        if self.frame % EVICTION_INTERVAL != 0 {
+            return Vec::new();
+        }
+
+        let texture_sizes = texture_sizes();
+        let mut covered_areas = FxHashMap::<AtlasTextureId, u64>::default();
+        for ((texture_id, _), tile) in &self.tiles {
+            *covered_areas.entry(*texture_id).or_default() += tile.area;
+        }
+        let is_sparse = |texture_id: AtlasTextureId| {
+            let kind_texture_count = texture_sizes
+                .iter()
+                .filter(|(id, _)| id.kind == texture_id.kind)
+                .count();
+            let Some((_, size)) = texture_sizes.iter().find(|(id, _)| *id == texture_id) else {
+                return false;
+            };
+            let covered_area = covered_areas.get(&texture_id).copied().unwrap_or_default();
+            kind_texture_count > 1
+                && (covered_area as f32) < tile_area(*size) as f32 * SPARSE_TEXTURE_COVERAGE
+        };
+
+        let frame = self.frame;
+        let mut evicted = Vec::new();
+        self.tiles.retain(|(texture_id, _), tile| {
+            let age = frame - tile.last_drawn;
+            let evict = age > MAX_TILE_AGE || (age > MAX_SPARSE_TILE_AGE && is_sparse(*texture_id));
+            if evict {
+                evicted.push(tile.key.clone());
+            }
+            !evict
+        });
+        self.evicted_tile_count += evicted.len();
+        evicted
+    }
+
+    /// The stats of the tiles in the atlas, leaving those of its textures for the atlas to fill in.
+    pub fn stats(&self) -> AtlasStats {
+        AtlasStats {
+            tile_count: self.tiles.len(),
+            tile_area: self.tiles.values().map(|tile| tile.area).sum(),
+            evicted_tile_count: self.evicted_tile_count,
+            ..Default::default()
+        }
+    }
+}
+
+fn tile_area(size: Size<DevicePixels>) -> u64 {
+    size.width.0 as u64 * size.height.0 as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{size, Bounds, RenderSvgParams, TileId};
+
+    fn key(ix: usize) -> AtlasKey {
+        AtlasKey::Svg(RenderSvgParams {
+            path: format!("icon-{ix}.svg").into(),
+            size: size(DevicePixels(16), DevicePixels(16)),
+        })
+    }
+
+    fn texture_id(index: u32) -> AtlasTextureId {
+        AtlasTextureId {
+            index,
+            kind: AtlasTextureKind::Monochrome,
+        }
+    }
+
+    fn tile(texture_index: u32, tile_id: u32, side: i32) -> AtlasTile {
+        AtlasTile {
+            texture_id: texture_id(texture_index),
+            tile_id: TileId(tile_id),
+            padding: 0,
+            bounds: Bounds {
+                origin: Default::default(),
+                size: size(DevicePixels(side), DevicePixels(side)),
+            },
+        }
+    }
+
+    #[test]
+    fn test_evicting_tiles() {
+        let texture_size = size(DevicePixels(100), DevicePixels(100));
+        let texture_sizes = || vec![(texture_id(0), texture_size), (texture_id(1), texture_size)];
+
+        // The first texture is mostly covered, while the second one has a tile in a corner.
+        let mut lru = AtlasLru::default();
+        let drawn = tile(0, 1, 90);
+        let stale = tile(0, 2, 10);
+        let sparse = tile(1, 1, 10);
+        lru.insert(&key(0), &drawn);
+        lru.insert(&key(1), &stale);
+        lru.insert(&key(2), &sparse);
+
+        let mut evicted = Vec::new();
+        for _ in 0..MAX_TILE_AGE + EVICTION_INTERVAL {
+            evicted.extend(lru.finish_frame(&mut [&drawn].into_iter(), texture_sizes));
+            if evicted.len() == 1 {
+                // Tiles on mostly empty textures are evicted sooner.
+                assert!(evicted[0] == key(2));
+            }
+        }
+
+        // Tiles that are drawn every frame are kept.
+        assert_eq!(evicted.len(), 2);
+        assert!(evicted[1] == key(1));
+        assert!(lru.has_tiles(drawn.texture_id));
+        assert!(!lru.has_tiles(sparse.texture_id));
+        let stats = lru.stats();
+        assert_eq!(stats.tile_count, 1);
+        assert_eq!(stats.tile_area, 90 * 90);
+        assert_eq!(stats.evicted_tile_count, 2);
+    }
+}
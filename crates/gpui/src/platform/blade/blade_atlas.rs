@@ -1,6 +1,6 @@
 use crate::{
-    AtlasKey, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, DevicePixels, PlatformAtlas,
-    Point, Size,
+    AtlasKey, AtlasLru, AtlasStats, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds,
+    DevicePixels, GlyphOutline, PlatformAtlas, Point, Size, LRU_TEXTURE_KINDS,
 };
 use anyhow::Result;
 use blade_graphics as gpu;
@@ -25,6 +25,7 @@ struct BladeAtlasState {
     upload_belt: BufferBelt,
     storage: BladeAtlasStorage,
     tiles_by_key: FxHashMap<AtlasKey, AtlasTile>,
+    lru: AtlasLru,
     initializations: Vec<AtlasTextureId>,
     uploads: Vec<PendingUpload>,
 }
@@ -55,6 +56,7 @@ impl BladeAtlas {
             }),
             storage: BladeAtlasStorage::default(),
             tiles_by_key: Default::default(),
+            lru: Default::default(),
             initializations: Vec::new(),
             uploads: Vec::new(),
         }))
@@ -127,9 +129,75 @@ impl PlatformAtlas for BladeAtlas {
             let tile = lock.allocate(size, key.texture_kind());
             lock.upload_texture(tile.texture_id, tile.bounds, &bytes);
             lock.tiles_by_key.insert(key.clone(), tile.clone());
+            lock.lru.insert(key, &tile);
             Ok(Some(tile))
         }
     }
+
+    fn get_or_insert_outline(
+        &self,
+        _key: &AtlasKey,
+        _build: &mut dyn FnMut() -> Result<Option<GlyphOutline>>,
+    ) -> Result<Option<AtlasTile>> {
+        // Glyphs are rasterized on the CPU.
+        Ok(None)
+    }
+
+    fn clear(&self) {
+        let mut lock = self.0.lock();
+        lock.tiles_by_key.clear();
+        lock.lru.clear();
+        for texture_kind in [AtlasTextureKind::Monochrome, AtlasTextureKind::Polychrome] {
+            for texture in &mut lock.storage[texture_kind] {
+                texture.clear();
+            }
+        }
+    }
+
+    fn finish_frame(&self, drawn_tiles: &mut dyn Iterator<Item = &AtlasTile>) {
+        let state = &mut *self.0.lock();
+        let evicted_keys = state.lru.finish_frame(drawn_tiles, || {
+            LRU_TEXTURE_KINDS
+                .into_iter()
+                .flat_map(|kind| &state.storage[kind])
+                .map(|texture| (texture.id, texture.allocator.size().into()))
+                .collect()
+        });
+        if evicted_keys.is_empty() {
+            return;
+        }
+
+        for key in evicted_keys {
+            if let Some(tile) = state.tiles_by_key.remove(&key) {
+                state.storage[tile.texture_id].deallocate(&tile);
+            }
+        }
+        // Textures can only be released from the end, since the others are indexed by the tiles
+        // allocated after them.
+        for kind in LRU_TEXTURE_KINDS {
+            while let Some(texture) = state.storage[kind].last() {
+                if state.lru.has_tiles(texture.id) {
+                    break;
+                }
+                let mut texture = state.storage[kind].pop().unwrap();
+                texture.destroy(&state.gpu);
+            }
+        }
+    }
+
+    fn stats(&self) -> AtlasStats {
+        let lock = self.0.lock();
+        let mut stats = lock.lru.stats();
+        for texture in LRU_TEXTURE_KINDS
+            .into_iter()
+            .flat_map(|kind| &lock.storage[kind])
+        {
+            let size = texture.allocator.size();
+            stats.texture_count += 1;
+            stats.texture_area += size.width as u64 * size.height as u64;
+        }
+        stats
+    }
 }
 
 impl BladeAtlasState {
@@ -296,6 +364,17 @@ impl ops::Index<AtlasTextureId> for BladeAtlasStorage {
     }
 }
 
+impl ops::IndexMut<AtlasTextureId> for BladeAtlasStorage {
+    fn index_mut(&mut self, id: AtlasTextureId) -> &mut Self::Output {
+        let textures = match id.kind {
+            crate::AtlasTextureKind::Monochrome => &mut self.monochrome_textures,
+            crate::AtlasTextureKind::Polychrome => &mut self.polychrome_textures,
+            crate::AtlasTextureKind::Path => &mut self.path_textures,
+        };
+        &mut textures[id.index as usize]
+    }
+}
+
 impl BladeAtlasStorage {
     fn destroy(&mut self, gpu: &gpu::Context) {
         for mut texture in self.monochrome_textures.drain(..) {
@@ -323,6 +402,10 @@ impl BladeAtlasTexture {
         self.allocator.clear();
     }
 
+    fn deallocate(&mut self, tile: &AtlasTile) {
+        self.allocator.deallocate(tile.tile_id.into());
+    }
+
     fn allocate(&mut self, size: Size<DevicePixels>) -> Option<AtlasTile> {
         let allocation = self.allocator.allocate(size.into())?;
         let tile = AtlasTile {
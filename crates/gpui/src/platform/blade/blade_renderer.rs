@@ -3,9 +3,10 @@
 
 use super::{BladeAtlas, PATH_TEXTURE_FORMAT};
 use crate::{
-    AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels, GPUSpecs, Hsla,
+    custom_shader_source, AtlasTextureKind, AtlasTile, BackdropBlur, Background, BlendMode, Bounds,
+    ColorSpace, ContentMask, CustomPrimitive, CustomShaderId, DevicePixels, GPUSpecs,
     MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad,
-    ScaledPixels, Scene, Shadow, Size, Underline,
+    ScaledPixels, Scene, Shadow, Size, SolidQuad, Underline,
 };
 use bytemuck::{Pod, Zeroable};
 use collections::HashMap;
@@ -33,6 +34,7 @@ pub unsafe fn new_renderer(
     native_view: *mut c_void,
     bounds: crate::Size<f32>,
     transparent: bool,
+    color_space: ColorSpace,
 ) -> Renderer {
     use raw_window_handle as rwh;
     struct RawWindow {
@@ -76,6 +78,7 @@ pub unsafe fn new_renderer(
                 depth: 1,
             },
             transparent,
+            color_space,
         },
     )
 }
@@ -85,7 +88,20 @@ pub unsafe fn new_renderer(
 struct GlobalParams {
     viewport_size: [f32; 2],
     premultiplied_alpha: u32,
-    pad: u32,
+    /// Whether colors are converted from sRGB to Display P3.
+    display_p3: u32,
+}
+
+impl GlobalParams {
+    /// The parameters for drawing quads or sprites in the given blend mode, whose pipelines
+    /// expect premultiplied colors for modes other than normal.
+    fn blended(self, blend_mode: BlendMode) -> Self {
+        Self {
+            premultiplied_alpha: (self.premultiplied_alpha != 0 || blend_mode != BlendMode::Normal)
+                as u32,
+            ..self
+        }
+    }
 }
 
 //Note: we can't use `Bounds` directly here because
@@ -119,12 +135,26 @@ struct ShaderQuadsData {
     b_quads: gpu::BufferPiece,
 }
 
+#[derive(blade_macros::ShaderData)]
+struct ShaderSolidQuadsData {
+    globals: GlobalParams,
+    b_solid_quads: gpu::BufferPiece,
+}
+
 #[derive(blade_macros::ShaderData)]
 struct ShaderShadowsData {
     globals: GlobalParams,
     b_shadows: gpu::BufferPiece,
 }
 
+#[derive(blade_macros::ShaderData)]
+struct ShaderBackdropBlursData {
+    globals: GlobalParams,
+    t_backdrop: gpu::TextureView,
+    s_backdrop: gpu::Sampler,
+    b_backdrop_blurs: gpu::BufferPiece,
+}
+
 #[derive(blade_macros::ShaderData)]
 struct ShaderPathRasterizationData {
     globals: GlobalParams,
@@ -170,23 +200,51 @@ struct ShaderSurfacesData {
     s_surface: gpu::Sampler,
 }
 
+#[derive(blade_macros::ShaderData)]
+struct ShaderTextureSurfacesData {
+    globals: GlobalParams,
+    surface_locals: SurfaceParams,
+    t_texture: gpu::TextureView,
+    s_surface: gpu::Sampler,
+}
+
+#[derive(blade_macros::ShaderData)]
+struct ShaderCustomData {
+    globals: GlobalParams,
+    b_custom_primitives: gpu::BufferPiece,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 struct PathSprite {
     bounds: Bounds<ScaledPixels>,
-    color: Hsla,
+    color: Background,
+    /// The bounds of the whole path, which gradients are relative to.
+    path_bounds: Bounds<ScaledPixels>,
     tile: AtlasTile,
+    /// Whether the path's coverage is clamped, rather than alternating between filled and empty.
+    nonzero: u32,
+    pad: u32,
 }
 
 struct BladePipelines {
-    quads: gpu::RenderPipeline,
+    /// Quads and sprites have a pipeline for each [`BlendMode`].
+    quads: [gpu::RenderPipeline; BlendMode::ALL.len()],
+    solid_quads: [gpu::RenderPipeline; BlendMode::ALL.len()],
     shadows: gpu::RenderPipeline,
+    backdrop_blurs: gpu::RenderPipeline,
     path_rasterization: gpu::RenderPipeline,
     paths: gpu::RenderPipeline,
     underlines: gpu::RenderPipeline,
-    mono_sprites: gpu::RenderPipeline,
-    poly_sprites: gpu::RenderPipeline,
+    mono_sprites: [gpu::RenderPipeline; BlendMode::ALL.len()],
+    poly_sprites: [gpu::RenderPipeline; BlendMode::ALL.len()],
     surfaces: gpu::RenderPipeline,
+    texture_surfaces: gpu::RenderPipeline,
+    opacity_groups: gpu::RenderPipeline,
+    /// The pipelines of the custom shaders drawn so far, or `None` for the ones that have no
+    /// WGSL source or failed to compile.
+    custom: HashMap<CustomShaderId, Option<gpu::RenderPipeline>>,
+    color_targets: [gpu::ColorTargetState; 1],
 }
 
 impl BladePipelines {
@@ -203,7 +261,9 @@ impl BladePipelines {
         shader.check_struct_size::<GlobalParams>();
         shader.check_struct_size::<SurfaceParams>();
         shader.check_struct_size::<Quad>();
+        shader.check_struct_size::<SolidQuad>();
         shader.check_struct_size::<Shadow>();
+        shader.check_struct_size::<BackdropBlur>();
         assert_eq!(
             mem::size_of::<PathVertex<ScaledPixels>>(),
             shader.get_struct_size("PathVertex") as usize,
@@ -224,32 +284,98 @@ impl BladePipelines {
             blend: Some(blend_mode),
             write_mask: gpu::ColorWrites::default(),
         }];
+        let custom_color_targets = color_targets.clone();
+        // Fragments are premultiplied for blend modes other than normal.
+        let blended_color_targets = BlendMode::ALL.map(|mode| {
+            let blend = match mode {
+                BlendMode::Normal => blend_mode,
+                BlendMode::Multiply => gpu::BlendState {
+                    color: gpu::BlendComponent {
+                        src_factor: gpu::BlendFactor::Dst,
+                        dst_factor: gpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: gpu::BlendOperation::Add,
+                    },
+                    alpha: gpu::BlendComponent::OVER,
+                },
+                BlendMode::Screen => gpu::BlendState {
+                    color: gpu::BlendComponent {
+                        src_factor: gpu::BlendFactor::One,
+                        dst_factor: gpu::BlendFactor::OneMinusSrc,
+                        operation: gpu::BlendOperation::Add,
+                    },
+                    alpha: gpu::BlendComponent::OVER,
+                },
+                BlendMode::PlusLighter => gpu::BlendState {
+                    color: gpu::BlendComponent {
+                        src_factor: gpu::BlendFactor::One,
+                        dst_factor: gpu::BlendFactor::One,
+                        operation: gpu::BlendOperation::Add,
+                    },
+                    alpha: gpu::BlendComponent::OVER,
+                },
+            };
+            [gpu::ColorTargetState {
+                format: surface_info.format,
+                blend: Some(blend),
+                write_mask: gpu::ColorWrites::default(),
+            }]
+        });
 
         Self {
-            quads: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "quads",
-                data_layouts: &[&ShaderQuadsData::layout()],
-                vertex: shader.at("vs_quad"),
+            quads: blended_color_targets.each_ref().map(|color_targets| {
+                gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                    name: "quads",
+                    data_layouts: &[&ShaderQuadsData::layout()],
+                    vertex: shader.at("vs_quad"),
+                    vertex_fetches: &[],
+                    primitive: gpu::PrimitiveState {
+                        topology: gpu::PrimitiveTopology::TriangleStrip,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    fragment: shader.at("fs_quad"),
+                    color_targets,
+                })
+            }),
+            solid_quads: blended_color_targets.each_ref().map(|color_targets| {
+                gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                    name: "solid_quads",
+                    data_layouts: &[&ShaderSolidQuadsData::layout()],
+                    vertex: shader.at("vs_solid_quad"),
+                    vertex_fetches: &[],
+                    primitive: gpu::PrimitiveState {
+                        topology: gpu::PrimitiveTopology::TriangleStrip,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    fragment: shader.at("fs_solid_quad"),
+                    color_targets,
+                })
+            }),
+            shadows: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "shadows",
+                data_layouts: &[&ShaderShadowsData::layout()],
+                vertex: shader.at("vs_shadow"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: shader.at("fs_quad"),
+                fragment: shader.at("fs_shadow"),
                 color_targets,
             }),
-            shadows: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "shadows",
-                data_layouts: &[&ShaderShadowsData::layout()],
-                vertex: shader.at("vs_shadow"),
+            backdrop_blurs: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "backdrop_blurs",
+                data_layouts: &[&ShaderBackdropBlursData::layout()],
+                vertex: shader.at("vs_backdrop_blur"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: shader.at("fs_shadow"),
+                fragment: shader.at("fs_backdrop_blur"),
                 color_targets,
             }),
             path_rasterization: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
@@ -295,69 +421,165 @@ impl BladePipelines {
                 fragment: shader.at("fs_underline"),
                 color_targets,
             }),
-            mono_sprites: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "mono-sprites",
-                data_layouts: &[&ShaderMonoSpritesData::layout()],
-                vertex: shader.at("vs_mono_sprite"),
+            mono_sprites: blended_color_targets.each_ref().map(|color_targets| {
+                gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                    name: "mono-sprites",
+                    data_layouts: &[&ShaderMonoSpritesData::layout()],
+                    vertex: shader.at("vs_mono_sprite"),
+                    vertex_fetches: &[],
+                    primitive: gpu::PrimitiveState {
+                        topology: gpu::PrimitiveTopology::TriangleStrip,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    fragment: shader.at("fs_mono_sprite"),
+                    color_targets,
+                })
+            }),
+            poly_sprites: blended_color_targets.each_ref().map(|color_targets| {
+                gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                    name: "poly-sprites",
+                    data_layouts: &[&ShaderPolySpritesData::layout()],
+                    vertex: shader.at("vs_poly_sprite"),
+                    vertex_fetches: &[],
+                    primitive: gpu::PrimitiveState {
+                        topology: gpu::PrimitiveTopology::TriangleStrip,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    fragment: shader.at("fs_poly_sprite"),
+                    color_targets,
+                })
+            }),
+            surfaces: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "surfaces",
+                data_layouts: &[&ShaderSurfacesData::layout()],
+                vertex: shader.at("vs_surface"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: shader.at("fs_mono_sprite"),
+                fragment: shader.at("fs_surface"),
                 color_targets,
             }),
-            poly_sprites: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "poly-sprites",
-                data_layouts: &[&ShaderPolySpritesData::layout()],
-                vertex: shader.at("vs_poly_sprite"),
+            texture_surfaces: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "texture-surfaces",
+                data_layouts: &[&ShaderTextureSurfacesData::layout()],
+                vertex: shader.at("vs_surface"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: shader.at("fs_poly_sprite"),
+                fragment: shader.at("fs_texture_surface"),
                 color_targets,
             }),
-            surfaces: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
-                name: "surfaces",
-                data_layouts: &[&ShaderSurfacesData::layout()],
-                vertex: shader.at("vs_surface"),
+            opacity_groups: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "opacity-groups",
+                data_layouts: &[&ShaderPolySpritesData::layout()],
+                vertex: shader.at("vs_poly_sprite"),
                 vertex_fetches: &[],
                 primitive: gpu::PrimitiveState {
                     topology: gpu::PrimitiveTopology::TriangleStrip,
                     ..Default::default()
                 },
                 depth_stencil: None,
-                fragment: shader.at("fs_surface"),
+                fragment: shader.at("fs_opacity_group"),
                 color_targets,
             }),
+            custom: HashMap::default(),
+            color_targets: custom_color_targets,
         }
     }
 
+    /// The pipeline drawing primitives with the given custom shader, which is compiled the first
+    /// time it's drawn.
+    fn custom(
+        &mut self,
+        gpu: &gpu::Context,
+        shader_id: CustomShaderId,
+    ) -> Option<&gpu::RenderPipeline> {
+        let color_targets = &self.color_targets;
+        self.custom
+            .entry(shader_id)
+            .or_insert_with(|| {
+                use gpu::ShaderData as _;
+
+                let source = custom_shader_source(shader_id)?;
+                let Some(wgsl) = source.wgsl else {
+                    log::error!("custom shader {:?} has no WGSL source", source.label);
+                    return None;
+                };
+                let wgsl = format!("{}\n{}", include_str!("custom_shader.wgsl"), wgsl);
+                // Blade panics on shaders that don't compile, which shouldn't bring the whole
+                // app down when they're supplied by extensions.
+                let shader = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    gpu.create_shader(gpu::ShaderDesc { source: &wgsl })
+                }))
+                .map_err(|_| log::error!("failed to compile custom shader {:?}", source.label))
+                .ok()?;
+                shader.check_struct_size::<CustomPrimitive>();
+
+                Some(gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                    name: &source.label,
+                    data_layouts: &[&ShaderCustomData::layout()],
+                    vertex: shader.at("vs_custom"),
+                    vertex_fetches: &[],
+                    primitive: gpu::PrimitiveState {
+                        topology: gpu::PrimitiveTopology::TriangleStrip,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    fragment: shader.at("fs_custom"),
+                    color_targets,
+                }))
+            })
+            .as_ref()
+    }
+
     fn destroy(&mut self, gpu: &gpu::Context) {
-        gpu.destroy_render_pipeline(&mut self.quads);
+        for pipeline in self
+            .quads
+            .iter_mut()
+            .chain(&mut self.solid_quads)
+            .chain(&mut self.mono_sprites)
+            .chain(&mut self.poly_sprites)
+        {
+            gpu.destroy_render_pipeline(pipeline);
+        }
         gpu.destroy_render_pipeline(&mut self.shadows);
+        gpu.destroy_render_pipeline(&mut self.backdrop_blurs);
         gpu.destroy_render_pipeline(&mut self.path_rasterization);
         gpu.destroy_render_pipeline(&mut self.paths);
         gpu.destroy_render_pipeline(&mut self.underlines);
-        gpu.destroy_render_pipeline(&mut self.mono_sprites);
-        gpu.destroy_render_pipeline(&mut self.poly_sprites);
         gpu.destroy_render_pipeline(&mut self.surfaces);
+        gpu.destroy_render_pipeline(&mut self.texture_surfaces);
+        gpu.destroy_render_pipeline(&mut self.opacity_groups);
+        for (_, mut pipeline) in self.custom.drain() {
+            if let Some(pipeline) = pipeline.as_mut() {
+                gpu.destroy_render_pipeline(pipeline);
+            }
+        }
     }
 }
 
 pub struct BladeSurfaceConfig {
     pub size: gpu::Extent,
     pub transparent: bool,
+    pub color_space: ColorSpace,
 }
 
 pub struct BladeRenderer {
     gpu: Arc<gpu::Context>,
     surface_config: gpu::SurfaceConfig,
+    surface_format: gpu::TextureFormat,
     alpha_mode: gpu::AlphaMode,
+    /// The color space of the surface. Blade can't tag surfaces, so colors are converted to it
+    /// in the shaders and presented as is.
+    color_space: ColorSpace,
     command_encoder: gpu::CommandEncoder,
     last_sync_point: Option<gpu::SyncPoint>,
     pipelines: BladePipelines,
@@ -365,6 +587,13 @@ pub struct BladeRenderer {
     path_tiles: HashMap<PathId, AtlasTile>,
     atlas: Arc<BladeAtlas>,
     atlas_sampler: gpu::Sampler,
+    /// A copy of the frame, sampled by backdrop blurs. Created the first time it's needed.
+    backdrop: Option<(gpu::Texture, gpu::TextureView)>,
+    /// The textures opacity groups are drawn to before being composited, indexed by group id.
+    opacity_group_targets: Vec<(gpu::Texture, gpu::TextureView)>,
+    /// The textures cached groups are drawn to, indexed by slot. They're kept from frame to
+    /// frame, so that the groups that paint the same as before aren't drawn again.
+    cached_group_targets: Vec<Option<(gpu::Texture, gpu::TextureView)>>,
     #[cfg(target_os = "macos")]
     core_video_texture_cache: CVMetalTextureCache,
 }
@@ -373,7 +602,7 @@ impl BladeRenderer {
     pub fn new(gpu: Arc<gpu::Context>, config: BladeSurfaceConfig) -> Self {
         let surface_config = gpu::SurfaceConfig {
             size: config.size,
-            usage: gpu::TextureUsage::TARGET,
+            usage: gpu::TextureUsage::TARGET | gpu::TextureUsage::COPY,
             display_sync: gpu::DisplaySync::Recent,
             color_space: gpu::ColorSpace::Linear,
             allow_exclusive_full_screen: false,
@@ -408,7 +637,9 @@ impl BladeRenderer {
         Self {
             gpu,
             surface_config,
+            surface_format: surface_info.format,
             alpha_mode: surface_info.alpha,
+            color_space: config.color_space,
             command_encoder,
             last_sync_point: None,
             pipelines,
@@ -416,6 +647,9 @@ impl BladeRenderer {
             path_tiles: HashMap::default(),
             atlas,
             atlas_sampler,
+            backdrop: None,
+            opacity_group_targets: Vec::new(),
+            cached_group_targets: Vec::new(),
             #[cfg(target_os = "macos")]
             core_video_texture_cache,
         }
@@ -441,6 +675,8 @@ impl BladeRenderer {
             self.wait_for_gpu();
             self.surface_config.size = gpu_size;
             self.gpu.resize(self.surface_config);
+            self.destroy_backdrop();
+            self.destroy_opacity_group_targets();
         }
     }
 
@@ -452,6 +688,124 @@ impl BladeRenderer {
             self.pipelines.destroy(&self.gpu);
             self.pipelines = BladePipelines::new(&self.gpu, surface_info);
             self.alpha_mode = surface_info.alpha;
+            if surface_info.format != self.surface_format {
+                self.surface_format = surface_info.format;
+                self.destroy_backdrop();
+                self.destroy_opacity_group_targets();
+            }
+        }
+    }
+
+    fn backdrop_view(&mut self) -> gpu::TextureView {
+        if let Some((_, view)) = self.backdrop {
+            return view;
+        }
+        let texture = self.gpu.create_texture(gpu::TextureDesc {
+            name: "backdrop",
+            format: self.surface_format,
+            size: self.surface_config.size,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            dimension: gpu::TextureDimension::D2,
+            usage: gpu::TextureUsage::COPY | gpu::TextureUsage::RESOURCE,
+        });
+        let view = self.gpu.create_texture_view(
+            texture,
+            gpu::TextureViewDesc {
+                name: "backdrop",
+                format: self.surface_format,
+                dimension: gpu::ViewDimension::D2,
+                subresources: &Default::default(),
+            },
+        );
+        self.command_encoder.init_texture(texture);
+        self.backdrop = Some((texture, view));
+        view
+    }
+
+    fn destroy_backdrop(&mut self) {
+        if let Some((texture, view)) = self.backdrop.take() {
+            self.gpu.destroy_texture_view(view);
+            self.gpu.destroy_texture(texture);
+        }
+    }
+
+    fn create_group_target(&mut self, name: &'static str) -> (gpu::Texture, gpu::TextureView) {
+        let texture = self.gpu.create_texture(gpu::TextureDesc {
+            name,
+            format: self.surface_format,
+            size: self.surface_config.size,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            dimension: gpu::TextureDimension::D2,
+            usage: gpu::TextureUsage::TARGET
+                | gpu::TextureUsage::COPY
+                | gpu::TextureUsage::RESOURCE,
+        });
+        let view = self.gpu.create_texture_view(
+            texture,
+            gpu::TextureViewDesc {
+                name,
+                format: self.surface_format,
+                dimension: gpu::ViewDimension::D2,
+                subresources: &Default::default(),
+            },
+        );
+        self.command_encoder.init_texture(texture);
+        (texture, view)
+    }
+
+    fn opacity_group_target(&mut self, id: usize) -> (gpu::Texture, gpu::TextureView) {
+        while self.opacity_group_targets.len() <= id {
+            let target = self.create_group_target("opacity group");
+            self.opacity_group_targets.push(target);
+        }
+        self.opacity_group_targets[id]
+    }
+
+    /// Returns the target of the given cache slot, and whether it was just created, so has
+    /// nothing drawn to it yet.
+    fn cached_group_target(&mut self, slot: usize) -> ((gpu::Texture, gpu::TextureView), bool) {
+        if self.cached_group_targets.len() <= slot {
+            self.cached_group_targets.resize(slot + 1, None);
+        }
+        if let Some(target) = self.cached_group_targets[slot] {
+            return (target, false);
+        }
+        let target = self.create_group_target("cached group");
+        self.cached_group_targets[slot] = Some(target);
+        (target, true)
+    }
+
+    /// Destroys the targets of the cache slots the scene doesn't use.
+    fn release_cached_group_targets(&mut self, scene: &Scene) {
+        let is_unused = |(slot, target): (usize, &Option<_>)| {
+            target.is_some() && !scene.cached_group_slots.contains(&slot)
+        };
+        if !self.cached_group_targets.iter().enumerate().any(is_unused) {
+            return;
+        }
+        // The previous frame may still be sampling them.
+        self.wait_for_gpu();
+        for (slot, target) in self.cached_group_targets.iter_mut().enumerate() {
+            if scene.cached_group_slots.contains(&slot) {
+                continue;
+            }
+            if let Some((texture, view)) = target.take() {
+                self.gpu.destroy_texture_view(view);
+                self.gpu.destroy_texture(texture);
+            }
+        }
+    }
+
+    fn destroy_opacity_group_targets(&mut self) {
+        for (texture, view) in self.opacity_group_targets.drain(..) {
+            self.gpu.destroy_texture_view(view);
+            self.gpu.destroy_texture(texture);
+        }
+        for (texture, view) in self.cached_group_targets.drain(..).flatten() {
+            self.gpu.destroy_texture_view(view);
+            self.gpu.destroy_texture(texture);
         }
     }
 
@@ -522,7 +876,7 @@ impl BladeRenderer {
             let globals = GlobalParams {
                 viewport_size: [tex_info.size.width as f32, tex_info.size.height as f32],
                 premultiplied_alpha: 0,
-                pad: 0,
+                display_p3: 0,
             };
 
             let vertex_buf = unsafe { self.instance_belt.alloc_typed(&vertices, &self.gpu) };
@@ -551,14 +905,34 @@ impl BladeRenderer {
         self.wait_for_gpu();
         self.atlas.destroy();
         self.gpu.destroy_sampler(self.atlas_sampler);
+        self.destroy_backdrop();
+        self.destroy_opacity_group_targets();
         self.instance_belt.destroy(&self.gpu);
         self.gpu.destroy_command_encoder(&mut self.command_encoder);
         self.pipelines.destroy(&self.gpu);
     }
 
+    fn globals(&self) -> GlobalParams {
+        GlobalParams {
+            viewport_size: [
+                self.surface_config.size.width as f32,
+                self.surface_config.size.height as f32,
+            ],
+            premultiplied_alpha: match self.alpha_mode {
+                gpu::AlphaMode::Ignored | gpu::AlphaMode::PostMultiplied => 0,
+                gpu::AlphaMode::PreMultiplied => 1,
+            },
+            display_p3: self.color_space.is_wide_gamut() as u32,
+        }
+    }
+
     pub fn draw(&mut self, scene: &Scene) {
         self.command_encoder.start();
         self.atlas.before_frame(&mut self.command_encoder);
+
+        let globals = self.globals();
+        self.release_cached_group_targets(scene);
+        self.draw_opacity_groups(scene, globals);
         self.rasterize_paths(scene.paths());
 
         let frame = {
@@ -566,22 +940,139 @@ impl BladeRenderer {
             self.gpu.acquire_frame()
         };
         self.command_encoder.init_texture(frame.texture());
+        self.draw_scene(scene, frame.texture(), frame.texture_view(), globals);
 
-        let globals = GlobalParams {
-            viewport_size: [
-                self.surface_config.size.width as f32,
-                self.surface_config.size.height as f32,
-            ],
-            premultiplied_alpha: match self.alpha_mode {
-                gpu::AlphaMode::Ignored | gpu::AlphaMode::PostMultiplied => 0,
-                gpu::AlphaMode::PreMultiplied => 1,
+        self.command_encoder.present(frame);
+        let sync_point = self.gpu.submit(&mut self.command_encoder);
+
+        profiling::scope!("finish");
+        self.instance_belt.flush(&sync_point);
+        self.atlas.after_frame(&sync_point);
+        self.atlas.clear_textures(AtlasTextureKind::Path);
+
+        self.wait_for_gpu();
+        self.last_sync_point = Some(sync_point);
+    }
+
+    pub fn render_to_image(&mut self, scene: &Scene) -> anyhow::Result<image::RgbaImage> {
+        let is_bgra = match self.surface_format {
+            gpu::TextureFormat::Bgra8Unorm | gpu::TextureFormat::Bgra8UnormSrgb => true,
+            gpu::TextureFormat::Rgba8Unorm | gpu::TextureFormat::Rgba8UnormSrgb => false,
+            format => anyhow::bail!("can't read back frames in the {format:?} format"),
+        };
+        let size = self.surface_config.size;
+        let bytes_per_row = size.width * 4;
+
+        let texture = self.gpu.create_texture(gpu::TextureDesc {
+            name: "capture",
+            format: self.surface_format,
+            size,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            dimension: gpu::TextureDimension::D2,
+            usage: gpu::TextureUsage::TARGET | gpu::TextureUsage::COPY,
+        });
+        let view = self.gpu.create_texture_view(
+            texture,
+            gpu::TextureViewDesc {
+                name: "capture",
+                format: self.surface_format,
+                dimension: gpu::ViewDimension::D2,
+                subresources: &Default::default(),
             },
-            pad: 0,
+        );
+        let buffer = self.gpu.create_buffer(gpu::BufferDesc {
+            name: "capture",
+            size: bytes_per_row as u64 * size.height as u64,
+            memory: gpu::Memory::Shared,
+        });
+
+        self.command_encoder.start();
+        self.atlas.before_frame(&mut self.command_encoder);
+        let globals = self.globals();
+        self.release_cached_group_targets(scene);
+        self.draw_opacity_groups(scene, globals);
+        self.rasterize_paths(scene.paths());
+        self.command_encoder.init_texture(texture);
+        self.draw_scene(scene, texture, view, globals);
+        self.command_encoder.transfer().copy_texture_to_buffer(
+            gpu::TexturePiece {
+                texture,
+                mip_level: 0,
+                array_layer: 0,
+                origin: [0, 0, 0],
+            },
+            buffer.into(),
+            bytes_per_row,
+            size,
+        );
+        let sync_point = self.gpu.submit(&mut self.command_encoder);
+        self.instance_belt.flush(&sync_point);
+        self.atlas.after_frame(&sync_point);
+        self.atlas.clear_textures(AtlasTextureKind::Path);
+        self.last_sync_point = Some(sync_point);
+        self.wait_for_gpu();
+
+        let mut pixels = unsafe {
+            std::slice::from_raw_parts(buffer.data(), bytes_per_row as usize * size.height as usize)
+        }
+        .to_vec();
+        self.gpu.destroy_buffer(buffer);
+        self.gpu.destroy_texture_view(view);
+        self.gpu.destroy_texture(texture);
+
+        // The compositor ignores the alpha of opaque windows, which may not have been written.
+        let is_opaque = matches!(self.alpha_mode, gpu::AlphaMode::Ignored);
+        for pixel in pixels.chunks_exact_mut(4) {
+            if is_bgra {
+                pixel.swap(0, 2);
+            }
+            if is_opaque {
+                pixel[3] = u8::MAX;
+            }
+        }
+        image::RgbaImage::from_raw(size.width, size.height, pixels)
+            .ok_or_else(|| anyhow::anyhow!("the frame doesn't match its size"))
+    }
+
+    /// Draws the contents of every opacity group in the scene to its own texture, innermost
+    /// groups first so that they can be composited into the groups containing them. Cached
+    /// groups that are unchanged since they were last drawn are skipped, along with the groups
+    /// inside them.
+    fn draw_opacity_groups(&mut self, scene: &Scene, globals: GlobalParams) {
+        for group in &scene.opacity_groups {
+            let (texture, view) = if let Some(cache) = group.cache {
+                let (target, is_new) = self.cached_group_target(cache.slot);
+                if cache.unchanged && !is_new {
+                    continue;
+                }
+                target
+            } else {
+                self.opacity_group_target(group.id)
+            };
+            self.draw_opacity_groups(&group.scene, globals);
+            self.rasterize_paths(group.scene.paths());
+            self.draw_scene(&group.scene, texture, view, globals);
+        }
+    }
+
+    /// Draws the scene into the target, whose paths must have been rasterized already.
+    fn draw_scene(
+        &mut self,
+        scene: &Scene,
+        target: gpu::Texture,
+        target_view: gpu::TextureView,
+        globals: GlobalParams,
+    ) {
+        let backdrop_view = if scene.backdrop_blurs.is_empty() {
+            None
+        } else {
+            Some(self.backdrop_view())
         };
 
         if let mut pass = self.command_encoder.render(gpu::RenderTargetSet {
             colors: &[gpu::RenderTarget {
-                view: frame.texture_view(),
+                view: target_view,
                 init_op: gpu::InitOp::Clear(gpu::TextureColor::TransparentBlack),
                 finish_op: gpu::FinishOp::Store,
             }],
@@ -593,16 +1084,33 @@ impl BladeRenderer {
                     PrimitiveBatch::Quads(quads) => {
                         let instance_buf =
                             unsafe { self.instance_belt.alloc_typed(quads, &self.gpu) };
-                        let mut encoder = pass.with(&self.pipelines.quads);
+                        let blend_mode = quads[0].blend_mode;
+                        let mut encoder = pass.with(&self.pipelines.quads[blend_mode as usize]);
                         encoder.bind(
                             0,
                             &ShaderQuadsData {
-                                globals,
+                                globals: globals.blended(blend_mode),
                                 b_quads: instance_buf,
                             },
                         );
                         encoder.draw(0, 4, 0, quads.len() as u32);
                     }
+                    PrimitiveBatch::SolidQuads(quads) => {
+                        let solid_quads = quads.iter().map(SolidQuad::from).collect::<Vec<_>>();
+                        let instance_buf =
+                            unsafe { self.instance_belt.alloc_typed(&solid_quads, &self.gpu) };
+                        let blend_mode = quads[0].blend_mode;
+                        let mut encoder =
+                            pass.with(&self.pipelines.solid_quads[blend_mode as usize]);
+                        encoder.bind(
+                            0,
+                            &ShaderSolidQuadsData {
+                                globals: globals.blended(blend_mode),
+                                b_solid_quads: instance_buf,
+                            },
+                        );
+                        encoder.draw(0, 4, 0, solid_quads.len() as u32);
+                    }
                     PrimitiveBatch::Shadows(shadows) => {
                         let instance_buf =
                             unsafe { self.instance_belt.alloc_typed(shadows, &self.gpu) };
@@ -616,6 +1124,53 @@ impl BladeRenderer {
                         );
                         encoder.draw(0, 4, 0, shadows.len() as u32);
                     }
+                    PrimitiveBatch::BackdropBlurs(blurs) => {
+                        let (Some((backdrop, _)), Some(backdrop_view)) =
+                            (self.backdrop, backdrop_view)
+                        else {
+                            continue;
+                        };
+                        // Blurs sample what has been drawn so far, which can only be read
+                        // outside of the render pass drawing into the target.
+                        drop(pass);
+                        self.command_encoder.transfer().copy_texture_to_texture(
+                            gpu::TexturePiece {
+                                texture: target,
+                                mip_level: 0,
+                                array_layer: 0,
+                                origin: [0, 0, 0],
+                            },
+                            gpu::TexturePiece {
+                                texture: backdrop,
+                                mip_level: 0,
+                                array_layer: 0,
+                                origin: [0, 0, 0],
+                            },
+                            self.surface_config.size,
+                        );
+                        pass = self.command_encoder.render(gpu::RenderTargetSet {
+                            colors: &[gpu::RenderTarget {
+                                view: target_view,
+                                init_op: gpu::InitOp::Load,
+                                finish_op: gpu::FinishOp::Store,
+                            }],
+                            depth_stencil: None,
+                        });
+
+                        let instance_buf =
+                            unsafe { self.instance_belt.alloc_typed(blurs, &self.gpu) };
+                        let mut encoder = pass.with(&self.pipelines.backdrop_blurs);
+                        encoder.bind(
+                            0,
+                            &ShaderBackdropBlursData {
+                                globals,
+                                t_backdrop: backdrop_view,
+                                s_backdrop: self.atlas_sampler,
+                                b_backdrop_blurs: instance_buf,
+                            },
+                        );
+                        encoder.draw(0, 4, 0, blurs.len() as u32);
+                    }
                     PrimitiveBatch::Paths(paths) => {
                         let mut encoder = pass.with(&self.pipelines.paths);
                         // todo(linux): group by texture ID
@@ -629,7 +1184,10 @@ impl BladeRenderer {
                                     size: tile.bounds.size.map(Into::into),
                                 },
                                 color: path.color,
+                                path_bounds: path.bounds,
                                 tile: (*tile).clone(),
+                                nonzero: path.nonzero as u32,
+                                pad: 0,
                             }];
 
                             let instance_buf =
@@ -659,47 +1217,101 @@ impl BladeRenderer {
                         );
                         encoder.draw(0, 4, 0, underlines.len() as u32);
                     }
-                    PrimitiveBatch::MonochromeSprites {
-                        texture_id,
-                        sprites,
-                    } => {
-                        let tex_info = self.atlas.get_texture_info(texture_id);
-                        let instance_buf =
-                            unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
-                        let mut encoder = pass.with(&self.pipelines.mono_sprites);
-                        encoder.bind(
-                            0,
-                            &ShaderMonoSpritesData {
-                                globals,
-                                t_sprite: tex_info.raw_view,
-                                s_sprite: self.atlas_sampler,
-                                b_mono_sprites: instance_buf,
-                            },
-                        );
-                        encoder.draw(0, 4, 0, sprites.len() as u32);
+                    PrimitiveBatch::MonochromeSprites { sprites, .. } => {
+                        // Unlike the Metal shaders, ours sample a single atlas texture, so
+                        // batches spanning several atlas textures are split into one draw per
+                        // texture. Merging them needs texture binding arrays in shaders.wgsl.
+                        for sprites in
+                            sprites.chunk_by(|a, b| a.tile.texture_id == b.tile.texture_id)
+                        {
+                            let tex_info = self.atlas.get_texture_info(sprites[0].tile.texture_id);
+                            let instance_buf =
+                                unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
+                            let blend_mode = sprites[0].blend_mode;
+                            let mut encoder =
+                                pass.with(&self.pipelines.mono_sprites[blend_mode as usize]);
+                            encoder.bind(
+                                0,
+                                &ShaderMonoSpritesData {
+                                    globals: globals.blended(blend_mode),
+                                    t_sprite: tex_info.raw_view,
+                                    s_sprite: self.atlas_sampler,
+                                    b_mono_sprites: instance_buf,
+                                },
+                            );
+                            encoder.draw(0, 4, 0, sprites.len() as u32);
+                        }
                     }
-                    PrimitiveBatch::PolychromeSprites {
-                        texture_id,
-                        sprites,
+                    PrimitiveBatch::PolychromeSprites { sprites, .. } => {
+                        for sprites in
+                            sprites.chunk_by(|a, b| a.tile.texture_id == b.tile.texture_id)
+                        {
+                            let tex_info = self.atlas.get_texture_info(sprites[0].tile.texture_id);
+                            let instance_buf =
+                                unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
+                            let blend_mode = sprites[0].blend_mode;
+                            let mut encoder =
+                                pass.with(&self.pipelines.poly_sprites[blend_mode as usize]);
+                            encoder.bind(
+                                0,
+                                &ShaderPolySpritesData {
+                                    globals: globals.blended(blend_mode),
+                                    t_sprite: tex_info.raw_view,
+                                    s_sprite: self.atlas_sampler,
+                                    b_poly_sprites: instance_buf,
+                                },
+                            );
+                            encoder.draw(0, 4, 0, sprites.len() as u32);
+                        }
+                    }
+                    PrimitiveBatch::Custom {
+                        shader_id,
+                        primitives,
                     } => {
-                        let tex_info = self.atlas.get_texture_info(texture_id);
+                        let Some(pipeline) = self.pipelines.custom(&self.gpu, shader_id) else {
+                            continue;
+                        };
                         let instance_buf =
-                            unsafe { self.instance_belt.alloc_typed(sprites, &self.gpu) };
-                        let mut encoder = pass.with(&self.pipelines.poly_sprites);
+                            unsafe { self.instance_belt.alloc_typed(primitives, &self.gpu) };
+                        let mut encoder = pass.with(pipeline);
                         encoder.bind(
                             0,
-                            &ShaderPolySpritesData {
+                            &ShaderCustomData {
                                 globals,
-                                t_sprite: tex_info.raw_view,
-                                s_sprite: self.atlas_sampler,
-                                b_poly_sprites: instance_buf,
+                                b_custom_primitives: instance_buf,
                             },
                         );
-                        encoder.draw(0, 4, 0, sprites.len() as u32);
+                        encoder.draw(0, 4, 0, primitives.len() as u32);
+                    }
+                    PrimitiveBatch::OpacityGroups(groups) => {
+                        let mut encoder = pass.with(&self.pipelines.opacity_groups);
+                        for group in groups {
+                            let target = match group.cache {
+                                Some(cache) => self
+                                    .cached_group_targets
+                                    .get(cache.slot)
+                                    .and_then(Option::as_ref),
+                                None => self.opacity_group_targets.get(group.id),
+                            };
+                            let Some(&(_, view)) = target else {
+                                continue;
+                            };
+                            let sprites = [group.sprite()];
+                            let instance_buf =
+                                unsafe { self.instance_belt.alloc_typed(&sprites, &self.gpu) };
+                            encoder.bind(
+                                0,
+                                &ShaderPolySpritesData {
+                                    globals,
+                                    t_sprite: view,
+                                    s_sprite: self.atlas_sampler,
+                                    b_poly_sprites: instance_buf,
+                                },
+                            );
+                            encoder.draw(0, 4, 0, sprites.len() as u32);
+                        }
                     }
                     PrimitiveBatch::Surfaces(surfaces) => {
-                        let mut _encoder = pass.with(&self.pipelines.surfaces);
-
                         for surface in surfaces {
                             #[cfg(not(target_os = "macos"))]
                             {
@@ -709,80 +1321,90 @@ impl BladeRenderer {
 
                             #[cfg(target_os = "macos")]
                             {
-                                let (t_y, t_cb_cr) = {
-                                    use core_foundation::base::TCFType as _;
-                                    use std::ptr;
-
-                                    assert_eq!(
-                                    surface.image_buffer.pixel_format_type(),
-                                    media::core_video::kCVPixelFormatType_420YpCbCr8BiPlanarFullRange
-                                );
-
-                                    let y_texture = unsafe {
-                                        self.core_video_texture_cache
-                                            .create_texture_from_image(
-                                                surface.image_buffer.as_concrete_TypeRef(),
-                                                ptr::null(),
-                                                metal::MTLPixelFormat::R8Unorm,
-                                                surface.image_buffer.plane_width(0),
-                                                surface.image_buffer.plane_height(0),
-                                                0,
-                                            )
-                                            .unwrap()
-                                    };
-                                    let cb_cr_texture = unsafe {
-                                        self.core_video_texture_cache
-                                            .create_texture_from_image(
-                                                surface.image_buffer.as_concrete_TypeRef(),
-                                                ptr::null(),
-                                                metal::MTLPixelFormat::RG8Unorm,
-                                                surface.image_buffer.plane_width(1),
-                                                surface.image_buffer.plane_height(1),
-                                                1,
-                                            )
-                                            .unwrap()
-                                    };
-                                    (
-                                        gpu::TextureView::from_metal_texture(
-                                            y_texture.as_texture_ref(),
-                                        ),
-                                        gpu::TextureView::from_metal_texture(
-                                            cb_cr_texture.as_texture_ref(),
-                                        ),
-                                    )
+                                let surface_locals = SurfaceParams {
+                                    bounds: surface.bounds.into(),
+                                    content_mask: surface.content_mask.bounds.into(),
                                 };
+                                match &surface.source {
+                                    crate::SurfaceSource::Surface(image_buffer) => {
+                                        let (t_y, t_cb_cr) = {
+                                            use core_foundation::base::TCFType as _;
+                                            use std::ptr;
+
+                                            assert_eq!(
+                                                image_buffer.pixel_format_type(),
+                                                media::core_video::kCVPixelFormatType_420YpCbCr8BiPlanarFullRange
+                                            );
+
+                                            let y_texture = unsafe {
+                                                self.core_video_texture_cache
+                                                    .create_texture_from_image(
+                                                        image_buffer.as_concrete_TypeRef(),
+                                                        ptr::null(),
+                                                        metal::MTLPixelFormat::R8Unorm,
+                                                        image_buffer.plane_width(0),
+                                                        image_buffer.plane_height(0),
+                                                        0,
+                                                    )
+                                                    .unwrap()
+                                            };
+                                            let cb_cr_texture = unsafe {
+                                                self.core_video_texture_cache
+                                                    .create_texture_from_image(
+                                                        image_buffer.as_concrete_TypeRef(),
+                                                        ptr::null(),
+                                                        metal::MTLPixelFormat::RG8Unorm,
+                                                        image_buffer.plane_width(1),
+                                                        image_buffer.plane_height(1),
+                                                        1,
+                                                    )
+                                                    .unwrap()
+                                            };
+                                            (
+                                                gpu::TextureView::from_metal_texture(
+                                                    y_texture.as_texture_ref(),
+                                                ),
+                                                gpu::TextureView::from_metal_texture(
+                                                    cb_cr_texture.as_texture_ref(),
+                                                ),
+                                            )
+                                        };
 
-                                _encoder.bind(
-                                    0,
-                                    &ShaderSurfacesData {
-                                        globals,
-                                        surface_locals: SurfaceParams {
-                                            bounds: surface.bounds.into(),
-                                            content_mask: surface.content_mask.bounds.into(),
-                                        },
-                                        t_y,
-                                        t_cb_cr,
-                                        s_surface: self.atlas_sampler,
-                                    },
-                                );
-
-                                _encoder.draw(0, 4, 0, 1);
+                                        let mut encoder = pass.with(&self.pipelines.surfaces);
+                                        encoder.bind(
+                                            0,
+                                            &ShaderSurfacesData {
+                                                globals,
+                                                surface_locals,
+                                                t_y,
+                                                t_cb_cr,
+                                                s_surface: self.atlas_sampler,
+                                            },
+                                        );
+                                        encoder.draw(0, 4, 0, 1);
+                                    }
+                                    crate::SurfaceSource::Texture(texture) => {
+                                        let mut encoder =
+                                            pass.with(&self.pipelines.texture_surfaces);
+                                        encoder.bind(
+                                            0,
+                                            &ShaderTextureSurfacesData {
+                                                globals,
+                                                surface_locals,
+                                                t_texture: gpu::TextureView::from_metal_texture(
+                                                    texture.metal_texture(),
+                                                ),
+                                                s_surface: self.atlas_sampler,
+                                            },
+                                        );
+                                        encoder.draw(0, 4, 0, 1);
+                                    }
+                                }
                             }
                         }
                     }
                 }
             }
         }
-
-        self.command_encoder.present(frame);
-        let sync_point = self.gpu.submit(&mut self.command_encoder);
-
-        profiling::scope!("finish");
-        self.instance_belt.flush(&sync_point);
-        self.atlas.after_frame(&sync_point);
-        self.atlas.clear_textures(AtlasTextureKind::Path);
-
-        self.wait_for_gpu();
-        self.last_sync_point = Some(sync_point);
     }
 }
@@ -0,0 +1,73 @@
+// Wraps the `custom_shader_main` function of a custom shader, whose source is appended to this
+// file, in a pipeline drawing `CustomPrimitive`s.
+
+struct GlobalParams {
+    viewport_size: vec2<f32>,
+    premultiplied_alpha: u32,
+    pad: u32,
+}
+
+var<uniform> globals: GlobalParams;
+
+struct Bounds {
+    origin: vec2<f32>,
+    size: vec2<f32>,
+}
+
+struct CustomPrimitive {
+    order: u32,
+    shader_id: u32,
+    bounds: Bounds,
+    content_mask: Bounds,
+    uniforms: array<f32, 16>,
+}
+var<storage, read> b_custom_primitives: array<CustomPrimitive>;
+
+struct CustomShaderInput {
+    position: vec2<f32>,
+    uv: vec2<f32>,
+    size: vec2<f32>,
+    uniforms: array<f32, 16>,
+}
+
+struct CustomVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) instance_id: u32,
+    @location(1) clip_distances: vec4<f32>,
+}
+
+@vertex
+fn vs_custom(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> CustomVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let primitive = b_custom_primitives[instance_id];
+    let position = unit_vertex * primitive.bounds.size + primitive.bounds.origin;
+    let device_position = position / globals.viewport_size * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0);
+    let clip_origin = primitive.content_mask.origin;
+    let clip_end = clip_origin + primitive.content_mask.size;
+
+    var out = CustomVarying();
+    out.position = vec4<f32>(device_position, 0.0, 1.0);
+    out.instance_id = instance_id;
+    out.clip_distances = vec4<f32>(position - clip_origin, clip_end - position);
+    return out;
+}
+
+@fragment
+fn fs_custom(input: CustomVarying) -> @location(0) vec4<f32> {
+    // Alpha clip first, since we don't have `clip_distance`.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let primitive = b_custom_primitives[input.instance_id];
+    var shader_input = CustomShaderInput();
+    shader_input.position = input.position.xy;
+    shader_input.uv = (input.position.xy - primitive.bounds.origin) / primitive.bounds.size;
+    shader_input.size = primitive.bounds.size;
+    shader_input.uniforms = primitive.uniforms;
+
+    let color = custom_shader_main(shader_input);
+    let multiplier = select(1.0, color.a, globals.premultiplied_alpha != 0u);
+    return vec4<f32>(color.rgb * multiplier, color.a);
+}
+
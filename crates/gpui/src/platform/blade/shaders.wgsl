@@ -1,7 +1,7 @@
 struct GlobalParams {
     viewport_size: vec2<f32>,
     premultiplied_alpha: u32,
-    pad: u32,
+    display_p3: u32,
 }
 
 var<uniform> globals: GlobalParams;
@@ -10,6 +10,12 @@ var s_sprite: sampler;
 
 const M_PI_F: f32 = 3.1415926;
 const GRAYSCALE_FACTORS: vec3<f32> = vec3<f32>(0.2126, 0.7152, 0.0722);
+// Maps linear sRGB to linear Display P3, given column by column.
+const SRGB_TO_DISPLAY_P3: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(0.8224621, 0.0331941, 0.0170827),
+    vec3<f32>(0.1775380, 0.9668058, 0.0723974),
+    vec3<f32>(0.0, 0.0, 0.9105199),
+);
 
 struct Bounds {
     origin: vec2<f32>,
@@ -33,6 +39,21 @@ struct Hsla {
     l: f32,
     a: f32,
 }
+struct LinearColorStop {
+    color: Hsla,
+    percentage: f32,
+}
+struct Background {
+    // 0u is a solid color, 1u a linear gradient, 2u a radial gradient and 3u a conic gradient.
+    tag: u32,
+    stop_count: u32,
+    angle: f32,
+    center_x: f32,
+    center_y: f32,
+    pad: u32,
+    solid: Hsla,
+    stops: array<LinearColorStop, 4>,
+}
 
 struct AtlasTextureId {
     index: u32,
@@ -72,6 +93,12 @@ fn to_device_position_transformed(unit_vertex: vec2<f32>, bounds: Bounds, transf
     return to_device_position_impl(transformed);
 }
 
+// The position of a vertex before the primitive's transformation is applied,
+// which fragments use to shade in the primitive's own coordinate space.
+fn to_local_position(unit_vertex: vec2<f32>, bounds: Bounds) -> vec2<f32> {
+    return unit_vertex * vec2<f32>(bounds.size) + bounds.origin;
+}
+
 fn to_tile_position(unit_vertex: vec2<f32>, tile: AtlasTile) -> vec2<f32> {
   let atlas_size = vec2<f32>(textureDimensions(t_sprite, 0));
   return (vec2<f32>(tile.bounds.origin) + unit_vertex * vec2<f32>(tile.bounds.size)) / atlas_size;
@@ -89,14 +116,29 @@ fn distance_from_clip_rect(unit_vertex: vec2<f32>, bounds: Bounds, clip_bounds:
 }
 
 // https://gamedev.stackexchange.com/questions/92015/optimized-linear-to-srgb-glsl
+// Components of extended sRGB colors below 0 are mirrored around it.
 fn srgb_to_linear(srgb: vec3<f32>) -> vec3<f32> {
-    let cutoff = srgb < vec3<f32>(0.04045);
-    let higher = pow((srgb + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
-    let lower = srgb / vec3<f32>(12.92);
-    return select(higher, lower, cutoff);
+    let magnitude = abs(srgb);
+    let cutoff = magnitude < vec3<f32>(0.04045);
+    let higher = pow((magnitude + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
+    let lower = magnitude / vec3<f32>(12.92);
+    return sign(srgb) * select(higher, lower, cutoff);
+}
+
+// Maps a linear sRGB color to the color space of the surface.
+fn to_surface_color_space(color: vec3<f32>) -> vec3<f32> {
+    if (globals.display_p3 != 0u) {
+        return SRGB_TO_DISPLAY_P3 * color;
+    }
+    return color;
 }
 
 fn hsla_to_rgba(hsla: Hsla) -> vec4<f32> {
+    let color = hsla_to_linear_srgb(hsla);
+    return vec4<f32>(to_surface_color_space(color.rgb), color.a);
+}
+
+fn hsla_to_linear_srgb(hsla: Hsla) -> vec4<f32> {
     let h = hsla.h * 6.0; // Now, it's an angle but scaled in [0, 6) range
     let s = hsla.s;
     let l = hsla.l;
@@ -189,8 +231,99 @@ fn quad_sdf(point: vec2<f32>, bounds: Bounds, corner_radii: Corners) -> f32 {
         corner_radius;
 }
 
+// https://bottosson.github.io/posts/oklab/
+fn linear_srgb_to_oklab(color: vec3<f32>) -> vec3<f32> {
+    let lms = vec3<f32>(
+        0.4122214708 * color.r + 0.5363325363 * color.g + 0.0514459929 * color.b,
+        0.2119034982 * color.r + 0.6806995451 * color.g + 0.1073969566 * color.b,
+        0.0883024619 * color.r + 0.2817188376 * color.g + 0.6299787005 * color.b,
+    );
+    let lms_ = pow(max(lms, vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        0.2104542553 * lms_.x + 0.7936177850 * lms_.y - 0.0040720468 * lms_.z,
+        1.9779984951 * lms_.x - 2.4285922050 * lms_.y + 0.4505937099 * lms_.z,
+        0.0259040371 * lms_.x + 0.7827717662 * lms_.y - 0.8086757660 * lms_.z,
+    );
+}
+
+fn oklab_to_linear_srgb(lab: vec3<f32>) -> vec3<f32> {
+    let lms_ = vec3<f32>(
+        lab.x + 0.3963377774 * lab.y + 0.2158037573 * lab.z,
+        lab.x - 0.1055613458 * lab.y - 0.0638541728 * lab.z,
+        lab.x - 0.0894841775 * lab.y - 1.2914855480 * lab.z,
+    );
+    let lms = lms_ * lms_ * lms_;
+    return vec3<f32>(
+        4.0767416621 * lms.x - 3.3077118299 * lms.y + 0.2309699292 * lms.z,
+        -1.2684380046 * lms.x + 2.6097574011 * lms.y - 0.3413193965 * lms.z,
+        -0.0041960863 * lms.x - 0.7034186147 * lms.y + 1.7076147010 * lms.z,
+    );
+}
+
+// Interpolates between two linear sRGB colors in the Oklab color space with
+// premultiplied alpha, like CSS gradients, so that the midpoints aren't muddy
+// and transparent colors don't darken the colors they are mixed with.
+fn mix_oklab(a: vec4<f32>, b: vec4<f32>, t: f32) -> vec4<f32> {
+    let start = vec4<f32>(linear_srgb_to_oklab(a.rgb) * a.a, a.a);
+    let end = vec4<f32>(linear_srgb_to_oklab(b.rgb) * b.a, b.a);
+    let color = mix(start, end, t);
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(oklab_to_linear_srgb(color.rgb / color.a), color.a);
+}
+
+// Returns the position of the point along a gradient, like CSS gradients:
+// angles are in degrees, where 0 points up and angles increase clockwise.
+fn gradient_position(background: Background, point: vec2<f32>, bounds: Bounds) -> f32 {
+    let angle = radians(background.angle);
+    if (background.tag == 1u) {
+        // The gradient line goes through the center, and is long enough for
+        // its ends to reach the corners.
+        let direction = vec2<f32>(sin(angle), -cos(angle));
+        let line_length = abs(bounds.size.x * direction.x) + abs(bounds.size.y * direction.y);
+        let center = bounds.origin + bounds.size / 2.0;
+        return dot(point - center, direction) / max(line_length, 0.0001) + 0.5;
+    }
+
+    let center = bounds.origin + bounds.size * vec2<f32>(background.center_x, background.center_y);
+    let center_to_point = point - center;
+    if (background.tag == 2u) {
+        // An ellipse reaching the farthest corner, with the aspect ratio of
+        // the farthest sides.
+        let farthest_sides = max(center - bounds.origin, bounds.origin + bounds.size - center);
+        let radii = max(farthest_sides * sqrt(2.0), vec2<f32>(0.0001));
+        return length(center_to_point / radii);
+    }
+
+    let point_angle = atan2(center_to_point.x, -center_to_point.y);
+    return fract((point_angle - angle) / (2.0 * M_PI_F));
+}
+
+// Computes the color of a gradient at the given point.
+fn gradient_color(background: Background, point: vec2<f32>, bounds: Bounds) -> vec4<f32> {
+    if (background.stop_count == 0u) {
+        return vec4<f32>(0.0);
+    }
+    let t = gradient_position(background, point, bounds);
+
+    var stops = background.stops;
+    var color = hsla_to_linear_srgb(stops[0].color);
+    for (var i = 1u; i < background.stop_count; i += 1u) {
+        let start = stops[i - 1u];
+        let end = stops[i];
+        if (t >= start.percentage) {
+            let span = max(end.percentage - start.percentage, 0.0001);
+            color = mix_oklab(hsla_to_linear_srgb(start.color), hsla_to_linear_srgb(end.color),
+                              saturate((t - start.percentage) / span));
+        }
+    }
+    return vec4<f32>(to_surface_color_space(color.rgb), color.a);
+}
+
 // Abstract away the final color transformation based on the
-// target alpha compositing mode.
+// target alpha compositing mode. Quads and sprites blended in modes other than
+// normal are always premultiplied.
 fn blend_color(color: vec4<f32>, alpha_factor: f32) -> vec4<f32> {
     let alpha = color.a * alpha_factor;
     let multiplier = select(1.0, alpha, globals.premultiplied_alpha != 0u);
@@ -201,13 +334,14 @@ fn blend_color(color: vec4<f32>, alpha_factor: f32) -> vec4<f32> {
 
 struct Quad {
     order: u32,
-    pad: u32,
+    blend_mode: u32,
     bounds: Bounds,
     content_mask: Bounds,
-    background: Hsla,
+    background: Background,
     border_color: Hsla,
     corner_radii: Corners,
     border_widths: Edges,
+    transformation: TransformationMatrix,
 }
 var<storage, read> b_quads: array<Quad>;
 
@@ -218,6 +352,7 @@ struct QuadVarying {
     @location(2) @interpolate(flat) quad_id: u32,
     //TODO: use `clip_distance` once Naga supports it
     @location(3) clip_distances: vec4<f32>,
+    @location(4) local_position: vec2<f32>,
 }
 
 @vertex
@@ -226,8 +361,9 @@ fn vs_quad(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
     let quad = b_quads[instance_id];
 
     var out = QuadVarying();
-    out.position = to_device_position(unit_vertex, quad.bounds);
-    out.background_color = hsla_to_rgba(quad.background);
+    out.position = to_device_position_transformed(unit_vertex, quad.bounds, quad.transformation);
+    out.local_position = to_local_position(unit_vertex, quad.bounds);
+    out.background_color = hsla_to_rgba(quad.background.solid);
     out.border_color = hsla_to_rgba(quad.border_color);
     out.quad_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, quad.bounds, quad.content_mask);
@@ -242,54 +378,80 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
     }
 
     let quad = b_quads[input.quad_id];
+    var background_color = input.background_color;
+    if (quad.background.tag != 0u) {
+        background_color = gradient_color(quad.background, input.local_position, quad.bounds);
+    }
+
     // Fast path when the quad is not rounded and doesn't have any border.
     if (quad.corner_radii.top_left == 0.0 && quad.corner_radii.bottom_left == 0.0 &&
         quad.corner_radii.top_right == 0.0 &&
         quad.corner_radii.bottom_right == 0.0 && quad.border_widths.top == 0.0 &&
         quad.border_widths.left == 0.0 && quad.border_widths.right == 0.0 &&
         quad.border_widths.bottom == 0.0) {
-        return blend_color(input.background_color, 1.0);
-    }
-
-    let half_size = quad.bounds.size / 2.0;
-    let center = quad.bounds.origin + half_size;
-    let center_to_point = input.position.xy - center;
-
-    let corner_radius = pick_corner_radius(center_to_point, quad.corner_radii);
-
-    let rounded_edge_to_point = abs(center_to_point) - half_size + corner_radius;
-    let distance =
-      length(max(vec2<f32>(0.0), rounded_edge_to_point)) +
-      min(0.0, max(rounded_edge_to_point.x, rounded_edge_to_point.y)) -
-      corner_radius;
-
-    let vertical_border = select(quad.border_widths.left, quad.border_widths.right, center_to_point.x > 0.0);
-    let horizontal_border = select(quad.border_widths.top, quad.border_widths.bottom, center_to_point.y > 0.0);
-    let inset_size = half_size - corner_radius - vec2<f32>(vertical_border, horizontal_border);
-    let point_to_inset_corner = abs(center_to_point) - inset_size;
-
-    var border_width = 0.0;
-    if (point_to_inset_corner.x < 0.0 && point_to_inset_corner.y < 0.0) {
-        border_width = 0.0;
-    } else if (point_to_inset_corner.y > point_to_inset_corner.x) {
-        border_width = horizontal_border;
-    } else {
-        border_width = vertical_border;
+        return blend_color(background_color, 1.0);
     }
 
-    var color = input.background_color;
-    if (border_width > 0.0) {
-        let inset_distance = distance + border_width;
+    let distance = quad_sdf(input.local_position, quad.bounds, quad.corner_radii);
+
+    var color = background_color;
+    let widths = quad.border_widths;
+    if (widths.top > 0.0 || widths.right > 0.0 || widths.bottom > 0.0 || widths.left > 0.0) {
+        // The border surrounds the quad inset by the width of each edge, whose corners are
+        // rounded by what's left of the outer radius past the widest adjacent edge. This keeps
+        // edges of different widths meeting cleanly.
+        let inner_bounds = Bounds(
+            quad.bounds.origin + vec2<f32>(widths.left, widths.top),
+            max(vec2<f32>(0.0), quad.bounds.size - vec2<f32>(widths.left + widths.right, widths.top + widths.bottom)),
+        );
+        let inner_radii = Corners(
+            max(0.0, quad.corner_radii.top_left - max(widths.top, widths.left)),
+            max(0.0, quad.corner_radii.top_right - max(widths.top, widths.right)),
+            max(0.0, quad.corner_radii.bottom_right - max(widths.bottom, widths.right)),
+            max(0.0, quad.corner_radii.bottom_left - max(widths.bottom, widths.left)),
+        );
+        let inner_distance = quad_sdf(input.local_position, inner_bounds, inner_radii);
         // Blend the border on top of the background and then linearly interpolate
         // between the two as we slide inside the background.
-        let blended_border = over(input.background_color, input.border_color);
-        color = mix(blended_border, input.background_color,
-                    saturate(0.5 - inset_distance));
+        let blended_border = over(background_color, input.border_color);
+        color = mix(blended_border, background_color,
+                    saturate(0.5 - inner_distance));
     }
 
     return blend_color(color, saturate(0.5 - distance));
 }
 
+// --- solid quads --- //
+
+struct SolidQuad {
+    bounds: Bounds,
+    color: Hsla,
+}
+var<storage, read> b_solid_quads: array<SolidQuad>;
+
+struct SolidQuadVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) color: vec4<f32>,
+}
+
+// Solid quads are clipped to their content mask and have nothing but a color, so they don't
+// need anything from the fragment shader but blending.
+@vertex
+fn vs_solid_quad(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> SolidQuadVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let quad = b_solid_quads[instance_id];
+
+    var out = SolidQuadVarying();
+    out.position = to_device_position(unit_vertex, quad.bounds);
+    out.color = hsla_to_rgba(quad.color);
+    return out;
+}
+
+@fragment
+fn fs_solid_quad(input: SolidQuadVarying) -> @location(0) vec4<f32> {
+    return blend_color(input.color, 1.0);
+}
+
 // --- shadows --- //
 
 struct Shadow {
@@ -299,6 +461,10 @@ struct Shadow {
     corner_radii: Corners,
     content_mask: Bounds,
     color: Hsla,
+    transformation: TransformationMatrix,
+    inset_offset: vec2<f32>,
+    inset_spread_radius: f32,
+    inset: u32,
 }
 var<storage, read> b_shadows: array<Shadow>;
 
@@ -308,6 +474,7 @@ struct ShadowVarying {
     @location(1) @interpolate(flat) shadow_id: u32,
     //TODO: use `clip_distance` once Naga supports it
     @location(3) clip_distances: vec4<f32>,
+    @location(4) local_position: vec2<f32>,
 }
 
 @vertex
@@ -315,14 +482,19 @@ fn vs_shadow(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) ins
     let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
     var shadow = b_shadows[instance_id];
 
-    let margin = 3.0 * shadow.blur_radius;
+    // Inset shadows are painted within their bounds, so they don't need a margin.
+    var margin = 3.0 * shadow.blur_radius;
+    if ((shadow.inset & 0xFFu) != 0u) {
+        margin = 0.0;
+    }
     // Set the bounds of the shadow and adjust its size based on the shadow's
     // spread radius to achieve the spreading effect
     shadow.bounds.origin -= vec2<f32>(margin);
     shadow.bounds.size += 2.0 * vec2<f32>(margin);
 
     var out = ShadowVarying();
-    out.position = to_device_position(unit_vertex, shadow.bounds);
+    out.position = to_device_position_transformed(unit_vertex, shadow.bounds, shadow.transformation);
+    out.local_position = to_local_position(unit_vertex, shadow.bounds);
     out.color = hsla_to_rgba(shadow.color);
     out.shadow_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, shadow.bounds, shadow.content_mask);
@@ -337,32 +509,123 @@ fn fs_shadow(input: ShadowVarying) -> @location(0) vec4<f32> {
     }
 
     let shadow = b_shadows[input.shadow_id];
-    let half_size = shadow.bounds.size / 2.0;
-    let center = shadow.bounds.origin + half_size;
-    let center_to_point = input.position.xy - center;
-
-    let corner_radius = pick_corner_radius(center_to_point, shadow.corner_radii);
-
-    // The signal is only non-zero in a limited range, so don't waste samples
-    let low = center_to_point.y - half_size.y;
-    let high = center_to_point.y + half_size.y;
-    let start = clamp(-3.0 * shadow.blur_radius, low, high);
-    let end = clamp(3.0 * shadow.blur_radius, low, high);
+    let inset = (shadow.inset & 0xFFu) != 0u;
+
+    // The shape casting the shadow. Inset shadows are cast by their bounds moved
+    // by the offset and shrunk by the spread radius, and darken what's outside.
+    var shape = shadow.bounds;
+    var shape_corner_radii = shadow.corner_radii;
+    if (inset) {
+        let spread = shadow.inset_spread_radius;
+        shape.origin += shadow.inset_offset + vec2<f32>(spread);
+        shape.size = max(shape.size - vec2<f32>(2.0 * spread), vec2<f32>(0.0));
+        shape_corner_radii.top_left = max(shape_corner_radii.top_left - spread, 0.0);
+        shape_corner_radii.top_right = max(shape_corner_radii.top_right - spread, 0.0);
+        shape_corner_radii.bottom_right = max(shape_corner_radii.bottom_right - spread, 0.0);
+        shape_corner_radii.bottom_left = max(shape_corner_radii.bottom_left - spread, 0.0);
+    }
 
-    // Accumulate samples (we can get away with surprisingly few samples)
-    let step = (end - start) / 4.0;
-    var y = start + step * 0.5;
     var alpha = 0.0;
-    for (var i = 0; i < 4; i += 1) {
-        let blur = blur_along_x(center_to_point.x, center_to_point.y - y,
-            shadow.blur_radius, corner_radius, half_size);
-        alpha +=  blur * gaussian(y, shadow.blur_radius) * step;
-        y += step;
+    if (shadow.blur_radius > 0.0) {
+        let half_size = shape.size / 2.0;
+        let center = shape.origin + half_size;
+        let center_to_point = input.local_position - center;
+
+        let corner_radius = pick_corner_radius(center_to_point, shape_corner_radii);
+
+        // The signal is only non-zero in a limited range, so don't waste samples
+        let low = center_to_point.y - half_size.y;
+        let high = center_to_point.y + half_size.y;
+        let start = clamp(-3.0 * shadow.blur_radius, low, high);
+        let end = clamp(3.0 * shadow.blur_radius, low, high);
+
+        // Accumulate samples (we can get away with surprisingly few samples)
+        let step = (end - start) / 4.0;
+        var y = start + step * 0.5;
+        for (var i = 0; i < 4; i += 1) {
+            let blur = blur_along_x(center_to_point.x, center_to_point.y - y,
+                shadow.blur_radius, corner_radius, half_size);
+            alpha +=  blur * gaussian(y, shadow.blur_radius) * step;
+            y += step;
+        }
+    } else {
+        alpha = saturate(0.5 - quad_sdf(input.local_position, shape, shape_corner_radii));
+    }
+
+    if (inset) {
+        let distance = quad_sdf(input.local_position, shadow.bounds, shadow.corner_radii);
+        alpha = (1.0 - alpha) * saturate(0.5 - distance);
     }
 
     return blend_color(input.color, alpha);
 }
 
+// --- backdrop blurs --- //
+
+struct BackdropBlur {
+    order: u32,
+    blur_radius: f32,
+    bounds: Bounds,
+    corner_radii: Corners,
+    content_mask: Bounds,
+}
+var<storage, read> b_backdrop_blurs: array<BackdropBlur>;
+var t_backdrop: texture_2d<f32>;
+var s_backdrop: sampler;
+
+struct BackdropBlurVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) blur_id: u32,
+    //TODO: use `clip_distance` once Naga supports it
+    @location(3) clip_distances: vec4<f32>,
+}
+
+@vertex
+fn vs_backdrop_blur(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> BackdropBlurVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let blur = b_backdrop_blurs[instance_id];
+
+    var out = BackdropBlurVarying();
+    out.position = to_device_position(unit_vertex, blur.bounds);
+    out.blur_id = instance_id;
+    out.clip_distances = distance_from_clip_rect(unit_vertex, blur.bounds, blur.content_mask);
+    return out;
+}
+
+@fragment
+fn fs_backdrop_blur(input: BackdropBlurVarying) -> @location(0) vec4<f32> {
+    // Alpha clip first, since we don't have `clip_distance`.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let blur = b_backdrop_blurs[input.blur_id];
+    let sigma = blur.blur_radius / 2.0;
+    let step = blur.blur_radius / 4.0;
+
+    // Sample a grid spanning the blur radius, relying on linear filtering
+    // to cover the texels in between.
+    var color = vec4<f32>(0.0);
+    var total_weight = 0.0;
+    for (var i = -4; i <= 4; i += 1) {
+        for (var j = -4; j <= 4; j += 1) {
+            let offset = vec2<f32>(f32(i), f32(j)) * step;
+            let weight = gaussian(offset.x, sigma) * gaussian(offset.y, sigma);
+            let uv = (input.position.xy + offset) / globals.viewport_size;
+            color += textureSampleLevel(t_backdrop, s_backdrop, uv, 0.0) * weight;
+            total_weight += weight;
+        }
+    }
+    color /= total_weight;
+
+    let alpha = saturate(0.5 - quad_sdf(input.position.xy, blur.bounds, blur.corner_radii));
+    // The backdrop is already premultiplied when the surface is.
+    if (globals.premultiplied_alpha != 0u) {
+        return color * alpha;
+    }
+    return vec4<f32>(color.rgb, color.a * alpha);
+}
+
 // --- path rasterization --- //
 
 struct PathVertex {
@@ -398,6 +661,16 @@ fn fs_path_rasterization(input: PathRasterizationVarying) -> @location(0) f32 {
         return 0.0;
     }
 
+    // The fringes along straight edges have a negative t coordinate, and an s coordinate
+    // going from 1 on the left of the edge to -1 on its right. Paths are filled where they're
+    // covered an odd number of times, so taking coverage away on the right is the same as
+    // adding 2 minus it.
+    if (input.st_position.y < 0.0) {
+        let distance = input.st_position.x / length(vec2<f32>(dx.x, dy.x));
+        let coverage = saturate(0.5 - abs(distance));
+        return select(coverage, 2.0 - coverage, distance < 0.0 && coverage > 0.0);
+    }
+
     let gradient = 2.0 * input.st_position.xx * vec2<f32>(dx.x, dy.x) - vec2<f32>(dx.y, dy.y);
     let f = input.st_position.x * input.st_position.x - input.st_position.y;
     let distance = f / length(gradient);
@@ -408,8 +681,11 @@ fn fs_path_rasterization(input: PathRasterizationVarying) -> @location(0) f32 {
 
 struct PathSprite {
     bounds: Bounds,
-    color: Hsla,
+    color: Background,
+    path_bounds: Bounds,
     tile: AtlasTile,
+    nonzero: u32,
+    pad: u32,
 }
 var<storage, read> b_path_sprites: array<PathSprite>;
 
@@ -417,6 +693,7 @@ struct PathVarying {
     @builtin(position) position: vec4<f32>,
     @location(0) tile_position: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) sprite_id: u32,
 }
 
 @vertex
@@ -428,27 +705,44 @@ fn vs_path(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
     var out = PathVarying();
     out.position = to_device_position(unit_vertex, sprite.bounds);
     out.tile_position = to_tile_position(unit_vertex, sprite.tile);
-    out.color = hsla_to_rgba(sprite.color);
+    out.color = hsla_to_rgba(sprite.color.solid);
+    out.sprite_id = instance_id;
     return out;
 }
 
 @fragment
 fn fs_path(input: PathVarying) -> @location(0) vec4<f32> {
     let sample = textureSample(t_sprite, s_sprite, input.tile_position).r;
-    let mask = 1.0 - abs(1.0 - sample % 2.0);
-    return blend_color(input.color, mask);
+    let sprite = b_path_sprites[input.sprite_id];
+    // Overlapping triangles alternately fill and cut out the path, unless they're all filled,
+    // like the ones of a stroke.
+    let mask = select(1.0 - abs(1.0 - sample % 2.0), min(sample, 1.0), sprite.nonzero != 0u);
+    var color = input.color;
+    if (sprite.color.tag != 0u) {
+        color = gradient_color(sprite.color, input.position.xy, sprite.path_bounds);
+    }
+    return blend_color(color, mask);
 }
 
 // --- underlines --- //
 
+const UNDERLINE_KIND_SINGLE: u32 = 0u;
+const UNDERLINE_KIND_DOUBLE: u32 = 1u;
+const UNDERLINE_KIND_DOTTED: u32 = 2u;
+
 struct Underline {
     order: u32,
-    pad: u32,
+    kind: u32,
     bounds: Bounds,
     content_mask: Bounds,
     color: Hsla,
     thickness: f32,
-    wavy: u32,
+    wavelength: f32,
+    transformation: TransformationMatrix,
+    dash: f32,
+    dash_gap: f32,
+    dash_offset: f32,
+    wave_amplitude: f32,
 }
 var<storage, read> b_underlines: array<Underline>;
 
@@ -458,6 +752,7 @@ struct UnderlineVarying {
     @location(1) @interpolate(flat) underline_id: u32,
     //TODO: use `clip_distance` once Naga supports it
     @location(3) clip_distances: vec4<f32>,
+    @location(4) local_position: vec2<f32>,
 }
 
 @vertex
@@ -466,7 +761,8 @@ fn vs_underline(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index)
     let underline = b_underlines[instance_id];
 
     var out = UnderlineVarying();
-    out.position = to_device_position(unit_vertex, underline.bounds);
+    out.position = to_device_position_transformed(unit_vertex, underline.bounds, underline.transformation);
+    out.local_position = to_local_position(unit_vertex, underline.bounds);
     out.color = hsla_to_rgba(underline.color);
     out.underline_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, underline.bounds, underline.content_mask);
@@ -481,30 +777,63 @@ fn fs_underline(input: UnderlineVarying) -> @location(0) vec4<f32> {
     }
 
     let underline = b_underlines[input.underline_id];
-    if ((underline.wavy & 0xFFu) == 0u)
-    {
-        return blend_color(input.color, input.color.a);
+    let dash_alpha = dash_pattern_alpha(input.local_position.x - underline.bounds.origin.x,
+        underline.dash, underline.dash_gap, underline.dash_offset);
+    if (underline.wavelength <= 0.0 && underline.kind == UNDERLINE_KIND_SINGLE) {
+        return blend_color(input.color, dash_alpha * input.color.a);
     }
 
+    // The distance from the middle of the underline's bounds, measured perpendicular to the
+    // wave for wavy underlines.
     let half_thickness = underline.thickness * 0.5;
-    let st = (input.position.xy - underline.bounds.origin) / underline.bounds.size.y - vec2<f32>(0.0, 0.5);
-    let frequency = M_PI_F * 3.0 * underline.thickness / 3.0;
-    let amplitude = 1.0 / (4.0 * underline.thickness);
-    let sine = sin(st.x * frequency) * amplitude;
-    let dSine = cos(st.x * frequency) * amplitude * frequency;
-    let distance = (st.y - sine) / sqrt(1.0 + dSine * dSine);
-    let distance_in_pixels = distance * underline.bounds.size.y;
-    let distance_from_top_border = distance_in_pixels - half_thickness;
-    let distance_from_bottom_border = distance_in_pixels + half_thickness;
-    let alpha = saturate(0.5 - max(-distance_from_bottom_border, distance_from_top_border));
-    return blend_color(input.color, alpha * input.color.a);
+    let center_y = underline.bounds.origin.y + underline.bounds.size.y * 0.5;
+    var distance = input.local_position.y - center_y;
+    if (underline.wavelength > 0.0) {
+        let frequency = 2.0 * M_PI_F / underline.wavelength;
+        // The phase is based on the position in the window, so that wavy underlines
+        // that are next to each other join up.
+        let sine = sin(input.local_position.x * frequency) * underline.wave_amplitude;
+        let dSine = cos(input.local_position.x * frequency) * underline.wave_amplitude * frequency;
+        // Approximate the distance to the wave by dividing the vertical distance by
+        // the length of the wave's normal.
+        distance = (distance - sine) / sqrt(1.0 + dSine * dSine);
+    }
+
+    var alpha: f32;
+    if (underline.kind == UNDERLINE_KIND_DOUBLE) {
+        // The lines are centered a thickness above and below the middle.
+        alpha = saturate(0.5 - (abs(abs(distance) - underline.thickness) - half_thickness));
+    } else if (underline.kind == UNDERLINE_KIND_DOTTED) {
+        // Dots as wide as the underline is thick, each followed by a gap of the same size.
+        let along = (input.local_position.x - underline.bounds.origin.x) % (underline.thickness * 2.0) - half_thickness;
+        alpha = saturate(0.5 - (length(vec2<f32>(along, distance)) - half_thickness));
+    } else {
+        alpha = saturate(0.5 - (abs(distance) - half_thickness));
+    }
+    return blend_color(input.color, dash_alpha * alpha * input.color.a);
+}
+
+// The coverage of a dash pattern at the given distance along a line, anti-aliasing the ends
+// of the dashes. Patterns without dashes are continuous.
+fn dash_pattern_alpha(distance: f32, dash: f32, gap: f32, offset: f32) -> f32 {
+    if (dash <= 0.0) {
+        return 1.0;
+    }
+    let period = dash + gap;
+    let position = distance + offset;
+    let position_in_period = position - floor(position / period) * period;
+    let distance_into_dash = max(
+        min(position_in_period, dash - position_in_period),
+        position_in_period - period
+    );
+    return saturate(0.5 + distance_into_dash);
 }
 
 // --- monochrome sprites --- //
 
 struct MonochromeSprite {
     order: u32,
-    pad: u32,
+    blend_mode: u32,
     bounds: Bounds,
     content_mask: Bounds,
     color: Hsla,
@@ -548,13 +877,22 @@ fn fs_mono_sprite(input: MonoSpriteVarying) -> @location(0) vec4<f32> {
 
 struct PolychromeSprite {
     order: u32,
-    pad: u32,
-    grayscale: u32,
+    hue_rotate: f32,
+    grayscale: f32,
     opacity: f32,
     bounds: Bounds,
     content_mask: Bounds,
     corner_radii: Corners,
     tile: AtlasTile,
+    transformation: TransformationMatrix,
+    nine_slice: Edges,
+    tile_nine_slice: Edges,
+    repeat_size: vec2<f32>,
+    repeat_spacing: vec2<f32>,
+    repeat_origin: vec2<f32>,
+    tint: Hsla,
+    blend_mode: u32,
+    pad: u32,
 }
 var<storage, read> b_poly_sprites: array<PolychromeSprite>;
 
@@ -563,6 +901,7 @@ struct PolySpriteVarying {
     @location(0) tile_position: vec2<f32>,
     @location(1) @interpolate(flat) sprite_id: u32,
     @location(3) clip_distances: vec4<f32>,
+    @location(4) local_position: vec2<f32>,
 }
 
 @vertex
@@ -571,32 +910,116 @@ fn vs_poly_sprite(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index
     let sprite = b_poly_sprites[instance_id];
 
     var out = PolySpriteVarying();
-    out.position = to_device_position(unit_vertex, sprite.bounds);
+    out.position = to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation);
+    out.local_position = to_local_position(unit_vertex, sprite.bounds);
     out.tile_position = to_tile_position(unit_vertex, sprite.tile);
     out.sprite_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, sprite.bounds, sprite.content_mask);
     return out;
 }
 
+// Maps a position along one axis of a nine-slice sprite to its tile: the borders at
+// either end keep their size, and the center stretches to fill the rest.
+fn nine_slice_axis(position: f32, size: f32, start: f32, end: f32, tile_size: f32, tile_start: f32, tile_end: f32) -> f32 {
+    if (position < start) {
+        return position / start * tile_start;
+    }
+    if (position > size - end) {
+        return tile_size - (size - position) / end * tile_end;
+    }
+    let center = max(size - start - end, 0.0001);
+    return tile_start + (position - start) / center * (tile_size - tile_start - tile_end);
+}
+
+fn is_repeating(sprite: PolychromeSprite) -> bool {
+    return sprite.repeat_size.x > 0.0 && sprite.repeat_size.y > 0.0;
+}
+
+// Maps a position in a sprite to the copy of its tile it falls in when the tile repeats.
+// Positions in the spacing between copies end up past the size of a copy.
+fn to_repeat_position(local_position: vec2<f32>, sprite: PolychromeSprite) -> vec2<f32> {
+    let position = local_position - sprite.bounds.origin;
+    if (!is_repeating(sprite)) {
+        return position;
+    }
+    let cell_size = sprite.repeat_size + sprite.repeat_spacing;
+    let offset = position - sprite.repeat_origin;
+    return offset - floor(offset / cell_size) * cell_size;
+}
+
+fn to_nine_slice_tile_position(position: vec2<f32>, size: vec2<f32>, sprite: PolychromeSprite) -> vec2<f32> {
+    let tile_size = vec2<f32>(sprite.tile.bounds.size);
+    let x = nine_slice_axis(position.x, size.x, sprite.nine_slice.left, sprite.nine_slice.right,
+        tile_size.x, sprite.tile_nine_slice.left, sprite.tile_nine_slice.right);
+    let y = nine_slice_axis(position.y, size.y, sprite.nine_slice.top, sprite.nine_slice.bottom,
+        tile_size.y, sprite.tile_nine_slice.top, sprite.tile_nine_slice.bottom);
+    // Keep the samples within the tile, so filtering doesn't blend in its neighbors in the atlas.
+    let tile_position = clamp(vec2<f32>(x, y), vec2<f32>(0.5), tile_size - vec2<f32>(0.5));
+    let atlas_size = vec2<f32>(textureDimensions(t_sprite, 0));
+    return (vec2<f32>(sprite.tile.bounds.origin) + tile_position) / atlas_size;
+}
+
+// Rotates the hue of a color while keeping its luminance, like the CSS `hue-rotate` filter.
+fn hue_rotation(angle: f32) -> mat3x3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return mat3x3<f32>(
+        vec3<f32>(0.213 + c * 0.787 - s * 0.213, 0.213 - c * 0.213 + s * 0.143, 0.213 - c * 0.213 - s * 0.787),
+        vec3<f32>(0.715 - c * 0.715 - s * 0.715, 0.715 + c * 0.285 + s * 0.140, 0.715 - c * 0.715 + s * 0.715),
+        vec3<f32>(0.072 - c * 0.072 + s * 0.928, 0.072 - c * 0.072 - s * 0.283, 0.072 + c * 0.928 + s * 0.072),
+    );
+}
+
+// Desaturates, rotates the hue of and tints a sprite's color, in that order.
+fn apply_color_filter(color: vec3<f32>, sprite: PolychromeSprite) -> vec3<f32> {
+    var filtered = mix(color, vec3<f32>(dot(color, GRAYSCALE_FACTORS)), sprite.grayscale);
+    if (sprite.hue_rotate != 0.0) {
+        filtered = saturate(hue_rotation(sprite.hue_rotate) * filtered);
+    }
+    let tint = hsla_to_linear_srgb(sprite.tint);
+    return mix(filtered, tint.rgb, tint.a);
+}
+
 @fragment
 fn fs_poly_sprite(input: PolySpriteVarying) -> @location(0) vec4<f32> {
-    let sample = textureSample(t_sprite, s_sprite, input.tile_position);
+    let sprite = b_poly_sprites[input.sprite_id];
+    let position = to_repeat_position(input.local_position, sprite);
+    let size = select(sprite.bounds.size, sprite.repeat_size, is_repeating(sprite));
+    // Without nine-slice borders or repetition this is the interpolated tile position.
+    let tile_position = to_nine_slice_tile_position(position, size, sprite);
+    let sample = textureSample(t_sprite, s_sprite, tile_position);
     // Alpha clip after using the derivatives.
     if (any(input.clip_distances < vec4<f32>(0.0))) {
         return vec4<f32>(0.0);
     }
+    // Leave the spacing between repeated copies empty.
+    if (any(position > size)) {
+        return vec4<f32>(0.0);
+    }
 
-    let sprite = b_poly_sprites[input.sprite_id];
-    let distance = quad_sdf(input.position.xy, sprite.bounds, sprite.corner_radii);
+    let distance = quad_sdf(input.local_position, sprite.bounds, sprite.corner_radii);
 
-    var color = sample;
-    if ((sprite.grayscale & 0xFFu) != 0u) {
-        let grayscale = dot(color.rgb, GRAYSCALE_FACTORS);
-        color = vec4<f32>(vec3<f32>(grayscale), sample.a);
-    }
+    let color = vec4<f32>(to_surface_color_space(apply_color_filter(sample.rgb, sprite)), sample.a);
     return blend_color(color, sprite.opacity * saturate(0.5 - distance));
 }
 
+// --- opacity groups --- //
+
+// Groups are composited with the polychrome sprite vertex shader. Their contents are drawn
+// into transparent textures, which leaves the colors in them premultiplied.
+@fragment
+fn fs_opacity_group(input: PolySpriteVarying) -> @location(0) vec4<f32> {
+    let sample = textureSample(t_sprite, s_sprite, input.tile_position);
+    // Alpha clip after using the derivatives.
+    if (any(input.clip_distances < vec4<f32>(0.0)) || sample.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let sprite = b_poly_sprites[input.sprite_id];
+    let color = vec4<f32>(sample.rgb / sample.a, sample.a);
+    return blend_color(color, sprite.opacity);
+}
+
 // --- surfaces --- //
 
 struct SurfaceParams {
@@ -607,6 +1030,7 @@ struct SurfaceParams {
 var<uniform> surface_locals: SurfaceParams;
 var t_y: texture_2d<f32>;
 var t_cb_cr: texture_2d<f32>;
+var t_texture: texture_2d<f32>;
 var s_surface: sampler;
 
 const ycbcr_to_RGB = mat4x4<f32>(
@@ -647,3 +1071,15 @@ fn fs_surface(input: SurfaceVarying) -> @location(0) vec4<f32> {
 
     return ycbcr_to_RGB * y_cb_cr;
 }
+
+// Samples a texture drawn by another renderer, whose colors are sRGB with straight alpha.
+@fragment
+fn fs_texture_surface(input: SurfaceVarying) -> @location(0) vec4<f32> {
+    // Alpha clip after using the derivatives.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let color = textureSampleLevel(t_texture, s_surface, input.texture_position, 0.0);
+    return blend_color(vec4<f32>(to_surface_color_space(color.rgb), color.a), 1.0);
+}
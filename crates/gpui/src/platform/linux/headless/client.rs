@@ -7,7 +7,10 @@ use util::ResultExt;
 
 use crate::platform::linux::LinuxClient;
 use crate::platform::{LinuxCommon, PlatformWindow};
-use crate::{AnyWindowHandle, CursorStyle, DisplayId, PlatformDisplay, WindowParams};
+use crate::{
+    AnyWindowHandle, CursorStyle, CustomCursor, DisplayId, Pixels, PlatformDisplay, Point,
+    RenderImage, WindowParams,
+};
 
 pub struct HeadlessClientState {
     pub(crate) _loop_handle: LoopHandle<'static, HeadlessClient>,
@@ -83,6 +86,14 @@ impl LinuxClient for HeadlessClient {
 
     fn set_cursor_style(&self, _style: CursorStyle) {}
 
+    fn register_cursor(
+        &self,
+        _cursor: CustomCursor,
+        _image: std::sync::Arc<RenderImage>,
+        _hotspot: Point<Pixels>,
+    ) {
+    }
+
     fn open_uri(&self, _uri: &str) {}
 
     fn reveal_path(&self, _path: std::path::PathBuf) {}
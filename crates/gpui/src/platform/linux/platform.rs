@@ -36,11 +36,12 @@ use xkbcommon::xkb::{self, Keycode, Keysym, State};
 
 use crate::platform::linux::wayland::WaylandClient;
 use crate::{
-    px, Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem, CosmicTextSystem, CursorStyle,
-    DisplayId, ForegroundExecutor, Keymap, Keystroke, LinuxDispatcher, Menu, MenuItem, Modifiers,
-    OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, PlatformInputHandler,
-    PlatformTextSystem, PlatformWindow, Point, PromptLevel, Result, SemanticVersion, SharedString,
-    Size, Task, WindowAppearance, WindowOptions, WindowParams,
+    px, AccessibilityPreferences, Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem,
+    CosmicTextSystem, CursorStyle, CustomCursor, DisplayId, ForegroundExecutor, Keymap, Keystroke,
+    LinuxDispatcher, Menu, MenuItem, Modifiers, OwnedMenu, PathPromptOptions, Pixels, Platform,
+    PlatformDisplay, PlatformInputHandler, PlatformTextSystem, PlatformWindow, Point, PowerState,
+    PromptLevel, RenderImage, Result, SemanticVersion, SharedString, Size, Task, WindowAppearance,
+    WindowOptions, WindowParams,
 };
 
 use super::x11::X11Client;
@@ -69,6 +70,12 @@ pub trait LinuxClient {
         options: WindowParams,
     ) -> anyhow::Result<Box<dyn PlatformWindow>>;
     fn set_cursor_style(&self, style: CursorStyle);
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    );
     fn open_uri(&self, uri: &str);
     fn reveal_path(&self, path: PathBuf);
     fn write_to_primary(&self, item: ClipboardItem);
@@ -96,6 +103,7 @@ pub(crate) struct LinuxCommon {
     pub(crate) text_system: Arc<CosmicTextSystem>,
     pub(crate) appearance: WindowAppearance,
     pub(crate) auto_hide_scrollbars: bool,
+    pub(crate) accessibility_preferences: AccessibilityPreferences,
     pub(crate) callbacks: PlatformHandlers,
     pub(crate) signal: LoopSignal,
     pub(crate) menus: Vec<OwnedMenu>,
@@ -117,6 +125,7 @@ impl LinuxCommon {
             text_system,
             appearance: WindowAppearance::Light,
             auto_hide_scrollbars: false,
+            accessibility_preferences: AccessibilityPreferences::default(),
             callbacks,
             signal,
             menus: Vec::new(),
@@ -422,10 +431,27 @@ impl<P: LinuxClient + 'static> Platform for P {
         self.set_cursor_style(style)
     }
 
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    ) {
+        self.register_cursor(cursor, image, hotspot)
+    }
+
     fn should_auto_hide_scrollbars(&self) -> bool {
         self.with_common(|common| common.auto_hide_scrollbars)
     }
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        self.with_common(|common| common.accessibility_preferences)
+    }
+
+    fn power_state(&self) -> PowerState {
+        read_power_state()
+    }
+
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>> {
         let url = url.to_string();
         let username = username.to_string();
@@ -577,6 +603,36 @@ pub(super) fn reveal_path_internal(
         .detach();
 }
 
+/// Reads the power supplies the kernel exposes. The machine is considered to run on battery when
+/// it has a system battery and no external power supply is online.
+fn read_power_state() -> PowerState {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return PowerState::default();
+    };
+
+    let mut has_battery = false;
+    for supply in supplies.flatten() {
+        let path = supply.path();
+        let read = |name: &str| {
+            std::fs::read_to_string(path.join(name))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        match read("type").as_str() {
+            "Mains" | "USB" if read("online") == "1" => return PowerState::default(),
+            // The batteries of peripherals, such as wireless mice, are in the "Device" scope.
+            "Battery" if read("scope") != "Device" => has_battery = true,
+            _ => {}
+        }
+    }
+
+    // There's no power saving mode that's common to all desktops to read here.
+    PowerState {
+        on_battery: has_battery,
+        low_power_mode: false,
+    }
+}
+
 pub(super) fn is_within_click_distance(a: Point<Pixels>, b: Point<Pixels>) -> bool {
     let diff = a - b;
     diff.x.abs() <= DOUBLE_CLICK_DISTANCE && diff.y.abs() <= DOUBLE_CLICK_DISTANCE
@@ -640,6 +696,13 @@ impl CursorStyle {
             CursorStyle::DragLink => Shape::Alias,
             CursorStyle::DragCopy => Shape::Copy,
             CursorStyle::ContextualMenu => Shape::ContextMenu,
+            CursorStyle::Move => Shape::Move,
+            CursorStyle::Wait => Shape::Wait,
+            CursorStyle::Progress => Shape::Progress,
+            CursorStyle::Help => Shape::Help,
+            CursorStyle::ZoomIn => Shape::ZoomIn,
+            CursorStyle::ZoomOut => Shape::ZoomOut,
+            CursorStyle::Custom(_) => Shape::Default,
         }
     }
 
@@ -669,6 +732,13 @@ impl CursorStyle {
             CursorStyle::DragLink => "alias",
             CursorStyle::DragCopy => "copy",
             CursorStyle::ContextualMenu => "context-menu",
+            CursorStyle::Move => "move",
+            CursorStyle::Wait => "wait",
+            CursorStyle::Progress => "progress",
+            CursorStyle::Help => "help",
+            CursorStyle::ZoomIn => "zoom-in",
+            CursorStyle::ZoomOut => "zoom-out",
+            CursorStyle::Custom(_) => "arrow",
         }
         .to_string()
     }
@@ -429,6 +429,7 @@ impl CosmicTextSystemState {
             descent: layout.max_descent.into(),
             runs,
             len: text.len(),
+            ligature_carets: Vec::new(),
         }
     }
 }
@@ -82,9 +82,9 @@ use crate::{
     DOUBLE_CLICK_INTERVAL, SCROLL_LINES,
 };
 use crate::{
-    AnyWindowHandle, CursorStyle, DisplayId, KeyDownEvent, KeyUpEvent, Keystroke, Modifiers,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    NavigationDirection, Pixels, PlatformDisplay, PlatformInput, Point, ScrollDelta,
+    AnyWindowHandle, CursorStyle, CustomCursor, DisplayId, KeyDownEvent, KeyUpEvent, Keystroke,
+    Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    NavigationDirection, Pixels, PlatformDisplay, PlatformInput, Point, RenderImage, ScrollDelta,
     ScrollWheelEvent, TouchPhase,
 };
 use crate::{LinuxCommon, WindowParams};
@@ -505,6 +505,24 @@ impl WaylandClient {
                             client.cursor.set_size(size);
                         }
                     }
+                    XDPEvent::EnableAnimations(enable_animations) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client
+                                .borrow_mut()
+                                .common
+                                .accessibility_preferences
+                                .reduce_motion = !enable_animations;
+                        }
+                    }
+                    XDPEvent::HighContrast(high_contrast) => {
+                        if let Some(client) = client.0.upgrade() {
+                            client
+                                .borrow_mut()
+                                .common
+                                .accessibility_preferences
+                                .increase_contrast = high_contrast;
+                        }
+                    }
                 }
             })
             .unwrap();
@@ -661,6 +679,16 @@ impl LinuxClient for WaylandClient {
         }
     }
 
+    // Custom cursors would have to be drawn to a surface of their own, so the arrow cursor is
+    // shown instead for now.
+    fn register_cursor(
+        &self,
+        _cursor: CustomCursor,
+        _image: std::sync::Arc<RenderImage>,
+        _hotspot: Point<Pixels>,
+    ) {
+    }
+
     fn open_uri(&self, uri: &str) {
         let mut state = self.0.borrow_mut();
         if let (Some(activation), Some(window)) = (
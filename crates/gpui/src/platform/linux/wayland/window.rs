@@ -154,6 +154,7 @@ impl WaylandWindowState {
                 depth: 1,
             },
             transparent: true,
+            color_space: options.color_space,
         };
 
         Ok(Self {
@@ -940,9 +941,31 @@ impl PlatformWindow for WaylandWindow {
 
     fn draw(&self, scene: &Scene) {
         let mut state = self.borrow_mut();
+        // Damage is a hint for the compositor, which only needs to recomposite what changed.
+        // Presenting through Vulkan may still damage the whole surface.
+        if let Some(damage) = scene.damage() {
+            if state.surface.version() >= wl_surface::REQ_DAMAGE_BUFFER_SINCE {
+                for bounds in damage {
+                    state.surface.damage_buffer(
+                        bounds.origin.x.0 as i32,
+                        bounds.origin.y.0 as i32,
+                        bounds.size.width.0 as i32,
+                        bounds.size.height.0 as i32,
+                    );
+                }
+            }
+        }
         state.renderer.draw(scene);
     }
 
+    fn uses_damage(&self) -> bool {
+        self.borrow().surface.version() >= wl_surface::REQ_DAMAGE_BUFFER_SINCE
+    }
+
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<image::RgbaImage> {
+        self.borrow_mut().renderer.render_to_image(scene)
+    }
+
     fn completed_frame(&self) {
         let state = self.borrow();
         state.surface.commit();
@@ -4,8 +4,10 @@ use std::collections::HashSet;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::rc::{Rc, Weak};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use anyhow::Context as _;
 use calloop::generic::{FdWrapper, Generic};
 use calloop::{EventLoop, LoopHandle, RegistrationToken};
 
@@ -18,6 +20,7 @@ use x11rb::connection::{Connection, RequestConnection};
 use x11rb::cursor;
 use x11rb::errors::ConnectionError;
 use x11rb::protocol::randr::ConnectionExt as _;
+use x11rb::protocol::render::ConnectionExt as _;
 use x11rb::protocol::xinput::ConnectionExt;
 use x11rb::protocol::xkb::ConnectionExt as _;
 use x11rb::protocol::xproto::{
@@ -37,8 +40,9 @@ use crate::platform::linux::LinuxClient;
 use crate::platform::{LinuxCommon, PlatformWindow};
 use crate::{
     modifiers_from_xinput_info, point, px, AnyWindowHandle, Bounds, ClipboardItem, CursorStyle,
-    DisplayId, FileDropEvent, Keystroke, Modifiers, ModifiersChangedEvent, Pixels, Platform,
-    PlatformDisplay, PlatformInput, Point, ScrollDelta, Size, TouchPhase, WindowParams, X11Window,
+    CustomCursor, DisplayId, FileDropEvent, Keystroke, Modifiers, ModifiersChangedEvent, Pixels,
+    Platform, PlatformDisplay, PlatformInput, Point, RenderImage, ScrollDelta, Size, TouchPhase,
+    WindowParams, X11Window,
 };
 
 use super::{button_of_key, modifiers_from_state, pressed_button_from_mask};
@@ -393,6 +397,16 @@ impl X11Client {
                     XDPEvent::CursorTheme(_) | XDPEvent::CursorSize(_) => {
                         // noop, X11 manages this for us.
                     }
+                    XDPEvent::EnableAnimations(enable_animations) => {
+                        client.with_common(|common| {
+                            common.accessibility_preferences.reduce_motion = !enable_animations
+                        });
+                    }
+                    XDPEvent::HighContrast(high_contrast) => {
+                        client.with_common(|common| {
+                            common.accessibility_preferences.increase_contrast = high_contrast
+                        });
+                    }
                 }
             })
             .unwrap();
@@ -1404,6 +1418,28 @@ impl LinuxClient for X11Client {
             .unwrap();
     }
 
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    ) {
+        let mut state = self.0.borrow_mut();
+        let root = state.xcb_connection.setup().roots[state.x_root_index].root;
+        let Some(x_cursor) = create_cursor(&state.xcb_connection, root, &image, hotspot)
+            .context("failed to create cursor")
+            .log_err()
+        else {
+            return;
+        };
+        if let Some(previous_cursor) = state
+            .cursor_cache
+            .insert(CursorStyle::Custom(cursor), x_cursor)
+        {
+            state.xcb_connection.free_cursor(previous_cursor).log_err();
+        }
+    }
+
     fn open_uri(&self, uri: &str) {
         open_uri_internal(self.background_executor(), uri, None);
     }
@@ -1735,3 +1771,79 @@ fn xdnd_send_status(
         .send_event(false, target, EventMask::default(), message)
         .unwrap();
 }
+
+/// Creates a cursor from the first frame of an image, with the render extension.
+fn create_cursor(
+    connection: &XCBConnection,
+    root: xproto::Window,
+    image: &RenderImage,
+    hotspot: Point<Pixels>,
+) -> anyhow::Result<xproto::Cursor> {
+    let size = image.size(0);
+    let (width, height) = (size.width.0 as u16, size.height.0 as u16);
+    // Render images are stored as BGRA, which is how little-endian machines store the ARGB
+    // pixels of cursors, except that cursors are premultiplied.
+    let bytes = image
+        .as_bytes(0)
+        .context("cursor image has no frames")?
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            let alpha = pixel[3] as u32;
+            let premultiply = |channel: u8| (channel as u32 * alpha / 255) as u8;
+            [
+                premultiply(pixel[0]),
+                premultiply(pixel[1]),
+                premultiply(pixel[2]),
+                pixel[3],
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let format = connection
+        .render_query_pict_formats()?
+        .reply()?
+        .formats
+        .into_iter()
+        .find(|format| {
+            format.type_ == render::PictType::DIRECT
+                && format.depth == 32
+                && format.direct.alpha_shift == 24
+                && format.direct.red_shift == 16
+                && format.direct.green_shift == 8
+                && format.direct.blue_shift == 0
+        })
+        .context("no ARGB picture format")?;
+
+    let pixmap = connection.generate_id()?;
+    connection.create_pixmap(32, pixmap, root, width, height)?;
+    let gc = connection.generate_id()?;
+    connection.create_gc(gc, pixmap, &xproto::CreateGCAux::default())?;
+    connection.put_image(
+        xproto::ImageFormat::Z_PIXMAP,
+        pixmap,
+        gc,
+        width,
+        height,
+        0,
+        0,
+        0,
+        32,
+        &bytes,
+    )?;
+    let picture = connection.generate_id()?;
+    connection.render_create_picture(
+        picture,
+        pixmap,
+        format.id,
+        &render::CreatePictureAux::default(),
+    )?;
+    let cursor = connection.generate_id()?;
+    connection
+        .render_create_cursor(cursor, picture, hotspot.x.0 as u16, hotspot.y.0 as u16)?
+        .check()?;
+
+    connection.render_free_picture(picture)?;
+    connection.free_gc(gc)?;
+    connection.free_pixmap(pixmap)?;
+    Ok(cursor)
+}
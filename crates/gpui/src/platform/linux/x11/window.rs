@@ -4,9 +4,9 @@ use crate::{
     platform::blade::{BladeRenderer, BladeSurfaceConfig},
     px, size, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GPUSpecs,
     Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
-    PlatformWindow, Point, PromptLevel, ResizeEdge, Scene, Size, Tiling, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowDecorations, WindowKind, WindowParams,
-    X11ClientStatePtr,
+    PlatformWindow, Point, PromptLevel, RenderImage, ResizeEdge, Scene, Size, Tiling,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowDecorations, WindowKind,
+    WindowParams, X11ClientStatePtr,
 };
 
 use blade_graphics as gpu;
@@ -54,6 +54,7 @@ x11rb::atom_manager! {
         WM_DELETE_WINDOW,
         WM_CHANGE_STATE,
         _NET_WM_NAME,
+        _NET_WM_ICON,
         _NET_WM_STATE,
         _NET_WM_STATE_MAXIMIZED_VERT,
         _NET_WM_STATE_MAXIMIZED_HORZ,
@@ -520,6 +521,7 @@ impl X11WindowState {
             // If the window appearance changes, then the renderer will get updated
             // too
             transparent: false,
+            color_space: params.color_space,
         };
         xcb_connection.map_window(x_window).unwrap();
 
@@ -1132,6 +1134,35 @@ impl PlatformWindow for X11Window {
             .unwrap();
     }
 
+    fn set_icon(&mut self, icon: &RenderImage) {
+        let Some(bytes) = icon.as_bytes(0) else {
+            return;
+        };
+        let size = icon.size(0);
+        // The icon is the width and height followed by ARGB pixels, which are stored as BGRA on
+        // little-endian machines.
+        let mut data = Vec::with_capacity(2 + bytes.len() / 4);
+        data.push(size.width.0 as u32);
+        data.push(size.height.0 as u32);
+        data.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|pixel| u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]])),
+        );
+
+        self.0
+            .xcb_connection
+            .change_property32(
+                xproto::PropMode::REPLACE,
+                self.0.x_window,
+                self.0.state.borrow().atoms._NET_WM_ICON,
+                xproto::AtomEnum::CARDINAL,
+                &data,
+            )
+            .log_err();
+        self.0.xcb_connection.flush().log_err();
+    }
+
     fn set_edited(&mut self, _edited: bool) {
         log::info!("ignoring macOS specific set_edited");
     }
@@ -1232,6 +1263,10 @@ impl PlatformWindow for X11Window {
         inner.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<image::RgbaImage> {
+        self.0.state.borrow_mut().renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         let inner = self.0.state.borrow();
         inner.renderer.sprite_atlas().clone()
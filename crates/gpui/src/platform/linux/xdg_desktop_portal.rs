@@ -13,6 +13,8 @@ pub enum Event {
     WindowAppearance(WindowAppearance),
     CursorTheme(String),
     CursorSize(u32),
+    EnableAnimations(bool),
+    HighContrast(bool),
 }
 
 pub struct XDPEventSource {
@@ -47,6 +49,19 @@ impl XDPEventSource {
                     sender.send(Event::CursorSize(initial_size))?;
                 }
 
+                if let Ok(enable_animations) = settings
+                    .read::<bool>("org.gnome.desktop.interface", "enable-animations")
+                    .await
+                {
+                    sender.send(Event::EnableAnimations(enable_animations))?;
+                }
+                if let Ok(high_contrast) = settings
+                    .read::<bool>("org.gnome.desktop.a11y.interface", "high-contrast")
+                    .await
+                {
+                    sender.send(Event::HighContrast(high_contrast))?;
+                }
+
                 if let Ok(mut cursor_theme_changed) = settings
                     .receive_setting_changed_with_args(
                         "org.gnome.desktop.interface",
@@ -85,6 +100,44 @@ impl XDPEventSource {
                         .detach();
                 }
 
+                if let Ok(mut enable_animations_changed) = settings
+                    .receive_setting_changed_with_args::<bool>(
+                        "org.gnome.desktop.interface",
+                        "enable-animations",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(enable_animations) =
+                                enable_animations_changed.next().await
+                            {
+                                sender.send(Event::EnableAnimations(enable_animations?))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
+                if let Ok(mut high_contrast_changed) = settings
+                    .receive_setting_changed_with_args::<bool>(
+                        "org.gnome.desktop.a11y.interface",
+                        "high-contrast",
+                    )
+                    .await
+                {
+                    let sender = sender.clone();
+                    background
+                        .spawn(async move {
+                            while let Some(high_contrast) = high_contrast_changed.next().await {
+                                sender.send(Event::HighContrast(high_contrast?))?;
+                            }
+                            anyhow::Ok(())
+                        })
+                        .detach();
+                }
+
                 let mut appearance_changed = settings.receive_color_scheme_changed().await?;
                 while let Some(scheme) = appearance_changed.next().await {
                     sender.send(Event::WindowAppearance(WindowAppearance::from_native(
@@ -20,6 +20,7 @@ mod attributed_string;
 mod open_type;
 mod platform;
 mod text_system;
+mod web_view;
 mod window;
 mod window_appearance;
 
@@ -40,6 +41,7 @@ pub(crate) use display::*;
 pub(crate) use display_link::*;
 pub(crate) use platform::*;
 pub(crate) use text_system::*;
+pub(crate) use web_view::*;
 pub(crate) use window::*;
 
 trait BoolExt {
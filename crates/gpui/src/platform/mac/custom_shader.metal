@@ -0,0 +1,74 @@
+// Wraps the `custom_shader_main` function of a custom shader, whose source is appended to this
+// file, in a pipeline drawing `CustomPrimitive`s. Compiled at runtime, so it can't use the
+// definitions generated from the Rust types for the other shaders.
+
+#include <metal_stdlib>
+
+using namespace metal;
+
+struct CustomPrimitive {
+  uint order;
+  uint shader_id;
+  float2 origin;
+  float2 size;
+  float2 clip_origin;
+  float2 clip_size;
+  float uniforms[16];
+};
+
+struct CustomShaderInput {
+  float2 position;
+  float2 uv;
+  float2 size;
+  float uniforms[16];
+};
+
+float4 custom_shader_main(CustomShaderInput input);
+
+struct CustomVertexOutput {
+  float4 position [[position]];
+  uint primitive_id [[flat]];
+  float clip_distance [[clip_distance]][4];
+};
+
+struct CustomFragmentInput {
+  float4 position [[position]];
+  uint primitive_id [[flat]];
+};
+
+vertex CustomVertexOutput custom_vertex(
+    uint unit_vertex_id [[vertex_id]], uint primitive_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(0)]],
+    constant CustomPrimitive *primitives [[buffer(1)]],
+    constant int2 *viewport_size [[buffer(2)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  CustomPrimitive primitive = primitives[primitive_id];
+  float2 position = unit_vertex * primitive.size + primitive.origin;
+  float2 device_position =
+      position / float2(*viewport_size) * float2(2., -2.) + float2(-1., 1.);
+  float2 clip_end = primitive.clip_origin + primitive.clip_size;
+
+  CustomVertexOutput output;
+  output.position = float4(device_position, 0., 1.);
+  output.primitive_id = primitive_id;
+  output.clip_distance[0] = position.x - primitive.clip_origin.x;
+  output.clip_distance[1] = clip_end.x - position.x;
+  output.clip_distance[2] = position.y - primitive.clip_origin.y;
+  output.clip_distance[3] = clip_end.y - position.y;
+  return output;
+}
+
+fragment float4 custom_fragment(
+    CustomFragmentInput input [[stage_in]],
+    constant CustomPrimitive *primitives [[buffer(1)]]) {
+  CustomPrimitive primitive = primitives[input.primitive_id];
+  CustomShaderInput shader_input;
+  shader_input.position = input.position.xy;
+  shader_input.uv = (input.position.xy - primitive.origin) / primitive.size;
+  shader_input.size = primitive.size;
+  for (int i = 0; i < 16; i++) {
+    shader_input.uniforms[i] = primitive.uniforms[i];
+  }
+  return custom_shader_main(shader_input);
+}
+
@@ -1,13 +1,17 @@
-use crate::{px, size, Bounds, DisplayId, Pixels, PlatformDisplay};
+use crate::{px, size, Bounds, ColorSpace, DisplayId, Pixels, PlatformDisplay};
 use anyhow::Result;
 use cocoa::{
     appkit::NSScreen,
     base::{id, nil},
-    foundation::{NSDictionary, NSString},
+    foundation::{NSArray, NSDictionary, NSString},
 };
 use core_foundation::uuid::{CFUUIDGetUUIDBytes, CFUUIDRef};
 use core_graphics::display::{CGDirectDisplayID, CGDisplayBounds, CGGetActiveDisplayList};
-use objc::{msg_send, sel, sel_impl};
+use objc::{
+    msg_send,
+    runtime::{BOOL, YES},
+    sel, sel_impl,
+};
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -62,6 +66,21 @@ impl MacDisplay {
             }
         }
     }
+
+    /// The screen showing this display, if it's still connected.
+    unsafe fn screen(&self) -> Option<id> {
+        let screens = NSScreen::screens(nil);
+        let screen_number_key: id = NSString::alloc(nil).init_str("NSScreenNumber");
+        (0..screens.count())
+            .map(|index| screens.objectAtIndex(index))
+            .find(|&screen| {
+                let device_description = NSScreen::deviceDescription(screen);
+                let screen_number = device_description.objectForKey_(screen_number_key);
+                let screen_number: CGDirectDisplayID =
+                    msg_send![screen_number, unsignedIntegerValue];
+                screen_number == self.0
+            })
+    }
 }
 
 #[link(name = "ApplicationServices", kind = "framework")]
@@ -69,6 +88,9 @@ extern "C" {
     fn CGDisplayCreateUUIDFromDisplayID(display: CGDirectDisplayID) -> CFUUIDRef;
 }
 
+#[allow(non_upper_case_globals)]
+const NSDisplayGamutP3: isize = 2;
+
 impl PlatformDisplay for MacDisplay {
     fn id(&self) -> DisplayId {
         DisplayId(self.0)
@@ -102,6 +124,28 @@ impl PlatformDisplay for MacDisplay {
         ]))
     }
 
+    fn color_space(&self) -> ColorSpace {
+        unsafe {
+            let Some(screen) = self.screen() else {
+                return ColorSpace::Srgb;
+            };
+            let supports_display_p3: BOOL =
+                msg_send![screen, canRepresentDisplayGamut: NSDisplayGamutP3];
+            // Displays that can't show colors brighter than white report 1.
+            let max_extended_dynamic_range: f64 = msg_send![
+                screen,
+                maximumPotentialExtendedDynamicRangeColorComponentValue
+            ];
+            if supports_display_p3 != YES {
+                ColorSpace::Srgb
+            } else if max_extended_dynamic_range > 1. {
+                ColorSpace::ExtendedDisplayP3
+            } else {
+                ColorSpace::DisplayP3
+            }
+        }
+    }
+
     fn bounds(&self) -> Bounds<Pixels> {
         unsafe {
             // CGDisplayBounds is in "global display" coordinates, where 0 is
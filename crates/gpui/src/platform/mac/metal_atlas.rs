@@ -1,6 +1,6 @@
 use crate::{
-    AtlasKey, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, DevicePixels, PlatformAtlas,
-    Point, Size,
+    AtlasKey, AtlasLru, AtlasStats, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds,
+    DevicePixels, GlyphOutline, PlatformAtlas, Point, Size, LRU_TEXTURE_KINDS,
 };
 use anyhow::{anyhow, Result};
 use collections::FxHashMap;
@@ -20,6 +20,8 @@ impl MetalAtlas {
             polychrome_textures: Default::default(),
             path_textures: Default::default(),
             tiles_by_key: Default::default(),
+            lru: Default::default(),
+            pending_outlines: Default::default(),
         }))
     }
 
@@ -27,6 +29,21 @@ impl MetalAtlas {
         self.0.lock().texture(id).metal_texture.clone()
     }
 
+    /// The textures bound for a sprite batch, which are the ones allocated in the group starting
+    /// at the given texture.
+    pub(crate) fn batch_metal_textures(&self, base_id: AtlasTextureId) -> Vec<metal::Texture> {
+        let lock = self.0.lock();
+        let textures = lock.textures(base_id.kind);
+        let start = base_id.index as usize;
+        let end = textures
+            .len()
+            .min(start + AtlasTextureId::PER_BATCH as usize);
+        textures[start..end]
+            .iter()
+            .map(|texture| texture.metal_texture.clone())
+            .collect()
+    }
+
     pub(crate) fn allocate(
         &self,
         size: Size<DevicePixels>,
@@ -35,6 +52,12 @@ impl MetalAtlas {
         self.0.lock().allocate(size, texture_kind)
     }
 
+    /// Takes the outlines inserted since the last call, which have to be rasterized into their
+    /// tiles before those are drawn.
+    pub(crate) fn take_pending_outlines(&self) -> Vec<(AtlasTile, GlyphOutline)> {
+        std::mem::take(&mut self.0.lock().pending_outlines)
+    }
+
     pub(crate) fn clear_textures(&self, texture_kind: AtlasTextureKind) {
         let mut lock = self.0.lock();
         let textures = match texture_kind {
@@ -54,6 +77,8 @@ struct MetalAtlasState {
     polychrome_textures: Vec<MetalAtlasTexture>,
     path_textures: Vec<MetalAtlasTexture>,
     tiles_by_key: FxHashMap<AtlasKey, AtlasTile>,
+    lru: AtlasLru,
+    pending_outlines: Vec<(AtlasTile, GlyphOutline)>,
 }
 
 impl PlatformAtlas for MetalAtlas {
@@ -75,9 +100,91 @@ impl PlatformAtlas for MetalAtlas {
             let texture = lock.texture(tile.texture_id);
             texture.upload(tile.bounds, &bytes);
             lock.tiles_by_key.insert(key.clone(), tile.clone());
+            lock.lru.insert(key, &tile);
             Ok(Some(tile))
         }
     }
+
+    fn get_or_insert_outline(
+        &self,
+        key: &AtlasKey,
+        build: &mut dyn FnMut() -> Result<Option<GlyphOutline>>,
+    ) -> Result<Option<AtlasTile>> {
+        let mut lock = self.0.lock();
+        if let Some(tile) = lock.tiles_by_key.get(key) {
+            return Ok(Some(tile.clone()));
+        }
+
+        let Some(outline) = build()? else {
+            return Ok(None);
+        };
+        let tile = lock
+            .allocate(outline.size, AtlasTextureKind::Monochrome)
+            .ok_or_else(|| anyhow!("failed to allocate"))?;
+        lock.tiles_by_key.insert(key.clone(), tile.clone());
+        lock.lru.insert(key, &tile);
+        lock.pending_outlines.push((tile.clone(), outline));
+        Ok(Some(tile))
+    }
+
+    fn clear(&self) {
+        let state = &mut *self.0.lock();
+        state.tiles_by_key.clear();
+        state.lru.clear();
+        state.pending_outlines.clear();
+        for texture in state
+            .monochrome_textures
+            .iter_mut()
+            .chain(&mut state.polychrome_textures)
+        {
+            texture.clear();
+        }
+    }
+
+    fn finish_frame(&self, drawn_tiles: &mut dyn Iterator<Item = &AtlasTile>) {
+        let state = &mut *self.0.lock();
+        let evicted_keys = state.lru.finish_frame(drawn_tiles, || {
+            state
+                .monochrome_textures
+                .iter()
+                .chain(&state.polychrome_textures)
+                .map(|texture| (texture.id, texture.allocator.size().into()))
+                .collect()
+        });
+        if evicted_keys.is_empty() {
+            return;
+        }
+
+        for key in evicted_keys {
+            if let Some(tile) = state.tiles_by_key.remove(&key) {
+                state.texture_mut(tile.texture_id).deallocate(&tile);
+            }
+        }
+        // Textures can only be released from the end, since the others are indexed by the tiles
+        // allocated after them.
+        for kind in LRU_TEXTURE_KINDS {
+            while let Some(texture) = state.textures(kind).last() {
+                if state.lru.has_tiles(texture.id) {
+                    break;
+                }
+                state.textures_mut(kind).pop();
+            }
+        }
+    }
+
+    fn stats(&self) -> AtlasStats {
+        let state = self.0.lock();
+        let mut stats = state.lru.stats();
+        for texture in LRU_TEXTURE_KINDS
+            .into_iter()
+            .flat_map(|kind| state.textures(kind))
+        {
+            let size: Size<DevicePixels> = texture.allocator.size().into();
+            stats.texture_count += 1;
+            stats.texture_area += size.width.0 as u64 * size.height.0 as u64;
+        }
+        stats
+    }
 }
 
 impl MetalAtlasState {
@@ -124,8 +231,10 @@ impl MetalAtlasState {
         let usage;
         match kind {
             AtlasTextureKind::Monochrome => {
-                pixel_format = metal::MTLPixelFormat::A8Unorm;
-                usage = metal::MTLTextureUsage::ShaderRead;
+                // Glyph outlines are rasterized into monochrome textures by a compute kernel, which
+                // can't write to alpha-only textures.
+                pixel_format = metal::MTLPixelFormat::R8Unorm;
+                usage = metal::MTLTextureUsage::ShaderRead | metal::MTLTextureUsage::ShaderWrite;
             }
             AtlasTextureKind::Polychrome => {
                 pixel_format = metal::MTLPixelFormat::BGRA8Unorm;
@@ -158,12 +267,27 @@ impl MetalAtlasState {
     }
 
     fn texture(&self, id: AtlasTextureId) -> &MetalAtlasTexture {
-        let textures = match id.kind {
+        &self.textures(id.kind)[id.index as usize]
+    }
+
+    fn textures(&self, kind: AtlasTextureKind) -> &[MetalAtlasTexture] {
+        match kind {
             crate::AtlasTextureKind::Monochrome => &self.monochrome_textures,
             crate::AtlasTextureKind::Polychrome => &self.polychrome_textures,
             crate::AtlasTextureKind::Path => &self.path_textures,
-        };
-        &textures[id.index as usize]
+        }
+    }
+
+    fn texture_mut(&mut self, id: AtlasTextureId) -> &mut MetalAtlasTexture {
+        &mut self.textures_mut(id.kind)[id.index as usize]
+    }
+
+    fn textures_mut(&mut self, kind: AtlasTextureKind) -> &mut Vec<MetalAtlasTexture> {
+        match kind {
+            crate::AtlasTextureKind::Monochrome => &mut self.monochrome_textures,
+            crate::AtlasTextureKind::Polychrome => &mut self.polychrome_textures,
+            crate::AtlasTextureKind::Path => &mut self.path_textures,
+        }
     }
 }
 
@@ -178,6 +302,10 @@ impl MetalAtlasTexture {
         self.allocator.clear();
     }
 
+    fn deallocate(&mut self, tile: &AtlasTile) {
+        self.allocator.deallocate(tile.tile_id.into());
+    }
+
     fn allocate(&mut self, size: Size<DevicePixels>) -> Option<AtlasTile> {
         let allocation = self.allocator.allocate(size.into())?;
         let tile = AtlasTile {
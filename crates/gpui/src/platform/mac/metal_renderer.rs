@@ -1,8 +1,10 @@
 use super::metal_atlas::MetalAtlas;
 use crate::{
-    point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels,
-    Hsla, MonochromeSprite, PaintSurface, Path, PathId, PathVertex, PolychromeSprite,
-    PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size, Surface, Underline,
+    custom_shader_source, point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, BackdropBlur,
+    Background, BlendMode, Bounds, ColorSpace, ContentMask, CustomPrimitive, CustomShaderId,
+    DevicePixels, GlyphOutlineSegment, MonochromeSprite, OpacityGroup, PaintSurface, Path, PathId,
+    PathVertex, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size,
+    SolidQuad, Surface, SurfaceSource, Underline,
 };
 use anyhow::{anyhow, Result};
 use block::ConcreteBlock;
@@ -12,7 +14,8 @@ use cocoa::{
     quartzcore::AutoresizingMask,
 };
 use collections::HashMap;
-use core_foundation::base::TCFType;
+use core_foundation::{base::TCFType, string::CFStringRef};
+use core_graphics::color_space::CGColorSpace;
 use foreign_types::ForeignType;
 use media::core_video::CVMetalTextureCache;
 use metal::{CAMetalLayer, CommandQueue, MTLPixelFormat, MTLResourceOptions, NSRange};
@@ -38,8 +41,16 @@ pub unsafe fn new_renderer(
     _native_view: *mut c_void,
     _bounds: crate::Size<f32>,
     _transparent: bool,
+    color_space: ColorSpace,
 ) -> Renderer {
-    MetalRenderer::new(context)
+    MetalRenderer::new(context, color_space)
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    static kCGColorSpaceSRGB: CFStringRef;
+    static kCGColorSpaceDisplayP3: CFStringRef;
+    static kCGColorSpaceExtendedDisplayP3: CFStringRef;
 }
 
 pub(crate) struct InstanceBufferPool {
@@ -90,25 +101,45 @@ impl InstanceBufferPool {
 pub(crate) struct MetalRenderer {
     device: metal::Device,
     layer: metal::MetalLayer,
+    /// The pixel format of the layer, which everything drawn to it is rendered in.
+    pixel_format: MTLPixelFormat,
     presents_with_transaction: bool,
     command_queue: CommandQueue,
     paths_rasterization_pipeline_state: metal::RenderPipelineState,
+    glyph_rasterization_pipeline_state: metal::ComputePipelineState,
     path_sprites_pipeline_state: metal::RenderPipelineState,
     shadows_pipeline_state: metal::RenderPipelineState,
-    quads_pipeline_state: metal::RenderPipelineState,
+    backdrop_blurs_pipeline_state: metal::RenderPipelineState,
+    quads_pipeline_states: [metal::RenderPipelineState; BlendMode::ALL.len()],
+    solid_quads_pipeline_states: [metal::RenderPipelineState; BlendMode::ALL.len()],
     underlines_pipeline_state: metal::RenderPipelineState,
-    monochrome_sprites_pipeline_state: metal::RenderPipelineState,
-    polychrome_sprites_pipeline_state: metal::RenderPipelineState,
+    monochrome_sprites_pipeline_states: [metal::RenderPipelineState; BlendMode::ALL.len()],
+    polychrome_sprites_pipeline_states: [metal::RenderPipelineState; BlendMode::ALL.len()],
     surfaces_pipeline_state: metal::RenderPipelineState,
+    texture_surfaces_pipeline_state: metal::RenderPipelineState,
+    opacity_groups_pipeline_state: metal::RenderPipelineState,
+    /// The pipelines of the custom shaders drawn so far, or `None` for the ones that have no
+    /// Metal source or failed to compile.
+    custom_pipeline_states: HashMap<CustomShaderId, Option<metal::RenderPipelineState>>,
     unit_vertices: metal::Buffer,
     #[allow(clippy::arc_with_non_send_sync)]
     instance_buffer_pool: Arc<Mutex<InstanceBufferPool>>,
     sprite_atlas: Arc<MetalAtlas>,
+    /// A copy of the drawable, sampled by backdrop blurs. Recreated when the size changes.
+    backdrop_texture: Option<metal::Texture>,
+    /// The textures opacity groups are drawn to before being composited, indexed by group id.
+    opacity_group_textures: Vec<metal::Texture>,
+    /// The textures cached groups are drawn to, indexed by slot. They're kept from frame to
+    /// frame, so that the groups that paint the same as before aren't drawn again.
+    cached_group_textures: Vec<Option<metal::Texture>>,
     core_video_texture_cache: CVMetalTextureCache,
 }
 
 impl MetalRenderer {
-    pub fn new(instance_buffer_pool: Arc<Mutex<InstanceBufferPool>>) -> Self {
+    pub fn new(
+        instance_buffer_pool: Arc<Mutex<InstanceBufferPool>>,
+        color_space: ColorSpace,
+    ) -> Self {
         // Prefer low‐power integrated GPUs on Intel Mac. On Apple
         // Silicon, there is only ever one GPU, so this is equivalent to
         // `metal::Device::system_default()`.
@@ -119,11 +150,19 @@ impl MetalRenderer {
             std::process::exit(1);
         };
 
+        // Colors brighter than white need a floating point format to be represented.
+        let pixel_format = if color_space.is_extended_range() {
+            MTLPixelFormat::RGBA16Float
+        } else {
+            MTLPixelFormat::BGRA8Unorm
+        };
         let layer = metal::MetalLayer::new();
         layer.set_device(&device);
-        layer.set_pixel_format(MTLPixelFormat::BGRA8Unorm);
+        layer.set_pixel_format(pixel_format);
         layer.set_opaque(false);
         layer.set_maximum_drawable_count(3);
+        // Backdrop blurs copy the drawable, which can't be done when it's only a render target.
+        layer.set_framebuffer_only(false);
         unsafe {
             let _: () = msg_send![&*layer, setAllowsNextDrawableTimeout: NO];
             let _: () = msg_send![&*layer, setNeedsDisplayOnBoundsChange: YES];
@@ -132,6 +171,20 @@ impl MetalRenderer {
                 setAutoresizingMask: AutoresizingMask::WIDTH_SIZABLE
                     | AutoresizingMask::HEIGHT_SIZABLE
             ];
+
+            // Untagged layers are presented in the display's color space, which oversaturates
+            // sRGB colors on wide-gamut displays.
+            let color_space_name = match color_space {
+                ColorSpace::Srgb => kCGColorSpaceSRGB,
+                ColorSpace::DisplayP3 => kCGColorSpaceDisplayP3,
+                ColorSpace::ExtendedDisplayP3 => kCGColorSpaceExtendedDisplayP3,
+            };
+            if let Some(cg_color_space) = CGColorSpace::create_with_name(color_space_name) {
+                let _: () = msg_send![&*layer, setColorspace: cg_color_space.as_ptr()];
+            }
+            if color_space.is_extended_range() {
+                let _: () = msg_send![&*layer, setWantsExtendedDynamicRangeContent: YES];
+            }
         }
         #[cfg(feature = "runtime_shaders")]
         let library = device
@@ -142,6 +195,15 @@ impl MetalRenderer {
             .new_library_with_data(SHADERS_METALLIB)
             .expect("error building metal library");
 
+        // The shaders convert colors to the layer's color space when it's wider than sRGB.
+        let function_constants = metal::FunctionConstantValues::new();
+        let display_p3_output = color_space.is_wide_gamut();
+        function_constants.set_constant_value_at_index(
+            &display_p3_output as *const bool as *const c_void,
+            metal::MTLDataType::Bool,
+            0,
+        );
+
         fn to_float2_bits(point: PointF) -> u64 {
             let mut output = point.y.to_bits() as u64;
             output <<= 32;
@@ -166,66 +228,124 @@ impl MetalRenderer {
         let paths_rasterization_pipeline_state = build_path_rasterization_pipeline_state(
             &device,
             &library,
+            &function_constants,
             "paths_rasterization",
             "path_rasterization_vertex",
             "path_rasterization_fragment",
             MTLPixelFormat::R16Float,
         );
+        let glyph_rasterization_pipeline_state =
+            build_compute_pipeline_state(&device, &library, "glyph_rasterization");
         let path_sprites_pipeline_state = build_pipeline_state(
             &device,
             &library,
+            &function_constants,
             "path_sprites",
             "path_sprite_vertex",
             "path_sprite_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            pixel_format,
         );
         let shadows_pipeline_state = build_pipeline_state(
             &device,
             &library,
+            &function_constants,
             "shadows",
             "shadow_vertex",
             "shadow_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            pixel_format,
         );
-        let quads_pipeline_state = build_pipeline_state(
+        let backdrop_blurs_pipeline_state = build_pipeline_state(
             &device,
             &library,
-            "quads",
-            "quad_vertex",
-            "quad_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            &function_constants,
+            "backdrop_blurs",
+            "backdrop_blur_vertex",
+            "backdrop_blur_fragment",
+            pixel_format,
         );
+        let quads_pipeline_states = BlendMode::ALL.map(|blend_mode| {
+            build_blended_pipeline_state(
+                &device,
+                &library,
+                &function_constants,
+                "quads",
+                "quad_vertex",
+                "quad_fragment",
+                pixel_format,
+                blend_mode,
+            )
+        });
+        let solid_quads_pipeline_states = BlendMode::ALL.map(|blend_mode| {
+            build_blended_pipeline_state(
+                &device,
+                &library,
+                &function_constants,
+                "solid_quads",
+                "solid_quad_vertex",
+                "solid_quad_fragment",
+                pixel_format,
+                blend_mode,
+            )
+        });
         let underlines_pipeline_state = build_pipeline_state(
             &device,
             &library,
+            &function_constants,
             "underlines",
             "underline_vertex",
             "underline_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            pixel_format,
         );
-        let monochrome_sprites_pipeline_state = build_pipeline_state(
+        let monochrome_sprites_pipeline_states = BlendMode::ALL.map(|blend_mode| {
+            build_blended_pipeline_state(
+                &device,
+                &library,
+                &function_constants,
+                "monochrome_sprites",
+                "monochrome_sprite_vertex",
+                "monochrome_sprite_fragment",
+                pixel_format,
+                blend_mode,
+            )
+        });
+        let polychrome_sprites_pipeline_states = BlendMode::ALL.map(|blend_mode| {
+            build_blended_pipeline_state(
+                &device,
+                &library,
+                &function_constants,
+                "polychrome_sprites",
+                "polychrome_sprite_vertex",
+                "polychrome_sprite_fragment",
+                pixel_format,
+                blend_mode,
+            )
+        });
+        let surfaces_pipeline_state = build_pipeline_state(
             &device,
             &library,
-            "monochrome_sprites",
-            "monochrome_sprite_vertex",
-            "monochrome_sprite_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            &function_constants,
+            "surfaces",
+            "surface_vertex",
+            "surface_fragment",
+            pixel_format,
         );
-        let polychrome_sprites_pipeline_state = build_pipeline_state(
+        let texture_surfaces_pipeline_state = build_pipeline_state(
             &device,
             &library,
-            "polychrome_sprites",
-            "polychrome_sprite_vertex",
-            "polychrome_sprite_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            &function_constants,
+            "texture_surfaces",
+            "surface_vertex",
+            "texture_surface_fragment",
+            pixel_format,
         );
-        let surfaces_pipeline_state = build_pipeline_state(
+        let opacity_groups_pipeline_state = build_pipeline_state(
             &device,
             &library,
-            "surfaces",
-            "surface_vertex",
-            "surface_fragment",
-            MTLPixelFormat::BGRA8Unorm,
+            &function_constants,
+            "opacity_groups",
+            "polychrome_sprite_vertex",
+            "opacity_group_fragment",
+            pixel_format,
         );
 
         let command_queue = device.new_command_queue();
@@ -236,19 +356,29 @@ impl MetalRenderer {
         Self {
             device,
             layer,
+            pixel_format,
             presents_with_transaction: false,
             command_queue,
             paths_rasterization_pipeline_state,
+            glyph_rasterization_pipeline_state,
             path_sprites_pipeline_state,
             shadows_pipeline_state,
-            quads_pipeline_state,
+            backdrop_blurs_pipeline_state,
+            quads_pipeline_states,
+            solid_quads_pipeline_states,
             underlines_pipeline_state,
-            monochrome_sprites_pipeline_state,
-            polychrome_sprites_pipeline_state,
+            monochrome_sprites_pipeline_states,
+            polychrome_sprites_pipeline_states,
             surfaces_pipeline_state,
+            texture_surfaces_pipeline_state,
+            opacity_groups_pipeline_state,
+            custom_pipeline_states: HashMap::default(),
             unit_vertices,
             instance_buffer_pool,
             sprite_atlas,
+            backdrop_texture: None,
+            opacity_group_textures: Vec::new(),
+            cached_group_textures: Vec::new(),
             core_video_texture_cache,
         }
     }
@@ -293,6 +423,10 @@ impl MetalRenderer {
     }
 
     pub fn draw(&mut self, scene: &Scene) {
+        // Glyphs are rasterized in a command buffer of their own, so that they're not lost when
+        // drawing the scene is retried.
+        self.rasterize_glyph_outlines();
+
         let layer = self.layer.clone();
         let viewport_size = layer.drawable_size();
         let viewport_size: Size<DevicePixels> = size(
@@ -312,8 +446,12 @@ impl MetalRenderer {
         loop {
             let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
 
-            let command_buffer =
-                self.draw_primitives(scene, &mut instance_buffer, drawable, viewport_size);
+            let command_buffer = self.draw_primitives(
+                scene,
+                &mut instance_buffer,
+                drawable.texture(),
+                viewport_size,
+            );
 
             match command_buffer {
                 Ok(command_buffer) => {
@@ -342,6 +480,8 @@ impl MetalRenderer {
                         "failed to render: {}. retrying with larger instance buffer size",
                         err
                     );
+                    // Cached groups may have been left half drawn.
+                    self.cached_group_textures.clear();
                     let mut instance_buffer_pool = self.instance_buffer_pool.lock();
                     let buffer_size = instance_buffer_pool.buffer_size;
                     if buffer_size >= 256 * 1024 * 1024 {
@@ -358,21 +498,171 @@ impl MetalRenderer {
         }
     }
 
+    pub fn render_to_image(&mut self, scene: &Scene) -> Result<image::RgbaImage> {
+        self.rasterize_glyph_outlines();
+
+        let drawable_size = self.layer.drawable_size();
+        let width = drawable_size.width.ceil() as u64;
+        let height = drawable_size.height.ceil() as u64;
+        let viewport_size: Size<DevicePixels> = size((width as i32).into(), (height as i32).into());
+
+        let texture_descriptor = metal::TextureDescriptor::new();
+        texture_descriptor.set_width(width);
+        texture_descriptor.set_height(height);
+        texture_descriptor.set_pixel_format(self.pixel_format);
+        texture_descriptor
+            .set_usage(metal::MTLTextureUsage::RenderTarget | metal::MTLTextureUsage::ShaderRead);
+        texture_descriptor.set_storage_mode(metal::MTLStorageMode::Managed);
+        let texture = self.device.new_texture(&texture_descriptor);
+
+        let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
+        let command_buffer =
+            self.draw_primitives(scene, &mut instance_buffer, &texture, viewport_size);
+        let command_buffer = match command_buffer {
+            Ok(command_buffer) => command_buffer,
+            Err(error) => {
+                self.instance_buffer_pool.lock().release(instance_buffer);
+                return Err(error);
+            }
+        };
+        let blit_encoder = command_buffer.new_blit_command_encoder();
+        blit_encoder.synchronize_resource(&texture);
+        blit_encoder.end_encoding();
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+        self.instance_buffer_pool.lock().release(instance_buffer);
+
+        let bytes_per_pixel = match self.pixel_format {
+            MTLPixelFormat::RGBA16Float => 8,
+            _ => 4,
+        };
+        let mut bytes = vec![0u8; (width * height * bytes_per_pixel) as usize];
+        texture.get_bytes(
+            bytes.as_mut_ptr() as *mut c_void,
+            width * bytes_per_pixel,
+            metal::MTLRegion::new_2d(0, 0, width, height),
+            0,
+        );
+
+        let pixels = if self.pixel_format == MTLPixelFormat::RGBA16Float {
+            // Colors brighter than white are clamped, as they can't be represented in 8 bits.
+            bytes
+                .chunks_exact(2)
+                .map(|channel| {
+                    let channel = f16_to_f32(u16::from_le_bytes([channel[0], channel[1]]));
+                    (channel.clamp(0., 1.) * 255.).round() as u8
+                })
+                .collect()
+        } else {
+            for pixel in bytes.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            bytes
+        };
+        image::RgbaImage::from_raw(width as u32, height as u32, pixels)
+            .ok_or_else(|| anyhow!("the frame doesn't match its size"))
+    }
+
     fn draw_primitives(
         &mut self,
         scene: &Scene,
         instance_buffer: &mut InstanceBuffer,
-        drawable: &metal::MetalDrawableRef,
+        target: &metal::TextureRef,
         viewport_size: Size<DevicePixels>,
     ) -> Result<metal::CommandBuffer> {
         let command_queue = self.command_queue.clone();
         let command_buffer = command_queue.new_command_buffer();
         let mut instance_offset = 0;
 
+        for (slot, texture) in self.cached_group_textures.iter_mut().enumerate() {
+            if !scene.cached_group_slots.contains(&slot) {
+                texture.take();
+            }
+        }
+        self.draw_opacity_groups(
+            scene,
+            instance_buffer,
+            &mut instance_offset,
+            viewport_size,
+            command_buffer,
+        )?;
+        let alpha = if self.layer.is_opaque() { 1. } else { 0. };
+        self.draw_scene(
+            scene,
+            target,
+            alpha,
+            instance_buffer,
+            &mut instance_offset,
+            viewport_size,
+            command_buffer,
+        )?;
+
+        instance_buffer.metal_buffer.did_modify_range(NSRange {
+            location: 0,
+            length: instance_offset as NSUInteger,
+        });
+        Ok(command_buffer.to_owned())
+    }
+
+    /// Draws the contents of every opacity group in the scene to its own texture, innermost
+    /// groups first so that they can be composited into the groups containing them. Cached
+    /// groups that are unchanged since they were last drawn are skipped, along with the groups
+    /// inside them.
+    fn draw_opacity_groups(
+        &mut self,
+        scene: &Scene,
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_buffer: &metal::CommandBufferRef,
+    ) -> Result<()> {
+        for group in &scene.opacity_groups {
+            let texture = if let Some(cache) = group.cache {
+                let (texture, is_new) = self.cached_group_texture(cache.slot, viewport_size);
+                if cache.unchanged && !is_new {
+                    continue;
+                }
+                texture
+            } else {
+                self.opacity_group_texture(group.id, viewport_size)
+            };
+            self.draw_opacity_groups(
+                &group.scene,
+                instance_buffer,
+                instance_offset,
+                viewport_size,
+                command_buffer,
+            )?;
+            self.draw_scene(
+                &group.scene,
+                &texture,
+                0.,
+                instance_buffer,
+                instance_offset,
+                viewport_size,
+                command_buffer,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rasterizes the scene's paths and draws the scene into the target, after clearing it
+    /// with the given alpha.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_scene(
+        &mut self,
+        scene: &Scene,
+        target: &metal::TextureRef,
+        clear_alpha: f64,
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_buffer: &metal::CommandBufferRef,
+    ) -> Result<()> {
         let Some(path_tiles) = self.rasterize_paths(
             scene.paths(),
             instance_buffer,
-            &mut instance_offset,
+            instance_offset,
             command_buffer,
         ) else {
             return Err(anyhow!("failed to rasterize {} paths", scene.paths().len()));
@@ -384,35 +674,77 @@ impl MetalRenderer {
             .object_at(0)
             .unwrap();
 
-        color_attachment.set_texture(Some(drawable.texture()));
+        color_attachment.set_texture(Some(target));
         color_attachment.set_load_action(metal::MTLLoadAction::Clear);
         color_attachment.set_store_action(metal::MTLStoreAction::Store);
-        let alpha = if self.layer.is_opaque() { 1. } else { 0. };
-        color_attachment.set_clear_color(metal::MTLClearColor::new(0., 0., 0., alpha));
-        let command_encoder = command_buffer.new_render_command_encoder(render_pass_descriptor);
-
-        command_encoder.set_viewport(metal::MTLViewport {
+        color_attachment.set_clear_color(metal::MTLClearColor::new(0., 0., 0., clear_alpha));
+        let viewport = metal::MTLViewport {
             originX: 0.0,
             originY: 0.0,
             width: i32::from(viewport_size.width) as f64,
             height: i32::from(viewport_size.height) as f64,
             znear: 0.0,
             zfar: 1.0,
-        });
+        };
+        let mut command_encoder = command_buffer.new_render_command_encoder(render_pass_descriptor);
+        command_encoder.set_viewport(viewport);
 
         for batch in scene.batches() {
             let ok = match batch {
                 PrimitiveBatch::Shadows(shadows) => self.draw_shadows(
                     shadows,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
+                PrimitiveBatch::BackdropBlurs(blurs) => {
+                    // Blurs sample what has been drawn so far, which has to be copied out of
+                    // the target outside of the render pass drawing into it.
+                    command_encoder.end_encoding();
+                    let backdrop_texture = self.backdrop_texture(target);
+                    let blit_encoder = command_buffer.new_blit_command_encoder();
+                    blit_encoder.copy_from_texture(
+                        target,
+                        0,
+                        0,
+                        metal::MTLOrigin { x: 0, y: 0, z: 0 },
+                        metal::MTLSize {
+                            width: backdrop_texture.width(),
+                            height: backdrop_texture.height(),
+                            depth: 1,
+                        },
+                        &backdrop_texture,
+                        0,
+                        0,
+                        metal::MTLOrigin { x: 0, y: 0, z: 0 },
+                    );
+                    blit_encoder.end_encoding();
+
+                    color_attachment.set_load_action(metal::MTLLoadAction::Load);
+                    command_encoder =
+                        command_buffer.new_render_command_encoder(render_pass_descriptor);
+                    command_encoder.set_viewport(viewport);
+                    self.draw_backdrop_blurs(
+                        blurs,
+                        &backdrop_texture,
+                        instance_buffer,
+                        instance_offset,
+                        viewport_size,
+                        command_encoder,
+                    )
+                }
                 PrimitiveBatch::Quads(quads) => self.draw_quads(
                     quads,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
+                    viewport_size,
+                    command_encoder,
+                ),
+                PrimitiveBatch::SolidQuads(quads) => self.draw_solid_quads(
+                    quads,
+                    instance_buffer,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
@@ -420,43 +752,61 @@ impl MetalRenderer {
                     paths,
                     &path_tiles,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
                 PrimitiveBatch::Underlines(underlines) => self.draw_underlines(
                     underlines,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
                 PrimitiveBatch::MonochromeSprites {
-                    texture_id,
+                    base_texture_id,
                     sprites,
                 } => self.draw_monochrome_sprites(
-                    texture_id,
+                    base_texture_id,
                     sprites,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
                 PrimitiveBatch::PolychromeSprites {
-                    texture_id,
+                    base_texture_id,
                     sprites,
                 } => self.draw_polychrome_sprites(
-                    texture_id,
+                    base_texture_id,
                     sprites,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
                 PrimitiveBatch::Surfaces(surfaces) => self.draw_surfaces(
                     surfaces,
                     instance_buffer,
-                    &mut instance_offset,
+                    instance_offset,
+                    viewport_size,
+                    command_encoder,
+                ),
+                PrimitiveBatch::Custom {
+                    shader_id,
+                    primitives,
+                } => self.draw_custom_primitives(
+                    shader_id,
+                    primitives,
+                    instance_buffer,
+                    instance_offset,
+                    viewport_size,
+                    command_encoder,
+                ),
+                PrimitiveBatch::OpacityGroups(groups) => self.draw_opacity_group_sprites(
+                    groups,
+                    instance_buffer,
+                    instance_offset,
                     viewport_size,
                     command_encoder,
                 ),
@@ -464,25 +814,23 @@ impl MetalRenderer {
 
             if !ok {
                 command_encoder.end_encoding();
-                return Err(anyhow!("scene too large: {} paths, {} shadows, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
+                return Err(anyhow!("scene too large: {} paths, {} shadows, {} backdrop blurs, {} quads, {} underlines, {} mono, {} poly, {} surfaces, {} custom, {} opacity groups",
                     scene.paths.len(),
                     scene.shadows.len(),
+                    scene.backdrop_blurs.len(),
                     scene.quads.len(),
                     scene.underlines.len(),
                     scene.monochrome_sprites.len(),
                     scene.polychrome_sprites.len(),
                     scene.surfaces.len(),
+                    scene.custom_primitives.len(),
+                    scene.opacity_groups.len(),
                 ));
             }
         }
 
         command_encoder.end_encoding();
-
-        instance_buffer.metal_buffer.did_modify_range(NSRange {
-            location: 0,
-            length: instance_offset as NSUInteger,
-        });
-        Ok(command_buffer.to_owned())
+        Ok(())
     }
 
     fn rasterize_paths(
@@ -575,6 +923,77 @@ impl MetalRenderer {
         Some(tiles)
     }
 
+    /// Rasterizes the glyph outlines inserted into the atlas since the last frame into their tiles.
+    fn rasterize_glyph_outlines(&mut self) {
+        let outlines = self.sprite_atlas.take_pending_outlines();
+        if outlines.is_empty() {
+            return;
+        }
+
+        let mut segments = Vec::new();
+        let mut jobs_by_texture_id =
+            HashMap::<AtlasTextureId, Vec<GlyphRasterizationJob>>::default();
+        for (tile, outline) in outlines {
+            jobs_by_texture_id
+                .entry(tile.texture_id)
+                .or_default()
+                .push(GlyphRasterizationJob {
+                    tile_bounds: tile.bounds,
+                    segment_start: segments.len() as u32,
+                    segment_count: outline.segments.len() as u32,
+                });
+            segments.extend(outline.segments);
+        }
+        // Metal doesn't allow binding empty buffers.
+        if segments.is_empty() {
+            segments.push(GlyphOutlineSegment::default());
+        }
+
+        let segments_buffer = self.device.new_buffer_with_data(
+            segments.as_ptr() as *const c_void,
+            mem::size_of_val(segments.as_slice()) as u64,
+            MTLResourceOptions::StorageModeManaged,
+        );
+        let command_buffer = self.command_queue.new_command_buffer();
+        for (texture_id, jobs) in jobs_by_texture_id {
+            let texture = self.sprite_atlas.metal_texture(texture_id);
+            let command_encoder = command_buffer.new_compute_command_encoder();
+            command_encoder.set_compute_pipeline_state(&self.glyph_rasterization_pipeline_state);
+            command_encoder.set_buffer(
+                GlyphRasterizationInputIndex::Segments as u64,
+                Some(&segments_buffer),
+                0,
+            );
+            command_encoder.set_texture(
+                GlyphRasterizationInputIndex::AtlasTexture as u64,
+                Some(&texture),
+            );
+            for job in jobs {
+                command_encoder.set_bytes(
+                    GlyphRasterizationInputIndex::Job as u64,
+                    mem::size_of_val(&job) as u64,
+                    &job as *const GlyphRasterizationJob as *const _,
+                );
+                let width = job.tile_bounds.size.width.0 as u64;
+                let height = job.tile_bounds.size.height.0 as u64;
+                command_encoder.dispatch_thread_groups(
+                    metal::MTLSize::new(
+                        width.div_ceil(GLYPH_RASTERIZATION_GROUP_SIZE),
+                        height.div_ceil(GLYPH_RASTERIZATION_GROUP_SIZE),
+                        1,
+                    ),
+                    metal::MTLSize::new(
+                        GLYPH_RASTERIZATION_GROUP_SIZE,
+                        GLYPH_RASTERIZATION_GROUP_SIZE,
+                        1,
+                    ),
+                );
+            }
+            command_encoder.end_encoding();
+        }
+        command_buffer.commit();
+    }
+
     fn draw_shadows(
         &mut self,
         shadows: &[Shadow],
@@ -638,6 +1057,151 @@ impl MetalRenderer {
         true
     }
 
+    fn backdrop_texture(&mut self, drawable_texture: &metal::TextureRef) -> metal::Texture {
+        let width = drawable_texture.width();
+        let height = drawable_texture.height();
+        if let Some(texture) = self.backdrop_texture.as_ref() {
+            if texture.width() == width && texture.height() == height {
+                return texture.clone();
+            }
+        }
+
+        let texture_descriptor = metal::TextureDescriptor::new();
+        texture_descriptor.set_width(width);
+        texture_descriptor.set_height(height);
+        texture_descriptor.set_pixel_format(self.pixel_format);
+        texture_descriptor.set_usage(metal::MTLTextureUsage::ShaderRead);
+        texture_descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+        let texture = self.device.new_texture(&texture_descriptor);
+        self.backdrop_texture = Some(texture.clone());
+        texture
+    }
+
+    fn opacity_group_texture(
+        &mut self,
+        id: usize,
+        viewport_size: Size<DevicePixels>,
+    ) -> metal::Texture {
+        let width = viewport_size.width.0 as u64;
+        let height = viewport_size.height.0 as u64;
+        if self
+            .opacity_group_textures
+            .first()
+            .map_or(false, |texture| {
+                texture.width() != width || texture.height() != height
+            })
+        {
+            self.opacity_group_textures.clear();
+        }
+
+        while self.opacity_group_textures.len() <= id {
+            let texture_descriptor = metal::TextureDescriptor::new();
+            texture_descriptor.set_width(width);
+            texture_descriptor.set_height(height);
+            texture_descriptor.set_pixel_format(self.pixel_format);
+            texture_descriptor.set_usage(
+                metal::MTLTextureUsage::RenderTarget | metal::MTLTextureUsage::ShaderRead,
+            );
+            texture_descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+            self.opacity_group_textures
+                .push(self.device.new_texture(&texture_descriptor));
+        }
+        self.opacity_group_textures[id].clone()
+    }
+
+    /// Returns the texture of the given cache slot, and whether it was just created, so has
+    /// nothing drawn to it yet.
+    fn cached_group_texture(
+        &mut self,
+        slot: usize,
+        viewport_size: Size<DevicePixels>,
+    ) -> (metal::Texture, bool) {
+        let width = viewport_size.width.0 as u64;
+        let height = viewport_size.height.0 as u64;
+        if self.cached_group_textures.len() <= slot {
+            self.cached_group_textures.resize(slot + 1, None);
+        }
+        if let Some(texture) = &self.cached_group_textures[slot] {
+            if texture.width() == width && texture.height() == height {
+                return (texture.clone(), false);
+            }
+        }
+
+        let texture_descriptor = metal::TextureDescriptor::new();
+        texture_descriptor.set_width(width);
+        texture_descriptor.set_height(height);
+        texture_descriptor.set_pixel_format(self.pixel_format);
+        texture_descriptor
+            .set_usage(metal::MTLTextureUsage::RenderTarget | metal::MTLTextureUsage::ShaderRead);
+        texture_descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+        let texture = self.device.new_texture(&texture_descriptor);
+        self.cached_group_textures[slot] = Some(texture.clone());
+        (texture, true)
+    }
+
+    fn draw_backdrop_blurs(
+        &mut self,
+        blurs: &[BackdropBlur],
+        backdrop_texture: &metal::TextureRef,
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) -> bool {
+        if blurs.is_empty() {
+            return true;
+        }
+        align_offset(instance_offset);
+
+        command_encoder.set_render_pipeline_state(&self.backdrop_blurs_pipeline_state);
+        command_encoder.set_vertex_buffer(
+            BackdropBlurInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_buffer(
+            BackdropBlurInputIndex::BackdropBlurs as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_fragment_buffer(
+            BackdropBlurInputIndex::BackdropBlurs as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_vertex_bytes(
+            BackdropBlurInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+        command_encoder.set_fragment_texture(
+            BackdropBlurInputIndex::BackdropTexture as u64,
+            Some(backdrop_texture),
+        );
+
+        let blur_bytes_len = mem::size_of_val(blurs);
+        let buffer_contents =
+            unsafe { (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset) };
+
+        let next_offset = *instance_offset + blur_bytes_len;
+        if next_offset > instance_buffer.size {
+            return false;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(blurs.as_ptr() as *const u8, buffer_contents, blur_bytes_len);
+        }
+
+        command_encoder.draw_primitives_instanced(
+            metal::MTLPrimitiveType::Triangle,
+            0,
+            6,
+            blurs.len() as u64,
+        );
+        *instance_offset = next_offset;
+        true
+    }
+
     fn draw_quads(
         &mut self,
         quads: &[Quad],
@@ -651,7 +1215,8 @@ impl MetalRenderer {
         }
         align_offset(instance_offset);
 
-        command_encoder.set_render_pipeline_state(&self.quads_pipeline_state);
+        command_encoder
+            .set_render_pipeline_state(&self.quads_pipeline_states[quads[0].blend_mode as usize]);
         command_encoder.set_vertex_buffer(
             QuadInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -697,6 +1262,170 @@ impl MetalRenderer {
         true
     }
 
+    fn draw_solid_quads(
+        &mut self,
+        quads: &[Quad],
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) -> bool {
+        if quads.is_empty() {
+            return true;
+        }
+        align_offset(instance_offset);
+
+        command_encoder.set_render_pipeline_state(
+            &self.solid_quads_pipeline_states[quads[0].blend_mode as usize],
+        );
+        command_encoder.set_vertex_buffer(
+            SolidQuadInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_buffer(
+            SolidQuadInputIndex::SolidQuads as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_vertex_bytes(
+            SolidQuadInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+
+        let solid_quad_bytes_len = mem::size_of::<SolidQuad>() * quads.len();
+        let next_offset = *instance_offset + solid_quad_bytes_len;
+        if next_offset > instance_buffer.size {
+            return false;
+        }
+
+        // The compact instances are written straight into the instance buffer.
+        let buffer_contents = unsafe {
+            (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset)
+                as *mut SolidQuad
+        };
+        for (ix, quad) in quads.iter().enumerate() {
+            unsafe {
+                buffer_contents.add(ix).write(SolidQuad::from(quad));
+            }
+        }
+
+        command_encoder.draw_primitives_instanced(
+            metal::MTLPrimitiveType::Triangle,
+            0,
+            6,
+            quads.len() as u64,
+        );
+        *instance_offset = next_offset;
+        true
+    }
+
+    /// The pipeline drawing primitives with the given custom shader, which is compiled the first
+    /// time it's drawn.
+    fn custom_pipeline_state(
+        &mut self,
+        shader_id: CustomShaderId,
+    ) -> Option<&metal::RenderPipelineState> {
+        let device = &self.device;
+        let pixel_format = self.pixel_format;
+        self.custom_pipeline_states
+            .entry(shader_id)
+            .or_insert_with(|| {
+                let source = custom_shader_source(shader_id)?;
+                let Some(msl) = source.msl else {
+                    log::error!("custom shader {:?} has no Metal source", source.label);
+                    return None;
+                };
+                let msl = format!("{}\n{}", include_str!("custom_shader.metal"), msl);
+                let library = device
+                    .new_library_with_source(&msl, &metal::CompileOptions::new())
+                    .map_err(|error| {
+                        log::error!(
+                            "failed to compile custom shader {:?}: {error}",
+                            source.label
+                        )
+                    })
+                    .ok()?;
+                Some(build_pipeline_state(
+                    device,
+                    &library,
+                    &metal::FunctionConstantValues::new(),
+                    &source.label,
+                    "custom_vertex",
+                    "custom_fragment",
+                    pixel_format,
+                ))
+            })
+            .as_ref()
+    }
+
+    fn draw_custom_primitives(
+        &mut self,
+        shader_id: CustomShaderId,
+        primitives: &[CustomPrimitive],
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) -> bool {
+        if primitives.is_empty() {
+            return true;
+        }
+        let Some(pipeline_state) = self.custom_pipeline_state(shader_id).cloned() else {
+            return true;
+        };
+        align_offset(instance_offset);
+
+        command_encoder.set_render_pipeline_state(&pipeline_state);
+        command_encoder.set_vertex_buffer(
+            CustomInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_buffer(
+            CustomInputIndex::Primitives as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_fragment_buffer(
+            CustomInputIndex::Primitives as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_vertex_bytes(
+            CustomInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+
+        let primitive_bytes_len = mem::size_of_val(primitives);
+        let buffer_contents =
+            unsafe { (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset) };
+
+        let next_offset = *instance_offset + primitive_bytes_len;
+        if next_offset > instance_buffer.size {
+            return false;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                primitives.as_ptr() as *const u8,
+                buffer_contents,
+                primitive_bytes_len,
+            );
+        }
+
+        command_encoder.draw_primitives_instanced(
+            metal::MTLPrimitiveType::Triangle,
+            0,
+            6,
+            primitives.len() as u64,
+        );
+        *instance_offset = next_offset;
+        true
+    }
+
     fn draw_paths(
         &mut self,
         paths: &[Path<ScaledPixels>],
@@ -740,7 +1469,10 @@ impl MetalRenderer {
                             size: tile.bounds.size.map(Into::into),
                         },
                         color: path.color,
+                        path_bounds: path.bounds,
                         tile: (*tile).clone(),
+                        nonzero: path.nonzero as u32,
+                        pad: 0,
                     });
                     paths_and_tiles.next();
                     continue;
@@ -872,7 +1604,7 @@ impl MetalRenderer {
 
     fn draw_monochrome_sprites(
         &mut self,
-        texture_id: AtlasTextureId,
+        base_texture_id: AtlasTextureId,
         sprites: &[MonochromeSprite],
         instance_buffer: &mut InstanceBuffer,
         instance_offset: &mut usize,
@@ -893,12 +1625,9 @@ impl MetalRenderer {
             return false;
         }
 
-        let texture = self.sprite_atlas.metal_texture(texture_id);
-        let texture_size = size(
-            DevicePixels(texture.width() as i32),
-            DevicePixels(texture.height() as i32),
+        command_encoder.set_render_pipeline_state(
+            &self.monochrome_sprites_pipeline_states[sprites[0].blend_mode as usize],
         );
-        command_encoder.set_render_pipeline_state(&self.monochrome_sprites_pipeline_state);
         command_encoder.set_vertex_buffer(
             SpriteInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -914,17 +1643,12 @@ impl MetalRenderer {
             mem::size_of_val(&viewport_size) as u64,
             &viewport_size as *const Size<DevicePixels> as *const _,
         );
-        command_encoder.set_vertex_bytes(
-            SpriteInputIndex::AtlasTextureSize as u64,
-            mem::size_of_val(&texture_size) as u64,
-            &texture_size as *const Size<DevicePixels> as *const _,
-        );
         command_encoder.set_fragment_buffer(
             SpriteInputIndex::Sprites as u64,
             Some(&instance_buffer.metal_buffer),
             *instance_offset as u64,
         );
-        command_encoder.set_fragment_texture(SpriteInputIndex::AtlasTexture as u64, Some(&texture));
+        self.bind_sprite_atlas_textures(base_texture_id, command_encoder);
 
         unsafe {
             ptr::copy_nonoverlapping(
@@ -946,7 +1670,7 @@ impl MetalRenderer {
 
     fn draw_polychrome_sprites(
         &mut self,
-        texture_id: AtlasTextureId,
+        base_texture_id: AtlasTextureId,
         sprites: &[PolychromeSprite],
         instance_buffer: &mut InstanceBuffer,
         instance_offset: &mut usize,
@@ -958,12 +1682,9 @@ impl MetalRenderer {
         }
         align_offset(instance_offset);
 
-        let texture = self.sprite_atlas.metal_texture(texture_id);
-        let texture_size = size(
-            DevicePixels(texture.width() as i32),
-            DevicePixels(texture.height() as i32),
+        command_encoder.set_render_pipeline_state(
+            &self.polychrome_sprites_pipeline_states[sprites[0].blend_mode as usize],
         );
-        command_encoder.set_render_pipeline_state(&self.polychrome_sprites_pipeline_state);
         command_encoder.set_vertex_buffer(
             SpriteInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -979,17 +1700,12 @@ impl MetalRenderer {
             mem::size_of_val(&viewport_size) as u64,
             &viewport_size as *const Size<DevicePixels> as *const _,
         );
-        command_encoder.set_vertex_bytes(
-            SpriteInputIndex::AtlasTextureSize as u64,
-            mem::size_of_val(&texture_size) as u64,
-            &texture_size as *const Size<DevicePixels> as *const _,
-        );
         command_encoder.set_fragment_buffer(
             SpriteInputIndex::Sprites as u64,
             Some(&instance_buffer.metal_buffer),
             *instance_offset as u64,
         );
-        command_encoder.set_fragment_texture(SpriteInputIndex::AtlasTexture as u64, Some(&texture));
+        self.bind_sprite_atlas_textures(base_texture_id, command_encoder);
 
         let sprite_bytes_len = mem::size_of_val(sprites);
         let buffer_contents =
@@ -1018,6 +1734,114 @@ impl MetalRenderer {
         true
     }
 
+    /// Binds the atlas textures a sprite batch samples from, which the shaders index by each
+    /// sprite's [`AtlasTextureId::batch_slot`], along with their sizes.
+    fn bind_sprite_atlas_textures(
+        &self,
+        base_texture_id: AtlasTextureId,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) {
+        let textures = self.sprite_atlas.batch_metal_textures(base_texture_id);
+        let mut texture_sizes =
+            [Size::<DevicePixels>::default(); AtlasTextureId::PER_BATCH as usize];
+        for (texture_size, texture) in texture_sizes.iter_mut().zip(&textures) {
+            *texture_size = size(
+                DevicePixels(texture.width() as i32),
+                DevicePixels(texture.height() as i32),
+            );
+        }
+        // Slots past the last allocated texture are never sampled, but every texture the shaders
+        // declare has to be bound.
+        let bound_textures = (0..AtlasTextureId::PER_BATCH as usize)
+            .map(|slot| Some(&**textures.get(slot).unwrap_or(&textures[0])))
+            .collect::<Vec<_>>();
+
+        command_encoder.set_vertex_bytes(
+            SpriteInputIndex::AtlasTextureSize as u64,
+            mem::size_of_val(&texture_sizes) as u64,
+            texture_sizes.as_ptr() as *const _,
+        );
+        command_encoder
+            .set_fragment_textures(SpriteInputIndex::AtlasTexture as u64, &bound_textures);
+    }
+
+    fn draw_opacity_group_sprites(
+        &mut self,
+        groups: &[OpacityGroup],
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) -> bool {
+        command_encoder.set_render_pipeline_state(&self.opacity_groups_pipeline_state);
+        command_encoder.set_vertex_buffer(
+            SpriteInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_bytes(
+            SpriteInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+        // Groups are drawn to viewport-sized textures. The vertex shader looks up the size of the
+        // texture in the slot of the group's id, as it does for atlas textures.
+        let texture_sizes = [viewport_size; AtlasTextureId::PER_BATCH as usize];
+        command_encoder.set_vertex_bytes(
+            SpriteInputIndex::AtlasTextureSize as u64,
+            mem::size_of_val(&texture_sizes) as u64,
+            texture_sizes.as_ptr() as *const _,
+        );
+
+        for group in groups {
+            let texture = match group.cache {
+                Some(cache) => self
+                    .cached_group_textures
+                    .get(cache.slot)
+                    .and_then(Option::as_ref),
+                None => self.opacity_group_textures.get(group.id),
+            };
+            let Some(texture) = texture else {
+                continue;
+            };
+            align_offset(instance_offset);
+
+            let sprite = group.sprite();
+            let sprite_bytes_len = mem::size_of_val(&sprite);
+            let next_offset = *instance_offset + sprite_bytes_len;
+            if next_offset > instance_buffer.size {
+                return false;
+            }
+
+            command_encoder.set_vertex_buffer(
+                SpriteInputIndex::Sprites as u64,
+                Some(&instance_buffer.metal_buffer),
+                *instance_offset as u64,
+            );
+            command_encoder.set_fragment_buffer(
+                SpriteInputIndex::Sprites as u64,
+                Some(&instance_buffer.metal_buffer),
+                *instance_offset as u64,
+            );
+            command_encoder
+                .set_fragment_texture(SpriteInputIndex::AtlasTexture as u64, Some(texture));
+
+            unsafe {
+                let buffer_contents =
+                    (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset);
+                ptr::copy_nonoverlapping(
+                    &sprite as *const PolychromeSprite as *const u8,
+                    buffer_contents,
+                    sprite_bytes_len,
+                );
+            }
+
+            command_encoder.draw_primitives_instanced(metal::MTLPrimitiveType::Triangle, 0, 6, 1);
+            *instance_offset = next_offset;
+        }
+        true
+    }
+
     fn draw_surfaces(
         &mut self,
         surfaces: &[PaintSurface],
@@ -1026,7 +1850,6 @@ impl MetalRenderer {
         viewport_size: Size<DevicePixels>,
         command_encoder: &metal::RenderCommandEncoderRef,
     ) -> bool {
-        command_encoder.set_render_pipeline_state(&self.surfaces_pipeline_state);
         command_encoder.set_vertex_buffer(
             SurfaceInputIndex::Vertices as u64,
             Some(&self.unit_vertices),
@@ -1039,39 +1862,61 @@ impl MetalRenderer {
         );
 
         for surface in surfaces {
-            let texture_size = size(
-                DevicePixels::from(surface.image_buffer.width() as i32),
-                DevicePixels::from(surface.image_buffer.height() as i32),
-            );
-
-            assert_eq!(
-                surface.image_buffer.pixel_format_type(),
-                media::core_video::kCVPixelFormatType_420YpCbCr8BiPlanarFullRange
-            );
+            let texture_size = match &surface.source {
+                SurfaceSource::Surface(image_buffer) => {
+                    assert_eq!(
+                        image_buffer.pixel_format_type(),
+                        media::core_video::kCVPixelFormatType_420YpCbCr8BiPlanarFullRange
+                    );
 
-            let y_texture = unsafe {
-                self.core_video_texture_cache
-                    .create_texture_from_image(
-                        surface.image_buffer.as_concrete_TypeRef(),
-                        ptr::null(),
-                        MTLPixelFormat::R8Unorm,
-                        surface.image_buffer.plane_width(0),
-                        surface.image_buffer.plane_height(0),
-                        0,
-                    )
-                    .unwrap()
-            };
-            let cb_cr_texture = unsafe {
-                self.core_video_texture_cache
-                    .create_texture_from_image(
-                        surface.image_buffer.as_concrete_TypeRef(),
-                        ptr::null(),
-                        MTLPixelFormat::RG8Unorm,
-                        surface.image_buffer.plane_width(1),
-                        surface.image_buffer.plane_height(1),
-                        1,
+                    let y_texture = unsafe {
+                        self.core_video_texture_cache
+                            .create_texture_from_image(
+                                image_buffer.as_concrete_TypeRef(),
+                                ptr::null(),
+                                MTLPixelFormat::R8Unorm,
+                                image_buffer.plane_width(0),
+                                image_buffer.plane_height(0),
+                                0,
+                            )
+                            .unwrap()
+                    };
+                    let cb_cr_texture = unsafe {
+                        self.core_video_texture_cache
+                            .create_texture_from_image(
+                                image_buffer.as_concrete_TypeRef(),
+                                ptr::null(),
+                                MTLPixelFormat::RG8Unorm,
+                                image_buffer.plane_width(1),
+                                image_buffer.plane_height(1),
+                                1,
+                            )
+                            .unwrap()
+                    };
+
+                    command_encoder.set_render_pipeline_state(&self.surfaces_pipeline_state);
+                    command_encoder.set_fragment_texture(
+                        SurfaceInputIndex::YTexture as u64,
+                        Some(y_texture.as_texture_ref()),
+                    );
+                    command_encoder.set_fragment_texture(
+                        SurfaceInputIndex::CbCrTexture as u64,
+                        Some(cb_cr_texture.as_texture_ref()),
+                    );
+                    size(
+                        DevicePixels::from(image_buffer.width() as i32),
+                        DevicePixels::from(image_buffer.height() as i32),
                     )
-                    .unwrap()
+                }
+                SurfaceSource::Texture(texture) => {
+                    command_encoder
+                        .set_render_pipeline_state(&self.texture_surfaces_pipeline_state);
+                    command_encoder.set_fragment_texture(
+                        SurfaceInputIndex::Texture as u64,
+                        Some(texture.metal_texture()),
+                    );
+                    texture.size()
+                }
             };
 
             align_offset(instance_offset);
@@ -1090,14 +1935,6 @@ impl MetalRenderer {
                 mem::size_of_val(&texture_size) as u64,
                 &texture_size as *const Size<DevicePixels> as *const _,
             );
-            command_encoder.set_fragment_texture(
-                SurfaceInputIndex::YTexture as u64,
-                Some(y_texture.as_texture_ref()),
-            );
-            command_encoder.set_fragment_texture(
-                SurfaceInputIndex::CbCrTexture as u64,
-                Some(cb_cr_texture.as_texture_ref()),
-            );
 
             unsafe {
                 let buffer_contents = (instance_buffer.metal_buffer.contents() as *mut u8)
@@ -1122,16 +1959,17 @@ impl MetalRenderer {
 fn build_pipeline_state(
     device: &metal::DeviceRef,
     library: &metal::LibraryRef,
+    function_constants: &metal::FunctionConstantValuesRef,
     label: &str,
     vertex_fn_name: &str,
     fragment_fn_name: &str,
     pixel_format: metal::MTLPixelFormat,
 ) -> metal::RenderPipelineState {
     let vertex_fn = library
-        .get_function(vertex_fn_name, None)
+        .get_function(vertex_fn_name, Some(function_constants.to_owned()))
         .expect("error locating vertex function");
     let fragment_fn = library
-        .get_function(fragment_fn_name, None)
+        .get_function(fragment_fn_name, Some(function_constants.to_owned()))
         .expect("error locating fragment function");
 
     let descriptor = metal::RenderPipelineDescriptor::new();
@@ -1153,19 +1991,82 @@ fn build_pipeline_state(
         .expect("could not create render pipeline state")
 }
 
+/// Builds a pipeline for quads or sprites blended with what was drawn beneath them in the given
+/// mode. Fragment functions return premultiplied colors for modes other than normal.
+#[allow(clippy::too_many_arguments)]
+fn build_blended_pipeline_state(
+    device: &metal::DeviceRef,
+    library: &metal::LibraryRef,
+    function_constants: &metal::FunctionConstantValuesRef,
+    label: &str,
+    vertex_fn_name: &str,
+    fragment_fn_name: &str,
+    pixel_format: metal::MTLPixelFormat,
+    blend_mode: BlendMode,
+) -> metal::RenderPipelineState {
+    let function_constants = function_constants.to_owned();
+    let premultiplied_output = blend_mode != BlendMode::Normal;
+    function_constants.set_constant_value_at_index(
+        &premultiplied_output as *const bool as *const c_void,
+        metal::MTLDataType::Bool,
+        1,
+    );
+    let vertex_fn = library
+        .get_function(vertex_fn_name, Some(function_constants.clone()))
+        .expect("error locating vertex function");
+    let fragment_fn = library
+        .get_function(fragment_fn_name, Some(function_constants))
+        .expect("error locating fragment function");
+
+    let (source_rgb_blend_factor, destination_rgb_blend_factor) = match blend_mode {
+        BlendMode::Normal => (
+            metal::MTLBlendFactor::SourceAlpha,
+            metal::MTLBlendFactor::OneMinusSourceAlpha,
+        ),
+        BlendMode::Multiply => (
+            metal::MTLBlendFactor::DestinationColor,
+            metal::MTLBlendFactor::OneMinusSourceAlpha,
+        ),
+        BlendMode::Screen => (
+            metal::MTLBlendFactor::One,
+            metal::MTLBlendFactor::OneMinusSourceColor,
+        ),
+        BlendMode::PlusLighter => (metal::MTLBlendFactor::One, metal::MTLBlendFactor::One),
+    };
+
+    let descriptor = metal::RenderPipelineDescriptor::new();
+    descriptor.set_label(&format!("{label}_{blend_mode:?}"));
+    descriptor.set_vertex_function(Some(vertex_fn.as_ref()));
+    descriptor.set_fragment_function(Some(fragment_fn.as_ref()));
+    let color_attachment = descriptor.color_attachments().object_at(0).unwrap();
+    color_attachment.set_pixel_format(pixel_format);
+    color_attachment.set_blending_enabled(true);
+    color_attachment.set_rgb_blend_operation(metal::MTLBlendOperation::Add);
+    color_attachment.set_alpha_blend_operation(metal::MTLBlendOperation::Add);
+    color_attachment.set_source_rgb_blend_factor(source_rgb_blend_factor);
+    color_attachment.set_source_alpha_blend_factor(metal::MTLBlendFactor::One);
+    color_attachment.set_destination_rgb_blend_factor(destination_rgb_blend_factor);
+    color_attachment.set_destination_alpha_blend_factor(metal::MTLBlendFactor::One);
+
+    device
+        .new_render_pipeline_state(&descriptor)
+        .expect("could not create render pipeline state")
+}
+
 fn build_path_rasterization_pipeline_state(
     device: &metal::DeviceRef,
     library: &metal::LibraryRef,
+    function_constants: &metal::FunctionConstantValuesRef,
     label: &str,
     vertex_fn_name: &str,
     fragment_fn_name: &str,
     pixel_format: metal::MTLPixelFormat,
 ) -> metal::RenderPipelineState {
     let vertex_fn = library
-        .get_function(vertex_fn_name, None)
+        .get_function(vertex_fn_name, Some(function_constants.to_owned()))
         .expect("error locating vertex function");
     let fragment_fn = library
-        .get_function(fragment_fn_name, None)
+        .get_function(fragment_fn_name, Some(function_constants.to_owned()))
         .expect("error locating fragment function");
 
     let descriptor = metal::RenderPipelineDescriptor::new();
@@ -1187,6 +2088,19 @@ fn build_path_rasterization_pipeline_state(
         .expect("could not create render pipeline state")
 }
 
+fn build_compute_pipeline_state(
+    device: &metal::DeviceRef,
+    library: &metal::LibraryRef,
+    function_name: &str,
+) -> metal::ComputePipelineState {
+    let function = library
+        .get_function(function_name, None)
+        .expect("error locating compute function");
+    device
+        .new_compute_pipeline_state_with_function(&function)
+        .expect("could not create compute pipeline state")
+}
+
 // Align to multiples of 256 make Metal happy.
 fn align_offset(offset: &mut usize) {
     *offset = ((*offset + 255) / 256) * 256;
@@ -1199,6 +2113,14 @@ enum ShadowInputIndex {
     ViewportSize = 2,
 }
 
+#[repr(C)]
+enum BackdropBlurInputIndex {
+    Vertices = 0,
+    BackdropBlurs = 1,
+    ViewportSize = 2,
+    BackdropTexture = 3,
+}
+
 #[repr(C)]
 enum QuadInputIndex {
     Vertices = 0,
@@ -1206,6 +2128,13 @@ enum QuadInputIndex {
     ViewportSize = 2,
 }
 
+#[repr(C)]
+enum SolidQuadInputIndex {
+    Vertices = 0,
+    SolidQuads = 1,
+    ViewportSize = 2,
+}
+
 #[repr(C)]
 enum UnderlineInputIndex {
     Vertices = 0,
@@ -1230,6 +2159,16 @@ enum SurfaceInputIndex {
     TextureSize = 3,
     YTexture = 4,
     CbCrTexture = 5,
+    Texture = 6,
+}
+
+/// Matches the buffer indices in `custom_shader.metal`, which is compiled separately from the
+/// other shaders.
+#[repr(C)]
+enum CustomInputIndex {
+    Vertices = 0,
+    Primitives = 1,
+    ViewportSize = 2,
 }
 
 #[repr(C)]
@@ -1238,12 +2177,37 @@ enum PathRasterizationInputIndex {
     AtlasTextureSize = 1,
 }
 
+#[repr(C)]
+enum GlyphRasterizationInputIndex {
+    Job = 0,
+    Segments = 1,
+    AtlasTexture = 2,
+}
+
+/// The width and height of the thread groups rasterizing glyphs, each thread covering a pixel.
+const GLYPH_RASTERIZATION_GROUP_SIZE: u64 = 8;
+
+/// A glyph for the rasterization kernel to fill, by the nonzero winding rule of the outline
+/// segments in the given range.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct GlyphRasterizationJob {
+    pub tile_bounds: Bounds<DevicePixels>,
+    pub segment_start: u32,
+    pub segment_count: u32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct PathSprite {
     pub bounds: Bounds<ScaledPixels>,
-    pub color: Hsla,
+    pub color: Background,
+    /// The bounds of the whole path, which gradients are relative to.
+    pub path_bounds: Bounds<ScaledPixels>,
     pub tile: AtlasTile,
+    /// Whether the path's coverage is clamped, rather than alternating between filled and empty.
+    pub nonzero: u32,
+    pub pad: u32,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1252,3 +2216,16 @@ pub struct SurfaceBounds {
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
 }
+
+/// Converts a half-precision float, as stored in `RGBA16Float` textures, to a single-precision one.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 == 0 { 1. } else { -1. };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0. => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1. + mantissa / 1024.) * 2f32.powi(exponent - 15),
+    }
+}
@@ -4,13 +4,13 @@ use super::{
     BoolExt,
 };
 use crate::{
-    hash, Action, AnyWindowHandle, BackgroundExecutor, ClipboardEntry, ClipboardItem,
-    ClipboardString, CursorStyle, ForegroundExecutor, Image, ImageFormat, Keymap, MacDispatcher,
-    MacDisplay, MacTextSystem, MacWindow, Menu, MenuItem, PathPromptOptions, Platform,
-    PlatformDisplay, PlatformTextSystem, PlatformWindow, Result, SemanticVersion, Task,
-    WindowAppearance, WindowParams,
+    hash, AccessibilityPreferences, Action, AnyWindowHandle, BackgroundExecutor, ClipboardEntry,
+    ClipboardItem, ClipboardString, CursorStyle, CustomCursor, ForegroundExecutor, Image,
+    ImageFormat, Keymap, MacDispatcher, MacDisplay, MacTextSystem, MacWindow, Menu, MenuItem,
+    PathPromptOptions, Pixels, Platform, PlatformDisplay, PlatformTextSystem, PlatformWindow,
+    Point, PowerState, RenderImage, Result, SemanticVersion, Task, WindowAppearance, WindowParams,
 };
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
 use block::ConcreteBlock;
 use cocoa::{
     appkit::{
@@ -21,10 +21,11 @@ use cocoa::{
     },
     base::{id, nil, selector, BOOL, YES},
     foundation::{
-        NSArray, NSAutoreleasePool, NSBundle, NSData, NSInteger, NSProcessInfo, NSRange, NSString,
-        NSUInteger, NSURL,
+        NSArray, NSAutoreleasePool, NSBundle, NSData, NSInteger, NSPoint, NSProcessInfo, NSRange,
+        NSString, NSUInteger, NSURL,
     },
 };
+use collections::HashMap;
 use core_foundation::{
     base::{CFRelease, CFType, CFTypeRef, OSStatus, TCFType},
     boolean::CFBoolean,
@@ -35,6 +36,7 @@ use core_foundation::{
 };
 use ctor::ctor;
 use futures::channel::oneshot;
+use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder as _};
 use objc::{
     class,
     declare::ClassDecl,
@@ -160,6 +162,7 @@ pub(crate) struct MacPlatformState {
     open_urls: Option<Box<dyn FnMut(Vec<String>)>>,
     finish_launching: Option<Box<dyn FnOnce()>>,
     dock_menu: Option<id>,
+    custom_cursors: HashMap<CustomCursor, id>,
 }
 
 impl Default for MacPlatform {
@@ -189,6 +192,7 @@ impl MacPlatform {
             open_urls: None,
             finish_launching: None,
             dock_menu: None,
+            custom_cursors: HashMap::default(),
         }))
     }
 
@@ -850,6 +854,20 @@ impl Platform for MacPlatform {
                 CursorStyle::DragLink => msg_send![class!(NSCursor), dragLinkCursor],
                 CursorStyle::DragCopy => msg_send![class!(NSCursor), dragCopyCursor],
                 CursorStyle::ContextualMenu => msg_send![class!(NSCursor), contextualMenuCursor],
+
+                // Undocumented, private class methods, which WebKit uses for the matching CSS
+                // cursors.
+                CursorStyle::Move => msg_send![class!(NSCursor), _moveCursor],
+                CursorStyle::Wait => msg_send![class!(NSCursor), _waitCursor],
+                CursorStyle::Progress => msg_send![class!(NSCursor), busyButClickableCursor],
+                CursorStyle::Help => msg_send![class!(NSCursor), _helpCursor],
+                CursorStyle::ZoomIn => msg_send![class!(NSCursor), _zoomInCursor],
+                CursorStyle::ZoomOut => msg_send![class!(NSCursor), _zoomOutCursor],
+
+                CursorStyle::Custom(cursor) => match self.0.lock().custom_cursors.get(&cursor) {
+                    Some(cursor) => *cursor,
+                    None => msg_send![class!(NSCursor), arrowCursor],
+                },
             };
 
             let old_cursor: id = msg_send![class!(NSCursor), currentCursor];
@@ -859,6 +877,39 @@ impl Platform for MacPlatform {
         }
     }
 
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    ) {
+        let png = match encode_cursor_image(&image) {
+            Ok(png) => png,
+            Err(error) => {
+                log::error!("failed to create cursor: {error:?}");
+                return;
+            }
+        };
+
+        unsafe {
+            let data =
+                NSData::dataWithBytes_length_(nil, png.as_ptr() as *const c_void, png.len() as u64);
+            let ns_image: id = msg_send![class!(NSImage), alloc];
+            let ns_image: id = msg_send![ns_image, initWithData: data];
+            let ns_cursor: id = msg_send![class!(NSCursor), alloc];
+            let ns_cursor: id = msg_send![
+                ns_cursor,
+                initWithImage: ns_image
+                hotSpot: NSPoint::new(hotspot.x.0 as f64, hotspot.y.0 as f64)
+            ];
+            let _: () = msg_send![ns_image, release];
+
+            if let Some(previous_cursor) = self.0.lock().custom_cursors.insert(cursor, ns_cursor) {
+                let _: () = msg_send![previous_cursor, release];
+            }
+        }
+    }
+
     fn should_auto_hide_scrollbars(&self) -> bool {
         #[allow(non_upper_case_globals)]
         const NSScrollerStyleOverlay: NSInteger = 1;
@@ -869,6 +920,46 @@ impl Platform for MacPlatform {
         }
     }
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let reduce_motion: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+            let increase_contrast: BOOL =
+                msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+            let reduce_transparency: BOOL =
+                msg_send![workspace, accessibilityDisplayShouldReduceTransparency];
+            AccessibilityPreferences {
+                reduce_motion: reduce_motion == YES,
+                increase_contrast: increase_contrast == YES,
+                reduce_transparency: reduce_transparency == YES,
+            }
+        }
+    }
+
+    fn power_state(&self) -> PowerState {
+        unsafe {
+            let process_info = NSProcessInfo::processInfo(nil);
+            let low_power_mode: BOOL = msg_send![process_info, isLowPowerModeEnabled];
+            PowerState {
+                on_battery: power_sources::IOPSGetTimeRemainingEstimate()
+                    != power_sources::kIOPSTimeRemainingUnlimited,
+                low_power_mode: low_power_mode == YES,
+            }
+        }
+    }
+
+    fn play_system_sound(&self, name: &str, volume: f32) {
+        unsafe {
+            let sound: id = msg_send![class!(NSSound), soundNamed: ns_string(name)];
+            if sound == nil {
+                log::error!("no system sound named {name:?}");
+                return;
+            }
+            let _: () = msg_send![sound, setVolume: volume];
+            let _: BOOL = msg_send![sound, play];
+        }
+    }
+
     fn write_to_clipboard(&self, item: ClipboardItem) {
         use crate::ClipboardEntry;
 
@@ -1171,6 +1262,28 @@ impl MacPlatform {
     }
 }
 
+/// Encodes the first frame of an image as a PNG, for `NSImage` to decode.
+fn encode_cursor_image(image: &RenderImage) -> anyhow::Result<Vec<u8>> {
+    let size = image.size(0);
+    let mut bytes = image
+        .as_bytes(0)
+        .context("cursor image has no frames")?
+        .to_vec();
+    // Render images are stored as BGRA.
+    for pixel in bytes.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png).write_image(
+        &bytes,
+        size.width.0 as u32,
+        size.height.0 as u32,
+        ExtendedColorType::Rgba8,
+    )?;
+    Ok(png)
+}
+
 fn try_clipboard_image(pasteboard: id, format: ImageFormat) -> Option<ClipboardItem> {
     let mut ut_type: UTType = format.into();
 
@@ -1350,6 +1463,18 @@ unsafe fn ns_url_to_path(url: id) -> Result<PathBuf> {
     }
 }
 
+mod power_sources {
+    #![allow(non_upper_case_globals)]
+
+    /// Returned by `IOPSGetTimeRemainingEstimate` when the machine is on external power.
+    pub const kIOPSTimeRemainingUnlimited: f64 = -2.0;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        pub fn IOPSGetTimeRemainingEstimate() -> f64;
+    }
+}
+
 mod security {
     #![allow(non_upper_case_globals)]
     use super::*;
@@ -3,17 +3,44 @@
 
 using namespace metal;
 
+// Set when the pipelines are built for a layer tagged with the Display P3 color
+// space, whose colors are converted from sRGB before they're drawn.
+constant bool display_p3_output [[function_constant(0)]];
+constant bool convert_to_display_p3 =
+    is_function_constant_defined(display_p3_output) && display_p3_output;
+
+// Set when the pipelines of quads and sprites are built for a blend mode other
+// than normal, whose blend factors expect premultiplied colors.
+constant bool premultiplied_output [[function_constant(1)]];
+constant bool premultiply_output =
+    is_function_constant_defined(premultiplied_output) && premultiplied_output;
+
+// How many atlas textures a sprite batch samples from, which are bound to
+// consecutive texture slots starting at `SpriteInputIndex_AtlasTexture`. This
+// must match `AtlasTextureId::PER_BATCH`.
+constant uint ATLAS_TEXTURES_PER_BATCH = 8;
+
 float4 hsla_to_rgba(Hsla hsla);
+float4 hsla_to_srgb(Hsla hsla);
+float3 to_surface_color_space(float3 color);
 float4 to_device_position(float2 unit_vertex, Bounds_ScaledPixels bounds,
                           constant Size_DevicePixels *viewport_size);
 float4 to_device_position_transformed(float2 unit_vertex, Bounds_ScaledPixels bounds,
                           TransformationMatrix transformation,
                           constant Size_DevicePixels *input_viewport_size);
-
+float2 to_local_position(float2 unit_vertex, Bounds_ScaledPixels bounds);
 float2 to_tile_position(float2 unit_vertex, AtlasTile tile,
                         constant Size_DevicePixels *atlas_size);
+float nine_slice_axis(float position, float size, float start, float end,
+                      float tile_size, float tile_start, float tile_end);
+bool is_repeating(PolychromeSprite sprite);
+float3 apply_color_filter(float3 color, PolychromeSprite sprite);
+float2 to_repeat_position(float2 local_position, PolychromeSprite sprite);
+float2 to_nine_slice_tile_position(float2 position, float2 size,
+                                   PolychromeSprite sprite, float2 atlas_size);
 float4 distance_from_clip_rect(float2 unit_vertex, Bounds_ScaledPixels bounds,
                                Bounds_ScaledPixels clip_bounds);
+float dash_pattern_alpha(float distance, float dash, float gap, float offset);
 float quad_sdf(float2 point, Bounds_ScaledPixels bounds,
                Corners_ScaledPixels corner_radii);
 float gaussian(float x, float sigma);
@@ -21,9 +48,20 @@ float2 erf(float2 x);
 float blur_along_x(float x, float y, float sigma, float corner,
                    float2 half_size);
 float4 over(float4 below, float4 above);
+float4 blend_output(float4 color);
+float3 srgb_to_linear(float3 color);
+float3 linear_to_srgb(float3 color);
+float3 linear_srgb_to_oklab(float3 color);
+float3 oklab_to_linear_srgb(float3 lab);
+float4 mix_oklab(float4 a, float4 b, float t);
+float gradient_position(Background background, float2 position,
+                        Bounds_ScaledPixels bounds);
+float4 gradient_color(Background background, float2 position,
+                      Bounds_ScaledPixels bounds);
 
 struct QuadVertexOutput {
   float4 position [[position]];
+  float2 local_position;
   float4 background_color [[flat]];
   float4 border_color [[flat]];
   uint quad_id [[flat]];
@@ -32,6 +70,7 @@ struct QuadVertexOutput {
 
 struct QuadFragmentInput {
   float4 position [[position]];
+  float2 local_position;
   float4 background_color [[flat]];
   float4 border_color [[flat]];
   uint quad_id [[flat]];
@@ -47,14 +86,16 @@ vertex QuadVertexOutput quad_vertex(uint unit_vertex_id [[vertex_id]],
                                     [[buffer(QuadInputIndex_ViewportSize)]]) {
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   Quad quad = quads[quad_id];
-  float4 device_position =
-      to_device_position(unit_vertex, quad.bounds, viewport_size);
+  float4 device_position = to_device_position_transformed(
+      unit_vertex, quad.bounds, quad.transformation, viewport_size);
+  float2 local_position = to_local_position(unit_vertex, quad.bounds);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, quad.bounds,
                                                  quad.content_mask.bounds);
-  float4 background_color = hsla_to_rgba(quad.background);
+  float4 background_color = hsla_to_rgba(quad.background.solid);
   float4 border_color = hsla_to_rgba(quad.border_color);
   return QuadVertexOutput{
       device_position,
+      local_position,
       background_color,
       border_color,
       quad_id,
@@ -65,6 +106,11 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
                               constant Quad *quads
                               [[buffer(QuadInputIndex_Quads)]]) {
   Quad quad = quads[input.quad_id];
+  float4 background_color = input.background_color;
+  if (quad.background.tag != BackgroundTag_Solid) {
+    background_color =
+        gradient_color(quad.background, input.local_position, quad.bounds);
+  }
 
   // Fast path when the quad is not rounded and doesn't have any border.
   if (quad.corner_radii.top_left == 0. && quad.corner_radii.bottom_left == 0. &&
@@ -72,69 +118,74 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
       quad.corner_radii.bottom_right == 0. && quad.border_widths.top == 0. &&
       quad.border_widths.left == 0. && quad.border_widths.right == 0. &&
       quad.border_widths.bottom == 0.) {
-    return input.background_color;
-  }
-
-  float2 half_size =
-      float2(quad.bounds.size.width, quad.bounds.size.height) / 2.;
-  float2 center =
-      float2(quad.bounds.origin.x, quad.bounds.origin.y) + half_size;
-  float2 center_to_point = input.position.xy - center;
-  float corner_radius;
-  if (center_to_point.x < 0.) {
-    if (center_to_point.y < 0.) {
-      corner_radius = quad.corner_radii.top_left;
-    } else {
-      corner_radius = quad.corner_radii.bottom_left;
-    }
-  } else {
-    if (center_to_point.y < 0.) {
-      corner_radius = quad.corner_radii.top_right;
-    } else {
-      corner_radius = quad.corner_radii.bottom_right;
-    }
+    return blend_output(background_color);
   }
 
-  float2 rounded_edge_to_point =
-      fabs(center_to_point) - half_size + corner_radius;
   float distance =
-      length(max(0., rounded_edge_to_point)) +
-      min(0., max(rounded_edge_to_point.x, rounded_edge_to_point.y)) -
-      corner_radius;
-
-  float vertical_border = center_to_point.x <= 0. ? quad.border_widths.left
-                                                  : quad.border_widths.right;
-  float horizontal_border = center_to_point.y <= 0. ? quad.border_widths.top
-                                                    : quad.border_widths.bottom;
-  float2 inset_size =
-      half_size - corner_radius - float2(vertical_border, horizontal_border);
-  float2 point_to_inset_corner = fabs(center_to_point) - inset_size;
-  float border_width;
-  if (point_to_inset_corner.x < 0. && point_to_inset_corner.y < 0.) {
-    border_width = 0.;
-  } else if (point_to_inset_corner.y > point_to_inset_corner.x) {
-    border_width = horizontal_border;
-  } else {
-    border_width = vertical_border;
-  }
-
-  float4 color;
-  if (border_width == 0.) {
-    color = input.background_color;
-  } else {
-    float inset_distance = distance + border_width;
+      quad_sdf(input.local_position, quad.bounds, quad.corner_radii);
+
+  float4 color = background_color;
+  Edges_ScaledPixels widths = quad.border_widths;
+  if (widths.top > 0. || widths.right > 0. || widths.bottom > 0. ||
+      widths.left > 0.) {
+    // The border surrounds the quad inset by the width of each edge, whose
+    // corners are rounded by what's left of the outer radius past the widest
+    // adjacent edge. This keeps edges of different widths meeting cleanly.
+    Bounds_ScaledPixels inner_bounds = quad.bounds;
+    inner_bounds.origin.x += widths.left;
+    inner_bounds.origin.y += widths.top;
+    inner_bounds.size.width =
+        max(0., quad.bounds.size.width - widths.left - widths.right);
+    inner_bounds.size.height =
+        max(0., quad.bounds.size.height - widths.top - widths.bottom);
+    Corners_ScaledPixels inner_radii = quad.corner_radii;
+    inner_radii.top_left =
+        max(0., inner_radii.top_left - max(widths.top, widths.left));
+    inner_radii.top_right =
+        max(0., inner_radii.top_right - max(widths.top, widths.right));
+    inner_radii.bottom_right =
+        max(0., inner_radii.bottom_right - max(widths.bottom, widths.right));
+    inner_radii.bottom_left =
+        max(0., inner_radii.bottom_left - max(widths.bottom, widths.left));
+    float inner_distance =
+        quad_sdf(input.local_position, inner_bounds, inner_radii);
     // Blend the border on top of the background and then linearly interpolate
     // between the two as we slide inside the background.
-    float4 blended_border = over(input.background_color, input.border_color);
-    color = mix(blended_border, input.background_color,
-                saturate(0.5 - inset_distance));
+    float4 blended_border = over(background_color, input.border_color);
+    color = mix(blended_border, background_color,
+                saturate(0.5 - inner_distance));
   }
 
-  return color * float4(1., 1., 1., saturate(0.5 - distance));
+  return blend_output(color * float4(1., 1., 1., saturate(0.5 - distance)));
+}
+
+struct SolidQuadVertexOutput {
+  float4 position [[position]];
+  float4 color [[flat]];
+};
+
+// Solid quads are already clipped to their content mask and have nothing but
+// a color, so the fragment shader only has to return it.
+vertex SolidQuadVertexOutput solid_quad_vertex(
+    uint unit_vertex_id [[vertex_id]], uint quad_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(SolidQuadInputIndex_Vertices)]],
+    constant SolidQuad *quads [[buffer(SolidQuadInputIndex_SolidQuads)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(SolidQuadInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  SolidQuad quad = quads[quad_id];
+  float4 device_position =
+      to_device_position(unit_vertex, quad.bounds, viewport_size);
+  return SolidQuadVertexOutput{device_position, hsla_to_rgba(quad.color)};
+}
+
+fragment float4 solid_quad_fragment(SolidQuadVertexOutput input [[stage_in]]) {
+  return blend_output(input.color);
 }
 
 struct ShadowVertexOutput {
   float4 position [[position]];
+  float2 local_position;
   float4 color [[flat]];
   uint shadow_id [[flat]];
   float clip_distance [[clip_distance]][4];
@@ -142,6 +193,7 @@ struct ShadowVertexOutput {
 
 struct ShadowFragmentInput {
   float4 position [[position]];
+  float2 local_position;
   float4 color [[flat]];
   uint shadow_id [[flat]];
 };
@@ -155,7 +207,8 @@ vertex ShadowVertexOutput shadow_vertex(
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   Shadow shadow = shadows[shadow_id];
 
-  float margin = 3. * shadow.blur_radius;
+  // Inset shadows are painted within their bounds, so they don't need a margin.
+  float margin = shadow.inset ? 0. : 3. * shadow.blur_radius;
   // Set the bounds of the shadow and adjust its size based on the shadow's
   // spread radius to achieve the spreading effect
   Bounds_ScaledPixels bounds = shadow.bounds;
@@ -164,14 +217,16 @@ vertex ShadowVertexOutput shadow_vertex(
   bounds.size.width += 2. * margin;
   bounds.size.height += 2. * margin;
 
-  float4 device_position =
-      to_device_position(unit_vertex, bounds, viewport_size);
+  float4 device_position = to_device_position_transformed(
+      unit_vertex, bounds, shadow.transformation, viewport_size);
+  float2 local_position = to_local_position(unit_vertex, bounds);
   float4 clip_distance =
       distance_from_clip_rect(unit_vertex, bounds, shadow.content_mask.bounds);
   float4 color = hsla_to_rgba(shadow.color);
 
   return ShadowVertexOutput{
       device_position,
+      local_position,
       color,
       shadow_id,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
@@ -182,48 +237,145 @@ fragment float4 shadow_fragment(ShadowFragmentInput input [[stage_in]],
                                 [[buffer(ShadowInputIndex_Shadows)]]) {
   Shadow shadow = shadows[input.shadow_id];
 
-  float2 origin = float2(shadow.bounds.origin.x, shadow.bounds.origin.y);
-  float2 size = float2(shadow.bounds.size.width, shadow.bounds.size.height);
-  float2 half_size = size / 2.;
-  float2 center = origin + half_size;
-  float2 point = input.position.xy - center;
-  float corner_radius;
-  if (point.x < 0.) {
-    if (point.y < 0.) {
-      corner_radius = shadow.corner_radii.top_left;
+  // The shape casting the shadow. Inset shadows are cast by their bounds moved
+  // by the offset and shrunk by the spread radius, and darken what's outside.
+  Bounds_ScaledPixels shape = shadow.bounds;
+  Corners_ScaledPixels shape_corner_radii = shadow.corner_radii;
+  if (shadow.inset) {
+    float spread = shadow.inset_spread_radius;
+    shape.origin.x += shadow.inset_offset.x + spread;
+    shape.origin.y += shadow.inset_offset.y + spread;
+    shape.size.width = max(shape.size.width - 2. * spread, 0.);
+    shape.size.height = max(shape.size.height - 2. * spread, 0.);
+    shape_corner_radii.top_left = max(shape_corner_radii.top_left - spread, 0.);
+    shape_corner_radii.top_right =
+        max(shape_corner_radii.top_right - spread, 0.);
+    shape_corner_radii.bottom_right =
+        max(shape_corner_radii.bottom_right - spread, 0.);
+    shape_corner_radii.bottom_left =
+        max(shape_corner_radii.bottom_left - spread, 0.);
+  }
+
+  float alpha = 0.;
+  if (shadow.blur_radius > 0.) {
+    float2 origin = float2(shape.origin.x, shape.origin.y);
+    float2 size = float2(shape.size.width, shape.size.height);
+    float2 half_size = size / 2.;
+    float2 center = origin + half_size;
+    float2 point = input.local_position - center;
+    float corner_radius;
+    if (point.x < 0.) {
+      if (point.y < 0.) {
+        corner_radius = shape_corner_radii.top_left;
+      } else {
+        corner_radius = shape_corner_radii.bottom_left;
+      }
     } else {
-      corner_radius = shadow.corner_radii.bottom_left;
+      if (point.y < 0.) {
+        corner_radius = shape_corner_radii.top_right;
+      } else {
+        corner_radius = shape_corner_radii.bottom_right;
+      }
     }
-  } else {
-    if (point.y < 0.) {
-      corner_radius = shadow.corner_radii.top_right;
-    } else {
-      corner_radius = shadow.corner_radii.bottom_right;
+
+    // The signal is only non-zero in a limited range, so don't waste samples
+    float low = point.y - half_size.y;
+    float high = point.y + half_size.y;
+    float start = clamp(-3. * shadow.blur_radius, low, high);
+    float end = clamp(3. * shadow.blur_radius, low, high);
+
+    // Accumulate samples (we can get away with surprisingly few samples)
+    float step = (end - start) / 4.;
+    float y = start + step * 0.5;
+    for (int i = 0; i < 4; i++) {
+      alpha += blur_along_x(point.x, point.y - y, shadow.blur_radius,
+                            corner_radius, half_size) *
+               gaussian(y, shadow.blur_radius) * step;
+      y += step;
     }
+  } else {
+    alpha = saturate(0.5 - quad_sdf(input.local_position, shape,
+                                    shape_corner_radii));
   }
 
-  // The signal is only non-zero in a limited range, so don't waste samples
-  float low = point.y - half_size.y;
-  float high = point.y + half_size.y;
-  float start = clamp(-3. * shadow.blur_radius, low, high);
-  float end = clamp(3. * shadow.blur_radius, low, high);
-
-  // Accumulate samples (we can get away with surprisingly few samples)
-  float step = (end - start) / 4.;
-  float y = start + step * 0.5;
-  float alpha = 0.;
-  for (int i = 0; i < 4; i++) {
-    alpha += blur_along_x(point.x, point.y - y, shadow.blur_radius,
-                          corner_radius, half_size) *
-             gaussian(y, shadow.blur_radius) * step;
-    y += step;
+  if (shadow.inset) {
+    float distance =
+        quad_sdf(input.local_position, shadow.bounds, shadow.corner_radii);
+    alpha = (1. - alpha) * saturate(0.5 - distance);
   }
 
   return input.color * float4(1., 1., 1., alpha);
 }
 
+struct BackdropBlurVertexOutput {
+  float4 position [[position]];
+  uint blur_id [[flat]];
+  float clip_distance [[clip_distance]][4];
+};
+
+struct BackdropBlurFragmentInput {
+  float4 position [[position]];
+  uint blur_id [[flat]];
+};
+
+vertex BackdropBlurVertexOutput backdrop_blur_vertex(
+    uint unit_vertex_id [[vertex_id]], uint blur_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(BackdropBlurInputIndex_Vertices)]],
+    constant BackdropBlur *blurs
+    [[buffer(BackdropBlurInputIndex_BackdropBlurs)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(BackdropBlurInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  BackdropBlur blur = blurs[blur_id];
+
+  float4 device_position =
+      to_device_position(unit_vertex, blur.bounds, viewport_size);
+  float4 clip_distance =
+      distance_from_clip_rect(unit_vertex, blur.bounds, blur.content_mask.bounds);
+
+  return BackdropBlurVertexOutput{
+      device_position,
+      blur_id,
+      {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
+}
+
+fragment float4 backdrop_blur_fragment(
+    BackdropBlurFragmentInput input [[stage_in]],
+    constant BackdropBlur *blurs
+    [[buffer(BackdropBlurInputIndex_BackdropBlurs)]],
+    texture2d<float> backdrop_texture
+    [[texture(BackdropBlurInputIndex_BackdropTexture)]]) {
+  BackdropBlur blur = blurs[input.blur_id];
+  constexpr sampler backdrop_sampler(coord::pixel, mag_filter::linear,
+                                     min_filter::linear,
+                                     address::clamp_to_edge);
+  float sigma = blur.blur_radius / 2.;
+  float step = blur.blur_radius / 4.;
+
+  // Sample a grid spanning the blur radius, relying on linear filtering to
+  // cover the texels in between.
+  float4 color = float4(0.);
+  float total_weight = 0.;
+  for (int i = -4; i <= 4; i++) {
+    for (int j = -4; j <= 4; j++) {
+      float2 offset = float2(i, j) * step;
+      float weight = gaussian(offset.x, sigma) * gaussian(offset.y, sigma);
+      color += backdrop_texture.sample(backdrop_sampler,
+                                       input.position.xy + offset) *
+               weight;
+      total_weight += weight;
+    }
+  }
+  color /= total_weight;
+
+  float distance =
+      quad_sdf(input.position.xy, blur.bounds, blur.corner_radii);
+  return color * float4(1., 1., 1., saturate(0.5 - distance));
+}
+
 struct UnderlineVertexOutput {
   float4 position [[position]];
+  float2 local_position;
   float4 color [[flat]];
   uint underline_id [[flat]];
   float clip_distance [[clip_distance]][4];
@@ -231,6 +383,7 @@ struct UnderlineVertexOutput {
 
 struct UnderlineFragmentInput {
   float4 position [[position]];
+  float2 local_position;
   float4 color [[flat]];
   uint underline_id [[flat]];
 };
@@ -243,13 +396,15 @@ vertex UnderlineVertexOutput underline_vertex(
     [[buffer(ShadowInputIndex_ViewportSize)]]) {
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   Underline underline = underlines[underline_id];
-  float4 device_position =
-      to_device_position(unit_vertex, underline.bounds, viewport_size);
+  float4 device_position = to_device_position_transformed(
+      unit_vertex, underline.bounds, underline.transformation, viewport_size);
+  float2 local_position = to_local_position(unit_vertex, underline.bounds);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, underline.bounds,
                                                  underline.content_mask.bounds);
   float4 color = hsla_to_rgba(underline.color);
   return UnderlineVertexOutput{
       device_position,
+      local_position,
       color,
       underline_id,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
@@ -259,32 +414,55 @@ fragment float4 underline_fragment(UnderlineFragmentInput input [[stage_in]],
                                    constant Underline *underlines
                                    [[buffer(UnderlineInputIndex_Underlines)]]) {
   Underline underline = underlines[input.underline_id];
-  if (underline.wavy) {
-    float half_thickness = underline.thickness * 0.5;
-    float2 origin =
-        float2(underline.bounds.origin.x, underline.bounds.origin.y);
-    float2 st = ((input.position.xy - origin) / underline.bounds.size.height) -
-                float2(0., 0.5);
-    float frequency = (M_PI_F * (3. * underline.thickness)) / 8.;
-    float amplitude = 1. / (2. * underline.thickness);
-    float sine = sin(st.x * frequency) * amplitude;
-    float dSine = cos(st.x * frequency) * amplitude * frequency;
-    float distance = (st.y - sine) / sqrt(1. + dSine * dSine);
-    float distance_in_pixels = distance * underline.bounds.size.height;
-    float distance_from_top_border = distance_in_pixels - half_thickness;
-    float distance_from_bottom_border = distance_in_pixels + half_thickness;
-    float alpha = saturate(
-        0.5 - max(-distance_from_bottom_border, distance_from_top_border));
-    return input.color * float4(1., 1., 1., alpha);
+  float dash_alpha = dash_pattern_alpha(
+      input.local_position.x - underline.bounds.origin.x, underline.dash,
+      underline.dash_gap, underline.dash_offset);
+  if (underline.wavelength <= 0. && underline.kind == UnderlineKind_Single) {
+    return input.color * float4(1., 1., 1., dash_alpha);
+  }
+
+  // The distance from the middle of the underline's bounds, measured
+  // perpendicular to the wave for wavy underlines.
+  float half_thickness = underline.thickness * 0.5;
+  float center_y =
+      underline.bounds.origin.y + underline.bounds.size.height * 0.5;
+  float distance = input.local_position.y - center_y;
+  if (underline.wavelength > 0.) {
+    float frequency = 2. * M_PI_F / underline.wavelength;
+    // The phase is based on the position in the window, so that wavy underlines
+    // that are next to each other join up.
+    float sine =
+        sin(input.local_position.x * frequency) * underline.wave_amplitude;
+    float dSine = cos(input.local_position.x * frequency) *
+                  underline.wave_amplitude * frequency;
+    // Approximate the distance to the wave by dividing the vertical distance by
+    // the length of the wave's normal.
+    distance = (distance - sine) / sqrt(1. + dSine * dSine);
+  }
+
+  float alpha;
+  if (underline.kind == UnderlineKind_Double) {
+    // The lines are centered a thickness above and below the middle.
+    alpha = saturate(
+        0.5 - (abs(abs(distance) - underline.thickness) - half_thickness));
+  } else if (underline.kind == UnderlineKind_Dotted) {
+    // Dots as wide as the underline is thick, each followed by a gap of the
+    // same size.
+    float along = fmod(input.local_position.x - underline.bounds.origin.x,
+                       underline.thickness * 2.) -
+                  half_thickness;
+    alpha = saturate(0.5 - (length(float2(along, distance)) - half_thickness));
   } else {
-    return input.color;
+    alpha = saturate(0.5 - (abs(distance) - half_thickness));
   }
+  return input.color * float4(1., 1., 1., dash_alpha * alpha);
 }
 
 struct MonochromeSpriteVertexOutput {
   float4 position [[position]];
   float2 tile_position;
   float4 color [[flat]];
+  uint atlas_slot [[flat]];
   float clip_distance [[clip_distance]][4];
 };
 
@@ -292,6 +470,7 @@ struct MonochromeSpriteFragmentInput {
   float4 position [[position]];
   float2 tile_position;
   float4 color [[flat]];
+  uint atlas_slot [[flat]];
 };
 
 vertex MonochromeSpriteVertexOutput monochrome_sprite_vertex(
@@ -300,7 +479,7 @@ vertex MonochromeSpriteVertexOutput monochrome_sprite_vertex(
     constant MonochromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
     constant Size_DevicePixels *viewport_size
     [[buffer(SpriteInputIndex_ViewportSize)]],
-    constant Size_DevicePixels *atlas_size
+    constant Size_DevicePixels *atlas_sizes
     [[buffer(SpriteInputIndex_AtlasTextureSize)]]) {
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   MonochromeSprite sprite = sprites[sprite_id];
@@ -308,30 +487,36 @@ vertex MonochromeSpriteVertexOutput monochrome_sprite_vertex(
       to_device_position_transformed(unit_vertex, sprite.bounds, sprite.transformation, viewport_size);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, sprite.bounds,
                                                  sprite.content_mask.bounds);
-  float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
+  uint atlas_slot = sprite.tile.texture_id.index % ATLAS_TEXTURES_PER_BATCH;
+  float2 tile_position =
+      to_tile_position(unit_vertex, sprite.tile, &atlas_sizes[atlas_slot]);
   float4 color = hsla_to_rgba(sprite.color);
   return MonochromeSpriteVertexOutput{
       device_position,
       tile_position,
       color,
+      atlas_slot,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
 }
 
 fragment float4 monochrome_sprite_fragment(
     MonochromeSpriteFragmentInput input [[stage_in]],
     constant MonochromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
-    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]]) {
+    array<texture2d<float>, ATLAS_TEXTURES_PER_BATCH> atlas_textures
+    [[texture(SpriteInputIndex_AtlasTexture)]]) {
   constexpr sampler atlas_texture_sampler(mag_filter::linear,
                                           min_filter::linear);
-  float4 sample =
-      atlas_texture.sample(atlas_texture_sampler, input.tile_position);
+  float4 sample = atlas_textures[input.atlas_slot].sample(
+      atlas_texture_sampler, input.tile_position);
   float4 color = input.color;
-  color.a *= sample.a;
-  return color;
+  // Monochrome textures only have a red channel, which holds the coverage.
+  color.a *= sample.r;
+  return blend_output(color);
 }
 
 struct PolychromeSpriteVertexOutput {
   float4 position [[position]];
+  float2 local_position;
   float2 tile_position;
   uint sprite_id [[flat]];
   float clip_distance [[clip_distance]][4];
@@ -339,6 +524,7 @@ struct PolychromeSpriteVertexOutput {
 
 struct PolychromeSpriteFragmentInput {
   float4 position [[position]];
+  float2 local_position;
   float2 tile_position;
   uint sprite_id [[flat]];
 };
@@ -349,18 +535,22 @@ vertex PolychromeSpriteVertexOutput polychrome_sprite_vertex(
     constant PolychromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
     constant Size_DevicePixels *viewport_size
     [[buffer(SpriteInputIndex_ViewportSize)]],
-    constant Size_DevicePixels *atlas_size
+    constant Size_DevicePixels *atlas_sizes
     [[buffer(SpriteInputIndex_AtlasTextureSize)]]) {
 
   float2 unit_vertex = unit_vertices[unit_vertex_id];
   PolychromeSprite sprite = sprites[sprite_id];
-  float4 device_position =
-      to_device_position(unit_vertex, sprite.bounds, viewport_size);
+  float4 device_position = to_device_position_transformed(
+      unit_vertex, sprite.bounds, sprite.transformation, viewport_size);
+  float2 local_position = to_local_position(unit_vertex, sprite.bounds);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, sprite.bounds,
                                                  sprite.content_mask.bounds);
-  float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
+  uint atlas_slot = sprite.tile.texture_id.index % ATLAS_TEXTURES_PER_BATCH;
+  float2 tile_position =
+      to_tile_position(unit_vertex, sprite.tile, &atlas_sizes[atlas_slot]);
   return PolychromeSpriteVertexOutput{
       device_position,
+      local_position,
       tile_position,
       sprite_id,
       {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
@@ -369,24 +559,73 @@ vertex PolychromeSpriteVertexOutput polychrome_sprite_vertex(
 fragment float4 polychrome_sprite_fragment(
     PolychromeSpriteFragmentInput input [[stage_in]],
     constant PolychromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
-    texture2d<float> atlas_texture [[texture(SpriteInputIndex_AtlasTexture)]]) {
+    array<texture2d<float>, ATLAS_TEXTURES_PER_BATCH> atlas_textures
+    [[texture(SpriteInputIndex_AtlasTexture)]]) {
   PolychromeSprite sprite = sprites[input.sprite_id];
+  texture2d<float> atlas_texture =
+      atlas_textures[sprite.tile.texture_id.index % ATLAS_TEXTURES_PER_BATCH];
   constexpr sampler atlas_texture_sampler(mag_filter::linear,
                                           min_filter::linear);
-  float4 sample =
-      atlas_texture.sample(atlas_texture_sampler, input.tile_position);
-  float distance =
-      quad_sdf(input.position.xy, sprite.bounds, sprite.corner_radii);
-
-  float4 color = sample;
-  if (sprite.grayscale) {
-    float grayscale = 0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b;
-    color.r = grayscale;
-    color.g = grayscale;
-    color.b = grayscale;
+  float2 position = to_repeat_position(input.local_position, sprite);
+  float2 size = is_repeating(sprite)
+                    ? float2(sprite.repeat_size.width, sprite.repeat_size.height)
+                    : float2(sprite.bounds.size.width, sprite.bounds.size.height);
+  // Without nine-slice borders or repetition this is the interpolated tile
+  // position.
+  float2 tile_position = to_nine_slice_tile_position(
+      position, size, sprite,
+      float2(atlas_texture.get_width(), atlas_texture.get_height()));
+  float4 sample = atlas_texture.sample(atlas_texture_sampler, tile_position);
+  // Leave the spacing between repeated copies empty.
+  if (any(position > size)) {
+    return float4(0.);
   }
+  float distance =
+      quad_sdf(input.local_position, sprite.bounds, sprite.corner_radii);
+
+  float4 color = float4(
+      to_surface_color_space(apply_color_filter(sample.rgb, sprite)), sample.a);
   color.a *= sprite.opacity * saturate(0.5 - distance);
-  return color;
+  return blend_output(color);
+}
+
+// Desaturates, rotates the hue of and tints a sprite's color, in that order.
+// The hue is rotated while keeping the luminance, like the CSS `hue-rotate`
+// filter.
+float3 apply_color_filter(float3 color, PolychromeSprite sprite) {
+  float grayscale = dot(color, float3(0.2126, 0.7152, 0.0722));
+  float3 filtered = mix(color, float3(grayscale), sprite.grayscale);
+  if (sprite.hue_rotate != 0.) {
+    float c = cos(sprite.hue_rotate);
+    float s = sin(sprite.hue_rotate);
+    float3x3 hue_rotation = float3x3(
+        float3(0.213 + c * 0.787 - s * 0.213, 0.213 - c * 0.213 + s * 0.143,
+               0.213 - c * 0.213 - s * 0.787),
+        float3(0.715 - c * 0.715 - s * 0.715, 0.715 + c * 0.285 + s * 0.140,
+               0.715 - c * 0.715 + s * 0.715),
+        float3(0.072 - c * 0.072 + s * 0.928, 0.072 - c * 0.072 - s * 0.283,
+               0.072 + c * 0.928 + s * 0.072));
+    filtered = saturate(hue_rotation * filtered);
+  }
+  float4 tint = hsla_to_srgb(sprite.tint);
+  return mix(filtered, tint.rgb, tint.a);
+}
+
+// Opacity groups are composited with the polychrome sprite vertex shader. Their
+// contents are drawn into transparent textures, which leaves the colors in them
+// premultiplied.
+fragment float4 opacity_group_fragment(
+    PolychromeSpriteFragmentInput input [[stage_in]],
+    constant PolychromeSprite *sprites [[buffer(SpriteInputIndex_Sprites)]],
+    texture2d<float> group_texture [[texture(SpriteInputIndex_AtlasTexture)]]) {
+  PolychromeSprite sprite = sprites[input.sprite_id];
+  constexpr sampler group_texture_sampler(mag_filter::linear,
+                                          min_filter::linear);
+  float4 sample = group_texture.sample(group_texture_sampler, input.tile_position);
+  if (sample.a == 0.) {
+    return float4(0.);
+  }
+  return float4(sample.rgb / sample.a, sample.a * sprite.opacity);
 }
 
 struct PathRasterizationVertexOutput {
@@ -426,6 +665,17 @@ fragment float4 path_rasterization_fragment(PathRasterizationFragmentInput input
                                             [[stage_in]]) {
   float2 dx = dfdx(input.st_position);
   float2 dy = dfdy(input.st_position);
+  // The fringes along straight edges have a negative t coordinate, and an s coordinate
+  // going from 1 on the left of the edge to -1 on its right. Paths are filled where they're
+  // covered an odd number of times, so taking coverage away on the right is the same as
+  // adding 2 minus it.
+  if (input.st_position.y < 0.) {
+    float distance = input.st_position.x / length(float2(dx.x, dy.x));
+    float coverage = saturate(0.5 - abs(distance));
+    return float4(distance < 0. && coverage > 0. ? 2. - coverage : coverage, 0.,
+                  0., 1.);
+  }
+
   float2 gradient = float2((2. * input.st_position.x) * dx.x - dx.y,
                            (2. * input.st_position.x) * dy.x - dy.y);
   float f = (input.st_position.x * input.st_position.x) - input.st_position.y;
@@ -434,10 +684,72 @@ fragment float4 path_rasterization_fragment(PathRasterizationFragmentInput input
   return float4(alpha, 0., 0., 1.);
 }
 
+// How many samples each pixel of a glyph is divided into along each axis.
+constant uint GLYPH_SAMPLES_PER_AXIS = 4;
+
+// Fills a pixel of a glyph's tile with the fraction of its samples that are
+// inside the glyph's outline, by the nonzero winding rule. Each segment going
+// down across a sample's row to its right winds around it clockwise, and each
+// one going up winds around it counterclockwise.
+kernel void glyph_rasterization(
+    constant GlyphRasterizationJob *job
+    [[buffer(GlyphRasterizationInputIndex_Job)]],
+    constant float4 *segments [[buffer(GlyphRasterizationInputIndex_Segments)]],
+    texture2d<float, access::write> atlas_texture
+    [[texture(GlyphRasterizationInputIndex_AtlasTexture)]],
+    uint2 pixel [[thread_position_in_grid]]) {
+  if (pixel.x >= uint(job->tile_bounds.size.width) ||
+      pixel.y >= uint(job->tile_bounds.size.height)) {
+    return;
+  }
+
+  int winding[GLYPH_SAMPLES_PER_AXIS][GLYPH_SAMPLES_PER_AXIS] = {};
+  float2 pixel_origin = float2(pixel);
+  for (uint i = 0; i < job->segment_count; i++) {
+    float4 segment = segments[job->segment_start + i];
+    float2 from = segment.xy - pixel_origin;
+    float2 to = segment.zw - pixel_origin;
+    float top = min(from.y, to.y);
+    float bottom = max(from.y, to.y);
+    if (bottom <= 0. || top >= 1.) {
+      continue;
+    }
+
+    int direction = to.y > from.y ? 1 : -1;
+    for (uint row = 0; row < GLYPH_SAMPLES_PER_AXIS; row++) {
+      float y = (float(row) + 0.5) / GLYPH_SAMPLES_PER_AXIS;
+      // Rows are crossed at the top end of segments but not the bottom one, so
+      // that rows going through a vertex shared by two segments are crossed
+      // once.
+      if (y < top || y >= bottom) {
+        continue;
+      }
+      float x = mix(from.x, to.x, (y - from.y) / (to.y - from.y));
+      for (uint column = 0; column < GLYPH_SAMPLES_PER_AXIS; column++) {
+        if ((float(column) + 0.5) / GLYPH_SAMPLES_PER_AXIS < x) {
+          winding[row][column] += direction;
+        }
+      }
+    }
+  }
+
+  uint covered_samples = 0;
+  for (uint row = 0; row < GLYPH_SAMPLES_PER_AXIS; row++) {
+    for (uint column = 0; column < GLYPH_SAMPLES_PER_AXIS; column++) {
+      covered_samples += winding[row][column] != 0 ? 1 : 0;
+    }
+  }
+  float coverage =
+      float(covered_samples) / (GLYPH_SAMPLES_PER_AXIS * GLYPH_SAMPLES_PER_AXIS);
+  uint2 texel = uint2(job->tile_bounds.origin.x, job->tile_bounds.origin.y) + pixel;
+  atlas_texture.write(float4(coverage, 0., 0., 1.), texel);
+}
+
 struct PathSpriteVertexOutput {
   float4 position [[position]];
   float2 tile_position;
   float4 color [[flat]];
+  uint sprite_id [[flat]];
 };
 
 vertex PathSpriteVertexOutput path_sprite_vertex(
@@ -456,8 +768,9 @@ vertex PathSpriteVertexOutput path_sprite_vertex(
   float4 device_position =
       to_device_position(unit_vertex, sprite.bounds, viewport_size);
   float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
-  float4 color = hsla_to_rgba(sprite.color);
-  return PathSpriteVertexOutput{device_position, tile_position, color};
+  float4 color = hsla_to_rgba(sprite.color.solid);
+  return PathSpriteVertexOutput{device_position, tile_position, color,
+                                sprite_id};
 }
 
 fragment float4 path_sprite_fragment(
@@ -468,8 +781,15 @@ fragment float4 path_sprite_fragment(
                                           min_filter::linear);
   float4 sample =
       atlas_texture.sample(atlas_texture_sampler, input.tile_position);
-  float mask = 1. - abs(1. - fmod(sample.r, 2.));
+  PathSprite sprite = sprites[input.sprite_id];
+  // Overlapping triangles alternately fill and cut out the path, unless they're
+  // all filled, like the ones of a stroke.
+  float mask = sprite.nonzero ? min(sample.r, 1.)
+                              : 1. - abs(1. - fmod(sample.r, 2.));
   float4 color = input.color;
+  if (sprite.color.tag != BackgroundTag_Solid) {
+    color = gradient_color(sprite.color, input.position.xy, sprite.path_bounds);
+  }
   color.a *= mask;
   return color;
 }
@@ -526,7 +846,36 @@ fragment float4 surface_fragment(SurfaceFragmentInput input [[stage_in]],
   return ycbcrToRGBTransform * ycbcr;
 }
 
+// Samples a texture drawn by another renderer, whose colors are sRGB with
+// straight alpha.
+fragment float4 texture_surface_fragment(SurfaceFragmentInput input
+                                         [[stage_in]],
+                                         texture2d<float> texture
+                                         [[texture(SurfaceInputIndex_Texture)]]) {
+  constexpr sampler texture_sampler(mag_filter::linear, min_filter::linear);
+  float4 color = texture.sample(texture_sampler, input.texture_position);
+  return float4(to_surface_color_space(color.rgb), color.a);
+}
+
 float4 hsla_to_rgba(Hsla hsla) {
+  float4 color = hsla_to_srgb(hsla);
+  return float4(to_surface_color_space(color.rgb), color.a);
+}
+
+// Maps an sRGB color to the color space of the layer.
+float3 to_surface_color_space(float3 color) {
+  if (!convert_to_display_p3) {
+    return color;
+  }
+  // Maps linear sRGB to linear Display P3, given column by column.
+  float3x3 srgb_to_display_p3 =
+      float3x3(float3(0.8224621, 0.0331941, 0.0170827),
+               float3(0.1775380, 0.9668058, 0.0723974),
+               float3(0., 0., 0.9105199));
+  return linear_to_srgb(srgb_to_display_p3 * srgb_to_linear(color));
+}
+
+float4 hsla_to_srgb(Hsla hsla) {
   float h = hsla.h * 6.0; // Now, it's an angle but scaled in [0, 6) range
   float s = hsla.s;
   float l = hsla.l;
@@ -609,6 +958,12 @@ float4 to_device_position_transformed(float2 unit_vertex, Bounds_ScaledPixels bo
   return float4(device_position, 0., 1.);
 }
 
+// The position of a vertex before the primitive's transformation is applied,
+// which fragments use to shade in the primitive's own coordinate space.
+float2 to_local_position(float2 unit_vertex, Bounds_ScaledPixels bounds) {
+  return unit_vertex * float2(bounds.size.width, bounds.size.height) +
+         float2(bounds.origin.x, bounds.origin.y);
+}
 
 float2 to_tile_position(float2 unit_vertex, AtlasTile tile,
                         constant Size_DevicePixels *atlas_size) {
@@ -618,6 +973,77 @@ float2 to_tile_position(float2 unit_vertex, AtlasTile tile,
          float2((float)atlas_size->width, (float)atlas_size->height);
 }
 
+// Maps a position along one axis of a nine-slice sprite to its tile: the borders
+// at either end keep their size, and the center stretches to fill the rest.
+float nine_slice_axis(float position, float size, float start, float end,
+                      float tile_size, float tile_start, float tile_end) {
+  if (position < start) {
+    return position / start * tile_start;
+  }
+  if (position > size - end) {
+    return tile_size - (size - position) / end * tile_end;
+  }
+  float center = max(size - start - end, 0.0001);
+  return tile_start +
+         (position - start) / center * (tile_size - tile_start - tile_end);
+}
+
+bool is_repeating(PolychromeSprite sprite) {
+  return sprite.repeat_size.width > 0. && sprite.repeat_size.height > 0.;
+}
+
+// Maps a position in a sprite to the copy of its tile it falls in when the tile
+// repeats. Positions in the spacing between copies end up past the size of a
+// copy.
+float2 to_repeat_position(float2 local_position, PolychromeSprite sprite) {
+  float2 position = local_position - float2(sprite.bounds.origin.x,
+                                            sprite.bounds.origin.y);
+  if (!is_repeating(sprite)) {
+    return position;
+  }
+  float2 cell_size =
+      float2(sprite.repeat_size.width + sprite.repeat_spacing.width,
+             sprite.repeat_size.height + sprite.repeat_spacing.height);
+  float2 offset = position - float2(sprite.repeat_origin.x,
+                                    sprite.repeat_origin.y);
+  return offset - floor(offset / cell_size) * cell_size;
+}
+
+float2 to_nine_slice_tile_position(float2 position, float2 size,
+                                   PolychromeSprite sprite, float2 atlas_size) {
+  float2 tile_size =
+      float2(sprite.tile.bounds.size.width, sprite.tile.bounds.size.height);
+  float x = nine_slice_axis(position.x, size.x, sprite.nine_slice.left,
+                            sprite.nine_slice.right, tile_size.x,
+                            sprite.tile_nine_slice.left,
+                            sprite.tile_nine_slice.right);
+  float y = nine_slice_axis(position.y, size.y, sprite.nine_slice.top,
+                            sprite.nine_slice.bottom, tile_size.y,
+                            sprite.tile_nine_slice.top,
+                            sprite.tile_nine_slice.bottom);
+  // Keep the samples within the tile, so filtering doesn't blend in its
+  // neighbors in the atlas.
+  float2 tile_position = clamp(float2(x, y), float2(0.5), tile_size - 0.5);
+  float2 tile_origin =
+      float2(sprite.tile.bounds.origin.x, sprite.tile.bounds.origin.y);
+  return (tile_origin + tile_position) / atlas_size;
+}
+
+// The coverage of a dash pattern at the given distance along a line,
+// anti-aliasing the ends of the dashes. Patterns without dashes are continuous.
+float dash_pattern_alpha(float distance, float dash, float gap, float offset) {
+  if (dash <= 0.) {
+    return 1.;
+  }
+  float period = dash + gap;
+  float position = distance + offset;
+  float position_in_period = position - floor(position / period) * period;
+  float distance_into_dash =
+      max(min(position_in_period, dash - position_in_period),
+          position_in_period - period);
+  return saturate(0.5 + distance_into_dash);
+}
+
 float quad_sdf(float2 point, Bounds_ScaledPixels bounds,
                Corners_ScaledPixels corner_radii) {
   float2 half_size = float2(bounds.size.width, bounds.size.height) / 2.;
@@ -691,3 +1117,119 @@ float4 over(float4 below, float4 above) {
   result.a = alpha;
   return result;
 }
+
+// Premultiplies the color returned by a fragment function when its pipeline
+// expects it to be.
+float4 blend_output(float4 color) {
+  if (premultiply_output) {
+    return float4(color.rgb * color.a, color.a);
+  }
+  return color;
+}
+
+// https://bottosson.github.io/posts/oklab/
+float3 linear_srgb_to_oklab(float3 color) {
+  float3 lms = float3(
+      0.4122214708 * color.r + 0.5363325363 * color.g + 0.0514459929 * color.b,
+      0.2119034982 * color.r + 0.6806995451 * color.g + 0.1073969566 * color.b,
+      0.0883024619 * color.r + 0.2817188376 * color.g + 0.6299787005 * color.b);
+  float3 lms_ = pow(max(lms, float3(0.)), float3(1. / 3.));
+  return float3(
+      0.2104542553 * lms_.x + 0.7936177850 * lms_.y - 0.0040720468 * lms_.z,
+      1.9779984951 * lms_.x - 2.4285922050 * lms_.y + 0.4505937099 * lms_.z,
+      0.0259040371 * lms_.x + 0.7827717662 * lms_.y - 0.8086757660 * lms_.z);
+}
+
+float3 oklab_to_linear_srgb(float3 lab) {
+  float3 lms_ = float3(lab.x + 0.3963377774 * lab.y + 0.2158037573 * lab.z,
+                       lab.x - 0.1055613458 * lab.y - 0.0638541728 * lab.z,
+                       lab.x - 0.0894841775 * lab.y - 1.2914855480 * lab.z);
+  float3 lms = lms_ * lms_ * lms_;
+  return float3(
+      4.0767416621 * lms.x - 3.3077118299 * lms.y + 0.2309699292 * lms.z,
+      -1.2684380046 * lms.x + 2.6097574011 * lms.y - 0.3413193965 * lms.z,
+      -0.0041960863 * lms.x - 0.7034186147 * lms.y + 1.7076147010 * lms.z);
+}
+
+// Extended sRGB colors, which are outside of sRGB's gamut, have components below
+// 0 that are mirrored around it.
+float3 srgb_to_linear(float3 color) {
+  float3 magnitude = fabs(color);
+  return copysign(select(pow((magnitude + 0.055) / 1.055, float3(2.4)),
+                         magnitude / 12.92, magnitude < 0.04045),
+                  color);
+}
+
+float3 linear_to_srgb(float3 color) {
+  float3 magnitude = fabs(color);
+  return copysign(select(1.055 * pow(magnitude, float3(1. / 2.4)) - 0.055,
+                         magnitude * 12.92, magnitude < 0.0031308),
+                  color);
+}
+
+// Interpolates between two sRGB colors in the Oklab color space with
+// premultiplied alpha, like CSS gradients, so that the midpoints aren't muddy
+// and transparent colors don't darken the colors they are mixed with.
+float4 mix_oklab(float4 a, float4 b, float t) {
+  float4 start = float4(linear_srgb_to_oklab(srgb_to_linear(a.rgb)) * a.a, a.a);
+  float4 end = float4(linear_srgb_to_oklab(srgb_to_linear(b.rgb)) * b.a, b.a);
+  float4 color = mix(start, end, t);
+  if (color.a == 0.) {
+    return float4(0.);
+  }
+  return float4(linear_to_srgb(oklab_to_linear_srgb(color.rgb / color.a)),
+                color.a);
+}
+
+// Returns the position of the point along a gradient, like CSS gradients:
+// angles are in degrees, where 0 points up and angles increase clockwise.
+float gradient_position(Background background, float2 position,
+                        Bounds_ScaledPixels bounds) {
+  float angle = background.angle * M_PI_F / 180.;
+  float2 origin = float2(bounds.origin.x, bounds.origin.y);
+  float2 size = float2(bounds.size.width, bounds.size.height);
+  if (background.tag == BackgroundTag_LinearGradient) {
+    // The gradient line goes through the center, and is long enough for its
+    // ends to reach the corners.
+    float2 direction = float2(sin(angle), -cos(angle));
+    float line_length =
+        fabs(size.x * direction.x) + fabs(size.y * direction.y);
+    float2 center = origin + size / 2.;
+    return dot(position - center, direction) / max(line_length, 0.0001) + 0.5;
+  }
+
+  float2 center =
+      origin + size * float2(background.center_x, background.center_y);
+  float2 center_to_position = position - center;
+  if (background.tag == BackgroundTag_RadialGradient) {
+    // An ellipse reaching the farthest corner, with the aspect ratio of the
+    // farthest sides.
+    float2 farthest_sides = max(center - origin, origin + size - center);
+    float2 radii = max(farthest_sides * sqrt(2.), float2(0.0001));
+    return length(center_to_position / radii);
+  }
+
+  float position_angle = atan2(center_to_position.x, -center_to_position.y);
+  return fract((position_angle - angle) / (2. * M_PI_F));
+}
+
+// Computes the color of a gradient at the given position.
+float4 gradient_color(Background background, float2 position,
+                      Bounds_ScaledPixels bounds) {
+  if (background.stop_count == 0) {
+    return float4(0.);
+  }
+  float t = gradient_position(background, position, bounds);
+
+  float4 color = hsla_to_srgb(background.stops[0].color);
+  for (uint i = 1; i < background.stop_count; i++) {
+    LinearColorStop start = background.stops[i - 1];
+    LinearColorStop end = background.stops[i];
+    if (t >= start.percentage) {
+      float span = max(end.percentage - start.percentage, 0.0001);
+      color = mix_oklab(hsla_to_srgb(start.color), hsla_to_srgb(end.color),
+                        saturate((t - start.percentage) / span));
+    }
+  }
+  return float4(to_surface_color_space(color.rgb), color.a);
+}
@@ -1,7 +1,8 @@
 use crate::{
     point, px, size, Bounds, DevicePixels, Font, FontFallbacks, FontFeatures, FontId, FontMetrics,
-    FontRun, FontStyle, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
-    RenderGlyphParams, Result, ShapedGlyph, ShapedRun, SharedString, Size, SUBPIXEL_VARIANTS,
+    FontRun, FontStyle, FontWeight, GlyphId, GlyphOutline, GlyphOutlineBuilder, LineLayout, Pixels,
+    PlatformTextSystem, Point, RenderGlyphParams, Result, ShapedGlyph, ShapedRun, SharedString,
+    Size, SUBPIXEL_VARIANTS,
 };
 use anyhow::anyhow;
 use cocoa::appkit::CGFloat;
@@ -31,12 +32,14 @@ use font_kit::{
     handle::Handle,
     hinting::HintingOptions,
     metrics::Metrics,
+    outline::OutlineSink,
     properties::{Style as FontkitStyle, Weight as FontkitWeight},
     source::SystemSource,
     sources::mem::MemSource,
 };
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use pathfinder_geometry::{
+    line_segment::LineSegment2F,
     rect::{RectF, RectI},
     transform2d::Transform2F,
     vector::{Vector2F, Vector2I},
@@ -178,6 +181,14 @@ impl PlatformTextSystem for MacTextSystem {
         self.0.read().rasterize_glyph(glyph_id, raster_bounds)
     }
 
+    fn glyph_outline(
+        &self,
+        params: &RenderGlyphParams,
+        raster_bounds: Bounds<DevicePixels>,
+    ) -> Result<Option<GlyphOutline>> {
+        self.0.read().glyph_outline(params, raster_bounds)
+    }
+
     fn layout_line(&self, text: &str, font_size: Pixels, font_runs: &[FontRun]) -> LineLayout {
         self.0.write().layout_line(text, font_size, font_runs)
     }
@@ -430,6 +441,47 @@ impl MacTextSystemState {
         }
     }
 
+    /// The outline of a glyph, positioned within its tile like [`Self::rasterize_glyph`] draws it,
+    /// or `None` for glyphs without one, like those of bitmap fonts.
+    fn glyph_outline(
+        &self,
+        params: &RenderGlyphParams,
+        glyph_bounds: Bounds<DevicePixels>,
+    ) -> Result<Option<GlyphOutline>> {
+        if glyph_bounds.size.width.0 == 0 || glyph_bounds.size.height.0 == 0 {
+            return Ok(None);
+        }
+        let mut tile_size = glyph_bounds.size;
+        if params.subpixel_variant.x > 0 {
+            tile_size.width += DevicePixels(1);
+        }
+        if params.subpixel_variant.y > 0 {
+            tile_size.height += DevicePixels(1);
+        }
+
+        let font = &self.fonts[params.font_id.0];
+        let scale =
+            f32::from(params.font_size) * params.scale_factor / font.metrics().units_per_em as f32;
+        let subpixel_shift = params
+            .subpixel_variant
+            .map(|v| v as f32 / SUBPIXEL_VARIANTS as f32);
+        // Font units point up from the baseline, while tiles are laid out top to bottom from the
+        // top of the bitmap `rasterize_glyph` draws into.
+        let mut sink = GlyphOutlineSink {
+            builder: GlyphOutlineBuilder::new(tile_size),
+            scale,
+            offset: point(
+                subpixel_shift.x - glyph_bounds.origin.x.0 as f32,
+                (tile_size.height - glyph_bounds.size.height).0 as f32
+                    - glyph_bounds.origin.y.0 as f32
+                    - subpixel_shift.y,
+            ),
+        };
+        font.outline(params.glyph_id.0, HintingOptions::None, &mut sink)?;
+        let outline = sink.builder.build();
+        Ok((!outline.segments.is_empty()).then_some(outline))
+    }
+
     fn layout_line(&mut self, text: &str, font_size: Pixels, font_runs: &[FontRun]) -> LineLayout {
         // Construct the attributed string, converting UTF8 ranges to UTF16 ranges.
         let mut string = CFMutableAttributedString::new();
@@ -511,11 +563,55 @@ impl MacTextSystemState {
             ascent: typographic_bounds.ascent.into(),
             descent: typographic_bounds.descent.into(),
             len: text.len(),
+            ligature_carets: Vec::new(),
         }
     }
 }
 
 #[derive(Clone)]
+/// Builds a [`GlyphOutline`] from the outline of a glyph in font units.
+struct GlyphOutlineSink {
+    builder: GlyphOutlineBuilder,
+    scale: f32,
+    offset: Point<f32>,
+}
+
+impl GlyphOutlineSink {
+    fn to_tile(&self, vector: Vector2F) -> Point<f32> {
+        point(
+            vector.x() * self.scale + self.offset.x,
+            -vector.y() * self.scale + self.offset.y,
+        )
+    }
+}
+
+impl OutlineSink for GlyphOutlineSink {
+    fn move_to(&mut self, to: Vector2F) {
+        let to = self.to_tile(to);
+        self.builder.move_to(to);
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        let to = self.to_tile(to);
+        self.builder.line_to(to);
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let (ctrl, to) = (self.to_tile(ctrl), self.to_tile(to));
+        self.builder.quadratic_to(ctrl, to);
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let (ctrl_a, ctrl_b) = (self.to_tile(ctrl.from()), self.to_tile(ctrl.to()));
+        let to = self.to_tile(to);
+        self.builder.cubic_to(ctrl_a, ctrl_b, to);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
 struct StringIndexConverter<'a> {
     text: &'a str,
     utf8_ix: usize,
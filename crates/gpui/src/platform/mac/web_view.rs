@@ -0,0 +1,345 @@
+use super::{ns_string, NSStringExt};
+use crate::{Bounds, Pixels, PlatformWebView, WebViewEvent, WebViewNavigation};
+use cocoa::{
+    appkit::NSView,
+    base::{id, nil},
+    foundation::{NSPoint, NSRect, NSSize},
+};
+use ctor::ctor;
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Class, Object, Protocol, Sel, BOOL, NO, YES},
+    sel, sel_impl,
+};
+use std::{cell::RefCell, ffi::c_void, ptr};
+
+#[link(name = "WebKit", kind = "framework")]
+extern "C" {}
+
+const CALLBACK_IVAR: &str = "eventCallback";
+const MESSAGE_HANDLER_NAME: &str = "gpui";
+
+/// Defines `window.gpui.postMessage` in every page, serializing messages to JSON so that any value
+/// can be sent to the app.
+const MESSAGE_SCRIPT: &str = "window.gpui = { postMessage: (message) => \
+    window.webkit.messageHandlers.gpui.postMessage(JSON.stringify(message)) };";
+
+#[allow(non_upper_case_globals)]
+const WKUserScriptInjectionTimeAtDocumentStart: isize = 0;
+
+static mut DELEGATE_CLASS: *const Class = ptr::null();
+
+type EventCallback = RefCell<Option<Box<dyn FnMut(WebViewEvent)>>>;
+
+#[ctor]
+unsafe fn build_classes() {
+    DELEGATE_CLASS = {
+        let mut decl = ClassDecl::new("GPUIWebViewDelegate", class!(NSObject)).unwrap();
+        decl.add_ivar::<*mut c_void>(CALLBACK_IVAR);
+
+        decl.add_method(
+            sel!(webView:didStartProvisionalNavigation:),
+            did_start_navigation as extern "C" fn(&Object, Sel, id, id),
+        );
+        decl.add_method(
+            sel!(webView:didFinishNavigation:),
+            did_finish_navigation as extern "C" fn(&Object, Sel, id, id),
+        );
+        decl.add_method(
+            sel!(webView:didFailNavigation:withError:),
+            did_fail_navigation as extern "C" fn(&Object, Sel, id, id, id),
+        );
+        decl.add_method(
+            sel!(webView:didFailProvisionalNavigation:withError:),
+            did_fail_navigation as extern "C" fn(&Object, Sel, id, id, id),
+        );
+        decl.add_method(
+            sel!(userContentController:didReceiveScriptMessage:),
+            did_receive_script_message as extern "C" fn(&Object, Sel, id, id),
+        );
+
+        if let Some(protocol) = Protocol::get("WKNavigationDelegate") {
+            decl.add_protocol(protocol);
+        }
+        if let Some(protocol) = Protocol::get("WKScriptMessageHandler") {
+            decl.add_protocol(protocol);
+        }
+
+        decl.register()
+    };
+}
+
+/// A WKWebView inside a view that clips it, added to a window's content view.
+pub(crate) struct MacWebView {
+    container: id,
+    web_view: id,
+    delegate: id,
+    event_callback: Box<EventCallback>,
+}
+
+impl MacWebView {
+    pub(crate) unsafe fn new(parent: id) -> Self {
+        let event_callback: Box<EventCallback> = Box::default();
+        let delegate: id = msg_send![DELEGATE_CLASS, new];
+        (*delegate).set_ivar(
+            CALLBACK_IVAR,
+            event_callback.as_ref() as *const EventCallback as *mut c_void,
+        );
+
+        let configuration: id = msg_send![class!(WKWebViewConfiguration), new];
+        let content_controller: id = msg_send![configuration, userContentController];
+        let script: id = msg_send![class!(WKUserScript), alloc];
+        let script: id = msg_send![
+            script,
+            initWithSource: ns_string(MESSAGE_SCRIPT)
+            injectionTime: WKUserScriptInjectionTimeAtDocumentStart
+            forMainFrameOnly: YES
+        ];
+        let _: () = msg_send![content_controller, addUserScript: script];
+        let _: () = msg_send![script, release];
+        let _: () = msg_send![
+            content_controller,
+            addScriptMessageHandler: delegate
+            name: ns_string(MESSAGE_HANDLER_NAME)
+        ];
+
+        let zero = NSRect::new(NSPoint::new(0., 0.), NSSize::new(0., 0.));
+        let web_view: id = msg_send![class!(WKWebView), alloc];
+        let web_view: id = msg_send![web_view, initWithFrame: zero configuration: configuration];
+        let _: () = msg_send![configuration, release];
+        let _: () = msg_send![web_view, setNavigationDelegate: delegate];
+
+        // The web view is clipped by a container with the bounds of the content mask, since
+        // views don't clip their subviews by default.
+        let container: id = msg_send![class!(NSView), alloc];
+        let container = NSView::initWithFrame_(container, zero);
+        container.setWantsLayer(YES);
+        let layer: id = msg_send![container, layer];
+        let _: () = msg_send![layer, setMasksToBounds: YES];
+        let _: () = msg_send![container, setHidden: YES];
+        let _: () = msg_send![container, addSubview: web_view];
+        let _: () = msg_send![parent, addSubview: container];
+
+        Self {
+            container,
+            web_view,
+            delegate,
+            event_callback,
+        }
+    }
+}
+
+impl PlatformWebView for MacWebView {
+    fn set_frame(&self, bounds: Bounds<Pixels>, clip: Bounds<Pixels>) {
+        unsafe {
+            let parent: id = msg_send![self.container, superview];
+            if parent == nil {
+                return;
+            }
+            // The window's content view isn't flipped, so its origin is at the bottom left.
+            let parent_frame = NSView::frame(parent);
+            let container_frame = NSRect::new(
+                NSPoint::new(
+                    clip.origin.x.0 as f64,
+                    parent_frame.size.height - clip.bottom().0 as f64,
+                ),
+                NSSize::new(clip.size.width.0 as f64, clip.size.height.0 as f64),
+            );
+            let web_view_frame = NSRect::new(
+                NSPoint::new(
+                    (bounds.origin.x - clip.origin.x).0 as f64,
+                    (clip.bottom() - bounds.bottom()).0 as f64,
+                ),
+                NSSize::new(bounds.size.width.0 as f64, bounds.size.height.0 as f64),
+            );
+            let _: () = msg_send![self.container, setFrame: container_frame];
+            let _: () = msg_send![self.web_view, setFrame: web_view_frame];
+            let _: () = msg_send![self.container, setHidden: NO];
+        }
+    }
+
+    fn hide(&self) {
+        unsafe {
+            let _: () = msg_send![self.container, setHidden: YES];
+        }
+    }
+
+    fn navigation(&self) -> WebViewNavigation {
+        unsafe {
+            let url: id = msg_send![self.web_view, URL];
+            let url = if url == nil {
+                None
+            } else {
+                let url: id = msg_send![url, absoluteString];
+                Some(url.to_str().to_string().into())
+            };
+            let title: id = msg_send![self.web_view, title];
+            let title = if title == nil || title.to_str().is_empty() {
+                None
+            } else {
+                Some(title.to_str().to_string().into())
+            };
+            let loading: BOOL = msg_send![self.web_view, isLoading];
+            let can_go_back: BOOL = msg_send![self.web_view, canGoBack];
+            let can_go_forward: BOOL = msg_send![self.web_view, canGoForward];
+            WebViewNavigation {
+                url,
+                title,
+                loading: loading == YES,
+                can_go_back: can_go_back == YES,
+                can_go_forward: can_go_forward == YES,
+            }
+        }
+    }
+
+    fn load_url(&self, url: &str) {
+        unsafe {
+            let ns_url: id = msg_send![class!(NSURL), URLWithString: ns_string(url)];
+            if ns_url == nil {
+                self.send_event(WebViewEvent::NavigationFailed(
+                    format!("invalid URL: {url}").into(),
+                ));
+                return;
+            }
+            let is_file_url: BOOL = msg_send![ns_url, isFileURL];
+            if is_file_url == YES {
+                let directory: id = msg_send![ns_url, URLByDeletingLastPathComponent];
+                let _: id = msg_send![
+                    self.web_view,
+                    loadFileURL: ns_url
+                    allowingReadAccessToURL: directory
+                ];
+            } else {
+                let request: id = msg_send![class!(NSURLRequest), requestWithURL: ns_url];
+                let _: id = msg_send![self.web_view, loadRequest: request];
+            }
+        }
+    }
+
+    fn load_html(&self, html: &str, base_url: Option<&str>) {
+        unsafe {
+            let base_url: id = match base_url {
+                Some(base_url) => msg_send![class!(NSURL), URLWithString: ns_string(base_url)],
+                None => nil,
+            };
+            let _: id = msg_send![self.web_view, loadHTMLString: ns_string(html) baseURL: base_url];
+        }
+    }
+
+    fn go_back(&self) {
+        unsafe {
+            let _: id = msg_send![self.web_view, goBack];
+        }
+    }
+
+    fn go_forward(&self) {
+        unsafe {
+            let _: id = msg_send![self.web_view, goForward];
+        }
+    }
+
+    fn reload(&self) {
+        unsafe {
+            let _: id = msg_send![self.web_view, reload];
+        }
+    }
+
+    fn stop_loading(&self) {
+        unsafe {
+            let _: () = msg_send![self.web_view, stopLoading];
+        }
+    }
+
+    fn evaluate_script(&self, script: &str) {
+        unsafe {
+            let _: () = msg_send![
+                self.web_view,
+                evaluateJavaScript: ns_string(script)
+                completionHandler: nil
+            ];
+        }
+    }
+
+    fn on_event(&self, callback: Box<dyn FnMut(WebViewEvent)>) {
+        self.event_callback.replace(Some(callback));
+    }
+}
+
+impl MacWebView {
+    fn send_event(&self, event: WebViewEvent) {
+        send_event(&self.event_callback, event);
+    }
+}
+
+impl Drop for MacWebView {
+    fn drop(&mut self) {
+        unsafe {
+            // The content controller retains its message handlers, so the delegate may outlive
+            // the web view until it's removed.
+            (*self.delegate).set_ivar::<*mut c_void>(CALLBACK_IVAR, ptr::null_mut());
+            let _: () = msg_send![self.web_view, setNavigationDelegate: nil];
+            let configuration: id = msg_send![self.web_view, configuration];
+            let content_controller: id = msg_send![configuration, userContentController];
+            let _: () = msg_send![
+                content_controller,
+                removeScriptMessageHandlerForName: ns_string(MESSAGE_HANDLER_NAME)
+            ];
+            let _: () = msg_send![self.container, removeFromSuperview];
+            let _: () = msg_send![self.web_view, release];
+            let _: () = msg_send![self.container, release];
+            let _: () = msg_send![self.delegate, release];
+        }
+    }
+}
+
+fn send_event(callback: &EventCallback, event: WebViewEvent) {
+    if let Ok(mut callback) = callback.try_borrow_mut() {
+        if let Some(callback) = callback.as_mut() {
+            callback(event);
+        }
+    }
+}
+
+unsafe fn delegate_event_callback<'a>(delegate: &'a Object) -> Option<&'a EventCallback> {
+    let callback: *mut c_void = *delegate.get_ivar(CALLBACK_IVAR);
+    (callback as *const EventCallback).as_ref()
+}
+
+extern "C" fn did_start_navigation(this: &Object, _: Sel, _: id, _: id) {
+    if let Some(callback) = unsafe { delegate_event_callback(this) } {
+        send_event(callback, WebViewEvent::NavigationStarted);
+    }
+}
+
+extern "C" fn did_finish_navigation(this: &Object, _: Sel, _: id, _: id) {
+    if let Some(callback) = unsafe { delegate_event_callback(this) } {
+        send_event(callback, WebViewEvent::NavigationFinished);
+    }
+}
+
+extern "C" fn did_fail_navigation(this: &Object, _: Sel, _: id, _: id, error: id) {
+    if let Some(callback) = unsafe { delegate_event_callback(this) } {
+        let description = unsafe {
+            let description: id = msg_send![error, localizedDescription];
+            description.to_str().to_string()
+        };
+        send_event(callback, WebViewEvent::NavigationFailed(description.into()));
+    }
+}
+
+extern "C" fn did_receive_script_message(this: &Object, _: Sel, _: id, message: id) {
+    if let Some(callback) = unsafe { delegate_event_callback(this) } {
+        let body = unsafe {
+            let body: id = msg_send![message, body];
+            let is_string: BOOL = msg_send![body, isKindOfClass: class!(NSString)];
+            if is_string == YES {
+                body.to_str().to_string()
+            } else {
+                return;
+            }
+        };
+        send_event(callback, WebViewEvent::Message(body));
+    }
+}
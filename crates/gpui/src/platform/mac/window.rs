@@ -1,15 +1,16 @@
-use super::{ns_string, renderer, MacDisplay, NSRange, NSStringExt};
+use super::{ns_string, renderer, MacDisplay, MacWebView, NSRange, NSStringExt};
 use crate::{
     platform::PlatformInputHandler, point, px, size, AnyWindowHandle, Bounds, DisplayLink,
     ExternalPaths, FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
-    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptLevel, Size, Timer,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowKind, WindowParams,
+    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWebView, PlatformWindow, Point,
+    PromptLevel, Size, Timer, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowKind, WindowParams,
 };
 use block::ConcreteBlock;
 use cocoa::{
     appkit::{
-        NSApplication, NSBackingStoreBuffered, NSColor, NSEvent, NSEventModifierFlags,
+        NSApplication, NSBackingStoreBuffered, NSColor, NSEvent, NSEventModifierFlags, NSEventType,
         NSFilenamesPboardType, NSPasteboard, NSScreen, NSView, NSViewHeightSizable,
         NSViewWidthSizable, NSWindow, NSWindowButton, NSWindowCollectionBehavior,
         NSWindowOcclusionState, NSWindowStyleMask, NSWindowTitleVisibility,
@@ -332,6 +333,7 @@ struct MacWindowState {
     should_close_callback: Option<Box<dyn FnMut() -> bool>>,
     close_callback: Option<Box<dyn FnOnce()>>,
     appearance_changed_callback: Option<Box<dyn FnMut()>>,
+    occlusion_change_callback: Option<Box<dyn FnMut(bool)>>,
     input_handler: Option<PlatformInputHandler>,
     last_key_equivalent: Option<KeyDownEvent>,
     synthetic_drag_counter: usize,
@@ -504,6 +506,7 @@ impl MacWindow {
             show,
             display_id,
             window_min_size,
+            color_space,
         }: WindowParams,
         executor: ForegroundExecutor,
         renderer_context: renderer::Context,
@@ -602,6 +605,7 @@ impl MacWindow {
                     native_view as *mut _,
                     bounds.size.map(|pixels| pixels.0),
                     false,
+                    color_space,
                 ),
                 request_frame_callback: None,
                 event_callback: None,
@@ -611,6 +615,7 @@ impl MacWindow {
                 should_close_callback: None,
                 close_callback: None,
                 appearance_changed_callback: None,
+                occlusion_change_callback: None,
                 input_handler: None,
                 last_key_equivalent: None,
                 synthetic_drag_counter: 0,
@@ -1106,11 +1111,19 @@ impl PlatformWindow for MacWindow {
         self.0.lock().appearance_changed_callback = Some(callback);
     }
 
+    fn on_occlusion_change(&self, callback: Box<dyn FnMut(bool)>) {
+        self.0.lock().occlusion_change_callback = Some(callback);
+    }
+
     fn draw(&self, scene: &crate::Scene) {
         let mut this = self.0.lock();
         this.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &crate::Scene) -> anyhow::Result<image::RgbaImage> {
+        self.0.lock().renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         self.0.lock().renderer.sprite_atlas().clone()
     }
@@ -1125,6 +1138,11 @@ impl PlatformWindow for MacWindow {
             let _: () = msg_send![input_context, invalidateCharacterCoordinates];
         }
     }
+
+    fn create_web_view(&self) -> Option<Box<dyn PlatformWebView>> {
+        let native_view = self.0.lock().native_view.as_ptr();
+        Some(Box::new(unsafe { MacWebView::new(native_view) }))
+    }
 }
 
 impl rwh::HasWindowHandle for MacWindow {
@@ -1332,6 +1350,22 @@ extern "C" fn handle_key_event(this: &Object, native_event: id, key_equivalent:
 }
 
 extern "C" fn handle_view_event(this: &Object, _: Sel, native_event: id) {
+    // Clicking outside of an embedded web view hands the keyboard back to the window.
+    unsafe {
+        if matches!(
+            native_event.eventType(),
+            NSEventType::NSLeftMouseDown
+                | NSEventType::NSRightMouseDown
+                | NSEventType::NSOtherMouseDown
+        ) {
+            let native_window: id = msg_send![this, window];
+            let first_responder: id = msg_send![native_window, firstResponder];
+            if first_responder != this as *const Object as id {
+                native_window.makeFirstResponder_(this as *const Object as id);
+            }
+        }
+    }
+
     let window_state = unsafe { get_window_state(this) };
     let weak_window_state = Arc::downgrade(&window_state);
     let mut lock = window_state.as_ref().lock();
@@ -1474,17 +1508,22 @@ extern "C" fn cancel_operation(this: &Object, _sel: Sel, _sender: id) {
 
 extern "C" fn window_did_change_occlusion_state(this: &Object, _: Sel, _: id) {
     let window_state = unsafe { get_window_state(this) };
-    let lock = &mut *window_state.lock();
-    unsafe {
-        if lock
-            .native_window
+    let mut lock = window_state.lock();
+    let visible = unsafe {
+        lock.native_window
             .occlusionState()
             .contains(NSWindowOcclusionState::NSWindowOcclusionStateVisible)
-        {
-            lock.start_display_link();
-        } else {
-            lock.stop_display_link();
-        }
+    };
+    if visible {
+        lock.start_display_link();
+    } else {
+        lock.stop_display_link();
+    }
+
+    if let Some(mut callback) = lock.occlusion_change_callback.take() {
+        drop(lock);
+        callback(!visible);
+        window_state.lock().occlusion_change_callback = Some(callback);
     }
 }
 
@@ -1513,6 +1552,13 @@ extern "C" fn window_did_change_screen(this: &Object, _: Sel, _: id) {
     let window_state = unsafe { get_window_state(this) };
     let mut lock = window_state.as_ref().lock();
     lock.start_display_link();
+
+    // The window may have changed screens without moving, e.g. when a display was disconnected.
+    if let Some(mut callback) = lock.moved_callback.take() {
+        drop(lock);
+        callback();
+        window_state.lock().moved_callback = Some(callback);
+    }
 }
 
 extern "C" fn window_did_change_key_status(this: &Object, selector: Sel, _: id) {
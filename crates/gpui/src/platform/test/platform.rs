@@ -1,7 +1,7 @@
 use crate::{
-    AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle, ForegroundExecutor, Keymap,
-    Platform, PlatformDisplay, PlatformTextSystem, Task, TestDisplay, TestWindow, WindowAppearance,
-    WindowParams,
+    AccessibilityPreferences, AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle,
+    ForegroundExecutor, Keymap, Platform, PlatformDisplay, PlatformTextSystem, PowerState, Task,
+    TestDisplay, TestWindow, WindowAppearance, WindowParams,
 };
 use anyhow::Result;
 use collections::VecDeque;
@@ -32,6 +32,9 @@ pub(crate) struct TestPlatform {
     current_primary_item: Mutex<Option<ClipboardItem>>,
     pub(crate) prompts: RefCell<TestPrompts>,
     pub opened_url: RefCell<Option<String>>,
+    pub(crate) accessibility_preferences: RefCell<AccessibilityPreferences>,
+    pub(crate) played_system_sounds: RefCell<Vec<(String, f32)>>,
+    pub(crate) power_state: RefCell<PowerState>,
     pub text_system: Arc<dyn PlatformTextSystem>,
     #[cfg(target_os = "windows")]
     bitmap_factory: std::mem::ManuallyDrop<IWICImagingFactory>,
@@ -80,6 +83,9 @@ impl TestPlatform {
             current_primary_item: Mutex::new(None),
             weak: weak.clone(),
             opened_url: Default::default(),
+            accessibility_preferences: Default::default(),
+            played_system_sounds: Default::default(),
+            power_state: Default::default(),
             #[cfg(target_os = "windows")]
             bitmap_factory,
             text_system,
@@ -281,10 +287,32 @@ impl Platform for TestPlatform {
         *self.active_cursor.lock() = style;
     }
 
+    fn register_cursor(
+        &self,
+        _cursor: crate::CustomCursor,
+        _image: Arc<crate::RenderImage>,
+        _hotspot: crate::Point<crate::Pixels>,
+    ) {
+    }
+
     fn should_auto_hide_scrollbars(&self) -> bool {
         false
     }
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        *self.accessibility_preferences.borrow()
+    }
+
+    fn power_state(&self) -> PowerState {
+        *self.power_state.borrow()
+    }
+
+    fn play_system_sound(&self, name: &str, volume: f32) {
+        self.played_system_sounds
+            .borrow_mut()
+            .push((name.to_string(), volume));
+    }
+
     #[cfg(target_os = "linux")]
     fn write_to_primary(&self, item: ClipboardItem) {
         *self.current_primary_item.lock() = Some(item);
@@ -1,8 +1,8 @@
 use crate::{
-    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, GPUSpecs,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
-    WindowParams,
+    AnyWindowHandle, AtlasKey, AtlasStats, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult,
+    GPUSpecs, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
+    PlatformWindow, Point, Size, TestPlatform, TileId, WindowAppearance,
+    WindowBackgroundAppearance, WindowBounds, WindowParams,
 };
 use collections::HashMap;
 use parking_lot::Mutex;
@@ -14,6 +14,7 @@ use std::{
 
 pub(crate) struct TestWindowState {
     pub(crate) bounds: Bounds<Pixels>,
+    scale_factor: f32,
     pub(crate) handle: AnyWindowHandle,
     display: Rc<dyn PlatformDisplay>,
     pub(crate) title: Option<String>,
@@ -21,11 +22,13 @@ pub(crate) struct TestWindowState {
     platform: Weak<TestPlatform>,
     sprite_atlas: Arc<dyn PlatformAtlas>,
     pub(crate) should_close_handler: Option<Box<dyn FnMut() -> bool>>,
+    request_frame_callback: Option<Box<dyn FnMut()>>,
     input_callback: Option<Box<dyn FnMut(PlatformInput) -> DispatchEventResult>>,
     active_status_change_callback: Option<Box<dyn FnMut(bool)>>,
     hover_status_change_callback: Option<Box<dyn FnMut(bool)>>,
     resize_callback: Option<Box<dyn FnMut(Size<Pixels>, f32)>>,
     moved_callback: Option<Box<dyn FnMut()>>,
+    occlusion_change_callback: Option<Box<dyn FnMut(bool)>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
 }
@@ -58,6 +61,7 @@ impl TestWindow {
     ) -> Self {
         Self(Rc::new(Mutex::new(TestWindowState {
             bounds: params.bounds,
+            scale_factor: 2.0,
             display,
             platform,
             handle,
@@ -65,11 +69,13 @@ impl TestWindow {
             title: Default::default(),
             edited: false,
             should_close_handler: None,
+            request_frame_callback: None,
             input_callback: None,
             active_status_change_callback: None,
             hover_status_change_callback: None,
             resize_callback: None,
             moved_callback: None,
+            occlusion_change_callback: None,
             input_handler: None,
             is_fullscreen: false,
         })))
@@ -87,6 +93,18 @@ impl TestWindow {
         self.0.lock().resize_callback = Some(callback);
     }
 
+    pub fn simulate_scale_factor_change(&mut self, scale_factor: f32) {
+        let mut lock = self.0.lock();
+        let Some(mut callback) = lock.resize_callback.take() else {
+            return;
+        };
+        lock.scale_factor = scale_factor;
+        let size = lock.bounds.size;
+        drop(lock);
+        callback(size, scale_factor);
+        self.0.lock().resize_callback = Some(callback);
+    }
+
     pub(crate) fn simulate_active_status_change(&self, active: bool) {
         let mut lock = self.0.lock();
         let Some(mut callback) = lock.active_status_change_callback.take() else {
@@ -97,6 +115,27 @@ impl TestWindow {
         self.0.lock().active_status_change_callback = Some(callback);
     }
 
+    /// Runs the window's frame callback, as the platform would on a display refresh.
+    pub(crate) fn simulate_frame(&self) {
+        let mut lock = self.0.lock();
+        let Some(mut callback) = lock.request_frame_callback.take() else {
+            return;
+        };
+        drop(lock);
+        callback();
+        self.0.lock().request_frame_callback = Some(callback);
+    }
+
+    pub(crate) fn simulate_occlusion_change(&self, occluded: bool) {
+        let mut lock = self.0.lock();
+        let Some(mut callback) = lock.occlusion_change_callback.take() else {
+            return;
+        };
+        drop(lock);
+        callback(occluded);
+        self.0.lock().occlusion_change_callback = Some(callback);
+    }
+
     pub fn simulate_input(&mut self, event: PlatformInput) -> bool {
         let mut lock = self.0.lock();
         let Some(mut callback) = lock.input_callback.take() else {
@@ -127,7 +166,7 @@ impl PlatformWindow for TestWindow {
     }
 
     fn scale_factor(&self) -> f32 {
-        2.0
+        self.0.lock().scale_factor
     }
 
     fn appearance(&self) -> WindowAppearance {
@@ -221,7 +260,9 @@ impl PlatformWindow for TestWindow {
         self.0.lock().is_fullscreen
     }
 
-    fn on_request_frame(&self, _callback: Box<dyn FnMut()>) {}
+    fn on_request_frame(&self, callback: Box<dyn FnMut()>) {
+        self.0.lock().request_frame_callback = Some(callback)
+    }
 
     fn on_input(&self, callback: Box<dyn FnMut(crate::PlatformInput) -> DispatchEventResult>) {
         self.0.lock().input_callback = Some(callback)
@@ -251,6 +292,10 @@ impl PlatformWindow for TestWindow {
 
     fn on_appearance_changed(&self, _callback: Box<dyn FnMut()>) {}
 
+    fn on_occlusion_change(&self, callback: Box<dyn FnMut(bool)>) {
+        self.0.lock().occlusion_change_callback = Some(callback)
+    }
+
     fn draw(&self, _scene: &crate::Scene) {}
 
     fn sprite_atlas(&self) -> sync::Arc<dyn crate::PlatformAtlas> {
@@ -339,4 +384,25 @@ impl PlatformAtlas for TestAtlas {
 
         Ok(Some(state.tiles[key].clone()))
     }
+
+    fn get_or_insert_outline(
+        &self,
+        _key: &crate::AtlasKey,
+        _build: &mut dyn FnMut() -> anyhow::Result<Option<crate::GlyphOutline>>,
+    ) -> anyhow::Result<Option<crate::AtlasTile>> {
+        Ok(None)
+    }
+
+    fn clear(&self) {
+        self.0.lock().tiles.clear();
+    }
+
+    fn finish_frame(&self, _drawn_tiles: &mut dyn Iterator<Item = &AtlasTile>) {}
+
+    fn stats(&self) -> AtlasStats {
+        AtlasStats {
+            tile_count: self.0.lock().tiles.len(),
+            ..Default::default()
+        }
+    }
 }
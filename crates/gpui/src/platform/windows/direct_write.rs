@@ -606,6 +606,7 @@ impl DirectWriteState {
                 descent,
                 runs,
                 len: text.len(),
+                ligature_carets: Vec::new(),
             })
         }
     }
@@ -32,7 +32,7 @@ pub(crate) fn handle_msg(
         WM_ACTIVATE => handle_activate_msg(handle, wparam, state_ptr),
         WM_CREATE => handle_create_msg(handle, state_ptr),
         WM_MOVE => handle_move_msg(handle, lparam, state_ptr),
-        WM_SIZE => handle_size_msg(lparam, state_ptr),
+        WM_SIZE => handle_size_msg(wparam, lparam, state_ptr),
         WM_ENTERSIZEMOVE | WM_ENTERMENULOOP => handle_size_move_loop(handle),
         WM_EXITSIZEMOVE | WM_EXITMENULOOP => handle_size_move_loop_exit(handle),
         WM_TIMER => handle_timer_msg(handle, wparam, state_ptr),
@@ -134,7 +134,24 @@ fn handle_move_msg(
     Some(0)
 }
 
-fn handle_size_msg(lparam: LPARAM, state_ptr: Rc<WindowsWindowStatePtr>) -> Option<isize> {
+fn handle_size_msg(
+    wparam: WPARAM,
+    lparam: LPARAM,
+    state_ptr: Rc<WindowsWindowStatePtr>,
+) -> Option<isize> {
+    // A minimized window is the only kind of occlusion Windows reports.
+    let minimized = wparam.0 as u32 == SIZE_MINIMIZED;
+    let callback = state_ptr
+        .state
+        .borrow_mut()
+        .callbacks
+        .occlusion_change
+        .take();
+    if let Some(mut callback) = callback {
+        callback(minimized);
+        state_ptr.state.borrow_mut().callbacks.occlusion_change = Some(callback);
+    }
+
     let width = lparam.loword().max(1) as i32;
     let height = lparam.hiword().max(1) as i32;
     let mut lock = state_ptr.state.borrow_mut();
@@ -800,6 +817,26 @@ fn handle_dpi_changed_msg(
         .log_err();
     }
 
+    // `WM_SIZE` isn't sent when the window keeps the same size in device pixels, in which case
+    // its logical size still has to be updated for the new scale factor.
+    let mut client_rect = RECT::default();
+    unsafe { GetClientRect(handle, &mut client_rect) }.log_err()?;
+    let mut lock = state_ptr.state.borrow_mut();
+    let scale_factor = lock.scale_factor;
+    let new_size = size(
+        DevicePixels(client_rect.right - client_rect.left),
+        DevicePixels(client_rect.bottom - client_rect.top),
+    )
+    .to_pixels(scale_factor);
+    if lock.logical_size != new_size {
+        lock.logical_size = new_size;
+        if let Some(mut callback) = lock.callbacks.resize.take() {
+            drop(lock);
+            callback(new_size, scale_factor);
+            state_ptr.state.borrow_mut().callbacks.resize = Some(callback);
+        }
+    }
+
     Some(0)
 }
 
@@ -9,6 +9,7 @@ use std::{
 use ::util::ResultExt;
 use anyhow::{anyhow, Context, Result};
 use async_task::Runnable;
+use collections::HashMap;
 use futures::channel::oneshot::{self, Receiver};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -22,6 +23,7 @@ use windows::{
             Gdi::*,
             Imaging::{CLSID_WICImagingFactory, IWICImagingFactory},
         },
+        Media::Audio::{PlaySoundW, SND_ALIAS, SND_ASYNC, SND_NODEFAULT, SND_SYSTEM},
         Security::Credentials::*,
         System::{
             Com::*,
@@ -32,12 +34,13 @@ use windows::{
             LibraryLoader::*,
             Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
             Ole::*,
+            Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS},
             SystemInformation::*,
             Threading::*,
         },
         UI::{Input::KeyboardAndMouse::*, Shell::*, WindowsAndMessaging::*},
     },
-    UI::ViewManagement::UISettings,
+    UI::ViewManagement::{AccessibilitySettings, UISettings},
 };
 
 use crate::*;
@@ -63,6 +66,7 @@ pub(crate) struct WindowsPlatformState {
     callbacks: PlatformCallbacks,
     // NOTE: standard cursor handles don't need to close.
     pub(crate) current_cursor: HCURSOR,
+    custom_cursors: HashMap<CustomCursor, HCURSOR>,
 }
 
 #[derive(Default)]
@@ -83,6 +87,7 @@ impl WindowsPlatformState {
         Self {
             callbacks,
             current_cursor,
+            custom_cursors: HashMap::default(),
         }
     }
 }
@@ -474,18 +479,68 @@ impl Platform for WindowsPlatform {
     }
 
     fn set_cursor_style(&self, style: CursorStyle) {
-        let hcursor = load_cursor(style);
         let mut lock = self.state.borrow_mut();
+        let hcursor = match style {
+            CursorStyle::Custom(cursor) => lock
+                .custom_cursors
+                .get(&cursor)
+                .copied()
+                .unwrap_or_else(|| load_cursor(CursorStyle::Arrow)),
+            style => load_cursor(style),
+        };
         if lock.current_cursor.0 != hcursor.0 {
             self.post_message(CURSOR_STYLE_CHANGED, WPARAM(0), LPARAM(hcursor.0 as isize));
             lock.current_cursor = hcursor;
         }
     }
 
+    fn register_cursor(
+        &self,
+        cursor: CustomCursor,
+        image: Arc<RenderImage>,
+        hotspot: Point<Pixels>,
+    ) {
+        let Some(hcursor) = create_cursor(&image, hotspot).log_err() else {
+            return;
+        };
+        let mut lock = self.state.borrow_mut();
+        if let Some(previous_cursor) = lock.custom_cursors.insert(cursor, hcursor) {
+            // Switch windows showing the previous cursor over to the new one before destroying it.
+            if lock.current_cursor.0 == previous_cursor.0 {
+                self.post_message(CURSOR_STYLE_CHANGED, WPARAM(0), LPARAM(hcursor.0 as isize));
+                lock.current_cursor = hcursor;
+            }
+            unsafe { DestroyCursor(previous_cursor) }.log_err();
+        }
+    }
+
     fn should_auto_hide_scrollbars(&self) -> bool {
         should_auto_hide_scrollbars().log_err().unwrap_or(false)
     }
 
+    fn accessibility_preferences(&self) -> AccessibilityPreferences {
+        accessibility_preferences().log_err().unwrap_or_default()
+    }
+
+    fn power_state(&self) -> PowerState {
+        power_state().log_err().unwrap_or_default()
+    }
+
+    fn play_system_sound(&self, name: &str, _volume: f32) {
+        // System sounds are played at the volume set for them in the sound settings.
+        let name = HSTRING::from(name);
+        let played = unsafe {
+            PlaySoundW(
+                &name,
+                None,
+                SND_ALIAS | SND_ASYNC | SND_NODEFAULT | SND_SYSTEM,
+            )
+        };
+        if !played.as_bool() {
+            log::error!("failed to play system sound {name}");
+        }
+    }
+
     fn write_to_clipboard(&self, item: ClipboardItem) {
         write_to_clipboard(
             item,
@@ -724,6 +779,26 @@ fn should_auto_hide_scrollbars() -> Result<bool> {
     Ok(ui_settings.AutoHideScrollBars()?)
 }
 
+fn accessibility_preferences() -> Result<AccessibilityPreferences> {
+    let ui_settings = UISettings::new()?;
+    let accessibility_settings = AccessibilitySettings::new()?;
+    Ok(AccessibilityPreferences {
+        reduce_motion: !ui_settings.AnimationsEnabled()?,
+        increase_contrast: accessibility_settings.HighContrast()?,
+        reduce_transparency: !ui_settings.AdvancedEffectsEnabled()?,
+    })
+}
+
+fn power_state() -> Result<PowerState> {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }?;
+    Ok(PowerState {
+        // 0 means offline, 1 online and 255 unknown, in which case we assume external power.
+        on_battery: status.ACLineStatus == 0,
+        low_power_mode: status.SystemStatusFlag == 1,
+    })
+}
+
 fn register_clipboard_format(format: PCWSTR) -> Result<u32> {
     let ret = unsafe { RegisterClipboardFormatW(format) };
     if ret == 0 {
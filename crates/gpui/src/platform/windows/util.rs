@@ -3,7 +3,11 @@ use std::sync::OnceLock;
 use ::util::ResultExt;
 use windows::{
     Wdk::System::SystemServices::RtlGetVersion,
-    Win32::{Foundation::*, UI::WindowsAndMessaging::*},
+    Win32::{
+        Foundation::*,
+        Graphics::Gdi::{CreateBitmap, DeleteObject},
+        UI::WindowsAndMessaging::*,
+    },
     UI::{
         Color,
         ViewManagement::{UIColorType, UISettings},
@@ -113,6 +117,12 @@ pub(crate) fn load_cursor(style: CursorStyle) -> HCURSOR {
     static SIZEWE: OnceLock<SafeCursor> = OnceLock::new();
     static SIZENS: OnceLock<SafeCursor> = OnceLock::new();
     static NO: OnceLock<SafeCursor> = OnceLock::new();
+    static SIZENWSE: OnceLock<SafeCursor> = OnceLock::new();
+    static SIZENESW: OnceLock<SafeCursor> = OnceLock::new();
+    static SIZEALL: OnceLock<SafeCursor> = OnceLock::new();
+    static WAIT: OnceLock<SafeCursor> = OnceLock::new();
+    static APPSTARTING: OnceLock<SafeCursor> = OnceLock::new();
+    static HELP: OnceLock<SafeCursor> = OnceLock::new();
     let (lock, name) = match style {
         CursorStyle::IBeam | CursorStyle::IBeamCursorForVerticalLayout => (&IBEAM, IDC_IBEAM),
         CursorStyle::Crosshair => (&CROSS, IDC_CROSS),
@@ -125,6 +135,12 @@ pub(crate) fn load_cursor(style: CursorStyle) -> HCURSOR {
         | CursorStyle::ResizeDown
         | CursorStyle::ResizeUpDown
         | CursorStyle::ResizeRow => (&SIZENS, IDC_SIZENS),
+        CursorStyle::ResizeUpLeftDownRight => (&SIZENWSE, IDC_SIZENWSE),
+        CursorStyle::ResizeUpRightDownLeft => (&SIZENESW, IDC_SIZENESW),
+        CursorStyle::Move => (&SIZEALL, IDC_SIZEALL),
+        CursorStyle::Wait => (&WAIT, IDC_WAIT),
+        CursorStyle::Progress => (&APPSTARTING, IDC_APPSTARTING),
+        CursorStyle::Help => (&HELP, IDC_HELP),
         CursorStyle::OperationNotAllowed => (&NO, IDC_NO),
         _ => (&ARROW, IDC_ARROW),
     };
@@ -139,6 +155,40 @@ pub(crate) fn load_cursor(style: CursorStyle) -> HCURSOR {
     }))
 }
 
+/// Creates a cursor from the given image. Unlike the standard cursors, the returned handle
+/// must be destroyed with `DestroyCursor` once it is no longer used.
+pub(crate) fn create_cursor(
+    image: &RenderImage,
+    hotspot: Point<Pixels>,
+) -> anyhow::Result<HCURSOR> {
+    let size = image.size(0);
+    let bytes = image
+        .as_bytes(0)
+        .ok_or_else(|| anyhow::anyhow!("cursor image has no frames"))?;
+    unsafe {
+        // Render images are stored as BGRA, which is the layout of 32-bit bitmaps. The mask
+        // is ignored when the color bitmap has an alpha channel, but it is still required.
+        let color = CreateBitmap(
+            size.width.0,
+            size.height.0,
+            1,
+            32,
+            Some(bytes.as_ptr() as *const _),
+        );
+        let mask = CreateBitmap(size.width.0, size.height.0, 1, 1, None);
+        let icon = CreateIconIndirect(&ICONINFO {
+            fIcon: false.into(),
+            xHotspot: hotspot.x.0.max(0.) as u32,
+            yHotspot: hotspot.y.0.max(0.) as u32,
+            hbmMask: mask,
+            hbmColor: color,
+        });
+        let _ = DeleteObject(color);
+        let _ = DeleteObject(mask);
+        Ok(HCURSOR(icon?.0))
+    }
+}
+
 #[inline]
 pub(crate) fn logical_point(x: f32, y: f32, scale_factor: f32) -> Point<Pixels> {
     Point {
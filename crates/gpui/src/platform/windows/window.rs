@@ -71,6 +71,7 @@ impl WindowsWindowState {
     fn new(
         hwnd: HWND,
         transparent: bool,
+        color_space: ColorSpace,
         cs: &CREATESTRUCTW,
         current_cursor: HCURSOR,
         display: WindowsDisplay,
@@ -89,7 +90,7 @@ impl WindowsWindowState {
             size: logical_size,
         };
         let border_offset = WindowBorderOffset::default();
-        let renderer = windows_renderer::windows_renderer(hwnd, transparent)?;
+        let renderer = windows_renderer::windows_renderer(hwnd, transparent, color_space)?;
         let callbacks = Callbacks::default();
         let input_handler = None;
         let system_key_handled = false;
@@ -214,6 +215,7 @@ impl WindowsWindowStatePtr {
         let state = RefCell::new(WindowsWindowState::new(
             hwnd,
             context.transparent,
+            context.color_space,
             cs,
             context.current_cursor,
             context.display,
@@ -243,6 +245,7 @@ pub(crate) struct Callbacks {
     pub(crate) should_close: Option<Box<dyn FnMut() -> bool>>,
     pub(crate) close: Option<Box<dyn FnOnce()>>,
     pub(crate) appearance_changed: Option<Box<dyn FnMut()>>,
+    pub(crate) occlusion_change: Option<Box<dyn FnMut(bool)>>,
 }
 
 struct WindowCreateContext {
@@ -251,6 +254,7 @@ struct WindowCreateContext {
     hide_title_bar: bool,
     display: WindowsDisplay,
     transparent: bool,
+    color_space: ColorSpace,
     is_movable: bool,
     executor: ForegroundExecutor,
     current_cursor: HCURSOR,
@@ -308,6 +312,7 @@ impl WindowsWindow {
             hide_title_bar,
             display,
             transparent: true,
+            color_space: params.color_space,
             is_movable: params.is_movable,
             executor,
             current_cursor,
@@ -669,10 +674,18 @@ impl PlatformWindow for WindowsWindow {
         self.0.state.borrow_mut().callbacks.appearance_changed = Some(callback);
     }
 
+    fn on_occlusion_change(&self, callback: Box<dyn FnMut(bool)>) {
+        self.0.state.borrow_mut().callbacks.occlusion_change = Some(callback);
+    }
+
     fn draw(&self, scene: &Scene) {
         self.0.state.borrow_mut().renderer.draw(scene)
     }
 
+    fn render_to_image(&self, scene: &Scene) -> Result<image::RgbaImage> {
+        self.0.state.borrow_mut().renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         self.0.state.borrow().renderer.sprite_atlas().clone()
     }
@@ -1082,9 +1095,14 @@ mod windows_renderer {
     use crate::{
         get_window_long,
         platform::blade::{BladeRenderer, BladeSurfaceConfig},
+        ColorSpace,
     };
 
-    pub(super) fn windows_renderer(hwnd: HWND, transparent: bool) -> anyhow::Result<BladeRenderer> {
+    pub(super) fn windows_renderer(
+        hwnd: HWND,
+        transparent: bool,
+        color_space: ColorSpace,
+    ) -> anyhow::Result<BladeRenderer> {
         let raw = RawWindow { hwnd };
         let gpu: Arc<gpu::Context> = Arc::new(
             unsafe {
@@ -1102,6 +1120,7 @@ mod windows_renderer {
         let config = BladeSurfaceConfig {
             size: gpu::Extent::default(),
             transparent,
+            color_space,
         };
 
         Ok(BladeRenderer::new(gpu, config))
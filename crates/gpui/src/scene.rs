@@ -1,43 +1,109 @@
 // todo("windows"): remove
 #![cfg_attr(windows, allow(dead_code))]
 
+mod recording;
+
+pub use recording::*;
+
 use crate::{
-    bounds_tree::BoundsTree, point, AtlasTextureId, AtlasTile, Bounds, ContentMask, Corners, Edges,
-    Hsla, Pixels, Point, Radians, ScaledPixels, Size,
+    bounds_tree::BoundsTree, point, px, AtlasTextureId, AtlasTextureKind, AtlasTile, Background,
+    BackgroundTag, Bounds, ContentMask, Corners, CustomShaderId, Edges, Hsla, Pixels, Point,
+    Radians, ScaledPixels, Size, TileId, UnderlineKind, MAX_CUSTOM_SHADER_UNIFORMS,
 };
-use std::{fmt::Debug, iter::Peekable, ops::Range, slice};
+use collections::FxHashMap;
+use serde_derive::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::{fmt::Debug, iter::Peekable, mem, ops::Range, slice, sync::Arc};
 
 #[allow(non_camel_case_types, unused)]
 pub(crate) type PathVertex_ScaledPixels = PathVertex<ScaledPixels>;
 
 pub(crate) type DrawOrder = u32;
 
+/// The z-index of a primitive is stored in the highest bits of its [`DrawOrder`], so that it's
+/// drawn above the primitives of lower z-indices regardless of how they overlap.
+const Z_INDEX_SHIFT: u32 = 24;
+
+/// The most regions a frame's damage is made of. Past it, they're merged into one.
+const MAX_DAMAGE_REGIONS: usize = 16;
+
+/// The fewest consecutive solid quads that are batched as [`SolidQuad`]s. Shorter runs aren't
+/// worth switching pipelines for.
+const MIN_SOLID_QUAD_RUN: usize = 16;
+
 #[derive(Default)]
 pub(crate) struct Scene {
     pub(crate) paint_operations: Vec<PaintOperation>,
-    primitive_bounds: BoundsTree<ScaledPixels>,
+    /// Assigns the primitives of each z-index an order above the ones they overlap. Indexed by
+    /// z-index, up to the highest one painted at.
+    primitive_bounds: Vec<BoundsTree<ScaledPixels>>,
+    /// The z-indices being painted at, along with the number of layers pushed before each.
+    z_index_stack: Vec<(u8, usize)>,
+    /// The clipped bounds of the opaque quads, which hide the primitives drawn before them.
+    occluders: BoundsTree<ScaledPixels>,
     layer_stack: Vec<DrawOrder>,
     pub(crate) shadows: Vec<Shadow>,
+    pub(crate) backdrop_blurs: Vec<BackdropBlur>,
     pub(crate) quads: Vec<Quad>,
+    /// The ranges of `quads` that only differ in their bounds and color, which are drawn from
+    /// compact instances.
+    solid_quad_runs: Vec<Range<usize>>,
     pub(crate) paths: Vec<Path<ScaledPixels>>,
     pub(crate) underlines: Vec<Underline>,
     pub(crate) monochrome_sprites: Vec<MonochromeSprite>,
     pub(crate) polychrome_sprites: Vec<PolychromeSprite>,
     pub(crate) surfaces: Vec<PaintSurface>,
+    pub(crate) custom_primitives: Vec<CustomPrimitive>,
+    pub(crate) opacity_groups: Vec<OpacityGroup>,
+    /// The opacity groups that are being painted, along with the scenes their contents are
+    /// inserted into until they're popped.
+    opacity_group_stack: Vec<(f32, Option<GroupCache>, Scene)>,
+    opacity_group_count: usize,
+    /// The cleared scenes of the opacity groups painted in previous frames, which new groups
+    /// are painted into so that their primitive lists keep their capacity from frame to frame.
+    spare_group_scenes: Vec<Scene>,
+    /// The slots of the textures drawn to by the cached groups in this scene, including nested
+    /// ones.
+    pub(crate) cached_group_slots: Vec<usize>,
+    /// The union of the clipped bounds of every primitive inserted into this scene.
+    painted_bounds: Option<Bounds<ScaledPixels>>,
+    /// The regions that changed since the last presented frame, or `None` if all of it did.
+    damage: Option<Vec<Bounds<ScaledPixels>>>,
 }
 
 impl Scene {
     pub fn clear(&mut self) {
         self.paint_operations.clear();
-        self.primitive_bounds.clear();
+        for primitive_bounds in &mut self.primitive_bounds {
+            primitive_bounds.clear();
+        }
+        self.z_index_stack.clear();
+        self.occluders.clear();
         self.layer_stack.clear();
+        for group in self.opacity_groups.drain(..) {
+            // Renderers and recordings may still hold on to a group's scene.
+            if let Ok(mut scene) = Arc::try_unwrap(group.scene) {
+                scene.clear();
+                self.spare_group_scenes
+                    .append(&mut scene.spare_group_scenes);
+                self.spare_group_scenes.push(scene);
+            }
+        }
+        self.opacity_group_stack.clear();
+        self.opacity_group_count = 0;
+        self.cached_group_slots.clear();
+        self.painted_bounds = None;
+        self.damage = None;
         self.paths.clear();
         self.shadows.clear();
+        self.backdrop_blurs.clear();
         self.quads.clear();
+        self.solid_quad_runs.clear();
         self.underlines.clear();
         self.monochrome_sprites.clear();
         self.polychrome_sprites.clear();
         self.surfaces.clear();
+        self.custom_primitives.clear();
     }
 
     pub fn paths(&self) -> &[Path<ScaledPixels>] {
@@ -48,40 +114,365 @@ impl Scene {
         self.paint_operations.len()
     }
 
+    /// The number of opacity groups in this scene, including nested ones. Each group has an
+    /// `id` below this number, so renderers can use it to index the textures groups are drawn to.
+    pub fn opacity_group_count(&self) -> usize {
+        self.opacity_group_count
+    }
+
+    /// The regions of the frame, in device pixels, that changed since the previous frame was
+    /// presented, or `None` if the whole frame has to be presented again. It's only computed for
+    /// windows whose platform uses it.
+    pub fn damage(&self) -> Option<&[Bounds<ScaledPixels>]> {
+        self.damage.as_deref()
+    }
+
+    pub(crate) fn set_damage(&mut self, damage: Option<Vec<Bounds<ScaledPixels>>>) {
+        self.damage = damage;
+    }
+
+    /// Compute the regions this scene paints differently from the previous one. Primitives are
+    /// matched by what they paint rather than by their draw order, so overlapping primitives
+    /// that only swap places aren't noticed.
+    pub fn diff(&self, previous: &Scene) -> Vec<Bounds<ScaledPixels>> {
+        let current = self.painted_regions();
+        let previous = previous.painted_regions();
+
+        // Previous regions sorted by key, so that the candidates for each current region are a
+        // contiguous run found by binary search, rather than a map rebuilt on every diff.
+        let mut previous_keys = previous
+            .iter()
+            .enumerate()
+            .map(|(ix, region)| (region.key(), ix))
+            .collect::<Vec<_>>();
+        previous_keys.sort_unstable();
+        let mut matched_previous = vec![false; previous.len()];
+
+        let mut damage = Vec::new();
+        for region in &current {
+            let key = region.key();
+            let start = previous_keys.partition_point(|(candidate_key, _)| *candidate_key < key);
+            let matched = previous_keys[start..]
+                .iter()
+                .take_while(|(candidate_key, _)| *candidate_key == key)
+                .map(|(_, ix)| *ix)
+                .find(|ix| !matched_previous[*ix] && previous[*ix].paints_same_as(region));
+            match matched {
+                Some(ix) => matched_previous[ix] = true,
+                None => damage.push(region.bounds),
+            }
+        }
+        damage.extend(
+            previous
+                .iter()
+                .zip(&matched_previous)
+                .filter(|(_, matched)| !**matched)
+                .map(|(region, _)| region.bounds),
+        );
+
+        // Backdrop blurs spread whatever changed beneath them across their bounds.
+        for region in &current {
+            if matches!(region.primitive, Primitive::BackdropBlur(_))
+                && damage
+                    .iter()
+                    .any(|bounds| bounds.intersects(&region.bounds))
+            {
+                damage.push(region.bounds);
+            }
+        }
+
+        coalesce_damage(damage)
+    }
+
+    fn painted_regions(&self) -> Vec<PaintedRegion<'_>> {
+        let mut regions = Vec::new();
+        let mut opacity = 1.;
+        let mut opacity_stack = Vec::new();
+        for operation in &self.paint_operations {
+            match operation {
+                PaintOperation::Primitive(primitive) => {
+                    let mut bounds = *primitive.bounds();
+                    if let Primitive::Shadow(shadow) = primitive {
+                        // Shadows are blurred past their bounds, unless they're inset.
+                        if !shadow.inset {
+                            bounds.dilate(ScaledPixels(shadow.blur_radius.0 * 3.));
+                        }
+                    }
+                    let bounds = bounds.intersect(&primitive.content_mask().bounds);
+                    regions.push(PaintedRegion {
+                        primitive,
+                        opacity,
+                        bounds: primitive.transformation().transform_bounds(bounds),
+                    });
+                }
+                PaintOperation::StartOpacityGroup(group_opacity) => {
+                    opacity_stack.push(opacity);
+                    opacity *= group_opacity;
+                }
+                PaintOperation::StartCachedGroup { .. } => opacity_stack.push(opacity),
+                PaintOperation::EndOpacityGroup => {
+                    opacity = opacity_stack.pop().unwrap_or(1.);
+                }
+                PaintOperation::StartLayer(_)
+                | PaintOperation::EndLayer
+                | PaintOperation::StartZIndex(_)
+                | PaintOperation::EndZIndex => {}
+            }
+        }
+        regions
+    }
+
     pub fn push_layer(&mut self, bounds: Bounds<ScaledPixels>) {
-        let order = self.primitive_bounds.insert(bounds);
-        self.layer_stack.push(order);
+        let scene = self.current_scene();
+        let order = scene.draw_order(bounds);
+        scene.layer_stack.push(order);
         self.paint_operations
             .push(PaintOperation::StartLayer(bounds));
     }
 
     pub fn pop_layer(&mut self) {
-        self.layer_stack.pop();
+        self.current_scene().layer_stack.pop();
         self.paint_operations.push(PaintOperation::EndLayer);
     }
 
+    /// Paint the primitives inserted until the z-index is popped above all those with a lower
+    /// z-index, regardless of the order they're inserted in.
+    pub fn push_z_index(&mut self, z_index: u8) {
+        let scene = self.current_scene();
+        scene.z_index_stack.push((z_index, scene.layer_stack.len()));
+        self.paint_operations
+            .push(PaintOperation::StartZIndex(z_index));
+    }
+
+    pub fn pop_z_index(&mut self) {
+        self.current_scene().z_index_stack.pop();
+        self.paint_operations.push(PaintOperation::EndZIndex);
+    }
+
+    /// Start collecting primitives into a group that is composited at the given opacity once
+    /// it's popped.
+    pub fn push_opacity_group(&mut self, opacity: f32) {
+        self.opacity_group_stack
+            .push((opacity, None, self.spare_group_scene()));
+        self.paint_operations
+            .push(PaintOperation::StartOpacityGroup(opacity));
+    }
+
+    /// Start collecting primitives into a group that is drawn to a texture kept from frame to
+    /// frame, in the given slot, and is composited like an opaque opacity group. Popping it
+    /// with [`Scene::pop_opacity_group`] ends it.
+    pub fn push_cached_group(&mut self, key: u64, slot: usize) {
+        let cache = GroupCache {
+            key,
+            slot,
+            unchanged: false,
+        };
+        self.opacity_group_stack
+            .push((1., Some(cache), self.spare_group_scene()));
+        self.paint_operations
+            .push(PaintOperation::StartCachedGroup { key, slot });
+    }
+
+    pub fn pop_opacity_group(&mut self) {
+        self.paint_operations.push(PaintOperation::EndOpacityGroup);
+        let Some((opacity, mut cache, mut scene)) = self.opacity_group_stack.pop() else {
+            return;
+        };
+        let Some(bounds) = scene.painted_bounds else {
+            scene.clear();
+            self.spare_group_scenes.push(scene);
+            return;
+        };
+
+        if let Some(slot) = cache.map(|cache| cache.slot) {
+            // A texture can only hold one group, so a group painted twice is only cached once.
+            if self.cached_group_slots.contains(&slot) {
+                cache = None;
+            } else {
+                self.cached_group_slots.push(slot);
+            }
+        }
+
+        scene.finish();
+        // Groups are composited texel for texel, so they have to cover whole device pixels.
+        let bounds = Bounds::from_corners(
+            bounds.origin.map(|coordinate| coordinate.floor()),
+            bounds.lower_right().map(|coordinate| coordinate.ceil()),
+        );
+        let id = self.opacity_group_count;
+        self.opacity_group_count += 1;
+        let mut group = Primitive::OpacityGroup(OpacityGroup {
+            order: 0,
+            id,
+            opacity,
+            cache,
+            bounds,
+            content_mask: ContentMask { bounds },
+            scene: Arc::new(scene),
+        });
+        self.current_scene().push_primitive(&mut group);
+    }
+
+    /// Marks the cached groups that paint the same as they did in the previous scene, so that
+    /// renderers can composite the textures they were drawn to then, rather than drawing them
+    /// again.
+    pub fn reuse_cached_groups(&mut self, previous: &Scene) {
+        if self.cached_group_slots.is_empty() {
+            return;
+        }
+        let mut previous_groups = FxHashMap::default();
+        previous.collect_cached_groups(&mut previous_groups);
+        self.mark_unchanged_groups(&previous_groups);
+    }
+
+    fn collect_cached_groups<'a>(&'a self, groups: &mut FxHashMap<u64, &'a OpacityGroup>) {
+        for group in &self.opacity_groups {
+            if let Some(cache) = group.cache {
+                groups.insert(cache.key, group);
+            }
+            group.scene.collect_cached_groups(groups);
+        }
+    }
+
+    fn mark_unchanged_groups(&mut self, previous_groups: &FxHashMap<u64, &OpacityGroup>) {
+        for group in &mut self.opacity_groups {
+            if let Some(scene) = Arc::get_mut(&mut group.scene) {
+                scene.mark_unchanged_groups(previous_groups);
+            }
+            let Some(cache) = group.cache.as_mut() else {
+                continue;
+            };
+            cache.unchanged = previous_groups.get(&cache.key).map_or(false, |previous| {
+                previous.cache.map(|cache| cache.slot) == Some(cache.slot)
+                    && previous.bounds == group.bounds
+                    && previous.scene.paints_same_as(&group.scene)
+            });
+        }
+    }
+
+    /// Whether this scene draws exactly the same primitives as the other one. Surfaces and
+    /// custom primitives may draw something different every time, so scenes with any never do.
+    fn paints_same_as(&self, other: &Scene) -> bool {
+        self.surfaces.is_empty()
+            && other.surfaces.is_empty()
+            && self.custom_primitives.is_empty()
+            && other.custom_primitives.is_empty()
+            && self.shadows == other.shadows
+            && self.backdrop_blurs == other.backdrop_blurs
+            && self.quads == other.quads
+            && self.underlines == other.underlines
+            && self.monochrome_sprites == other.monochrome_sprites
+            && self.polychrome_sprites == other.polychrome_sprites
+            && self.paths.len() == other.paths.len()
+            && self
+                .paths
+                .iter()
+                .zip(&other.paths)
+                .all(|(a, b)| a.order == b.order && a.paints_same_as(b))
+            && self.opacity_groups.len() == other.opacity_groups.len()
+            && self
+                .opacity_groups
+                .iter()
+                .zip(&other.opacity_groups)
+                .all(|(a, b)| {
+                    a.order == b.order
+                        && a.opacity == b.opacity
+                        && a.bounds == b.bounds
+                        && a.scene.paints_same_as(&b.scene)
+                })
+    }
+
     pub fn insert_primitive(&mut self, primitive: impl Into<Primitive>) {
         let mut primitive = primitive.into();
+        if self.current_scene().push_primitive(&mut primitive) {
+            self.paint_operations
+                .push(PaintOperation::Primitive(primitive));
+        }
+    }
+
+    /// Inserts the stack of shadows cast by one element, in the order they're given. They share
+    /// a draw order, so that renderers draw them in a single batch rather than interleaving
+    /// them with the primitives they overlap.
+    pub fn insert_shadows(&mut self, shadows: impl IntoIterator<Item = Shadow>) {
+        let shadows = shadows.into_iter().collect::<SmallVec<[Shadow; 2]>>();
+        if shadows.len() < 2 {
+            if let Some(shadow) = shadows.into_iter().next() {
+                self.insert_primitive(shadow);
+            }
+            return;
+        }
+
+        let group_bounds = shadows
+            .iter()
+            .filter_map(|shadow| {
+                let clipped_bounds = shadow.bounds.intersect(&shadow.content_mask.bounds);
+                (!clipped_bounds.is_empty())
+                    .then(|| shadow.transformation.transform_bounds(clipped_bounds))
+            })
+            .reduce(|group_bounds, bounds| group_bounds.union(&bounds));
+        let Some(group_bounds) = group_bounds else {
+            return;
+        };
+
+        self.push_layer(group_bounds);
+        for shadow in shadows {
+            self.insert_primitive(shadow);
+        }
+        self.pop_layer();
+    }
+
+    /// The scene primitives are inserted into, which is the one of the innermost opacity group
+    /// being painted, if any.
+    fn current_scene(&mut self) -> &mut Scene {
+        if self.opacity_group_stack.is_empty() {
+            self
+        } else {
+            &mut self.opacity_group_stack.last_mut().unwrap().2
+        }
+    }
+
+    fn spare_group_scene(&mut self) -> Scene {
+        self.spare_group_scenes.pop().unwrap_or_default()
+    }
+
+    /// Adds the primitive to the lists drawn by renderers, returning whether it's visible.
+    fn push_primitive(&mut self, primitive: &mut Primitive) -> bool {
         let clipped_bounds = primitive
             .bounds()
             .intersect(&primitive.content_mask().bounds);
+        let clipped_bounds = primitive.transformation().transform_bounds(clipped_bounds);
 
         if clipped_bounds.is_empty() {
-            return;
+            return false;
         }
 
-        let order = self
-            .layer_stack
+        // Layers pushed before the current z-index don't apply to what's painted at it.
+        let layers_start = self
+            .z_index_stack
+            .last()
+            .map_or(0, |(_, layer_count)| *layer_count);
+        let order = self.layer_stack[layers_start..]
             .last()
             .copied()
-            .unwrap_or_else(|| self.primitive_bounds.insert(clipped_bounds));
-        match &mut primitive {
+            .unwrap_or_else(|| self.draw_order(clipped_bounds));
+        self.painted_bounds = Some(match self.painted_bounds {
+            Some(painted_bounds) => painted_bounds.union(&clipped_bounds),
+            None => clipped_bounds,
+        });
+        match primitive {
             Primitive::Shadow(shadow) => {
                 shadow.order = order;
                 self.shadows.push(shadow.clone());
             }
+            Primitive::BackdropBlur(blur) => {
+                blur.order = order;
+                self.backdrop_blurs.push(blur.clone());
+            }
             Primitive::Quad(quad) => {
                 quad.order = order;
+                if quad.is_opaque() {
+                    self.occluders.insert_with_order(clipped_bounds, order);
+                }
                 self.quads.push(quad.clone());
             }
             Primitive::Path(path) => {
@@ -105,9 +496,34 @@ impl Scene {
                 surface.order = order;
                 self.surfaces.push(surface.clone());
             }
+            Primitive::Custom(primitive) => {
+                primitive.order = order;
+                self.custom_primitives.push(primitive.clone());
+            }
+            Primitive::OpacityGroup(group) => {
+                group.order = order;
+                self.opacity_groups.push(group.clone());
+            }
         }
-        self.paint_operations
-            .push(PaintOperation::Primitive(primitive));
+        true
+    }
+
+    /// Assigns an order to primitives with the given bounds at the current z-index.
+    fn draw_order(&mut self, bounds: Bounds<ScaledPixels>) -> DrawOrder {
+        let z_index = self
+            .z_index_stack
+            .last()
+            .map_or(0, |(z_index, _)| *z_index as usize);
+        if self.primitive_bounds.len() <= z_index {
+            self.primitive_bounds
+                .resize_with(z_index + 1, BoundsTree::default);
+        }
+        let order = self.primitive_bounds[z_index].insert(bounds);
+        debug_assert!(
+            order < 1 << Z_INDEX_SHIFT,
+            "too many overlapping primitives"
+        );
+        (z_index as DrawOrder) << Z_INDEX_SHIFT | order
     }
 
     pub fn replay(&mut self, range: Range<usize>, prev_scene: &Scene) {
@@ -116,18 +532,148 @@ impl Scene {
                 PaintOperation::Primitive(primitive) => self.insert_primitive(primitive.clone()),
                 PaintOperation::StartLayer(bounds) => self.push_layer(*bounds),
                 PaintOperation::EndLayer => self.pop_layer(),
+                PaintOperation::StartZIndex(z_index) => self.push_z_index(*z_index),
+                PaintOperation::EndZIndex => self.pop_z_index(),
+                PaintOperation::StartOpacityGroup(opacity) => self.push_opacity_group(*opacity),
+                PaintOperation::StartCachedGroup { key, slot } => {
+                    self.push_cached_group(*key, *slot)
+                }
+                PaintOperation::EndOpacityGroup => self.pop_opacity_group(),
             }
         }
     }
 
     pub fn finish(&mut self) {
+        self.cull_occluded_primitives();
         self.shadows.sort();
+        self.backdrop_blurs.sort();
         self.quads.sort();
         self.paths.sort();
         self.underlines.sort();
         self.monochrome_sprites.sort();
         self.polychrome_sprites.sort();
         self.surfaces.sort();
+        self.custom_primitives.sort();
+        self.opacity_groups.sort();
+
+        self.solid_quad_runs.clear();
+        let mut run_start = None;
+        for (ix, quad) in self.quads.iter().enumerate() {
+            match (quad.is_solid(), run_start) {
+                (true, None) => run_start = Some(ix),
+                (false, Some(start)) => {
+                    if ix - start >= MIN_SOLID_QUAD_RUN {
+                        self.solid_quad_runs.push(start..ix);
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = run_start {
+            if self.quads.len() - start >= MIN_SOLID_QUAD_RUN {
+                self.solid_quad_runs.push(start..self.quads.len());
+            }
+        }
+    }
+
+    /// Drop the primitives that are entirely covered by an opaque quad drawn after them, so that
+    /// e.g. the editor beneath a full-screen panel isn't uploaded and drawn every frame.
+    fn cull_occluded_primitives(&mut self) {
+        let occluders = &self.occluders;
+        let is_visible = |order: DrawOrder,
+                          bounds: Bounds<ScaledPixels>,
+                          content_mask: &ContentMask<ScaledPixels>,
+                          transformation: TransformationMatrix| {
+            let bounds = transformation.transform_bounds(bounds.intersect(&content_mask.bounds));
+            occluders
+                .max_order_containing(&bounds)
+                .map_or(true, |occluder_order| occluder_order <= order)
+        };
+
+        self.shadows.retain(|shadow| {
+            // Shadows are blurred past their bounds.
+            let mut bounds = shadow.bounds;
+            bounds.dilate(ScaledPixels(shadow.blur_radius.0 * 3.));
+            is_visible(
+                shadow.order,
+                bounds,
+                &shadow.content_mask,
+                shadow.transformation,
+            )
+        });
+        self.backdrop_blurs.retain(|blur| {
+            is_visible(
+                blur.order,
+                blur.bounds,
+                &blur.content_mask,
+                TransformationMatrix::unit(),
+            )
+        });
+        self.quads.retain(|quad| {
+            is_visible(
+                quad.order,
+                quad.bounds,
+                &quad.content_mask,
+                quad.transformation,
+            )
+        });
+        self.paths.retain(|path| {
+            is_visible(
+                path.order,
+                path.bounds,
+                &path.content_mask,
+                TransformationMatrix::unit(),
+            )
+        });
+        self.underlines.retain(|underline| {
+            is_visible(
+                underline.order,
+                underline.bounds,
+                &underline.content_mask,
+                underline.transformation,
+            )
+        });
+        self.monochrome_sprites.retain(|sprite| {
+            is_visible(
+                sprite.order,
+                sprite.bounds,
+                &sprite.content_mask,
+                sprite.transformation,
+            )
+        });
+        self.polychrome_sprites.retain(|sprite| {
+            is_visible(
+                sprite.order,
+                sprite.bounds,
+                &sprite.content_mask,
+                sprite.transformation,
+            )
+        });
+        self.surfaces.retain(|surface| {
+            is_visible(
+                surface.order,
+                surface.bounds,
+                &surface.content_mask,
+                TransformationMatrix::unit(),
+            )
+        });
+        self.custom_primitives.retain(|primitive| {
+            is_visible(
+                primitive.order,
+                primitive.bounds,
+                &primitive.content_mask,
+                TransformationMatrix::unit(),
+            )
+        });
+        self.opacity_groups.retain(|group| {
+            is_visible(
+                group.order,
+                group.bounds,
+                &group.content_mask,
+                TransformationMatrix::unit(),
+            )
+        });
     }
 
     pub(crate) fn batches(&self) -> impl Iterator<Item = PrimitiveBatch> {
@@ -135,9 +681,13 @@ impl Scene {
             shadows: &self.shadows,
             shadows_start: 0,
             shadows_iter: self.shadows.iter().peekable(),
+            backdrop_blurs: &self.backdrop_blurs,
+            backdrop_blurs_start: 0,
+            backdrop_blurs_iter: self.backdrop_blurs.iter().peekable(),
             quads: &self.quads,
             quads_start: 0,
             quads_iter: self.quads.iter().peekable(),
+            solid_quad_runs_iter: self.solid_quad_runs.iter().peekable(),
             paths: &self.paths,
             paths_start: 0,
             paths_iter: self.paths.iter().peekable(),
@@ -153,13 +703,328 @@ impl Scene {
             surfaces: &self.surfaces,
             surfaces_start: 0,
             surfaces_iter: self.surfaces.iter().peekable(),
+            custom_primitives: &self.custom_primitives,
+            custom_primitives_start: 0,
+            custom_primitives_iter: self.custom_primitives.iter().peekable(),
+            opacity_groups: &self.opacity_groups,
+            opacity_groups_start: 0,
+            opacity_groups_iter: self.opacity_groups.iter().peekable(),
         }
     }
+
+    /// The atlas tiles of the sprites in this scene, including the contents of its opacity groups.
+    pub(crate) fn atlas_tiles(&self) -> Box<dyn Iterator<Item = &AtlasTile> + '_> {
+        Box::new(
+            self.monochrome_sprites
+                .iter()
+                .map(|sprite| &sprite.tile)
+                .chain(self.polychrome_sprites.iter().map(|sprite| &sprite.tile))
+                .chain(
+                    self.opacity_groups
+                        .iter()
+                        .flat_map(|group| group.scene.atlas_tiles()),
+                ),
+        )
+    }
+
+    /// Count what renderers draw for this scene, including the contents of its opacity groups.
+    /// The scene must have been finished.
+    pub fn stats(&self) -> SceneStats {
+        let mut stats = SceneStats {
+            bounds_tree_depth: self
+                .primitive_bounds
+                .iter()
+                .map(BoundsTree::depth)
+                .max()
+                .unwrap_or(0),
+            ..Default::default()
+        };
+        let mut previous_batch = None;
+        for batch in self.batches() {
+            let counts = &mut stats.primitive_counts;
+            let (count, bytes) = match batch {
+                PrimitiveBatch::Shadows(shadows) => {
+                    counts.shadows += shadows.len();
+                    (shadows.len(), mem::size_of_val(shadows))
+                }
+                PrimitiveBatch::BackdropBlurs(blurs) => {
+                    counts.backdrop_blurs += blurs.len();
+                    (blurs.len(), mem::size_of_val(blurs))
+                }
+                PrimitiveBatch::Quads(quads) => {
+                    counts.quads += quads.len();
+                    (quads.len(), mem::size_of_val(quads))
+                }
+                PrimitiveBatch::SolidQuads(quads) => {
+                    counts.solid_quads += quads.len();
+                    (quads.len(), quads.len() * mem::size_of::<SolidQuad>())
+                }
+                PrimitiveBatch::Paths(paths) => {
+                    counts.paths += paths.len();
+                    let vertex_count = paths.iter().map(|path| path.vertices.len()).sum::<usize>();
+                    (
+                        paths.len(),
+                        vertex_count * mem::size_of::<PathVertex<ScaledPixels>>(),
+                    )
+                }
+                PrimitiveBatch::Underlines(underlines) => {
+                    counts.underlines += underlines.len();
+                    (underlines.len(), mem::size_of_val(underlines))
+                }
+                PrimitiveBatch::MonochromeSprites { sprites, .. } => {
+                    counts.monochrome_sprites += sprites.len();
+                    (sprites.len(), mem::size_of_val(sprites))
+                }
+                PrimitiveBatch::PolychromeSprites { sprites, .. } => {
+                    counts.polychrome_sprites += sprites.len();
+                    (sprites.len(), mem::size_of_val(sprites))
+                }
+                PrimitiveBatch::Surfaces(surfaces) => {
+                    counts.surfaces += surfaces.len();
+                    (surfaces.len(), mem::size_of_val(surfaces))
+                }
+                PrimitiveBatch::Custom { primitives, .. } => {
+                    counts.custom += primitives.len();
+                    (primitives.len(), mem::size_of_val(primitives))
+                }
+                PrimitiveBatch::OpacityGroups(groups) => {
+                    counts.opacity_groups += groups.len();
+                    for group in groups {
+                        stats.add_nested(group.scene.stats());
+                    }
+                    (
+                        groups.len(),
+                        groups.len() * mem::size_of::<PolychromeSprite>(),
+                    )
+                }
+            };
+            if count == 0 {
+                continue;
+            }
+
+            stats.batch_count += 1;
+            stats.instance_bytes += bytes;
+            if let Some(previous_batch) = previous_batch {
+                match (previous_batch, &batch) {
+                    (
+                        PrimitiveBatch::MonochromeSprites { .. },
+                        PrimitiveBatch::MonochromeSprites { .. },
+                    )
+                    | (
+                        PrimitiveBatch::PolychromeSprites { .. },
+                        PrimitiveBatch::PolychromeSprites { .. },
+                    ) => stats.batch_breaks.texture_change += 1,
+                    (PrimitiveBatch::Custom { .. }, PrimitiveBatch::Custom { .. }) => {
+                        stats.batch_breaks.shader_change += 1
+                    }
+                    (PrimitiveBatch::Quads(_), PrimitiveBatch::SolidQuads(_))
+                    | (PrimitiveBatch::SolidQuads(_), PrimitiveBatch::Quads(_)) => {
+                        stats.batch_breaks.solid_quad_run += 1
+                    }
+                    _ => stats.batch_breaks.kind_change += 1,
+                }
+            }
+            previous_batch = Some(batch);
+        }
+        stats
+    }
+
+    /// Describe everything this scene paints, in the order renderers draw it. The scene must
+    /// have been finished.
+    pub fn serialize(&self) -> SerializedScene {
+        let mut primitives = Vec::new();
+        for batch in self.batches() {
+            match batch {
+                PrimitiveBatch::Shadows(shadows) => {
+                    primitives.extend(shadows.iter().map(|shadow| SerializedPrimitive {
+                        order: shadow.order,
+                        bounds: shadow.bounds.into(),
+                        content_mask: shadow.content_mask.bounds.into(),
+                        transformation: serialize_transformation(&shadow.transformation),
+                        kind: SerializedPrimitiveKind::Shadow {
+                            corner_radii: serialize_corners(&shadow.corner_radii),
+                            blur_radius: shadow.blur_radius.0,
+                            color: serialize_color(shadow.color),
+                            inset: shadow.inset.then(|| {
+                                [
+                                    shadow.inset_offset.x.0,
+                                    shadow.inset_offset.y.0,
+                                    shadow.inset_spread_radius.0,
+                                ]
+                            }),
+                        },
+                    }))
+                }
+                PrimitiveBatch::BackdropBlurs(blurs) => {
+                    primitives.extend(blurs.iter().map(|blur| SerializedPrimitive {
+                        order: blur.order,
+                        bounds: blur.bounds.into(),
+                        content_mask: blur.content_mask.bounds.into(),
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::BackdropBlur {
+                            corner_radii: serialize_corners(&blur.corner_radii),
+                            blur_radius: blur.blur_radius.0,
+                        },
+                    }))
+                }
+                PrimitiveBatch::Quads(quads) | PrimitiveBatch::SolidQuads(quads) => {
+                    primitives.extend(quads.iter().map(|quad| SerializedPrimitive {
+                        order: quad.order,
+                        bounds: quad.bounds.into(),
+                        content_mask: quad.content_mask.bounds.into(),
+                        transformation: serialize_transformation(&quad.transformation),
+                        kind: SerializedPrimitiveKind::Quad {
+                            background: (&quad.background).into(),
+                            border_color: serialize_color(quad.border_color),
+                            border_widths: [
+                                quad.border_widths.top.0,
+                                quad.border_widths.right.0,
+                                quad.border_widths.bottom.0,
+                                quad.border_widths.left.0,
+                            ],
+                            corner_radii: serialize_corners(&quad.corner_radii),
+                            blend_mode: quad.blend_mode,
+                        },
+                    }));
+                }
+                PrimitiveBatch::Paths(paths) => {
+                    primitives.extend(paths.iter().map(|path| SerializedPrimitive {
+                        order: path.order,
+                        bounds: path.bounds.into(),
+                        content_mask: path.content_mask.bounds.into(),
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::Path {
+                            background: (&path.color).into(),
+                            vertex_count: path.vertices.len(),
+                        },
+                    }))
+                }
+                PrimitiveBatch::Underlines(underlines) => {
+                    primitives.extend(underlines.iter().map(|underline| SerializedPrimitive {
+                        order: underline.order,
+                        bounds: underline.bounds.into(),
+                        content_mask: underline.content_mask.bounds.into(),
+                        transformation: serialize_transformation(&underline.transformation),
+                        kind: SerializedPrimitiveKind::Underline {
+                            color: serialize_color(underline.color),
+                            thickness: underline.thickness.0,
+                            kind: underline.kind,
+                            wave: [underline.wave_amplitude.0, underline.wavelength.0],
+                            dashes: [
+                                underline.dash.0,
+                                underline.dash_gap.0,
+                                underline.dash_offset.0,
+                            ],
+                        },
+                    }))
+                }
+                PrimitiveBatch::MonochromeSprites { sprites, .. } => {
+                    primitives.extend(sprites.iter().map(|sprite| SerializedPrimitive {
+                        order: sprite.order,
+                        bounds: sprite.bounds.into(),
+                        content_mask: sprite.content_mask.bounds.into(),
+                        transformation: serialize_transformation(&sprite.transformation),
+                        kind: SerializedPrimitiveKind::MonochromeSprite {
+                            color: serialize_color(sprite.color),
+                            blend_mode: sprite.blend_mode,
+                        },
+                    }))
+                }
+                PrimitiveBatch::PolychromeSprites { sprites, .. } => {
+                    primitives.extend(sprites.iter().map(|sprite| SerializedPrimitive {
+                        order: sprite.order,
+                        bounds: sprite.bounds.into(),
+                        content_mask: sprite.content_mask.bounds.into(),
+                        transformation: serialize_transformation(&sprite.transformation),
+                        kind: SerializedPrimitiveKind::PolychromeSprite {
+                            corner_radii: serialize_corners(&sprite.corner_radii),
+                            grayscale: sprite.grayscale,
+                            hue_rotate: sprite.hue_rotate,
+                            tint: serialize_color(sprite.tint),
+                            opacity: sprite.opacity,
+                            blend_mode: sprite.blend_mode,
+                        },
+                    }))
+                }
+                PrimitiveBatch::Surfaces(surfaces) => {
+                    primitives.extend(surfaces.iter().map(|surface| SerializedPrimitive {
+                        order: surface.order,
+                        bounds: surface.bounds.into(),
+                        content_mask: surface.content_mask.bounds.into(),
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::Surface,
+                    }))
+                }
+                PrimitiveBatch::Custom {
+                    shader_id,
+                    primitives: custom_primitives,
+                } => primitives.extend(custom_primitives.iter().map(|primitive| {
+                    SerializedPrimitive {
+                        order: primitive.order,
+                        bounds: primitive.bounds.into(),
+                        content_mask: primitive.content_mask.bounds.into(),
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::Custom {
+                            shader_id: shader_id.0,
+                            uniforms: primitive.uniforms,
+                        },
+                    }
+                })),
+                PrimitiveBatch::OpacityGroups(groups) => {
+                    primitives.extend(groups.iter().map(|group| SerializedPrimitive {
+                        order: group.order,
+                        bounds: group.bounds.into(),
+                        content_mask: group.content_mask.bounds.into(),
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::OpacityGroup {
+                            opacity: group.opacity,
+                            primitives: group.scene.serialize().primitives,
+                        },
+                    }))
+                }
+            }
+        }
+        SerializedScene { primitives }
+    }
+
+    /// Describe the primitives painted at the given point, topmost first. The scene must have
+    /// been finished. A primitive is hit anywhere within the bounding box of its clipped and
+    /// transformed bounds, and the contents of an opacity group are described within it.
+    pub fn primitives_at(&self, point: Point<ScaledPixels>) -> Vec<SerializedPrimitive> {
+        let mut orders = self
+            .primitive_bounds
+            .iter()
+            .enumerate()
+            .flat_map(|(z_index, primitive_bounds)| {
+                primitive_bounds
+                    .orders_containing_point(&point)
+                    .into_iter()
+                    .map(move |order| (z_index as DrawOrder) << Z_INDEX_SHIFT | order)
+            })
+            .collect::<Vec<_>>();
+        if orders.is_empty() {
+            return Vec::new();
+        }
+        orders.sort_unstable();
+
+        // Primitives painted in a layer share its order, so they're hit-tested on their own.
+        let mut primitives = self
+            .serialize()
+            .primitives
+            .into_iter()
+            .filter(|primitive| {
+                orders.binary_search(&primitive.order).is_ok() && primitive.contains_point(point)
+            })
+            .collect::<Vec<_>>();
+        primitives.reverse();
+        primitives
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 pub(crate) enum PrimitiveKind {
     Shadow,
+    BackdropBlur,
     #[default]
     Quad,
     Path,
@@ -167,58 +1032,197 @@ pub(crate) enum PrimitiveKind {
     MonochromeSprite,
     PolychromeSprite,
     Surface,
+    Custom,
+    OpacityGroup,
 }
 
 pub(crate) enum PaintOperation {
     Primitive(Primitive),
     StartLayer(Bounds<ScaledPixels>),
     EndLayer,
+    StartZIndex(u8),
+    EndZIndex,
+    StartOpacityGroup(f32),
+    StartCachedGroup { key: u64, slot: usize },
+    EndOpacityGroup,
 }
 
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub(crate) enum Primitive {
     Shadow(Shadow),
+    BackdropBlur(BackdropBlur),
     Quad(Quad),
     Path(Path<ScaledPixels>),
     Underline(Underline),
     MonochromeSprite(MonochromeSprite),
     PolychromeSprite(PolychromeSprite),
     Surface(PaintSurface),
+    Custom(CustomPrimitive),
+    OpacityGroup(OpacityGroup),
 }
 
 impl Primitive {
     pub fn bounds(&self) -> &Bounds<ScaledPixels> {
         match self {
             Primitive::Shadow(shadow) => &shadow.bounds,
+            Primitive::BackdropBlur(blur) => &blur.bounds,
             Primitive::Quad(quad) => &quad.bounds,
             Primitive::Path(path) => &path.bounds,
             Primitive::Underline(underline) => &underline.bounds,
             Primitive::MonochromeSprite(sprite) => &sprite.bounds,
             Primitive::PolychromeSprite(sprite) => &sprite.bounds,
             Primitive::Surface(surface) => &surface.bounds,
+            Primitive::Custom(primitive) => &primitive.bounds,
+            Primitive::OpacityGroup(group) => &group.bounds,
         }
     }
 
     pub fn content_mask(&self) -> &ContentMask<ScaledPixels> {
         match self {
             Primitive::Shadow(shadow) => &shadow.content_mask,
+            Primitive::BackdropBlur(blur) => &blur.content_mask,
             Primitive::Quad(quad) => &quad.content_mask,
             Primitive::Path(path) => &path.content_mask,
             Primitive::Underline(underline) => &underline.content_mask,
             Primitive::MonochromeSprite(sprite) => &sprite.content_mask,
             Primitive::PolychromeSprite(sprite) => &sprite.content_mask,
             Primitive::Surface(surface) => &surface.content_mask,
+            Primitive::Custom(primitive) => &primitive.content_mask,
+            Primitive::OpacityGroup(group) => &group.content_mask,
+        }
+    }
+
+    /// The transformation the renderer applies to this primitive's bounds. Paths are transformed
+    /// when they're painted, and backdrop blurs, surfaces, custom primitives and opacity groups
+    /// can only cover axis-aligned bounds.
+    pub fn transformation(&self) -> TransformationMatrix {
+        match self {
+            Primitive::Shadow(shadow) => shadow.transformation,
+            Primitive::Quad(quad) => quad.transformation,
+            Primitive::Underline(underline) => underline.transformation,
+            Primitive::MonochromeSprite(sprite) => sprite.transformation,
+            Primitive::PolychromeSprite(sprite) => sprite.transformation,
+            Primitive::BackdropBlur(_)
+            | Primitive::Path(_)
+            | Primitive::Surface(_)
+            | Primitive::Custom(_)
+            | Primitive::OpacityGroup(_) => TransformationMatrix::unit(),
+        }
+    }
+}
+
+impl Primitive {
+    /// Whether both primitives paint the same thing, whatever their draw order. Surfaces and
+    /// custom primitives never do, since their contents can change without the scene changing.
+    fn paints_same_as(&self, other: &Primitive) -> bool {
+        match (self, other) {
+            (Primitive::Shadow(a), Primitive::Shadow(b)) => {
+                *a == Shadow {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            (Primitive::BackdropBlur(a), Primitive::BackdropBlur(b)) => {
+                *a == BackdropBlur {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            (Primitive::Quad(a), Primitive::Quad(b)) => {
+                *a == Quad {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            (Primitive::Path(a), Primitive::Path(b)) => a.paints_same_as(b),
+            (Primitive::Underline(a), Primitive::Underline(b)) => {
+                *a == Underline {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            (Primitive::MonochromeSprite(a), Primitive::MonochromeSprite(b)) => {
+                *a == MonochromeSprite {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            (Primitive::PolychromeSprite(a), Primitive::PolychromeSprite(b)) => {
+                *a == PolychromeSprite {
+                    order: a.order,
+                    ..b.clone()
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Where a primitive paints, used to find what changed between frames.
+struct PaintedRegion<'a> {
+    primitive: &'a Primitive,
+    /// The combined opacity of the groups the primitive is painted in.
+    opacity: f32,
+    /// The bounds the primitive paints within, once clipped and transformed.
+    bounds: Bounds<ScaledPixels>,
+}
+
+impl PaintedRegion<'_> {
+    fn key(&self) -> [u32; 4] {
+        [
+            self.bounds.origin.x.0.to_bits(),
+            self.bounds.origin.y.0.to_bits(),
+            self.bounds.size.width.0.to_bits(),
+            self.bounds.size.height.0.to_bits(),
+        ]
+    }
+
+    fn paints_same_as(&self, other: &PaintedRegion) -> bool {
+        self.bounds == other.bounds
+            && self.opacity == other.opacity
+            && self.primitive.paints_same_as(other.primitive)
+    }
+}
+
+/// Round the damaged regions out to whole device pixels and merge the ones that overlap, or all
+/// of them once there are too many.
+fn coalesce_damage(damage: Vec<Bounds<ScaledPixels>>) -> Vec<Bounds<ScaledPixels>> {
+    let mut coalesced: Vec<Bounds<ScaledPixels>> = Vec::new();
+    for bounds in damage {
+        if bounds.is_empty() {
+            continue;
+        }
+
+        let mut bounds = Bounds::from_corners(
+            bounds.origin.map(|coordinate| coordinate.floor()),
+            bounds.lower_right().map(|coordinate| coordinate.ceil()),
+        );
+        while let Some(ix) = coalesced.iter().position(|other| other.intersects(&bounds)) {
+            bounds = bounds.union(&coalesced.swap_remove(ix));
+        }
+        coalesced.push(bounds);
+
+        if coalesced.len() > MAX_DAMAGE_REGIONS {
+            let union = coalesced
+                .drain(..)
+                .reduce(|union, bounds| union.union(&bounds));
+            coalesced.extend(union);
         }
     }
+    coalesced
 }
 
 struct BatchIterator<'a> {
     shadows: &'a [Shadow],
     shadows_start: usize,
     shadows_iter: Peekable<slice::Iter<'a, Shadow>>,
+    backdrop_blurs: &'a [BackdropBlur],
+    backdrop_blurs_start: usize,
+    backdrop_blurs_iter: Peekable<slice::Iter<'a, BackdropBlur>>,
     quads: &'a [Quad],
     quads_start: usize,
     quads_iter: Peekable<slice::Iter<'a, Quad>>,
+    solid_quad_runs_iter: Peekable<slice::Iter<'a, Range<usize>>>,
     paths: &'a [Path<ScaledPixels>],
     paths_start: usize,
     paths_iter: Peekable<slice::Iter<'a, Path<ScaledPixels>>>,
@@ -234,6 +1238,12 @@ struct BatchIterator<'a> {
     surfaces: &'a [PaintSurface],
     surfaces_start: usize,
     surfaces_iter: Peekable<slice::Iter<'a, PaintSurface>>,
+    custom_primitives: &'a [CustomPrimitive],
+    custom_primitives_start: usize,
+    custom_primitives_iter: Peekable<slice::Iter<'a, CustomPrimitive>>,
+    opacity_groups: &'a [OpacityGroup],
+    opacity_groups_start: usize,
+    opacity_groups_iter: Peekable<slice::Iter<'a, OpacityGroup>>,
 }
 
 impl<'a> Iterator for BatchIterator<'a> {
@@ -245,6 +1255,10 @@ impl<'a> Iterator for BatchIterator<'a> {
                 self.shadows_iter.peek().map(|s| s.order),
                 PrimitiveKind::Shadow,
             ),
+            (
+                self.backdrop_blurs_iter.peek().map(|b| b.order),
+                PrimitiveKind::BackdropBlur,
+            ),
             (self.quads_iter.peek().map(|q| q.order), PrimitiveKind::Quad),
             (self.paths_iter.peek().map(|q| q.order), PrimitiveKind::Path),
             (
@@ -263,6 +1277,14 @@ impl<'a> Iterator for BatchIterator<'a> {
                 self.surfaces_iter.peek().map(|s| s.order),
                 PrimitiveKind::Surface,
             ),
+            (
+                self.custom_primitives_iter.peek().map(|p| p.order),
+                PrimitiveKind::Custom,
+            ),
+            (
+                self.opacity_groups_iter.peek().map(|g| g.order),
+                PrimitiveKind::OpacityGroup,
+            ),
         ];
         orders_and_kinds.sort_by_key(|(order, kind)| (order.unwrap_or(u32::MAX), *kind));
 
@@ -291,19 +1313,64 @@ impl<'a> Iterator for BatchIterator<'a> {
                     &self.shadows[shadows_start..shadows_end],
                 ))
             }
+            PrimitiveKind::BackdropBlur => {
+                // Every blur samples a copy of what was drawn before it, so only blurs that
+                // don't overlap, and thus can't see each other, are drawn together.
+                let blurs_start = self.backdrop_blurs_start;
+                let mut blurs_end = blurs_start + 1;
+                self.backdrop_blurs_iter.next();
+                while self
+                    .backdrop_blurs_iter
+                    .next_if(|blur| {
+                        (blur.order, batch_kind) < max_order_and_kind
+                            && self.backdrop_blurs[blurs_start..blurs_end]
+                                .iter()
+                                .all(|other| !other.bounds.intersects(&blur.bounds))
+                    })
+                    .is_some()
+                {
+                    blurs_end += 1;
+                }
+                self.backdrop_blurs_start = blurs_end;
+                Some(PrimitiveBatch::BackdropBlurs(
+                    &self.backdrop_blurs[blurs_start..blurs_end],
+                ))
+            }
             PrimitiveKind::Quad => {
                 let quads_start = self.quads_start;
-                let mut quads_end = quads_start + 1;
-                self.quads_iter.next();
                 while self
-                    .quads_iter
-                    .next_if(|quad| (quad.order, batch_kind) < max_order_and_kind)
+                    .solid_quad_runs_iter
+                    .next_if(|run| run.end <= quads_start)
                     .is_some()
+                {}
+                // Solid quads are batched separately, up to the end of their run, and other
+                // quads up to the start of the next one.
+                let (is_solid, quads_limit) = match self.solid_quad_runs_iter.peek() {
+                    Some(run) if run.start <= quads_start => (true, run.end),
+                    Some(run) => (false, run.start),
+                    None => (false, self.quads.len()),
+                };
+
+                let blend_mode = self.quads_iter.next().unwrap().blend_mode;
+                let mut quads_end = quads_start + 1;
+                while quads_end < quads_limit
+                    && self
+                        .quads_iter
+                        .next_if(|quad| {
+                            (quad.order, batch_kind) < max_order_and_kind
+                                && quad.blend_mode == blend_mode
+                        })
+                        .is_some()
                 {
                     quads_end += 1;
                 }
                 self.quads_start = quads_end;
-                Some(PrimitiveBatch::Quads(&self.quads[quads_start..quads_end]))
+                let quads = &self.quads[quads_start..quads_end];
+                if is_solid {
+                    Some(PrimitiveBatch::SolidQuads(quads))
+                } else {
+                    Some(PrimitiveBatch::Quads(quads))
+                }
             }
             PrimitiveKind::Path => {
                 let paths_start = self.paths_start;
@@ -336,15 +1403,17 @@ impl<'a> Iterator for BatchIterator<'a> {
                 ))
             }
             PrimitiveKind::MonochromeSprite => {
-                let texture_id = self.monochrome_sprites_iter.peek().unwrap().tile.texture_id;
+                let first = self.monochrome_sprites_iter.next().unwrap();
+                let (base_texture_id, blend_mode) =
+                    (first.tile.texture_id.batch_base(), first.blend_mode);
                 let sprites_start = self.monochrome_sprites_start;
                 let mut sprites_end = sprites_start + 1;
-                self.monochrome_sprites_iter.next();
                 while self
                     .monochrome_sprites_iter
                     .next_if(|sprite| {
                         (sprite.order, batch_kind) < max_order_and_kind
-                            && sprite.tile.texture_id == texture_id
+                            && sprite.tile.texture_id.batch_base() == base_texture_id
+                            && sprite.blend_mode == blend_mode
                     })
                     .is_some()
                 {
@@ -352,20 +1421,22 @@ impl<'a> Iterator for BatchIterator<'a> {
                 }
                 self.monochrome_sprites_start = sprites_end;
                 Some(PrimitiveBatch::MonochromeSprites {
-                    texture_id,
+                    base_texture_id,
                     sprites: &self.monochrome_sprites[sprites_start..sprites_end],
                 })
             }
             PrimitiveKind::PolychromeSprite => {
-                let texture_id = self.polychrome_sprites_iter.peek().unwrap().tile.texture_id;
+                let first = self.polychrome_sprites_iter.next().unwrap();
+                let (base_texture_id, blend_mode) =
+                    (first.tile.texture_id.batch_base(), first.blend_mode);
                 let sprites_start = self.polychrome_sprites_start;
                 let mut sprites_end = self.polychrome_sprites_start + 1;
-                self.polychrome_sprites_iter.next();
                 while self
                     .polychrome_sprites_iter
                     .next_if(|sprite| {
                         (sprite.order, batch_kind) < max_order_and_kind
-                            && sprite.tile.texture_id == texture_id
+                            && sprite.tile.texture_id.batch_base() == base_texture_id
+                            && sprite.blend_mode == blend_mode
                     })
                     .is_some()
                 {
@@ -373,7 +1444,7 @@ impl<'a> Iterator for BatchIterator<'a> {
                 }
                 self.polychrome_sprites_start = sprites_end;
                 Some(PrimitiveBatch::PolychromeSprites {
-                    texture_id,
+                    base_texture_id,
                     sprites: &self.polychrome_sprites[sprites_start..sprites_end],
                 })
             }
@@ -393,6 +1464,43 @@ impl<'a> Iterator for BatchIterator<'a> {
                     &self.surfaces[surfaces_start..surfaces_end],
                 ))
             }
+            PrimitiveKind::Custom => {
+                let shader_id = self.custom_primitives_iter.peek().unwrap().shader_id;
+                let primitives_start = self.custom_primitives_start;
+                let mut primitives_end = primitives_start + 1;
+                self.custom_primitives_iter.next();
+                while self
+                    .custom_primitives_iter
+                    .next_if(|primitive| {
+                        (primitive.order, batch_kind) < max_order_and_kind
+                            && primitive.shader_id == shader_id
+                    })
+                    .is_some()
+                {
+                    primitives_end += 1;
+                }
+                self.custom_primitives_start = primitives_end;
+                Some(PrimitiveBatch::Custom {
+                    shader_id,
+                    primitives: &self.custom_primitives[primitives_start..primitives_end],
+                })
+            }
+            PrimitiveKind::OpacityGroup => {
+                let groups_start = self.opacity_groups_start;
+                let mut groups_end = groups_start + 1;
+                self.opacity_groups_iter.next();
+                while self
+                    .opacity_groups_iter
+                    .next_if(|group| (group.order, batch_kind) < max_order_and_kind)
+                    .is_some()
+                {
+                    groups_end += 1;
+                }
+                self.opacity_groups_start = groups_end;
+                Some(PrimitiveBatch::OpacityGroups(
+                    &self.opacity_groups[groups_start..groups_end],
+                ))
+            }
         }
     }
 }
@@ -400,31 +1508,109 @@ impl<'a> Iterator for BatchIterator<'a> {
 #[derive(Debug)]
 pub(crate) enum PrimitiveBatch<'a> {
     Shadows(&'a [Shadow]),
+    /// Blurs of what was drawn before them, which renderers draw after copying the frame.
+    BackdropBlurs(&'a [BackdropBlur]),
     Quads(&'a [Quad]),
+    /// Quads with a solid background and nothing else, which renderers draw from
+    /// [`SolidQuad`] instances.
+    SolidQuads(&'a [Quad]),
     Paths(&'a [Path<ScaledPixels>]),
     Underlines(&'a [Underline]),
+    /// Sprites whose textures are in the group of [`AtlasTextureId::PER_BATCH`] textures that
+    /// starts at `base_texture_id`.
     MonochromeSprites {
-        texture_id: AtlasTextureId,
+        base_texture_id: AtlasTextureId,
         sprites: &'a [MonochromeSprite],
     },
     PolychromeSprites {
-        texture_id: AtlasTextureId,
+        base_texture_id: AtlasTextureId,
         sprites: &'a [PolychromeSprite],
     },
     Surfaces(&'a [PaintSurface]),
+    /// Primitives drawn with the same custom shader.
+    Custom {
+        shader_id: CustomShaderId,
+        primitives: &'a [CustomPrimitive],
+    },
+    /// Groups whose contents renderers draw to a texture of their own before compositing it.
+    OpacityGroups(&'a [OpacityGroup]),
+}
+
+/// How the colors a quad or a sprite paints are combined with what was drawn beneath it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u32)]
+pub enum BlendMode {
+    /// Paint over what's beneath, like CSS's `normal` blend mode.
+    #[default]
+    Normal,
+    /// Multiply the colors with those beneath them, which darkens them. Unlike painting over
+    /// them, tinting with multiply keeps the contrast of what's beneath, such as the colors of
+    /// highlighted text.
+    Multiply,
+    /// Multiply the inverses of the colors with those beneath them, which lightens them.
+    Screen,
+    /// Add the colors to those beneath them, like CSS's `plus-lighter` blend mode.
+    PlusLighter,
+}
+
+impl BlendMode {
+    /// Every blend mode, in the order of their discriminants.
+    pub(crate) const ALL: [BlendMode; 4] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::PlusLighter,
+    ];
+
+    fn is_normal(&self) -> bool {
+        *self == BlendMode::Normal
+    }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 #[repr(C)]
 pub(crate) struct Quad {
     pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
+    pub blend_mode: BlendMode,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
-    pub background: Hsla,
+    pub background: Background,
     pub border_color: Hsla,
     pub corner_radii: Corners<ScaledPixels>,
     pub border_widths: Edges<ScaledPixels>,
+    pub transformation: TransformationMatrix,
+}
+
+impl Quad {
+    /// Whether the quad only paints its background color, so it can be drawn as a [`SolidQuad`].
+    fn is_solid(&self) -> bool {
+        self.background.tag == BackgroundTag::Solid
+            && !self.border_widths.any(|width| width.0 > 0.)
+            && self.has_square_corners()
+            && self.transformation == TransformationMatrix::unit()
+    }
+
+    fn has_square_corners(&self) -> bool {
+        let corner_radii = &self.corner_radii;
+        [
+            corner_radii.top_left,
+            corner_radii.top_right,
+            corner_radii.bottom_right,
+            corner_radii.bottom_left,
+        ]
+        .iter()
+        .all(|radius| radius.0 <= 0.)
+    }
+
+    /// Whether nothing drawn beneath the quad shows through its clipped bounds.
+    fn is_opaque(&self) -> bool {
+        self.blend_mode == BlendMode::Normal
+            && self.background.is_opaque()
+            && (self.border_color.is_opaque() || !self.border_widths.any(|width| width.0 > 0.))
+            && self.has_square_corners()
+            && self.transformation == TransformationMatrix::unit()
+    }
 }
 
 impl Ord for Quad {
@@ -445,16 +1631,42 @@ impl From<Quad> for Primitive {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// The compact instance renderers draw [`PrimitiveBatch::SolidQuads`] from, with the bounds
+/// already clipped to the content mask.
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
-pub(crate) struct Underline {
-    pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
+pub(crate) struct SolidQuad {
+    pub bounds: Bounds<ScaledPixels>,
+    pub color: Hsla,
+}
+
+impl From<&Quad> for SolidQuad {
+    fn from(quad: &Quad) -> Self {
+        Self {
+            bounds: quad.bounds.intersect(&quad.content_mask.bounds),
+            color: quad.background.solid,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[repr(C)]
+pub(crate) struct Underline {
+    pub order: DrawOrder,
+    pub kind: UnderlineKind,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
     pub thickness: ScaledPixels,
-    pub wavy: bool,
+    /// The length of one period of a wavy underline's wave, or zero for a straight underline.
+    pub wavelength: ScaledPixels,
+    pub transformation: TransformationMatrix,
+    /// The length of the dashes of a dashed underline, or zero for a continuous one.
+    pub dash: ScaledPixels,
+    pub dash_gap: ScaledPixels,
+    pub dash_offset: ScaledPixels,
+    /// How far a wavy underline's wave reaches above and below the middle of its bounds.
+    pub wave_amplitude: ScaledPixels,
 }
 
 impl Ord for Underline {
@@ -484,6 +1696,14 @@ pub(crate) struct Shadow {
     pub corner_radii: Corners<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
+    pub transformation: TransformationMatrix,
+    /// How far an inset shadow's casting shape is moved from its bounds.
+    pub inset_offset: Point<ScaledPixels>,
+    /// How far an inset shadow's casting shape is shrunk from its bounds.
+    pub inset_spread_radius: ScaledPixels,
+    /// Whether the shadow is painted inside its bounds, outside of a shape cast from them,
+    /// rather than around them.
+    pub inset: bool,
 }
 
 impl Ord for Shadow {
@@ -504,6 +1724,34 @@ impl From<Shadow> for Primitive {
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[repr(C)]
+pub(crate) struct BackdropBlur {
+    pub order: DrawOrder,
+    pub blur_radius: ScaledPixels,
+    pub bounds: Bounds<ScaledPixels>,
+    pub corner_radii: Corners<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+}
+
+impl Ord for BackdropBlur {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order.cmp(&other.order)
+    }
+}
+
+impl PartialOrd for BackdropBlur {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<BackdropBlur> for Primitive {
+    fn from(blur: BackdropBlur) -> Self {
+        Primitive::BackdropBlur(blur)
+    }
+}
+
 /// A data type representing a 2 dimensional transformation that can be applied to an element.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
@@ -588,16 +1836,79 @@ impl TransformationMatrix {
         }
     }
 
+    /// The transformation that undoes this one, if it can be undone. Transformations that
+    /// scale an axis down to nothing can't be.
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b], [c, d]] = self.rotation_scale;
+        let determinant = a * d - b * c;
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let rotation_scale = [
+            [d / determinant, -b / determinant],
+            [-c / determinant, a / determinant],
+        ];
+        let [x, y] = self.translation;
+        Some(Self {
+            rotation_scale,
+            translation: [
+                -(rotation_scale[0][0] * x + rotation_scale[0][1] * y),
+                -(rotation_scale[1][0] * x + rotation_scale[1][1] * y),
+            ],
+        })
+    }
+
+    /// Multiply the translation of this transformation by the given factor, converting it
+    /// between logical and scaled pixels.
+    pub(crate) fn scale_translation(mut self, factor: f32) -> Self {
+        self.translation = [self.translation[0] * factor, self.translation[1] * factor];
+        self
+    }
+
     /// Apply transformation to a point, mainly useful for debugging
     pub fn apply(&self, point: Point<Pixels>) -> Point<Pixels> {
-        let input = [point.x.0, point.y.0];
+        let [x, y] = self.apply_raw([point.x.0, point.y.0]);
+        Point::new(x.into(), y.into())
+    }
+
+    /// Transform the given bounds, returning the smallest axis-aligned bounds that contain
+    /// the result.
+    pub(crate) fn transform_bounds(&self, bounds: Bounds<ScaledPixels>) -> Bounds<ScaledPixels> {
+        if *self == Self::unit() {
+            return bounds;
+        }
+
+        let Bounds { origin, size } = bounds;
+        let corners = [
+            [origin.x.0, origin.y.0],
+            [origin.x.0 + size.width.0, origin.y.0],
+            [origin.x.0, origin.y.0 + size.height.0],
+            [origin.x.0 + size.width.0, origin.y.0 + size.height.0],
+        ];
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        for corner in corners {
+            let corner = self.apply_raw(corner);
+            for axis in 0..2 {
+                min[axis] = min[axis].min(corner[axis]);
+                max[axis] = max[axis].max(corner[axis]);
+            }
+        }
+        Bounds::from_corners(
+            point(ScaledPixels(min[0]), ScaledPixels(min[1])),
+            point(ScaledPixels(max[0]), ScaledPixels(max[1])),
+        )
+    }
+
+    fn apply_raw(&self, input: [f32; 2]) -> [f32; 2] {
         let mut output = self.translation;
         for (i, output_cell) in output.iter_mut().enumerate() {
             for (k, input_cell) in input.iter().enumerate() {
                 *output_cell += self.rotation_scale[i][k] * *input_cell;
             }
         }
-        Point::new(output[0].into(), output[1].into())
+        output
     }
 }
 
@@ -611,7 +1922,7 @@ impl Default for TransformationMatrix {
 #[repr(C)]
 pub(crate) struct MonochromeSprite {
     pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
+    pub blend_mode: BlendMode,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub color: Hsla,
@@ -644,13 +1955,33 @@ impl From<MonochromeSprite> for Primitive {
 #[repr(C)]
 pub(crate) struct PolychromeSprite {
     pub order: DrawOrder,
-    pub pad: u32, // align to 8 bytes
-    pub grayscale: bool,
+    /// How far the hues of the sprite are rotated, in radians.
+    pub hue_rotate: f32,
+    /// How much the sprite is desaturated, from 0 for its own colors to 1 for grayscale.
+    pub grayscale: f32,
     pub opacity: f32,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub corner_radii: Corners<ScaledPixels>,
     pub tile: AtlasTile,
+    pub transformation: TransformationMatrix,
+    /// The widths of the borders kept at a fixed size when the sprite is drawn as a nine-slice:
+    /// corners aren't stretched, edges only stretch along their length, and the center fills the
+    /// rest. Zero for sprites whose tile is stretched uniformly.
+    pub nine_slice: Edges<ScaledPixels>,
+    /// The widths of the same borders within the tile, in the tile's pixels.
+    pub tile_nine_slice: Edges<ScaledPixels>,
+    /// The size each copy of the tile is drawn at when it repeats across the bounds, like a
+    /// CSS `background-repeat`. Zero for sprites drawing the tile once.
+    pub repeat_size: Size<ScaledPixels>,
+    /// The gap left between neighboring copies of a repeating tile.
+    pub repeat_spacing: Size<ScaledPixels>,
+    /// Where a copy of a repeating tile starts, relative to the origin of the bounds.
+    pub repeat_origin: Point<ScaledPixels>,
+    /// The color the sprite's colors are blended towards, by as much as its alpha.
+    pub tint: Hsla,
+    pub blend_mode: BlendMode,
+    pub pad: u32, // align to 8 bytes
 }
 impl Eq for PolychromeSprite {}
 
@@ -681,7 +2012,7 @@ pub(crate) struct PaintSurface {
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     #[cfg(target_os = "macos")]
-    pub image_buffer: media::core_video::CVImageBuffer,
+    pub source: crate::SurfaceSource,
 }
 
 impl Ord for PaintSurface {
@@ -702,6 +2033,139 @@ impl From<PaintSurface> for Primitive {
     }
 }
 
+/// A primitive whose pixels are colored by a shader the application registered. The layout is
+/// shared with the shaders that renderers wrap custom shaders in.
+#[derive(Clone, Debug, PartialEq)]
+#[repr(C)]
+pub(crate) struct CustomPrimitive {
+    pub order: DrawOrder,
+    pub shader_id: CustomShaderId,
+    pub bounds: Bounds<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+    pub uniforms: [f32; MAX_CUSTOM_SHADER_UNIFORMS],
+}
+
+impl Eq for CustomPrimitive {}
+
+impl Ord for CustomPrimitive {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order.cmp(&other.order)
+    }
+}
+
+impl PartialOrd for CustomPrimitive {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<CustomPrimitive> for Primitive {
+    fn from(primitive: CustomPrimitive) -> Self {
+        Primitive::Custom(primitive)
+    }
+}
+
+/// Primitives that are drawn to a texture of their own, which is then composited at the
+/// group's opacity, so the primitives in it don't show through each other.
+#[derive(Clone)]
+pub(crate) struct OpacityGroup {
+    pub order: DrawOrder,
+    /// Unique among the groups of a frame, including nested ones, and lower than
+    /// [`Scene::opacity_group_count`].
+    pub id: usize,
+    pub opacity: f32,
+    /// Set for groups whose texture is kept from frame to frame.
+    pub cache: Option<GroupCache>,
+    pub bounds: Bounds<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+    pub scene: Arc<Scene>,
+}
+
+/// How the texture of a cached group is kept from frame to frame.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GroupCache {
+    /// Identifies the group across frames.
+    pub key: u64,
+    /// The texture the group is drawn to. Renderers keep one texture per slot, separate from
+    /// the ones of other opacity groups.
+    pub slot: usize,
+    /// Whether the group paints the same as in the previous frame, so that the texture still
+    /// holds what it paints.
+    pub unchanged: bool,
+}
+
+impl OpacityGroup {
+    /// The sprite that composites this group. Groups are drawn to viewport-sized textures where
+    /// they end up in the frame, so the sprite's tile is its bounds.
+    pub fn sprite(&self) -> PolychromeSprite {
+        PolychromeSprite {
+            order: self.order,
+            hue_rotate: 0.,
+            grayscale: 0.,
+            opacity: self.opacity,
+            bounds: self.bounds,
+            content_mask: self.content_mask.clone(),
+            corner_radii: Corners::default(),
+            nine_slice: Edges::default(),
+            tile_nine_slice: Edges::default(),
+            repeat_size: Size::default(),
+            repeat_spacing: Size::default(),
+            repeat_origin: Point::default(),
+            tile: AtlasTile {
+                texture_id: AtlasTextureId {
+                    index: self.id as u32,
+                    kind: AtlasTextureKind::Polychrome,
+                },
+                tile_id: TileId(0),
+                padding: 0,
+                bounds: self.bounds.map(Into::into),
+            },
+            transformation: TransformationMatrix::unit(),
+            tint: Hsla::default(),
+            blend_mode: BlendMode::Normal,
+            pad: 0,
+        }
+    }
+}
+
+impl Debug for OpacityGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpacityGroup")
+            .field("order", &self.order)
+            .field("id", &self.id)
+            .field("opacity", &self.opacity)
+            .field("cache", &self.cache)
+            .field("bounds", &self.bounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for OpacityGroup {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order && self.id == other.id
+    }
+}
+
+impl Eq for OpacityGroup {}
+
+impl Ord for OpacityGroup {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order.cmp(&other.order)
+    }
+}
+
+impl PartialOrd for OpacityGroup {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<OpacityGroup> for Primitive {
+    fn from(group: OpacityGroup) -> Self {
+        Primitive::OpacityGroup(group)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct PathId(pub(crate) usize);
 
@@ -713,12 +2177,22 @@ pub struct Path<P: Clone + Default + Debug> {
     pub(crate) bounds: Bounds<P>,
     pub(crate) content_mask: ContentMask<P>,
     pub(crate) vertices: Vec<PathVertex<P>>,
-    pub(crate) color: Hsla,
+    pub(crate) color: Background,
+    /// Whether overlapping triangles all fill the path, like the ones of a stroke, rather than
+    /// alternately filling and cutting it out.
+    pub(crate) nonzero: bool,
     start: Point<P>,
     current: Point<P>,
     contour_count: usize,
+    /// The index of the first vertex of the fringe along the line closing the current contour,
+    /// which moves as the contour grows.
+    closing_fringe: Option<usize>,
 }
 
+/// How far the fringes anti-aliasing the straight edges of a path extend on each side. It only
+/// needs to cover half a device pixel, as coverage is computed from the distance to the edge.
+const EDGE_FRINGE_WIDTH: Pixels = px(1.);
+
 impl Path<Pixels> {
     /// Create a new path with the given starting point.
     pub fn new(start: Point<Pixels>) -> Self {
@@ -734,7 +2208,9 @@ impl Path<Pixels> {
             },
             content_mask: Default::default(),
             color: Default::default(),
+            nonzero: false,
             contour_count: 0,
+            closing_fringe: None,
         }
     }
 
@@ -753,10 +2229,21 @@ impl Path<Pixels> {
             start: self.start.map(|start| start.scale(factor)),
             current: self.current.scale(factor),
             contour_count: self.contour_count,
+            closing_fringe: self.closing_fringe,
             color: self.color,
+            nonzero: self.nonzero,
         }
     }
 
+    /// Start a new contour at the given point, without drawing a line to it. Like the last one,
+    /// the contour is filled as if it was closed.
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.start = to;
+        self.current = to;
+        self.contour_count = 0;
+        self.closing_fringe = None;
+    }
+
     /// Draw a straight line from the current point to the given point.
     pub fn line_to(&mut self, to: Point<Pixels>) {
         self.contour_count += 1;
@@ -766,7 +2253,9 @@ impl Path<Pixels> {
                 (point(0., 1.), point(0., 1.), point(0., 1.)),
             );
         }
+        self.push_edge_fringe(self.current, to);
         self.current = to;
+        self.update_closing_fringe();
     }
 
     /// Draw a curve from the current point to the given point, using the given control point.
@@ -784,9 +2273,51 @@ impl Path<Pixels> {
             (point(0., 0.), point(0.5, 0.), point(1., 1.)),
         );
         self.current = to;
+        self.update_closing_fringe();
     }
 
-    fn push_triangle(
+    /// Anti-aliases a straight edge of the path with a quad straddling it, whose coverage is
+    /// computed in the shader from the distance to the edge.
+    ///
+    /// Paths are filled where they're covered an odd number of times, so coverage is added on
+    /// the left of the edge and taken away on its right, both of which anti-alias the edge
+    /// whichever side is inside. The fringes of edges going back and forth along the same line,
+    /// like the ones between adjacent shapes of a combined path, then cancel each other out.
+    fn push_edge_fringe(&mut self, from: Point<Pixels>, to: Point<Pixels>) {
+        let direction = point((to.x - from.x).0, (to.y - from.y).0);
+        let length = direction.x.hypot(direction.y);
+        let normal = if length > 0. {
+            point(direction.y / length, -direction.x / length).map(|n| EDGE_FRINGE_WIDTH * n)
+        } else {
+            Point::default()
+        };
+
+        let (left, right) = (point(1., -1.), point(-1., -1.));
+        self.push_triangle(
+            (from + normal, to + normal, to - normal),
+            (left, left, right),
+        );
+        self.push_triangle(
+            (from + normal, to - normal, from - normal),
+            (left, right, right),
+        );
+    }
+
+    /// Moves the fringe of the line closing the current contour to its new last point. Paths
+    /// are filled as if their contours were closed, so that line is an edge too.
+    fn update_closing_fringe(&mut self) {
+        let fringe_start = self.vertices.len();
+        self.push_edge_fringe(self.current, self.start);
+        if let Some(closing_fringe) = self.closing_fringe {
+            let fringe = self.vertices.split_off(fringe_start);
+            self.vertices
+                .splice(closing_fringe..closing_fringe + fringe.len(), fringe);
+        } else {
+            self.closing_fringe = Some(fringe_start);
+        }
+    }
+
+    pub(crate) fn push_triangle(
         &mut self,
         xy: (Point<Pixels>, Point<Pixels>, Point<Pixels>),
         st: (Point<f32>, Point<f32>, Point<f32>),
@@ -824,6 +2355,38 @@ impl Path<Pixels> {
     }
 }
 
+impl Path<ScaledPixels> {
+    /// Transform the vertices of this path. Paths are rasterized into axis-aligned tiles, so
+    /// they're clipped by the bounds of their transformed content mask.
+    pub(crate) fn transform(&mut self, transformation: &TransformationMatrix) {
+        if *transformation == TransformationMatrix::unit() {
+            return;
+        }
+
+        for vertex in &mut self.vertices {
+            let [x, y] = transformation.apply_raw([vertex.xy_position.x.0, vertex.xy_position.y.0]);
+            vertex.xy_position = point(ScaledPixels(x), ScaledPixels(y));
+        }
+        self.bounds = transformation.transform_bounds(self.bounds);
+        self.content_mask.bounds = transformation.transform_bounds(self.content_mask.bounds);
+    }
+
+    /// Whether this path covers the same pixels as the other one in the same color, regardless
+    /// of their draw order.
+    fn paints_same_as(&self, other: &Self) -> bool {
+        self.bounds == other.bounds
+            && self.content_mask == other.content_mask
+            && self.color == other.color
+            && self.nonzero == other.nonzero
+            && self.vertices.len() == other.vertices.len()
+            && self.vertices.iter().zip(&other.vertices).all(|(a, b)| {
+                a.xy_position == b.xy_position
+                    && a.st_position == b.st_position
+                    && a.content_mask == b.content_mask
+            })
+    }
+}
+
 impl Eq for Path<ScaledPixels> {}
 
 impl PartialEq for Path<ScaledPixels> {
@@ -867,3 +2430,1236 @@ impl PathVertex<Pixels> {
         }
     }
 }
+
+/// What renderers drew for a frame, to find out why it took as many draw calls as it did. See
+/// [`WindowContext::scene_stats`](crate::WindowContext::scene_stats).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SceneStats {
+    /// The number of primitives of each kind that were drawn.
+    pub primitive_counts: PrimitiveCounts,
+    /// The number of batches, each of which takes a draw call.
+    pub batch_count: usize,
+    /// Why each batch after the first one had to be started.
+    pub batch_breaks: BatchBreaks,
+    /// The depth of the tree used to order the primitives, which grows with how much they
+    /// overlap.
+    pub bounds_tree_depth: usize,
+    /// The size of the primitive data uploaded to the GPU, in bytes.
+    pub instance_bytes: usize,
+}
+
+impl SceneStats {
+    /// Adds the stats of a scene drawn as part of this one.
+    fn add_nested(&mut self, nested: SceneStats) {
+        self.primitive_counts.add(&nested.primitive_counts);
+        self.batch_count += nested.batch_count;
+        self.batch_breaks.add(&nested.batch_breaks);
+        self.bounds_tree_depth = self.bounds_tree_depth.max(nested.bounds_tree_depth);
+        self.instance_bytes += nested.instance_bytes;
+    }
+}
+
+/// The number of primitives of each kind drawn in a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrimitiveCounts {
+    /// Drop shadows.
+    pub shadows: usize,
+    /// Backdrop blurs.
+    pub backdrop_blurs: usize,
+    /// Quads drawn with borders, rounded corners or gradients.
+    pub quads: usize,
+    /// Quads drawn from compact instances, because they only paint a solid color.
+    pub solid_quads: usize,
+    /// Vector paths.
+    pub paths: usize,
+    /// Underlines and strikethroughs.
+    pub underlines: usize,
+    /// Monochrome sprites, such as glyphs and icons.
+    pub monochrome_sprites: usize,
+    /// Polychrome sprites, such as images and emoji.
+    pub polychrome_sprites: usize,
+    /// Platform surfaces, such as video frames.
+    pub surfaces: usize,
+    /// Primitives drawn with custom shaders.
+    pub custom: usize,
+    /// Groups of primitives drawn with an opacity.
+    pub opacity_groups: usize,
+}
+
+impl PrimitiveCounts {
+    fn add(&mut self, other: &PrimitiveCounts) {
+        self.shadows += other.shadows;
+        self.backdrop_blurs += other.backdrop_blurs;
+        self.quads += other.quads;
+        self.solid_quads += other.solid_quads;
+        self.paths += other.paths;
+        self.underlines += other.underlines;
+        self.monochrome_sprites += other.monochrome_sprites;
+        self.polychrome_sprites += other.polychrome_sprites;
+        self.surfaces += other.surfaces;
+        self.custom += other.custom;
+        self.opacity_groups += other.opacity_groups;
+    }
+
+    /// The number of primitives of all kinds.
+    pub fn total(&self) -> usize {
+        self.shadows
+            + self.backdrop_blurs
+            + self.quads
+            + self.solid_quads
+            + self.paths
+            + self.underlines
+            + self.monochrome_sprites
+            + self.polychrome_sprites
+            + self.surfaces
+            + self.custom
+            + self.opacity_groups
+    }
+}
+
+/// Why the batches of a frame ended before all primitives of their kind were drawn.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchBreaks {
+    /// Primitives of another kind had to be drawn in between, because they overlap.
+    pub kind_change: usize,
+    /// The next sprite is in another atlas texture.
+    pub texture_change: usize,
+    /// The next custom primitive is drawn with another shader.
+    pub shader_change: usize,
+    /// A run of solid quads started or ended.
+    pub solid_quad_run: usize,
+}
+
+impl BatchBreaks {
+    fn add(&mut self, other: &BatchBreaks) {
+        self.kind_change += other.kind_change;
+        self.texture_change += other.texture_change;
+        self.shader_change += other.shader_change;
+        self.solid_quad_run += other.solid_quad_run;
+    }
+}
+
+/// A deterministic description of everything a scene paints, which can be compared against
+/// snapshots in tests. See [`WindowContext::serialize_scene`](crate::WindowContext::serialize_scene).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializedScene {
+    /// The painted primitives, in the order they're drawn.
+    pub primitives: Vec<SerializedPrimitive>,
+}
+
+/// A primitive painted into a scene. Positions and sizes are in device pixels.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SerializedPrimitive {
+    /// The order the primitive is drawn in. Primitives that overlap others are drawn later.
+    pub order: u32,
+    /// The bounds of the primitive, before it's transformed.
+    pub bounds: SerializedBounds,
+    /// The bounds the primitive is clipped to.
+    pub content_mask: SerializedBounds,
+    /// The transformation applied to the primitive, if any, as a row-major 2x2 rotation and
+    /// scale matrix followed by the translation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transformation: Option<[f32; 6]>,
+    /// The kind of primitive, along with the properties specific to it.
+    #[serde(flatten)]
+    pub kind: SerializedPrimitiveKind,
+}
+
+impl SerializedPrimitive {
+    fn contains_point(&self, point: Point<ScaledPixels>) -> bool {
+        let bounds = Bounds::from(self.bounds).intersect(&self.content_mask.into());
+        let transformation =
+            self.transformation
+                .map_or(TransformationMatrix::unit(), |[a, b, c, d, x, y]| {
+                    TransformationMatrix {
+                        rotation_scale: [[a, b], [c, d]],
+                        translation: [x, y],
+                    }
+                });
+        transformation.transform_bounds(bounds).contains(&point)
+    }
+}
+
+/// The kind of a [`SerializedPrimitive`]. Colors are given as hue, saturation, lightness and
+/// alpha, corner radii clockwise from the top left corner, and edges clockwise from the top.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SerializedPrimitiveKind {
+    /// A drop shadow.
+    Shadow {
+        /// The radii of the shadow's corners.
+        corner_radii: [f32; 4],
+        /// How far the shadow is blurred.
+        blur_radius: f32,
+        /// The color of the shadow.
+        color: [f32; 4],
+        /// For inset shadows, the offset and spread radius of the shape casting the shadow.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        inset: Option<[f32; 3]>,
+    },
+    /// A blur of what was painted behind it.
+    BackdropBlur {
+        /// The radii of the blurred area's corners.
+        corner_radii: [f32; 4],
+        /// How far what's behind the area is blurred.
+        blur_radius: f32,
+    },
+    /// A rectangle with optional borders and rounded corners.
+    Quad {
+        /// The fill of the quad.
+        background: SerializedBackground,
+        /// The color of the quad's borders.
+        border_color: [f32; 4],
+        /// The widths of the quad's borders.
+        border_widths: [f32; 4],
+        /// The radii of the quad's corners.
+        corner_radii: [f32; 4],
+        /// How the quad is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+    /// A vector path.
+    Path {
+        /// The fill of the path.
+        background: SerializedBackground,
+        /// The number of vertices in the path's triangles.
+        vertex_count: usize,
+    },
+    /// A straight or wavy underline.
+    Underline {
+        /// The color of the underline.
+        color: [f32; 4],
+        /// How thick the underline is.
+        thickness: f32,
+        /// Whether the underline is a single line, a double line or a row of dots.
+        kind: UnderlineKind,
+        /// The amplitude and wavelength of a wavy underline's wave, both zero for a straight
+        /// underline.
+        wave: [f32; 2],
+        /// The length of the underline's dashes, their gaps and its offset into the pattern,
+        /// all zero for continuous underlines.
+        dashes: [f32; 3],
+    },
+    /// A glyph or an icon, tinted with a single color.
+    MonochromeSprite {
+        /// The color of the sprite.
+        color: [f32; 4],
+        /// How the sprite is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+    /// An image, or a glyph with colors of its own such as an emoji.
+    PolychromeSprite {
+        /// The radii of the sprite's corners.
+        corner_radii: [f32; 4],
+        /// How much the sprite is desaturated, from 0 to 1.
+        grayscale: f32,
+        /// How far the hues of the sprite are rotated, in radians.
+        hue_rotate: f32,
+        /// The color the sprite is tinted with.
+        tint: [f32; 4],
+        /// The opacity of the sprite.
+        opacity: f32,
+        /// How the sprite is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+    /// A platform surface, such as a video frame.
+    Surface,
+    /// A primitive drawn with a custom shader.
+    Custom {
+        /// The index the shader was registered at.
+        shader_id: u32,
+        /// The uniforms the shader is run with.
+        uniforms: [f32; MAX_CUSTOM_SHADER_UNIFORMS],
+    },
+    /// Primitives composited together at a reduced opacity.
+    OpacityGroup {
+        /// The opacity the group is composited at.
+        opacity: f32,
+        /// The primitives in the group, in the order they're drawn.
+        primitives: Vec<SerializedPrimitive>,
+    },
+}
+
+/// The fill of a serialized quad or path. Colors are given as hue, saturation, lightness and
+/// alpha.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SerializedBackground {
+    /// A solid color.
+    Solid {
+        /// The color.
+        color: [f32; 4],
+    },
+    /// A linear gradient.
+    LinearGradient {
+        /// The direction of the gradient, in degrees clockwise from the top.
+        angle: f32,
+        /// The colors of the gradient, and their positions along it from 0 to 1.
+        stops: Vec<([f32; 4], f32)>,
+    },
+    /// A radial gradient.
+    RadialGradient {
+        /// The center of the gradient, relative to the bounds of the shape.
+        center: [f32; 2],
+        /// The colors of the gradient, and their distances from the center from 0 to 1.
+        stops: Vec<([f32; 4], f32)>,
+    },
+    /// A conic gradient.
+    ConicGradient {
+        /// The angle the gradient starts at, in degrees clockwise from the top.
+        angle: f32,
+        /// The center of the gradient, relative to the bounds of the shape.
+        center: [f32; 2],
+        /// The colors of the gradient, and their positions around it from 0 to 1.
+        stops: Vec<([f32; 4], f32)>,
+    },
+}
+
+impl From<&Background> for SerializedBackground {
+    fn from(background: &Background) -> Self {
+        let stops = background.stops[..background.stop_count as usize]
+            .iter()
+            .map(|stop| (serialize_color(stop.color), stop.percentage))
+            .collect();
+        let center = [background.center_x, background.center_y];
+        match background.tag {
+            BackgroundTag::Solid => SerializedBackground::Solid {
+                color: serialize_color(background.solid),
+            },
+            BackgroundTag::LinearGradient => SerializedBackground::LinearGradient {
+                angle: background.angle,
+                stops,
+            },
+            BackgroundTag::RadialGradient => SerializedBackground::RadialGradient { center, stops },
+            BackgroundTag::ConicGradient => SerializedBackground::ConicGradient {
+                angle: background.angle,
+                center,
+                stops,
+            },
+        }
+    }
+}
+
+/// Serialized bounds, in device pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializedBounds {
+    /// The position of the left edge.
+    pub x: f32,
+    /// The position of the top edge.
+    pub y: f32,
+    /// The width.
+    pub width: f32,
+    /// The height.
+    pub height: f32,
+}
+
+impl From<Bounds<ScaledPixels>> for SerializedBounds {
+    fn from(bounds: Bounds<ScaledPixels>) -> Self {
+        Self {
+            x: bounds.origin.x.0,
+            y: bounds.origin.y.0,
+            width: bounds.size.width.0,
+            height: bounds.size.height.0,
+        }
+    }
+}
+
+impl From<SerializedBounds> for Bounds<ScaledPixels> {
+    fn from(bounds: SerializedBounds) -> Self {
+        Bounds {
+            origin: point(ScaledPixels(bounds.x), ScaledPixels(bounds.y)),
+            size: Size {
+                width: ScaledPixels(bounds.width),
+                height: ScaledPixels(bounds.height),
+            },
+        }
+    }
+}
+
+fn serialize_color(color: Hsla) -> [f32; 4] {
+    [color.h, color.s, color.l, color.a]
+}
+
+fn serialize_corners(corners: &Corners<ScaledPixels>) -> [f32; 4] {
+    [
+        corners.top_left.0,
+        corners.top_right.0,
+        corners.bottom_right.0,
+        corners.bottom_left.0,
+    ]
+}
+
+fn serialize_transformation(transformation: &TransformationMatrix) -> Option<[f32; 6]> {
+    if *transformation == TransformationMatrix::unit() {
+        return None;
+    }
+    let [[a, b], [c, d]] = transformation.rotation_scale;
+    let [x, y] = transformation.translation;
+    Some([a, b, c, d, x, y])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::Negate as _, px, radians, size};
+    use std::f32::consts::PI;
+
+    fn round_bounds(bounds: Bounds<ScaledPixels>) -> Bounds<ScaledPixels> {
+        bounds
+            .map_origin(|origin| ScaledPixels(origin.0.round()))
+            .map_size(|size| ScaledPixels(size.0.round()))
+    }
+
+    #[test]
+    fn test_transform_bounds() {
+        let center = point(ScaledPixels(10.), ScaledPixels(10.));
+        let bounds = Bounds {
+            origin: center,
+            size: size(ScaledPixels(20.), ScaledPixels(10.)),
+        };
+
+        let rotation = TransformationMatrix::unit()
+            .translate(center)
+            .rotate(radians(PI / 2.))
+            .translate(center.negate());
+        assert_eq!(
+            round_bounds(rotation.transform_bounds(bounds)),
+            Bounds {
+                origin: point(ScaledPixels(0.), ScaledPixels(10.)),
+                size: size(ScaledPixels(10.), ScaledPixels(20.)),
+            }
+        );
+
+        let scale = TransformationMatrix::unit().scale(size(2., 0.5));
+        assert_eq!(
+            round_bounds(scale.transform_bounds(bounds)),
+            Bounds {
+                origin: point(ScaledPixels(20.), ScaledPixels(5.)),
+                size: size(ScaledPixels(40.), ScaledPixels(5.)),
+            }
+        );
+
+        assert_eq!(
+            TransformationMatrix::unit().transform_bounds(bounds),
+            bounds
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let transformation = TransformationMatrix::unit()
+            .translate(point(ScaledPixels(5.), ScaledPixels(-3.)))
+            .rotate(radians(0.7))
+            .scale(size(2., 3.));
+        let inverse = transformation.inverse().unwrap();
+        let position = point(px(12.), px(7.));
+        let round_trip = inverse.apply(transformation.apply(position));
+        assert!((round_trip.x - position.x).abs() < px(0.001));
+        assert!((round_trip.y - position.y).abs() < px(0.001));
+
+        assert_eq!(
+            TransformationMatrix::unit().scale(size(0., 1.)).inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_opacity_groups() {
+        fn quad(x: f32, width: f32) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(width), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad(0., 10.));
+        scene.push_opacity_group(0.5);
+        scene.insert_primitive(quad(5., 10.5));
+        scene.insert_primitive(quad(10., 10.));
+        scene.push_opacity_group(0.25);
+        scene.insert_primitive(quad(12., 2.));
+        scene.pop_opacity_group();
+        scene.pop_opacity_group();
+        scene.push_opacity_group(0.5);
+        scene.pop_opacity_group();
+        scene.finish();
+
+        assert_eq!(scene.quads.len(), 1);
+        assert_eq!(scene.opacity_group_count(), 2);
+        assert_eq!(scene.opacity_groups.len(), 1);
+        let group = &scene.opacity_groups[0];
+        assert_eq!(group.id, 1);
+        assert_eq!(group.opacity, 0.5);
+        assert_eq!(
+            group.bounds,
+            Bounds {
+                origin: point(ScaledPixels(5.), ScaledPixels(0.)),
+                size: size(ScaledPixels(15.), ScaledPixels(10.)),
+            }
+        );
+        assert_eq!(group.scene.quads.len(), 2);
+        assert_eq!(group.scene.opacity_groups.len(), 1);
+        assert_eq!(group.scene.opacity_groups[0].id, 0);
+        assert_eq!(group.scene.opacity_groups[0].scene.quads.len(), 1);
+        assert!(group.order > scene.quads[0].order);
+
+        let mut replayed = Scene::default();
+        replayed.replay(0..scene.len(), &scene);
+        replayed.finish();
+        assert_eq!(replayed.quads.len(), 1);
+        assert_eq!(replayed.opacity_group_count(), 2);
+        assert_eq!(replayed.opacity_groups[0].scene.quads.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_groups() {
+        fn quad(x: f32) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+        fn scene(first_x: f32, second_x: f32) -> Scene {
+            let mut scene = Scene::default();
+            scene.push_cached_group(1, 0);
+            scene.insert_primitive(quad(first_x));
+            scene.pop_opacity_group();
+            scene.push_cached_group(2, 1);
+            scene.insert_primitive(quad(second_x));
+            scene.pop_opacity_group();
+            // The slot is taken, so this group is only drawn to a texture of its own.
+            scene.push_cached_group(3, 1);
+            scene.insert_primitive(quad(40.));
+            scene.pop_opacity_group();
+            scene.finish();
+            scene
+        }
+        fn unchanged(scene: &Scene) -> Vec<Option<bool>> {
+            scene
+                .opacity_groups
+                .iter()
+                .map(|group| group.cache.map(|cache| cache.unchanged))
+                .collect()
+        }
+
+        let previous = scene(0., 20.);
+        assert_eq!(previous.cached_group_slots, [0, 1]);
+
+        let mut next = scene(0., 25.);
+        next.reuse_cached_groups(&previous);
+        assert_eq!(unchanged(&next), [Some(true), Some(false), None]);
+
+        let mut replayed = Scene::default();
+        replayed.replay(0..next.len(), &next);
+        replayed.finish();
+        replayed.reuse_cached_groups(&next);
+        assert_eq!(unchanged(&replayed), [Some(true), Some(true), None]);
+    }
+
+    #[test]
+    fn test_occlusion_culling() {
+        fn quad(x: f32, width: f32, color: Hsla) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(width), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: color.into(),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad(0., 10., crate::red()));
+        scene.insert_primitive(quad(20., 10., crate::red()));
+        scene.insert_primitive(quad(40., 10., crate::red()));
+        // Covers the first quad entirely and the second one partially.
+        scene.insert_primitive(quad(0., 25., crate::blue()));
+        // Translucent quads don't hide anything.
+        scene.insert_primitive(quad(35., 20., crate::blue().opacity(0.5)));
+        scene.finish();
+
+        assert_eq!(
+            scene
+                .quads
+                .iter()
+                .map(|quad| quad.bounds.origin.x.0)
+                .collect::<Vec<_>>(),
+            vec![20., 40., 0., 35.]
+        );
+
+        // Primitives in the same layer share an order, and are drawn in the order they're
+        // inserted, so they don't hide each other.
+        let mut scene = Scene::default();
+        scene.push_layer(quad(0., 50., crate::red()).bounds);
+        scene.insert_primitive(quad(0., 10., crate::red()));
+        scene.insert_primitive(quad(0., 20., crate::blue()));
+        scene.pop_layer();
+        scene.finish();
+        assert_eq!(scene.quads.len(), 2);
+    }
+
+    #[test]
+    fn test_solid_quad_batches() {
+        fn quad(ix: usize, corner_radius: f32) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(ix as f32 * 20.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: crate::red().into(),
+                corner_radii: Corners::all(ScaledPixels(corner_radius)),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        // Long runs of solid quads are batched on their own, and short ones with the others.
+        let mut scene = Scene::default();
+        for ix in 0..MIN_SOLID_QUAD_RUN {
+            scene.insert_primitive(quad(ix, 0.));
+        }
+        scene.insert_primitive(quad(MIN_SOLID_QUAD_RUN, 2.));
+        for ix in 1..4 {
+            scene.insert_primitive(quad(MIN_SOLID_QUAD_RUN + ix, 0.));
+        }
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::SolidQuads(quads) => ("solid", quads.len()),
+                PrimitiveBatch::Quads(quads) => ("quads", quads.len()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![("solid", MIN_SOLID_QUAD_RUN), ("quads", 4)]);
+    }
+
+    #[test]
+    fn test_blend_mode_batches() {
+        fn quad(ix: usize, blend_mode: BlendMode) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(ix as f32 * 20.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: crate::red().opacity(0.5).into(),
+                transformation: TransformationMatrix::unit(),
+                blend_mode,
+                ..Default::default()
+            }
+        }
+
+        // Quads are only batched with those blended in the same mode.
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad(0, BlendMode::Normal));
+        scene.insert_primitive(quad(1, BlendMode::Normal));
+        scene.insert_primitive(quad(2, BlendMode::Multiply));
+        scene.insert_primitive(quad(3, BlendMode::Multiply));
+        scene.insert_primitive(quad(4, BlendMode::Normal));
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::Quads(quads) => (quads[0].blend_mode, quads.len()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            batches,
+            vec![
+                (BlendMode::Normal, 2),
+                (BlendMode::Multiply, 2),
+                (BlendMode::Normal, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sprite_batches_across_atlas_textures() {
+        fn sprite(ix: usize, texture_index: u32) -> MonochromeSprite {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(ix as f32 * 20.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            MonochromeSprite {
+                order: 0,
+                blend_mode: BlendMode::Normal,
+                bounds,
+                content_mask: ContentMask { bounds },
+                color: crate::black(),
+                tile: AtlasTile {
+                    texture_id: AtlasTextureId {
+                        index: texture_index,
+                        kind: AtlasTextureKind::Monochrome,
+                    },
+                    tile_id: TileId(ix as u32),
+                    padding: 0,
+                    bounds: Bounds::default(),
+                },
+                transformation: TransformationMatrix::unit(),
+            }
+        }
+
+        // Sprites from textures bound together share a batch, even when they alternate.
+        let per_batch = AtlasTextureId::PER_BATCH;
+        let mut scene = Scene::default();
+        scene.insert_primitive(sprite(0, 0));
+        scene.insert_primitive(sprite(1, 1));
+        scene.insert_primitive(sprite(2, 0));
+        scene.insert_primitive(sprite(3, per_batch - 1));
+        scene.insert_primitive(sprite(4, per_batch));
+        scene.insert_primitive(sprite(5, per_batch + 1));
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::MonochromeSprites {
+                    base_texture_id,
+                    sprites,
+                } => (base_texture_id.index, sprites.len()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![(0, 4), (per_batch, 2)]);
+    }
+
+    #[test]
+    fn test_path_edge_fringes() {
+        fn fringe_corners(path: &Path<Pixels>) -> Vec<Point<Pixels>> {
+            path.vertices
+                .iter()
+                .filter(|vertex| vertex.st_position == point(1., -1.))
+                .map(|vertex| vertex.xy_position)
+                .collect()
+        }
+
+        let mut path = Path::new(point(px(0.), px(0.)));
+        path.line_to(point(px(10.), px(0.)));
+        path.line_to(point(px(0.), px(10.)));
+
+        // Each edge has a fringe, including the one closing the triangle, with its left side
+        // outside the triangle.
+        let corners = fringe_corners(&path);
+        assert_eq!(corners.len(), 9);
+        assert!(corners.contains(&point(px(0.), px(-1.))));
+        assert!(corners.contains(&point(px(-1.), px(10.))));
+        assert_eq!(path.bounds.origin, point(px(-1.), px(-1.)));
+
+        // The closing fringe moves as the contour grows, and curves anti-alias themselves.
+        path.curve_to(point(px(-10.), px(0.)), point(px(-10.), px(10.)));
+        let corners = fringe_corners(&path);
+        assert_eq!(corners.len(), 9);
+        assert!(!corners.contains(&point(px(-1.), px(10.))));
+        assert!(corners.contains(&point(px(-10.), px(-1.))));
+
+        // Paths that only differ in their fill rule don't paint the same.
+        let path = path.scale(1.);
+        let mut nonzero_path = path.clone();
+        nonzero_path.nonzero = true;
+        assert!(path.paints_same_as(&path.clone()));
+        assert!(!path.paints_same_as(&nonzero_path));
+    }
+
+    #[test]
+    fn test_custom_primitive_batches() {
+        fn custom_primitive(ix: usize, shader_id: u32) -> CustomPrimitive {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(ix as f32 * 20.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            CustomPrimitive {
+                order: 0,
+                shader_id: CustomShaderId(shader_id),
+                bounds,
+                content_mask: ContentMask { bounds },
+                uniforms: [ix as f32; MAX_CUSTOM_SHADER_UNIFORMS],
+            }
+        }
+
+        // Primitives are batched with the next ones drawn with the same shader.
+        let mut scene = Scene::default();
+        for (ix, shader_id) in [0, 0, 1, 1, 1, 0].into_iter().enumerate() {
+            scene.insert_primitive(custom_primitive(ix, shader_id));
+        }
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::Custom {
+                    shader_id,
+                    primitives,
+                } => (shader_id.0, primitives.len()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![(0, 2), (1, 3), (0, 1)]);
+        assert_eq!(scene.stats().batch_breaks.shader_change, 2);
+    }
+
+    #[test]
+    fn test_z_index() {
+        fn quad(x: f32) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: crate::red().into(),
+                corner_radii: Corners::all(ScaledPixels(2.)),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        // The quad inserted first is drawn last because of its z-index, even within a layer.
+        let mut scene = Scene::default();
+        scene.push_layer(quad(0.).bounds.union(&quad(10.).bounds));
+        scene.push_z_index(1);
+        scene.insert_primitive(quad(0.));
+        scene.pop_z_index();
+        scene.insert_primitive(quad(5.));
+        scene.pop_layer();
+        scene.insert_primitive(quad(10.));
+        scene.finish();
+
+        let quads = scene
+            .quads
+            .iter()
+            .map(|quad| quad.bounds.origin.x.0)
+            .collect::<Vec<_>>();
+        assert_eq!(quads, vec![5., 10., 0.]);
+        assert!(scene.quads[2].order > scene.quads[1].order);
+    }
+
+    #[test]
+    fn test_stats() {
+        fn bounds(x: f32) -> Bounds<ScaledPixels> {
+            Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            }
+        }
+
+        fn quad(x: f32) -> Quad {
+            Quad {
+                bounds: bounds(x),
+                content_mask: ContentMask { bounds: bounds(x) },
+                background: crate::red().into(),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        // The shadow overlaps both quads, so they can't be drawn in the same batch.
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad(0.));
+        scene.insert_primitive(Shadow {
+            order: 0,
+            blur_radius: ScaledPixels(0.),
+            bounds: bounds(5.),
+            corner_radii: Corners::default(),
+            content_mask: ContentMask { bounds: bounds(5.) },
+            color: crate::black(),
+            transformation: TransformationMatrix::unit(),
+            inset_offset: Point::default(),
+            inset_spread_radius: ScaledPixels(0.),
+            inset: false,
+        });
+        scene.insert_primitive(quad(10.));
+        scene.finish();
+
+        let stats = scene.stats();
+        assert_eq!(stats.primitive_counts.quads, 2);
+        assert_eq!(stats.primitive_counts.shadows, 1);
+        assert_eq!(stats.primitive_counts.total(), 3);
+        assert_eq!(stats.batch_count, 3);
+        assert_eq!(
+            stats.batch_breaks,
+            BatchBreaks {
+                kind_change: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            stats.instance_bytes,
+            2 * mem::size_of::<Quad>() + mem::size_of::<Shadow>()
+        );
+        assert!(stats.bounds_tree_depth >= 2);
+    }
+
+    #[test]
+    fn test_insert_shadows() {
+        fn bounds(x: f32) -> Bounds<ScaledPixels> {
+            Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            }
+        }
+
+        fn shadow(x: f32) -> Shadow {
+            Shadow {
+                order: 0,
+                blur_radius: ScaledPixels(0.),
+                bounds: bounds(x),
+                corner_radii: Corners::default(),
+                content_mask: ContentMask { bounds: bounds(x) },
+                color: crate::black(),
+                transformation: TransformationMatrix::unit(),
+                inset_offset: Point::default(),
+                inset_spread_radius: ScaledPixels(0.),
+                inset: false,
+            }
+        }
+
+        let quad = Quad {
+            bounds: bounds(12.),
+            content_mask: ContentMask {
+                bounds: bounds(12.),
+            },
+            background: crate::red().into(),
+            transformation: TransformationMatrix::unit(),
+            ..Default::default()
+        };
+
+        // Only the second shadow overlaps the quad, so on their own the shadows are drawn in
+        // separate batches, before and after it.
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad.clone());
+        scene.insert_primitive(shadow(0.));
+        scene.insert_primitive(shadow(5.));
+        scene.finish();
+        assert_eq!(scene.stats().batch_count, 3);
+
+        // As a group, they're both drawn after the quad in a single batch, in the given order.
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad);
+        scene.insert_shadows([shadow(0.), shadow(5.)]);
+        scene.finish();
+        assert_eq!(scene.stats().batch_count, 2);
+        assert_eq!(scene.shadows[0].order, scene.shadows[1].order);
+        assert!(scene.shadows[0].order > scene.quads[0].order);
+        assert_eq!(
+            scene
+                .shadows
+                .iter()
+                .map(|shadow| shadow.bounds.origin.x.0)
+                .collect::<Vec<_>>(),
+            vec![0., 5.]
+        );
+    }
+
+    #[test]
+    fn test_reuse_group_scenes() {
+        let bounds = Bounds {
+            origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+            size: size(ScaledPixels(10.), ScaledPixels(10.)),
+        };
+        let quad = Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            transformation: TransformationMatrix::unit(),
+            ..Default::default()
+        };
+
+        let mut scene = Scene::default();
+        let mut group_quads = Vec::new();
+        for _ in 0..2 {
+            scene.clear();
+            scene.push_opacity_group(0.5);
+            scene.insert_primitive(quad.clone());
+            scene.pop_opacity_group();
+            scene.finish();
+            group_quads.push(scene.opacity_groups[0].scene.quads.as_ptr());
+        }
+        assert_eq!(group_quads[0], group_quads[1]);
+
+        // A group that is still referenced after the frame isn't reused.
+        let previous_group = scene.opacity_groups[0].scene.clone();
+        scene.clear();
+        scene.push_opacity_group(0.5);
+        scene.insert_primitive(quad);
+        scene.pop_opacity_group();
+        scene.finish();
+        assert!(!Arc::ptr_eq(
+            &previous_group,
+            &scene.opacity_groups[0].scene
+        ));
+        assert_eq!(previous_group.quads.len(), 1);
+    }
+
+    #[test]
+    fn test_diff() {
+        fn quad(x: f32, color: Hsla) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.5)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: color.into(),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        fn scene(quads: &[Quad]) -> Scene {
+            let mut scene = Scene::default();
+            for quad in quads {
+                scene.insert_primitive(quad.clone());
+            }
+            scene.finish();
+            scene
+        }
+
+        let previous = scene(&[quad(0., crate::red()), quad(20., crate::red())]);
+        assert_eq!(
+            scene(&[quad(0., crate::red()), quad(20., crate::red())]).diff(&previous),
+            Vec::new()
+        );
+
+        // Painting the same quads in another order doesn't damage anything.
+        assert_eq!(
+            scene(&[quad(20., crate::red()), quad(0., crate::red())]).diff(&previous),
+            Vec::new()
+        );
+
+        // A changed quad damages its bounds, rounded out to whole pixels.
+        assert_eq!(
+            scene(&[quad(0., crate::red()), quad(20., crate::blue())]).diff(&previous),
+            vec![Bounds {
+                origin: point(ScaledPixels(20.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(11.)),
+            }]
+        );
+
+        // A moved quad damages where it was and where it is, merging the regions that overlap.
+        assert_eq!(
+            scene(&[quad(5., crate::red()), quad(20., crate::red())]).diff(&previous),
+            vec![Bounds {
+                origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+                size: size(ScaledPixels(15.), ScaledPixels(11.)),
+            }]
+        );
+
+        // Removed quads damage their bounds too.
+        let mut damage = scene(&[]).diff(&previous);
+        damage.sort_by(|a, b| a.origin.x.partial_cmp(&b.origin.x).unwrap());
+        assert_eq!(
+            damage,
+            vec![
+                Bounds {
+                    origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+                    size: size(ScaledPixels(10.), ScaledPixels(11.)),
+                },
+                Bounds {
+                    origin: point(ScaledPixels(20.), ScaledPixels(0.)),
+                    size: size(ScaledPixels(10.), ScaledPixels(11.)),
+                },
+            ]
+        );
+
+        // Quads painted at the same bounds are matched one for one.
+        let previous = scene(&[quad(0., crate::red()), quad(0., crate::blue())]);
+        assert_eq!(
+            scene(&[quad(0., crate::blue()), quad(0., crate::red())]).diff(&previous),
+            Vec::new()
+        );
+        assert_eq!(
+            scene(&[quad(0., crate::blue()), quad(0., crate::blue())]).diff(&previous),
+            vec![Bounds {
+                origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(11.)),
+            }]
+        );
+
+        // Past a limit, all of the damage is merged into one region.
+        let quads = (0..=MAX_DAMAGE_REGIONS)
+            .map(|ix| quad(ix as f32 * 20., crate::red()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scene(&quads).diff(&scene(&[])),
+            vec![Bounds {
+                origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+                size: size(
+                    ScaledPixels(MAX_DAMAGE_REGIONS as f32 * 20. + 10.),
+                    ScaledPixels(11.)
+                ),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let bounds = Bounds {
+            origin: point(ScaledPixels(0.), ScaledPixels(0.)),
+            size: size(ScaledPixels(10.), ScaledPixels(10.)),
+        };
+        let quad = Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            background: crate::red().into(),
+            transformation: TransformationMatrix::unit(),
+            ..Default::default()
+        };
+
+        let mut scene = Scene::default();
+        scene.push_opacity_group(0.5);
+        scene.insert_primitive(quad);
+        scene.pop_opacity_group();
+        scene.insert_primitive(Underline {
+            order: 0,
+            kind: UnderlineKind::Single,
+            bounds,
+            content_mask: ContentMask { bounds },
+            color: crate::black(),
+            thickness: ScaledPixels(1.),
+            wavelength: ScaledPixels(0.),
+            transformation: TransformationMatrix::unit()
+                .translate(point(ScaledPixels(5.), ScaledPixels(0.))),
+            dash: ScaledPixels(0.),
+            dash_gap: ScaledPixels(0.),
+            dash_offset: ScaledPixels(0.),
+            wave_amplitude: ScaledPixels(0.),
+        });
+        scene.finish();
+
+        let serialized = scene.serialize();
+        let expected_bounds = SerializedBounds {
+            x: 0.,
+            y: 0.,
+            width: 10.,
+            height: 10.,
+        };
+        assert_eq!(
+            serialized,
+            SerializedScene {
+                primitives: vec![
+                    SerializedPrimitive {
+                        order: 1,
+                        bounds: expected_bounds,
+                        content_mask: expected_bounds,
+                        transformation: None,
+                        kind: SerializedPrimitiveKind::OpacityGroup {
+                            opacity: 0.5,
+                            primitives: vec![SerializedPrimitive {
+                                order: 1,
+                                bounds: expected_bounds,
+                                content_mask: expected_bounds,
+                                transformation: None,
+                                kind: SerializedPrimitiveKind::Quad {
+                                    background: SerializedBackground::Solid {
+                                        color: [0., 1., 0.5, 1.],
+                                    },
+                                    border_color: [0.; 4],
+                                    border_widths: [0.; 4],
+                                    corner_radii: [0.; 4],
+                                    blend_mode: BlendMode::Normal,
+                                },
+                            }],
+                        },
+                    },
+                    SerializedPrimitive {
+                        order: 2,
+                        bounds: expected_bounds,
+                        content_mask: expected_bounds,
+                        transformation: Some([1., 0., 0., 1., 5., 0.]),
+                        kind: SerializedPrimitiveKind::Underline {
+                            color: [0., 0., 0., 1.],
+                            thickness: 1.,
+                            kind: UnderlineKind::Single,
+                            wave: [0.; 2],
+                            dashes: [0.; 3],
+                        },
+                    },
+                ],
+            }
+        );
+
+        let json = serde_json::to_value(&serialized).unwrap();
+        assert_eq!(json["primitives"][0]["kind"], "opacity_group");
+        assert_eq!(json["primitives"][0]["primitives"][0]["kind"], "quad");
+        assert_eq!(
+            json["primitives"][0]["primitives"][0]["background"]["kind"],
+            "solid"
+        );
+        let deserialized: SerializedScene = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, serialized);
+    }
+
+    #[test]
+    fn test_primitives_at() {
+        fn quad(x: f32) -> Quad {
+            let bounds = Bounds {
+                origin: point(ScaledPixels(x), ScaledPixels(0.)),
+                size: size(ScaledPixels(10.), ScaledPixels(10.)),
+            };
+            Quad {
+                bounds,
+                content_mask: ContentMask { bounds },
+                background: crate::red().into(),
+                corner_radii: Corners::all(ScaledPixels(2.)),
+                transformation: TransformationMatrix::unit(),
+                ..Default::default()
+            }
+        }
+
+        let mut scene = Scene::default();
+        scene.insert_primitive(quad(0.));
+        scene.insert_primitive(quad(5.));
+        scene.push_layer(quad(30.).bounds.union(&quad(45.).bounds));
+        scene.insert_primitive(quad(30.));
+        scene.insert_primitive(quad(45.));
+        scene.pop_layer();
+        scene.push_z_index(1);
+        scene.insert_primitive(quad(100.));
+        scene.pop_z_index();
+        scene.insert_primitive(quad(105.));
+        scene.finish();
+
+        let hits = |x: f32| {
+            scene
+                .primitives_at(point(ScaledPixels(x), ScaledPixels(5.)))
+                .into_iter()
+                .map(|primitive| primitive.bounds.x)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hits(7.), vec![5., 0.]);
+        assert_eq!(hits(2.), vec![0.]);
+        // Primitives painted in a layer are only hit within their own bounds.
+        assert_eq!(hits(32.), vec![30.]);
+        assert_eq!(hits(42.), Vec::<f32>::new());
+        // Primitives with a higher z-index are hit first.
+        assert_eq!(hits(107.), vec![100., 105.]);
+        assert_eq!(hits(200.), Vec::<f32>::new());
+    }
+}
@@ -0,0 +1,960 @@
+//! Recording the scenes a window draws, so that they can be replayed through the renderer without
+//! the application that painted them. See
+//! [`WindowContext::record_scenes`](crate::WindowContext::record_scenes) and
+//! [`WindowContext::paint_recorded_frame`](crate::WindowContext::paint_recorded_frame).
+
+use super::{
+    serialize_color, serialize_corners, serialize_transformation, BackdropBlur, MonochromeSprite,
+    PaintOperation, Path, PathId, PathVertex, PolychromeSprite, Primitive, Quad, Scene,
+    SerializedBackground, SerializedBounds, Shadow, TransformationMatrix, Underline,
+};
+use crate::{
+    point, size, AtlasKey, AtlasStats, AtlasTextureId, AtlasTextureKind, AtlasTile, Background,
+    BackgroundTag, BlendMode, Bounds, ContentMask, Corners, DevicePixels, Edges, GlyphOutline,
+    Hsla, LinearColorStop, PlatformAtlas, ScaledPixels, Size, TileId, UnderlineKind,
+    MAX_GRADIENT_STOPS,
+};
+use anyhow::{Context as _, Result};
+use collections::FxHashMap;
+use parking_lot::Mutex;
+use seahash::SeaHasher;
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fs::File,
+    hash::Hasher,
+    io::{BufReader, BufWriter},
+    path::Path as FsPath,
+    sync::Arc,
+};
+
+/// The frames drawn by a window, along with the glyphs, SVGs and images they draw, which can be
+/// saved to a file and replayed on another machine.
+///
+/// Platform surfaces and primitives drawn with custom shaders depend on the application that
+/// drew them, so they aren't recorded.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneRecording {
+    /// The version of the format the recording was saved in.
+    pub version: u32,
+    /// The recorded frames, in the order they were drawn.
+    pub frames: Vec<RecordedFrame>,
+    /// The rasterized glyphs, SVGs and images the frames' sprites draw.
+    pub tiles: Vec<RecordedTile>,
+}
+
+impl SceneRecording {
+    /// The version of the recording format, bumped whenever older recordings can't be replayed.
+    pub const VERSION: u32 = 1;
+
+    /// Load a recording saved with [`SceneRecording::save`].
+    pub fn load(path: &FsPath) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("opening scene recording {}", path.display()))?;
+        let recording: Self = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("parsing scene recording {}", path.display()))?;
+        anyhow::ensure!(
+            recording.version == Self::VERSION,
+            "scene recording {} has version {}, but only version {} can be replayed",
+            path.display(),
+            recording.version,
+            Self::VERSION
+        );
+        Ok(recording)
+    }
+
+    /// Save the recording to a file.
+    pub fn save(&self, path: &FsPath) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("creating scene recording {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self)
+            .with_context(|| format!("writing scene recording {}", path.display()))
+    }
+}
+
+/// A frame drawn by a window.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// The size of the window's contents, in device pixels.
+    pub viewport_size: [f32; 2],
+    /// The scale factor the frame was drawn at.
+    pub scale_factor: f32,
+    /// What was painted, in the order it was painted in.
+    pub operations: Vec<RecordedOperation>,
+}
+
+/// Something painted into a recorded frame. Positions and sizes are in device pixels.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum RecordedOperation {
+    /// A primitive was painted.
+    Primitive(RecordedPrimitive),
+    /// Until the layer ends, primitives are drawn in the same order, above what the layer's
+    /// bounds overlap.
+    StartLayer {
+        /// The bounds of the layer.
+        bounds: SerializedBounds,
+    },
+    /// The innermost layer ended.
+    EndLayer,
+    /// Until it ends, primitives are painted at the given z-index.
+    StartZIndex {
+        /// The z-index.
+        z_index: u8,
+    },
+    /// The innermost z-index ended.
+    EndZIndex,
+    /// Until it ends, primitives are composited together at the given opacity.
+    StartOpacityGroup {
+        /// The opacity the group is composited at.
+        opacity: f32,
+    },
+    /// The innermost opacity group ended.
+    EndOpacityGroup,
+}
+
+/// A primitive painted into a recorded frame. Colors are given as hue, saturation, lightness and
+/// alpha, corner radii clockwise from the top left corner, and edges clockwise from the top.
+/// Transformations are given as a row-major 2x2 rotation and scale matrix followed by the
+/// translation, and omitted when there are none.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedPrimitive {
+    /// A drop shadow.
+    Shadow {
+        /// The bounds of the shape casting the shadow.
+        bounds: SerializedBounds,
+        /// The bounds the shadow is clipped to.
+        content_mask: SerializedBounds,
+        /// The transformation applied to the shadow.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transformation: Option<[f32; 6]>,
+        /// The radii of the shadow's corners.
+        corner_radii: [f32; 4],
+        /// How far the shadow is blurred.
+        blur_radius: f32,
+        /// The color of the shadow.
+        color: [f32; 4],
+        /// For inset shadows, the offset and spread radius of the shape casting the shadow.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        inset: Option<[f32; 3]>,
+    },
+    /// A blur of what was painted behind it.
+    BackdropBlur {
+        /// The bounds of the blurred area.
+        bounds: SerializedBounds,
+        /// The bounds the blur is clipped to.
+        content_mask: SerializedBounds,
+        /// The radii of the blurred area's corners.
+        corner_radii: [f32; 4],
+        /// How far what's behind the area is blurred.
+        blur_radius: f32,
+    },
+    /// A rectangle with optional borders and rounded corners.
+    Quad {
+        /// The bounds of the quad.
+        bounds: SerializedBounds,
+        /// The bounds the quad is clipped to.
+        content_mask: SerializedBounds,
+        /// The transformation applied to the quad.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transformation: Option<[f32; 6]>,
+        /// The fill of the quad.
+        background: SerializedBackground,
+        /// The color of the quad's borders.
+        border_color: [f32; 4],
+        /// The widths of the quad's borders.
+        border_widths: [f32; 4],
+        /// The radii of the quad's corners.
+        corner_radii: [f32; 4],
+        /// How the quad is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+    /// A vector path, made of triangles.
+    Path {
+        /// The bounds of the path.
+        bounds: SerializedBounds,
+        /// The bounds the path is clipped to.
+        content_mask: SerializedBounds,
+        /// The fill of the path.
+        background: SerializedBackground,
+        /// Whether overlapping triangles all fill the path, rather than alternately filling and
+        /// cutting it out.
+        nonzero: bool,
+        /// The vertices of the path's triangles: their position, their position along the
+        /// curve they're part of, and the bounds they're clipped to.
+        vertices: Vec<[f32; 8]>,
+    },
+    /// A straight or wavy underline.
+    Underline {
+        /// The bounds of the underline.
+        bounds: SerializedBounds,
+        /// The bounds the underline is clipped to.
+        content_mask: SerializedBounds,
+        /// The transformation applied to the underline.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transformation: Option<[f32; 6]>,
+        /// The color of the underline.
+        color: [f32; 4],
+        /// How thick the underline is.
+        thickness: f32,
+        /// Whether the underline is a single line, a double line or a row of dots.
+        kind: UnderlineKind,
+        /// The amplitude and wavelength of a wavy underline's wave.
+        wave: [f32; 2],
+        /// The length of the underline's dashes, their gaps and its offset into the pattern.
+        dashes: [f32; 3],
+    },
+    /// A glyph or an icon, tinted with a single color.
+    MonochromeSprite {
+        /// The bounds of the sprite.
+        bounds: SerializedBounds,
+        /// The bounds the sprite is clipped to.
+        content_mask: SerializedBounds,
+        /// The transformation applied to the sprite.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transformation: Option<[f32; 6]>,
+        /// The index of the sprite's tile in [`SceneRecording::tiles`].
+        tile: usize,
+        /// The color of the sprite.
+        color: [f32; 4],
+        /// How the sprite is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+    /// An image, or a glyph with colors of its own such as an emoji.
+    PolychromeSprite {
+        /// The bounds of the sprite.
+        bounds: SerializedBounds,
+        /// The bounds the sprite is clipped to.
+        content_mask: SerializedBounds,
+        /// The transformation applied to the sprite.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transformation: Option<[f32; 6]>,
+        /// The index of the sprite's tile in [`SceneRecording::tiles`].
+        tile: usize,
+        /// The radii of the sprite's corners.
+        corner_radii: [f32; 4],
+        /// How much the sprite is desaturated, from 0 to 1.
+        grayscale: f32,
+        /// How far the hues of the sprite are rotated, in radians.
+        hue_rotate: f32,
+        /// The color the sprite is tinted with.
+        tint: [f32; 4],
+        /// The opacity of the sprite.
+        opacity: f32,
+        /// The widths of the borders kept at a fixed size when the sprite is drawn as a
+        /// nine-slice, and the widths of the same borders within the tile.
+        nine_slice: [[f32; 4]; 2],
+        /// The size each copy of a repeating tile is drawn at, the gap between copies, and
+        /// where a copy starts, relative to the origin of the bounds.
+        repeat: [f32; 6],
+        /// How the sprite is blended with what's beneath it.
+        #[serde(default, skip_serializing_if = "BlendMode::is_normal")]
+        blend_mode: BlendMode,
+    },
+}
+
+/// A glyph, SVG or image, rasterized for the sprites of a recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedTile {
+    /// Whether the tile holds coverage, tinted by the sprites drawing it, or colors.
+    pub kind: RecordedTileKind,
+    /// The width and height of the tile, in device pixels.
+    pub size: [i32; 2],
+    /// A hash of the tile's size and pixels, which identifies it in the atlas it's replayed
+    /// into.
+    pub content_hash: u64,
+    /// The pixels of the tile: one byte of coverage per pixel for monochrome tiles, and four
+    /// bytes of color for polychrome ones.
+    pub bytes: Vec<u8>,
+}
+
+/// The kind of a [`RecordedTile`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedTileKind {
+    /// Coverage, tinted by the sprites drawing the tile.
+    Monochrome,
+    /// Colors.
+    Polychrome,
+}
+
+impl RecordedTile {
+    pub(crate) fn atlas_key(&self) -> AtlasKey {
+        AtlasKey::Recorded {
+            kind: match self.kind {
+                RecordedTileKind::Monochrome => AtlasTextureKind::Monochrome,
+                RecordedTileKind::Polychrome => AtlasTextureKind::Polychrome,
+            },
+            content_hash: self.content_hash,
+        }
+    }
+
+    pub(crate) fn size(&self) -> Size<DevicePixels> {
+        size(DevicePixels(self.size[0]), DevicePixels(self.size[1]))
+    }
+}
+
+/// Records the frames a window draws, until it has drawn as many as were requested.
+pub(crate) struct SceneRecorder {
+    pub(crate) atlas: Arc<RecordingAtlas>,
+    pub(crate) frames: Vec<RecordedFrame>,
+    pub(crate) frames_left: usize,
+    pub(crate) done: futures::channel::oneshot::Sender<SceneRecording>,
+}
+
+impl SceneRecorder {
+    /// Record a finished scene, returning whether all the requested frames have been recorded.
+    pub(crate) fn record_frame(
+        &mut self,
+        scene: &Scene,
+        viewport_size: Size<ScaledPixels>,
+        scale_factor: f32,
+    ) -> bool {
+        let state = self.atlas.state.lock();
+        let operations = scene
+            .paint_operations
+            .iter()
+            .filter_map(|operation| record_operation(operation, &state.tile_indices))
+            .collect();
+        drop(state);
+
+        self.frames.push(RecordedFrame {
+            viewport_size: [viewport_size.width.0, viewport_size.height.0],
+            scale_factor,
+            operations,
+        });
+        self.frames_left = self.frames_left.saturating_sub(1);
+        self.frames_left == 0
+    }
+
+    pub(crate) fn finish(self) {
+        let tiles = std::mem::take(&mut self.atlas.state.lock().tiles);
+        self.done
+            .send(SceneRecording {
+                version: SceneRecording::VERSION,
+                frames: self.frames,
+                tiles,
+            })
+            .ok();
+    }
+}
+
+/// Wraps a window's sprite atlas while its frames are recorded, keeping a copy of the pixels of
+/// every tile inserted into it.
+pub(crate) struct RecordingAtlas {
+    pub(crate) inner: Arc<dyn PlatformAtlas>,
+    state: Mutex<RecordingAtlasState>,
+}
+
+#[derive(Default)]
+struct RecordingAtlasState {
+    tiles: Vec<RecordedTile>,
+    /// The index of the recorded tile each atlas tile currently holds.
+    tile_indices: FxHashMap<(AtlasTextureId, TileId), usize>,
+}
+
+impl RecordingAtlas {
+    pub(crate) fn new(inner: Arc<dyn PlatformAtlas>) -> Self {
+        Self {
+            inner,
+            state: Default::default(),
+        }
+    }
+}
+
+impl PlatformAtlas for RecordingAtlas {
+    fn get_or_insert_with<'a>(
+        &self,
+        key: &AtlasKey,
+        build: &mut dyn FnMut() -> Result<Option<(Size<DevicePixels>, Cow<'a, [u8]>)>>,
+    ) -> Result<Option<AtlasTile>> {
+        let mut built = None;
+        let tile = self.inner.get_or_insert_with(key, &mut || {
+            let result = build()?;
+            if let Some((size, bytes)) = &result {
+                built = Some((*size, bytes.to_vec()));
+            }
+            Ok(result)
+        })?;
+
+        if let (Some(tile), Some((size, bytes))) = (&tile, built) {
+            let kind = match key.texture_kind() {
+                AtlasTextureKind::Polychrome => RecordedTileKind::Polychrome,
+                AtlasTextureKind::Monochrome | AtlasTextureKind::Path => {
+                    RecordedTileKind::Monochrome
+                }
+            };
+            let mut hasher = SeaHasher::new();
+            hasher.write_i32(size.width.0);
+            hasher.write_i32(size.height.0);
+            hasher.write(&bytes);
+            let content_hash = hasher.finish();
+            let mut state = self.state.lock();
+            let index = state.tiles.len();
+            state.tiles.push(RecordedTile {
+                kind,
+                size: [size.width.0, size.height.0],
+                content_hash,
+                bytes,
+            });
+            state
+                .tile_indices
+                .insert((tile.texture_id, tile.tile_id), index);
+        }
+        Ok(tile)
+    }
+
+    fn get_or_insert_outline(
+        &self,
+        _key: &AtlasKey,
+        _build: &mut dyn FnMut() -> Result<Option<GlyphOutline>>,
+    ) -> Result<Option<AtlasTile>> {
+        // Glyphs rasterized on the GPU have no pixels to record, so they're rasterized on the
+        // CPU instead while recording.
+        Ok(None)
+    }
+
+    fn clear(&self) {
+        self.state.lock().tile_indices.clear();
+        self.inner.clear();
+    }
+
+    fn finish_frame(&self, drawn_tiles: &mut dyn Iterator<Item = &AtlasTile>) {
+        self.inner.finish_frame(drawn_tiles);
+    }
+
+    fn stats(&self) -> AtlasStats {
+        self.inner.stats()
+    }
+}
+
+fn record_operation(
+    operation: &PaintOperation,
+    tile_indices: &FxHashMap<(AtlasTextureId, TileId), usize>,
+) -> Option<RecordedOperation> {
+    let tile_index = |tile: &AtlasTile| tile_indices.get(&(tile.texture_id, tile.tile_id)).copied();
+    Some(match operation {
+        PaintOperation::StartLayer(bounds) => RecordedOperation::StartLayer {
+            bounds: (*bounds).into(),
+        },
+        PaintOperation::EndLayer => RecordedOperation::EndLayer,
+        PaintOperation::StartZIndex(z_index) => {
+            RecordedOperation::StartZIndex { z_index: *z_index }
+        }
+        PaintOperation::EndZIndex => RecordedOperation::EndZIndex,
+        PaintOperation::StartOpacityGroup(opacity) => {
+            RecordedOperation::StartOpacityGroup { opacity: *opacity }
+        }
+        // Recordings are replayed without the textures of cached groups, which are drawn like
+        // opaque opacity groups instead.
+        PaintOperation::StartCachedGroup { .. } => {
+            RecordedOperation::StartOpacityGroup { opacity: 1. }
+        }
+        PaintOperation::EndOpacityGroup => RecordedOperation::EndOpacityGroup,
+        PaintOperation::Primitive(primitive) => {
+            RecordedOperation::Primitive(match primitive {
+                Primitive::Shadow(shadow) => RecordedPrimitive::Shadow {
+                    bounds: shadow.bounds.into(),
+                    content_mask: shadow.content_mask.bounds.into(),
+                    transformation: serialize_transformation(&shadow.transformation),
+                    corner_radii: serialize_corners(&shadow.corner_radii),
+                    blur_radius: shadow.blur_radius.0,
+                    color: serialize_color(shadow.color),
+                    inset: shadow.inset.then(|| {
+                        [
+                            shadow.inset_offset.x.0,
+                            shadow.inset_offset.y.0,
+                            shadow.inset_spread_radius.0,
+                        ]
+                    }),
+                },
+                Primitive::BackdropBlur(blur) => RecordedPrimitive::BackdropBlur {
+                    bounds: blur.bounds.into(),
+                    content_mask: blur.content_mask.bounds.into(),
+                    corner_radii: serialize_corners(&blur.corner_radii),
+                    blur_radius: blur.blur_radius.0,
+                },
+                Primitive::Quad(quad) => RecordedPrimitive::Quad {
+                    bounds: quad.bounds.into(),
+                    content_mask: quad.content_mask.bounds.into(),
+                    transformation: serialize_transformation(&quad.transformation),
+                    background: (&quad.background).into(),
+                    border_color: serialize_color(quad.border_color),
+                    border_widths: serialize_edges(&quad.border_widths),
+                    corner_radii: serialize_corners(&quad.corner_radii),
+                    blend_mode: quad.blend_mode,
+                },
+                Primitive::Path(path) => RecordedPrimitive::Path {
+                    bounds: path.bounds.into(),
+                    content_mask: path.content_mask.bounds.into(),
+                    background: (&path.color).into(),
+                    nonzero: path.nonzero,
+                    vertices: path
+                        .vertices
+                        .iter()
+                        .map(|vertex| {
+                            let mask = vertex.content_mask.bounds;
+                            [
+                                vertex.xy_position.x.0,
+                                vertex.xy_position.y.0,
+                                vertex.st_position.x,
+                                vertex.st_position.y,
+                                mask.origin.x.0,
+                                mask.origin.y.0,
+                                mask.size.width.0,
+                                mask.size.height.0,
+                            ]
+                        })
+                        .collect(),
+                },
+                Primitive::Underline(underline) => RecordedPrimitive::Underline {
+                    bounds: underline.bounds.into(),
+                    content_mask: underline.content_mask.bounds.into(),
+                    transformation: serialize_transformation(&underline.transformation),
+                    color: serialize_color(underline.color),
+                    thickness: underline.thickness.0,
+                    kind: underline.kind,
+                    wave: [underline.wave_amplitude.0, underline.wavelength.0],
+                    dashes: [
+                        underline.dash.0,
+                        underline.dash_gap.0,
+                        underline.dash_offset.0,
+                    ],
+                },
+                Primitive::MonochromeSprite(sprite) => RecordedPrimitive::MonochromeSprite {
+                    bounds: sprite.bounds.into(),
+                    content_mask: sprite.content_mask.bounds.into(),
+                    transformation: serialize_transformation(&sprite.transformation),
+                    tile: tile_index(&sprite.tile)?,
+                    color: serialize_color(sprite.color),
+                    blend_mode: sprite.blend_mode,
+                },
+                Primitive::PolychromeSprite(sprite) => RecordedPrimitive::PolychromeSprite {
+                    bounds: sprite.bounds.into(),
+                    content_mask: sprite.content_mask.bounds.into(),
+                    transformation: serialize_transformation(&sprite.transformation),
+                    tile: tile_index(&sprite.tile)?,
+                    corner_radii: serialize_corners(&sprite.corner_radii),
+                    grayscale: sprite.grayscale,
+                    hue_rotate: sprite.hue_rotate,
+                    tint: serialize_color(sprite.tint),
+                    opacity: sprite.opacity,
+                    nine_slice: [
+                        serialize_edges(&sprite.nine_slice),
+                        serialize_edges(&sprite.tile_nine_slice),
+                    ],
+                    repeat: [
+                        sprite.repeat_size.width.0,
+                        sprite.repeat_size.height.0,
+                        sprite.repeat_spacing.width.0,
+                        sprite.repeat_spacing.height.0,
+                        sprite.repeat_origin.x.0,
+                        sprite.repeat_origin.y.0,
+                    ],
+                    blend_mode: sprite.blend_mode,
+                },
+                // Surfaces and custom shaders belong to the application. Opacity groups are
+                // recorded through the operations that start and end them.
+                Primitive::Surface(_) | Primitive::Custom(_) | Primitive::OpacityGroup(_) => {
+                    return None
+                }
+            })
+        }
+    })
+}
+
+impl Scene {
+    /// Paint the operations of a recorded frame, looking up the atlas tiles of sprites with the
+    /// given function. Sprites whose tiles can't be found are skipped.
+    pub(crate) fn insert_recorded_operations(
+        &mut self,
+        operations: &[RecordedOperation],
+        tile: &mut dyn FnMut(usize) -> Result<Option<AtlasTile>>,
+    ) -> Result<()> {
+        for operation in operations {
+            match operation {
+                RecordedOperation::Primitive(primitive) => {
+                    if let Some(primitive) = primitive.to_primitive(tile)? {
+                        self.insert_primitive(primitive);
+                    }
+                }
+                RecordedOperation::StartLayer { bounds } => {
+                    self.push_layer(deserialize_bounds(bounds))
+                }
+                RecordedOperation::EndLayer => self.pop_layer(),
+                RecordedOperation::StartZIndex { z_index } => self.push_z_index(*z_index),
+                RecordedOperation::EndZIndex => self.pop_z_index(),
+                RecordedOperation::StartOpacityGroup { opacity } => {
+                    self.push_opacity_group(*opacity)
+                }
+                RecordedOperation::EndOpacityGroup => self.pop_opacity_group(),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Paints recorded frames into scenes outside of a window, so that benchmarks can measure how
+/// long building and diffing a frame's scene takes.
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Default)]
+pub struct SceneBenchmark {
+    scene: Scene,
+    previous_scene: Scene,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl SceneBenchmark {
+    /// Paint the recorded frame into a scene and finish it, keeping the scene painted before it
+    /// to diff against. Sprites are skipped, as there's no atlas to look their tiles up in.
+    pub fn paint(&mut self, frame: &RecordedFrame) -> Result<()> {
+        std::mem::swap(&mut self.scene, &mut self.previous_scene);
+        self.scene.clear();
+        self.scene
+            .insert_recorded_operations(&frame.operations, &mut |_| Ok(None))?;
+        self.scene.finish();
+        Ok(())
+    }
+
+    /// The regions that changed between the last two scenes painted.
+    pub fn diff(&self) -> Vec<Bounds<ScaledPixels>> {
+        self.scene.diff(&self.previous_scene)
+    }
+}
+
+impl RecordedPrimitive {
+    /// Convert the recorded primitive back into one that can be inserted into a scene, looking
+    /// up the atlas tiles of sprites with the given function.
+    fn to_primitive(
+        &self,
+        tile: &mut dyn FnMut(usize) -> Result<Option<AtlasTile>>,
+    ) -> Result<Option<Primitive>> {
+        Ok(Some(match self {
+            RecordedPrimitive::Shadow {
+                bounds,
+                content_mask,
+                transformation,
+                corner_radii,
+                blur_radius,
+                color,
+                inset,
+            } => {
+                let [inset_x, inset_y, inset_spread_radius] = inset.unwrap_or_default();
+                Primitive::Shadow(Shadow {
+                    order: 0,
+                    blur_radius: ScaledPixels(*blur_radius),
+                    bounds: deserialize_bounds(bounds),
+                    corner_radii: deserialize_corners(corner_radii),
+                    content_mask: deserialize_content_mask(content_mask),
+                    color: deserialize_color(color),
+                    transformation: deserialize_transformation(transformation),
+                    inset_offset: point(ScaledPixels(inset_x), ScaledPixels(inset_y)),
+                    inset_spread_radius: ScaledPixels(inset_spread_radius),
+                    inset: inset.is_some(),
+                })
+            }
+            RecordedPrimitive::BackdropBlur {
+                bounds,
+                content_mask,
+                corner_radii,
+                blur_radius,
+            } => Primitive::BackdropBlur(BackdropBlur {
+                order: 0,
+                blur_radius: ScaledPixels(*blur_radius),
+                bounds: deserialize_bounds(bounds),
+                corner_radii: deserialize_corners(corner_radii),
+                content_mask: deserialize_content_mask(content_mask),
+            }),
+            RecordedPrimitive::Quad {
+                bounds,
+                content_mask,
+                transformation,
+                background,
+                border_color,
+                border_widths,
+                corner_radii,
+                blend_mode,
+            } => Primitive::Quad(Quad {
+                order: 0,
+                blend_mode: *blend_mode,
+                bounds: deserialize_bounds(bounds),
+                content_mask: deserialize_content_mask(content_mask),
+                background: deserialize_background(background),
+                border_color: deserialize_color(border_color),
+                corner_radii: deserialize_corners(corner_radii),
+                border_widths: deserialize_edges(border_widths),
+                transformation: deserialize_transformation(transformation),
+            }),
+            RecordedPrimitive::Path {
+                bounds,
+                content_mask,
+                background,
+                nonzero,
+                vertices,
+            } => Primitive::Path(Path {
+                id: PathId(0),
+                order: 0,
+                bounds: deserialize_bounds(bounds),
+                content_mask: deserialize_content_mask(content_mask),
+                vertices: vertices
+                    .iter()
+                    .map(
+                        |[x, y, s, t, mask_x, mask_y, mask_width, mask_height]| PathVertex {
+                            xy_position: point(ScaledPixels(*x), ScaledPixels(*y)),
+                            st_position: point(*s, *t),
+                            content_mask: deserialize_content_mask(&SerializedBounds {
+                                x: *mask_x,
+                                y: *mask_y,
+                                width: *mask_width,
+                                height: *mask_height,
+                            }),
+                        },
+                    )
+                    .collect(),
+                color: deserialize_background(background),
+                nonzero: *nonzero,
+                start: Default::default(),
+                current: Default::default(),
+                contour_count: 0,
+                closing_fringe: None,
+            }),
+            RecordedPrimitive::Underline {
+                bounds,
+                content_mask,
+                transformation,
+                color,
+                thickness,
+                kind,
+                wave: [wave_amplitude, wavelength],
+                dashes: [dash, dash_gap, dash_offset],
+            } => Primitive::Underline(Underline {
+                order: 0,
+                kind: *kind,
+                bounds: deserialize_bounds(bounds),
+                content_mask: deserialize_content_mask(content_mask),
+                color: deserialize_color(color),
+                thickness: ScaledPixels(*thickness),
+                wavelength: ScaledPixels(*wavelength),
+                transformation: deserialize_transformation(transformation),
+                dash: ScaledPixels(*dash),
+                dash_gap: ScaledPixels(*dash_gap),
+                dash_offset: ScaledPixels(*dash_offset),
+                wave_amplitude: ScaledPixels(*wave_amplitude),
+            }),
+            RecordedPrimitive::MonochromeSprite {
+                bounds,
+                content_mask,
+                transformation,
+                tile: tile_index,
+                color,
+                blend_mode,
+            } => {
+                let Some(tile) = tile(*tile_index)? else {
+                    return Ok(None);
+                };
+                Primitive::MonochromeSprite(MonochromeSprite {
+                    order: 0,
+                    blend_mode: *blend_mode,
+                    bounds: deserialize_bounds(bounds),
+                    content_mask: deserialize_content_mask(content_mask),
+                    color: deserialize_color(color),
+                    tile,
+                    transformation: deserialize_transformation(transformation),
+                })
+            }
+            RecordedPrimitive::PolychromeSprite {
+                bounds,
+                content_mask,
+                transformation,
+                tile: tile_index,
+                corner_radii,
+                grayscale,
+                hue_rotate,
+                tint,
+                opacity,
+                nine_slice: [nine_slice, tile_nine_slice],
+                repeat:
+                    [repeat_width, repeat_height, spacing_width, spacing_height, repeat_x, repeat_y],
+                blend_mode,
+            } => {
+                let Some(tile) = tile(*tile_index)? else {
+                    return Ok(None);
+                };
+                Primitive::PolychromeSprite(PolychromeSprite {
+                    order: 0,
+                    hue_rotate: *hue_rotate,
+                    grayscale: *grayscale,
+                    opacity: *opacity,
+                    bounds: deserialize_bounds(bounds),
+                    content_mask: deserialize_content_mask(content_mask),
+                    corner_radii: deserialize_corners(corner_radii),
+                    tile,
+                    transformation: deserialize_transformation(transformation),
+                    nine_slice: deserialize_edges(nine_slice),
+                    tile_nine_slice: deserialize_edges(tile_nine_slice),
+                    repeat_size: size(ScaledPixels(*repeat_width), ScaledPixels(*repeat_height)),
+                    repeat_spacing: size(
+                        ScaledPixels(*spacing_width),
+                        ScaledPixels(*spacing_height),
+                    ),
+                    repeat_origin: point(ScaledPixels(*repeat_x), ScaledPixels(*repeat_y)),
+                    tint: deserialize_color(tint),
+                    blend_mode: *blend_mode,
+                    pad: 0,
+                })
+            }
+        }))
+    }
+}
+
+fn serialize_edges(edges: &Edges<ScaledPixels>) -> [f32; 4] {
+    [edges.top.0, edges.right.0, edges.bottom.0, edges.left.0]
+}
+
+fn deserialize_edges([top, right, bottom, left]: &[f32; 4]) -> Edges<ScaledPixels> {
+    Edges {
+        top: ScaledPixels(*top),
+        right: ScaledPixels(*right),
+        bottom: ScaledPixels(*bottom),
+        left: ScaledPixels(*left),
+    }
+}
+
+fn deserialize_corners(
+    [top_left, top_right, bottom_right, bottom_left]: &[f32; 4],
+) -> Corners<ScaledPixels> {
+    Corners {
+        top_left: ScaledPixels(*top_left),
+        top_right: ScaledPixels(*top_right),
+        bottom_right: ScaledPixels(*bottom_right),
+        bottom_left: ScaledPixels(*bottom_left),
+    }
+}
+
+fn deserialize_color([h, s, l, a]: &[f32; 4]) -> Hsla {
+    Hsla {
+        h: *h,
+        s: *s,
+        l: *l,
+        a: *a,
+    }
+}
+
+fn deserialize_bounds(bounds: &SerializedBounds) -> Bounds<ScaledPixels> {
+    Bounds {
+        origin: point(ScaledPixels(bounds.x), ScaledPixels(bounds.y)),
+        size: size(ScaledPixels(bounds.width), ScaledPixels(bounds.height)),
+    }
+}
+
+fn deserialize_content_mask(bounds: &SerializedBounds) -> ContentMask<ScaledPixels> {
+    ContentMask {
+        bounds: deserialize_bounds(bounds),
+    }
+}
+
+fn deserialize_transformation(transformation: &Option<[f32; 6]>) -> TransformationMatrix {
+    match transformation {
+        Some([a, b, c, d, x, y]) => TransformationMatrix {
+            rotation_scale: [[*a, *b], [*c, *d]],
+            translation: [*x, *y],
+        },
+        None => TransformationMatrix::unit(),
+    }
+}
+
+fn deserialize_background(background: &SerializedBackground) -> Background {
+    let (tag, angle, [center_x, center_y], stops) = match background {
+        SerializedBackground::Solid { color } => {
+            return Background {
+                solid: deserialize_color(color),
+                ..Default::default()
+            }
+        }
+        SerializedBackground::LinearGradient { angle, stops } => {
+            (BackgroundTag::LinearGradient, *angle, [0.5, 0.5], stops)
+        }
+        SerializedBackground::RadialGradient { center, stops } => {
+            (BackgroundTag::RadialGradient, 0., *center, stops)
+        }
+        SerializedBackground::ConicGradient {
+            angle,
+            center,
+            stops,
+        } => (BackgroundTag::ConicGradient, *angle, *center, stops),
+    };
+    let mut background = Background {
+        tag,
+        angle,
+        center_x,
+        center_y,
+        stop_count: stops.len().min(MAX_GRADIENT_STOPS) as u32,
+        ..Default::default()
+    };
+    for (stop, (color, percentage)) in background.stops.iter_mut().zip(stops) {
+        *stop = LinearColorStop {
+            color: deserialize_color(color),
+            percentage: *percentage,
+        };
+    }
+    background
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear_gradient, px, red};
+
+    #[test]
+    fn test_recorded_operations_round_trip() {
+        let content_mask = ContentMask {
+            bounds: Bounds::new(
+                point(ScaledPixels(0.), ScaledPixels(0.)),
+                size(ScaledPixels(100.), ScaledPixels(100.)),
+            ),
+        };
+        let mut scene = Scene::default();
+        scene.push_z_index(1);
+        scene.insert_primitive(Quad {
+            bounds: Bounds::new(
+                point(ScaledPixels(10.), ScaledPixels(10.)),
+                size(ScaledPixels(50.), ScaledPixels(20.)),
+            ),
+            content_mask: content_mask.clone(),
+            background: linear_gradient(90., [(red(), 0.), (Hsla::white(), 1.)]),
+            corner_radii: Corners::all(ScaledPixels(4.)),
+            ..Default::default()
+        });
+        let mut path = Path::new(point(px(0.), px(0.)));
+        path.line_to(point(px(20.), px(0.)));
+        path.line_to(point(px(20.), px(20.)));
+        let mut path = path.scale(2.);
+        path.content_mask = content_mask;
+        scene.insert_primitive(path);
+        scene.pop_z_index();
+        scene.finish();
+
+        let tile_indices = FxHashMap::default();
+        let operations = scene
+            .paint_operations
+            .iter()
+            .filter_map(|operation| record_operation(operation, &tile_indices))
+            .collect::<Vec<_>>();
+        let json = serde_json::to_string(&operations).unwrap();
+        let operations: Vec<RecordedOperation> = serde_json::from_str(&json).unwrap();
+
+        let mut replayed = Scene::default();
+        replayed
+            .insert_recorded_operations(&operations, &mut |_| Ok(None))
+            .unwrap();
+        replayed.finish();
+        assert_eq!(replayed.serialize(), scene.serialize());
+        assert_eq!(
+            replayed.paths()[0].vertices.len(),
+            scene.paths()[0].vertices.len()
+        );
+    }
+}
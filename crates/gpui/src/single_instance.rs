@@ -0,0 +1,123 @@
+//! Detects whether another instance of an app is already running, and forwards the arguments
+//! of subsequent launches to the first one.
+//!
+//! Instances find each other through a Unix domain socket in the runtime directory, or through
+//! a loopback TCP port derived from the app id on other platforms.
+
+use anyhow::{Context as _, Result};
+use std::io::{Read, Write};
+
+#[cfg(not(unix))]
+use std::net::{TcpListener as Listener, TcpStream as Stream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener as Listener, UnixStream as Stream};
+
+/// Arguments are separated by NUL bytes, which can't occur within them.
+const SEPARATOR: char = '\0';
+
+/// The listener of the first instance of an app, receiving the arguments of later instances.
+pub(crate) struct InstanceListener(Listener);
+
+/// Claims the given app id for this process. Returns `None` if another process had already
+/// claimed it, in which case the arguments were forwarded to that process.
+pub(crate) fn claim_instance(app_id: &str, args: &[String]) -> Result<Option<InstanceListener>> {
+    if let Ok(mut stream) = connect(app_id) {
+        stream
+            .write_all(args.join(&SEPARATOR.to_string()).as_bytes())
+            .context("forwarding arguments to the running instance")?;
+        return Ok(None);
+    }
+
+    bind(app_id).map(|listener| Some(InstanceListener(listener)))
+}
+
+impl InstanceListener {
+    /// Blocks the current thread, calling `callback` with the arguments of every subsequent
+    /// instance, until it returns `false`.
+    pub(crate) fn listen(self, mut callback: impl FnMut(Vec<String>) -> bool) {
+        for stream in self.0.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let mut message = String::new();
+            if let Err(error) = stream.read_to_string(&mut message) {
+                log::error!("failed to read arguments from another instance: {error}");
+                continue;
+            }
+
+            let args = if message.is_empty() {
+                Vec::new()
+            } else {
+                message.split(SEPARATOR).map(str::to_string).collect()
+            };
+            if !callback(args) {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn socket_path(app_id: &str) -> std::path::PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("{app_id}.sock"))
+}
+
+#[cfg(unix)]
+fn connect(app_id: &str) -> std::io::Result<Stream> {
+    Stream::connect(socket_path(app_id))
+}
+
+#[cfg(unix)]
+fn bind(app_id: &str) -> Result<Listener> {
+    let path = socket_path(app_id);
+    // Nothing accepted connections on the socket, so it was left behind by a previous instance.
+    std::fs::remove_file(&path).ok();
+    Listener::bind(&path).with_context(|| format!("binding socket at {path:?}"))
+}
+
+#[cfg(not(unix))]
+fn port(app_id: &str) -> u16 {
+    const DYNAMIC_PORTS_START: u16 = 49152;
+    let range = (u16::MAX - DYNAMIC_PORTS_START) as u64;
+    DYNAMIC_PORTS_START + (crate::hash(&app_id) % range) as u16
+}
+
+#[cfg(not(unix))]
+fn connect(app_id: &str) -> std::io::Result<Stream> {
+    Stream::connect(("127.0.0.1", port(app_id)))
+}
+
+#[cfg(not(unix))]
+fn bind(app_id: &str) -> Result<Listener> {
+    let port = port(app_id);
+    Listener::bind(("127.0.0.1", port)).with_context(|| format!("binding port {port}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_instance() {
+        let app_id = format!("gpui-test-{}", std::process::id());
+        let listener = claim_instance(&app_id, &[]).unwrap().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            listener.listen(|args| {
+                tx.send(args).unwrap();
+                false
+            })
+        });
+
+        let args = vec!["--new".to_string(), "a b.txt".to_string()];
+        assert!(claim_instance(&app_id, &args).unwrap().is_none());
+        assert_eq!(rx.recv().unwrap(), args);
+        thread.join().unwrap();
+
+        #[cfg(unix)]
+        std::fs::remove_file(socket_path(&app_id)).ok();
+    }
+}
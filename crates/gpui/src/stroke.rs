@@ -0,0 +1,552 @@
+use crate::{point, px, DashPattern, Path, Pixels, Point};
+use std::{f32::consts::PI, mem};
+
+/// How far flattened curves and round joins and caps may stray from their exact shape.
+const TOLERANCE: f32 = 0.1;
+const MAX_ARC_SEGMENTS: usize = 64;
+const EPSILON: f32 = 1e-4;
+
+/// How the segments of a stroked path are joined where they meet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// Extend the outer edges of the segments until they meet, unless the corner is sharper than
+    /// the miter limit allows, in which case it's beveled.
+    #[default]
+    Miter,
+    /// Round off the corner with an arc centered on the joint.
+    Round,
+    /// Cut off the corner with a straight line between the outer edges of the segments.
+    Bevel,
+}
+
+/// How the ends of a stroked path are drawn, unless it's closed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// End the stroke flush with the end points.
+    #[default]
+    Butt,
+    /// Extend the stroke past the end points with half circles.
+    Round,
+    /// Extend the stroke past the end points by half its width.
+    Square,
+}
+
+/// The appearance of the lines drawn by a [`StrokeBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// The width of the lines, centered on the path.
+    pub width: Pixels,
+    /// How segments are joined.
+    pub join: LineJoin,
+    /// How the ends of open subpaths are drawn.
+    pub cap: LineCap,
+    /// The longest a miter join may be, as a multiple of the stroke's width, before it's beveled
+    /// instead. Like in SVG, this defaults to 4, which bevels corners sharper than about 29°.
+    pub miter_limit: f32,
+    /// The lengths of alternating dashes and gaps to draw the lines with, like SVG's
+    /// `stroke-dasharray`. An odd number of lengths is repeated to make an even one. Empty for
+    /// continuous lines.
+    pub dash_array: Vec<Pixels>,
+    /// How far into the dash array the lines start. Animating the offset makes the dashes march
+    /// along the lines.
+    pub dash_offset: Pixels,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: px(1.),
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            miter_limit: 4.,
+            dash_array: Vec::new(),
+            dash_offset: Pixels::ZERO,
+        }
+    }
+}
+
+impl StrokeStyle {
+    /// A stroke of the given width, with miter joins and butt caps.
+    pub fn new(width: Pixels) -> Self {
+        Self {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Sets how segments are joined.
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Sets how the ends of open subpaths are drawn.
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets the longest a miter join may be, as a multiple of the stroke's width.
+    pub fn miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Sets the lengths of alternating dashes and gaps to draw the lines with, and how far into
+    /// them the lines start.
+    pub fn dashes(mut self, dash_array: impl IntoIterator<Item = Pixels>, offset: Pixels) -> Self {
+        self.dash_array = dash_array.into_iter().collect();
+        self.dash_offset = offset;
+        self
+    }
+
+    /// Draws the lines with the same pattern of dashes as a dashed underline.
+    pub fn dash_pattern(self, pattern: DashPattern) -> Self {
+        self.dashes([pattern.dash, pattern.gap], pattern.offset)
+    }
+}
+
+/// Builds a [`Path`] that strokes a series of lines and curves, rather than filling them. Lines
+/// are tessellated into triangles on the CPU, with anti-aliased edges, so the path can be painted
+/// with [`WindowContext::paint_path`](crate::WindowContext::paint_path) like any other.
+///
+/// ```ignore
+/// let mut stroke = StrokeBuilder::new(
+///     point(px(0.), px(20.)),
+///     StrokeStyle::new(px(2.)).join(LineJoin::Round).cap(LineCap::Round),
+/// );
+/// stroke.line_to(point(px(10.), px(5.)));
+/// stroke.curve_to(point(px(30.), px(15.)), point(px(20.), px(0.)));
+/// cx.paint_path(stroke.build(), black());
+/// ```
+pub struct StrokeBuilder {
+    style: StrokeStyle,
+    start: Point<Pixels>,
+    subpaths: Vec<Subpath>,
+}
+
+struct Subpath {
+    points: Vec<Point<f32>>,
+    closed: bool,
+}
+
+impl StrokeBuilder {
+    /// Starts a stroke at the given point.
+    pub fn new(start: Point<Pixels>, style: StrokeStyle) -> Self {
+        Self {
+            style,
+            start,
+            subpaths: vec![Subpath {
+                points: vec![to_f32(start)],
+                closed: false,
+            }],
+        }
+    }
+
+    /// Starts a new subpath at the given point, without drawing a line to it.
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.subpaths.push(Subpath {
+            points: vec![to_f32(to)],
+            closed: false,
+        });
+    }
+
+    /// Draws a straight line from the current point to the given point.
+    pub fn line_to(&mut self, to: Point<Pixels>) {
+        self.current_subpath().points.push(to_f32(to));
+    }
+
+    /// Draws a curve from the current point to the given point, using the given control point.
+    pub fn curve_to(&mut self, to: Point<Pixels>, ctrl: Point<Pixels>) {
+        let points = &mut self.current_subpath().points;
+        let from = *points.last().unwrap();
+        let (ctrl, to) = (to_f32(ctrl), to_f32(to));
+
+        // The distance between a quadratic curve and its flattening shrinks with the square of
+        // the number of segments.
+        let deviation = length(add(sub(from, scale(ctrl, 2.)), to));
+        let segment_count =
+            ((deviation / (8. * TOLERANCE)).sqrt().ceil() as usize).clamp(1, MAX_ARC_SEGMENTS);
+        for i in 1..=segment_count {
+            let t = i as f32 / segment_count as f32;
+            points.push(add(
+                add(
+                    scale(from, (1. - t) * (1. - t)),
+                    scale(ctrl, 2. * t * (1. - t)),
+                ),
+                scale(to, t * t),
+            ));
+        }
+    }
+
+    /// Closes the current subpath with a line back to its start, joining the two ends. Lines
+    /// drawn after closing a subpath start a new one at the same point.
+    pub fn close(&mut self) {
+        let subpath = self.current_subpath();
+        subpath.closed = true;
+        let start = subpath.points[0];
+        self.subpaths.push(Subpath {
+            points: vec![start],
+            closed: false,
+        });
+    }
+
+    fn current_subpath(&mut self) -> &mut Subpath {
+        self.subpaths.last_mut().unwrap()
+    }
+
+    /// Tessellates the stroke into a path.
+    pub fn build(self) -> Path<Pixels> {
+        let mut path = Path::new(self.start);
+        path.nonzero = true;
+
+        let half_width = self.style.width.0 / 2.;
+        if half_width <= 0. {
+            return path;
+        }
+        let mut dash_array = self
+            .style
+            .dash_array
+            .iter()
+            .map(|length| length.0.max(0.))
+            .collect::<Vec<_>>();
+        if dash_array.iter().sum::<f32>() <= 0. {
+            dash_array.clear();
+        } else if dash_array.len() % 2 == 1 {
+            dash_array.extend_from_within(..);
+        }
+
+        let mut tessellator = Tessellator {
+            path: &mut path,
+            style: &self.style,
+            half_width,
+            dash_array,
+        };
+        for subpath in &self.subpaths {
+            tessellator.stroke_subpath(subpath);
+        }
+        path
+    }
+}
+
+/// Emits the triangles of a stroke. Each one has a vertex on the center of the line, and the
+/// others on its edge, which lets path rasterization anti-alias the edge.
+struct Tessellator<'a> {
+    path: &'a mut Path<Pixels>,
+    style: &'a StrokeStyle,
+    half_width: f32,
+    /// An even number of dash and gap lengths, or none for continuous lines.
+    dash_array: Vec<f32>,
+}
+
+impl Tessellator<'_> {
+    fn stroke_subpath(&mut self, subpath: &Subpath) {
+        let drawn = subpath.points.len() > 1;
+        if self.dash_array.is_empty() || !drawn {
+            self.stroke_points(subpath.points.clone(), subpath.closed, drawn);
+            return;
+        }
+
+        let mut points = subpath.points.clone();
+        if subpath.closed {
+            points.push(points[0]);
+        }
+        for dash in self.split_into_dashes(&points) {
+            self.stroke_points(dash, false, true);
+        }
+    }
+
+    /// Splits a line through the given points into the lines of its dashes.
+    fn split_into_dashes(&self, points: &[Point<f32>]) -> Vec<Vec<Point<f32>>> {
+        let dash_array = &self.dash_array;
+        let is_dash = |index: usize| index % 2 == 0;
+
+        // Find where in the dash array the line starts.
+        let period = dash_array.iter().sum::<f32>();
+        let mut offset = self.style.dash_offset.0.rem_euclid(period);
+        let mut index = 0;
+        while offset >= dash_array[index] {
+            offset -= dash_array[index];
+            index = (index + 1) % dash_array.len();
+        }
+        let mut remaining = dash_array[index] - offset;
+
+        let mut dashes = Vec::new();
+        let mut dash = Vec::new();
+        if is_dash(index) {
+            dash.push(points[0]);
+        }
+        for segment in points.windows(2) {
+            let (mut from, to) = (segment[0], segment[1]);
+            let direction = normalize(sub(to, from));
+            let mut segment_remaining = length(sub(to, from));
+            while segment_remaining > remaining {
+                from = add(from, scale(direction, remaining));
+                segment_remaining -= remaining;
+                dash.push(from);
+                if is_dash(index) {
+                    dashes.push(mem::take(&mut dash));
+                }
+                index = (index + 1) % dash_array.len();
+                remaining = dash_array[index];
+            }
+            remaining -= segment_remaining;
+            if is_dash(index) {
+                dash.push(to);
+            }
+        }
+        if is_dash(index) {
+            dashes.push(dash);
+        }
+        dashes
+    }
+
+    /// Strokes a line through the given points. Lines without any length only show their caps,
+    /// and only if they were `drawn` with more than a single point.
+    fn stroke_points(&mut self, mut points: Vec<Point<f32>>, closed: bool, drawn: bool) {
+        points.dedup_by(|a, b| length(sub(*a, *b)) < EPSILON);
+        if closed && points.len() > 1 && length(sub(points[0], points[points.len() - 1])) < EPSILON
+        {
+            points.pop();
+        }
+
+        if points.len() == 1 {
+            if !closed && drawn {
+                let direction = point(1., 0.);
+                self.push_cap(points[0], direction);
+                self.push_cap(points[0], scale(direction, -1.));
+            }
+            return;
+        }
+
+        let point_count = points.len();
+        let segment_count = if closed { point_count } else { point_count - 1 };
+        for i in 0..segment_count {
+            self.push_segment(points[i], points[(i + 1) % point_count]);
+        }
+
+        if closed {
+            for i in 0..point_count {
+                let previous = points[(i + point_count - 1) % point_count];
+                let next = points[(i + 1) % point_count];
+                self.push_join(previous, points[i], next);
+            }
+        } else {
+            for i in 1..point_count - 1 {
+                self.push_join(points[i - 1], points[i], points[i + 1]);
+            }
+            self.push_cap(points[0], normalize(sub(points[0], points[1])));
+            self.push_cap(
+                points[point_count - 1],
+                normalize(sub(points[point_count - 1], points[point_count - 2])),
+            );
+        }
+    }
+
+    fn push_segment(&mut self, from: Point<f32>, to: Point<f32>) {
+        let normal = scale(perpendicular(normalize(sub(to, from))), self.half_width);
+        for normal in [normal, scale(normal, -1.)] {
+            let (from_edge, to_edge) = (add(from, normal), add(to, normal));
+            self.push_triangle(from, from_edge, to_edge);
+            self.push_triangle(to, from_edge, to_edge);
+        }
+    }
+
+    fn push_join(&mut self, previous: Point<f32>, joint: Point<f32>, next: Point<f32>) {
+        let incoming = normalize(sub(joint, previous));
+        let outgoing = normalize(sub(next, joint));
+        let turn = cross(incoming, outgoing);
+        if turn.abs() < EPSILON && dot(incoming, outgoing) > 0. {
+            return;
+        }
+
+        // The corner to fill is on the outside of the turn.
+        let side = if turn > 0. { -1. } else { 1. };
+        let incoming_normal = scale(perpendicular(incoming), side);
+        let outgoing_normal = scale(perpendicular(outgoing), side);
+        let incoming_edge = add(joint, scale(incoming_normal, self.half_width));
+        let outgoing_edge = add(joint, scale(outgoing_normal, self.half_width));
+
+        match self.style.join {
+            LineJoin::Miter => {
+                let bisector = add(incoming_normal, outgoing_normal);
+                let bisector_length = length(bisector);
+                if bisector_length > EPSILON && 2. / bisector_length <= self.style.miter_limit {
+                    let miter = add(
+                        joint,
+                        scale(
+                            bisector,
+                            2. * self.half_width / (bisector_length * bisector_length),
+                        ),
+                    );
+                    self.push_triangle(joint, incoming_edge, miter);
+                    self.push_triangle(joint, miter, outgoing_edge);
+                } else {
+                    self.push_triangle(joint, incoming_edge, outgoing_edge);
+                }
+            }
+            LineJoin::Round => {
+                let sweep = cross(incoming_normal, outgoing_normal)
+                    .atan2(dot(incoming_normal, outgoing_normal));
+                self.push_arc(joint, incoming_normal, sweep);
+            }
+            LineJoin::Bevel => self.push_triangle(joint, incoming_edge, outgoing_edge),
+        }
+    }
+
+    /// Pushes the cap at an end point of the stroke, facing the given direction.
+    fn push_cap(&mut self, end: Point<f32>, direction: Point<f32>) {
+        let normal = perpendicular(direction);
+        match self.style.cap {
+            LineCap::Butt => {}
+            LineCap::Round => self.push_arc(end, normal, -PI),
+            LineCap::Square => {
+                let normal = scale(normal, self.half_width);
+                let extent = scale(direction, self.half_width);
+                let center = add(end, scale(extent, 0.5));
+                let corners = [
+                    add(end, normal),
+                    add(add(end, normal), extent),
+                    sub(add(end, extent), normal),
+                    sub(end, normal),
+                ];
+                for i in 0..corners.len() {
+                    self.push_triangle(center, corners[i], corners[(i + 1) % corners.len()]);
+                }
+            }
+        }
+    }
+
+    /// Pushes a fan of triangles around `center`, starting from the unit vector `from` and
+    /// sweeping by the given angle.
+    fn push_arc(&mut self, center: Point<f32>, from: Point<f32>, sweep: f32) {
+        let max_step = 2. * (1. - TOLERANCE / self.half_width).max(-1.).acos();
+        let segment_count = ((sweep.abs() / max_step).ceil() as usize).clamp(1, MAX_ARC_SEGMENTS);
+        let start_angle = from.y.atan2(from.x);
+        let arc_point = |i: usize| {
+            let angle = start_angle + sweep * i as f32 / segment_count as f32;
+            add(
+                center,
+                point(angle.cos() * self.half_width, angle.sin() * self.half_width),
+            )
+        };
+        for i in 0..segment_count {
+            self.push_triangle(center, arc_point(i), arc_point(i + 1));
+        }
+    }
+
+    fn push_triangle(&mut self, center: Point<f32>, edge_a: Point<f32>, edge_b: Point<f32>) {
+        // Coverage fades out towards the vertices whose t coordinate is 0.
+        self.path.push_triangle(
+            (to_pixels(center), to_pixels(edge_a), to_pixels(edge_b)),
+            (point(0., 1.), point(0., 0.), point(0., 0.)),
+        );
+    }
+}
+
+fn to_f32(point: Point<Pixels>) -> Point<f32> {
+    point.map(|coordinate| coordinate.0)
+}
+
+fn to_pixels(point: Point<f32>) -> Point<Pixels> {
+    point.map(px)
+}
+
+fn add(a: Point<f32>, b: Point<f32>) -> Point<f32> {
+    point(a.x + b.x, a.y + b.y)
+}
+
+fn sub(a: Point<f32>, b: Point<f32>) -> Point<f32> {
+    point(a.x - b.x, a.y - b.y)
+}
+
+fn scale(a: Point<f32>, factor: f32) -> Point<f32> {
+    point(a.x * factor, a.y * factor)
+}
+
+fn dot(a: Point<f32>, b: Point<f32>) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Point<f32>, b: Point<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn length(a: Point<f32>) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: Point<f32>) -> Point<f32> {
+    scale(a, 1. / length(a))
+}
+
+fn perpendicular(a: Point<f32>) -> Point<f32> {
+    point(-a.y, a.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{size, Bounds};
+
+    fn stroke_bounds(style: StrokeStyle) -> Bounds<Pixels> {
+        let mut stroke = StrokeBuilder::new(point(px(0.), px(0.)), style);
+        stroke.line_to(point(px(10.), px(0.)));
+        stroke.build().bounds
+    }
+
+    #[test]
+    fn test_stroke_caps() {
+        assert_eq!(
+            stroke_bounds(StrokeStyle::new(px(2.))),
+            Bounds::new(point(px(0.), px(-1.)), size(px(10.), px(2.)))
+        );
+        assert_eq!(
+            stroke_bounds(StrokeStyle::new(px(2.)).cap(LineCap::Square)),
+            Bounds::new(point(px(-1.), px(-1.)), size(px(12.), px(2.)))
+        );
+
+        let round = stroke_bounds(StrokeStyle::new(px(2.)).cap(LineCap::Round));
+        assert!((round.origin.x.0 + 1.).abs() < 1e-4);
+        assert!((round.size.width.0 - 12.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_stroke_joins() {
+        // A corner of 60°, whose miter reaches √3 past the joint.
+        let right_edge = |style| {
+            let mut stroke = StrokeBuilder::new(point(px(0.), px(0.)), style);
+            stroke.line_to(point(px(10.), px(0.)));
+            stroke.line_to(point(px(5.), px(8.66)));
+            stroke.build().bounds.right().0
+        };
+        let style = StrokeStyle::new(px(2.));
+
+        assert!((right_edge(style.clone()) - 11.732).abs() < 1e-2);
+        assert!((right_edge(style.clone().join(LineJoin::Round)) - 11.).abs() < TOLERANCE);
+        assert!((right_edge(style.clone().join(LineJoin::Bevel)) - 10.866).abs() < 1e-2);
+
+        // Beyond the miter limit, corners are beveled.
+        assert_eq!(
+            right_edge(style.clone().miter_limit(1.5)),
+            right_edge(style.join(LineJoin::Bevel))
+        );
+    }
+
+    #[test]
+    fn test_stroke_dashes() {
+        let dash_end = |offset: f32| {
+            let mut stroke = StrokeBuilder::new(
+                point(px(0.), px(0.)),
+                StrokeStyle::new(px(2.)).dashes([px(2.), px(20.)], px(offset)),
+            );
+            stroke.line_to(point(px(10.), px(0.)));
+            stroke.build().bounds.right()
+        };
+
+        assert_eq!(dash_end(0.), px(2.));
+        assert_eq!(dash_end(1.), px(1.));
+        // Negative offsets move the dashes forward along the line.
+        assert_eq!(dash_end(-5.), px(7.));
+    }
+}
@@ -5,13 +5,15 @@ use std::{
 };
 
 use crate::{
-    black, phi, point, quad, rems, size, AbsoluteLength, Bounds, ContentMask, Corners,
-    CornersRefinement, CursorStyle, DefiniteLength, DevicePixels, Edges, EdgesRefinement, Font,
-    FontFallbacks, FontFeatures, FontStyle, FontWeight, Hsla, Length, Pixels, Point,
-    PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, WindowContext,
+    black, phi, point, quad, rems, size, AbsoluteLength, Background, BlendMode, Bounds,
+    ContentMask, Corners, CornersRefinement, CursorStyle, DefiniteLength, DevicePixels, Edges,
+    EdgesRefinement, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Hsla, Length,
+    Pixels, Point, PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun,
+    WindowContext,
 };
 use collections::HashSet;
 use refineable::Refineable;
+use serde_derive::{Deserialize, Serialize};
 use smallvec::SmallVec;
 pub use taffy::style::{
     AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, JustifyContent,
@@ -223,9 +225,13 @@ pub struct Style {
     #[refineable]
     pub corner_radii: Corners<AbsoluteLength>,
 
-    /// Box Shadow of the element
+    /// The shadows of the element, which are stacked in order so that the last one is painted
+    /// on top
     pub box_shadow: SmallVec<[BoxShadow; 2]>,
 
+    /// The radius of the blur applied to whatever is painted behind this element
+    pub backdrop_blur: Option<Pixels>,
+
     /// The text style of this element
     pub text: TextStyleRefinement,
 
@@ -235,6 +241,22 @@ pub struct Style {
     /// The opacity of this element
     pub opacity: Option<f32>,
 
+    /// Whether this element and its children are composited as one group at the element's
+    /// opacity, rather than each primitive they paint being faded on its own.
+    pub opacity_group: bool,
+
+    /// How the quads and sprites this element and its children paint are blended with what was
+    /// painted beneath them.
+    pub blend_mode: Option<BlendMode>,
+
+    /// The z-index of this element, relative to that of its parent. The element and its children
+    /// are drawn above, and hit before, the elements with a lower z-index.
+    pub z_index: Option<u8>,
+
+    /// The direction this element and its children are laid out in, overriding the one inherited
+    /// from its parent.
+    pub layout_direction: Option<LayoutDirection>,
+
     /// Whether to draw a red debugging outline around this element
     #[cfg(debug_assertions)]
     pub debug: bool,
@@ -260,6 +282,25 @@ pub enum Visibility {
     Hidden,
 }
 
+/// The horizontal direction in which elements are laid out, similar to the CSS property
+/// `direction`
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LayoutDirection {
+    /// Elements flow from left to right.
+    #[default]
+    LeftToRight,
+    /// Elements flow from right to left, as in interfaces translated to Arabic or Hebrew. Rows,
+    /// horizontal edges and corners are mirrored.
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Returns true if elements flow from right to left.
+    pub fn is_rtl(self) -> bool {
+        self == LayoutDirection::RightToLeft
+    }
+}
+
 /// The possible values of the box-shadow property
 #[derive(Clone, Debug)]
 pub struct BoxShadow {
@@ -271,6 +312,10 @@ pub struct BoxShadow {
     pub blur_radius: Pixels,
     /// How much should the shadow spread?
     pub spread_radius: Pixels,
+    /// Should the shadow be drawn inside the element instead of around it, like a CSS `inset`
+    /// shadow? Inset shadows are clipped to the element's rounded bounds, and painted above its
+    /// background.
+    pub inset: bool,
 }
 
 /// How to handle whitespace in text
@@ -480,7 +525,7 @@ impl Style {
     pub fn has_opaque_background(&self) -> bool {
         self.background
             .as_ref()
-            .is_some_and(|fill| fill.color().is_some_and(|color| !color.is_transparent()))
+            .is_some_and(|fill| !fill.background().is_transparent())
     }
 
     /// Get the text style in this element style.
@@ -492,6 +537,67 @@ impl Style {
         }
     }
 
+    /// Blends this style's background, border and text colors with those of `from`, as is done
+    /// while an element transitions between two styles. A `progress` of 0.0 takes the colors of
+    /// `from`, and 1.0 keeps this style's colors. Missing colors are treated as transparent, and
+    /// gradients aren't blended.
+    pub fn blend_colors_from(&mut self, from: &Style, progress: f32) {
+        fn blend(from: Option<Hsla>, to: &mut Option<Hsla>, progress: f32) {
+            *to = match (from, *to) {
+                (Some(from), Some(to)) => Some(from.interpolate(to, progress)),
+                (None, Some(to)) => Some(to.opacity(progress)),
+                (Some(from), None) => Some(from.opacity(1. - progress)),
+                (None, None) => None,
+            };
+        }
+
+        let from_background = from.background.as_ref().and_then(Fill::color);
+        let mut background = self.background.as_ref().and_then(Fill::color);
+        if !matches!(from.background, Some(Fill::Gradient(_)))
+            && !matches!(self.background, Some(Fill::Gradient(_)))
+        {
+            blend(from_background, &mut background, progress);
+            self.background = background.map(Fill::Color);
+        }
+        blend(from.border_color, &mut self.border_color, progress);
+        blend(from.text.color, &mut self.text.color, progress);
+    }
+
+    /// Mirrors this style horizontally, as is done for elements laid out right to left: rows
+    /// are reversed, the left and right edges and corners are swapped, and the cross axis of
+    /// columns starts on the right.
+    pub fn mirror_horizontally(&mut self) {
+        match self.flex_direction {
+            FlexDirection::Row => self.flex_direction = FlexDirection::RowReverse,
+            FlexDirection::RowReverse => self.flex_direction = FlexDirection::Row,
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                self.align_items = self.align_items.map(|align_items| match align_items {
+                    AlignItems::Start => AlignItems::End,
+                    AlignItems::End => AlignItems::Start,
+                    AlignItems::FlexStart => AlignItems::FlexEnd,
+                    AlignItems::FlexEnd => AlignItems::FlexStart,
+                    align_items => align_items,
+                });
+            }
+        }
+
+        mem::swap(&mut self.inset.left, &mut self.inset.right);
+        mem::swap(&mut self.margin.left, &mut self.margin.right);
+        mem::swap(&mut self.padding.left, &mut self.padding.right);
+        mem::swap(&mut self.border_widths.left, &mut self.border_widths.right);
+        mem::swap(
+            &mut self.corner_radii.top_left,
+            &mut self.corner_radii.top_right,
+        );
+        mem::swap(
+            &mut self.corner_radii.bottom_left,
+            &mut self.corner_radii.bottom_right,
+        );
+        for shadow in &mut self.box_shadow {
+            shadow.offset.x = -shadow.offset.x;
+        }
+    }
+
     /// Get the content mask for this element style, based on the given bounds.
     /// If the element does not hide its overflow, this will return `None`.
     pub fn overflow_mask(
@@ -562,25 +668,50 @@ impl Style {
 
         let rem_size = cx.rem_size();
 
+        let (inset_shadows, outer_shadows): (SmallVec<[BoxShadow; 2]>, SmallVec<[BoxShadow; 2]>) =
+            self.box_shadow
+                .iter()
+                .cloned()
+                .partition(|shadow| shadow.inset);
         cx.paint_shadows(
             bounds,
             self.corner_radii.to_pixels(bounds.size, rem_size),
-            &self.box_shadow,
+            &outer_shadows,
         );
 
-        let background_color = self.background.as_ref().and_then(Fill::color);
-        if background_color.map_or(false, |color| !color.is_transparent()) {
-            let mut border_color = background_color.unwrap_or_default();
+        // Users who prefer reduced transparency get an opaque background instead of a blur.
+        let reduce_transparency =
+            self.backdrop_blur.is_some() && cx.accessibility_preferences().reduce_transparency;
+        if let Some(blur_radius) = self.backdrop_blur.filter(|_| !reduce_transparency) {
+            cx.paint_backdrop_blur(
+                bounds,
+                self.corner_radii.to_pixels(bounds.size, rem_size),
+                blur_radius,
+            );
+        }
+
+        let mut background = self.background.as_ref().map(Fill::background);
+        if reduce_transparency {
+            background = background.map(|background| background.opaque());
+        }
+        if let Some(background) = background.filter(|background| !background.is_transparent()) {
+            let mut border_color = background.color().unwrap_or_default();
             border_color.a = 0.;
             cx.paint_quad(quad(
                 bounds,
                 self.corner_radii.to_pixels(bounds.size, rem_size),
-                background_color.unwrap_or_default(),
+                background,
                 Edges::default(),
                 border_color,
             ));
         }
 
+        cx.paint_shadows(
+            bounds,
+            self.corner_radii.to_pixels(bounds.size, rem_size),
+            &inset_shadows,
+        );
+
         continuation(cx);
 
         if self.is_border_visible() {
@@ -693,9 +824,14 @@ impl Default for Style {
             border_color: None,
             corner_radii: Corners::default(),
             box_shadow: Default::default(),
+            backdrop_blur: None,
             text: TextStyleRefinement::default(),
             mouse_cursor: None,
             opacity: None,
+            opacity_group: false,
+            blend_mode: None,
+            z_index: None,
+            layout_direction: None,
 
             #[cfg(debug_assertions)]
             debug: false,
@@ -717,6 +853,55 @@ pub struct UnderlineStyle {
 
     /// Whether the underline should be wavy, like in a spell checker.
     pub wavy: bool,
+
+    /// Whether the underline is a single line, a double line or a row of dots.
+    pub kind: UnderlineKind,
+
+    /// The dashes to draw the underline with, or `None` for a continuous underline.
+    pub dash_pattern: Option<DashPattern>,
+}
+
+/// The shape of the line an underline is drawn with. Each of them can also be wavy.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(C)]
+pub enum UnderlineKind {
+    /// A single line.
+    #[default]
+    Single = 0,
+    /// Two lines as thick as the underline, with a gap of the same size between them.
+    Double = 1,
+    /// A row of round dots, as wide as the underline is thick.
+    Dotted = 2,
+}
+
+/// A dash followed by a gap, repeated along a line.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct DashPattern {
+    /// The length of each dash.
+    pub dash: Pixels,
+    /// The length of the gap after each dash.
+    pub gap: Pixels,
+    /// How far into the pattern the line starts. Animating the offset makes the dashes march
+    /// along the line.
+    pub offset: Pixels,
+}
+
+impl DashPattern {
+    /// Dashes of the given length, separated by gaps of the given length.
+    pub fn new(dash: Pixels, gap: Pixels) -> Self {
+        Self {
+            dash,
+            gap,
+            offset: Pixels::ZERO,
+        }
+    }
+
+    /// Sets how far into the pattern the line starts.
+    pub fn offset(mut self, offset: Pixels) -> Self {
+        self.offset = offset;
+        self
+    }
 }
 
 /// The properties that can be applied to a strikethrough.
@@ -735,6 +920,9 @@ pub struct StrikethroughStyle {
 pub enum Fill {
     /// A solid color fill.
     Color(Hsla),
+    /// A gradient fill, created with [`crate::linear_gradient`], [`crate::radial_gradient`] or
+    /// [`crate::conic_gradient`].
+    Gradient(Background),
 }
 
 impl Fill {
@@ -742,6 +930,15 @@ impl Fill {
     pub fn color(&self) -> Option<Hsla> {
         match self {
             Fill::Color(color) => Some(*color),
+            Fill::Gradient(_) => None,
+        }
+    }
+
+    /// Converts this fill into the background of a quad.
+    pub fn background(&self) -> Background {
+        match self {
+            Fill::Color(color) => (*color).into(),
+            Fill::Gradient(background) => *background,
         }
     }
 }
@@ -764,6 +961,15 @@ impl From<Rgba> for Fill {
     }
 }
 
+impl From<Background> for Fill {
+    fn from(background: Background) -> Self {
+        match background.color() {
+            Some(color) => Self::Color(color),
+            None => Self::Gradient(background),
+        }
+    }
+}
+
 impl From<TextStyle> for HighlightStyle {
     fn from(other: TextStyle) -> Self {
         Self::from(&other)
@@ -1,9 +1,10 @@
 use crate::{
-    self as gpui, px, relative, rems, AbsoluteLength, AlignItems, CursorStyle, DefiniteLength,
-    Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight, Hsla, JustifyContent, Length,
-    SharedString, StyleRefinement, WhiteSpace,
+    self as gpui, linear_gradient, px, relative, rems, AbsoluteLength, AlignItems, BlendMode,
+    CursorStyle, DefiniteLength, Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight, Hsla,
+    JustifyContent, LayoutDirection, Length, LinearColorStop, Pixels, SharedString,
+    StyleRefinement, WhiteSpace,
 };
-use crate::{TextStyleRefinement, Truncate};
+use crate::{TextStyleRefinement, Truncate, UnderlineKind};
 pub use gpui_macros::{
     border_style_methods, box_shadow_style_methods, cursor_style_methods, margin_style_methods,
     overflow_style_methods, padding_style_methods, position_style_methods,
@@ -323,6 +324,17 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the background of the element to a linear gradient, like CSS's `linear-gradient`.
+    /// See [`crate::linear_gradient`] for how the angle and stops are interpreted.
+    fn bg_gradient<S>(mut self, angle: f32, stops: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<LinearColorStop>,
+        Self: Sized,
+    {
+        self.style().background = Some(Fill::Gradient(linear_gradient(angle, stops)));
+        self
+    }
+
     /// Get the text style that has been configured on this element.
     fn text_style(&mut self) -> &mut Option<TextStyleRefinement> {
         let style: &mut StyleRefinement = self.style();
@@ -460,6 +472,23 @@ pub trait Styled: Sized {
         let style = self.text_style().get_or_insert_with(Default::default);
         let underline = style.underline.get_or_insert_with(Default::default);
         underline.wavy = false;
+        underline.kind = UnderlineKind::Single;
+        self
+    }
+
+    /// Set the underline to two parallel lines
+    fn text_decoration_double(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        let underline = style.underline.get_or_insert_with(Default::default);
+        underline.kind = UnderlineKind::Double;
+        self
+    }
+
+    /// Set the underline to a dotted line
+    fn text_decoration_dotted(mut self) -> Self {
+        let style = self.text_style().get_or_insert_with(Default::default);
+        let underline = style.underline.get_or_insert_with(Default::default);
+        underline.kind = UnderlineKind::Dotted;
         self
     }
 
@@ -553,6 +582,52 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Composite this element and its children as one group at the element's opacity, like the
+    /// CSS `opacity` property, rather than fading each thing they paint on its own. Overlapping
+    /// children then don't show through each other while the element fades, at the cost of
+    /// drawing the group to a texture first.
+    fn opacity_group(mut self) -> Self {
+        self.style().opacity_group = Some(true);
+        self
+    }
+
+    /// Set how this element and its children are blended with what was painted beneath them,
+    /// like the CSS `mix-blend-mode` property. Only applies to quads and sprites, such as
+    /// backgrounds, text and images.
+    fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.style().blend_mode = Some(blend_mode);
+        self
+    }
+
+    /// Paint this element and its children above the elements with a lower z-index, regardless
+    /// of their order in the element tree. The z-index is relative to that of the parent.
+    /// [Docs](https://tailwindcss.com/docs/z-index)
+    fn z_index(mut self, z_index: u8) -> Self {
+        self.style().z_index = Some(z_index);
+        self
+    }
+
+    /// Lay this element and its children out from left to right, even when the window's layout
+    /// direction is right to left, e.g. for code or for controls that mirror physical layouts.
+    fn ltr(mut self) -> Self {
+        self.style().layout_direction = Some(LayoutDirection::LeftToRight);
+        self
+    }
+
+    /// Lay this element and its children out from right to left, mirroring its rows, edges and
+    /// corners.
+    fn rtl(mut self) -> Self {
+        self.style().layout_direction = Some(LayoutDirection::RightToLeft);
+        self
+    }
+
+    /// Blur whatever is painted behind this element by the given radius.
+    /// [Docs](https://tailwindcss.com/docs/backdrop-blur)
+    fn backdrop_blur(mut self, radius: impl Into<Pixels>) -> Self {
+        self.style().backdrop_blur = Some(radius.into());
+        self
+    }
+
     /// Draw a debug border around this element.
     #[cfg(debug_assertions)]
     fn debug(mut self) -> Self {
@@ -0,0 +1,175 @@
+//! Optional bookkeeping of the tasks spawned on GPUI's executors, to help diagnose runaway
+//! background work and tasks that keep running after the entity that spawned them is gone.
+//!
+//! Tracking is disabled by default, and only tasks spawned while it is enabled are tracked.
+
+use crate::{AnyWeakModel, SharedString};
+use parking_lot::{const_mutex, Mutex};
+use std::{
+    collections::BTreeMap,
+    panic::Location,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering::SeqCst},
+    time::{Duration, Instant},
+};
+
+static TRACKING_ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_TASK_ID: AtomicUsize = AtomicUsize::new(0);
+static LIVE_TASKS: Mutex<BTreeMap<usize, TrackedTask>> = const_mutex(BTreeMap::new());
+
+/// Starts or stops tracking the tasks spawned from now on. Tasks that are already tracked
+/// remain so until they finish.
+pub fn set_task_tracking_enabled(enabled: bool) {
+    TRACKING_ENABLED.store(enabled, SeqCst);
+}
+
+/// Whether newly spawned tasks are tracked.
+pub fn task_tracking_enabled() -> bool {
+    TRACKING_ENABLED.load(SeqCst)
+}
+
+/// Returns the tracked tasks that are still running, oldest first.
+pub fn live_tasks() -> Vec<TrackedTask> {
+    LIVE_TASKS.lock().values().cloned().collect()
+}
+
+/// The executor a task was spawned on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TaskExecutor {
+    /// The task runs on the main thread.
+    Foreground,
+    /// The task runs on a background thread.
+    Background,
+}
+
+/// A task that was spawned while task tracking was enabled, and hasn't finished yet.
+#[derive(Clone)]
+pub struct TrackedTask {
+    /// The name given to the task when it was spawned, if any.
+    pub name: Option<SharedString>,
+    /// Where the task was spawned.
+    pub location: &'static Location<'static>,
+    /// The executor the task runs on.
+    pub executor: TaskExecutor,
+    /// When the task was spawned.
+    pub spawned_at: Instant,
+    owner: Option<TaskOwner>,
+}
+
+/// The model or view whose context spawned a task.
+#[derive(Clone)]
+pub(crate) struct TaskOwner {
+    pub entity: AnyWeakModel,
+    pub type_name: &'static str,
+}
+
+impl TrackedTask {
+    /// How long ago the task was spawned.
+    pub fn age(&self) -> Duration {
+        self.spawned_at.elapsed()
+    }
+
+    /// The type of the model or view that spawned the task, if it was spawned from their
+    /// context.
+    pub fn owner_type_name(&self) -> Option<&'static str> {
+        self.owner.as_ref().map(|owner| owner.type_name)
+    }
+
+    /// Whether the model or view that spawned the task has been released, even though the task
+    /// is still running. This usually means the task should have been stored and dropped along
+    /// with its owner instead of being detached.
+    pub fn outlived_owner(&self) -> bool {
+        self.owner
+            .as_ref()
+            .map_or(false, |owner| !owner.entity.is_upgradable())
+    }
+}
+
+/// Removes a task from the live tasks when dropped. Spawned futures hold on to it, so that
+/// happens both when the task completes and when it is cancelled.
+pub(crate) struct TaskGuard(usize);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        LIVE_TASKS.lock().remove(&self.0);
+    }
+}
+
+/// Starts tracking a task that is about to be spawned, if tracking is enabled.
+pub(crate) fn track(
+    name: Option<SharedString>,
+    location: &'static Location<'static>,
+    executor: TaskExecutor,
+    owner: Option<TaskOwner>,
+) -> Option<TaskGuard> {
+    if !task_tracking_enabled() {
+        return None;
+    }
+
+    let id = NEXT_TASK_ID.fetch_add(1, SeqCst);
+    LIVE_TASKS.lock().insert(
+        id,
+        TrackedTask {
+            name,
+            location,
+            executor,
+            spawned_at: Instant::now(),
+            owner,
+        },
+    );
+    Some(TaskGuard(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context as _, TestAppContext};
+    use futures::channel::oneshot;
+
+    #[crate::test]
+    fn test_task_tracking(cx: &mut TestAppContext) {
+        // Other tests can spawn tasks concurrently, so only the ones spawned here are checked.
+        let tasks_spawned_here = || {
+            live_tasks()
+                .into_iter()
+                .filter(|task| task.location.file() == file!())
+                .collect::<Vec<_>>()
+        };
+
+        set_task_tracking_enabled(true);
+        let model = cx.new_model(|_| ());
+        let (tx, rx) = oneshot::channel::<()>();
+        model.update(cx, |_, cx| {
+            cx.spawn(|_, _| async move {
+                rx.await.ok();
+            })
+            .detach()
+        });
+        let background_task = cx
+            .executor()
+            .spawn_named("pending", futures::future::pending::<()>());
+        set_task_tracking_enabled(false);
+        cx.run_until_parked();
+
+        let mut tasks = tasks_spawned_here();
+        tasks.sort_by_key(|task| task.executor);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].executor, TaskExecutor::Foreground);
+        assert_eq!(tasks[0].owner_type_name(), Some("()"));
+        assert!(!tasks[0].outlived_owner());
+        assert_eq!(tasks[1].executor, TaskExecutor::Background);
+        assert_eq!(tasks[1].name.as_deref(), Some("pending"));
+        assert_eq!(tasks[1].owner_type_name(), None);
+
+        drop(model);
+        cx.run_until_parked();
+        let tasks = tasks_spawned_here();
+        assert!(tasks
+            .iter()
+            .any(|task| task.executor == TaskExecutor::Foreground && task.outlived_owner()));
+
+        tx.send(()).unwrap();
+        drop(background_task);
+        cx.run_until_parked();
+        assert!(tasks_spawned_here().is_empty());
+    }
+}
@@ -1,11 +1,13 @@
 mod font_fallbacks;
 mod font_features;
+mod glyph_outline;
 mod line;
 mod line_layout;
 mod line_wrapper;
 
 pub use font_fallbacks::*;
 pub use font_features::*;
+pub(crate) use glyph_outline::*;
 pub use line::*;
 pub use line_layout::*;
 pub use line_wrapper::*;
@@ -13,8 +15,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    px, Bounds, DevicePixels, Hsla, Pixels, PlatformTextSystem, Point, Result, SharedString, Size,
-    StrikethroughStyle, UnderlineStyle,
+    px, Bounds, DevicePixels, Hsla, Pixels, PlatformTextSystem, Point, Result, ScaledPixels,
+    SharedString, Size, StrikethroughStyle, UnderlineStyle,
 };
 use anyhow::anyhow;
 use collections::FxHashMap;
@@ -43,6 +45,20 @@ pub struct FontFamilyId(pub usize);
 
 pub(crate) const SUBPIXEL_VARIANTS: u8 = 4;
 
+/// Splits a position in scaled pixels into the whole pixel a glyph is drawn at and the subpixel
+/// variant of the glyph that's rasterized for it, picking the variant closest to the fractional
+/// part so that glyphs move smoothly as their position changes rather than jumping between
+/// variants a whole step too early.
+pub(crate) fn subpixel_position(position: ScaledPixels) -> (ScaledPixels, u8) {
+    let pixel = position.floor();
+    let variant = ((position.0 - pixel.0) * SUBPIXEL_VARIANTS as f32).round() as u8;
+    if variant >= SUBPIXEL_VARIANTS {
+        (pixel + ScaledPixels(1.), 0)
+    } else {
+        (pixel, variant)
+    }
+}
+
 /// The GPUI text rendering sub system.
 pub struct TextSystem {
     platform_text_system: Arc<dyn PlatformTextSystem>,
@@ -288,6 +304,17 @@ impl TextSystem {
         self.platform_text_system
             .rasterize_glyph(params, raster_bounds)
     }
+
+    /// Get the outline of a glyph for rasterizing it on the GPU, or `None` if it can only be
+    /// rasterized on the CPU, like color glyphs.
+    pub(crate) fn glyph_outline(&self, params: &RenderGlyphParams) -> Result<Option<GlyphOutline>> {
+        if params.is_emoji {
+            return Ok(None);
+        }
+        let raster_bounds = self.raster_bounds(params)?;
+        self.platform_text_system
+            .glyph_outline(params, raster_bounds)
+    }
 }
 
 /// The GPUI text layout subsystem.
@@ -792,3 +819,35 @@ impl FontMetrics {
         (self.bounding_box / self.units_per_em as f32 * font_size.0).map(px)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subpixel_position() {
+        assert_eq!(subpixel_position(ScaledPixels(10.)), (ScaledPixels(10.), 0));
+        assert_eq!(
+            subpixel_position(ScaledPixels(10.1)),
+            (ScaledPixels(10.), 0)
+        );
+        assert_eq!(
+            subpixel_position(ScaledPixels(10.2)),
+            (ScaledPixels(10.), 1)
+        );
+        assert_eq!(
+            subpixel_position(ScaledPixels(10.5)),
+            (ScaledPixels(10.), 2)
+        );
+        assert_eq!(
+            subpixel_position(ScaledPixels(10.7)),
+            (ScaledPixels(10.), 3)
+        );
+        // Positions closer to the next pixel than to the last variant snap to it.
+        assert_eq!(
+            subpixel_position(ScaledPixels(10.9)),
+            (ScaledPixels(11.), 0)
+        );
+        assert_eq!(subpixel_position(ScaledPixels(-0.1)), (ScaledPixels(0.), 0));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{point, DevicePixels, Point, Size};
+
+/// How far, in device pixels, the straight segments a curve is flattened into may stray from it.
+const FLATTENING_TOLERANCE: f32 = 0.1;
+
+/// The most segments a single curve is flattened into.
+const MAX_CURVE_SEGMENTS: usize = 32;
+
+/// The outline of a glyph as straight segments, in device pixels relative to the top left corner
+/// of the glyph's atlas tile, which the GPU fills by the nonzero winding rule.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct GlyphOutline {
+    pub size: Size<DevicePixels>,
+    pub segments: Vec<GlyphOutlineSegment>,
+}
+
+/// A segment of a glyph outline. Horizontal segments don't affect the winding number of any
+/// point, so outlines don't contain them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub(crate) struct GlyphOutlineSegment {
+    pub from: Point<f32>,
+    pub to: Point<f32>,
+}
+
+/// Builds a [`GlyphOutline`] from the contours of a glyph, flattening their curves.
+pub(crate) struct GlyphOutlineBuilder {
+    outline: GlyphOutline,
+    start: Point<f32>,
+    current: Point<f32>,
+}
+
+impl GlyphOutlineBuilder {
+    pub fn new(size: Size<DevicePixels>) -> Self {
+        Self {
+            outline: GlyphOutline {
+                size,
+                segments: Vec::new(),
+            },
+            start: Point::default(),
+            current: Point::default(),
+        }
+    }
+
+    /// Starts a new contour at the given point, closing the current one.
+    pub fn move_to(&mut self, to: Point<f32>) {
+        self.close();
+        self.start = to;
+        self.current = to;
+    }
+
+    pub fn line_to(&mut self, to: Point<f32>) {
+        if to.y != self.current.y {
+            self.outline.segments.push(GlyphOutlineSegment {
+                from: self.current,
+                to,
+            });
+        }
+        self.current = to;
+    }
+
+    pub fn quadratic_to(&mut self, ctrl: Point<f32>, to: Point<f32>) {
+        let from = self.current;
+        let count = segment_count(distance(midpoint(from, to), ctrl) / 2.);
+        for step in 1..=count {
+            let t = step as f32 / count as f32;
+            let u = 1. - t;
+            self.line_to(point(
+                u * u * from.x + 2. * u * t * ctrl.x + t * t * to.x,
+                u * u * from.y + 2. * u * t * ctrl.y + t * t * to.y,
+            ));
+        }
+    }
+
+    pub fn cubic_to(&mut self, ctrl_a: Point<f32>, ctrl_b: Point<f32>, to: Point<f32>) {
+        let from = self.current;
+        let deviation = distance(from, ctrl_a)
+            .max(distance(ctrl_a, ctrl_b))
+            .max(distance(ctrl_b, to))
+            * 0.75;
+        let count = segment_count(deviation);
+        for step in 1..=count {
+            let t = step as f32 / count as f32;
+            let u = 1. - t;
+            let (a, b, c, d) = (u * u * u, 3. * u * u * t, 3. * u * t * t, t * t * t);
+            self.line_to(point(
+                a * from.x + b * ctrl_a.x + c * ctrl_b.x + d * to.x,
+                a * from.y + b * ctrl_a.y + c * ctrl_b.y + d * to.y,
+            ));
+        }
+    }
+
+    /// Closes the current contour with a line back to its start.
+    pub fn close(&mut self) {
+        if self.current != self.start {
+            self.line_to(self.start);
+        }
+    }
+
+    pub fn build(mut self) -> GlyphOutline {
+        self.close();
+        self.outline
+    }
+}
+
+/// How many straight segments approximate a curve whose control points stray from its chord by
+/// the given distance, within [`FLATTENING_TOLERANCE`].
+fn segment_count(deviation: f32) -> usize {
+    ((deviation / FLATTENING_TOLERANCE).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+fn midpoint(a: Point<f32>, b: Point<f32>) -> Point<f32> {
+    point((a.x + b.x) / 2., (a.y + b.y) / 2.)
+}
+
+fn distance(a: Point<f32>, b: Point<f32>) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::size;
+
+    #[test]
+    fn test_building_glyph_outlines() {
+        let mut builder = GlyphOutlineBuilder::new(size(DevicePixels(10), DevicePixels(10)));
+        builder.move_to(point(1., 1.));
+        builder.line_to(point(9., 1.));
+        builder.line_to(point(9., 9.));
+        builder.quadratic_to(point(5., 13.), point(1., 9.));
+
+        // The second contour is closed by moving to the third, and the third one when building.
+        builder.move_to(point(3., 3.));
+        builder.line_to(point(3., 5.));
+        builder.move_to(point(6., 3.));
+        builder.line_to(point(6., 5.));
+        builder.line_to(point(7., 5.));
+        let outline = builder.build();
+
+        // Horizontal segments are left out.
+        assert!(outline
+            .segments
+            .iter()
+            .all(|segment| segment.from.y != segment.to.y));
+        // Curves are flattened into segments that stay close to them.
+        let curve_segments = outline
+            .segments
+            .iter()
+            .filter(|segment| segment.from.y > 9. || segment.to.y > 9.)
+            .collect::<Vec<_>>();
+        assert!(curve_segments.len() > 2);
+        assert!(curve_segments
+            .iter()
+            .all(|segment| segment.to.y <= 11. && distance(segment.from, segment.to) < 4.));
+        // Every contour ends where it started.
+        assert_eq!(outline.segments.last().unwrap().to, point(6., 3.));
+        assert!(outline
+            .segments
+            .iter()
+            .any(|segment| segment.from == point(3., 5.) && segment.to == point(3., 3.)));
+        assert!(outline
+            .segments
+            .iter()
+            .any(|segment| segment.from == point(1., 9.) && segment.to == point(1., 1.)));
+    }
+}
@@ -207,6 +207,8 @@ fn paint_line(
                                     color: Some(run_underline.color.unwrap_or(style_run.color)),
                                     thickness: run_underline.thickness,
                                     wavy: run_underline.wavy,
+                                    kind: run_underline.kind,
+                                    dash_pattern: run_underline.dash_pattern,
                                 },
                             ));
                         }
@@ -1,5 +1,6 @@
 use crate::{point, px, FontId, GlyphId, Pixels, PlatformTextSystem, Point, Size};
 use collections::FxHashMap;
+use itertools::Itertools as _;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use smallvec::SmallVec;
 use std::{
@@ -8,6 +9,7 @@ use std::{
     ops::Range,
     sync::Arc,
 };
+use unicode_segmentation::GraphemeCursor;
 
 use super::LineWrapper;
 
@@ -26,6 +28,9 @@ pub struct LineLayout {
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// The positions of the character boundaries inside ligatures, which are drawn as a single
+    /// glyph, as pairs of an index in the original text and an x coordinate.
+    pub ligature_carets: Vec<(usize, Pixels)>,
 }
 
 /// A run of text that has been shaped .
@@ -59,14 +64,12 @@ impl LineLayout {
         if x >= self.width {
             None
         } else {
-            for run in self.runs.iter().rev() {
-                for glyph in run.glyphs.iter().rev() {
-                    if glyph.position.x <= x {
-                        return Some(glyph.index);
-                    }
-                }
-            }
-            Some(0)
+            Some(
+                self.carets()
+                    .filter(|(_, caret_x)| *caret_x <= x)
+                    .last()
+                    .map_or(0, |(index, _)| index),
+            )
         }
     }
 
@@ -76,18 +79,16 @@ impl LineLayout {
         let mut prev_index = 0;
         let mut prev_x = px(0.);
 
-        for run in self.runs.iter() {
-            for glyph in run.glyphs.iter() {
-                if glyph.position.x >= x {
-                    if glyph.position.x - x < x - prev_x {
-                        return glyph.index;
-                    } else {
-                        return prev_index;
-                    }
+        for (index, caret_x) in self.carets() {
+            if caret_x >= x {
+                if caret_x - x < x - prev_x {
+                    return index;
+                } else {
+                    return prev_index;
                 }
-                prev_index = glyph.index;
-                prev_x = glyph.position.x;
             }
+            prev_index = index;
+            prev_x = caret_x;
         }
 
         self.len
@@ -95,16 +96,67 @@ impl LineLayout {
 
     /// The x position of the character at the given index
     pub fn x_for_index(&self, index: usize) -> Pixels {
-        for run in &self.runs {
-            for glyph in &run.glyphs {
-                if glyph.index >= index {
-                    return glyph.position.x;
-                }
+        for (caret_index, x) in self.carets() {
+            if caret_index >= index {
+                return x;
             }
         }
         self.width
     }
 
+    /// The positions a caret can be placed at, in the order of the text: the start of every
+    /// glyph, and the character boundaries inside ligatures.
+    fn carets(&self) -> impl Iterator<Item = (usize, Pixels)> + '_ {
+        self.runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter())
+            .map(|glyph| (glyph.index, glyph.position.x))
+            .merge_by(self.ligature_carets.iter().copied(), |glyph, caret| {
+                glyph.0 <= caret.0
+            })
+    }
+
+    /// Splits the advance of every glyph drawing several graphemes, like a ligature, evenly
+    /// between them, so that a caret can be placed between the characters it draws.
+    fn compute_ligature_carets(&self, text: &str) -> Vec<(usize, Pixels)> {
+        let mut carets = Vec::new();
+        let mut glyphs = self
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter())
+            .map(|glyph| (glyph.index, glyph.position.x))
+            .peekable();
+        while let Some((start, start_x)) = glyphs.next() {
+            // Glyphs combined into a single cluster, like a letter and its accent, share an index.
+            while glyphs.peek().map_or(false, |(index, _)| *index == start) {
+                glyphs.next();
+            }
+            let (end, end_x) = glyphs.peek().copied().unwrap_or((self.len, self.width));
+            // Right-to-left text is shaped in visual order, and isn't split.
+            if end <= start || end > text.len() || text[start..end].chars().nth(1).is_none() {
+                continue;
+            }
+
+            let mut boundaries = Vec::new();
+            let mut cursor = GraphemeCursor::new(start, text.len(), true);
+            while let Ok(Some(boundary)) = cursor.next_boundary(text, 0) {
+                if boundary >= end {
+                    break;
+                }
+                boundaries.push(boundary);
+            }
+            let grapheme_count = boundaries.len() + 1;
+            let grapheme_width = (end_x - start_x) / grapheme_count as f32;
+            carets.extend(
+                boundaries
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, boundary)| (boundary, start_x + grapheme_width * (ix + 1) as f32)),
+            );
+        }
+        carets
+    }
+
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         for run in &self.runs {
@@ -497,7 +549,9 @@ impl LineLayoutCache {
             current_frame.used_lines.push(key);
             layout
         } else {
-            let layout = Arc::new(self.platform_text_system.layout_line(text, font_size, runs));
+            let mut layout = self.platform_text_system.layout_line(text, font_size, runs);
+            layout.ligature_carets = layout.compute_ligature_carets(text);
+            let layout = Arc::new(layout);
             let key = Arc::new(CacheKey {
                 text: text.into(),
                 font_size,
@@ -586,3 +640,58 @@ impl<'a> AsCacheKeyRef for CacheKeyRef<'a> {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smallvec::smallvec;
+
+    fn glyph(index: usize, x: f32) -> ShapedGlyph {
+        ShapedGlyph {
+            id: GlyphId(0),
+            position: point(px(x), px(0.)),
+            index,
+            is_emoji: false,
+        }
+    }
+
+    #[test]
+    fn test_ligature_carets() {
+        // "a->b", with "->" drawn as a single glyph twice as wide as the others.
+        let text = "a->b";
+        let mut layout = LineLayout {
+            width: px(40.),
+            runs: vec![ShapedRun {
+                font_id: FontId(0),
+                glyphs: smallvec![glyph(0, 0.), glyph(1, 10.), glyph(3, 30.)],
+            }],
+            len: text.len(),
+            ..Default::default()
+        };
+        layout.ligature_carets = layout.compute_ligature_carets(text);
+        assert_eq!(layout.ligature_carets, vec![(2, px(20.))]);
+
+        assert_eq!(layout.x_for_index(1), px(10.));
+        assert_eq!(layout.x_for_index(2), px(20.));
+        assert_eq!(layout.x_for_index(3), px(30.));
+        assert_eq!(layout.index_for_x(px(19.)), Some(1));
+        assert_eq!(layout.index_for_x(px(25.)), Some(2));
+        assert_eq!(layout.closest_index_for_x(px(14.)), 1);
+        assert_eq!(layout.closest_index_for_x(px(17.)), 2);
+        assert_eq!(layout.closest_index_for_x(px(26.)), 3);
+
+        // Grapheme clusters drawn as a single glyph, like emoji sequences, aren't split.
+        let text = "👍🏽!";
+        let mut layout = LineLayout {
+            width: px(30.),
+            runs: vec![ShapedRun {
+                font_id: FontId(0),
+                glyphs: smallvec![glyph(0, 0.), glyph(8, 20.)],
+            }],
+            len: text.len(),
+            ..Default::default()
+        };
+        layout.ligature_carets = layout.compute_ligature_carets(text);
+        assert!(layout.ligature_carets.is_empty());
+    }
+}
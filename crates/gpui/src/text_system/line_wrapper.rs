@@ -35,6 +35,27 @@ impl LineWrapper {
         line: &'a str,
         wrap_width: Pixels,
     ) -> impl Iterator<Item = Boundary> + 'a {
+        self.wrap_line_with_options(line, wrap_width, WrapOptions::default())
+    }
+
+    /// Wrap a line of text to the given width, choosing where lines can break and how
+    /// continuation lines are indented with the given options.
+    ///
+    /// The width taken by the markers is reserved, so that the marked lines still fit.
+    pub fn wrap_line_with_options<'a>(
+        &'a mut self,
+        line: &'a str,
+        wrap_width: Pixels,
+        options: WrapOptions,
+    ) -> impl Iterator<Item = Boundary> + 'a {
+        let wrap_width = match options.end_marker {
+            Some(marker) => wrap_width - self.width_for_char(marker),
+            None => wrap_width,
+        };
+        let continuation_width = match options.continuation_marker {
+            Some(marker) => self.width_for_char(marker),
+            None => px(0.),
+        };
         let mut width = px(0.);
         let mut first_non_whitespace_ix = None;
         let mut indent = None;
@@ -49,17 +70,21 @@ impl LineWrapper {
                     continue;
                 }
 
-                if Self::is_word_char(c) {
-                    if prev_c == ' ' && c != ' ' && first_non_whitespace_ix.is_some() {
-                        last_candidate_ix = ix;
-                        last_candidate_width = width;
-                    }
-                } else {
-                    // CJK may not be space separated, e.g.: `Hello world你好世界`
-                    if c != ' ' && first_non_whitespace_ix.is_some() {
-                        last_candidate_ix = ix;
-                        last_candidate_width = width;
+                let is_candidate = match options.break_at {
+                    WrapBreak::Word => {
+                        if Self::is_word_char(c) {
+                            prev_c == ' ' && c != ' '
+                        } else {
+                            // CJK may not be space separated, e.g.: `Hello world你好世界`
+                            c != ' '
+                        }
                     }
+                    WrapBreak::Whitespace => prev_c == ' ' && c != ' ',
+                    WrapBreak::Anywhere => false,
+                };
+                if is_candidate && first_non_whitespace_ix.is_some() {
+                    last_candidate_ix = ix;
+                    last_candidate_width = width;
                 }
 
                 if c != ' ' && first_non_whitespace_ix.is_none() {
@@ -69,11 +94,20 @@ impl LineWrapper {
                 let char_width = self.width_for_char(c);
                 width += char_width;
                 if width > wrap_width && ix > last_wrap_ix {
+                    if last_candidate_ix == 0 && options.break_at == WrapBreak::Whitespace {
+                        // Let tokens that are too long overflow rather than splitting them.
+                        prev_c = c;
+                        continue;
+                    }
+
                     if let (None, Some(first_non_whitespace_ix)) = (indent, first_non_whitespace_ix)
                     {
-                        indent = Some(
-                            Self::MAX_INDENT.min((first_non_whitespace_ix - last_wrap_ix) as u32),
-                        );
+                        let leading_whitespace = (first_non_whitespace_ix - last_wrap_ix) as u32;
+                        indent = Some(Self::MAX_INDENT.min(match options.indent {
+                            WrapIndent::Aligned => leading_whitespace,
+                            WrapIndent::Extra(extra) => leading_whitespace.saturating_add(extra),
+                            WrapIndent::None => 0,
+                        }));
                     }
 
                     if last_candidate_ix > 0 {
@@ -88,6 +122,7 @@ impl LineWrapper {
                     if let Some(indent) = indent {
                         width += self.width_for_char(' ') * indent as f32;
                     }
+                    width += continuation_width;
 
                     return Some(Boundary::new(last_wrap_ix, indent.unwrap_or(0)));
                 }
@@ -195,6 +230,43 @@ impl LineWrapper {
     }
 }
 
+/// Options controlling how [`LineWrapper::wrap_line_with_options`] wraps a line.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// Where lines can be broken.
+    pub break_at: WrapBreak,
+    /// How continuation lines are indented.
+    pub indent: WrapIndent,
+    /// A character displayed at the start of continuation lines, before their indent.
+    pub continuation_marker: Option<char>,
+    /// A character displayed at the end of lines that were wrapped.
+    pub end_marker: Option<char>,
+}
+
+/// Where a line can be broken when wrapping it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WrapBreak {
+    /// Break between words, splitting words that don't fit on a line by themselves.
+    #[default]
+    Word,
+    /// Break only after whitespace, letting words that don't fit on a line overflow it.
+    Whitespace,
+    /// Break at any character.
+    Anywhere,
+}
+
+/// How the continuation lines of a wrapped line are indented.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WrapIndent {
+    /// Align continuation lines with the first non-whitespace character of the line.
+    #[default]
+    Aligned,
+    /// Indent continuation lines by the given number of columns more than the line.
+    Extra(u32),
+    /// Don't indent continuation lines.
+    None,
+}
+
 /// A boundary between two lines of text.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Boundary {
@@ -289,6 +361,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wrap_line_with_options() {
+        let mut wrapper = build_wrapper();
+        let mut wrap = |line: &str, options: WrapOptions| {
+            wrapper
+                .wrap_line_with_options(line, px(72.), options)
+                .collect::<Vec<_>>()
+        };
+
+        let whitespace = WrapOptions {
+            break_at: WrapBreak::Whitespace,
+            ..Default::default()
+        };
+        assert_eq!(
+            wrap("aaa aaaaaaaaaaaaaaaaaa", whitespace),
+            &[Boundary::new(4, 0)]
+        );
+        let anywhere = WrapOptions {
+            break_at: WrapBreak::Anywhere,
+            ..Default::default()
+        };
+        assert_eq!(
+            wrap("aaa aaaaaaaaaaaaaaaaaa", anywhere),
+            &[
+                Boundary::new(7, 0),
+                Boundary::new(14, 0),
+                Boundary::new(21, 0)
+            ]
+        );
+
+        let extra_indent = WrapOptions {
+            indent: WrapIndent::Extra(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            wrap("  aaaaaaaaaa", extra_indent),
+            &[Boundary::new(7, 4), Boundary::new(10, 4)]
+        );
+        let no_indent = WrapOptions {
+            indent: WrapIndent::None,
+            ..Default::default()
+        };
+        assert_eq!(wrap("  aaaaaaaaaa", no_indent), &[Boundary::new(7, 0)]);
+
+        let markers = WrapOptions {
+            continuation_marker: Some('>'),
+            end_marker: Some('<'),
+            ..Default::default()
+        };
+        assert_eq!(
+            wrap("aaaaaaaaaaaaaa", markers),
+            &[Boundary::new(6, 0), Boundary::new(11, 0)]
+        );
+    }
+
     #[test]
     fn test_truncate_line() {
         let mut wrapper = build_wrapper();
@@ -227,6 +227,7 @@ pub struct AnyView {
     model: AnyModel,
     render: fn(&AnyView, &mut WindowContext) -> AnyElement,
     cached_style: Option<StyleRefinement>,
+    cache_to_texture: bool,
 }
 
 impl AnyView {
@@ -238,6 +239,15 @@ impl AnyView {
         self
     }
 
+    /// Indicate that this view should be drawn to a texture that's kept from frame to frame, and
+    /// only drawn again when the view paints something different. This suits large views that
+    /// rarely change, and works best together with [AnyView::cached].
+    /// See [WindowContext::with_cached_texture] for more details.
+    pub fn cache_to_texture(mut self) -> Self {
+        self.cache_to_texture = true;
+        self
+    }
+
     /// Convert this to a weak handle.
     pub fn downgrade(&self) -> AnyWeakView {
         AnyWeakView {
@@ -255,6 +265,7 @@ impl AnyView {
                 model,
                 render: self.render,
                 cached_style: self.cached_style,
+                cache_to_texture: self.cache_to_texture,
             }),
         }
     }
@@ -276,6 +287,7 @@ impl<V: Render> From<View<V>> for AnyView {
             model: value.model.into_any(),
             render: any_view::render::<V>,
             cached_style: None,
+            cache_to_texture: false,
         }
     }
 }
@@ -374,6 +386,24 @@ impl Element for AnyView {
         _: &mut Self::RequestLayoutState,
         element: &mut Self::PrepaintState,
         cx: &mut WindowContext,
+    ) {
+        if self.cache_to_texture {
+            let global_id = global_id.unwrap();
+            cx.with_cached_texture(global_id, |cx| {
+                self.paint_view(Some(global_id), element, cx)
+            })
+        } else {
+            self.paint_view(global_id, element, cx)
+        }
+    }
+}
+
+impl AnyView {
+    fn paint_view(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        element: &mut Option<AnyElement>,
+        cx: &mut WindowContext,
     ) {
         if self.cached_style.is_some() {
             cx.with_element_state::<AnyViewState, _>(global_id.unwrap(), |element_state, cx| {
@@ -428,6 +458,7 @@ impl AnyWeakView {
             model,
             render: self.render,
             cached_style: None,
+            cache_to_texture: false,
         })
     }
 }
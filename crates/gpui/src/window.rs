@@ -1,28 +1,32 @@
+#[cfg(target_os = "macos")]
+use crate::SurfaceSource;
 use crate::{
-    point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
-    AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Bounds, BoxShadow,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, Flatten, FontId, GPUSpecs, Global, GlobalElementId, GlyphId, Hsla, InputHandler,
-    IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent,
-    KeystrokeObserver, LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers,
+    hash, point, prelude::*, px, radians, size, subpixel_position, task_tracking_enabled,
+    transparent_black, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Arena, Asset,
+    AsyncWindowContext, AtlasStats, AvailableSpace, BackdropBlur, Background, BlendMode, Bounds,
+    BoxShadow, Context, Corners, CursorStyle, CustomPrimitive, CustomShaderId, Decorations,
+    DevicePixels, DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect,
+    Entity, EntityId, EventEmitter, FileDropEvent, Flatten, FontId, GPUSpecs, Global,
+    GlobalElementId, GlyphId, Hsla, Image, ImageFormat, InputHandler, IsZero, KeyBinding,
+    KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, KeystrokeObserver,
+    LayoutDirection, LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers,
     ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent,
     Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
-    PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams,
-    RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge, ScaledPixels, Scene,
-    Shadow, SharedString, Size, StrikethroughStyle, Style, SubscriberSet, Subscription,
-    TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement, TransformationMatrix, Underline,
-    UnderlineStyle, View, VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
-    SUBPIXEL_VARIANTS,
+    PlatformWebView, PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Radians,
+    RecordingAtlas, Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
+    Replay, ResizeEdge, ScaledPixels, Scene, SceneRecorder, SceneRecording, SceneStats,
+    SerializedPrimitive, SerializedScene, Shadow, SharedString, Size, StrikethroughStyle, Style,
+    SubscriberSet, Subscription, TaffyLayoutEngine, Task, TaskOwner, TextStyle,
+    TextStyleRefinement, TransformationMatrix, Underline, UnderlineKind, UnderlineStyle, View,
+    VisualContext, WeakView, WebView, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
+    MAX_CUSTOM_SHADER_UNIFORMS,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{FxHashMap, FxHashSet};
 use derive_more::{Deref, DerefMut};
 use futures::channel::oneshot;
 use futures::FutureExt;
-#[cfg(target_os = "macos")]
-use media::core_video::CVImageBuffer;
 use parking_lot::RwLock;
 use refineable::Refineable;
 use slotmap::SlotMap;
@@ -55,6 +59,24 @@ pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
 
+/// The z-index prompts, dragged elements and tooltips are drawn at, above every other element.
+const OVERLAY_Z_INDEX: u8 = u8::MAX;
+
+/// The minimum time between two runs of the next frame callbacks, which drive animations, while
+/// the machine should conserve power. This caps animations at about 30 frames per second.
+const CONSERVING_POWER_FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+/// How far a wavy underline's wave reaches above and below its center line, relative to the
+/// underline's thickness.
+const WAVY_UNDERLINE_AMPLITUDE: f32 = 1.;
+
+/// The length of one period of a wavy underline's wave, relative to the underline's thickness.
+const WAVY_UNDERLINE_WAVELENGTH: f32 = 6.;
+
+/// The most textures kept for subtrees painted with [`WindowContext::with_cached_texture`].
+/// Each covers the whole window, so the subtrees painted after this many are drawn every frame.
+const MAX_CACHED_TEXTURES: usize = 4;
+
 /// Represents the two different phases when dispatching events.
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
 pub enum DispatchPhase {
@@ -321,15 +343,112 @@ pub(crate) struct CursorStyleRequest {
     pub(crate) style: CursorStyle,
 }
 
+#[derive(Clone)]
+pub(crate) struct WebViewRequest {
+    pub(crate) web_view_id: EntityId,
+    pub(crate) platform: Rc<dyn PlatformWebView>,
+    pub(crate) bounds: Bounds<Pixels>,
+    pub(crate) clip: Bounds<Pixels>,
+}
+
 /// An identifier for a [Hitbox].
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct HitboxId(usize);
 
 impl HitboxId {
-    /// Checks if the hitbox with this id is currently hovered.
+    /// Checks if the hitbox with this id is currently hovered. For hitboxes with
+    /// [HoverDelays], this is the hover state once the delays have elapsed.
     pub fn is_hovered(&self, cx: &WindowContext) -> bool {
-        cx.window.mouse_hit_test.0.contains(self)
+        let window = &cx.window;
+        let delayed_hover = window
+            .next_frame
+            .hover_delays
+            .get(self)
+            .or_else(|| window.rendered_frame.hover_delays.get(self))
+            .and_then(|request| window.delayed_hovers.get(&request.element_id));
+        if let Some(delayed_hover) = delayed_hover {
+            return delayed_hover.hovered;
+        }
+
+        window.mouse_hit_test.0.contains(self)
+    }
+}
+
+/// How long the mouse has to stay over a hitbox, or away from it, before its hover state
+/// changes. This keeps hover styles from flickering when the mouse skims across elements.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct HoverDelays {
+    /// How long the mouse has to be over the hitbox before it becomes hovered.
+    pub enter: Duration,
+    /// How long the mouse has to be away from the hitbox before it stops being hovered.
+    pub leave: Duration,
+}
+
+pub(crate) struct HoverDelayRequest {
+    element_id: GlobalElementId,
+    delays: HoverDelays,
+}
+
+/// The hover state of an element with [HoverDelays], which lags behind the hit test.
+pub(crate) struct DelayedHover {
+    hovered: bool,
+    /// The hover state the element is switching to, and when it will switch.
+    pending: Option<(bool, Instant)>,
+}
+
+/// Updates the delayed hover state of the elements that requested [HoverDelays] in the given
+/// frame, dropping the state of the ones that are no longer painted. Returns when the next
+/// pending change is due.
+fn update_delayed_hovers(
+    frame: &Frame,
+    hit_test: &HitTest,
+    delayed_hovers: &mut FxHashMap<GlobalElementId, DelayedHover>,
+    now: Instant,
+) -> Option<Instant> {
+    let mut next_change = None::<Instant>;
+    let mut updated_hovers = FxHashMap::default();
+    for (hitbox_id, request) in &frame.hover_delays {
+        let hovered = hit_test.0.contains(hitbox_id);
+        let mut delayed_hover =
+            delayed_hovers
+                .remove(&request.element_id)
+                .unwrap_or(DelayedHover {
+                    hovered,
+                    pending: None,
+                });
+
+        if let Some((pending_hovered, at)) = delayed_hover.pending {
+            if at <= now {
+                delayed_hover.hovered = pending_hovered;
+                delayed_hover.pending = None;
+            }
+        }
+
+        if hovered == delayed_hover.hovered {
+            delayed_hover.pending = None;
+        } else if delayed_hover
+            .pending
+            .map_or(true, |(pending_hovered, _)| pending_hovered != hovered)
+        {
+            let delay = if hovered {
+                request.delays.enter
+            } else {
+                request.delays.leave
+            };
+            if delay.is_zero() {
+                delayed_hover.hovered = hovered;
+            } else {
+                delayed_hover.pending = Some((hovered, now + delay));
+            }
+        }
+
+        if let Some((_, at)) = delayed_hover.pending {
+            next_change = Some(next_change.map_or(at, |next_change| next_change.min(at)));
+        }
+        updated_hovers.insert(GlobalElementId(request.element_id.0.clone()), delayed_hover);
     }
+    *delayed_hovers = updated_hovers;
+    next_change
 }
 
 /// A rectangular region that potentially blocks hitboxes inserted prior.
@@ -343,8 +462,13 @@ pub struct Hitbox {
     pub bounds: Bounds<Pixels>,
     /// The content mask when the hitbox was inserted.
     pub content_mask: ContentMask<Pixels>,
+    /// The transformation when the hitbox was inserted, in logical pixels.
+    pub transformation: TransformationMatrix,
     /// Whether the hitbox occludes other hitboxes inserted prior.
     pub opaque: bool,
+    /// The z-index the hitbox was inserted at. Hitboxes with a higher z-index are hit first,
+    /// regardless of the order they were inserted in.
+    pub z_index: u8,
 }
 
 impl Hitbox {
@@ -389,6 +513,8 @@ pub(crate) struct DeferredDraw {
     parent_node: DispatchNodeId,
     element_id_stack: SmallVec<[ElementId; 32]>,
     text_style_stack: Vec<TextStyleRefinement>,
+    z_index: u8,
+    layout_direction: LayoutDirection,
     element: Option<AnyElement>,
     absolute_offset: Point<Pixels>,
     prepaint_range: Range<PrepaintStateIndex>,
@@ -408,6 +534,8 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) web_views: Vec<WebViewRequest>,
+    pub(crate) hover_delays: FxHashMap<HitboxId, HoverDelayRequest>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -428,6 +556,7 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    web_views_index: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -447,6 +576,8 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            web_views: Vec::new(),
+            hover_delays: FxHashMap::default(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -462,15 +593,38 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.web_views.clear();
         self.hitboxes.clear();
+        self.hover_delays.clear();
         self.deferred_draws.clear();
         self.focus = None;
     }
 
     pub(crate) fn hit_test(&self, position: Point<Pixels>) -> HitTest {
+        if self.hitboxes.iter().any(|hitbox| hitbox.z_index > 0) {
+            // Hitboxes inserted later are hit first among those with the same z-index.
+            let mut hitboxes = self.hitboxes.iter().rev().collect::<Vec<_>>();
+            hitboxes.sort_by_key(|hitbox| cmp::Reverse(hitbox.z_index));
+            Self::hit_test_hitboxes(hitboxes, position)
+        } else {
+            Self::hit_test_hitboxes(self.hitboxes.iter().rev(), position)
+        }
+    }
+
+    fn hit_test_hitboxes<'a>(
+        hitboxes: impl IntoIterator<Item = &'a Hitbox>,
+        position: Point<Pixels>,
+    ) -> HitTest {
         let mut hit_test = HitTest::default();
-        for hitbox in self.hitboxes.iter().rev() {
+        for hitbox in hitboxes {
             let bounds = hitbox.bounds.intersect(&hitbox.content_mask.bounds);
+            let position = if hitbox.transformation == TransformationMatrix::unit() {
+                position
+            } else if let Some(inverse) = hitbox.transformation.inverse() {
+                inverse.apply(position)
+            } else {
+                continue;
+            };
             if bounds.contains(&position) {
                 hit_test.0.push(hitbox.id);
                 if hitbox.opaque {
@@ -520,9 +674,13 @@ pub struct Window {
     pub(crate) root_view: Option<AnyView>,
     pub(crate) element_id_stack: SmallVec<[ElementId; 32]>,
     pub(crate) text_style_stack: Vec<TextStyleRefinement>,
+    pub(crate) layout_direction_stack: Vec<LayoutDirection>,
+    pub(crate) blend_mode_stack: Vec<BlendMode>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) element_opacity: Option<f32>,
     pub(crate) content_mask_stack: Vec<ContentMask<Pixels>>,
+    pub(crate) transformation_stack: Vec<TransformationMatrix>,
+    pub(crate) z_index_stack: Vec<u8>,
     pub(crate) requested_autoscroll: Option<Bounds<Pixels>>,
     pub(crate) rendered_frame: Frame,
     pub(crate) next_frame: Frame,
@@ -537,16 +695,27 @@ pub struct Window {
     default_prevented: bool,
     mouse_position: Point<Pixels>,
     mouse_hit_test: HitTest,
+    delayed_hovers: FxHashMap<GlobalElementId, DelayedHover>,
+    delayed_hovers_task: Option<Task<()>>,
     modifiers: Modifiers,
     scale_factor: f32,
     bounds_observers: SubscriberSet<(), AnyObserver>,
     appearance: WindowAppearance,
     appearance_observers: SubscriberSet<(), AnyObserver>,
+    display_observers: SubscriberSet<(), AnyObserver>,
+    scale_factor_observers: SubscriberSet<(), AnyObserver>,
+    occluded: bool,
+    occlusion_observers: SubscriberSet<(), AnyObserver>,
     active: Rc<Cell<bool>>,
     hovered: Rc<Cell<bool>>,
     pub(crate) dirty: Rc<Cell<bool>>,
     pub(crate) needs_present: Rc<Cell<bool>>,
+    /// Whether the next frame has to be presented in full, rather than only where it differs
+    /// from the previous one.
+    needs_full_damage: Cell<bool>,
     pub(crate) last_input_timestamp: Rc<Cell<Instant>>,
+    dispatching_mouse_event: bool,
+    _power_state_subscription: Subscription,
     pub(crate) refreshing: bool,
     pub(crate) draw_phase: DrawPhase,
     activation_observers: SubscriberSet<(), AnyObserver>,
@@ -556,6 +725,10 @@ pub struct Window {
     pending_modifier: ModifierState,
     pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
+    scene_recorder: Option<SceneRecorder>,
+    /// The slots of the textures kept for the subtrees painted with `with_cached_texture`, by
+    /// the hash of their element id.
+    cached_texture_slots: FxHashMap<u64, usize>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -621,8 +794,10 @@ impl Window {
             display_id,
             window_background,
             app_id,
+            icon,
             window_min_size,
             window_decorations,
+            color_space,
         } = options;
 
         let bounds = window_bounds
@@ -639,6 +814,7 @@ impl Window {
                 show,
                 display_id,
                 window_min_size,
+                color_space,
             },
         )?;
         let display_id = platform_window.display().map(|display| display.id());
@@ -655,6 +831,11 @@ impl Window {
         let needs_present = Rc::new(Cell::new(false));
         let next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>> = Default::default();
         let last_input_timestamp = Rc::new(Cell::new(Instant::now()));
+        let conserve_power = Rc::new(Cell::new(cx.power_state().should_conserve_power()));
+        let power_state_subscription = cx.observe_power_state({
+            let conserve_power = conserve_power.clone();
+            move |power_state, _| conserve_power.set(power_state.should_conserve_power())
+        });
 
         platform_window
             .request_decorations(window_decorations.unwrap_or(WindowDecorations::Server));
@@ -681,9 +862,20 @@ impl Window {
             let needs_present = needs_present.clone();
             let next_frame_callbacks = next_frame_callbacks.clone();
             let last_input_timestamp = last_input_timestamp.clone();
+            let conserve_power = conserve_power.clone();
+            let mut last_callbacks_timestamp = Instant::now();
             move || {
-                let next_frame_callbacks = next_frame_callbacks.take();
+                // While conserving power, leave the callbacks queued for a later frame if they ran
+                // recently, which lowers the frame rate of animations.
+                let next_frame_callbacks = if conserve_power.get()
+                    && last_callbacks_timestamp.elapsed() < CONSERVING_POWER_FRAME_INTERVAL
+                {
+                    Vec::new()
+                } else {
+                    next_frame_callbacks.take()
+                };
                 if !next_frame_callbacks.is_empty() {
+                    last_callbacks_timestamp = Instant::now();
                     handle
                         .update(&mut cx, |_, cx| {
                             for callback in next_frame_callbacks {
@@ -694,9 +886,11 @@ impl Window {
                 }
 
                 // Keep presenting the current scene for 1 extra second since the
-                // last input to prevent the display from underclocking the refresh rate.
+                // last input to prevent the display from underclocking the refresh rate,
+                // unless we should conserve power.
                 let needs_present = needs_present.get()
                     || (active.get()
+                        && !conserve_power.get()
                         && last_input_timestamp.get().elapsed() < Duration::from_secs(1));
 
                 if dirty.get() {
@@ -743,6 +937,14 @@ impl Window {
                     .log_err();
             }
         }));
+        platform_window.on_occlusion_change(Box::new({
+            let mut cx = cx.to_async();
+            move |occluded| {
+                handle
+                    .update(&mut cx, |_, cx| cx.occlusion_changed(occluded))
+                    .log_err();
+            }
+        }));
         platform_window.on_active_status_change(Box::new({
             let mut cx = cx.to_async();
             move |active| {
@@ -779,9 +981,15 @@ impl Window {
             })
         });
 
-        if let Some(app_id) = app_id {
+        if let Some(app_id) = app_id.or_else(|| cx.app_id.as_ref().map(ToString::to_string)) {
             platform_window.set_app_id(&app_id);
         }
+        if let Some(icon) = icon
+            .or_else(|| cx.window_icon.clone())
+            .and_then(|icon| icon.to_image_data(cx).log_err())
+        {
+            platform_window.set_icon(&icon);
+        }
 
         Ok(Window {
             handle,
@@ -797,8 +1005,12 @@ impl Window {
             root_view: None,
             element_id_stack: SmallVec::default(),
             text_style_stack: Vec::new(),
+            layout_direction_stack: Vec::new(),
+            blend_mode_stack: Vec::new(),
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
+            transformation_stack: Vec::new(),
+            z_index_stack: Vec::new(),
             element_opacity: None,
             requested_autoscroll: None,
             rendered_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
@@ -814,16 +1026,25 @@ impl Window {
             default_prevented: true,
             mouse_position,
             mouse_hit_test: HitTest::default(),
+            delayed_hovers: FxHashMap::default(),
+            delayed_hovers_task: None,
             modifiers,
             scale_factor,
             bounds_observers: SubscriberSet::new(),
             appearance,
             appearance_observers: SubscriberSet::new(),
+            display_observers: SubscriberSet::new(),
+            scale_factor_observers: SubscriberSet::new(),
+            occluded: false,
+            occlusion_observers: SubscriberSet::new(),
             active,
             hovered,
             dirty,
             needs_present,
+            needs_full_damage: Cell::new(true),
             last_input_timestamp,
+            dispatching_mouse_event: false,
+            _power_state_subscription: power_state_subscription,
             refreshing: false,
             draw_phase: DrawPhase::None,
             activation_observers: SubscriberSet::new(),
@@ -833,6 +1054,8 @@ impl Window {
             pending_modifier: ModifierState::default(),
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
+            scene_recorder: None,
+            cached_texture_slots: FxHashMap::default(),
         })
     }
     fn new_focus_listener(
@@ -1006,6 +1229,7 @@ impl<'a> WindowContext<'a> {
     /// Dispatch the given action on the currently focused element.
     pub fn dispatch_action(&mut self, action: Box<dyn Action>) {
         let focus_handle = self.focused();
+        let clicked = self.window.dispatching_mouse_event;
 
         let window = self.window.handle;
         self.app.defer(move |cx| {
@@ -1021,11 +1245,20 @@ impl<'a> WindowContext<'a> {
                         .unwrap_or_else(|| cx.window.rendered_frame.dispatch_tree.root_node_id());
 
                     cx.dispatch_action_on_node(node_id, action.as_ref());
+                    if clicked {
+                        cx.dispatch_clicked_action_observers(action.as_ref());
+                    }
                 })
                 .log_err();
         })
     }
 
+    fn dispatch_clicked_action_observers(&mut self, action: &dyn Action) {
+        self.clicked_action_observers
+            .clone()
+            .retain(&(), move |callback| (callback)(action, self));
+    }
+
     pub(crate) fn dispatch_keystroke_observers(
         &mut self,
         event: &dyn Any,
@@ -1179,6 +1412,7 @@ impl<'a> WindowContext<'a> {
     /// Spawn the future returned by the given closure on the application thread pool.
     /// The closure is provided a handle to the current window and an `AsyncWindowContext` for
     /// use within your future.
+    #[track_caller]
     pub fn spawn<Fut, R>(&mut self, f: impl FnOnce(AsyncWindowContext) -> Fut) -> Task<R>
     where
         R: 'static,
@@ -1189,6 +1423,8 @@ impl<'a> WindowContext<'a> {
     }
 
     fn bounds_changed(&mut self) {
+        let previous_scale_factor = self.window.scale_factor;
+        let previous_display_id = self.window.display_id;
         self.window.scale_factor = self.window.platform_window.scale_factor();
         self.window.viewport_size = self.window.platform_window.content_size();
         self.window.display_id = self
@@ -1196,6 +1432,12 @@ impl<'a> WindowContext<'a> {
             .platform_window
             .display()
             .map(|display| display.id());
+        self.window.needs_full_damage.set(true);
+        if self.window.scale_factor != previous_scale_factor {
+            // Glyphs, SVGs and images were rasterized for the previous scale factor, so they're
+            // dropped to be rasterized again as the window is redrawn at the new one.
+            self.window.sprite_atlas.clear();
+        }
 
         self.refresh();
 
@@ -1203,6 +1445,40 @@ impl<'a> WindowContext<'a> {
             .bounds_observers
             .clone()
             .retain(&(), |callback| callback(self));
+        if self.window.display_id != previous_display_id {
+            self.window
+                .display_observers
+                .clone()
+                .retain(&(), |callback| callback(self));
+        }
+        if self.window.scale_factor != previous_scale_factor {
+            self.window
+                .scale_factor_observers
+                .clone()
+                .retain(&(), |callback| callback(self));
+        }
+    }
+
+    pub(crate) fn occlusion_changed(&mut self, occluded: bool) {
+        if self.window.occluded == occluded {
+            return;
+        }
+        self.window.occluded = occluded;
+        if !occluded {
+            self.refresh();
+        }
+
+        self.window
+            .occlusion_observers
+            .clone()
+            .retain(&(), |callback| callback(self));
+    }
+
+    /// Returns whether the window is fully hidden from the user, e.g. because it's minimized or
+    /// covered by other windows. Not every platform reports this, in which case the window is
+    /// never considered occluded.
+    pub fn is_window_occluded(&self) -> bool {
+        self.window.occluded
     }
 
     /// Returns the bounds of the current window in the global coordinate space, which could span across multiple displays.
@@ -1294,9 +1570,15 @@ impl<'a> WindowContext<'a> {
 
     /// Sets the window background appearance.
     pub fn set_background_appearance(&self, background_appearance: WindowBackgroundAppearance) {
+        let background_appearance = if self.accessibility_preferences().reduce_transparency {
+            WindowBackgroundAppearance::Opaque
+        } else {
+            background_appearance
+        };
         self.window
             .platform_window
             .set_background_appearance(background_appearance);
+        self.window.needs_full_damage.set(true);
     }
 
     /// Mark the window as dirty at the platform level.
@@ -1317,6 +1599,114 @@ impl<'a> WindowContext<'a> {
         self.window.platform_window.show_character_palette();
     }
 
+    /// Describe everything painted in the last frame drawn to the window, so tests can assert
+    /// what elements actually painted.
+    pub fn serialize_scene(&self) -> SerializedScene {
+        self.window.rendered_frame.scene.serialize()
+    }
+
+    /// Describe the primitives painted at the given position in the last frame drawn to the
+    /// window, topmost first. The descriptions are in device pixels, like those of
+    /// [`WindowContext::serialize_scene`].
+    pub fn primitives_at(&self, position: Point<Pixels>) -> Vec<SerializedPrimitive> {
+        self.window
+            .rendered_frame
+            .scene
+            .primitives_at(position.scale(self.scale_factor()))
+    }
+
+    /// Returns the hitboxes at the given position in the last frame drawn to the window, in the
+    /// order they're hit. The hitboxes beneath an opaque one aren't hit, so they aren't returned.
+    pub fn hitboxes_at(&self, position: Point<Pixels>) -> Vec<Hitbox> {
+        let frame = &self.window.rendered_frame;
+        frame
+            .hit_test(position)
+            .0
+            .iter()
+            .filter_map(|hitbox_id| {
+                frame
+                    .hitboxes
+                    .iter()
+                    .find(|hitbox| hitbox.id == *hitbox_id)
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Count the primitives and draw calls of the last frame drawn to the window.
+    pub fn scene_stats(&self) -> SceneStats {
+        self.window.rendered_frame.scene.stats()
+    }
+
+    /// Describe how much of the atlas the window's glyphs, SVGs and images are rasterized into is
+    /// in use.
+    pub fn atlas_stats(&self) -> AtlasStats {
+        self.window.sprite_atlas.stats()
+    }
+
+    /// Read back the last frame presented in the window, encoded as a PNG at the window's
+    /// resolution in device pixels. The frame is rendered again offscreen, so it doesn't include
+    /// anything the system draws over the window, and fails on platforms that can't read back
+    /// frames, such as the test platform.
+    pub fn capture_frame(&self) -> Task<Result<Image>> {
+        let frame = self
+            .window
+            .platform_window
+            .render_to_image(&self.window.rendered_frame.scene);
+        self.background_executor().spawn(async move {
+            let mut bytes = Vec::new();
+            frame?
+                .write_to(
+                    &mut std::io::Cursor::new(&mut bytes),
+                    image::ImageFormat::Png,
+                )
+                .context("encoding the frame")?;
+            Ok(Image::from_bytes(ImageFormat::Png, bytes))
+        })
+    }
+
+    /// Record the scenes of the next `frame_count` frames drawn to the window, along with the
+    /// glyphs, SVGs and images they draw, so they can be saved and replayed without the
+    /// application with [`paint_recorded_frame`](Self::paint_recorded_frame). Starting a new
+    /// recording finishes the one in progress.
+    pub fn record_scenes(&mut self, frame_count: usize) -> Task<Result<SceneRecording>> {
+        self.finish_recording_scenes();
+
+        if frame_count == 0 {
+            return Task::ready(Ok(SceneRecording {
+                version: SceneRecording::VERSION,
+                ..Default::default()
+            }));
+        }
+
+        // Everything is rasterized again while recording, so that the recording gets the pixels
+        // of every tile its frames draw.
+        let (done_tx, done_rx) = oneshot::channel();
+        let atlas = Arc::new(RecordingAtlas::new(self.window.sprite_atlas.clone()));
+        atlas.clear();
+        self.window.sprite_atlas = atlas.clone();
+        self.window.scene_recorder = Some(SceneRecorder {
+            atlas,
+            frames: Vec::new(),
+            frames_left: frame_count,
+            done: done_tx,
+        });
+        self.refresh();
+
+        self.background_executor().spawn(async move {
+            done_rx
+                .await
+                .map_err(|_| anyhow!("the window was closed before its scenes were recorded"))
+        })
+    }
+
+    fn finish_recording_scenes(&mut self) {
+        if let Some(recorder) = self.window.scene_recorder.take() {
+            self.window.sprite_atlas = recorder.atlas.inner.clone();
+            recorder.finish();
+        }
+    }
+
     /// The scale factor of the display associated with the window. For example, it could
     /// return 2.0 for a "retina" display, indicating that each logical pixel should actually
     /// be rendered as two pixels on screen.
@@ -1365,6 +1755,74 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// The direction in which elements are currently being laid out. This is the application's
+    /// [`AppContext::default_layout_direction`], unless an ancestor element overrides it.
+    pub fn layout_direction(&self) -> LayoutDirection {
+        self.window
+            .layout_direction_stack
+            .last()
+            .copied()
+            .unwrap_or(self.app.default_layout_direction())
+    }
+
+    /// Executes the provided function with the specified layout direction, if any, in effect.
+    ///
+    /// This method must only be called as part of element drawing.
+    pub fn with_layout_direction<F, R>(&mut self, direction: Option<LayoutDirection>, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during request_layout, prepaint, or paint"
+        );
+
+        if let Some(direction) = direction {
+            self.window.layout_direction_stack.push(direction);
+            let result = f(self);
+            self.window.layout_direction_stack.pop();
+            result
+        } else {
+            f(self)
+        }
+    }
+
+    /// The mode quads and sprites are currently blended with what was painted beneath them.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.window
+            .blend_mode_stack
+            .last()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Invoke the given function, blending the quads and sprites it paints with what was
+    /// painted beneath them in the given mode, if any. This method should only be called as part
+    /// of the paint phase of element drawing.
+    pub fn with_blend_mode<R>(
+        &mut self,
+        blend_mode: Option<BlendMode>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        if let Some(blend_mode) = blend_mode {
+            self.window.blend_mode_stack.push(blend_mode);
+            let result = f(self);
+            self.window.blend_mode_stack.pop();
+            result
+        } else {
+            f(self)
+        }
+    }
+
     /// The line height associated with the current text style.
     pub fn line_height(&self) -> Pixels {
         self.text_style().line_height_in_pixels(self.rem_size())
@@ -1443,6 +1901,20 @@ impl<'a> WindowContext<'a> {
         self.window
             .next_frame
             .finish(&mut self.window.rendered_frame);
+        self.reuse_cached_textures();
+        if let Some(recorder) = self.window.scene_recorder.as_mut() {
+            let scale_factor = self.window.scale_factor;
+            let viewport_size = self.window.viewport_size.scale(scale_factor);
+            if recorder.record_frame(&self.window.next_frame.scene, viewport_size, scale_factor) {
+                self.finish_recording_scenes();
+            }
+        }
+        let damage = if self.window.platform_window.uses_damage() {
+            self.damage_since_present()
+        } else {
+            None
+        };
+        self.window.next_frame.scene.set_damage(damage);
         ELEMENT_ARENA.with_borrow_mut(|element_arena| {
             let percentage = (element_arena.len() as f32 / element_arena.capacity() as f32) * 100.;
             if percentage >= 80. {
@@ -1454,8 +1926,12 @@ impl<'a> WindowContext<'a> {
         self.window.draw_phase = DrawPhase::Focus;
         let previous_focus_path = self.window.rendered_frame.focus_path();
         let previous_window_active = self.window.rendered_frame.window_active;
+        self.position_web_views();
         mem::swap(&mut self.window.rendered_frame, &mut self.window.next_frame);
         self.window.next_frame.clear();
+        self.window
+            .sprite_atlas
+            .finish_frame(&mut self.window.rendered_frame.scene.atlas_tiles());
         let current_focus_path = self.window.rendered_frame.focus_path();
         let current_window_active = self.window.rendered_frame.window_active;
 
@@ -1493,6 +1969,34 @@ impl<'a> WindowContext<'a> {
         self.window.needs_present.set(true);
     }
 
+    /// Lets the renderer reuse the textures of the cached subtrees that paint the same as in the
+    /// last presented frame, and frees the slots of the ones that weren't painted.
+    fn reuse_cached_textures(&mut self) {
+        let scene = &mut self.window.next_frame.scene;
+        self.window
+            .cached_texture_slots
+            .retain(|_, slot| scene.cached_group_slots.contains(slot));
+        // A frame that was never presented wasn't drawn to the textures either.
+        if !self.window.needs_present.get() {
+            scene.reuse_cached_groups(&self.window.rendered_frame.scene);
+        }
+    }
+
+    /// The regions of the next frame that differ from the last presented one, including the
+    /// damage of a previous frame that was never presented.
+    fn damage_since_present(&self) -> Option<Vec<Bounds<ScaledPixels>>> {
+        if self.window.needs_full_damage.replace(false) {
+            return None;
+        }
+
+        let previous_scene = &self.window.rendered_frame.scene;
+        let mut damage = self.window.next_frame.scene.diff(previous_scene);
+        if self.window.needs_present.get() {
+            damage.extend_from_slice(previous_scene.damage()?);
+        }
+        Some(damage)
+    }
+
     #[profiling::function]
     fn present(&self) {
         self.window
@@ -1518,22 +2022,25 @@ impl<'a> WindowContext<'a> {
         let mut prompt_element = None;
         let mut active_drag_element = None;
         let mut tooltip_element = None;
-        if let Some(prompt) = self.window.prompt.take() {
-            let mut element = prompt.view.any_view().into_any();
-            element.prepaint_as_root(Point::default(), self.window.viewport_size.into(), self);
-            prompt_element = Some(element);
-            self.window.prompt = Some(prompt);
-        } else if let Some(active_drag) = self.app.active_drag.take() {
-            let mut element = active_drag.view.clone().into_any();
-            let offset = self.mouse_position() - active_drag.cursor_offset;
-            element.prepaint_as_root(offset, AvailableSpace::min_size(), self);
-            active_drag_element = Some(element);
-            self.app.active_drag = Some(active_drag);
-        } else {
-            tooltip_element = self.prepaint_tooltip();
-        }
+        self.with_z_index(OVERLAY_Z_INDEX, |cx| {
+            if let Some(prompt) = cx.window.prompt.take() {
+                let mut element = prompt.view.any_view().into_any();
+                element.prepaint_as_root(Point::default(), cx.window.viewport_size.into(), cx);
+                prompt_element = Some(element);
+                cx.window.prompt = Some(prompt);
+            } else if let Some(active_drag) = cx.app.active_drag.take() {
+                let mut element = active_drag.view.clone().into_any();
+                let offset = cx.mouse_position() - active_drag.cursor_offset;
+                element.prepaint_as_root(offset, AvailableSpace::min_size(), cx);
+                active_drag_element = Some(element);
+                cx.app.active_drag = Some(active_drag);
+            } else {
+                tooltip_element = cx.prepaint_tooltip();
+            }
+        });
 
         self.window.mouse_hit_test = self.window.next_frame.hit_test(self.window.mouse_position);
+        self.update_delayed_hovers();
 
         // Now actually paint the elements.
         self.window.draw_phase = DrawPhase::Paint;
@@ -1541,13 +2048,15 @@ impl<'a> WindowContext<'a> {
 
         self.paint_deferred_draws(&sorted_deferred_draws);
 
-        if let Some(mut prompt_element) = prompt_element {
-            prompt_element.paint(self);
-        } else if let Some(mut drag_element) = active_drag_element {
-            drag_element.paint(self);
-        } else if let Some(mut tooltip_element) = tooltip_element {
-            tooltip_element.paint(self);
-        }
+        self.with_z_index(OVERLAY_Z_INDEX, |cx| {
+            if let Some(mut prompt_element) = prompt_element {
+                prompt_element.paint(cx);
+            } else if let Some(mut drag_element) = active_drag_element {
+                drag_element.paint(cx);
+            } else if let Some(mut tooltip_element) = tooltip_element {
+                tooltip_element.paint(cx);
+            }
+        });
     }
 
     fn prepaint_tooltip(&mut self) -> Option<AnyElement> {
@@ -1614,13 +2123,17 @@ impl<'a> WindowContext<'a> {
                 .set_active_node(deferred_draw.parent_node);
 
             let prepaint_start = self.prepaint_index();
-            if let Some(element) = deferred_draw.element.as_mut() {
-                self.with_absolute_element_offset(deferred_draw.absolute_offset, |cx| {
-                    element.prepaint(cx)
-                });
-            } else {
-                self.reuse_prepaint(deferred_draw.prepaint_range.clone());
-            }
+            self.with_z_index(deferred_draw.z_index, |cx| {
+                cx.with_layout_direction(Some(deferred_draw.layout_direction), |cx| {
+                    if let Some(element) = deferred_draw.element.as_mut() {
+                        cx.with_absolute_element_offset(deferred_draw.absolute_offset, |cx| {
+                            element.prepaint(cx)
+                        });
+                    } else {
+                        cx.reuse_prepaint(deferred_draw.prepaint_range.clone());
+                    }
+                })
+            });
             let prepaint_end = self.prepaint_index();
             deferred_draw.prepaint_range = prepaint_start..prepaint_end;
         }
@@ -1649,11 +2162,15 @@ impl<'a> WindowContext<'a> {
                 .set_active_node(deferred_draw.parent_node);
 
             let paint_start = self.paint_index();
-            if let Some(element) = deferred_draw.element.as_mut() {
-                element.paint(self);
-            } else {
-                self.reuse_paint(deferred_draw.paint_range.clone());
-            }
+            self.with_z_index(deferred_draw.z_index, |cx| {
+                cx.with_layout_direction(Some(deferred_draw.layout_direction), |cx| {
+                    if let Some(element) = deferred_draw.element.as_mut() {
+                        element.paint(cx);
+                    } else {
+                        cx.reuse_paint(deferred_draw.paint_range.clone());
+                    }
+                })
+            });
             let paint_end = self.paint_index();
             deferred_draw.paint_range = paint_start..paint_end;
         }
@@ -1674,11 +2191,23 @@ impl<'a> WindowContext<'a> {
 
     pub(crate) fn reuse_prepaint(&mut self, range: Range<PrepaintStateIndex>) {
         let window = &mut self.window;
-        window.next_frame.hitboxes.extend(
-            window.rendered_frame.hitboxes[range.start.hitboxes_index..range.end.hitboxes_index]
-                .iter()
-                .cloned(),
-        );
+        let reused_hitboxes =
+            &window.rendered_frame.hitboxes[range.start.hitboxes_index..range.end.hitboxes_index];
+        for hitbox in reused_hitboxes {
+            if let Some(request) = window.rendered_frame.hover_delays.get(&hitbox.id) {
+                window.next_frame.hover_delays.insert(
+                    hitbox.id,
+                    HoverDelayRequest {
+                        element_id: GlobalElementId(request.element_id.0.clone()),
+                        delays: request.delays,
+                    },
+                );
+            }
+        }
+        window
+            .next_frame
+            .hitboxes
+            .extend(reused_hitboxes.iter().cloned());
         window.next_frame.tooltip_requests.extend(
             window.rendered_frame.tooltip_requests
                 [range.start.tooltips_index..range.end.tooltips_index]
@@ -1713,6 +2242,8 @@ impl<'a> WindowContext<'a> {
                     parent_node: reused_subtree.refresh_node_id(deferred_draw.parent_node),
                     element_id_stack: deferred_draw.element_id_stack.clone(),
                     text_style_stack: deferred_draw.text_style_stack.clone(),
+                    z_index: deferred_draw.z_index,
+                    layout_direction: deferred_draw.layout_direction,
                     priority: deferred_draw.priority,
                     element: None,
                     absolute_offset: deferred_draw.absolute_offset,
@@ -1728,6 +2259,7 @@ impl<'a> WindowContext<'a> {
             mouse_listeners_index: self.window.next_frame.mouse_listeners.len(),
             input_handlers_index: self.window.next_frame.input_handlers.len(),
             cursor_styles_index: self.window.next_frame.cursor_styles.len(),
+            web_views_index: self.window.next_frame.web_views.len(),
             accessed_element_states_index: self.window.next_frame.accessed_element_states.len(),
             line_layout_index: self.window.text_system.layout_index(),
         }
@@ -1742,6 +2274,11 @@ impl<'a> WindowContext<'a> {
                 .iter()
                 .cloned(),
         );
+        window.next_frame.web_views.extend(
+            window.rendered_frame.web_views[range.start.web_views_index..range.end.web_views_index]
+                .iter()
+                .cloned(),
+        );
         window.next_frame.input_handlers.extend(
             window.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -1851,6 +2388,60 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Invoke the given function with the given transformation applied after any transformation
+    /// already in effect. Everything painted and every hitbox inserted by the function is
+    /// transformed, so it should be called during both prepaint and paint. The transformation is
+    /// expressed in scaled pixels, see [`Transformation`](crate::Transformation) to build one
+    /// around an element's center. This method should only be called during element drawing.
+    pub fn with_transformation<R>(
+        &mut self,
+        transformation: TransformationMatrix,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during prepaint, or paint"
+        );
+        let transformation = self.transformation().compose(transformation);
+        self.window_mut().transformation_stack.push(transformation);
+        let result = f(self);
+        self.window_mut().transformation_stack.pop();
+        result
+    }
+
+    /// Invoke the given function with the given z-index added to the current one. Everything
+    /// it paints is drawn above whatever is painted at a lower z-index, and its hitboxes are
+    /// hit before theirs, regardless of where they are in the element tree. It should be called
+    /// during both prepaint and paint. This method should only be called during element drawing.
+    pub fn with_z_index<R>(&mut self, z_index: u8, f: impl FnOnce(&mut Self) -> R) -> R {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during prepaint, or paint"
+        );
+        if z_index == 0 {
+            return f(self);
+        }
+
+        let z_index = self.z_index().saturating_add(z_index);
+        let paint = self.window.draw_phase == DrawPhase::Paint;
+        self.window.z_index_stack.push(z_index);
+        if paint {
+            self.window.next_frame.scene.push_z_index(z_index);
+        }
+        let result = f(self);
+        if paint {
+            self.window.next_frame.scene.pop_z_index();
+        }
+        self.window.z_index_stack.pop();
+        result
+    }
+
     /// Updates the global element offset relative to the current offset. This is used to implement
     /// scrolling. This method should only be called during the prepaint phase of element drawing.
     pub fn with_element_offset<R>(
@@ -1913,6 +2504,64 @@ impl<'a> WindowContext<'a> {
         result
     }
 
+    /// Invoke the given function, compositing everything it paints as a single group at the
+    /// given opacity. Unlike fading each primitive on its own, overlapping primitives in the
+    /// group don't show through each other, which makes this suited to fading whole elements in
+    /// and out. This method should only be called as part of the paint phase of element drawing.
+    pub fn with_opacity<R>(&mut self, opacity: f32, f: impl FnOnce(&mut Self) -> R) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        if opacity >= 1. {
+            return f(self);
+        }
+
+        self.window
+            .next_frame
+            .scene
+            .push_opacity_group(opacity.max(0.));
+        let result = f(self);
+        self.window.next_frame.scene.pop_opacity_group();
+        result
+    }
+
+    /// Invoke the given function, drawing everything it paints to a texture that's kept from
+    /// frame to frame and composited in its place. The texture is only drawn again in frames
+    /// where the function paints something different, which saves drawing large subtrees that
+    /// rarely change, like views cached with [`AnyView::cached`]. Each texture covers the whole
+    /// window, so only a few subtrees are cached at a time, and the others are drawn as usual.
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn with_cached_texture<R>(
+        &mut self,
+        id: &GlobalElementId,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        let key = hash(id);
+        let slots = &mut self.window.cached_texture_slots;
+        let slot = slots.get(&key).copied().or_else(|| {
+            let slot = (0..MAX_CACHED_TEXTURES).find(|slot| !slots.values().any(|s| s == slot))?;
+            slots.insert(key, slot);
+            Some(slot)
+        });
+        let Some(slot) = slot else {
+            return f(self);
+        };
+
+        self.window.next_frame.scene.push_cached_group(key, slot);
+        let result = f(self);
+        self.window.next_frame.scene.pop_opacity_group();
+        result
+    }
+
     /// Perform prepaint on child elements in a "retryable" manner, so that any side effects
     /// of prepaints can be discarded before prepainting again. This is used to support autoscroll
     /// where we need to prepaint children to detect the autoscroll bounds, then adjust the
@@ -2060,6 +2709,28 @@ impl<'a> WindowContext<'a> {
             })
     }
 
+    /// Obtain the current z-index. This method should only be called during element drawing.
+    pub fn z_index(&self) -> u8 {
+        self.window.z_index_stack.last().copied().unwrap_or(0)
+    }
+
+    /// Obtain the current transformation, in scaled pixels. This method should only be called
+    /// during element drawing.
+    pub fn transformation(&self) -> TransformationMatrix {
+        debug_assert!(
+            matches!(
+                self.window.draw_phase,
+                DrawPhase::Prepaint | DrawPhase::Paint
+            ),
+            "this method can only be called during prepaint, or paint"
+        );
+        self.window()
+            .transformation_stack
+            .last()
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Provide elements in the called function with a new namespace in which their identifiers must be unique.
     /// This can be used within a custom element to distinguish multiple sets of child elements.
     pub fn with_element_namespace<R>(
@@ -2198,6 +2869,61 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Returns a model holding state for the element being drawn that lives across frames, keyed
+    /// by the given id within the current element's namespace. The model is created with `init`
+    /// the first time, and dropped once a frame is drawn without calling this method for it.
+    /// Notifying the model redraws the view containing the element.
+    ///
+    /// This lets [`RenderOnce`](crate::RenderOnce) components keep state such as scroll offsets
+    /// or whether they're expanded without being turned into views. It should only be called
+    /// as part of element drawing, e.g. while rendering.
+    pub fn use_keyed_state<S: 'static>(
+        &mut self,
+        key: impl Into<ElementId>,
+        init: impl FnOnce(&mut WindowContext) -> S,
+    ) -> Model<S> {
+        let view_id = self
+            .window
+            .element_id_stack
+            .iter()
+            .rev()
+            .find_map(|element_id| match element_id {
+                ElementId::View(view_id) => Some(*view_id),
+                _ => None,
+            });
+
+        self.window.element_id_stack.push(key.into());
+        let global_id = GlobalElementId(self.window.element_id_stack.clone());
+        self.window.element_id_stack.pop();
+
+        self.with_element_state(&global_id, |state: Option<Model<S>>, cx| {
+            let state = state.unwrap_or_else(|| {
+                let initial_state = init(cx);
+                let state = cx.new_model(|_| initial_state);
+                cx.observe(&state, move |_, cx| match view_id {
+                    Some(view_id) => cx.notify(view_id),
+                    None => cx.refresh(),
+                })
+                .detach();
+                state
+            });
+            (state.clone(), state)
+        })
+    }
+
+    /// Like [`WindowContext::use_keyed_state`], keyed by where this method is called from, so it
+    /// can't be called more than once from the same place for a single element.
+    #[track_caller]
+    pub fn use_state<S: 'static>(
+        &mut self,
+        init: impl FnOnce(&mut WindowContext) -> S,
+    ) -> Model<S> {
+        self.use_keyed_state(
+            ElementId::CodeLocation(*std::panic::Location::caller()),
+            init,
+        )
+    }
+
     /// Defers the drawing of the given element, scheduling it to be painted on top of the currently-drawn tree
     /// at a later time. The `priority` parameter determines the drawing order relative to other deferred elements,
     /// with higher values being drawn on top.
@@ -2209,6 +2935,7 @@ impl<'a> WindowContext<'a> {
         absolute_offset: Point<Pixels>,
         priority: usize,
     ) {
+        let layout_direction = self.layout_direction();
         let window = &mut self.window;
         debug_assert_eq!(
             window.draw_phase,
@@ -2220,6 +2947,8 @@ impl<'a> WindowContext<'a> {
             parent_node,
             element_id_stack: window.element_id_stack.clone(),
             text_style_stack: window.text_style_stack.clone(),
+            z_index: window.z_index_stack.last().copied().unwrap_or(0),
+            layout_direction,
             priority,
             element: Some(element),
             absolute_offset,
@@ -2242,12 +2971,13 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
+        let transformation = self.transformation();
         let clipped_bounds = bounds.intersect(&content_mask.bounds);
         if !clipped_bounds.is_empty() {
             self.window
                 .next_frame
                 .scene
-                .push_layer(clipped_bounds.scale(scale_factor));
+                .push_layer(transformation.transform_bounds(clipped_bounds.scale(scale_factor)));
         }
 
         let result = f(self);
@@ -2260,6 +2990,9 @@ impl<'a> WindowContext<'a> {
     }
 
     /// Paint one or more drop shadows into the scene for the next frame at the current z-index.
+    /// Inset shadows are painted inside the given bounds, so they should be painted after the
+    /// element's background. The shadows are stacked in the order they're given, so the last one
+    /// is painted on top.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
     pub fn paint_shadows(
@@ -2277,19 +3010,86 @@ impl<'a> WindowContext<'a> {
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
         let opacity = self.element_opacity();
-        for shadow in shadows {
-            let mut shadow_bounds = bounds;
-            shadow_bounds.origin += shadow.offset;
-            shadow_bounds.dilate(shadow.spread_radius);
-            self.window.next_frame.scene.insert_primitive(Shadow {
-                order: 0,
-                blur_radius: shadow.blur_radius.scale(scale_factor),
-                bounds: shadow_bounds.scale(scale_factor),
-                content_mask: content_mask.scale(scale_factor),
-                corner_radii: corner_radii.scale(scale_factor),
-                color: shadow.color.opacity(opacity),
-            });
+        let transformation = self.transformation();
+        let shadows = shadows.iter().map(|shadow| {
+            if shadow.inset {
+                // Inset shadows cover the element, and are cast by its bounds moved by the
+                // offset and shrunk by the spread radius. The shaders derive that shape.
+                Shadow {
+                    order: 0,
+                    blur_radius: shadow.blur_radius.scale(scale_factor),
+                    bounds: bounds.scale(scale_factor),
+                    content_mask: content_mask.scale(scale_factor),
+                    corner_radii: corner_radii.scale(scale_factor),
+                    color: shadow.color.opacity(opacity),
+                    transformation,
+                    inset_offset: shadow.offset.scale(scale_factor),
+                    inset_spread_radius: shadow.spread_radius.scale(scale_factor),
+                    inset: true,
+                }
+            } else {
+                let mut shadow_bounds = bounds;
+                shadow_bounds.origin += shadow.offset;
+                shadow_bounds.dilate(shadow.spread_radius);
+                // Like in CSS, rounded corners grow and shrink with the spread radius, while
+                // square ones stay square.
+                let shadow_corner_radii = corner_radii.map(|radius| {
+                    if *radius > Pixels::ZERO {
+                        (*radius + shadow.spread_radius).max(Pixels::ZERO)
+                    } else {
+                        Pixels::ZERO
+                    }
+                });
+                Shadow {
+                    order: 0,
+                    blur_radius: shadow.blur_radius.scale(scale_factor),
+                    bounds: shadow_bounds.scale(scale_factor),
+                    content_mask: content_mask.scale(scale_factor),
+                    corner_radii: shadow_corner_radii.scale(scale_factor),
+                    color: shadow.color.opacity(opacity),
+                    transformation,
+                    inset_offset: Point::default(),
+                    inset_spread_radius: ScaledPixels(0.),
+                    inset: false,
+                }
+            }
+        });
+        self.window.next_frame.scene.insert_shadows(shadows);
+    }
+
+    /// Paint a blur of everything painted underneath the given bounds into the scene for the
+    /// next frame at the current z-index, like CSS's `backdrop-filter: blur()`.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_backdrop_blur(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        blur_radius: Pixels,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        if blur_radius <= px(0.) {
+            return;
         }
+        let scale_factor = self.scale_factor();
+        let content_mask = self.content_mask().scale(scale_factor);
+        // Blurs sample the frame behind them in window space, so they can only cover the
+        // bounding box of their transformed bounds.
+        let transformation = self.transformation();
+        self.window.next_frame.scene.insert_primitive(BackdropBlur {
+            order: 0,
+            blur_radius: blur_radius.scale(scale_factor),
+            bounds: transformation.transform_bounds(bounds.scale(scale_factor)),
+            corner_radii: corner_radii.scale(scale_factor),
+            content_mask: ContentMask {
+                bounds: transformation.transform_bounds(content_mask.bounds),
+            },
+        });
     }
 
     /// Paint one or more quads into the scene for the next frame at the current stacking context.
@@ -2307,22 +3107,25 @@ impl<'a> WindowContext<'a> {
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
         let opacity = self.element_opacity();
+        let transformation = self.transformation();
+        let blend_mode = self.blend_mode();
         self.window.next_frame.scene.insert_primitive(Quad {
             order: 0,
-            pad: 0,
+            blend_mode,
             bounds: quad.bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
             background: quad.background.opacity(opacity),
             border_color: quad.border_color.opacity(opacity),
             corner_radii: quad.corner_radii.scale(scale_factor),
             border_widths: quad.border_widths.scale(scale_factor),
+            transformation,
         });
     }
 
     /// Paint the given `Path` into the scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
-    pub fn paint_path(&mut self, mut path: Path<Pixels>, color: impl Into<Hsla>) {
+    pub fn paint_path(&mut self, mut path: Path<Pixels>, color: impl Into<Background>) {
         debug_assert_eq!(
             self.window.draw_phase,
             DrawPhase::Paint,
@@ -2334,10 +3137,9 @@ impl<'a> WindowContext<'a> {
         let opacity = self.element_opacity();
         path.content_mask = content_mask;
         path.color = color.into().opacity(opacity);
-        self.window
-            .next_frame
-            .scene
-            .insert_primitive(path.scale(scale_factor));
+        let mut path = path.scale(scale_factor);
+        path.transform(&self.transformation());
+        self.window.next_frame.scene.insert_primitive(path);
     }
 
     /// Paint an underline into the scene for the next frame at the current z-index.
@@ -2356,26 +3158,50 @@ impl<'a> WindowContext<'a> {
         );
 
         let scale_factor = self.scale_factor();
-        let height = if style.wavy {
-            style.thickness * 3.
-        } else {
-            style.thickness
+        let thickness = style.thickness.scale(scale_factor);
+        // The second line of a double underline is below the first, with a gap as high as the
+        // lines are thick.
+        let height = match style.kind {
+            UnderlineKind::Double => style.thickness * 3.,
+            UnderlineKind::Single | UnderlineKind::Dotted => style.thickness,
         };
-        let bounds = Bounds {
+        let mut bounds = Bounds {
             origin,
             size: size(width, height),
+        }
+        .scale(scale_factor);
+        let (wave_amplitude, wavelength) = if style.wavy {
+            let wave_amplitude = ScaledPixels(thickness.0 * WAVY_UNDERLINE_AMPLITUDE);
+            // The wave's crests start where a straight underline would, and its bounds leave
+            // a device pixel above and below it for antialiasing.
+            let margin = ScaledPixels(1.);
+            bounds.origin.y -= margin;
+            bounds.size.height += ScaledPixels((wave_amplitude.0 + margin.0) * 2.);
+            (
+                wave_amplitude,
+                ScaledPixels(thickness.0 * WAVY_UNDERLINE_WAVELENGTH),
+            )
+        } else {
+            (ScaledPixels(0.), ScaledPixels(0.))
         };
         let content_mask = self.content_mask();
         let element_opacity = self.element_opacity();
+        let transformation = self.transformation();
+        let dash_pattern = style.dash_pattern.unwrap_or_default();
 
         self.window.next_frame.scene.insert_primitive(Underline {
             order: 0,
-            pad: 0,
-            bounds: bounds.scale(scale_factor),
+            kind: style.kind,
+            bounds,
             content_mask: content_mask.scale(scale_factor),
             color: style.color.unwrap_or_default().opacity(element_opacity),
-            thickness: style.thickness.scale(scale_factor),
-            wavy: style.wavy,
+            thickness,
+            wavelength,
+            transformation,
+            dash: dash_pattern.dash.scale(scale_factor),
+            dash_gap: dash_pattern.gap.scale(scale_factor),
+            dash_offset: dash_pattern.offset.scale(scale_factor),
+            wave_amplitude,
         });
     }
 
@@ -2402,15 +3228,21 @@ impl<'a> WindowContext<'a> {
         };
         let content_mask = self.content_mask();
         let opacity = self.element_opacity();
+        let transformation = self.transformation();
 
         self.window.next_frame.scene.insert_primitive(Underline {
             order: 0,
-            pad: 0,
+            kind: UnderlineKind::Single,
             bounds: bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
             thickness: style.thickness.scale(scale_factor),
             color: style.color.unwrap_or_default().opacity(opacity),
-            wavy: false,
+            wavelength: ScaledPixels(0.),
+            transformation,
+            dash: ScaledPixels(0.),
+            dash_gap: ScaledPixels(0.),
+            dash_offset: ScaledPixels(0.),
+            wave_amplitude: ScaledPixels(0.),
         });
     }
 
@@ -2439,9 +3271,11 @@ impl<'a> WindowContext<'a> {
         let element_opacity = self.element_opacity();
         let scale_factor = self.scale_factor();
         let glyph_origin = origin.scale(scale_factor);
+        let (pixel_x, subpixel_x) = subpixel_position(glyph_origin.x);
+        let (pixel_y, subpixel_y) = subpixel_position(glyph_origin.y);
         let subpixel_variant = Point {
-            x: (glyph_origin.x.0.fract() * SUBPIXEL_VARIANTS as f32).floor() as u8,
-            y: (glyph_origin.y.0.fract() * SUBPIXEL_VARIANTS as f32).floor() as u8,
+            x: subpixel_x,
+            y: subpixel_y,
         };
         let params = RenderGlyphParams {
             font_id,
@@ -2454,30 +3288,41 @@ impl<'a> WindowContext<'a> {
 
         let raster_bounds = self.text_system().raster_bounds(&params)?;
         if !raster_bounds.is_zero() {
-            let tile = self
+            let key = params.clone().into();
+            // Glyphs are rasterized on the GPU from their outlines where the platform supports it.
+            let outline_tile = self
                 .window
                 .sprite_atlas
-                .get_or_insert_with(&params.clone().into(), &mut || {
-                    let (size, bytes) = self.text_system().rasterize_glyph(&params)?;
-                    Ok(Some((size, Cow::Owned(bytes))))
-                })?
-                .expect("Callback above only errors or returns Some");
+                .get_or_insert_outline(&key, &mut || self.text_system().glyph_outline(&params))?;
+            let tile = match outline_tile {
+                Some(tile) => tile,
+                None => self
+                    .window
+                    .sprite_atlas
+                    .get_or_insert_with(&key, &mut || {
+                        let (size, bytes) = self.text_system().rasterize_glyph(&params)?;
+                        Ok(Some((size, Cow::Owned(bytes))))
+                    })?
+                    .expect("Callback above only errors or returns Some"),
+            };
             let bounds = Bounds {
-                origin: glyph_origin.map(|px| px.floor()) + raster_bounds.origin.map(Into::into),
+                origin: point(pixel_x, pixel_y) + raster_bounds.origin.map(Into::into),
                 size: tile.bounds.size.map(Into::into),
             };
             let content_mask = self.content_mask().scale(scale_factor);
+            let transformation = self.transformation();
+            let blend_mode = self.blend_mode();
             self.window
                 .next_frame
                 .scene
                 .insert_primitive(MonochromeSprite {
                     order: 0,
-                    pad: 0,
+                    blend_mode,
                     bounds,
                     content_mask,
                     color: color.opacity(element_opacity),
                     tile,
-                    transformation: TransformationMatrix::unit(),
+                    transformation,
                 });
         }
         Ok(())
@@ -2533,19 +3378,30 @@ impl<'a> WindowContext<'a> {
             };
             let content_mask = self.content_mask().scale(scale_factor);
             let opacity = self.element_opacity();
+            let transformation = self.transformation();
+            let blend_mode = self.blend_mode();
 
             self.window
                 .next_frame
                 .scene
                 .insert_primitive(PolychromeSprite {
                     order: 0,
-                    pad: 0,
-                    grayscale: false,
+                    hue_rotate: 0.,
+                    grayscale: 0.,
                     bounds,
                     corner_radii: Default::default(),
                     content_mask,
                     tile,
                     opacity,
+                    transformation,
+                    nine_slice: Edges::default(),
+                    tile_nine_slice: Edges::default(),
+                    repeat_size: Size::default(),
+                    repeat_spacing: Size::default(),
+                    repeat_origin: Point::default(),
+                    tint: transparent_black(),
+                    blend_mode,
+                    pad: 0,
                 });
         }
         Ok(())
@@ -2591,13 +3447,15 @@ impl<'a> WindowContext<'a> {
             return Ok(());
         };
         let content_mask = self.content_mask().scale(scale_factor);
+        let transformation = self.transformation().compose(transformation);
+        let blend_mode = self.blend_mode();
 
         self.window
             .next_frame
             .scene
             .insert_primitive(MonochromeSprite {
                 order: 0,
-                pad: 0,
+                blend_mode,
                 bounds: bounds
                     .map_origin(|origin| origin.floor())
                     .map_size(|size| size.ceil()),
@@ -2620,7 +3478,80 @@ impl<'a> WindowContext<'a> {
         corner_radii: Corners<Pixels>,
         data: Arc<RenderImage>,
         frame_index: usize,
-        grayscale: bool,
+        filter: ColorFilter,
+    ) -> Result<()> {
+        self.paint_nine_slice_image(
+            bounds,
+            corner_radii,
+            data,
+            frame_index,
+            Edges::default(),
+            filter,
+        )
+    }
+
+    /// Paint an image into the scene for the next frame at the current z-index, stretched as a
+    /// nine-slice: the borders given by `insets`, in the image's pixels, are drawn at the same
+    /// size in logical pixels. Corners aren't stretched, edges only stretch along their length,
+    /// and the center fills the rest of the bounds. Borders that don't fit in the bounds shrink
+    /// proportionally. This method will panic if the frame_index is not valid
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_nine_slice_image(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        data: Arc<RenderImage>,
+        frame_index: usize,
+        insets: Edges<Pixels>,
+        filter: ColorFilter,
+    ) -> Result<()> {
+        self.paint_image_sprite(
+            bounds,
+            corner_radii,
+            data,
+            frame_index,
+            insets,
+            None,
+            filter,
+        )
+    }
+
+    /// Paint an image into the scene for the next frame at the current z-index, repeated to fill
+    /// the bounds as described by `repeat`. However many copies are visible, the image is drawn
+    /// as a single primitive. This method will panic if the frame_index is not valid
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_repeated_image(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        data: Arc<RenderImage>,
+        frame_index: usize,
+        repeat: ImageRepeat,
+        filter: ColorFilter,
+    ) -> Result<()> {
+        self.paint_image_sprite(
+            bounds,
+            corner_radii,
+            data,
+            frame_index,
+            Edges::default(),
+            Some(repeat),
+            filter,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn paint_image_sprite(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: Corners<Pixels>,
+        data: Arc<RenderImage>,
+        frame_index: usize,
+        insets: Edges<Pixels>,
+        repeat: Option<ImageRepeat>,
+        filter: ColorFilter,
     ) -> Result<()> {
         debug_assert_eq!(
             self.window.draw_phase,
@@ -2630,9 +3561,28 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let bounds = bounds.scale(scale_factor);
+        let (repeat_size, repeat_spacing, repeat_origin) = match repeat {
+            Some(repeat) if !repeat.size.is_zero() => (
+                repeat.size.scale(scale_factor),
+                repeat.spacing.scale(scale_factor),
+                repeat.origin.scale(scale_factor),
+            ),
+            _ => (Size::default(), Size::default(), Point::default()),
+        };
+        // Nine-slice borders are fit into each copy of a repeated image.
+        let slice_size = if repeat_size.is_zero() {
+            bounds.size
+        } else {
+            repeat_size
+        };
+
+        // Images drawn much smaller than their size are downscaled up front, as bilinear
+        // sampling alone would alias.
+        let mip_level = data.mip_level(frame_index, slice_size.map(DevicePixels::from));
         let params = RenderImageParams {
             image_id: data.id,
             frame_index,
+            mip_level,
         };
 
         let tile = self
@@ -2640,39 +3590,118 @@ impl<'a> WindowContext<'a> {
             .sprite_atlas
             .get_or_insert_with(&params.clone().into(), &mut || {
                 Ok(Some((
-                    data.size(frame_index),
-                    Cow::Borrowed(
-                        data.as_bytes(frame_index)
-                            .expect("It's the caller's job to pass a valid frame index"),
-                    ),
+                    data.mip_size(frame_index, mip_level),
+                    data.mip_bytes(frame_index, mip_level)
+                        .expect("It's the caller's job to pass a valid frame index"),
                 )))
             })?
             .expect("Callback above only returns Some");
         let content_mask = self.content_mask().scale(scale_factor);
         let corner_radii = corner_radii.scale(scale_factor);
-        let opacity = self.element_opacity();
+        let opacity = self.element_opacity() * filter.opacity;
+        let transformation = self.transformation();
+        let blend_mode = self.blend_mode();
+
+        let image_size = data.size(frame_index);
+        let mip_size = data.mip_size(frame_index, mip_level);
+        let mip_scale = size(
+            mip_size.width.0 as f32 / image_size.width.0 as f32,
+            mip_size.height.0 as f32 / image_size.height.0 as f32,
+        );
+        let tile_nine_slice = Edges {
+            top: ScaledPixels(insets.top.0 * mip_scale.height),
+            right: ScaledPixels(insets.right.0 * mip_scale.width),
+            bottom: ScaledPixels(insets.bottom.0 * mip_scale.height),
+            left: ScaledPixels(insets.left.0 * mip_scale.width),
+        };
+        let mut nine_slice = insets.scale(scale_factor);
+        let horizontal = nine_slice.left.0 + nine_slice.right.0;
+        if horizontal > slice_size.width.0 {
+            let shrink = slice_size.width.0 / horizontal;
+            nine_slice.left.0 *= shrink;
+            nine_slice.right.0 *= shrink;
+        }
+        let vertical = nine_slice.top.0 + nine_slice.bottom.0;
+        if vertical > slice_size.height.0 {
+            let shrink = slice_size.height.0 / vertical;
+            nine_slice.top.0 *= shrink;
+            nine_slice.bottom.0 *= shrink;
+        }
 
         self.window
             .next_frame
             .scene
             .insert_primitive(PolychromeSprite {
                 order: 0,
-                pad: 0,
-                grayscale,
+                hue_rotate: filter.hue_rotate.0,
+                grayscale: filter.grayscale,
                 bounds,
                 content_mask,
                 corner_radii,
                 tile,
                 opacity,
+                transformation,
+                nine_slice,
+                tile_nine_slice,
+                repeat_size,
+                repeat_spacing,
+                repeat_origin,
+                tint: filter.tint,
+                blend_mode,
+                pad: 0,
             });
         Ok(())
     }
 
-    /// Paint a surface into the scene for the next frame at the current z-index.
+    /// Paint a primitive whose pixels are colored by a shader registered with
+    /// [`AppContext::register_custom_shader`], passing it up to [`MAX_CUSTOM_SHADER_UNIFORMS`]
+    /// floats. Custom primitives are drawn again every frame, so animated shaders only need to
+    /// request animation frames.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_custom_shader(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        shader_id: CustomShaderId,
+        uniforms: &[f32],
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        debug_assert!(
+            uniforms.len() <= MAX_CUSTOM_SHADER_UNIFORMS,
+            "custom shaders take at most {MAX_CUSTOM_SHADER_UNIFORMS} uniforms"
+        );
+
+        let scale_factor = self.scale_factor();
+        let transformation = self.transformation();
+        let bounds = transformation.transform_bounds(bounds.scale(scale_factor));
+        let content_mask = ContentMask {
+            bounds: transformation.transform_bounds(self.content_mask().scale(scale_factor).bounds),
+        };
+        let mut padded_uniforms = [0.; MAX_CUSTOM_SHADER_UNIFORMS];
+        let uniform_count = uniforms.len().min(MAX_CUSTOM_SHADER_UNIFORMS);
+        padded_uniforms[..uniform_count].copy_from_slice(&uniforms[..uniform_count]);
+        self.window
+            .next_frame
+            .scene
+            .insert_primitive(CustomPrimitive {
+                order: 0,
+                shader_id,
+                bounds,
+                content_mask,
+                uniforms: padded_uniforms,
+            });
+    }
+
+    /// Paint a surface, such as a video frame or a texture drawn by another renderer, into the
+    /// scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
     #[cfg(target_os = "macos")]
-    pub fn paint_surface(&mut self, bounds: Bounds<Pixels>, image_buffer: CVImageBuffer) {
+    pub fn paint_surface(&mut self, bounds: Bounds<Pixels>, source: impl Into<SurfaceSource>) {
         use crate::PaintSurface;
 
         debug_assert_eq!(
@@ -2682,16 +3711,111 @@ impl<'a> WindowContext<'a> {
         );
 
         let scale_factor = self.scale_factor();
-        let bounds = bounds.scale(scale_factor);
-        let content_mask = self.content_mask().scale(scale_factor);
+        let transformation = self.transformation();
+        let bounds = transformation.transform_bounds(bounds.scale(scale_factor));
+        let content_mask = ContentMask {
+            bounds: transformation.transform_bounds(self.content_mask().scale(scale_factor).bounds),
+        };
         self.window.next_frame.scene.insert_primitive(PaintSurface {
             order: 0,
             bounds,
             content_mask,
-            image_buffer,
+            source: source.into(),
         });
     }
 
+    /// Paint a frame of a [`SceneRecording`] into the scene for the next frame, as it was drawn
+    /// when it was recorded. Its primitives are positioned in device pixels from the window's
+    /// origin, regardless of the current element offset, content mask and z-index.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_recorded_frame(
+        &mut self,
+        recording: &SceneRecording,
+        frame_index: usize,
+    ) -> Result<()> {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        let frame = recording.frames.get(frame_index).with_context(|| {
+            format!(
+                "frame {frame_index} of a recording of {} frames",
+                recording.frames.len()
+            )
+        })?;
+        let atlas = self.window.sprite_atlas.clone();
+        self.window.next_frame.scene.insert_recorded_operations(
+            &frame.operations,
+            &mut |tile_index| {
+                let tile = recording
+                    .tiles
+                    .get(tile_index)
+                    .with_context(|| format!("recorded tile {tile_index}"))?;
+                atlas.get_or_insert_with(&tile.atlas_key(), &mut || {
+                    Ok(Some((tile.size(), Cow::Borrowed(tile.bytes.as_slice()))))
+                })
+            },
+        )
+    }
+
+    /// Create a native web view in this window, failing on platforms that don't support them.
+    /// It's shown by painting a [`web_view`](crate::web_view) element for it.
+    pub fn new_web_view(&mut self) -> Result<Model<WebView>> {
+        let platform: Rc<dyn PlatformWebView> = self
+            .window
+            .platform_window
+            .create_web_view()
+            .ok_or_else(|| anyhow!("web views aren't supported on this platform"))?
+            .into();
+        Ok(self.new_model(|cx| WebView::new(platform, cx)))
+    }
+
+    /// Show a web view within the given bounds, clipped to the current content mask, on the
+    /// next frame.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_web_view(&mut self, bounds: Bounds<Pixels>, web_view: &Model<WebView>) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        let clip = bounds.intersect(&self.content_mask().bounds);
+        let platform = web_view.read(self).platform.clone();
+        self.window.next_frame.web_views.push(WebViewRequest {
+            web_view_id: web_view.entity_id(),
+            platform,
+            bounds,
+            clip,
+        });
+    }
+
+    /// Moves the web views painted in the next frame into place, and hides the ones that were
+    /// shown in the last frame but not painted in this one.
+    fn position_web_views(&self) {
+        let next_frame = &self.window.next_frame;
+        for request in &self.window.rendered_frame.web_views {
+            if !next_frame
+                .web_views
+                .iter()
+                .any(|next| next.web_view_id == request.web_view_id)
+            {
+                request.platform.hide();
+            }
+        }
+        for request in &next_frame.web_views {
+            if request.clip.is_empty() {
+                request.platform.hide();
+            } else {
+                request.platform.set_frame(request.bounds, request.clip);
+            }
+        }
+    }
+
     #[must_use]
     /// Add a node to the layout tree for the current frame. Takes the `Style` of the element for which
     /// layout is being requested, along with the layout ids of any children. This method is called during
@@ -2802,6 +3926,10 @@ impl<'a> WindowContext<'a> {
         );
 
         let content_mask = self.content_mask();
+        let transformation = self
+            .transformation()
+            .scale_translation(1. / self.scale_factor());
+        let z_index = self.z_index();
         let window = &mut self.window;
         let id = window.next_hitbox_id;
         window.next_hitbox_id.0 += 1;
@@ -2809,10 +3937,71 @@ impl<'a> WindowContext<'a> {
             id,
             bounds,
             content_mask,
+            transformation,
             opaque,
+            z_index,
+        };
+        window.next_frame.hitboxes.push(hitbox.clone());
+        hitbox
+    }
+
+    /// Delays changes to the hover state of the given hitbox, which belongs to the element with
+    /// the given id. The hover state is resolved when the mouse moves and when each frame is
+    /// drawn, and the window is redrawn when a delayed change comes due.
+    ///
+    /// This method should only be called as part of the prepaint phase of element drawing.
+    pub fn set_hover_delays(
+        &mut self,
+        hitbox: &Hitbox,
+        element_id: &GlobalElementId,
+        delays: HoverDelays,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Prepaint,
+            "this method can only be called during prepaint"
+        );
+
+        self.window.next_frame.hover_delays.insert(
+            hitbox.id,
+            HoverDelayRequest {
+                element_id: GlobalElementId(element_id.0.clone()),
+                delays,
+            },
+        );
+    }
+
+    /// Resolves the hover state of the hitboxes with [HoverDelays] against the current hit test,
+    /// using the frame being drawn if there is one. Schedules a redraw for when the next delayed
+    /// change comes due.
+    fn update_delayed_hovers(&mut self) {
+        let now = Instant::now();
+        let window = &mut self.window;
+        let frame = if window.draw_phase == DrawPhase::None {
+            &window.rendered_frame
+        } else {
+            &window.next_frame
         };
-        window.next_frame.hitboxes.push(hitbox.clone());
-        hitbox
+        let next_change = update_delayed_hovers(
+            frame,
+            &window.mouse_hit_test,
+            &mut window.delayed_hovers,
+            now,
+        );
+
+        let task = next_change.map(|at| {
+            self.spawn(|mut cx| async move {
+                cx.background_executor
+                    .timer(at.saturating_duration_since(now))
+                    .await;
+                cx.update(|cx| {
+                    cx.update_delayed_hovers();
+                    cx.refresh();
+                })
+                .ok();
+            })
+        });
+        self.window.delayed_hovers_task = task;
     }
 
     /// Sets the key context for the current element. This context will be used to translate
@@ -3157,7 +4346,9 @@ impl<'a> WindowContext<'a> {
         };
 
         if let Some(any_mouse_event) = event.mouse_event() {
+            self.window.dispatching_mouse_event = true;
             self.dispatch_mouse_event(any_mouse_event);
+            self.window.dispatching_mouse_event = false;
         } else if let Some(any_key_event) = event.keyboard_event() {
             self.dispatch_key_event(any_key_event);
         }
@@ -3172,6 +4363,7 @@ impl<'a> WindowContext<'a> {
         let hit_test = self.window.rendered_frame.hit_test(self.mouse_position());
         if hit_test != self.window.mouse_hit_test {
             self.window.mouse_hit_test = hit_test;
+            self.update_delayed_hovers();
             self.reset_cursor_style();
         }
 
@@ -3724,6 +4916,47 @@ impl<'a> WindowContext<'a> {
         dispatch_tree.bindings_for_action(action, &context_stack)
     }
 
+    /// Returns the key bindings that can currently be used on the focused element, along with the
+    /// key context each binding applies in, or `None` for the bindings that apply everywhere. The
+    /// bindings are ordered from the innermost context outwards.
+    pub fn available_bindings(&self) -> Vec<(Option<KeyContext>, KeyBinding)> {
+        let dispatch_tree = &self.window.rendered_frame.dispatch_tree;
+        let node_id = self
+            .window
+            .focus
+            .and_then(|focus_id| dispatch_tree.focusable_node_id(focus_id))
+            .unwrap_or_else(|| dispatch_tree.root_node_id());
+        let context_stack: Vec<_> = dispatch_tree
+            .dispatch_path(node_id)
+            .into_iter()
+            .filter_map(|node_id| dispatch_tree.node(node_id).context.clone())
+            .collect();
+
+        let mut bindings = Vec::new();
+        for action in self.available_actions() {
+            for binding in dispatch_tree.bindings_for_action(action.as_ref(), &context_stack) {
+                // A binding applies in the innermost context in which its predicate holds.
+                let depth = binding.context_predicate.as_ref().map_or(0, |predicate| {
+                    (1..=context_stack.len())
+                        .rev()
+                        .find(|depth| predicate.eval(&context_stack[..*depth]))
+                        .unwrap_or(0)
+                });
+                bindings.push((depth, binding));
+            }
+        }
+        bindings.sort_by_key(|(depth, _)| cmp::Reverse(*depth));
+        bindings
+            .into_iter()
+            .map(|(depth, binding)| {
+                (
+                    depth.checked_sub(1).map(|ix| context_stack[ix].clone()),
+                    binding,
+                )
+            })
+            .collect()
+    }
+
     /// Returns a generic event listener that invokes the given listener with the view and context associated with the given view handle.
     pub fn listener_for<V: Render, E>(
         &self,
@@ -3886,9 +5119,18 @@ impl VisualContext for WindowContext<'_> {
         let view = View {
             model: slot.clone(),
         };
+        #[cfg(any(test, feature = "test-support"))]
+        {
+            self.app
+                .entities
+                .view_created(self.window.handle.id, view.entity_id());
+            self.app.entities.push_holder(view.entity_id());
+        }
         let mut cx = ViewContext::new(&mut *self.app, &mut *self.window, &view);
         let entity = build_view_state(&mut cx);
         cx.entities.insert(slot, entity);
+        #[cfg(any(test, feature = "test-support"))]
+        self.app.entities.pop_holder();
 
         // Non-generic part to avoid leaking SubscriberSet to invokers of `new_view`.
         fn notify_observers(cx: &mut WindowContext, tid: TypeId, view: AnyView) {
@@ -4250,6 +5492,50 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         subscription
     }
 
+    /// Registers a callback to be invoked when the window moves to another display.
+    pub fn observe_window_display(
+        &mut self,
+        mut callback: impl FnMut(&mut V, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let view = self.view.downgrade();
+        let (subscription, activate) = self.window.display_observers.insert(
+            (),
+            Box::new(move |cx| view.update(cx, |view, cx| callback(view, cx)).is_ok()),
+        );
+        activate();
+        subscription
+    }
+
+    /// Registers a callback to be invoked when the window's scale factor changes, e.g. because
+    /// it moved to a display with a different DPI.
+    pub fn observe_window_scale_factor(
+        &mut self,
+        mut callback: impl FnMut(&mut V, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let view = self.view.downgrade();
+        let (subscription, activate) = self.window.scale_factor_observers.insert(
+            (),
+            Box::new(move |cx| view.update(cx, |view, cx| callback(view, cx)).is_ok()),
+        );
+        activate();
+        subscription
+    }
+
+    /// Registers a callback to be invoked when the window becomes occluded or visible again, see
+    /// [`WindowContext::is_window_occluded`].
+    pub fn observe_window_occlusion(
+        &mut self,
+        mut callback: impl FnMut(&mut V, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let view = self.view.downgrade();
+        let (subscription, activate) = self.window.occlusion_observers.insert(
+            (),
+            Box::new(move |cx| view.update(cx, |view, cx| callback(view, cx)).is_ok()),
+        );
+        activate();
+        subscription
+    }
+
     /// Register a callback to be invoked when a keystroke is received by the application
     /// in any window. Note that this fires after all other action and event mechanisms have resolved
     /// and that this API will not be invoked if the event's propagation is stopped.
@@ -4280,6 +5566,27 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         )
     }
 
+    /// Register a callback to be invoked when an action is dispatched in this window in response to
+    /// a mouse event, such as clicking a button, rather than a keystroke.
+    pub fn observe_clicked_actions(
+        &mut self,
+        mut f: impl FnMut(&mut V, &dyn Action, &mut ViewContext<V>) + 'static,
+    ) -> Subscription {
+        let view = self.view.downgrade();
+        let window = self.window_handle();
+        let (subscription, activate) = self.clicked_action_observers.insert(
+            (),
+            Box::new(move |action, cx| {
+                if cx.window_handle() != window {
+                    return true;
+                }
+                view.update(cx, |view, cx| f(view, action, cx)).is_ok()
+            }),
+        );
+        activate();
+        subscription
+    }
+
     /// Register a callback to be invoked when the window's pending input changes.
     pub fn observe_pending_input(
         &mut self,
@@ -4416,6 +5723,7 @@ impl<'a, V: 'static> ViewContext<'a, V> {
     /// The given callback is invoked with a [`WeakView<V>`] to avoid leaking the view for a long-running process.
     /// It's also given an [`AsyncWindowContext`], which can be used to access the state of the view across await points.
     /// The returned future will be polled on the main thread.
+    #[track_caller]
     pub fn spawn<Fut, R>(
         &mut self,
         f: impl FnOnce(WeakView<V>, AsyncWindowContext) -> Fut,
@@ -4425,7 +5733,15 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         Fut: Future<Output = R> + 'static,
     {
         let view = self.view().downgrade();
-        self.window_cx.spawn(|cx| f(view, cx))
+        let owner = task_tracking_enabled().then(|| TaskOwner {
+            entity: view.model.clone().into(),
+            type_name: std::any::type_name::<V>(),
+        });
+        let cx = self.window_cx.to_async();
+        self.window_cx
+            .app
+            .foreground_executor
+            .spawn_tracked(f(view, cx), None, owner)
     }
 
     /// Register a callback to be invoked when the given global state changes.
@@ -4838,6 +6154,8 @@ pub enum ElementId {
     FocusHandle(FocusId),
     /// A combination of a name and an integer.
     NamedInteger(SharedString, usize),
+    /// The place in the source code an ID was created from, see [`WindowContext::use_state`].
+    CodeLocation(std::panic::Location<'static>),
 }
 
 impl Display for ElementId {
@@ -4849,6 +6167,7 @@ impl Display for ElementId {
             ElementId::FocusHandle(_) => write!(f, "FocusHandle")?,
             ElementId::NamedInteger(s, i) => write!(f, "{}-{}", s, i)?,
             ElementId::Uuid(uuid) => write!(f, "{}", uuid)?,
+            ElementId::CodeLocation(location) => write!(f, "{}", location)?,
         }
 
         Ok(())
@@ -4927,6 +6246,101 @@ impl From<(&'static str, u32)> for ElementId {
     }
 }
 
+/// How an image repeats to fill the bounds it's painted in, like a CSS `background-repeat`.
+/// Passed as an argument to [`WindowContext::paint_repeated_image`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImageRepeat {
+    /// The size each copy of the image is drawn at.
+    pub size: Size<Pixels>,
+    /// The gap left between neighboring copies.
+    pub spacing: Size<Pixels>,
+    /// Where one of the copies starts, relative to the origin of the bounds. The others are
+    /// laid out from there in every direction.
+    pub origin: Point<Pixels>,
+}
+
+impl ImageRepeat {
+    /// Repeats copies of the given size edge to edge, starting at the origin of the bounds.
+    pub fn new(size: Size<Pixels>) -> Self {
+        Self {
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the gap left between neighboring copies.
+    pub fn spacing(mut self, spacing: Size<Pixels>) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets where one of the copies starts, relative to the origin of the bounds.
+    pub fn origin(mut self, origin: Point<Pixels>) -> Self {
+        self.origin = origin;
+        self
+    }
+}
+
+/// Color adjustments applied to an image as it's drawn, like a CSS `filter`. They're applied by
+/// the GPU, so an image can be dimmed or desaturated without adding a filtered copy of it to the
+/// atlas. Passed as an argument to [`WindowContext::paint_image`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorFilter {
+    /// How much the image is desaturated, from 0 for its own colors to 1 for grayscale.
+    pub grayscale: f32,
+    /// How far the hues of the image are rotated around the color wheel.
+    pub hue_rotate: Radians,
+    /// A color the image's colors are blended towards, by as much as the tint's alpha.
+    pub tint: Hsla,
+    /// The opacity of the image, combined with that of the element painting it.
+    pub opacity: f32,
+}
+
+impl Default for ColorFilter {
+    fn default() -> Self {
+        Self {
+            grayscale: 0.,
+            hue_rotate: radians(0.),
+            tint: transparent_black(),
+            opacity: 1.,
+        }
+    }
+}
+
+impl ColorFilter {
+    /// A filter that draws the image in grayscale.
+    pub fn grayscale() -> Self {
+        Self {
+            grayscale: 1.,
+            ..Default::default()
+        }
+    }
+
+    /// Sets how much the image is desaturated, from 0 for its own colors to 1 for grayscale.
+    pub fn desaturate(mut self, amount: f32) -> Self {
+        self.grayscale = amount.clamp(0., 1.);
+        self
+    }
+
+    /// Sets how far the hues of the image are rotated around the color wheel.
+    pub fn hue_rotate(mut self, angle: impl Into<Radians>) -> Self {
+        self.hue_rotate = angle.into();
+        self
+    }
+
+    /// Sets the color the image's colors are blended towards, by as much as the tint's alpha.
+    pub fn tint(mut self, tint: impl Into<Hsla>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// Sets the opacity of the image.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+}
+
 /// A rectangle to be rendered in the window at the given position and size.
 /// Passed as an argument [`WindowContext::paint_quad`].
 #[derive(Clone)]
@@ -4935,8 +6349,8 @@ pub struct PaintQuad {
     pub bounds: Bounds<Pixels>,
     /// The radii of the quad's corners.
     pub corner_radii: Corners<Pixels>,
-    /// The background color of the quad.
-    pub background: Hsla,
+    /// The background of the quad, either a color or a [`crate::linear_gradient`].
+    pub background: Background,
     /// The widths of the quad's borders.
     pub border_widths: Edges<Pixels>,
     /// The color of the quad's borders.
@@ -4968,8 +6382,8 @@ impl PaintQuad {
         }
     }
 
-    /// Sets the background color of the quad.
-    pub fn background(self, background: impl Into<Hsla>) -> Self {
+    /// Sets the background of the quad.
+    pub fn background(self, background: impl Into<Background>) -> Self {
         PaintQuad {
             background: background.into(),
             ..self
@@ -4981,7 +6395,7 @@ impl PaintQuad {
 pub fn quad(
     bounds: Bounds<Pixels>,
     corner_radii: impl Into<Corners<Pixels>>,
-    background: impl Into<Hsla>,
+    background: impl Into<Background>,
     border_widths: impl Into<Edges<Pixels>>,
     border_color: impl Into<Hsla>,
 ) -> PaintQuad {
@@ -4995,7 +6409,7 @@ pub fn quad(
 }
 
 /// Creates a filled quad with the given bounds and background color.
-pub fn fill(bounds: impl Into<Bounds<Pixels>>, background: impl Into<Hsla>) -> PaintQuad {
+pub fn fill(bounds: impl Into<Bounds<Pixels>>, background: impl Into<Background>) -> PaintQuad {
     PaintQuad {
         bounds: bounds.into(),
         corner_radii: (0.).into(),
@@ -5010,8 +6424,570 @@ pub fn outline(bounds: impl Into<Bounds<Pixels>>, border_color: impl Into<Hsla>)
     PaintQuad {
         bounds: bounds.into(),
         corner_radii: (0.).into(),
-        background: transparent_black(),
+        background: transparent_black().into(),
         border_widths: (1.).into(),
         border_color: border_color.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, div, AtlasKey, InteractiveElement, IntoElement, PowerState, RenderOnce,
+        SerializedBounds, SerializedPrimitiveKind, StatefulInteractiveElement, Styled,
+        TestAppContext, VisualTestContext, WeakModel,
+    };
+
+    #[derive(IntoElement)]
+    struct Disclosure {
+        states: Rc<RefCell<Vec<WeakModel<bool>>>>,
+    }
+
+    impl RenderOnce for Disclosure {
+        fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+            let expanded = cx.use_state(|_| false);
+            self.states.borrow_mut().push(expanded.downgrade());
+            div()
+        }
+    }
+
+    struct TestView {
+        show_disclosure: bool,
+        states: Rc<RefCell<Vec<WeakModel<bool>>>>,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().when(self.show_disclosure, |this| {
+                this.child(Disclosure {
+                    states: self.states.clone(),
+                })
+            })
+        }
+    }
+
+    #[gpui::test]
+    fn test_use_state(cx: &mut TestAppContext) {
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let window = cx.update(|cx| {
+            cx.open_window(Default::default(), |cx| {
+                cx.new_view(|_| TestView {
+                    show_disclosure: true,
+                    states: states.clone(),
+                })
+            })
+            .unwrap()
+        });
+        cx.run_until_parked();
+        let state = states.borrow().last().unwrap().upgrade().unwrap();
+        let render_count = states.borrow().len();
+
+        // Updating the state redraws the view, which gets the same state back.
+        cx.update(|cx| {
+            state.update(cx, |expanded, cx| {
+                *expanded = true;
+                cx.notify();
+            })
+        });
+        cx.run_until_parked();
+        assert!(states.borrow().len() > render_count);
+        assert_eq!(
+            states.borrow().last().unwrap().entity_id(),
+            state.entity_id()
+        );
+        assert!(cx.read(|cx| *state.read(cx)));
+        drop(state);
+
+        // The state is dropped once the component isn't rendered anymore.
+        window
+            .update(cx, |view, cx| {
+                view.show_disclosure = false;
+                cx.notify();
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert!(states.borrow().last().unwrap().upgrade().is_none());
+    }
+
+    struct Parent {
+        _child: View<Child>,
+    }
+
+    enum Child {
+        Strong(View<Parent>),
+        Weak(WeakView<Parent>),
+    }
+
+    impl Render for Parent {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    impl Render for Child {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_window_leak_detection(cx: &mut TestAppContext) {
+        cx.detect_window_leaks();
+        let open_window = |cx: &mut TestAppContext, strong: bool| {
+            cx.update(|cx| {
+                cx.open_window(Default::default(), |cx| {
+                    cx.new_view(|cx| {
+                        let parent = cx.view().downgrade();
+                        Parent {
+                            _child: cx.new_view(|_| {
+                                if strong {
+                                    Child::Strong(parent.upgrade().unwrap())
+                                } else {
+                                    Child::Weak(parent)
+                                }
+                            }),
+                        }
+                    })
+                })
+                .unwrap()
+            })
+        };
+
+        // Views holding each other through weak handles are released with their window.
+        let window = open_window(cx, false);
+        window.update(cx, |_, cx| cx.remove_window()).unwrap();
+        cx.assert_closed_window_views_released();
+
+        // A strong handle back to the parent keeps both views alive.
+        let window = open_window(cx, true);
+        window.update(cx, |_, cx| cx.remove_window()).unwrap();
+        let report = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            cx.assert_closed_window_views_released()
+        }))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+        assert!(report.starts_with("2 views are still alive"), "{report}");
+        let cycle = report
+            .lines()
+            .skip_while(|line| *line != "retain cycles:")
+            .nth(1);
+        assert!(
+            cycle.map_or(false, |cycle| cycle.contains("Parent")
+                && cycle.contains("Child")),
+            "{report}"
+        );
+    }
+
+    struct OcclusionView {
+        occlusion_changes: Vec<bool>,
+        _subscription: Subscription,
+    }
+
+    impl Render for OcclusionView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_observe_window_occlusion(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| OcclusionView {
+            occlusion_changes: Vec::new(),
+            _subscription: cx.observe_window_occlusion(|view, cx| {
+                view.occlusion_changes.push(cx.is_window_occluded())
+            }),
+        });
+
+        cx.simulate_occlusion_change(true);
+        cx.simulate_occlusion_change(true);
+        cx.simulate_occlusion_change(false);
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.occlusion_changes, [true, false]);
+        });
+    }
+
+    struct ScaleFactorView {
+        scale_factors: Vec<f32>,
+        _subscription: Subscription,
+    }
+
+    impl Render for ScaleFactorView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_scale_factor_change(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| ScaleFactorView {
+            scale_factors: Vec::new(),
+            _subscription: cx
+                .observe_window_scale_factor(|view, cx| view.scale_factors.push(cx.scale_factor())),
+        });
+
+        let rasterizations = Rc::new(Cell::new(0));
+        let rasterize = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let key = AtlasKey::Svg(RenderSvgParams {
+                    path: "icon.svg".into(),
+                    size: size(DevicePixels(16), DevicePixels(16)),
+                });
+                cx.window
+                    .sprite_atlas
+                    .get_or_insert_with(&key, &mut || {
+                        rasterizations.set(rasterizations.get() + 1);
+                        Ok(Some((
+                            size(DevicePixels(16), DevicePixels(16)),
+                            Cow::Owned(vec![0; 16 * 16]),
+                        )))
+                    })
+                    .unwrap();
+            });
+        };
+
+        rasterize(cx);
+        rasterize(cx);
+        assert_eq!(rasterizations.get(), 1);
+
+        // Moving to a display with the same scale factor keeps what was rasterized.
+        cx.simulate_scale_factor_change(2.);
+        rasterize(cx);
+        assert_eq!(rasterizations.get(), 1);
+
+        cx.simulate_scale_factor_change(1.);
+        rasterize(cx);
+        assert_eq!(rasterizations.get(), 2);
+        assert_eq!(cx.update(|cx| cx.scale_factor()), 1.);
+        view.read_with(cx, |view, _| assert_eq!(view.scale_factors, [1.]));
+    }
+
+    #[gpui::test]
+    fn test_accessibility_overrides(cx: &mut TestAppContext) {
+        cx.simulate_accessibility_preferences(AccessibilityPreferences {
+            reduce_motion: true,
+            ..Default::default()
+        });
+        cx.update(|cx| {
+            assert!(cx.accessibility_preferences().reduce_motion);
+
+            cx.set_accessibility_overrides(AccessibilityOverrides {
+                reduce_motion: Some(false),
+                increase_contrast: Some(true),
+                reduce_transparency: None,
+            });
+            assert_eq!(
+                cx.accessibility_preferences(),
+                AccessibilityPreferences {
+                    reduce_motion: false,
+                    increase_contrast: true,
+                    reduce_transparency: false,
+                }
+            );
+            assert!(cx.system_accessibility_preferences().reduce_motion);
+        });
+    }
+
+    #[gpui::test]
+    fn test_power_state(cx: &mut TestAppContext) {
+        cx.simulate_power_state(PowerState {
+            on_battery: true,
+            low_power_mode: false,
+        });
+
+        let observed_states = Rc::new(RefCell::new(Vec::new()));
+        let _subscription = cx.update(|cx| {
+            let observed_states = observed_states.clone();
+            cx.observe_power_state(move |power_state, _| {
+                observed_states.borrow_mut().push(power_state)
+            })
+        });
+        let waited = Rc::new(Cell::new(false));
+        let wait_for_power = cx.update(|cx| cx.wait_while_conserving_power());
+        cx.spawn({
+            let waited = waited.clone();
+            |_| async move {
+                wait_for_power.await;
+                waited.set(true);
+            }
+        })
+        .detach();
+        cx.run_until_parked();
+        assert!(!waited.get());
+        assert!(cx.update(|cx| cx.power_state().should_conserve_power()));
+
+        cx.simulate_power_state(PowerState::default());
+        cx.run_until_parked();
+        assert!(waited.get());
+        assert_eq!(*observed_states.borrow(), [PowerState::default()]);
+        assert!(!cx.update(|cx| cx.power_state().should_conserve_power()));
+    }
+
+    struct HoverDelaysView;
+
+    impl Render for HoverDelaysView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(
+                div()
+                    .id("target")
+                    .size(px(50.))
+                    .hover_delays(Duration::from_millis(100), Duration::from_millis(200))
+                    .hover(|style| style.bg(crate::red())),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_hover_delays(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| HoverDelaysView);
+        let is_hovered = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let hitbox_id = *cx.window.rendered_frame.hover_delays.keys().next().unwrap();
+                hitbox_id.is_hovered(cx)
+            })
+        };
+
+        cx.simulate_mouse_move(point(px(100.), px(100.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.run_until_parked();
+        assert!(!is_hovered(cx));
+
+        cx.simulate_mouse_move(point(px(25.), px(25.)), None, Modifiers::none());
+        assert!(!is_hovered(cx));
+        cx.executor().advance_clock(Duration::from_millis(50));
+        cx.run_until_parked();
+        assert!(!is_hovered(cx));
+        cx.executor().advance_clock(Duration::from_millis(50));
+        cx.run_until_parked();
+        assert!(is_hovered(cx));
+
+        // Skimming out and back in before the leave delay keeps the element hovered.
+        cx.simulate_mouse_move(point(px(100.), px(100.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(150));
+        cx.run_until_parked();
+        assert!(is_hovered(cx));
+        cx.simulate_mouse_move(point(px(25.), px(25.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert!(is_hovered(cx));
+
+        cx.simulate_mouse_move(point(px(100.), px(100.)), None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.run_until_parked();
+        assert!(!is_hovered(cx));
+    }
+
+    struct HitTestView;
+
+    impl Render for HitTestView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("background")
+                .size_full()
+                .bg(crate::white())
+                .on_click(|_, _| {})
+                .child(
+                    div()
+                        .id("button")
+                        .absolute()
+                        .top(px(10.))
+                        .left(px(10.))
+                        .size(px(20.))
+                        .bg(crate::red())
+                        .on_click(|_, _| {}),
+                )
+                .child(
+                    div()
+                        .id("overlay")
+                        .absolute()
+                        .top(px(40.))
+                        .left(px(40.))
+                        .size(px(20.))
+                        .occlude(),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_hit_test_queries(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| HitTestView);
+        let hitbox_bounds = |position, cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                cx.hitboxes_at(position)
+                    .into_iter()
+                    .map(|hitbox| hitbox.bounds)
+                    .collect::<Vec<_>>()
+            })
+        };
+        let window_bounds = cx.update(|cx| Bounds::new(Point::default(), cx.viewport_size()));
+
+        assert_eq!(
+            hitbox_bounds(point(px(15.), px(15.)), cx),
+            [
+                Bounds::new(point(px(10.), px(10.)), size(px(20.), px(20.))),
+                window_bounds
+            ]
+        );
+        assert_eq!(hitbox_bounds(point(px(35.), px(35.)), cx), [window_bounds]);
+        // The overlay occludes the background beneath it.
+        assert_eq!(
+            hitbox_bounds(point(px(45.), px(45.)), cx),
+            [Bounds::new(point(px(40.), px(40.)), size(px(20.), px(20.)))]
+        );
+
+        // The window has a scale factor of 2, so primitives are described in device pixels.
+        let primitives = cx.update(|cx| cx.primitives_at(point(px(15.), px(15.))));
+        let bounds = primitives
+            .iter()
+            .map(|primitive| primitive.bounds)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bounds,
+            [
+                SerializedBounds {
+                    x: 20.,
+                    y: 20.,
+                    width: 40.,
+                    height: 40.,
+                },
+                window_bounds.scale(2.).into()
+            ]
+        );
+        assert!(cx
+            .update(|cx| cx.primitives_at(point(px(45.), px(45.))))
+            .iter()
+            .all(|primitive| primitive.bounds == window_bounds.scale(2.).into()));
+    }
+
+    struct InsetShadowView;
+
+    impl Render for InsetShadowView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let shadow = |inset| BoxShadow {
+                color: crate::black(),
+                offset: point(px(0.), px(2.)),
+                blur_radius: px(4.),
+                spread_radius: px(1.),
+                inset,
+            };
+            div()
+                .size(px(20.))
+                .bg(crate::red())
+                .shadow(smallvec::smallvec![shadow(false), shadow(true)])
+        }
+    }
+
+    #[gpui::test]
+    fn test_inset_shadows(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| InsetShadowView);
+        let scene = cx.update(|cx| cx.serialize_scene());
+
+        // Outer shadows are painted below the background, and inset ones above it, within the
+        // element's bounds. The window has a scale factor of 2.
+        let insets = scene
+            .primitives
+            .iter()
+            .map(|primitive| match primitive.kind {
+                SerializedPrimitiveKind::Shadow { inset, .. } => Some(inset),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(insets, [Some(None), None, Some(Some([0., 4., 2.]))]);
+        assert_eq!(
+            scene.primitives[0].bounds,
+            SerializedBounds {
+                x: -2.,
+                y: 2.,
+                width: 44.,
+                height: 44.,
+            }
+        );
+        assert_eq!(
+            scene.primitives[2].bounds,
+            SerializedBounds {
+                x: 0.,
+                y: 0.,
+                width: 40.,
+                height: 40.,
+            }
+        );
+    }
+
+    struct GroupFocusView {
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for GroupFocusView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .group("row")
+                .child(
+                    div()
+                        .debug_selector(|| "child".into())
+                        .size(px(10.))
+                        .group_focus("row", |style| style.w(px(30.))),
+                )
+                .track_focus(&self.focus_handle)
+        }
+    }
+
+    #[gpui::test]
+    fn test_group_focus_layout(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|cx| GroupFocusView {
+            focus_handle: cx.focus_handle(),
+        });
+        assert_eq!(cx.debug_bounds("child").unwrap().size.width, px(10.));
+
+        // Group focus styles apply to the properties that affect layout as well.
+        cx.update(|cx| view.read(cx).focus_handle.clone().focus(cx));
+        cx.run_until_parked();
+        assert_eq!(cx.debug_bounds("child").unwrap().size.width, px(30.));
+    }
+
+    struct OpacityGroupView {
+        opacity_group: bool,
+    }
+
+    impl Render for OpacityGroupView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .opacity(0.5)
+                .when(self.opacity_group, |this| this.opacity_group())
+                .child(div().size(px(10.)).bg(crate::red()))
+                .child(div().size(px(10.)).bg(crate::blue()))
+        }
+    }
+
+    #[gpui::test]
+    fn test_opacity_group_style(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| OpacityGroupView {
+            opacity_group: false,
+        });
+        let scene = cx.update(|cx| cx.serialize_scene());
+        assert_eq!(scene.primitives.len(), 2);
+        assert!(scene
+            .primitives
+            .iter()
+            .all(|primitive| matches!(primitive.kind, SerializedPrimitiveKind::Quad { .. })));
+
+        // With an opacity group, the children are painted at full opacity into a single group
+        // that is faded as a whole.
+        view.update(cx, |view, cx| {
+            view.opacity_group = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        let scene = cx.update(|cx| cx.serialize_scene());
+        assert_eq!(scene.primitives.len(), 1);
+        let SerializedPrimitiveKind::OpacityGroup {
+            opacity,
+            primitives,
+        } = &scene.primitives[0].kind
+        else {
+            panic!("expected an opacity group");
+        };
+        assert_eq!(*opacity, 0.5);
+        assert_eq!(primitives.len(), 2);
+    }
+}
@@ -312,6 +312,62 @@ pub fn cursor_style_methods(input: TokenStream) -> TokenStream {
             self.style().mouse_cursor = Some(gpui::CursorStyle::ResizeLeft);
             self
         }
+
+        /// Sets cursor style when hovering over an element to `nwse-resize`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_nwse_resize(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::ResizeUpLeftDownRight);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `nesw-resize`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_nesw_resize(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::ResizeUpRightDownLeft);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `all-scroll`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_all_scroll(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::Move);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `wait`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_wait(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::Wait);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `progress`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_progress(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::Progress);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `help`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_help(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::Help);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `zoom-in`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_zoom_in(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::ZoomIn);
+            self
+        }
+
+        /// Sets cursor style when hovering over an element to `zoom-out`.
+        /// [Docs](https://tailwindcss.com/docs/cursor)
+        #visibility fn cursor_zoom_out(mut self) -> Self {
+            self.style().mouse_cursor = Some(gpui::CursorStyle::ZoomOut);
+            self
+        }
     };
 
     output.into()
@@ -370,7 +426,8 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as StyleableMacroInput);
     let visibility = input.method_visibility;
     let output = quote! {
-        /// Sets the box shadow of the element.
+        /// Sets the box shadows of the element, which are stacked so that the last one is
+        /// painted on top.
         /// [Docs](https://tailwindcss.com/docs/box-shadow)
         #visibility fn shadow(mut self, shadows: smallvec::SmallVec<[gpui::BoxShadow; 2]>) -> Self {
             self.style().box_shadow = Some(shadows);
@@ -395,6 +452,7 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                 offset: point(px(0.), px(1.)),
                 blur_radius: px(2.),
                 spread_radius: px(0.),
+                inset: false,
             }]);
             self
         }
@@ -411,12 +469,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(4.)),
                     blur_radius: px(6.),
                     spread_radius: px(-1.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(2.)),
                     blur_radius: px(4.),
                     spread_radius: px(-2.),
+                    inset: false,
                 }
             ]);
             self
@@ -434,12 +494,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(10.)),
                     blur_radius: px(15.),
                     spread_radius: px(-3.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(4.)),
                     blur_radius: px(6.),
                     spread_radius: px(-4.),
+                    inset: false,
                 }
             ]);
             self
@@ -457,12 +519,14 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                     offset: point(px(0.), px(20.)),
                     blur_radius: px(25.),
                     spread_radius: px(-5.),
+                    inset: false,
                 },
                 BoxShadow {
                     color: hsla(0., 0., 0., 0.1),
                     offset: point(px(0.), px(8.)),
                     blur_radius: px(10.),
                     spread_radius: px(-6.),
+                    inset: false,
                 }
             ]);
             self
@@ -479,6 +543,23 @@ pub fn box_shadow_style_methods(input: TokenStream) -> TokenStream {
                 offset: point(px(0.), px(25.)),
                 blur_radius: px(50.),
                 spread_radius: px(-12.),
+                inset: false,
+            }]);
+            self
+        }
+
+        /// Sets the box shadow of the element to a shadow inside its bounds.
+        /// [Docs](https://tailwindcss.com/docs/box-shadow#adding-an-inset-shadow)
+        #visibility fn shadow_inner(mut self) -> Self {
+            use gpui::{BoxShadow, hsla, point, px};
+            use smallvec::smallvec;
+
+            self.style().box_shadow = Some(smallvec![BoxShadow {
+                color: hsla(0., 0., 0., 0.05),
+                offset: point(px(0.), px(2.)),
+                blur_radius: px(4.),
+                spread_radius: px(0.),
+                inset: true,
             }]);
             self
         }
@@ -0,0 +1,13 @@
+use gpui::{actions, AppContext};
+use workspace::Workspace;
+
+pub mod html_preview_view;
+
+actions!(html_preview, [OpenPreview, OpenPreviewToTheSide]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        html_preview_view::HtmlPreviewView::register(workspace, cx);
+    })
+    .detach();
+}
@@ -0,0 +1,472 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use anyhow::Result;
+use editor::Editor;
+use fs::{Fs, Watcher};
+use futures::StreamExt as _;
+use gpui::{
+    list, AppContext, EventEmitter, FocusHandle, FocusableView, IntoElement, ListState,
+    ParentElement, Render, Styled, Task, View, ViewContext, WeakView,
+};
+use html_to_markdown::{convert_html_to_markdown, markdown, TagHandler};
+use language::LanguageRegistry;
+use markdown_preview::{
+    markdown_elements::ParsedMarkdown,
+    markdown_parser::parse_markdown,
+    markdown_renderer::{render_markdown_block, RenderContext},
+};
+use regex::Regex;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::item::Item;
+use workspace::{Pane, Workspace};
+
+use crate::{OpenPreview, OpenPreviewToTheSide};
+
+/// How long to wait for the file system to settle after a change before reloading.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The width the preview is rendered at, to see how a page lays out on different devices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DevicePreset {
+    /// The preview fills the pane.
+    #[default]
+    Responsive,
+    Mobile,
+    Tablet,
+    Desktop,
+}
+
+impl DevicePreset {
+    pub const ALL: [DevicePreset; 4] = [
+        DevicePreset::Responsive,
+        DevicePreset::Mobile,
+        DevicePreset::Tablet,
+        DevicePreset::Desktop,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DevicePreset::Responsive => "Responsive",
+            DevicePreset::Mobile => "Mobile",
+            DevicePreset::Tablet => "Tablet",
+            DevicePreset::Desktop => "Desktop",
+        }
+    }
+
+    pub fn width(&self) -> Option<Pixels> {
+        match self {
+            DevicePreset::Responsive => None,
+            DevicePreset::Mobile => Some(px(375.)),
+            DevicePreset::Tablet => Some(px(768.)),
+            DevicePreset::Desktop => Some(px(1280.)),
+        }
+    }
+}
+
+/// Renders a local HTML file, reloading it when it or the files it references are saved.
+///
+/// The page is rendered by converting it to Markdown, so its structure and text are shown while
+/// its styles and scripts are not.
+pub struct HtmlPreviewView {
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    path: PathBuf,
+    language_registry: Arc<LanguageRegistry>,
+    focus_handle: FocusHandle,
+    contents: Option<ParsedMarkdown>,
+    list_state: ListState,
+    device: DevicePreset,
+    /// The local files the page references, like stylesheets and images.
+    assets: Vec<PathBuf>,
+    watcher: Option<Arc<dyn Watcher>>,
+    watched_dirs: Vec<PathBuf>,
+    error: Option<SharedString>,
+    reload_task: Option<Task<()>>,
+    _watch_task: Task<()>,
+}
+
+impl HtmlPreviewView {
+    pub fn register(workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>) {
+        workspace.register_action(move |workspace, _: &OpenPreview, cx| {
+            if let Some(path) = Self::resolve_active_html_path(workspace, cx) {
+                let pane = workspace.active_pane().clone();
+                Self::open_in_pane(workspace, path, pane, true, cx);
+            }
+        });
+
+        workspace.register_action(move |workspace, _: &OpenPreviewToTheSide, cx| {
+            if let Some(path) = Self::resolve_active_html_path(workspace, cx) {
+                let pane = workspace
+                    .find_pane_in_direction(workspace::SplitDirection::Right, cx)
+                    .unwrap_or_else(|| {
+                        workspace.split_pane(
+                            workspace.active_pane().clone(),
+                            workspace::SplitDirection::Right,
+                            cx,
+                        )
+                    });
+                Self::open_in_pane(workspace, path, pane, false, cx);
+            }
+        });
+    }
+
+    /// Activates the preview of the file in the pane, or adds one if there's none.
+    fn open_in_pane(
+        workspace: &mut Workspace,
+        path: PathBuf,
+        pane: View<Pane>,
+        focus: bool,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let existing_view_idx = pane.read(cx).items_of_type::<Self>().find_map(|view| {
+            (view.read(cx).path == path)
+                .then(|| pane.read(cx).index_for_item(&view))
+                .flatten()
+        });
+        if let Some(existing_view_idx) = existing_view_idx {
+            pane.update(cx, |pane, cx| {
+                pane.activate_item(existing_view_idx, focus, focus, cx)
+            });
+        } else {
+            let view = Self::new(workspace, path, cx);
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(view), focus, focus, None, cx)
+            });
+        }
+    }
+
+    fn resolve_active_html_path(
+        workspace: &Workspace,
+        cx: &mut ViewContext<Workspace>,
+    ) -> Option<PathBuf> {
+        let editor = workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))?;
+        Self::html_path(&editor, cx)
+    }
+
+    /// The absolute path of the local HTML file edited in the editor.
+    fn html_path(editor: &View<Editor>, cx: &AppContext) -> Option<PathBuf> {
+        let editor = editor.read(cx);
+        let buffer = editor.buffer().read(cx).as_singleton()?;
+        let language = buffer.read(cx).language()?;
+        if language.name() != "HTML".into() {
+            return None;
+        }
+        let file = editor.file_at(0, cx)?;
+        Some(file.as_local()?.abs_path(cx))
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        path: PathBuf,
+        cx: &mut ViewContext<Workspace>,
+    ) -> View<Self> {
+        let language_registry = workspace.project().read(cx).languages().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        cx.new_view(|cx: &mut ViewContext<Self>| {
+            let view = cx.view().downgrade();
+            let list_state =
+                ListState::new(0, gpui::ListAlignment::Top, px(1000.), move |ix, cx| {
+                    view.update(cx, |this, cx| {
+                        let Some(block) = this
+                            .contents
+                            .as_ref()
+                            .and_then(|contents| contents.children.get(ix))
+                        else {
+                            return div().into_any();
+                        };
+                        let mut render_cx = RenderContext::new(Some(this.workspace.clone()), cx);
+                        div()
+                            .pb_3()
+                            .child(render_markdown_block(block, &mut render_cx))
+                            .into_any()
+                    })
+                    .unwrap_or_else(|_| div().into_any())
+                });
+
+            let watch_task = Self::watch(fs.clone(), path.clone(), cx);
+            let mut this = Self {
+                workspace,
+                fs,
+                path,
+                language_registry,
+                focus_handle: cx.focus_handle(),
+                contents: None,
+                list_state,
+                device: DevicePreset::default(),
+                assets: Vec::new(),
+                watcher: None,
+                watched_dirs: Vec::new(),
+                error: None,
+                reload_task: None,
+                _watch_task: watch_task,
+            };
+            this.reload(false, cx);
+            this
+        })
+    }
+
+    /// Reloads the page whenever its file, or one of its assets, changes on disk.
+    fn watch(fs: Arc<dyn Fs>, path: PathBuf, cx: &mut ViewContext<Self>) -> Task<()> {
+        cx.spawn(|this, mut cx| async move {
+            let Some(dir) = path.parent() else {
+                return;
+            };
+            let (mut events, watcher) = fs.watch(dir, RELOAD_DEBOUNCE).await;
+            if this
+                .update(&mut cx, |this, _| {
+                    this.watched_dirs.push(dir.to_path_buf());
+                    this.watcher = Some(watcher);
+                })
+                .is_err()
+            {
+                return;
+            }
+
+            while let Some(events) = events.next().await {
+                let Ok(()) = this.update(&mut cx, |this, cx| {
+                    let changed = events
+                        .iter()
+                        .any(|event| event.path == this.path || this.assets.contains(&event.path));
+                    if changed {
+                        this.reload(true, cx);
+                    }
+                }) else {
+                    break;
+                };
+            }
+        })
+    }
+
+    fn reload(&mut self, wait_for_debounce: bool, cx: &mut ViewContext<Self>) {
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+        let language_registry = self.language_registry.clone();
+        self.reload_task = Some(cx.spawn(|this, mut cx| async move {
+            if wait_for_debounce {
+                cx.background_executor().timer(RELOAD_DEBOUNCE).await;
+            }
+
+            let dir = path.parent().map(Path::to_path_buf);
+            let result = async {
+                let html = fs.load(&path).await?;
+                let assets = referenced_assets(&html, dir.as_deref());
+                let markdown = cx
+                    .background_executor()
+                    .spawn(async move { html_to_markdown(&html) })
+                    .await?;
+                let contents = parse_markdown(&markdown, dir, Some(language_registry)).await;
+                anyhow::Ok((contents, assets))
+            }
+            .await;
+
+            this.update(&mut cx, |this, cx| {
+                match result {
+                    Ok((contents, assets)) => {
+                        this.error = None;
+                        this.watch_assets(&assets);
+                        this.assets = assets;
+                        let scroll_top = this.list_state.logical_scroll_top();
+                        this.list_state.reset(contents.children.len());
+                        this.list_state.scroll_to(scroll_top);
+                        this.contents = Some(contents);
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Starts watching the directories of assets outside the ones watched already.
+    fn watch_assets(&mut self, assets: &[PathBuf]) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        for dir in assets.iter().filter_map(|asset| asset.parent()) {
+            if !self.watched_dirs.iter().any(|watched| watched == dir) {
+                if watcher.add(dir).log_err().is_some() {
+                    self.watched_dirs.push(dir.to_path_buf());
+                }
+            }
+        }
+    }
+
+    fn set_device(&mut self, device: DevicePreset, cx: &mut ViewContext<Self>) {
+        self.device = device;
+        // Blocks are measured at the width they were last laid out at.
+        self.list_state.reset(
+            self.contents
+                .as_ref()
+                .map_or(0, |contents| contents.children.len()),
+        );
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .pb_2()
+            .mb_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(DevicePreset::ALL.into_iter().map(|device| {
+                let label = match device.width() {
+                    Some(width) => format!("{} ({}px)", device.label(), width.0),
+                    None => device.label().to_string(),
+                };
+                Button::new(device.label(), label)
+                    .label_size(LabelSize::Small)
+                    .selected(self.device == device)
+                    .on_click(cx.listener(move |this, _, cx| this.set_device(device, cx)))
+            }))
+    }
+}
+
+/// The local files an HTML page links to or embeds, resolved against its directory. Remote URLs,
+/// fragments and data URLs are left out.
+fn referenced_assets(html: &str, dir: Option<&Path>) -> Vec<PathBuf> {
+    static ASSET_ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"(?i)\b(?:href|src)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+    });
+
+    let Some(dir) = dir else {
+        return Vec::new();
+    };
+    let mut assets = Vec::new();
+    for captures in ASSET_ATTRIBUTE.captures_iter(html) {
+        let Some(reference) = captures.get(1).or_else(|| captures.get(2)) else {
+            continue;
+        };
+        // Drop queries and fragments, which cache-busting references often have.
+        let reference = reference
+            .as_str()
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default();
+        let is_remote = reference.starts_with("//")
+            || reference
+                .split_once(':')
+                .is_some_and(|(scheme, _)| !scheme.contains('/') && scheme.len() > 1);
+        if reference.is_empty() || is_remote {
+            continue;
+        }
+        let asset = dir.join(reference.trim_start_matches('/'));
+        if !assets.contains(&asset) {
+            assets.push(asset);
+        }
+    }
+    assets
+}
+
+fn html_to_markdown(html: &str) -> Result<String> {
+    let mut handlers: Vec<TagHandler> = vec![
+        Rc::new(RefCell::new(markdown::WebpageChromeRemover)),
+        Rc::new(RefCell::new(markdown::ParagraphHandler)),
+        Rc::new(RefCell::new(markdown::HeadingHandler)),
+        Rc::new(RefCell::new(markdown::ListHandler)),
+        Rc::new(RefCell::new(markdown::TableHandler::new())),
+        Rc::new(RefCell::new(markdown::StyledTextHandler)),
+        Rc::new(RefCell::new(markdown::CodeHandler)),
+    ];
+    convert_html_to_markdown(html.as_bytes(), &mut handlers)
+}
+
+impl FocusableView for HtmlPreviewView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for HtmlPreviewView {}
+
+impl Item for HtmlPreviewView {
+    type Event = ();
+
+    fn tab_icon(&self, _cx: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::FileCode))
+    }
+
+    fn tab_content_text(&self, _cx: &WindowContext) -> Option<SharedString> {
+        let file_name = self.path.file_name()?.to_string_lossy();
+        Some(format!("Preview {file_name}").into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("html preview")
+    }
+
+    fn to_item_events(_event: &Self::Event, _f: impl FnMut(workspace::item::ItemEvent)) {}
+}
+
+impl Render for HtmlPreviewView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let width = self.device.width();
+        v_flex()
+            .id("HtmlPreview")
+            .key_context("HtmlPreview")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .p_4()
+            .child(self.render_toolbar(cx))
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Label::new(error).color(Color::Error))
+            })
+            .child(
+                h_flex().flex_1().w_full().justify_center().child(
+                    div()
+                        .h_full()
+                        .map(|this| match width {
+                            Some(width) => this
+                                .w(width)
+                                .max_w_full()
+                                .px_2()
+                                .border_x_1()
+                                .border_color(cx.theme().colors().border_variant),
+                            None => this.w_full(),
+                        })
+                        .child(list(self.list_state.clone()).size_full()),
+                ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_assets() {
+        let html = r##"
+            <link rel="stylesheet" href="styles/main.css?v=2">
+            <link rel="icon" href='/favicon.ico'>
+            <script src="https://cdn.example.com/lib.js"></script>
+            <script src="//cdn.example.com/other.js"></script>
+            <img SRC="images/logo.png#large" alt="Logo">
+            <img src="data:image/png;base64,AAAA">
+            <a href="#top">Top</a>
+            <a href="mailto:someone@example.com">Mail</a>
+            <a href="about.html">About</a>
+            <img src="images/logo.png">
+        "##;
+        assert_eq!(
+            referenced_assets(html, Some(Path::new("/site"))),
+            [
+                PathBuf::from("/site/styles/main.css"),
+                PathBuf::from("/site/favicon.ico"),
+                PathBuf::from("/site/images/logo.png"),
+                PathBuf::from("/site/about.html"),
+            ]
+        );
+        assert!(referenced_assets(html, None).is_empty());
+    }
+}
@@ -0,0 +1,579 @@
+//! Translation of the strings shown in Zed's UI.
+//!
+//! UI strings are looked up by key in catalogs, one per language. Zed ships an English catalog in
+//! `assets/translations`, and extensions can provide catalogs for other languages. A message
+//! can have plural forms, and placeholders like `{name}` that are filled in when it's looked up.
+
+mod localization_settings;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use gpui::{AppContext, Global, LayoutDirection, ReadGlobal, SharedString, UpdateGlobal};
+use parking_lot::RwLock;
+use rust_embed::RustEmbed;
+use serde_derive::Deserialize;
+use settings::{Settings, SettingsStore};
+use std::{
+    fmt::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+use util::{asset_str, ResultExt};
+
+pub use localization_settings::*;
+
+/// The language UI strings fall back to when the current language has no translation for them.
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+#[derive(RustEmbed)]
+#[folder = "../../assets"]
+#[include = "translations/*.json"]
+#[exclude = "*.DS_Store"]
+struct TranslationAssets;
+
+pub fn init(cx: &mut AppContext) {
+    LocalizationSettings::register(cx);
+    TranslationRegistry::init_global(cx);
+
+    update_layout_direction(cx);
+    cx.observe_global::<SettingsStore>(update_layout_direction)
+        .detach();
+}
+
+/// Lays the UI out right to left when it's displayed in a right-to-left language, like Arabic
+/// or Hebrew, and left to right otherwise. Call this when the available catalogs change.
+pub fn update_layout_direction(cx: &mut AppContext) {
+    let direction = layout_direction(cx);
+    cx.set_default_layout_direction(direction);
+}
+
+/// Returns the direction the UI is laid out in for the language it's displayed in.
+///
+/// The UI is only mirrored when a right-to-left language has a catalog, so that it's never
+/// mirrored while its strings are shown in English.
+pub fn layout_direction(cx: &AppContext) -> LayoutDirection {
+    let Some(registry) = TranslationRegistry::try_global(cx) else {
+        return LayoutDirection::LeftToRight;
+    };
+    let ui_language = ui_language(cx);
+    let languages = registry.languages();
+    let translated_language = fallback_languages(&ui_language)
+        .find(|language| languages.iter().any(|candidate| candidate == language));
+    if translated_language.is_some_and(is_rtl_language) {
+        LayoutDirection::RightToLeft
+    } else {
+        LayoutDirection::LeftToRight
+    }
+}
+
+/// Returns whether the given language, such as `ar` or `he-il`, is written from right to left.
+pub fn is_rtl_language(language: &str) -> bool {
+    let base_language = language.split('-').next().unwrap_or_default();
+    matches!(
+        base_language,
+        "ar" | "ckb" | "dv" | "fa" | "he" | "iw" | "ps" | "sd" | "ug" | "ur" | "yi"
+    )
+}
+
+/// Returns the translation of the UI string with the given key.
+///
+/// Falls back to the English string, and then to the key itself, when there's no translation.
+pub fn t(key: &str, cx: &AppContext) -> SharedString {
+    translate(key, None, &[], cx)
+}
+
+/// Returns the translation of the UI string with the given key, with its placeholders replaced
+/// by the given arguments.
+pub fn t_args(key: &str, args: &[(&str, &dyn fmt::Display)], cx: &AppContext) -> SharedString {
+    translate(key, None, args, cx)
+}
+
+/// Returns the plural form of the UI string with the given key that is used for `count` in the
+/// current language. The count is available to the message as the `{count}` placeholder.
+pub fn t_plural(
+    key: &str,
+    count: usize,
+    args: &[(&str, &dyn fmt::Display)],
+    cx: &AppContext,
+) -> SharedString {
+    translate(key, Some(count), args, cx)
+}
+
+fn translate(
+    key: &str,
+    count: Option<usize>,
+    args: &[(&str, &dyn fmt::Display)],
+    cx: &AppContext,
+) -> SharedString {
+    let Some(registry) = TranslationRegistry::try_global(cx) else {
+        return SharedString::from(key.to_string());
+    };
+    let ui_language = ui_language(cx);
+    let Some((language, message)) = fallback_languages(&ui_language)
+        .find_map(|language| Some((language, registry.message(language, key)?)))
+    else {
+        return SharedString::from(key.to_string());
+    };
+
+    let template = match (&message, count) {
+        (Message::Plural(forms), Some(count)) => forms.get(plural_category(language, count)),
+        (Message::Plural(forms), None) => forms.other.clone(),
+        (Message::Text(text), _) => text.clone(),
+    };
+    match count {
+        Some(count) => {
+            let mut args = args.to_vec();
+            args.push(("count", &count));
+            format_message(&template, &args).into()
+        }
+        None if args.is_empty() => template,
+        None => format_message(&template, args).into(),
+    }
+}
+
+/// Returns the language the UI is displayed in: the `ui_language` setting or, when that isn't
+/// set, the locale of the system.
+pub fn ui_language(cx: &AppContext) -> SharedString {
+    static SYSTEM_LANGUAGE: OnceLock<SharedString> = OnceLock::new();
+
+    match &LocalizationSettings::get_global(cx).ui_language {
+        Some(language) => normalize_language(language).into(),
+        None => SYSTEM_LANGUAGE
+            .get_or_init(|| {
+                let locale = sys_locale::get_locale().unwrap_or_else(|| FALLBACK_LANGUAGE.into());
+                normalize_language(&locale).into()
+            })
+            .clone(),
+    }
+}
+
+/// Converts a language tag or POSIX locale, such as `pt_BR.UTF-8`, to the lowercase form
+/// catalogs are keyed by, such as `pt-br`.
+fn normalize_language(language: &str) -> String {
+    language
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .replace('_', "-")
+        .to_lowercase()
+}
+
+/// The languages whose catalogs are searched for a string, from the most to the least specific:
+/// `pt-br`, then `pt`, then English.
+fn fallback_languages(language: &str) -> impl Iterator<Item = &str> {
+    let base_language = language.split_once('-').map(|(base, _)| base);
+    [Some(language), base_language, Some(FALLBACK_LANGUAGE)]
+        .into_iter()
+        .flatten()
+        .filter(|language| !language.is_empty())
+}
+
+/// A set of translated UI strings, keyed by the string's identifier.
+///
+/// Catalogs are JSON objects whose values are either a message, or an object with the message's
+/// plural forms:
+///
+/// ```json
+/// {
+///   "picker.no_matches": "No matches",
+///   "project_panel.files_not_shown": {
+///     "one": ".. {count} file not shown",
+///     "other": ".. {count} files not shown"
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Catalog {
+    messages: HashMap<SharedString, Message>,
+}
+
+impl Catalog {
+    pub fn from_json(contents: &str) -> Result<Self> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.messages.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Message {
+    Text(SharedString),
+    Plural(PluralForms),
+}
+
+/// The forms of a message that depends on a count. Languages only use some of the forms, and
+/// `other` is used for any form that a message doesn't provide.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct PluralForms {
+    #[serde(default)]
+    pub zero: Option<SharedString>,
+    #[serde(default)]
+    pub one: Option<SharedString>,
+    #[serde(default)]
+    pub two: Option<SharedString>,
+    #[serde(default)]
+    pub few: Option<SharedString>,
+    #[serde(default)]
+    pub many: Option<SharedString>,
+    pub other: SharedString,
+}
+
+impl PluralForms {
+    pub fn get(&self, category: PluralCategory) -> SharedString {
+        let form = match category {
+            PluralCategory::Zero => &self.zero,
+            PluralCategory::One => &self.one,
+            PluralCategory::Two => &self.two,
+            PluralCategory::Few => &self.few,
+            PluralCategory::Many => &self.many,
+            PluralCategory::Other => return self.other.clone(),
+        };
+        form.clone().unwrap_or_else(|| self.other.clone())
+    }
+}
+
+/// The [CLDR plural categories](https://cldr.unicode.org/index/cldr-spec/plural-rules).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// Returns the plural category that a language uses for the given count.
+///
+/// This implements the cardinal rules for whole numbers of the languages that have them. Other
+/// languages use the English rule.
+pub fn plural_category(language: &str, count: usize) -> PluralCategory {
+    let base_language = language.split('-').next().unwrap_or(language);
+    let (n10, n100) = (count % 10, count % 100);
+    match base_language {
+        "ja" | "ko" | "zh" | "vi" | "th" | "id" | "ms" => PluralCategory::Other,
+        "fr" | "pt" | "hi" if count <= 1 => PluralCategory::One,
+        "fr" | "pt" | "hi" => PluralCategory::Other,
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+            if n10 == 1 && n100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "pl" => {
+            if count == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "cs" | "sk" => match count {
+            1 => PluralCategory::One,
+            2..=4 => PluralCategory::Few,
+            _ => PluralCategory::Other,
+        },
+        "ar" => match (count, n100) {
+            (0, _) => PluralCategory::Zero,
+            (1, _) => PluralCategory::One,
+            (2, _) => PluralCategory::Two,
+            (_, 3..=10) => PluralCategory::Few,
+            (_, 11..=99) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        _ if count == 1 => PluralCategory::One,
+        _ => PluralCategory::Other,
+    }
+}
+
+/// Replaces the `{name}` placeholders in a message with the argument of that name. Braces are
+/// escaped by doubling them, and placeholders without an argument are left as they are.
+pub fn format_message(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(ix) = rest.find(['{', '}']) {
+        result.push_str(&rest[..ix]);
+        rest = &rest[ix..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            result.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let placeholder = rest
+            .strip_prefix('{')
+            .and_then(|rest| rest.split_once('}'))
+            .and_then(|(name, _)| Some((name, args.iter().find(|(arg, _)| *arg == name)?.1)));
+        match placeholder {
+            Some((name, value)) => {
+                write!(result, "{value}").ok();
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                result.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+struct GlobalTranslationRegistry(Arc<TranslationRegistry>);
+
+impl Global for GlobalTranslationRegistry {}
+
+struct Bundle {
+    /// The file the bundle was loaded from, or `None` for the catalogs built into Zed.
+    path: Option<PathBuf>,
+    catalog: Catalog,
+}
+
+/// The catalogs of UI strings available for each language.
+#[derive(Default)]
+pub struct TranslationRegistry {
+    bundles: RwLock<HashMap<SharedString, Vec<Bundle>>>,
+}
+
+impl TranslationRegistry {
+    pub fn global(cx: &AppContext) -> Arc<Self> {
+        GlobalTranslationRegistry::global(cx).0.clone()
+    }
+
+    pub fn try_global(cx: &AppContext) -> Option<Arc<Self>> {
+        cx.try_global::<GlobalTranslationRegistry>()
+            .map(|registry| registry.0.clone())
+    }
+
+    pub fn init_global(cx: &mut AppContext) {
+        let registry = Self::new();
+        registry.load_built_in_catalogs();
+        GlobalTranslationRegistry::set_global(cx, GlobalTranslationRegistry(Arc::new(registry)))
+    }
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn load_built_in_catalogs(&self) {
+        for path in TranslationAssets::iter() {
+            let Some(language) = Path::new(path.as_ref()).file_stem() else {
+                continue;
+            };
+            let language = normalize_language(&language.to_string_lossy());
+            if let Some(catalog) = Catalog::from_json(&asset_str::<TranslationAssets>(&path))
+                .with_context(|| format!("invalid translations in {path}"))
+                .log_err()
+            {
+                self.insert_bundle(language, None, catalog);
+            }
+        }
+    }
+
+    /// Adds the catalog in the given file to the strings available for the given language,
+    /// replacing the catalog that was previously loaded from the same file.
+    ///
+    /// Strings in catalogs that were registered later take precedence over earlier ones.
+    pub fn register_catalog(&self, language: &str, path: &Path, contents: &str) -> Result<()> {
+        let catalog = Catalog::from_json(contents)
+            .with_context(|| format!("invalid translations in {path:?}"))?;
+        self.remove_catalogs(&[path.to_path_buf()]);
+        self.insert_bundle(
+            normalize_language(language),
+            Some(path.to_path_buf()),
+            catalog,
+        );
+        Ok(())
+    }
+
+    /// Removes the catalogs that were loaded from the given files.
+    pub fn remove_catalogs(&self, paths: &[PathBuf]) {
+        let mut bundles = self.bundles.write();
+        for language_bundles in bundles.values_mut() {
+            language_bundles.retain(|bundle| {
+                bundle
+                    .path
+                    .as_ref()
+                    .map_or(true, |path| !paths.contains(path))
+            });
+        }
+        bundles.retain(|_, language_bundles| !language_bundles.is_empty());
+    }
+
+    fn insert_bundle(&self, language: String, path: Option<PathBuf>, catalog: Catalog) {
+        self.bundles
+            .write()
+            .entry(language.into())
+            .or_default()
+            .push(Bundle { path, catalog });
+    }
+
+    /// Returns the languages that have translations, sorted by their tag.
+    pub fn languages(&self) -> Vec<SharedString> {
+        let mut languages = self.bundles.read().keys().cloned().collect::<Vec<_>>();
+        languages.sort();
+        languages
+    }
+
+    /// Returns the message with the given key in the given language, if it's translated.
+    pub fn message(&self, language: &str, key: &str) -> Option<Message> {
+        self.bundles
+            .read()
+            .get(language)?
+            .iter()
+            .rev()
+            .find_map(|bundle| bundle.catalog.get(key).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::SettingsStore;
+
+    #[test]
+    fn test_plural_category() {
+        use PluralCategory::*;
+
+        let categories = |language| {
+            [0, 1, 2, 5, 11, 21, 22, 25, 101, 111]
+                .map(|count| plural_category(language, count))
+                .to_vec()
+        };
+        assert_eq!(
+            categories("en"),
+            [Other, One, Other, Other, Other, Other, Other, Other, Other, Other]
+        );
+        assert_eq!(
+            categories("fr-ca"),
+            [One, One, Other, Other, Other, Other, Other, Other, Other, Other]
+        );
+        assert_eq!(
+            categories("ru"),
+            [Many, One, Few, Many, Many, One, Few, Many, One, Many]
+        );
+        assert_eq!(
+            categories("ar"),
+            [Zero, One, Two, Few, Many, Many, Many, Many, Other, Many]
+        );
+        assert_eq!(
+            categories("ja"),
+            [Other, Other, Other, Other, Other, Other, Other, Other, Other, Other]
+        );
+    }
+
+    #[test]
+    fn test_format_message() {
+        assert_eq!(
+            format_message("Delete {path}?", &[("path", &"main.rs")]),
+            "Delete main.rs?"
+        );
+        assert_eq!(
+            format_message("{count} of {total}", &[("count", &3), ("total", &10)]),
+            "3 of 10"
+        );
+        assert_eq!(
+            format_message("{{literal}} {missing} {", &[("count", &3)]),
+            "{literal} {missing} {"
+        );
+    }
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize_language("pt_BR.UTF-8"), "pt-br");
+        assert_eq!(normalize_language("de_DE@euro"), "de-de");
+        assert_eq!(normalize_language("en-US"), "en-us");
+        assert_eq!(
+            fallback_languages("pt-br").collect::<Vec<_>>(),
+            ["pt-br", "pt", "en"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_translate(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        init(cx);
+
+        let registry = TranslationRegistry::global(cx);
+        registry
+            .register_catalog(
+                "de",
+                Path::new("/extensions/german/translations/de.json"),
+                r#"{
+                    "picker.no_matches": "Keine Treffer",
+                    "project_panel.files_not_shown": {
+                        "one": ".. {count} Datei nicht angezeigt",
+                        "other": ".. {count} Dateien nicht angezeigt"
+                    }
+                }"#,
+            )
+            .unwrap();
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<LocalizationSettings>(cx, |settings| {
+                settings.ui_language = Some("de_AT".into());
+            });
+        });
+
+        assert_eq!(t("picker.no_matches", cx), "Keine Treffer");
+        assert_eq!(
+            t_plural("project_panel.files_not_shown", 1, &[], cx),
+            ".. 1 Datei nicht angezeigt"
+        );
+        assert_eq!(
+            t_plural("project_panel.files_not_shown", 4, &[], cx),
+            ".. 4 Dateien nicht angezeigt"
+        );
+        // Strings without a translation fall back to English, and then to their key.
+        assert_eq!(t("project_panel.new_file", cx), "New File");
+        assert_eq!(t("unknown.key", cx), "unknown.key");
+
+        registry.remove_catalogs(&[PathBuf::from("/extensions/german/translations/de.json")]);
+        assert_eq!(t("picker.no_matches", cx), "No matches");
+        assert_eq!(registry.languages(), ["en"]);
+    }
+
+    #[gpui::test]
+    fn test_layout_direction(cx: &mut AppContext) {
+        let store = SettingsStore::test(cx);
+        cx.set_global(store);
+        init(cx);
+
+        assert!(is_rtl_language("he-il"));
+        assert!(!is_rtl_language("de"));
+
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<LocalizationSettings>(cx, |settings| {
+                settings.ui_language = Some("ar_EG".into());
+            });
+        });
+        // An untranslated UI isn't mirrored, even if its language is written right to left.
+        assert_eq!(layout_direction(cx), LayoutDirection::LeftToRight);
+
+        TranslationRegistry::global(cx)
+            .register_catalog(
+                "ar",
+                Path::new("/extensions/arabic/translations/ar.json"),
+                r#"{ "picker.no_matches": "لا توجد نتائج" }"#,
+            )
+            .unwrap();
+        assert_eq!(layout_direction(cx), LayoutDirection::RightToLeft);
+    }
+}
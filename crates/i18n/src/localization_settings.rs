@@ -0,0 +1,29 @@
+use anyhow::Result;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug)]
+pub struct LocalizationSettings {
+    pub ui_language: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct LocalizationSettingsContent {
+    /// The language to display Zed's UI in, as a language tag like "de" or "pt-BR". Strings
+    /// that aren't translated into the language are shown in English.
+    ///
+    /// Default: null (use the system's language)
+    pub ui_language: Option<String>,
+}
+
+impl Settings for LocalizationSettings {
+    const KEY: Option<&'static str> = None;
+
+    type FileContent = LocalizationSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,179 @@
+use command_palette::humanize_action_name;
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, KeyBinding, KeyContext,
+    Render, ScrollHandle,
+};
+use ui::{prelude::*, Modal, ModalHeader, Section, SectionHeader};
+use workspace::{ModalView, Workspace};
+
+/// Lists the keybindings that can be used where focus was when the cheat sheet opened, grouped
+/// by the key context they apply in.
+pub struct CheatSheet {
+    groups: Vec<(SharedString, Vec<(SharedString, KeyBinding)>)>,
+    opened_by_holding: bool,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+impl ModalView for CheatSheet {}
+
+impl EventEmitter<DismissEvent> for CheatSheet {}
+
+impl FocusableView for CheatSheet {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl CheatSheet {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        opened_by_holding: bool,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        // Collect the bindings before the cheat sheet takes focus, so that they're the ones of the
+        // element the user was working in.
+        let groups = group_bindings(cx.available_bindings());
+        workspace.toggle_modal(cx, |cx| Self {
+            groups,
+            opened_by_holding,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+        });
+    }
+
+    /// Whether the cheat sheet was opened by holding a modifier, and should close once it's released.
+    pub fn opened_by_holding(&self) -> bool {
+        self.opened_by_holding
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent)
+    }
+}
+
+/// Groups bindings by the primary identifier of their context, keeping the last binding of each
+/// action, since that's the one shown elsewhere in the UI.
+fn group_bindings(
+    bindings: Vec<(Option<KeyContext>, KeyBinding)>,
+) -> Vec<(SharedString, Vec<(SharedString, KeyBinding)>)> {
+    let mut groups = Vec::<(SharedString, Vec<(SharedString, KeyBinding)>)>::new();
+    for (context, binding) in bindings {
+        let group_name = context
+            .as_ref()
+            .and_then(|context| context.primary().cloned())
+            .unwrap_or_else(|| "Global".into());
+        let action_name: SharedString = humanize_action_name(binding.action().name()).into();
+
+        let ix = match groups.iter().position(|(name, _)| *name == group_name) {
+            Some(ix) => ix,
+            None => {
+                groups.push((group_name, Vec::new()));
+                groups.len() - 1
+            }
+        };
+        let entries = &mut groups[ix].1;
+        if let Some(entry) = entries.iter_mut().find(|(name, _)| *name == action_name) {
+            entry.1 = binding;
+        } else {
+            entries.push((action_name, binding));
+        }
+    }
+
+    for (_, entries) in &mut groups {
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+    groups
+}
+
+impl Render for CheatSheet {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let mut modal = Modal::new("keybinding-cheat-sheet", Some(self.scroll_handle.clone()))
+            .header(
+                ModalHeader::new()
+                    .headline("Keybindings")
+                    .show_dismiss_button(true),
+            );
+        for (group_name, entries) in &self.groups {
+            modal = modal.section(
+                Section::new()
+                    .header(SectionHeader::new(group_name.clone()))
+                    .children(entries.iter().map(|(action_name, binding)| {
+                        h_flex()
+                            .w_full()
+                            .justify_between()
+                            .gap_2()
+                            .child(Label::new(action_name.clone()).size(LabelSize::Small))
+                            .child(ui::KeyBinding::new(binding.clone()))
+                    })),
+            );
+        }
+
+        v_flex()
+            .key_context("CheatSheet")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .occlude()
+            .w(rems(34.))
+            .max_h(rems(40.))
+            .pb_2()
+            .when(self.groups.is_empty(), |this| {
+                this.child(
+                    div()
+                        .mx_auto()
+                        .my_4()
+                        .child(Label::new("No keybindings apply here.").color(Color::Muted)),
+                )
+            })
+            .child(modal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::actions;
+
+    actions!(test, [Save, Undo, Redo]);
+
+    #[test]
+    fn test_grouping_bindings() {
+        let editor = KeyContext::parse("Editor mode = full").ok();
+        let groups = group_bindings(vec![
+            (
+                editor.clone(),
+                KeyBinding::new("cmd-z", Undo, Some("Editor")),
+            ),
+            (
+                editor.clone(),
+                KeyBinding::new("ctrl-u", Undo, Some("Editor")),
+            ),
+            (editor, KeyBinding::new("cmd-shift-z", Redo, Some("Editor"))),
+            (None, KeyBinding::new("cmd-s", Save, None)),
+        ]);
+
+        let summary = groups
+            .iter()
+            .map(|(group_name, entries)| {
+                let entries = entries
+                    .iter()
+                    .map(|(action_name, binding)| {
+                        format!("{action_name}: {}", binding.keystrokes()[0].key)
+                    })
+                    .collect::<Vec<_>>();
+                (group_name.to_string(), entries)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "Editor".to_string(),
+                    vec!["test: redo: z".to_string(), "test: undo: u".to_string()]
+                ),
+                ("Global".to_string(), vec!["test: save: s".to_string()]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+mod cheat_sheet;
+mod keybinding_tutor_settings;
+
+use collections::HashMap;
+use command_palette::humanize_action_name;
+use gpui::{
+    actions, Action, AppContext, DismissEvent, Empty, IntoElement, KeystrokeEvent, Modifiers,
+    Render, Subscription, Task, ViewContext, VisualContext as _, WeakView,
+};
+use settings::{update_settings_file, Settings};
+use std::time::Duration;
+use workspace::{notifications::NotificationId, Toast, Workspace};
+
+pub use cheat_sheet::CheatSheet;
+pub use keybinding_tutor_settings::KeybindingTutorSettings;
+
+actions!(keybinding_tutor, [ToggleCheatSheet, ToggleTutorMode]);
+
+/// How long the platform modifier has to be held on its own before the cheat sheet shows up.
+const CHEAT_SHEET_HOLD_DELAY: Duration = Duration::from_millis(800);
+
+/// How many times an action has to be performed with the mouse before its keybinding is
+/// suggested.
+const CLICKS_BEFORE_SUGGESTION: usize = 3;
+
+pub fn init(cx: &mut AppContext) {
+    KeybindingTutorSettings::register(cx);
+    cx.observe_new_views(KeybindingTutor::register).detach();
+}
+
+/// Shows the cheat sheet while the platform modifier is held, and suggests keybindings for the
+/// actions performed with the mouse in tutor mode.
+pub struct KeybindingTutor {
+    workspace: WeakView<Workspace>,
+    clicks: HashMap<String, usize>,
+    pending_cheat_sheet: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl KeybindingTutor {
+    fn register(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &ToggleCheatSheet, cx| {
+            CheatSheet::toggle(workspace, false, cx);
+        });
+        workspace.register_action(|workspace, _: &ToggleTutorMode, cx| {
+            let tutor_mode = !KeybindingTutorSettings::get_global(cx).tutor_mode;
+            update_settings_file::<KeybindingTutorSettings>(
+                workspace.app_state().fs.clone(),
+                cx,
+                move |settings, _| settings.tutor_mode = Some(tutor_mode),
+            );
+        });
+
+        let workspace_handle = cx.view().clone();
+        let tutor = cx.new_view(|cx| Self::new(workspace_handle.downgrade(), cx));
+        // The workspace's subscription keeps the tutor alive for as long as the workspace.
+        cx.subscribe(&workspace_handle, move |workspace, _, event, cx| {
+            if let workspace::Event::ModifiersChanged(modifiers) = event {
+                tutor.update(cx, |tutor, cx| tutor.modifiers_changed(*modifiers, cx));
+                if !modifiers.modified() {
+                    dismiss_held_cheat_sheet(workspace, cx);
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            workspace,
+            clicks: HashMap::default(),
+            pending_cheat_sheet: None,
+            _subscriptions: vec![
+                cx.observe_clicked_actions(Self::action_clicked),
+                cx.observe_keystrokes(Self::keystroke_observed),
+            ],
+        }
+    }
+
+    fn modifiers_changed(&mut self, modifiers: Modifiers, cx: &mut ViewContext<Self>) {
+        self.pending_cheat_sheet = None;
+        if !KeybindingTutorSettings::get_global(cx).cheat_sheet_on_hold
+            || modifiers != Modifiers::secondary_key()
+        {
+            return;
+        }
+
+        self.pending_cheat_sheet = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(CHEAT_SHEET_HOLD_DELAY).await;
+            this.update(&mut cx, |this, cx| {
+                this.workspace
+                    .update(cx, |workspace, cx| {
+                        if workspace.active_modal::<CheatSheet>(cx).is_none() {
+                            CheatSheet::toggle(workspace, true, cx);
+                        }
+                    })
+                    .ok();
+            })
+            .ok();
+        }));
+    }
+
+    fn keystroke_observed(&mut self, _: &KeystrokeEvent, _: &mut ViewContext<Self>) {
+        // Holding the modifier is part of a keybinding, rather than a request for the cheat sheet.
+        self.pending_cheat_sheet = None;
+    }
+
+    fn action_clicked(&mut self, action: &dyn Action, cx: &mut ViewContext<Self>) {
+        if !KeybindingTutorSettings::get_global(cx).tutor_mode {
+            return;
+        }
+        let Some(binding) = cx.bindings_for_action(action).pop() else {
+            return;
+        };
+
+        let clicks = self.clicks.entry(action.name().to_string()).or_default();
+        *clicks += 1;
+        if *clicks != CLICKS_BEFORE_SUGGESTION {
+            return;
+        }
+
+        let keystrokes = binding
+            .keystrokes()
+            .iter()
+            .map(|keystroke| keystroke.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let message = format!(
+            "Tip: press {keystrokes} to run `{}` without the mouse.",
+            humanize_action_name(action.name())
+        );
+        let id = NotificationId::identified::<Self>(action.name().to_string());
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(id, message).on_click("Open Cheat Sheet", |cx| {
+                        cx.dispatch_action(ToggleCheatSheet.boxed_clone())
+                    }),
+                    cx,
+                )
+            })
+            .ok();
+    }
+}
+
+fn dismiss_held_cheat_sheet(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    if let Some(cheat_sheet) = workspace.active_modal::<CheatSheet>(cx) {
+        if cheat_sheet.read(cx).opened_by_holding() {
+            cheat_sheet.update(cx, |_, cx| cx.emit(DismissEvent));
+        }
+    }
+}
+
+impl Render for KeybindingTutor {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        Empty
+    }
+}
@@ -0,0 +1,36 @@
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct KeybindingTutorSettings {
+    pub tutor_mode: bool,
+    pub cheat_sheet_on_hold: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct KeybindingTutorSettingsContent {
+    /// Whether to suggest the keybinding of an action after it has been performed with the mouse
+    /// a few times.
+    ///
+    /// Default: false
+    pub tutor_mode: Option<bool>,
+    /// Whether holding the platform modifier (cmd on macOS, ctrl elsewhere) on its own shows the
+    /// keybinding cheat sheet until it's released.
+    ///
+    /// Default: false
+    pub cheat_sheet_on_hold: Option<bool>,
+}
+
+impl Settings for KeybindingTutorSettings {
+    const KEY: Option<&'static str> = Some("keybinding_tutor");
+
+    type FileContent = KeybindingTutorSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
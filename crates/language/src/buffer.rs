@@ -6,7 +6,7 @@ pub use crate::{
 };
 use crate::{
     diagnostic_set::{DiagnosticEntry, DiagnosticGroup},
-    language_settings::{language_settings, IndentGuideSettings, LanguageSettings},
+    language_settings::{language_settings, IndentGuideSettings, LanguageSettings, WordCharacter},
     markdown::parse_markdown,
     outline::OutlineItem,
     syntax_map::{
@@ -4229,6 +4229,7 @@ pub struct CharClassifier {
     scope: Option<LanguageScope>,
     for_completion: bool,
     ignore_punctuation: bool,
+    extra_word_characters: Vec<WordCharacter>,
 }
 
 impl CharClassifier {
@@ -4237,6 +4238,7 @@ impl CharClassifier {
             scope,
             for_completion: false,
             ignore_punctuation: false,
+            extra_word_characters: Vec::new(),
         }
     }
 
@@ -4254,6 +4256,14 @@ impl CharClassifier {
         }
     }
 
+    /// Treats the given characters as word characters, on top of the language's own.
+    pub fn extra_word_characters(self, extra_word_characters: Vec<WordCharacter>) -> Self {
+        Self {
+            extra_word_characters,
+            ..self
+        }
+    }
+
     pub fn is_whitespace(&self, c: char) -> bool {
         self.kind(c) == CharKind::Whitespace
     }
@@ -4269,7 +4279,13 @@ impl CharClassifier {
     pub fn kind(&self, c: char) -> CharKind {
         if c.is_whitespace() {
             return CharKind::Whitespace;
-        } else if c.is_alphanumeric() || c == '_' {
+        } else if c.is_alphanumeric()
+            || c == '_'
+            || self
+                .extra_word_characters
+                .iter()
+                .any(|characters| characters.contains(c))
+        {
             return CharKind::Word;
         }
 
@@ -119,6 +119,9 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// Characters to treat as part of words when selecting them, in addition to the
+    /// language's own word characters.
+    pub word_characters: Vec<WordCharacter>,
     /// Task configuration for this language.
     pub tasks: LanguageTaskConfig,
 }
@@ -341,6 +344,13 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// Characters to treat as part of words when selecting them, for example by double
+    /// clicking, in addition to the language's own word characters. Each entry is either
+    /// a single character, a range of characters like "α-ω", "ascii_punctuation" or
+    /// "non_ascii".
+    ///
+    /// Default: []
+    pub word_characters: Option<Vec<WordCharacter>>,
     /// Task configuration for this language.
     ///
     /// Default: {}
@@ -495,6 +505,71 @@ impl<'de> Deserialize<'de> for FormatOnSave {
     }
 }
 
+/// A character, or a class of characters, to treat as part of words.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum WordCharacter {
+    /// A single character, like `$`.
+    Char(char),
+    /// An inclusive range of characters, like `α-ω`.
+    Range(char, char),
+    /// Any ASCII punctuation character.
+    AsciiPunctuation,
+    /// Any character outside of ASCII, like emoji or CJK characters.
+    NonAscii,
+}
+
+impl WordCharacter {
+    /// Returns whether the given character belongs to this class.
+    pub fn contains(&self, c: char) -> bool {
+        match *self {
+            WordCharacter::Char(character) => c == character,
+            WordCharacter::Range(start, end) => (start..=end).contains(&c),
+            WordCharacter::AsciiPunctuation => c.is_ascii_punctuation(),
+            WordCharacter::NonAscii => !c.is_ascii(),
+        }
+    }
+}
+
+impl JsonSchema for WordCharacter {
+    fn schema_name() -> String {
+        "WordCharacter".into()
+    }
+
+    fn json_schema(generator: &mut schemars::r#gen::SchemaGenerator) -> Schema {
+        String::json_schema(generator)
+    }
+}
+
+impl TryFrom<String> for WordCharacter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        match value.as_str() {
+            "ascii_punctuation" => return Ok(WordCharacter::AsciiPunctuation),
+            "non_ascii" => return Ok(WordCharacter::NonAscii),
+            _ => {}
+        }
+        let chars = value.chars().collect::<Vec<_>>();
+        match chars.as_slice() {
+            [c] => Ok(WordCharacter::Char(*c)),
+            [start, '-', end] if start <= end => Ok(WordCharacter::Range(*start, *end)),
+            _ => Err(anyhow::anyhow!("invalid word character {value:?}")),
+        }
+    }
+}
+
+impl From<WordCharacter> for String {
+    fn from(value: WordCharacter) -> Self {
+        match value {
+            WordCharacter::Char(c) => c.to_string(),
+            WordCharacter::Range(start, end) => format!("{start}-{end}"),
+            WordCharacter::AsciiPunctuation => "ascii_punctuation".to_string(),
+            WordCharacter::NonAscii => "non_ascii".to_string(),
+        }
+    }
+}
+
 /// Controls how whitespace should be displayedin the editor.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -1055,6 +1130,7 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.code_actions_on_format.clone(),
     );
     merge(&mut settings.linked_edits, src.linked_edits);
+    merge(&mut settings.word_characters, src.word_characters.clone());
     merge(&mut settings.tasks, src.tasks.clone());
 
     merge(
@@ -1159,6 +1235,27 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_word_characters_deserialization() {
+        let raw = r#"{"word_characters": ["-", "α-ω", "ascii_punctuation", "non_ascii"]}"#;
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.word_characters,
+            Some(vec![
+                WordCharacter::Char('-'),
+                WordCharacter::Range('α', 'ω'),
+                WordCharacter::AsciiPunctuation,
+                WordCharacter::NonAscii,
+            ])
+        );
+        assert!(WordCharacter::Range('α', 'ω').contains('β'));
+        assert!(!WordCharacter::AsciiPunctuation.contains('é'));
+
+        let raw = r#"{"word_characters": ["ab"]}"#;
+        let result: Result<LanguageSettingsContent, _> = serde_json::from_str(raw);
+        assert!(result.is_err());
+    }
+
     #[test]
     pub fn test_resolve_language_servers() {
         fn language_server_names(names: &[&str]) -> Vec<LanguageServerName> {
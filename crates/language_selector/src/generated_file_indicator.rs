@@ -0,0 +1,83 @@
+use editor::Editor;
+use gpui::{
+    div, IntoElement, ParentElement, Render, StatefulInteractiveElement, Subscription, View,
+    ViewContext,
+};
+use project::File;
+use ui::{Color, FluentBuilder, Label, LabelCommon, LabelSize, Tooltip};
+use workspace::{item::ItemHandle, StatusItemView};
+
+/// Shows a badge in the status bar when the active buffer matches the `generated_files` setting.
+pub struct GeneratedFileIndicator {
+    is_generated: bool,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl GeneratedFileIndicator {
+    pub fn new() -> Self {
+        Self {
+            is_generated: false,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_generated(&mut self, editor: View<Editor>, cx: &mut ViewContext<Self>) {
+        self.is_generated = editor
+            .read(cx)
+            .active_excerpt(cx)
+            .and_then(|(_, buffer, _)| {
+                File::from_dyn(buffer.read(cx).file()).map(|file| file.is_generated(cx))
+            })
+            .unwrap_or(false);
+
+        cx.notify();
+    }
+}
+
+impl Default for GeneratedFileIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for GeneratedFileIndicator {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div().when(self.is_generated, |el| {
+            el.child(
+                div()
+                    .id("generated-file")
+                    .child(
+                        Label::new("Generated")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .tooltip(|cx| {
+                        Tooltip::with_meta(
+                            "Generated File",
+                            None,
+                            "Not formatted on save, and only errors are reported",
+                            cx,
+                        )
+                    }),
+            )
+        })
+    }
+}
+
+impl StatusItemView for GeneratedFileIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.act_as::<Editor>(cx)) {
+            self._observe_active_editor = Some(cx.observe(&editor, Self::update_generated));
+            self.update_generated(editor, cx);
+        } else {
+            self.is_generated = false;
+            self._observe_active_editor = None;
+        }
+
+        cx.notify();
+    }
+}
@@ -1,9 +1,11 @@
 mod active_buffer_language;
+mod generated_file_indicator;
 
 pub use active_buffer_language::ActiveBufferLanguage;
 use anyhow::anyhow;
 use editor::Editor;
 use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+pub use generated_file_indicator::GeneratedFileIndicator;
 use gpui::{
     actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Model,
     ParentElement, Render, Styled, View, ViewContext, VisualContext, WeakView,
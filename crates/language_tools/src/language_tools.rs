@@ -1,5 +1,6 @@
 mod lsp_log;
 mod syntax_tree_view;
+mod task_browser;
 
 #[cfg(test)]
 mod lsp_log_tests;
@@ -8,8 +9,10 @@ use gpui::AppContext;
 
 pub use lsp_log::{LogStore, LspLogToolbarItemView, LspLogView};
 pub use syntax_tree_view::{SyntaxTreeToolbarItemView, SyntaxTreeView};
+pub use task_browser::TaskBrowser;
 
 pub fn init(cx: &mut AppContext) {
     lsp_log::init(cx);
     syntax_tree_view::init(cx);
+    task_browser::init(cx);
 }
@@ -0,0 +1,186 @@
+use gpui::{
+    actions, live_tasks, set_task_tracking_enabled, AppContext, EventEmitter, FocusHandle,
+    FocusableView, Task, TaskExecutor, TrackedTask, View,
+};
+use std::time::Duration;
+use ui::prelude::*;
+use workspace::{item::Item, Workspace};
+
+actions!(debug, [OpenTaskBrowser]);
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &OpenTaskBrowser, cx| {
+            let task_browser = cx.new_view(TaskBrowser::new);
+            workspace.add_item_to_active_pane(Box::new(task_browser), None, true, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the tasks spawned on GPUI's executors that are still running, to help find runaway
+/// background work and tasks that outlive the views and models that spawned them.
+///
+/// Task tracking is enabled when the browser is first opened, so tasks spawned before then are
+/// not listed.
+pub struct TaskBrowser {
+    tasks: Vec<TrackedTask>,
+    focus_handle: FocusHandle,
+    _refresh_task: Task<()>,
+}
+
+impl TaskBrowser {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        set_task_tracking_enabled(true);
+        let refresh_task = cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+                if this.update(&mut cx, |this, cx| this.refresh(cx)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            tasks: live_tasks(),
+            focus_handle: cx.focus_handle(),
+            _refresh_task: refresh_task,
+        }
+    }
+
+    fn refresh(&mut self, cx: &mut ViewContext<Self>) {
+        self.tasks = live_tasks();
+        cx.notify();
+    }
+
+    fn render_executor_tasks(&self, executor: TaskExecutor) -> impl IntoElement {
+        let tasks = self
+            .tasks
+            .iter()
+            .filter(|task| task.executor == executor)
+            .collect::<Vec<_>>();
+        let title = match executor {
+            TaskExecutor::Foreground => "Foreground",
+            TaskExecutor::Background => "Background",
+        };
+
+        v_flex()
+            .gap_1()
+            .child(Label::new(format!("{title} ({})", tasks.len())).size(LabelSize::Large))
+            .children(tasks.into_iter().map(render_task))
+    }
+}
+
+fn render_task(task: &TrackedTask) -> impl IntoElement {
+    let location = format!("{}:{}", task.location.file(), task.location.line());
+    let outlived_owner = task.outlived_owner();
+
+    h_flex()
+        .gap_4()
+        .child(
+            div().w_20().child(
+                Label::new(format_age(task.age()))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            ),
+        )
+        .child(
+            Label::new(
+                task.name
+                    .clone()
+                    .unwrap_or_else(|| SharedString::from(location.clone())),
+            )
+            .size(LabelSize::Small),
+        )
+        .when(task.name.is_some(), |this| {
+            this.child(
+                Label::new(location)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+        })
+        .when_some(task.owner_type_name(), |this, owner| {
+            let (label, color) = if outlived_owner {
+                (format!("outlived its {owner}"), Color::Warning)
+            } else {
+                (format!("spawned by {owner}"), Color::Muted)
+            };
+            this.child(Label::new(label).size(LabelSize::Small).color(color))
+        })
+}
+
+fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 60 * 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {}m", seconds / (60 * 60), seconds / 60 % 60)
+    }
+}
+
+impl Render for TaskBrowser {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let outlived_count = self
+            .tasks
+            .iter()
+            .filter(|task| task.outlived_owner())
+            .count();
+
+        v_flex()
+            .id("task-browser")
+            .key_context("TaskBrowser")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .when(outlived_count > 0, |this| {
+                this.child(
+                    Label::new(format!(
+                        "{outlived_count} tasks are still running after the entity that spawned them was released"
+                    ))
+                    .color(Color::Warning),
+                )
+            })
+            .child(self.render_executor_tasks(TaskExecutor::Foreground))
+            .child(self.render_executor_tasks(TaskExecutor::Background))
+    }
+}
+
+impl EventEmitter<()> for TaskBrowser {}
+
+impl FocusableView for TaskBrowser {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for TaskBrowser {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(workspace::item::ItemEvent)) {}
+
+    fn tab_content_text(&self, _cx: &WindowContext) -> Option<SharedString> {
+        Some("Tasks".into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn clone_on_split(
+        &self,
+        _: Option<workspace::WorkspaceId>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Self>>
+    where
+        Self: Sized,
+    {
+        Some(cx.new_view(Self::new))
+    }
+}
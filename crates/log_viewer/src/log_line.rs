@@ -0,0 +1,234 @@
+use regex::{Regex, RegexBuilder};
+use std::{ops::Range, sync::LazyLock};
+
+static TIMESTAMP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // 2024-09-30T12:34:56.789+02:00, 2024-09-30 12:34:56,789
+        r"\b\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2}(?:[.,]\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?)?",
+        // Sep 30 12:34:56
+        r"|\b[A-Z][a-z]{2} +\d{1,2} \d{2}:\d{2}:\d{2}\b",
+        // 12:34:56.789
+        r"|\b\d{2}:\d{2}:\d{2}(?:[.,]\d+)?\b",
+    ))
+    .unwrap()
+});
+
+/// The severity of a log line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn from_word(word: &str) -> Option<Self> {
+        match word.to_ascii_uppercase().as_str() {
+            "ERROR" | "ERR" | "FATAL" | "CRITICAL" | "CRIT" | "PANIC" | "SEVERE" => {
+                Some(Self::Error)
+            }
+            "WARNING" | "WARN" => Some(Self::Warning),
+            "INFO" | "NOTICE" => Some(Self::Info),
+            "DEBUG" | "DBG" => Some(Self::Debug),
+            "TRACE" | "VERBOSE" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of a log line that are highlighted, as byte ranges within the line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogLine {
+    pub level: Option<(LogLevel, Range<usize>)>,
+    pub timestamp: Option<Range<usize>>,
+}
+
+/// Finds the first timestamp and log level in the line.
+///
+/// To avoid matching ordinary words in messages, a level is only recognized when it's
+/// written in uppercase, enclosed in brackets, or follows `level=`.
+pub fn parse_log_line(line: &str) -> LogLine {
+    let timestamp = TIMESTAMP_REGEX.find(line).map(|found| found.range());
+    let level = words(line).find_map(|range| {
+        let word = &line[range.clone()];
+        let preceding = &line[..range.start];
+        let is_marked = word.chars().all(|c| c.is_ascii_uppercase())
+            || preceding.ends_with(['[', '<', '('])
+            || preceding.to_ascii_lowercase().ends_with("level=");
+        is_marked
+            .then(|| LogLevel::from_word(word))
+            .flatten()
+            .map(|level| (level, range))
+    });
+    LogLine { level, timestamp }
+}
+
+fn words(line: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = None;
+    line.char_indices()
+        .chain([(line.len(), ' ')])
+        .filter_map(move |(ix, c)| {
+            if c.is_ascii_alphabetic() {
+                start.get_or_insert(ix);
+                None
+            } else {
+                start.take().map(|start| start..ix)
+            }
+        })
+}
+
+/// Decides which lines of a log are shown.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+}
+
+impl LogFilter {
+    /// Creates a filter showing the lines matching `include` and hiding the ones matching
+    /// `exclude`. Both are case-insensitive regular expressions, matched literally when
+    /// they aren't valid, and empty patterns are ignored.
+    pub fn new(include: &str, exclude: &str) -> Self {
+        Self {
+            include: Self::build_regex(include),
+            exclude: Self::build_regex(exclude),
+        }
+    }
+
+    fn build_regex(pattern: &str) -> Option<Regex> {
+        if pattern.is_empty() {
+            return None;
+        }
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .or_else(|_| {
+                RegexBuilder::new(&regex::escape(pattern))
+                    .case_insensitive(true)
+                    .build()
+            })
+            .ok()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    pub fn is_visible(&self, line: &str) -> bool {
+        self.include
+            .as_ref()
+            .map_or(true, |include| include.is_match(line))
+            && self
+                .exclude
+                .as_ref()
+                .map_or(true, |exclude| !exclude.is_match(line))
+    }
+
+    /// Returns the byte ranges to fold in order to hide the filtered out lines, along with
+    /// the number of lines each range hides.
+    ///
+    /// A range spans from the end of the last shown line to the end of the last hidden one,
+    /// so that the hidden lines are collapsed into the line preceding them.
+    pub fn hidden_ranges(&self, text: &str) -> Vec<(Range<usize>, u32)> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in text.split('\n') {
+            lines.push((offset..offset + line.len(), self.is_visible(line)));
+            offset += line.len() + 1;
+        }
+
+        let mut ranges = Vec::new();
+        let mut ix = 0;
+        while ix < lines.len() {
+            if lines[ix].1 {
+                ix += 1;
+                continue;
+            }
+            let first_hidden = ix;
+            while ix < lines.len() && !lines[ix].1 {
+                ix += 1;
+            }
+            let last_hidden = &lines[ix - 1].0;
+            let range = if first_hidden > 0 {
+                lines[first_hidden - 1].0.end..last_hidden.end
+            } else if let Some((next_line, _)) = lines.get(ix) {
+                0..next_line.start
+            } else {
+                0..last_hidden.end
+            };
+            ranges.push((range, (ix - first_hidden) as u32));
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let line = "2024-09-30T12:34:56.789Z ERROR server: connection refused";
+        assert_eq!(
+            parse_log_line(line),
+            LogLine {
+                level: Some((LogLevel::Error, 25..30)),
+                timestamp: Some(0..24),
+            }
+        );
+
+        let line = "Sep 30 12:34:56 host app[42]: [warn] disk almost full";
+        assert_eq!(
+            parse_log_line(line),
+            LogLine {
+                level: Some((LogLevel::Warning, 31..35)),
+                timestamp: Some(0..15),
+            }
+        );
+
+        let line = "ts=12:34:56 level=debug msg=\"an error occurred\"";
+        assert_eq!(
+            parse_log_line(line),
+            LogLine {
+                level: Some((LogLevel::Debug, 18..23)),
+                timestamp: Some(3..11),
+            }
+        );
+
+        // Lowercase words in messages aren't levels.
+        assert_eq!(
+            parse_log_line("retrying after an error"),
+            LogLine::default()
+        );
+    }
+
+    #[test]
+    fn test_hidden_ranges() {
+        let text = "a INFO\nb DEBUG\nc DEBUG\nd ERROR\ne DEBUG";
+        assert_eq!(LogFilter::new("", "").hidden_ranges(text), vec![]);
+
+        // Hidden lines are folded into the preceding line.
+        assert_eq!(
+            LogFilter::new("", "debug").hidden_ranges(text),
+            vec![(6..22, 2), (30..38, 1)]
+        );
+
+        // Leading hidden lines are folded into the following line.
+        assert_eq!(
+            LogFilter::new("error", "").hidden_ranges(text),
+            vec![(0..23, 3), (30..38, 1)]
+        );
+
+        // Invalid regular expressions are matched literally.
+        assert_eq!(
+            LogFilter::new("d (", "").hidden_ranges("d (\nx"),
+            vec![(3..5, 1)]
+        );
+    }
+}
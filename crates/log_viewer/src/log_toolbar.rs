@@ -0,0 +1,152 @@
+use crate::{filter, is_following_tail, is_log, set_filter, toggle_follow_tail, ToggleFollowTail};
+use editor::{Editor, EditorEvent};
+use gpui::{EventEmitter, Subscription, View, ViewContext};
+use ui::{prelude::*, Tooltip};
+use workspace::{item::ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
+
+/// Filters the lines of the active log, and toggles whether it follows its tail.
+pub struct LogToolbar {
+    active_editor: Option<View<Editor>>,
+    include_editor: View<Editor>,
+    exclude_editor: View<Editor>,
+    location: ToolbarItemLocation,
+    following_tail: bool,
+    _active_editor_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LogToolbar {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let include_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Show lines matching…", cx);
+            editor
+        });
+        let exclude_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Hide lines matching…", cx);
+            editor
+        });
+        let subscriptions = [&include_editor, &exclude_editor]
+            .into_iter()
+            .map(|editor| {
+                cx.subscribe(editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_filter(cx);
+                    }
+                })
+            })
+            .collect();
+        Self {
+            active_editor: None,
+            include_editor,
+            exclude_editor,
+            location: ToolbarItemLocation::Hidden,
+            following_tail: false,
+            _active_editor_subscription: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn update_filter(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(editor) = self.active_editor.clone() else {
+            return;
+        };
+        let include = self.include_editor.read(cx).text(cx);
+        let exclude = self.exclude_editor.read(cx).text(cx);
+        editor.update(cx, |editor, cx| set_filter(editor, include, exclude, cx));
+    }
+
+    /// Shows the toolbar while the active editor is a log.
+    fn update_location(&mut self, cx: &mut ViewContext<Self>) -> ToolbarItemLocation {
+        let filter = self
+            .active_editor
+            .as_ref()
+            .and_then(|editor| filter(editor.read(cx), cx));
+        let location = if let Some((include, exclude)) = filter {
+            if self.location == ToolbarItemLocation::Hidden {
+                self.include_editor
+                    .update(cx, |editor, cx| editor.set_text(include, cx));
+                self.exclude_editor
+                    .update(cx, |editor, cx| editor.set_text(exclude, cx));
+            }
+            ToolbarItemLocation::Secondary
+        } else {
+            ToolbarItemLocation::Hidden
+        };
+        self.location = location;
+        location
+    }
+
+    fn render_filter_editor(&self, editor: &View<Editor>, cx: &ViewContext<Self>) -> Div {
+        h_flex()
+            .flex_1()
+            .min_w(rems(12.))
+            .px_2()
+            .py_1()
+            .gap_2()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                Icon::new(IconName::Filter)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(editor.clone())
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for LogToolbar {}
+
+impl Render for LogToolbar {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .child(self.render_filter_editor(&self.include_editor, cx))
+            .child(self.render_filter_editor(&self.exclude_editor, cx))
+            .child(
+                IconButton::new("toggle-follow-tail", IconName::ArrowDownFromLine)
+                    .selected(self.following_tail)
+                    .tooltip(|cx| Tooltip::for_action("Follow Tail", &ToggleFollowTail, cx))
+                    .on_click(cx.listener(|this, _, cx| {
+                        if let Some(editor) = this.active_editor.clone() {
+                            editor.update(cx, toggle_follow_tail);
+                        }
+                    })),
+            )
+    }
+}
+
+impl ToolbarItemView for LogToolbar {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        cx: &mut ViewContext<Self>,
+    ) -> ToolbarItemLocation {
+        self.active_editor = active_pane_item.and_then(|item| item.downcast::<Editor>());
+        self.location = ToolbarItemLocation::Hidden;
+        self._active_editor_subscription = self.active_editor.as_ref().map(|editor| {
+            // Log mode can be toggled while the editor is active.
+            cx.observe(editor, |this, editor, cx| {
+                let was_log = this.location != ToolbarItemLocation::Hidden;
+                if was_log != is_log(editor.read(cx)) {
+                    let location = this.update_location(cx);
+                    cx.emit(ToolbarItemEvent::ChangeLocation(location));
+                }
+                let following_tail = is_following_tail(editor.read(cx), cx);
+                if this.following_tail != following_tail {
+                    this.following_tail = following_tail;
+                    cx.notify();
+                }
+            })
+        });
+        self.following_tail = self
+            .active_editor
+            .as_ref()
+            .map_or(false, |editor| is_following_tail(editor.read(cx), cx));
+        self.update_location(cx)
+    }
+}
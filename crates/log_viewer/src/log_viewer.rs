@@ -0,0 +1,379 @@
+mod log_line;
+mod log_toolbar;
+mod log_viewer_settings;
+
+use editor::{Anchor, Editor, EditorEvent, EditorMode, FoldPlaceholder};
+use gpui::{
+    actions, point, AppContext, FontWeight, HighlightStyle, KeyContext, Model, Subscription, Task,
+    ViewContext,
+};
+use log_line::{parse_log_line, LogFilter, LogLevel};
+use settings::Settings as _;
+use std::{ops::Range, sync::Arc, time::Duration};
+use ui::prelude::*;
+use util::paths::PathMatcher;
+
+pub use log_toolbar::LogToolbar;
+pub use log_viewer_settings::LogViewerSettings;
+
+actions!(log_viewer, [ToggleLogMode, ToggleFollowTail]);
+
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+pub fn init(cx: &mut AppContext) {
+    LogViewerSettings::register(cx);
+
+    cx.observe_new_views(|editor: &mut Editor, cx| {
+        if editor.mode() != EditorMode::Full {
+            return;
+        }
+
+        editor
+            .register_action(cx.listener(|editor, _: &ToggleLogMode, cx| {
+                if is_log(editor) {
+                    disable(editor, cx);
+                } else {
+                    enable(editor, cx);
+                }
+            }))
+            .detach();
+        editor
+            .register_action(
+                cx.listener(|editor, _: &ToggleFollowTail, cx| toggle_follow_tail(editor, cx)),
+            )
+            .detach();
+
+        if matches_path_patterns(editor, cx) {
+            enable(editor, cx);
+        }
+    })
+    .detach();
+}
+
+/// The state of an editor showing a log.
+struct LogViewer {
+    include: String,
+    exclude: String,
+    follow_tail: bool,
+    /// The last display row of the log when it was last changed.
+    tail_row: u32,
+    folds: Vec<Range<Anchor>>,
+    refresh_task: Task<()>,
+}
+
+struct LogViewerAddon {
+    log_viewer: Model<LogViewer>,
+    _subscription: Subscription,
+}
+
+impl editor::Addon for LogViewerAddon {
+    fn extend_key_context(&self, key_context: &mut KeyContext, _: &AppContext) {
+        key_context.add("log_viewer");
+    }
+
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Marker types for the text highlights of each kind of log token.
+enum ErrorHighlight {}
+enum WarningHighlight {}
+enum InfoHighlight {}
+enum DebugHighlight {}
+enum TraceHighlight {}
+enum TimestampHighlight {}
+
+#[derive(Default)]
+struct LogHighlights {
+    errors: Vec<Range<Anchor>>,
+    warnings: Vec<Range<Anchor>>,
+    infos: Vec<Range<Anchor>>,
+    debugs: Vec<Range<Anchor>>,
+    traces: Vec<Range<Anchor>>,
+    timestamps: Vec<Range<Anchor>>,
+    hidden: Vec<(Range<Anchor>, u32)>,
+}
+
+fn matches_path_patterns(editor: &Editor, cx: &AppContext) -> bool {
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return false;
+    };
+    let Some(file) = buffer.read(cx).file() else {
+        return false;
+    };
+    let patterns = &LogViewerSettings::get_global(cx).path_patterns;
+    PathMatcher::new(patterns).map_or(false, |matcher| matcher.is_match(file.full_path(cx)))
+}
+
+fn log_viewer(editor: &Editor) -> Option<Model<LogViewer>> {
+    editor
+        .addon::<LogViewerAddon>()
+        .map(|addon| addon.log_viewer.clone())
+}
+
+pub(crate) fn is_log(editor: &Editor) -> bool {
+    editor.addon::<LogViewerAddon>().is_some()
+}
+
+fn enable(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let follow_tail = LogViewerSettings::get_global(cx).follow_tail;
+    let tail_row = editor.max_point(cx).row().0;
+    let log_viewer = cx.new_model(|_| LogViewer {
+        include: String::new(),
+        exclude: String::new(),
+        follow_tail,
+        tail_row,
+        folds: Vec::new(),
+        refresh_task: Task::ready(()),
+    });
+    let this = cx.view().clone();
+    let subscription = cx.subscribe(&this, |editor, _, event: &EditorEvent, cx| {
+        if let EditorEvent::BufferEdited = event {
+            let follow = should_follow_tail(editor, cx);
+            update_tail(editor, follow, cx);
+            refresh(editor, true, cx);
+        }
+    });
+    editor.register_addon(LogViewerAddon {
+        log_viewer,
+        _subscription: subscription,
+    });
+    refresh(editor, false, cx);
+    cx.notify();
+}
+
+fn disable(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let Some(log_viewer) = log_viewer(editor) else {
+        return;
+    };
+    let folds = log_viewer.update(cx, |log_viewer, _| {
+        log_viewer.refresh_task = Task::ready(());
+        std::mem::take(&mut log_viewer.folds)
+    });
+    editor.unregister_addon::<LogViewerAddon>();
+    editor.unfold_ranges(folds, true, false, cx);
+    apply_highlights(editor, LogHighlights::default(), cx);
+    cx.notify();
+}
+
+/// Returns the include and exclude filters of a log.
+pub(crate) fn filter(editor: &Editor, cx: &AppContext) -> Option<(String, String)> {
+    let log_viewer = log_viewer(editor)?;
+    let log_viewer = log_viewer.read(cx);
+    Some((log_viewer.include.clone(), log_viewer.exclude.clone()))
+}
+
+/// Hides the lines that don't match `include`, or that match `exclude`.
+pub(crate) fn set_filter(
+    editor: &mut Editor,
+    include: String,
+    exclude: String,
+    cx: &mut ViewContext<Editor>,
+) {
+    let Some(log_viewer) = log_viewer(editor) else {
+        return;
+    };
+    let changed = log_viewer.update(cx, |log_viewer, _| {
+        if log_viewer.include == include && log_viewer.exclude == exclude {
+            return false;
+        }
+        log_viewer.include = include;
+        log_viewer.exclude = exclude;
+        true
+    });
+    if changed {
+        refresh(editor, true, cx);
+    }
+}
+
+pub(crate) fn is_following_tail(editor: &Editor, cx: &AppContext) -> bool {
+    log_viewer(editor).map_or(false, |log_viewer| log_viewer.read(cx).follow_tail)
+}
+
+pub(crate) fn toggle_follow_tail(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let Some(log_viewer) = log_viewer(editor) else {
+        return;
+    };
+    let follow_tail = log_viewer.update(cx, |log_viewer, _| {
+        log_viewer.follow_tail = !log_viewer.follow_tail;
+        log_viewer.follow_tail
+    });
+    update_tail(editor, follow_tail, cx);
+    cx.notify();
+}
+
+/// Whether the log should be scrolled to its end after it changed, because it follows its
+/// tail and the end was in view before the change. Scrolling away from the end stops
+/// following it.
+fn should_follow_tail(editor: &Editor, cx: &mut ViewContext<Editor>) -> bool {
+    let Some(log_viewer) = log_viewer(editor) else {
+        return false;
+    };
+    let (follow_tail, tail_row) = {
+        let log_viewer = log_viewer.read(cx);
+        (log_viewer.follow_tail, log_viewer.tail_row)
+    };
+    let Some(visible_lines) = editor.visible_line_count() else {
+        return false;
+    };
+    follow_tail && editor.scroll_position(cx).y + visible_lines >= tail_row as f32 + 1.
+}
+
+/// Scrolls to the end of the log when `follow` is true, and remembers where the end is.
+fn update_tail(editor: &mut Editor, follow: bool, cx: &mut ViewContext<Editor>) {
+    if follow {
+        scroll_to_end(editor, cx);
+    }
+    let tail_row = editor.max_point(cx).row().0;
+    if let Some(log_viewer) = log_viewer(editor) {
+        log_viewer.update(cx, |log_viewer, _| log_viewer.tail_row = tail_row);
+    }
+}
+
+fn scroll_to_end(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    let Some(visible_lines) = editor.visible_line_count() else {
+        return;
+    };
+    let max_row = editor.max_point(cx).row().0 as f32;
+    let scroll_position = editor.scroll_position(cx);
+    editor.set_scroll_position(
+        point(scroll_position.x, (max_row + 1. - visible_lines).max(0.)),
+        cx,
+    );
+}
+
+/// Recomputes the highlighted tokens and hidden lines of the log in the background.
+fn refresh(editor: &mut Editor, debounce: bool, cx: &mut ViewContext<Editor>) {
+    let Some(log_viewer) = log_viewer(editor) else {
+        return;
+    };
+    let filter = {
+        let log_viewer = log_viewer.read(cx);
+        LogFilter::new(&log_viewer.include, &log_viewer.exclude)
+    };
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let task = cx.spawn(|editor, mut cx| async move {
+        if debounce {
+            cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+        }
+        let highlights = cx
+            .background_executor()
+            .spawn(async move {
+                let text = snapshot.text();
+                let mut highlights = LogHighlights::default();
+                let mut offset = 0;
+                for line in text.split('\n') {
+                    let parsed = parse_log_line(line);
+                    if let Some((level, range)) = parsed.level {
+                        let range = snapshot.anchor_after(offset + range.start)
+                            ..snapshot.anchor_before(offset + range.end);
+                        match level {
+                            LogLevel::Error => highlights.errors.push(range),
+                            LogLevel::Warning => highlights.warnings.push(range),
+                            LogLevel::Info => highlights.infos.push(range),
+                            LogLevel::Debug => highlights.debugs.push(range),
+                            LogLevel::Trace => highlights.traces.push(range),
+                        }
+                    }
+                    if let Some(range) = parsed.timestamp {
+                        highlights.timestamps.push(
+                            snapshot.anchor_after(offset + range.start)
+                                ..snapshot.anchor_before(offset + range.end),
+                        );
+                    }
+                    offset += line.len() + 1;
+                }
+                highlights.hidden = filter
+                    .hidden_ranges(&text)
+                    .into_iter()
+                    .map(|(range, count)| {
+                        (
+                            snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end),
+                            count,
+                        )
+                    })
+                    .collect();
+                highlights
+            })
+            .await;
+        editor
+            .update(&mut cx, |editor, cx| {
+                apply_highlights(editor, highlights, cx)
+            })
+            .ok();
+    });
+    log_viewer.update(cx, |log_viewer, _| log_viewer.refresh_task = task);
+}
+
+fn apply_highlights(editor: &mut Editor, highlights: LogHighlights, cx: &mut ViewContext<Editor>) {
+    let status = cx.theme().status();
+    let level_style = |color| HighlightStyle {
+        color: Some(color),
+        font_weight: Some(FontWeight::BOLD),
+        ..Default::default()
+    };
+    let styles = [
+        level_style(status.error),
+        level_style(status.warning),
+        level_style(status.info),
+        level_style(status.hint),
+        level_style(status.ignored),
+        HighlightStyle {
+            color: Some(cx.theme().colors().text_muted),
+            ..Default::default()
+        },
+    ];
+    let [error, warning, info, debug, trace, timestamp] = styles;
+    editor.highlight_text::<ErrorHighlight>(highlights.errors, error, cx);
+    editor.highlight_text::<WarningHighlight>(highlights.warnings, warning, cx);
+    editor.highlight_text::<InfoHighlight>(highlights.infos, info, cx);
+    editor.highlight_text::<DebugHighlight>(highlights.debugs, debug, cx);
+    editor.highlight_text::<TraceHighlight>(highlights.traces, trace, cx);
+    editor.highlight_text::<TimestampHighlight>(highlights.timestamps, timestamp, cx);
+
+    let Some(log_viewer) = log_viewer(editor) else {
+        return;
+    };
+    let follow = should_follow_tail(editor, cx);
+    let new_folds = highlights
+        .hidden
+        .iter()
+        .map(|(range, _)| range.clone())
+        .collect();
+    let old_folds = log_viewer.update(cx, |log_viewer, _| {
+        std::mem::replace(&mut log_viewer.folds, new_folds)
+    });
+    editor.unfold_ranges(old_folds, true, false, cx);
+    editor.fold_ranges(
+        highlights
+            .hidden
+            .into_iter()
+            .map(|(range, count)| (range, hidden_lines_placeholder(count))),
+        false,
+        cx,
+    );
+    update_tail(editor, follow, cx);
+}
+
+fn hidden_lines_placeholder(count: u32) -> FoldPlaceholder {
+    let label: SharedString = if count == 1 {
+        "1 line hidden".into()
+    } else {
+        format!("{count} lines hidden").into()
+    };
+    FoldPlaceholder {
+        render: Arc::new(move |_, _, _| {
+            div()
+                .mx_2()
+                .child(
+                    Label::new(label.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        }),
+        constrain_width: false,
+        merge_adjacent: false,
+    }
+}
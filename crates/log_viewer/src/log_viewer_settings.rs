@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LogViewerSettings {
+    pub path_patterns: Vec<String>,
+    pub follow_tail: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct LogViewerSettingsContent {
+    /// Globs matching the files that are opened as logs.
+    ///
+    /// Default: ["**/*.log", "**/*.log.[0-9]"]
+    pub path_patterns: Option<Vec<String>>,
+    /// Whether logs scroll to show the lines appended to them while they're scrolled
+    /// to the end.
+    ///
+    /// Default: true
+    pub follow_tail: Option<bool>,
+}
+
+impl Settings for LogViewerSettings {
+    const KEY: Option<&'static str> = Some("log_viewer");
+
+    type FileContent = LogViewerSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -142,6 +142,7 @@ pub fn main() {
                             thickness: px(1.),
                             color: Some(Color::Accent.color(cx)),
                             wavy: false,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     },
@@ -19,5 +19,7 @@ actions!(
         SelectNext,
         SelectFirst,
         SelectLast,
+        SelectPrevPage,
+        SelectNextPage,
     ]
 );
@@ -2315,16 +2315,26 @@ impl MultiBufferSnapshot {
         &self,
         start: T,
         for_completion: bool,
+    ) -> (Range<usize>, Option<CharKind>) {
+        let start = start.to_offset(self);
+        let classifier = self
+            .char_classifier_at(start)
+            .for_completion(for_completion);
+        self.surrounding_word_with_classifier(start, &classifier)
+    }
+
+    /// Returns the range and kind of the word surrounding the given position, as classified
+    /// by the given classifier.
+    pub fn surrounding_word_with_classifier<T: ToOffset>(
+        &self,
+        start: T,
+        classifier: &CharClassifier,
     ) -> (Range<usize>, Option<CharKind>) {
         let mut start = start.to_offset(self);
         let mut end = start;
         let mut next_chars = self.chars_at(start).peekable();
         let mut prev_chars = self.reversed_chars_at(start).peekable();
 
-        let classifier = self
-            .char_classifier_at(start)
-            .for_completion(for_completion);
-
         let word_kind = cmp::max(
             prev_chars.peek().copied().map(|c| classifier.kind(c)),
             next_chars.peek().copied().map(|c| classifier.kind(c)),
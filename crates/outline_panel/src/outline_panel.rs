@@ -24,12 +24,12 @@ use editor::{
 use file_icons::FileIcons;
 use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
 use gpui::{
-    actions, anchored, deferred, div, impl_actions, px, uniform_list, Action, AnyElement,
-    AppContext, AssetSource, AsyncWindowContext, ClipboardItem, DismissEvent, Div, ElementId,
-    EventEmitter, FocusHandle, FocusableView, HighlightStyle, InteractiveElement, IntoElement,
-    KeyContext, Model, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Render,
-    SharedString, Stateful, Styled, Subscription, Task, UniformListScrollHandle, View, ViewContext,
-    VisualContext, WeakView, WindowContext,
+    actions, anchored, deferred, div, impl_actions, px, scrollbar, uniform_list, Action,
+    AnyElement, AppContext, AssetSource, AsyncWindowContext, Axis, ClipboardItem, DismissEvent,
+    Div, ElementId, EventEmitter, FocusHandle, FocusableView, HighlightStyle, InteractiveElement,
+    IntoElement, KeyContext, Model, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
+    Render, ScrollbarState, SharedString, Stateful, Styled, Subscription, Task,
+    UniformListScrollHandle, View, ViewContext, VisualContext, WeakView, WindowContext,
 };
 use itertools::Itertools;
 use language::{BufferId, BufferSnapshot, OffsetRangeExt, OutlineItem};
@@ -95,6 +95,7 @@ pub struct OutlinePanel {
     active: bool,
     pinned: bool,
     scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
     context_menu: Option<(View<ContextMenu>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
     pending_serialization: Task<Option<()>>,
@@ -661,6 +662,8 @@ impl OutlinePanel {
                 }
             });
 
+            let scroll_handle = UniformListScrollHandle::new();
+            let scrollbar_state = ScrollbarState::new(scroll_handle.0.borrow().base_handle.clone());
             let mut outline_panel = Self {
                 mode: ItemsDisplayMode::Outline,
                 active: false,
@@ -668,7 +671,8 @@ impl OutlinePanel {
                 workspace: workspace_handle,
                 project,
                 fs: workspace.app_state().fs.clone(),
-                scroll_handle: UniformListScrollHandle::new(),
+                scroll_handle,
+                scrollbar_state,
                 focus_handle,
                 filter_editor,
                 fs_entries: Vec::new(),
@@ -1038,6 +1042,7 @@ impl OutlinePanel {
                 .position(|cached_entry| &cached_entry.entry == selected_entry);
             if let Some(index) = index {
                 self.scroll_handle.scroll_to_item(index);
+                self.scrollbar_state.flash();
                 cx.notify();
             }
         }
@@ -3976,78 +3981,94 @@ impl Render for OutlinePanel {
                             .child(horizontal_separator(cx)),
                     )
                 })
-                .child({
-                    let items_len = self.cached_entries.len();
-                    let multi_buffer_snapshot = self
-                        .active_editor()
-                        .map(|editor| editor.read(cx).buffer().read(cx).snapshot(cx));
-                    uniform_list(cx.view().clone(), "entries", items_len, {
-                        move |outline_panel, range, cx| {
-                            let entries = outline_panel.cached_entries.get(range);
-                            entries
-                                .map(|entries| entries.to_vec())
-                                .unwrap_or_default()
-                                .into_iter()
-                                .filter_map(|cached_entry| match cached_entry.entry {
-                                    PanelEntry::Fs(entry) => Some(outline_panel.render_entry(
-                                        &entry,
-                                        cached_entry.depth,
-                                        cached_entry.string_match.as_ref(),
-                                        cx,
-                                    )),
-                                    PanelEntry::FoldedDirs(worktree_id, entries) => {
-                                        Some(outline_panel.render_folded_dirs(
-                                            worktree_id,
-                                            &entries,
-                                            cached_entry.depth,
-                                            cached_entry.string_match.as_ref(),
-                                            cx,
-                                        ))
-                                    }
-                                    PanelEntry::Outline(OutlineEntry::Excerpt(
-                                        buffer_id,
-                                        excerpt_id,
-                                        excerpt,
-                                    )) => outline_panel.render_excerpt(
-                                        buffer_id,
-                                        excerpt_id,
-                                        &excerpt,
-                                        cached_entry.depth,
-                                        cx,
-                                    ),
-                                    PanelEntry::Outline(OutlineEntry::Outline(
-                                        buffer_id,
-                                        excerpt_id,
-                                        outline,
-                                    )) => Some(outline_panel.render_outline(
-                                        buffer_id,
-                                        excerpt_id,
-                                        &outline,
-                                        cached_entry.depth,
-                                        cached_entry.string_match.as_ref(),
-                                        cx,
-                                    )),
-                                    PanelEntry::Search(SearchEntry {
-                                        match_range,
-                                        render_data,
-                                        kind,
-                                        ..
-                                    }) => Some(outline_panel.render_search_match(
-                                        multi_buffer_snapshot.as_ref(),
-                                        &match_range,
-                                        &render_data,
-                                        kind,
-                                        cached_entry.depth,
-                                        cached_entry.string_match.as_ref(),
-                                        cx,
-                                    )),
-                                })
-                                .collect()
-                        }
-                    })
-                    .size_full()
-                    .track_scroll(self.scroll_handle.clone())
-                })
+                .child(
+                    div()
+                        .relative()
+                        .size_full()
+                        .child({
+                            let items_len = self.cached_entries.len();
+                            let multi_buffer_snapshot = self
+                                .active_editor()
+                                .map(|editor| editor.read(cx).buffer().read(cx).snapshot(cx));
+                            uniform_list(cx.view().clone(), "entries", items_len, {
+                                move |outline_panel, range, cx| {
+                                    let entries = outline_panel.cached_entries.get(range);
+                                    entries
+                                        .map(|entries| entries.to_vec())
+                                        .unwrap_or_default()
+                                        .into_iter()
+                                        .filter_map(|cached_entry| match cached_entry.entry {
+                                            PanelEntry::Fs(entry) => {
+                                                Some(outline_panel.render_entry(
+                                                    &entry,
+                                                    cached_entry.depth,
+                                                    cached_entry.string_match.as_ref(),
+                                                    cx,
+                                                ))
+                                            }
+                                            PanelEntry::FoldedDirs(worktree_id, entries) => {
+                                                Some(outline_panel.render_folded_dirs(
+                                                    worktree_id,
+                                                    &entries,
+                                                    cached_entry.depth,
+                                                    cached_entry.string_match.as_ref(),
+                                                    cx,
+                                                ))
+                                            }
+                                            PanelEntry::Outline(OutlineEntry::Excerpt(
+                                                buffer_id,
+                                                excerpt_id,
+                                                excerpt,
+                                            )) => outline_panel.render_excerpt(
+                                                buffer_id,
+                                                excerpt_id,
+                                                &excerpt,
+                                                cached_entry.depth,
+                                                cx,
+                                            ),
+                                            PanelEntry::Outline(OutlineEntry::Outline(
+                                                buffer_id,
+                                                excerpt_id,
+                                                outline,
+                                            )) => Some(outline_panel.render_outline(
+                                                buffer_id,
+                                                excerpt_id,
+                                                &outline,
+                                                cached_entry.depth,
+                                                cached_entry.string_match.as_ref(),
+                                                cx,
+                                            )),
+                                            PanelEntry::Search(SearchEntry {
+                                                match_range,
+                                                render_data,
+                                                kind,
+                                                ..
+                                            }) => Some(outline_panel.render_search_match(
+                                                multi_buffer_snapshot.as_ref(),
+                                                &match_range,
+                                                &render_data,
+                                                kind,
+                                                cached_entry.depth,
+                                                cached_entry.string_match.as_ref(),
+                                                cx,
+                                            )),
+                                        })
+                                        .collect()
+                                }
+                            })
+                            .size_full()
+                            .track_scroll(self.scroll_handle.clone())
+                        })
+                        .child(
+                            scrollbar(&self.scrollbar_state, Axis::Vertical)
+                                .overlay(true)
+                                .track_color(cx.theme().colors().scrollbar_track_background)
+                                .thumb_color(cx.theme().colors().scrollbar_thumb_background)
+                                .thumb_hover_color(
+                                    cx.theme().colors().scrollbar_thumb_hover_background,
+                                ),
+                        ),
+                )
         }
         .children(self.context_menu.as_ref().map(|(menu, position, _)| {
             deferred(
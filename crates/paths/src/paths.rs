@@ -162,6 +162,14 @@ pub fn themes_dir() -> &'static PathBuf {
     THEMES_DIR.get_or_init(|| config_dir().join("themes"))
 }
 
+/// Returns the path to the activity log.
+///
+/// This is where the editing time is recorded when activity tracking is enabled.
+pub fn activity_file() -> &'static PathBuf {
+    static ACTIVITY_FILE: OnceLock<PathBuf> = OnceLock::new();
+    ACTIVITY_FILE.get_or_init(|| support_dir().join("activity.json"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -78,8 +78,8 @@ pub trait PickerDelegate: Sized + 'static {
         None
     }
     fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str>;
-    fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
-        "No matches".into()
+    fn no_matches_text(&self, cx: &mut WindowContext) -> SharedString {
+        i18n::t("picker.no_matches", cx)
     }
     fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()>;
 
@@ -325,6 +325,52 @@ impl<D: PickerDelegate> Picker<D> {
         }
     }
 
+    fn select_prev_page(&mut self, _: &menu::SelectPrevPage, cx: &mut ViewContext<Self>) {
+        let count = self.delegate.match_count();
+        if count > 0 {
+            let index = self.delegate.selected_index();
+            self.set_selected_index(index.saturating_sub(self.page_len()), true, cx);
+            cx.notify();
+        }
+    }
+
+    fn select_next_page(&mut self, _: &menu::SelectNextPage, cx: &mut ViewContext<Self>) {
+        let count = self.delegate.match_count();
+        if count > 0 {
+            let index = self.delegate.selected_index();
+            self.set_selected_index((index + self.page_len()).min(count - 1), true, cx);
+            cx.notify();
+        }
+    }
+
+    /// The number of matches that fit in the picker, which is how far the selection moves by
+    /// a page.
+    fn page_len(&self) -> usize {
+        let page_len = match &self.element_container {
+            ElementContainer::List(state) => {
+                let viewport_bottom = state.viewport_bounds().bottom();
+                (state.logical_scroll_top().item_ix..self.delegate.match_count())
+                    .take_while(|ix| {
+                        state
+                            .bounds_for_item(*ix)
+                            .map_or(false, |bounds| bounds.bottom() <= viewport_bottom)
+                    })
+                    .count()
+            }
+            ElementContainer::UniformList(scroll_handle) => {
+                let scroll_state = scroll_handle.0.borrow();
+                let viewport_height = scroll_state.base_handle.bounds().size.height;
+                scroll_state
+                    .last_item_height
+                    .filter(|item_height| *item_height > px(0.))
+                    .map_or(1, |item_height| {
+                        (viewport_height / item_height).floor() as usize
+                    })
+            }
+        };
+        page_len.max(1)
+    }
+
     pub fn cycle_selection(&mut self, cx: &mut ViewContext<Self>) {
         let count = self.delegate.match_count();
         let index = self.delegate.selected_index();
@@ -591,6 +637,8 @@ impl<D: PickerDelegate> Render for Picker<D> {
             .on_action(cx.listener(Self::select_prev))
             .on_action(cx.listener(Self::select_first))
             .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_prev_page))
+            .on_action(cx.listener(Self::select_next_page))
             .on_action(cx.listener(Self::cancel))
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::secondary_confirm))
@@ -1,10 +1,10 @@
 mod signature_help;
 
 use crate::{
-    buffer_store::BufferStore, lsp_store::LspStore, CodeAction, CoreCompletion, DocumentHighlight,
-    Hover, HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel, InlayHintLabelPart,
-    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, MarkupContent,
-    ProjectTransaction, ResolveState,
+    buffer_store::BufferStore, lsp_store::LspStore, project_settings::ProjectSettings, CodeAction,
+    CoreCompletion, DocumentHighlight, Hover, HoverBlock, HoverBlockKind, InlayHint,
+    InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
+    LocationLink, MarkupContent, ProjectTransaction, ResolveState,
 };
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
@@ -26,8 +26,9 @@ use lsp::{
     LanguageServer, LanguageServerId, LinkedEditingRangeServerCapabilities, OneOf,
     ServerCapabilities,
 };
+use settings::Settings;
 use signature_help::{lsp_to_proto_signature, proto_to_lsp_signature};
-use std::{cmp::Reverse, ops::Range, path::Path, sync::Arc};
+use std::{cmp::Reverse, ops::Range, path::Path, sync::Arc, time::Duration};
 use text::{BufferId, LineEnding};
 
 pub use signature_help::{
@@ -59,6 +60,11 @@ pub trait LspCommand: 'static + Sized + Send + std::fmt::Debug {
         None
     }
 
+    /// How long to wait for local language servers to respond before cancelling the request.
+    fn timeout(&self, _: &AppContext) -> Option<Duration> {
+        None
+    }
+
     fn to_lsp(
         &self,
         path: &Path,
@@ -1491,6 +1497,10 @@ impl LspCommand for GetHover {
     type LspRequest = lsp::request::HoverRequest;
     type ProtoRequest = proto::GetHover;
 
+    fn timeout(&self, cx: &AppContext) -> Option<Duration> {
+        ProjectSettings::get_global(cx).lsp_timeouts.hover()
+    }
+
     fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
         match capabilities.server_capabilities.hover_provider {
             Some(lsp::HoverProviderCapability::Simple(enabled)) => enabled,
@@ -1722,6 +1732,10 @@ impl LspCommand for GetCompletions {
     type LspRequest = lsp::request::Completion;
     type ProtoRequest = proto::GetCompletions;
 
+    fn timeout(&self, cx: &AppContext) -> Option<Duration> {
+        ProjectSettings::get_global(cx).lsp_timeouts.completions()
+    }
+
     fn to_lsp(
         &self,
         path: &Path,
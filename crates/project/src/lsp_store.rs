@@ -817,6 +817,7 @@ impl LspStore {
         if let (Some(file), Some(language_server)) = (file, language_server) {
             let lsp_params = request.to_lsp(&file.abs_path(cx), buffer, &language_server, cx);
             let status = request.status();
+            let timeout = request.timeout(cx);
             return cx.spawn(move |this, cx| async move {
                 if !request.check_capabilities(language_server.adapter_server_capabilities()) {
                     return Ok(Default::default());
@@ -861,7 +862,20 @@ impl LspStore {
                     None
                 };
 
-                let result = lsp_request.await;
+                let result = match timeout {
+                    Some(timeout) => {
+                        Self::request_with_timeout::<R::LspRequest>(
+                            lsp_request,
+                            id,
+                            &language_server,
+                            timeout,
+                            this.clone(),
+                            &cx,
+                        )
+                        .await
+                    }
+                    None => lsp_request.await,
+                };
 
                 let response = result.map_err(|err| {
                     log::warn!(
@@ -887,6 +901,64 @@ impl LspStore {
         Task::ready(Ok(Default::default()))
     }
 
+    /// Waits for the response to a request for at most the given time, after which the request
+    /// is cancelled. Past half of that time, the server is reported as slow in its status.
+    async fn request_with_timeout<R: lsp::request::Request>(
+        request: impl Future<Output = Result<R::Result>>,
+        request_id: i32,
+        language_server: &LanguageServer,
+        timeout: Duration,
+        this: WeakModel<Self>,
+        cx: &AsyncAppContext,
+    ) -> Result<R::Result> {
+        let request = request.fuse();
+        futures::pin_mut!(request);
+        let slow_threshold = timeout / 2;
+        let mut slow_timer = cx.background_executor().timer(slow_threshold).fuse();
+        select! {
+            response = request => return response,
+            _ = slow_timer => {}
+        }
+
+        let server_id = language_server.server_id();
+        let token = format!("slow-request-{request_id}");
+        cx.update(|cx| {
+            this.update(cx, |this, cx| {
+                this.on_lsp_work_start(
+                    server_id,
+                    token.clone(),
+                    LanguageServerProgress {
+                        is_disk_based_diagnostics_progress: false,
+                        is_cancellable: false,
+                        title: Some(format!("{} is slow to respond", language_server.name())),
+                        message: Some(R::METHOD.to_string()),
+                        percentage: None,
+                        last_update_at: cx.background_executor().now(),
+                    },
+                    cx,
+                );
+            })
+        })
+        .log_err();
+        let _end_work = defer(|| {
+            cx.update(|cx| this.update(cx, |this, cx| this.on_lsp_work_end(server_id, token, cx)))
+                .log_err();
+        });
+
+        let mut timeout_timer = cx
+            .background_executor()
+            .timer(timeout - slow_threshold)
+            .fuse();
+        select! {
+            response = request => response,
+            _ = timeout_timer => Err(anyhow!(LanguageServerTimeout {
+                server_name: LanguageServerName(language_server.name().into()),
+                method: R::METHOD,
+                timeout,
+            })),
+        }
+    }
+
     fn on_settings_changed(&mut self, cx: &mut ModelContext<Self>) {
         let mut language_servers_to_start = Vec::new();
         let mut language_formatters_to_check = Vec::new();
@@ -1576,20 +1648,32 @@ impl LspStore {
                 })?;
 
                 let mut completions = Vec::new();
+                let mut timeout = None;
                 for (lsp_adapter, task) in tasks {
-                    if let Ok(new_completions) = task.await {
-                        populate_labels_for_completions(
-                            new_completions,
-                            &language_registry,
-                            language.clone(),
-                            lsp_adapter,
-                            &mut completions,
-                        )
-                        .await;
+                    match task.await {
+                        Ok(new_completions) => {
+                            populate_labels_for_completions(
+                                new_completions,
+                                &language_registry,
+                                language.clone(),
+                                lsp_adapter,
+                                &mut completions,
+                            )
+                            .await;
+                        }
+                        Err(error) => {
+                            if let Some(error) = error.downcast_ref::<LanguageServerTimeout>() {
+                                timeout = Some(error.clone());
+                            }
+                        }
                     }
                 }
 
-                Ok(completions)
+                // Let callers fall back to other completions when no server responded in time.
+                match timeout {
+                    Some(timeout) if completions.is_empty() => Err(anyhow!(timeout)),
+                    _ => Ok(completions),
+                }
             })
         }
     }
@@ -2778,7 +2862,7 @@ impl LspStore {
         server_id: LanguageServerId,
         abs_path: PathBuf,
         version: Option<i32>,
-        diagnostics: Vec<DiagnosticEntry<Unclipped<PointUtf16>>>,
+        mut diagnostics: Vec<DiagnosticEntry<Unclipped<PointUtf16>>>,
         cx: &mut ModelContext<Self>,
     ) -> Result<(), anyhow::Error> {
         let (worktree, relative_path) =
@@ -2792,6 +2876,15 @@ impl LspStore {
             path: relative_path.into(),
         };
 
+        // Only errors are reported for generated and vendored code, since it isn't edited by hand.
+        let location = SettingsLocation {
+            worktree_id: project_path.worktree_id,
+            path: &project_path.path,
+        };
+        if WorktreeSettings::get(Some(location), cx).is_path_generated(&project_path.path) {
+            diagnostics.retain(|entry| entry.diagnostic.severity == DiagnosticSeverity::ERROR);
+        }
+
         if let Some(buffer) = self.buffer_store.read(cx).get_by_path(&project_path, cx) {
             self.update_buffer_diagnostics(&buffer, server_id, version, diagnostics.clone(), cx)?;
         }
@@ -7021,6 +7114,27 @@ pub struct LanguageServerProgress {
     pub last_update_at: Instant,
 }
 
+/// The error of a language server request that was cancelled after taking longer than its time
+/// budget.
+#[derive(Clone, Debug)]
+pub struct LanguageServerTimeout {
+    pub server_name: LanguageServerName,
+    pub method: &'static str,
+    pub timeout: Duration,
+}
+
+impl std::fmt::Display for LanguageServerTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} didn't respond to {} within {:?}",
+            self.server_name.0, self.method, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for LanguageServerTimeout {}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Serialize)]
 pub struct DiagnosticSummary {
     pub error_count: usize,
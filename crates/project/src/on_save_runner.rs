@@ -0,0 +1,306 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Output,
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::anyhow;
+use collections::{HashMap, HashSet};
+use gpui::{Model, ModelContext, Task};
+use language::{Buffer, LanguageRegistry};
+use lsp::LanguageServerId;
+use regex::Regex;
+use settings::{Settings, SettingsLocation};
+use util::{paths::PathMatcher, ResultExt};
+
+use crate::{
+    project_settings::{OnSaveCommand, OnSaveOutput, ProjectSettings},
+    File, LspStore,
+};
+
+static DIAGNOSTIC_LINE_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Runs the commands configured in the `on_save` setting when files are saved, and shows
+/// their output as diagnostics.
+pub struct OnSaveRunner {
+    languages: Arc<LanguageRegistry>,
+    lsp_store: Model<LspStore>,
+    runs: Vec<OnSaveRun>,
+}
+
+/// The latest run of an `on_save` command in a worktree.
+pub struct OnSaveRun {
+    pub label: String,
+    pub status: OnSaveRunStatus,
+    command: OnSaveCommand,
+    working_directory: PathBuf,
+    /// The diagnostics of each command are reported as if they came from a language server of
+    /// their own, so that they replace each other rather than those of the actual servers.
+    language_server_id: LanguageServerId,
+    diagnosed_paths: HashSet<PathBuf>,
+    _task: Task<()>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OnSaveRunStatus {
+    Running,
+    Finished,
+    /// The command couldn't be run, or exited unsuccessfully without reporting diagnostics
+    /// that explain why.
+    Failed {
+        output: String,
+    },
+}
+
+impl OnSaveRunner {
+    pub fn new(languages: Arc<LanguageRegistry>, lsp_store: Model<LspStore>) -> Self {
+        Self {
+            languages,
+            lsp_store,
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn runs(&self) -> impl Iterator<Item = &OnSaveRun> {
+        self.runs.iter()
+    }
+
+    pub fn buffer_saved(&mut self, buffer: &Model<Buffer>, cx: &mut ModelContext<Self>) {
+        let Some(file) = File::from_dyn(buffer.read(cx).file()) else {
+            return;
+        };
+        let Some(buffer_abs_path) = file.as_local().map(|file| file.abs_path(cx)) else {
+            return;
+        };
+
+        let worktree = file.worktree.read(cx);
+        let mut working_directory = worktree.abs_path().to_path_buf();
+        if worktree.root_entry().map_or(false, |entry| entry.is_file()) {
+            working_directory.pop();
+        }
+
+        let location = SettingsLocation {
+            worktree_id: worktree.id(),
+            path: &file.path,
+        };
+        let commands = ProjectSettings::get(Some(location), cx).on_save.clone();
+        for command in commands {
+            if command.path_matches.is_empty()
+                || PathMatcher::new(&command.path_matches)
+                    .log_err()
+                    .map_or(false, |matcher| matcher.is_match(&file.path))
+            {
+                self.run(command, working_directory.clone(), &buffer_abs_path, cx);
+            }
+        }
+    }
+
+    fn run(
+        &mut self,
+        command: OnSaveCommand,
+        working_directory: PathBuf,
+        buffer_abs_path: &Path,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let existing_run = self
+            .runs
+            .iter()
+            .position(|run| run.command == command && run.working_directory == working_directory);
+        let ix = match existing_run {
+            Some(ix) => ix,
+            None => {
+                self.runs.push(OnSaveRun {
+                    label: command.label(),
+                    status: OnSaveRunStatus::Running,
+                    command: command.clone(),
+                    working_directory: working_directory.clone(),
+                    language_server_id: self.languages.next_language_server_id(),
+                    diagnosed_paths: HashSet::default(),
+                    _task: Task::ready(()),
+                });
+                self.runs.len() - 1
+            }
+        };
+
+        let mut child = smol::process::Command::new(&command.command);
+        #[cfg(target_os = "windows")]
+        {
+            use smol::process::windows::CommandExt;
+            child.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+        child
+            .current_dir(&working_directory)
+            .args(
+                command
+                    .arguments
+                    .iter()
+                    .map(|arg| arg.replace("{buffer_path}", &buffer_abs_path.to_string_lossy())),
+            )
+            .stdin(smol::process::Stdio::null())
+            .stdout(smol::process::Stdio::piped())
+            .stderr(smol::process::Stdio::piped())
+            // Saving again while the command is running restarts it.
+            .kill_on_drop(true);
+
+        let run = &mut self.runs[ix];
+        run.status = OnSaveRunStatus::Running;
+        run._task = cx.spawn(|this, mut cx| async move {
+            let output = child.output().await;
+            this.update(&mut cx, |this, cx| this.finish_run(ix, output, cx))
+                .ok();
+        });
+        cx.notify();
+    }
+
+    fn finish_run(&mut self, ix: usize, output: io::Result<Output>, cx: &mut ModelContext<Self>) {
+        let run = &mut self.runs[ix];
+        let output = match output {
+            Ok(output) => output,
+            Err(error) => {
+                let error = anyhow!(error).context(format!("failed to run `{}`", run.label));
+                run.status = OnSaveRunStatus::Failed {
+                    output: format!("{error:?}"),
+                };
+                cx.notify();
+                return;
+            }
+        };
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+
+        if run.command.output == OnSaveOutput::Diagnostics {
+            let diagnostics = parse_diagnostics(&text, &run.working_directory, &run.command);
+            let stale_paths = std::mem::take(&mut run.diagnosed_paths)
+                .into_iter()
+                .filter(|path| !diagnostics.contains_key(path))
+                .map(|path| (path, Vec::new()))
+                .collect::<Vec<_>>();
+            run.diagnosed_paths = diagnostics.keys().cloned().collect();
+            let language_server_id = run.language_server_id;
+            self.lsp_store.update(cx, |lsp_store, cx| {
+                for (path, diagnostics) in stale_paths.into_iter().chain(diagnostics) {
+                    let Ok(uri) = lsp::Url::from_file_path(&path) else {
+                        continue;
+                    };
+                    // The output can mention files outside of the project, which are skipped.
+                    lsp_store
+                        .update_diagnostics(
+                            language_server_id,
+                            lsp::PublishDiagnosticsParams {
+                                uri,
+                                diagnostics,
+                                version: None,
+                            },
+                            &[],
+                            cx,
+                        )
+                        .ok();
+                }
+            });
+        }
+
+        let run = &mut self.runs[ix];
+        run.status = if output.status.success() || !run.diagnosed_paths.is_empty() {
+            OnSaveRunStatus::Finished
+        } else {
+            OnSaveRunStatus::Failed { output: text }
+        };
+        cx.notify();
+    }
+}
+
+/// Parse the lines of the output shaped like `path:line:column: severity: message`, which
+/// most compilers and linters can print, e.g. `cargo check --message-format=short`.
+fn parse_diagnostics(
+    output: &str,
+    working_directory: &Path,
+    command: &OnSaveCommand,
+) -> HashMap<PathBuf, Vec<lsp::Diagnostic>> {
+    let regex = DIAGNOSTIC_LINE_REGEX.get_or_init(|| {
+        Regex::new(
+            r"^(?P<path>(?:[A-Za-z]:)?[^\s:][^:]*):(?P<line>\d+):(?:(?P<column>\d+):)?\s*(?:(?P<severity>error|warning|info|note|hint|help)(?:\[(?P<code>[^\]]+)\])?:\s*)?(?P<message>.+)$",
+        )
+        .unwrap()
+    });
+
+    let mut diagnostics = HashMap::<PathBuf, Vec<lsp::Diagnostic>>::default();
+    for line in output.lines() {
+        let Some(captures) = regex.captures(line.trim_end()) else {
+            continue;
+        };
+        let Ok(row) = captures["line"].parse::<u32>() else {
+            continue;
+        };
+        let column = captures
+            .name("column")
+            .and_then(|column| column.as_str().parse::<u32>().ok())
+            .unwrap_or(1);
+        let position = lsp::Position::new(row.saturating_sub(1), column.saturating_sub(1));
+        let severity = match captures.name("severity").map(|severity| severity.as_str()) {
+            Some("warning") => lsp::DiagnosticSeverity::WARNING,
+            Some("info") | Some("note") => lsp::DiagnosticSeverity::INFORMATION,
+            Some("hint") | Some("help") => lsp::DiagnosticSeverity::HINT,
+            _ => lsp::DiagnosticSeverity::ERROR,
+        };
+
+        diagnostics
+            .entry(working_directory.join(&captures["path"]))
+            .or_default()
+            .push(lsp::Diagnostic {
+                range: lsp::Range::new(position, position),
+                severity: Some(severity),
+                code: captures
+                    .name("code")
+                    .map(|code| lsp::NumberOrString::String(code.as_str().to_string())),
+                source: Some(command.command.clone()),
+                message: captures["message"].to_string(),
+                ..Default::default()
+            });
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let command = OnSaveCommand {
+            command: "cargo".to_string(),
+            arguments: vec!["check".to_string(), "--message-format=short".to_string()],
+            path_matches: Vec::new(),
+            output: OnSaveOutput::Diagnostics,
+        };
+        let output = "\
+    Checking app v0.1.0 (/work/app)
+src/main.rs:2:9: warning: unused variable: `x`
+src/main.rs:4:5: error[E0425]: cannot find value `y` in this scope
+src/lib.rs:10: missing documentation
+error: could not compile `app` (bin \"app\") due to 1 previous error; 1 warning emitted
+";
+
+        let diagnostics = parse_diagnostics(output, Path::new("/work/app"), &command);
+        assert_eq!(diagnostics.len(), 2);
+
+        let main = &diagnostics[Path::new("/work/app/src/main.rs")];
+        assert_eq!(main.len(), 2);
+        assert_eq!(main[0].range.start, lsp::Position::new(1, 8));
+        assert_eq!(main[0].severity, Some(lsp::DiagnosticSeverity::WARNING));
+        assert_eq!(main[0].message, "unused variable: `x`");
+        assert_eq!(main[1].range.start, lsp::Position::new(3, 4));
+        assert_eq!(main[1].severity, Some(lsp::DiagnosticSeverity::ERROR));
+        assert_eq!(
+            main[1].code,
+            Some(lsp::NumberOrString::String("E0425".to_string()))
+        );
+        assert_eq!(main[1].source.as_deref(), Some("cargo"));
+
+        let lib = &diagnostics[Path::new("/work/app/src/lib.rs")];
+        assert_eq!(lib[0].range.start, lsp::Position::new(9, 0));
+        assert_eq!(lib[0].severity, Some(lsp::DiagnosticSeverity::ERROR));
+        assert_eq!(lib[0].message, "missing documentation");
+    }
+}
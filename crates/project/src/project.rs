@@ -4,6 +4,7 @@ pub mod debounced_delay;
 pub mod lsp_command;
 pub mod lsp_ext_command;
 pub mod lsp_store;
+pub mod on_save_runner;
 pub mod prettier_store;
 pub mod project_settings;
 pub mod search;
@@ -56,6 +57,7 @@ use language::{
 use lsp::{CompletionContext, DocumentHighlightKind, LanguageServer, LanguageServerId};
 use lsp_command::*;
 use node_runtime::NodeRuntime;
+use on_save_runner::{OnSaveRun, OnSaveRunner};
 use parking_lot::{Mutex, RwLock};
 use paths::{local_tasks_file_relative_path, local_vscode_tasks_file_relative_path};
 pub use prettier_store::PrettierStore;
@@ -79,6 +81,7 @@ use std::{
     time::Duration,
 };
 use task::{
+    project_scripts::ScriptFile,
     static_source::{StaticSource, TrackedFile},
     HideStrategy, RevealStrategy, Shell, TaskContext, TaskTemplate, TaskVariables, VariableName,
 };
@@ -104,7 +107,7 @@ pub use worktree::{
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, LanguageServerLogType, LanguageServerProgress, LanguageServerPromptRequest,
-    LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent,
+    LanguageServerStatus, LanguageServerTimeout, LanguageServerToQuery, LspStore, LspStoreEvent,
     SERVER_PROGRESS_THROTTLE_TIMEOUT,
 };
 
@@ -168,6 +171,7 @@ pub struct Project {
     buffers_being_formatted: HashSet<BufferId>,
     environment: Model<ProjectEnvironment>,
     settings_observer: Model<SettingsObserver>,
+    on_save_runner: Option<Model<OnSaveRunner>>,
 }
 
 #[derive(Default)]
@@ -665,6 +669,10 @@ impl Project {
             });
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
 
+            let on_save_runner =
+                cx.new_model(|_| OnSaveRunner::new(languages.clone(), lsp_store.clone()));
+            cx.observe(&on_save_runner, |_, _, cx| cx.notify()).detach();
+
             Self {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
@@ -699,6 +707,7 @@ impl Project {
                 buffers_being_formatted: Default::default(),
                 search_included_history: Self::new_search_history(),
                 search_excluded_history: Self::new_search_history(),
+                on_save_runner: Some(on_save_runner),
             }
         })
     }
@@ -784,6 +793,7 @@ impl Project {
                 buffers_being_formatted: Default::default(),
                 search_included_history: Self::new_search_history(),
                 search_excluded_history: Self::new_search_history(),
+                on_save_runner: None,
             };
 
             let client: AnyProtoClient = ssh.clone().into();
@@ -966,6 +976,7 @@ impl Project {
                 remotely_created_models: Arc::new(Mutex::new(RemotelyCreatedModels::default())),
                 last_formatting_failure: None,
                 buffers_being_formatted: Default::default(),
+                on_save_runner: None,
             };
             this.set_role(role, cx);
             for worktree in worktrees {
@@ -2201,6 +2212,12 @@ impl Project {
                 .ok();
             }
 
+            BufferEvent::Saved => {
+                if let Some(on_save_runner) = &self.on_save_runner {
+                    on_save_runner.update(cx, |runner, cx| runner.buffer_saved(&buffer, cx));
+                }
+            }
+
             BufferEvent::Reloaded => {
                 if self.is_local_or_ssh() {
                     if let Some(project_id) = self.remote_id() {
@@ -2346,6 +2363,13 @@ impl Project {
         self.lsp_store.read(cx).language_server_statuses()
     }
 
+    /// The latest runs of the commands configured to run when files are saved.
+    pub fn on_save_runs<'a>(&'a self, cx: &'a AppContext) -> impl Iterator<Item = &'a OnSaveRun> {
+        self.on_save_runner
+            .iter()
+            .flat_map(|runner| runner.read(cx).runs())
+    }
+
     pub fn last_formatting_failure(&self) -> Option<&str> {
         self.last_formatting_failure.as_deref()
     }
@@ -2455,6 +2479,12 @@ impl Project {
         if self.is_local_or_ssh() {
             let buffers_with_paths = buffers
                 .into_iter()
+                // Generated and vendored files are only formatted on request.
+                .filter(|buffer_handle| {
+                    trigger != FormatTrigger::Save
+                        || !File::from_dyn(buffer_handle.read(cx).file())
+                            .map_or(false, |file| file.is_generated(cx))
+                })
                 .map(|buffer_handle| {
                     let buffer = buffer_handle.read(cx);
                     let buffer_abs_path = File::from_dyn(buffer.file())
@@ -3373,6 +3403,8 @@ impl Project {
             let mut limit_reached = false;
             let query = Arc::new(query);
             let mut chunks = matching_buffers_rx.ready_chunks(64);
+            // Matches in generated and vendored files are reported after all the others.
+            let mut generated_results = Vec::new();
 
             // Now that we know what paths match the query, we will load at most
             // 64 buffers at a time to avoid overwhelming the main thread. For each
@@ -3401,6 +3433,14 @@ impl Project {
                 let chunk_results = futures::future::join_all(chunk_results).await;
                 for result in chunk_results {
                     if let Some((buffer, ranges)) = result.log_err() {
+                        let is_generated = buffer.read_with(&cx, |buffer, cx| {
+                            File::from_dyn(buffer.file())
+                                .map_or(false, |file| file.is_generated(cx))
+                        })?;
+                        if is_generated {
+                            generated_results.push((buffer, ranges));
+                            continue;
+                        }
                         range_count += ranges.len();
                         buffer_count += 1;
                         result_tx
@@ -3416,6 +3456,22 @@ impl Project {
                 }
             }
 
+            if !limit_reached {
+                for (buffer, ranges) in generated_results {
+                    range_count += ranges.len();
+                    buffer_count += 1;
+                    result_tx
+                        .send(SearchResult::Buffer { buffer, ranges })
+                        .await?;
+                    if buffer_count > MAX_SEARCH_RESULT_FILES
+                        || range_count > MAX_SEARCH_RESULT_RANGES
+                    {
+                        limit_reached = true;
+                        break;
+                    }
+                }
+            }
+
             if limit_reached {
                 result_tx.send(SearchResult::LimitReached).await?;
             }
@@ -3808,6 +3864,42 @@ impl Project {
                         );
                     }
                 })
+            } else if let Some(script_file) = ScriptFile::for_path(path) {
+                let is_ignored = worktree
+                    .read(cx)
+                    .entry_for_path(path)
+                    .map_or(false, |entry| entry.is_ignored);
+                let Some(cwd) = script_file
+                    .working_directory(&abs_path)
+                    .map(Path::to_path_buf)
+                else {
+                    continue;
+                };
+                self.task_inventory().update(cx, |task_inventory, cx| {
+                    if removed {
+                        task_inventory.remove_local_static_source(&abs_path);
+                    } else if !is_ignored {
+                        let fs = self.fs.clone();
+                        let scripts_file_rx =
+                            watch_config_file(cx.background_executor(), fs, abs_path.clone());
+                        task_inventory.add_source(
+                            TaskSourceKind::Worktree {
+                                id: remote_worktree_id,
+                                abs_path,
+                                id_base: script_file.id_base().into(),
+                            },
+                            |tx, cx| {
+                                StaticSource::new(TrackedFile::new_parsed_with(
+                                    scripts_file_rx,
+                                    tx,
+                                    move |contents| script_file.scripts(contents, &cwd),
+                                    cx,
+                                ))
+                            },
+                            cx,
+                        );
+                    }
+                })
             }
         }
     }
@@ -7,6 +7,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{InvalidSettingsError, Settings, SettingsSources, SettingsStore};
 use std::{
+    iter,
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
@@ -40,6 +41,101 @@ pub struct ProjectSettings {
     /// Configuration for session-related features
     #[serde(default)]
     pub session: SessionSettings,
+
+    /// Commands to run when matching files are saved, such as `cargo check`.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub on_save: Vec<OnSaveCommand>,
+
+    /// How long to wait for language servers to respond to interactive requests
+    /// before cancelling them.
+    #[serde(default)]
+    pub lsp_timeouts: LspTimeoutSettings,
+}
+
+/// The time budgets of interactive language server requests, in milliseconds.
+/// A budget of 0 waits for the server indefinitely.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LspTimeoutSettings {
+    /// How long to wait for completions. When no server responds in time,
+    /// words from the buffer are suggested instead.
+    ///
+    /// Default: 2000
+    pub completions: u64,
+    /// How long to wait for hover information.
+    ///
+    /// Default: 2000
+    pub hover: u64,
+}
+
+impl Default for LspTimeoutSettings {
+    fn default() -> Self {
+        Self {
+            completions: 2000,
+            hover: 2000,
+        }
+    }
+}
+
+impl LspTimeoutSettings {
+    pub fn completions(&self) -> Option<Duration> {
+        Self::budget(self.completions)
+    }
+
+    pub fn hover(&self) -> Option<Duration> {
+        Self::budget(self.hover)
+    }
+
+    fn budget(milliseconds: u64) -> Option<Duration> {
+        (milliseconds > 0).then(|| Duration::from_millis(milliseconds))
+    }
+}
+
+/// A command run in the worktree's root directory whenever a matching file is saved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OnSaveCommand {
+    /// The program to run.
+    pub command: String,
+    /// The arguments to pass to the program. `{buffer_path}` is replaced
+    /// with the absolute path of the saved file.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    /// Globs matching the paths, relative to the worktree, of the files that
+    /// run the command when saved. When empty, every file does.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub path_matches: Vec<String>,
+    /// How to show the command's output.
+    ///
+    /// Default: diagnostics
+    #[serde(default)]
+    pub output: OnSaveOutput,
+}
+
+impl OnSaveCommand {
+    /// The command line, as it's shown to the user.
+    pub fn label(&self) -> String {
+        iter::once(self.command.as_str())
+            .chain(self.arguments.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OnSaveOutput {
+    /// Show the `path:line:column: severity: message` lines of the output as
+    /// diagnostics, along with whether the command succeeded in the status bar.
+    #[default]
+    Diagnostics,
+    /// Only show whether the command succeeded in the status bar.
+    Status,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -9,6 +9,7 @@ use std::{
     env::{self},
     iter,
     path::{Path, PathBuf},
+    time::Instant,
 };
 use task::{Shell, SpawnInTerminal};
 use terminal::{
@@ -167,6 +168,7 @@ impl Project {
                     hide: spawn_task.hide,
                     status: TaskStatus::Running,
                     completion_rx,
+                    started_at: Instant::now(),
                 });
 
                 env.extend(spawn_task.env);
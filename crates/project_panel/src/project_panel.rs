@@ -1,7 +1,5 @@
 mod project_panel_settings;
-mod scrollbar;
 use client::{ErrorCode, ErrorExt};
-use scrollbar::ProjectPanelScrollbar;
 use settings::{Settings, SettingsStore};
 
 use db::kvp::KEY_VALUE_STORE;
@@ -16,15 +14,18 @@ use anyhow::{anyhow, Result};
 use collections::{hash_map, BTreeSet, HashMap};
 use git::repository::GitFileStatus;
 use gpui::{
-    actions, anchored, deferred, div, impl_actions, px, uniform_list, Action, AnyElement,
-    AppContext, AssetSource, AsyncWindowContext, ClipboardItem, DismissEvent, Div, DragMoveEvent,
-    EventEmitter, ExternalPaths, FocusHandle, FocusableView, InteractiveElement, KeyContext,
-    ListSizingBehavior, Model, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
-    PromptLevel, Render, Stateful, Styled, Subscription, Task, UniformListScrollHandle, View,
+    actions, anchored, deferred, div, impl_actions, px, scrollbar, uniform_list, Action,
+    AnyElement, AppContext, AssetSource, AsyncWindowContext, Axis, ClipboardItem, DismissEvent,
+    Div, DragMoveEvent, EventEmitter, ExternalPaths, FocusHandle, FocusableView,
+    InteractiveElement, KeyContext, KeyDownEvent, ListSizingBehavior, Model, Modifiers,
+    MouseButton, MouseDownEvent, ParentElement, Pixels, Point, PromptLevel, Render, Scrollbar,
+    ScrollbarState, Stateful, Styled, Subscription, Task, UniformListScrollHandle, View,
     ViewContext, VisualContext as _, WeakView, WindowContext,
 };
 use indexmap::IndexMap;
-use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrev};
+use menu::{
+    Confirm, SelectFirst, SelectLast, SelectNext, SelectNextPage, SelectPrev, SelectPrevPage,
+};
 use project::{
     relativize_path, Entry, EntryKind, Fs, Project, ProjectEntryId, ProjectPath, Worktree,
     WorktreeId,
@@ -32,14 +33,13 @@ use project::{
 use project_panel_settings::{ProjectPanelDockPosition, ProjectPanelSettings, ShowScrollbar};
 use serde::{Deserialize, Serialize};
 use std::{
-    cell::{Cell, OnceCell},
+    cell::OnceCell,
     collections::HashSet,
     ffi::OsStr,
     ops::Range,
     path::{Path, PathBuf},
-    rc::Rc,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use theme::ThemeSettings;
 use ui::{prelude::*, v_flex, ContextMenu, Icon, KeyBinding, Label, ListItem, Tooltip};
@@ -79,11 +79,21 @@ pub struct ProjectPanel {
     workspace: WeakView<Workspace>,
     width: Option<Pixels>,
     pending_serialization: Task<Option<()>>,
-    show_scrollbar: bool,
-    scrollbar_drag_thumb_offset: Rc<Cell<Option<f32>>>,
-    hide_scrollbar_task: Option<Task<()>>,
+    scrollbar_state: ScrollbarState,
+    type_ahead: Option<TypeAhead>,
+}
+
+/// What was typed to jump to an entry by the beginning of its name.
+#[derive(Debug)]
+struct TypeAhead {
+    query: String,
+    last_keystroke: Instant,
 }
 
+/// How long after a keystroke the next one continues the type-ahead query, rather than starting
+/// a new one.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Debug)]
 struct EditState {
     worktree_id: WorktreeId,
@@ -117,6 +127,7 @@ struct EntryDetails {
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Box<Path>>,
+    coverage_percentage: Option<u32>,
 }
 
 #[derive(PartialEq, Clone, Default, Debug, Deserialize)]
@@ -220,10 +231,6 @@ impl ProjectPanel {
         let project_panel = cx.new_view(|cx: &mut ViewContext<Self>| {
             let focus_handle = cx.focus_handle();
             cx.on_focus(&focus_handle, Self::focus_in).detach();
-            cx.on_focus_out(&focus_handle, |this, _, cx| {
-                this.hide_scrollbar(cx);
-            })
-            .detach();
             cx.subscribe(&project, |this, project, event, cx| match event {
                 project::Event::ActiveEntryChanged(Some(entry_id)) => {
                     if ProjectPanelSettings::get_global(cx).auto_reveal_entries {
@@ -278,6 +285,10 @@ impl ProjectPanel {
             })
             .detach();
 
+            if let Some(coverage_store) = coverage::CoverageStore::try_global(cx) {
+                cx.observe(&coverage_store, |_, _, cx| cx.notify()).detach();
+            }
+
             let mut project_panel_settings = *ProjectPanelSettings::get_global(cx);
             cx.observe_global::<SettingsStore>(move |_, cx| {
                 let new_settings = *ProjectPanelSettings::get_global(cx);
@@ -288,10 +299,12 @@ impl ProjectPanel {
             })
             .detach();
 
+            let scroll_handle = UniformListScrollHandle::new();
+            let scrollbar_state = ScrollbarState::new(scroll_handle.0.borrow().base_handle.clone());
             let mut this = Self {
                 project: project.clone(),
                 fs: workspace.app_state().fs.clone(),
-                scroll_handle: UniformListScrollHandle::new(),
+                scroll_handle,
                 focus_handle,
                 visible_entries: Default::default(),
                 ancestors: Default::default(),
@@ -309,9 +322,8 @@ impl ProjectPanel {
                 workspace: workspace.weak_handle(),
                 width: None,
                 pending_serialization: Task::ready(None),
-                show_scrollbar: !Self::should_autohide_scrollbar(cx),
-                hide_scrollbar_task: None,
-                scrollbar_drag_thumb_offset: Default::default(),
+                scrollbar_state,
+                type_ahead: None,
             };
             this.update_visible_entries(None, cx);
 
@@ -489,59 +501,97 @@ impl ProjectPanel {
                 menu.context(self.focus_handle.clone()).map(|menu| {
                     if is_read_only {
                         menu.when(is_dir, |menu| {
-                            menu.action("Search Inside", Box::new(NewSearchInDirectory))
+                            menu.action(
+                                i18n::t("project_panel.search_inside", cx),
+                                Box::new(NewSearchInDirectory),
+                            )
                         })
                     } else {
-                        menu.action("New File", Box::new(NewFile))
-                            .action("New Folder", Box::new(NewDirectory))
+                        menu.action(i18n::t("project_panel.new_file", cx), Box::new(NewFile))
+                            .action(
+                                i18n::t("project_panel.new_folder", cx),
+                                Box::new(NewDirectory),
+                            )
                             .separator()
                             .when(cfg!(target_os = "macos"), |menu| {
-                                menu.action("Reveal in Finder", Box::new(RevealInFileManager))
+                                menu.action(
+                                    i18n::t("project_panel.reveal_in_finder", cx),
+                                    Box::new(RevealInFileManager),
+                                )
                             })
                             .when(cfg!(not(target_os = "macos")), |menu| {
-                                menu.action("Reveal in File Manager", Box::new(RevealInFileManager))
+                                menu.action(
+                                    i18n::t("project_panel.reveal_in_file_manager", cx),
+                                    Box::new(RevealInFileManager),
+                                )
                             })
-                            .action("Open in Default App", Box::new(OpenWithSystem))
-                            .action("Open in Terminal", Box::new(OpenInTerminal))
+                            .action(
+                                i18n::t("project_panel.open_in_default_app", cx),
+                                Box::new(OpenWithSystem),
+                            )
+                            .action(
+                                i18n::t("project_panel.open_in_terminal", cx),
+                                Box::new(OpenInTerminal),
+                            )
                             .when(is_dir, |menu| {
-                                menu.separator()
-                                    .action("Find in Folder…", Box::new(NewSearchInDirectory))
+                                menu.separator().action(
+                                    i18n::t("project_panel.find_in_folder", cx),
+                                    Box::new(NewSearchInDirectory),
+                                )
                             })
                             .when(is_unfoldable, |menu| {
-                                menu.action("Unfold Directory", Box::new(UnfoldDirectory))
+                                menu.action(
+                                    i18n::t("project_panel.unfold_directory", cx),
+                                    Box::new(UnfoldDirectory),
+                                )
                             })
                             .when(is_foldable, |menu| {
-                                menu.action("Fold Directory", Box::new(FoldDirectory))
+                                menu.action(
+                                    i18n::t("project_panel.fold_directory", cx),
+                                    Box::new(FoldDirectory),
+                                )
                             })
                             .separator()
-                            .action("Cut", Box::new(Cut))
-                            .action("Copy", Box::new(Copy))
-                            .action("Duplicate", Box::new(Duplicate))
+                            .action(i18n::t("project_panel.cut", cx), Box::new(Cut))
+                            .action(i18n::t("project_panel.copy", cx), Box::new(Copy))
+                            .action(i18n::t("project_panel.duplicate", cx), Box::new(Duplicate))
                             // TODO: Paste should always be visible, cbut disabled when clipboard is empty
                             .map(|menu| {
                                 if self.clipboard.as_ref().is_some() {
-                                    menu.action("Paste", Box::new(Paste))
+                                    menu.action(i18n::t("project_panel.paste", cx), Box::new(Paste))
                                 } else {
-                                    menu.disabled_action("Paste", Box::new(Paste))
+                                    menu.disabled_action(
+                                        i18n::t("project_panel.paste", cx),
+                                        Box::new(Paste),
+                                    )
                                 }
                             })
                             .separator()
-                            .action("Copy Path", Box::new(CopyPath))
-                            .action("Copy Relative Path", Box::new(CopyRelativePath))
+                            .action(i18n::t("project_panel.copy_path", cx), Box::new(CopyPath))
+                            .action(
+                                i18n::t("project_panel.copy_relative_path", cx),
+                                Box::new(CopyRelativePath),
+                            )
                             .separator()
-                            .action("Rename", Box::new(Rename))
+                            .action(i18n::t("project_panel.rename", cx), Box::new(Rename))
                             .when(!is_root, |menu| {
-                                menu.action("Trash", Box::new(Trash { skip_prompt: false }))
-                                    .action("Delete", Box::new(Delete { skip_prompt: false }))
+                                menu.action(
+                                    i18n::t("project_panel.trash", cx),
+                                    Box::new(Trash { skip_prompt: false }),
+                                )
+                                .action(
+                                    i18n::t("project_panel.delete", cx),
+                                    Box::new(Delete { skip_prompt: false }),
+                                )
                             })
                             .when(!is_remote & is_root, |menu| {
                                 menu.separator()
                                     .action(
-                                        "Add Folder to Project…",
+                                        i18n::t("project_panel.add_folder_to_project", cx),
                                         Box::new(workspace::AddFolderToProject),
                                     )
                                     .entry(
-                                        "Remove from Project",
+                                        i18n::t("project_panel.remove_from_project", cx),
                                         None,
                                         cx.handler_for(&this, move |this, cx| {
                                             this.project.update(cx, |project, cx| {
@@ -551,8 +601,10 @@ impl ProjectPanel {
                                     )
                             })
                             .when(is_root, |menu| {
-                                menu.separator()
-                                    .action("Collapse All", Box::new(CollapseAllEntries))
+                                menu.separator().action(
+                                    i18n::t("project_panel.collapse_all", cx),
+                                    Box::new(CollapseAllEntries),
+                                )
                             })
                     }
                 })
@@ -1077,11 +1129,15 @@ impl ProjectPanel {
                                 .take(CUTOFF_POINT)
                                 .collect::<Vec<_>>();
                             paths.truncate(CUTOFF_POINT);
-                            if truncated_path_counts == 1 {
-                                paths.push(".. 1 file not shown".into());
-                            } else {
-                                paths.push(format!(".. {} files not shown", truncated_path_counts));
-                            }
+                            paths.push(
+                                i18n::t_plural(
+                                    "project_panel.files_not_shown",
+                                    truncated_path_counts,
+                                    &[],
+                                    cx,
+                                )
+                                .into(),
+                            );
                             paths
                         } else {
                             file_paths.iter().map(|(_, path)| path.clone()).collect()
@@ -1250,26 +1306,157 @@ impl ProjectPanel {
     }
 
     fn select_last(&mut self, _: &SelectLast, cx: &mut ViewContext<Self>) {
-        let worktree = self.visible_entries.last().and_then(|(worktree_id, _, _)| {
-            self.project.read(cx).worktree_for_id(*worktree_id, cx)
-        });
-        if let Some(worktree) = worktree {
-            let worktree = worktree.read(cx);
-            let worktree_id = worktree.id();
-            if let Some(last_entry) = worktree.entries(true, 0).last() {
-                self.selection = Some(SelectedEntry {
-                    worktree_id,
-                    entry_id: last_entry.id,
+        // Entries inside of collapsed directories aren't visible, so they can't be selected.
+        if let Some(last_ix) = self.visible_entry_count().checked_sub(1) {
+            self.select_visible_entry(last_ix, cx);
+        }
+    }
+
+    fn select_prev_page(&mut self, _: &SelectPrevPage, cx: &mut ViewContext<Self>) {
+        match self.selected_visible_index() {
+            Some(ix) => self.select_visible_entry(ix.saturating_sub(self.page_len()), cx),
+            None => self.select_first(&SelectFirst {}, cx),
+        }
+    }
+
+    fn select_next_page(&mut self, _: &SelectNextPage, cx: &mut ViewContext<Self>) {
+        match self.selected_visible_index() {
+            Some(ix) => {
+                let last_ix = self.visible_entry_count().saturating_sub(1);
+                self.select_visible_entry((ix + self.page_len()).min(last_ix), cx);
+            }
+            None => self.select_first(&SelectFirst {}, cx),
+        }
+    }
+
+    /// The number of entries that fit in the panel, which is how far the selection moves by
+    /// a page.
+    fn page_len(&self) -> usize {
+        let scroll_state = self.scroll_handle.0.borrow();
+        let viewport_height = scroll_state.base_handle.bounds().size.height;
+        scroll_state
+            .last_item_height
+            .filter(|item_height| *item_height > px(0.))
+            .map_or(1, |item_height| {
+                (viewport_height / item_height).floor() as usize
+            })
+            .max(1)
+    }
+
+    fn visible_entry_count(&self) -> usize {
+        self.visible_entries
+            .iter()
+            .map(|(_, worktree_entries, _)| worktree_entries.len())
+            .sum()
+    }
+
+    fn selected_visible_index(&self) -> Option<usize> {
+        let (_, _, ix) = self.index_for_selection(self.selection?)?;
+        Some(ix)
+    }
+
+    /// Selects the entry at the given index among all visible entries, marking it too when
+    /// shift is held.
+    fn select_visible_entry(&mut self, mut ix: usize, cx: &mut ViewContext<Self>) {
+        let mut selection = None;
+        for (worktree_id, worktree_entries, _) in &self.visible_entries {
+            if let Some(entry) = worktree_entries.get(ix) {
+                selection = Some(SelectedEntry {
+                    worktree_id: *worktree_id,
+                    entry_id: entry.id,
                 });
-                self.autoscroll(cx);
-                cx.notify();
+                break;
             }
+            ix -= worktree_entries.len();
+        }
+
+        if let Some(selection) = selection {
+            self.selection = Some(selection);
+            if cx.modifiers().shift {
+                self.marked_entries.insert(selection);
+            }
+            self.autoscroll(cx);
+            cx.notify();
         }
     }
 
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let keystroke = &event.keystroke;
+        if self.edit_state.is_some() || !keystroke.modifiers.is_subset_of(&Modifiers::shift()) {
+            return;
+        }
+        let text = keystroke.ime_key.as_deref().unwrap_or(&keystroke.key);
+        let mut chars = text.chars();
+        if let (Some(character), None) = (chars.next(), chars.next()) {
+            if !character.is_control() {
+                self.type_ahead(character, cx);
+                cx.stop_propagation();
+            }
+        }
+    }
+
+    /// Selects an entry by the beginning of its name, which is typed in quick succession.
+    /// Typing the same character repeatedly cycles through the entries starting with it.
+    fn type_ahead(&mut self, character: char, cx: &mut ViewContext<Self>) {
+        let now = cx.background_executor().now();
+        let mut query = match self.type_ahead.take() {
+            Some(type_ahead) if now - type_ahead.last_keystroke < TYPE_AHEAD_TIMEOUT => {
+                type_ahead.query
+            }
+            _ => String::new(),
+        };
+        query.extend(character.to_lowercase());
+
+        let first_character = query.chars().next().unwrap_or(character);
+        let (prefix, start_after_selection) = if query.chars().all(|c| c == first_character) {
+            (&query[..first_character.len_utf8()], true)
+        } else {
+            (query.as_str(), false)
+        };
+        let names = self.visible_entry_names(cx);
+        let start_ix = match self.selected_visible_index() {
+            Some(ix) if start_after_selection => ix + 1,
+            Some(ix) => ix,
+            None => 0,
+        };
+        let matching_ix = (0..names.len())
+            .map(|offset| (start_ix + offset) % names.len())
+            .find(|ix| names[*ix].starts_with(prefix));
+        if let Some(ix) = matching_ix {
+            self.select_visible_entry(ix, cx);
+        }
+
+        self.type_ahead = Some(TypeAhead {
+            query,
+            last_keystroke: now,
+        });
+    }
+
+    /// The lowercase names of the visible entries, in the order they're displayed.
+    fn visible_entry_names(&self, cx: &AppContext) -> Vec<String> {
+        let project = self.project.read(cx);
+        self.visible_entries
+            .iter()
+            .flat_map(|(worktree_id, worktree_entries, _)| {
+                let root_name = project
+                    .worktree_for_id(*worktree_id, cx)
+                    .map(|worktree| worktree.read(cx).root_name().to_string())
+                    .unwrap_or_default();
+                worktree_entries.iter().map(move |entry| {
+                    entry
+                        .path
+                        .file_name()
+                        .map_or(root_name.clone(), |name| name.to_string_lossy().to_string())
+                        .to_lowercase()
+                })
+            })
+            .collect()
+    }
+
     fn autoscroll(&mut self, cx: &mut ViewContext<Self>) {
         if let Some((_, _, index)) = self.selection.and_then(|s| self.index_for_selection(s)) {
             self.scroll_handle.scroll_to_item(index);
+            self.scrollbar_state.flash();
             cx.notify();
         }
     }
@@ -2090,6 +2277,12 @@ impl ProjectPanel {
                         worktree_id: snapshot.id(),
                         entry_id: entry.id,
                     };
+                    let coverage_percentage = if entry.is_file() {
+                        coverage::file_percentage(&snapshot.abs_path().join(&entry.path), cx)
+                            .map(|percentage| percentage.round() as u32)
+                    } else {
+                        None
+                    };
                     let mut details = EntryDetails {
                         filename,
                         icon,
@@ -2110,6 +2303,7 @@ impl ProjectPanel {
                         is_private: entry.is_private,
                         worktree_id: *worktree_id,
                         canonical_path: entry.canonical_path.clone(),
+                        coverage_percentage,
                     };
 
                     if let Some(edit_state) = &self.edit_state {
@@ -2211,6 +2405,7 @@ impl ProjectPanel {
             .as_ref()
             .map(|f| f.to_string_lossy().to_string());
         let path = details.path.clone();
+        let coverage_percentage = details.coverage_percentage;
 
         let depth = details.depth;
         let worktree_id = details.worktree_id;
@@ -2292,6 +2487,21 @@ impl ProjectPanel {
                     .indent_level(depth)
                     .indent_step_size(px(settings.indent_size))
                     .selected(is_marked || is_active)
+                    .when_some(
+                        coverage_percentage.filter(|_| canonical_path.is_none()),
+                        |this, percentage| {
+                            this.end_slot::<AnyElement>(
+                                div()
+                                    .pr_3()
+                                    .child(
+                                        Label::new(format!("{percentage}%"))
+                                            .size(LabelSize::XSmall)
+                                            .color(Color::Muted),
+                                    )
+                                    .into_any_element(),
+                            )
+                        },
+                    )
                     .when_some(canonical_path, |this, path| {
                         this.end_slot::<AnyElement>(
                             div()
@@ -2495,85 +2705,18 @@ impl ProjectPanel {
             )
     }
 
-    fn render_scrollbar(
-        &self,
-        items_count: usize,
-        cx: &mut ViewContext<Self>,
-    ) -> Option<Stateful<Div>> {
-        let settings = ProjectPanelSettings::get_global(cx);
-        if settings.scrollbar.show == ShowScrollbar::Never {
+    fn render_scrollbar(&self, cx: &mut ViewContext<Self>) -> Option<Scrollbar> {
+        if ProjectPanelSettings::get_global(cx).scrollbar.show == ShowScrollbar::Never {
             return None;
         }
-        let scroll_handle = self.scroll_handle.0.borrow();
 
-        let height = scroll_handle
-            .last_item_height
-            .filter(|_| self.show_scrollbar || self.scrollbar_drag_thumb_offset.get().is_some())?;
-
-        let total_list_length = height.0 as f64 * items_count as f64;
-        let current_offset = scroll_handle.base_handle.offset().y.0.min(0.).abs() as f64;
-        let mut percentage = current_offset / total_list_length;
-        let end_offset = (current_offset + scroll_handle.base_handle.bounds().size.height.0 as f64)
-            / total_list_length;
-        // Uniform scroll handle might briefly report an offset greater than the length of a list;
-        // in such case we'll adjust the starting offset as well to keep the scrollbar thumb length stable.
-        let overshoot = (end_offset - 1.).clamp(0., 1.);
-        if overshoot > 0. {
-            percentage -= overshoot;
-        }
-        const MINIMUM_SCROLLBAR_PERCENTAGE_HEIGHT: f64 = 0.005;
-        if percentage + MINIMUM_SCROLLBAR_PERCENTAGE_HEIGHT > 1.0 || end_offset > total_list_length
-        {
-            return None;
-        }
-        if total_list_length < scroll_handle.base_handle.bounds().size.height.0 as f64 {
-            return None;
-        }
-        let end_offset = end_offset.clamp(percentage + MINIMUM_SCROLLBAR_PERCENTAGE_HEIGHT, 1.);
+        let colors = cx.theme().colors();
         Some(
-            div()
-                .occlude()
-                .id("project-panel-scroll")
-                .on_mouse_move(cx.listener(|_, _, cx| {
-                    cx.notify();
-                    cx.stop_propagation()
-                }))
-                .on_hover(|_, cx| {
-                    cx.stop_propagation();
-                })
-                .on_any_mouse_down(|_, cx| {
-                    cx.stop_propagation();
-                })
-                .on_mouse_up(
-                    MouseButton::Left,
-                    cx.listener(|this, _, cx| {
-                        if this.scrollbar_drag_thumb_offset.get().is_none()
-                            && !this.focus_handle.contains_focused(cx)
-                        {
-                            this.hide_scrollbar(cx);
-                            cx.notify();
-                        }
-
-                        cx.stop_propagation();
-                    }),
-                )
-                .on_scroll_wheel(cx.listener(|_, _, cx| {
-                    cx.notify();
-                }))
-                .h_full()
-                .absolute()
-                .right_0()
-                .top_0()
-                .bottom_0()
-                .w(px(12.))
-                .cursor_default()
-                .child(ProjectPanelScrollbar::new(
-                    percentage as f32..end_offset as f32,
-                    self.scroll_handle.clone(),
-                    self.scrollbar_drag_thumb_offset.clone(),
-                    cx.view().clone().into(),
-                    items_count,
-                )),
+            scrollbar(&self.scrollbar_state, Axis::Vertical)
+                .overlay(Self::should_autohide_scrollbar(cx))
+                .track_color(colors.scrollbar_track_background)
+                .thumb_color(colors.scrollbar_thumb_background)
+                .thumb_hover_color(colors.scrollbar_thumb_hover_background),
         )
     }
 
@@ -2597,24 +2740,6 @@ impl ProjectPanel {
             .map_or_else(|| cx.should_auto_hide_scrollbars(), |autohide| autohide.0)
     }
 
-    fn hide_scrollbar(&mut self, cx: &mut ViewContext<Self>) {
-        const SCROLLBAR_SHOW_INTERVAL: Duration = Duration::from_secs(1);
-        if !Self::should_autohide_scrollbar(cx) {
-            return;
-        }
-        self.hide_scrollbar_task = Some(cx.spawn(|panel, mut cx| async move {
-            cx.background_executor()
-                .timer(SCROLLBAR_SHOW_INTERVAL)
-                .await;
-            panel
-                .update(&mut cx, |panel, cx| {
-                    panel.show_scrollbar = false;
-                    cx.notify();
-                })
-                .log_err();
-        }))
-    }
-
     fn reveal_entry(
         &mut self,
         project: Model<Project>,
@@ -2659,20 +2784,14 @@ impl Render for ProjectPanel {
                 .group("project-panel")
                 .size_full()
                 .relative()
-                .on_hover(cx.listener(|this, hovered, cx| {
-                    if *hovered {
-                        this.show_scrollbar = true;
-                        this.hide_scrollbar_task.take();
-                        cx.notify();
-                    } else if !this.focus_handle.contains_focused(cx) {
-                        this.hide_scrollbar(cx);
-                    }
-                }))
                 .key_context(self.dispatch_context(cx))
                 .on_action(cx.listener(Self::select_next))
                 .on_action(cx.listener(Self::select_prev))
                 .on_action(cx.listener(Self::select_first))
                 .on_action(cx.listener(Self::select_last))
+                .on_action(cx.listener(Self::select_prev_page))
+                .on_action(cx.listener(Self::select_next_page))
+                .on_key_down(cx.listener(Self::handle_key_down))
                 .on_action(cx.listener(Self::select_parent))
                 .on_action(cx.listener(Self::expand_selected_entry))
                 .on_action(cx.listener(Self::collapse_selected_entry))
@@ -2749,7 +2868,7 @@ impl Render for ProjectPanel {
                     .with_sizing_behavior(ListSizingBehavior::Infer)
                     .track_scroll(self.scroll_handle.clone()),
                 )
-                .children(self.render_scrollbar(items_count, cx))
+                .children(self.render_scrollbar(cx))
                 .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                     deferred(
                         anchored()
@@ -2898,6 +3017,10 @@ impl Panel for ProjectPanel {
                     .map_or(false, |entry| entry.is_dir())
             })
     }
+
+    fn cache_to_texture(&self, _: &WindowContext) -> bool {
+        true
+    }
 }
 
 impl FocusableView for ProjectPanel {
@@ -4573,6 +4696,93 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_type_ahead(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor().clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "apple": {},
+                "apricot.txt": "",
+                "banana.txt": "",
+                "berry.txt": "",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace.update(cx, ProjectPanel::new).unwrap();
+
+        panel.update(cx, |panel, cx| panel.type_ahead('b', cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root",
+                "    > apple",
+                "      apricot.txt",
+                "      banana.txt  <== selected",
+                "      berry.txt",
+            ]
+        );
+
+        // Typing the same character again cycles through the entries starting with it.
+        panel.update(cx, |panel, cx| panel.type_ahead('b', cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root",
+                "    > apple",
+                "      apricot.txt",
+                "      banana.txt",
+                "      berry.txt  <== selected",
+            ]
+        );
+
+        // After a pause, typing starts a new query.
+        cx.executor().advance_clock(TYPE_AHEAD_TIMEOUT);
+        panel.update(cx, |panel, cx| {
+            panel.type_ahead('a', cx);
+            panel.type_ahead('p', cx);
+        });
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root",
+                "    > apple  <== selected",
+                "      apricot.txt",
+                "      banana.txt",
+                "      berry.txt",
+            ]
+        );
+        panel.update(cx, |panel, cx| panel.type_ahead('r', cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root",
+                "    > apple",
+                "      apricot.txt  <== selected",
+                "      banana.txt",
+                "      berry.txt",
+            ]
+        );
+
+        panel.update(cx, |panel, cx| panel.select_last(&SelectLast, cx));
+        assert_eq!(
+            visible_entries_as_strings(&panel, 0..10, cx),
+            &[
+                "v root",
+                "    > apple",
+                "      apricot.txt",
+                "      banana.txt",
+                "      berry.txt  <== selected",
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_new_file_move(cx: &mut gpui::TestAppContext) {
         init_test(cx);
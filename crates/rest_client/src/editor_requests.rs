@@ -0,0 +1,299 @@
+use crate::{
+    environment::load_environment,
+    http_file::{HttpFile, TokenKind},
+    response_view::{HttpResponse, ResponseView},
+    RestClientSettings, SendRequest,
+};
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashSet;
+use editor::{
+    display_map::{
+        BlockContext, BlockDisposition, BlockProperties, BlockStyle, CustomBlockId, RenderBlock,
+    },
+    Anchor, Editor, EditorMode, ToOffset as _,
+};
+use futures::AsyncReadExt as _;
+use gpui::{AppContext, Model, Task, ViewContext, WeakView, WindowContext};
+use http_client::{AsyncBody, HttpClient as _, Request};
+use language::{Buffer, BufferEvent};
+use settings::Settings as _;
+use std::{ops::Range, path::Path, time::Duration, time::Instant};
+use theme::ActiveTheme as _;
+use ui::{prelude::*, ButtonLike};
+use util::ResultExt as _;
+use workspace::SplitDirection;
+
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+struct MethodHighlight;
+struct UrlHighlight;
+struct HeaderNameHighlight;
+struct VariableHighlight;
+struct CommentHighlight;
+
+/// Tracks the "Send Request" blocks rendered above each request in a `.http` editor.
+#[derive(Default)]
+struct RequestBlocks {
+    block_ids: HashSet<CustomBlockId>,
+    refresh_task: Option<Task<()>>,
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut ViewContext<Editor>) {
+    if editor.mode() != EditorMode::Full {
+        return;
+    }
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+    if !is_http_file(&buffer, cx) {
+        return;
+    }
+
+    let editor_handle = cx.view().downgrade();
+    editor
+        .register_action(move |_: &SendRequest, cx| {
+            let Some(editor) = editor_handle.upgrade() else {
+                return;
+            };
+            let cursor = editor.read(cx).selections.newest_anchor().head();
+            send_request(editor_handle.clone(), cursor, cx);
+        })
+        .detach();
+
+    let blocks = cx.new_model(|_| RequestBlocks::default());
+    refresh_requests(editor, &blocks, cx);
+    cx.subscribe(&buffer, move |_, _, event, cx| {
+        if let BufferEvent::Edited = event {
+            schedule_refresh(blocks.clone(), cx);
+        }
+    })
+    .detach();
+}
+
+fn is_http_file(buffer: &Model<Buffer>, cx: &AppContext) -> bool {
+    buffer
+        .read(cx)
+        .file()
+        .and_then(|file| file.path().extension())
+        .map_or(false, |extension| {
+            extension == "http" || extension == "rest"
+        })
+}
+
+fn schedule_refresh(blocks: Model<RequestBlocks>, cx: &mut ViewContext<Editor>) {
+    let task = cx.spawn({
+        let blocks = blocks.clone();
+        |editor, mut cx| async move {
+            cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+            editor
+                .update(&mut cx, |editor, cx| refresh_requests(editor, &blocks, cx))
+                .ok();
+        }
+    });
+    blocks.update(cx, |blocks, _| blocks.refresh_task = Some(task));
+}
+
+fn refresh_requests(
+    editor: &mut Editor,
+    blocks: &Model<RequestBlocks>,
+    cx: &mut ViewContext<Editor>,
+) {
+    let old_block_ids = blocks.update(cx, |blocks, _| std::mem::take(&mut blocks.block_ids));
+    editor.remove_blocks(old_block_ids, None, cx);
+    editor.clear_highlights::<MethodHighlight>(cx);
+    editor.clear_highlights::<UrlHighlight>(cx);
+    editor.clear_highlights::<HeaderNameHighlight>(cx);
+    editor.clear_highlights::<VariableHighlight>(cx);
+    editor.clear_highlights::<CommentHighlight>(cx);
+
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let file = HttpFile::parse(&snapshot.text());
+    let anchor_range = |range: &Range<usize>| {
+        snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end)
+    };
+
+    let mut method_ranges = Vec::new();
+    let mut url_ranges = Vec::new();
+    let mut header_name_ranges = Vec::new();
+    let mut variable_ranges = Vec::new();
+    let mut comment_ranges = Vec::new();
+    for (range, kind) in &file.tokens {
+        let ranges = match kind {
+            TokenKind::Method => &mut method_ranges,
+            TokenKind::Url => &mut url_ranges,
+            TokenKind::HeaderName => &mut header_name_ranges,
+            TokenKind::Variable | TokenKind::VariableName => &mut variable_ranges,
+            TokenKind::Comment | TokenKind::Separator => &mut comment_ranges,
+        };
+        ranges.push(anchor_range(range));
+    }
+    let syntax = cx.theme().syntax().clone();
+    editor.highlight_text::<MethodHighlight>(method_ranges, syntax.get("keyword"), cx);
+    editor.highlight_text::<UrlHighlight>(url_ranges, syntax.get("link_uri"), cx);
+    editor.highlight_text::<HeaderNameHighlight>(header_name_ranges, syntax.get("property"), cx);
+    editor.highlight_text::<VariableHighlight>(variable_ranges, syntax.get("variable.special"), cx);
+    editor.highlight_text::<CommentHighlight>(comment_ranges, syntax.get("comment"), cx);
+
+    if !RestClientSettings::get_global(cx).enabled {
+        return;
+    }
+    let editor_handle = cx.view().downgrade();
+    let new_blocks = file
+        .requests
+        .iter()
+        .enumerate()
+        .map(|(ix, request)| {
+            let position = snapshot.anchor_after(request.request_line.start);
+            BlockProperties {
+                position,
+                height: 1,
+                style: BlockStyle::Sticky,
+                render: render_send_button(ix, editor_handle.clone(), position),
+                disposition: BlockDisposition::Above,
+                priority: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+    let block_ids = editor.insert_blocks(new_blocks, None, cx);
+    blocks.update(cx, |blocks, _| {
+        blocks.block_ids = block_ids.into_iter().collect()
+    });
+}
+
+fn render_send_button(ix: usize, editor: WeakView<Editor>, position: Anchor) -> RenderBlock {
+    Box::new(move |cx: &mut BlockContext| {
+        let editor = editor.clone();
+        h_flex()
+            .h(cx.line_height)
+            .pl(cx.anchor_x)
+            .child(
+                ButtonLike::new(("send-request", ix))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Icon::new(IconName::Play)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new("Send Request")
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .on_click(move |_, cx| send_request(editor.clone(), position, cx)),
+            )
+            .into_any_element()
+    })
+}
+
+/// Sends the request containing `position` and shows the response in the workspace's
+/// response view, opening one in a split if needed.
+fn send_request(editor: WeakView<Editor>, position: Anchor, cx: &mut WindowContext) {
+    send_request_internal(editor, position, cx).log_err();
+}
+
+fn send_request_internal(
+    editor: WeakView<Editor>,
+    position: Anchor,
+    cx: &mut WindowContext,
+) -> Result<()> {
+    let editor = editor.upgrade().context("editor was dropped")?;
+    let workspace = editor
+        .read(cx)
+        .workspace()
+        .context("editor is not part of a workspace")?;
+    let buffer = editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+        .context("expected a singleton buffer")?;
+
+    let snapshot = buffer.read(cx).snapshot();
+    let offset = position.to_offset(&editor.read(cx).buffer().read(cx).snapshot(cx));
+    let file = HttpFile::parse(&snapshot.text());
+    let request = file
+        .request_at(offset)
+        .ok_or_else(|| anyhow!("no request at cursor"))?
+        .clone();
+
+    let project_file = project::File::from_dyn(buffer.read(cx).file());
+    let abs_path = project_file.map(|file| file.worktree.read(cx).abs_path().join(&file.path));
+    let root = project_file.map(|file| file.worktree.read(cx).abs_path());
+
+    let settings = RestClientSettings::get_global(cx).clone();
+    let http_client = cx.http_client();
+    let fs = workspace.read(cx).app_state().fs.clone();
+    let language_registry = workspace.read(cx).project().read(cx).languages().clone();
+
+    let response_view = workspace.update(cx, |workspace, cx| {
+        if let Some(response_view) = workspace.item_of_type::<ResponseView>(cx) {
+            workspace.activate_item(&response_view, false, false, cx);
+            response_view
+        } else {
+            let response_view = cx.new_view(|cx| ResponseView::new(language_registry, cx));
+            workspace.split_item(SplitDirection::Right, Box::new(response_view.clone()), cx);
+            response_view
+        }
+    });
+    response_view.update(cx, |view, cx| view.set_pending(request.display_name(), cx));
+
+    cx.spawn(|mut cx| async move {
+        let result = async {
+            let environment = match (abs_path.as_deref().and_then(Path::parent), root) {
+                (Some(dir), Some(root)) => {
+                    load_environment(fs.as_ref(), dir, &root, settings.environment.as_deref())
+                        .await?
+                }
+                _ => Default::default(),
+            };
+            let variables = file.variables_with_environment(&environment);
+            let resolved = request.resolve(&variables)?;
+
+            let mut builder = Request::builder()
+                .method(resolved.method.as_str())
+                .uri(resolved.url.as_str());
+            for (name, value) in &resolved.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            let body = resolved.body.map_or_else(AsyncBody::empty, AsyncBody::from);
+            let request = builder.body(body)?;
+
+            let start = Instant::now();
+            let mut response = http_client
+                .send_with_redirect_policy(request, settings.follow_redirects)
+                .await?;
+            let mut body = Vec::new();
+            response.body_mut().read_to_end(&mut body).await?;
+            let elapsed = start.elapsed();
+
+            anyhow::Ok(HttpResponse {
+                status: response.status().as_u16(),
+                reason: response.status().canonical_reason(),
+                elapsed,
+                headers: response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        )
+                    })
+                    .collect(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            })
+        }
+        .await;
+
+        response_view.update(&mut cx, |view, cx| match result {
+            Ok(response) => view.set_response(response, cx),
+            Err(error) => view.set_error(error, cx),
+        })
+    })
+    .detach_and_log_err(cx);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashMap;
+use fs::Fs;
+use std::path::Path;
+
+/// Environment files are looked up next to the `.http` file and in each of its ancestors.
+pub const ENVIRONMENT_FILE_NAME: &str = "http-client.env.json";
+/// Variables from this file override the public one, so that secrets can be kept out of
+/// version control.
+pub const PRIVATE_ENVIRONMENT_FILE_NAME: &str = "http-client.private.env.json";
+
+/// Variables in this environment are available regardless of which environment is selected.
+const SHARED_ENVIRONMENT: &str = "$shared";
+
+/// Extracts the variables for the given environment from the contents of an environment
+/// file, which maps environment names to objects of variables.
+pub fn parse_environment(text: &str, environment: Option<&str>) -> Result<HashMap<String, String>> {
+    let json: serde_json::Value = serde_json::from_str(text)?;
+    let environments = json
+        .as_object()
+        .ok_or_else(|| anyhow!("expected an object mapping environment names to variables"))?;
+
+    let mut variables = HashMap::default();
+    for name in std::iter::once(SHARED_ENVIRONMENT).chain(environment) {
+        let Some(values) = environments.get(name) else {
+            continue;
+        };
+        let values = values
+            .as_object()
+            .ok_or_else(|| anyhow!("expected environment `{name}` to be an object"))?;
+        for (key, value) in values {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            variables.insert(key.clone(), value);
+        }
+    }
+    Ok(variables)
+}
+
+/// Loads the variables for `environment` from the environment files closest to `dir`,
+/// stopping at `root`.
+pub async fn load_environment(
+    fs: &dyn Fs,
+    dir: &Path,
+    root: &Path,
+    environment: Option<&str>,
+) -> Result<HashMap<String, String>> {
+    let mut variables = HashMap::default();
+    for ancestor in dir.ancestors() {
+        let public_path = ancestor.join(ENVIRONMENT_FILE_NAME);
+        let private_path = ancestor.join(PRIVATE_ENVIRONMENT_FILE_NAME);
+        let mut found = false;
+        for path in [public_path, private_path] {
+            if !fs.is_file(&path).await {
+                continue;
+            }
+            found = true;
+            let text = fs.load(&path).await?;
+            let environment_variables = parse_environment(&text, environment)
+                .with_context(|| format!("failed to parse {path:?}"))?;
+            variables.extend(environment_variables);
+        }
+        if found || ancestor == root {
+            break;
+        }
+    }
+    Ok(variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_environment() {
+        let text = indoc! {r#"
+            {
+                "$shared": { "version": "v1", "host": "https://example.com" },
+                "dev": { "host": "http://localhost:3000", "port": 3000 }
+            }
+        "#};
+
+        let variables = parse_environment(text, Some("dev")).unwrap();
+        assert_eq!(variables["host"], "http://localhost:3000");
+        assert_eq!(variables["version"], "v1");
+        assert_eq!(variables["port"], "3000");
+
+        let variables = parse_environment(text, None).unwrap();
+        assert_eq!(variables["host"], "https://example.com");
+        assert!(!variables.contains_key("port"));
+
+        assert!(parse_environment("[]", Some("dev")).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+use anyhow::{anyhow, Result};
+use collections::HashMap;
+use std::ops::Range;
+
+const METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// Variables may refer to other variables; this bounds how deep we follow them so that
+/// cycles produce an error instead of looping forever.
+const MAX_SUBSTITUTION_DEPTH: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Comment,
+    Separator,
+    Method,
+    Url,
+    HeaderName,
+    VariableName,
+    Variable,
+}
+
+/// A single request in a `.http` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// The name given after the `###` separator that introduces the request, if any.
+    pub name: Option<String>,
+    /// Byte range of the request line.
+    pub request_line: Range<usize>,
+    /// Byte range covering the request line, its headers and its body.
+    pub range: Range<usize>,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// A request whose `{{variable}}` references have all been substituted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// The parsed contents of a `.http` or `.rest` file, in the format shared by the
+/// JetBrains HTTP client and the VS Code REST Client extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HttpFile {
+    pub requests: Vec<HttpRequest>,
+    /// Variables declared with `@name = value`, in declaration order.
+    pub variables: Vec<(String, String)>,
+    /// Ranges used to highlight the file, sorted by start offset.
+    pub tokens: Vec<(Range<usize>, TokenKind)>,
+}
+
+#[derive(PartialEq)]
+enum Section {
+    /// Between requests, where only comments and variable declarations are expected.
+    Preamble,
+    Headers,
+    Body,
+}
+
+impl HttpFile {
+    pub fn parse(text: &str) -> Self {
+        let mut file = HttpFile::default();
+        let mut section = Section::Preamble;
+        let mut pending_name = None;
+        let mut current: Option<HttpRequest> = None;
+        let mut body = String::new();
+
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            let content = line.trim_end_matches(['\n', '\r']);
+            let trimmed = content.trim();
+            let indent = content.len() - content.trim_start().len();
+            let line_range = line_start + indent..line_start + content.trim_end().len();
+
+            if let Some(name) = trimmed.strip_prefix("###") {
+                finish_request(&mut file, current.take(), &mut body, line_start);
+                file.tokens.push((line_range, TokenKind::Separator));
+                let name = name.trim();
+                pending_name = (!name.is_empty()).then(|| name.to_string());
+                section = Section::Preamble;
+                continue;
+            }
+
+            match section {
+                Section::Preamble | Section::Headers
+                    if trimmed.starts_with('#') || trimmed.starts_with("//") =>
+                {
+                    file.tokens.push((line_range, TokenKind::Comment));
+                }
+                Section::Preamble => {
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    if let Some(declaration) = trimmed.strip_prefix('@') {
+                        if let Some((name, value)) = declaration.split_once('=') {
+                            let name = name.trim();
+                            let name_start = line_range.start;
+                            file.tokens.push((
+                                name_start..name_start + 1 + name.len(),
+                                TokenKind::VariableName,
+                            ));
+                            push_variable_tokens(&mut file.tokens, content, line_start);
+                            file.variables
+                                .push((name.to_string(), value.trim().to_string()));
+                            continue;
+                        }
+                    }
+
+                    let (method, url) = match trimmed.split_once(char::is_whitespace) {
+                        Some((method, rest)) if METHODS.contains(&method) => {
+                            file.tokens.push((
+                                line_range.start..line_range.start + method.len(),
+                                TokenKind::Method,
+                            ));
+                            (method.to_string(), rest.trim())
+                        }
+                        _ => ("GET".to_string(), trimmed),
+                    };
+                    let url = strip_http_version(url);
+                    if let Some(url_start) = content.find(url).filter(|_| !url.is_empty()) {
+                        let url_start = line_start + url_start;
+                        file.tokens
+                            .push((url_start..url_start + url.len(), TokenKind::Url));
+                    }
+                    push_variable_tokens(&mut file.tokens, content, line_start);
+
+                    current = Some(HttpRequest {
+                        name: pending_name.take(),
+                        request_line: line_range,
+                        range: line_start..line_start,
+                        method,
+                        url: url.to_string(),
+                        headers: Vec::new(),
+                        body: None,
+                    });
+                    section = Section::Headers;
+                }
+                Section::Headers => {
+                    let Some(request) = current.as_mut() else {
+                        continue;
+                    };
+                    if trimmed.is_empty() {
+                        section = Section::Body;
+                        continue;
+                    }
+                    // Long URLs can be split across lines that start with `?` or `&`.
+                    if request.headers.is_empty()
+                        && (trimmed.starts_with('?') || trimmed.starts_with('&'))
+                    {
+                        request.url.push_str(trimmed);
+                        file.tokens.push((line_range, TokenKind::Url));
+                        push_variable_tokens(&mut file.tokens, content, line_start);
+                        continue;
+                    }
+                    if let Some((name, value)) = trimmed.split_once(':') {
+                        let name = name.trim_end();
+                        file.tokens.push((
+                            line_range.start..line_range.start + name.len(),
+                            TokenKind::HeaderName,
+                        ));
+                        push_variable_tokens(&mut file.tokens, content, line_start);
+                        request
+                            .headers
+                            .push((name.to_string(), value.trim().to_string()));
+                    }
+                }
+                Section::Body => {
+                    push_variable_tokens(&mut file.tokens, content, line_start);
+                    body.push_str(content);
+                    body.push('\n');
+                }
+            }
+        }
+        finish_request(&mut file, current.take(), &mut body, text.len());
+
+        file.tokens.sort_by_key(|(range, _)| range.start);
+        file
+    }
+
+    /// Returns the request whose range contains the given byte offset.
+    pub fn request_at(&self, offset: usize) -> Option<&HttpRequest> {
+        self.requests
+            .iter()
+            .find(|request| request.range.start <= offset && offset <= request.range.end)
+    }
+
+    /// Combines environment variables with the variables declared in this file, which
+    /// take precedence.
+    pub fn variables_with_environment(
+        &self,
+        environment: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut variables = environment.clone();
+        variables.extend(self.variables.iter().cloned());
+        variables
+    }
+}
+
+impl HttpRequest {
+    pub fn resolve(&self, variables: &HashMap<String, String>) -> Result<ResolvedRequest> {
+        Ok(ResolvedRequest {
+            method: self.method.clone(),
+            url: substitute(&self.url, variables)?,
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    Ok((substitute(name, variables)?, substitute(value, variables)?))
+                })
+                .collect::<Result<_>>()?,
+            body: self
+                .body
+                .as_deref()
+                .map(|body| substitute(body, variables))
+                .transpose()?,
+        })
+    }
+
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{} {}", self.method, self.url))
+    }
+}
+
+/// Replaces every `{{name}}` in `text` with the value of the corresponding variable.
+pub fn substitute(text: &str, variables: &HashMap<String, String>) -> Result<String> {
+    substitute_at_depth(text, variables, 0)
+}
+
+fn substitute_at_depth(
+    text: &str,
+    variables: &HashMap<String, String>,
+    depth: usize,
+) -> Result<String> {
+    if depth > MAX_SUBSTITUTION_DEPTH {
+        return Err(anyhow!(
+            "variables are nested too deeply or refer to each other"
+        ));
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 2..start + end].trim();
+        let value = match name {
+            "$timestamp" => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs()
+                .to_string(),
+            "$guid" | "$uuid" => uuid::Uuid::new_v4().to_string(),
+            _ => {
+                let value = variables
+                    .get(name)
+                    .ok_or_else(|| anyhow!("undefined variable `{name}`"))?;
+                substitute_at_depth(value, variables, depth + 1)?
+            }
+        };
+        result.push_str(&value);
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn finish_request(
+    file: &mut HttpFile,
+    request: Option<HttpRequest>,
+    body: &mut String,
+    end: usize,
+) {
+    let body = std::mem::take(body);
+    let Some(mut request) = request else {
+        return;
+    };
+    let body = body.trim_end();
+    request.body = (!body.is_empty()).then(|| body.to_string());
+    request.range = request.request_line.start..end;
+    file.requests.push(request);
+}
+
+fn strip_http_version(url: &str) -> &str {
+    match url.rsplit_once(char::is_whitespace) {
+        Some((url, version)) if version.starts_with("HTTP/") => url.trim_end(),
+        _ => url,
+    }
+}
+
+fn push_variable_tokens(tokens: &mut Vec<(Range<usize>, TokenKind)>, line: &str, offset: usize) {
+    let mut search_start = 0;
+    while let Some(start) = line[search_start..].find("{{") {
+        let start = search_start + start;
+        let Some(end) = line[start..].find("}}") else {
+            break;
+        };
+        let end = start + end + 2;
+        tokens.push((offset + start..offset + end, TokenKind::Variable));
+        search_start = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_requests() {
+        let file = HttpFile::parse(indoc! {r#"
+            @host = https://example.com
+            @token = secret
+
+            ### List users
+            GET {{host}}/users
+                ?page=2
+            Accept: application/json
+
+            ###
+            # Create a user
+            POST {{host}}/users HTTP/1.1
+            Authorization: Bearer {{token}}
+            Content-Type: application/json
+
+            {
+                "name": "Ada"
+            }
+
+            ###
+            https://example.com/health
+        "#});
+
+        assert_eq!(
+            file.variables,
+            vec![
+                ("host".to_string(), "https://example.com".to_string()),
+                ("token".to_string(), "secret".to_string()),
+            ]
+        );
+        assert_eq!(file.requests.len(), 3);
+
+        let list = &file.requests[0];
+        assert_eq!(list.name.as_deref(), Some("List users"));
+        assert_eq!(list.method, "GET");
+        assert_eq!(list.url, "{{host}}/users?page=2");
+        assert_eq!(
+            list.headers,
+            vec![("Accept".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(list.body, None);
+
+        let create = &file.requests[1];
+        assert_eq!(create.name, None);
+        assert_eq!(create.method, "POST");
+        assert_eq!(create.url, "{{host}}/users");
+        assert_eq!(create.headers.len(), 2);
+        assert_eq!(create.body.as_deref(), Some("{\n    \"name\": \"Ada\"\n}"));
+
+        let health = &file.requests[2];
+        assert_eq!(health.method, "GET");
+        assert_eq!(health.url, "https://example.com/health");
+    }
+
+    #[test]
+    fn test_resolve_variables() {
+        let text = indoc! {"
+            @base = {{host}}/api
+            POST {{base}}/items
+            X-Env: {{env}}
+
+            {\"id\": 1}
+        "};
+        let file = HttpFile::parse(text);
+        let environment = HashMap::from_iter([
+            ("host".to_string(), "http://localhost:8080".to_string()),
+            ("env".to_string(), "dev".to_string()),
+            ("base".to_string(), "ignored".to_string()),
+        ]);
+        let variables = file.variables_with_environment(&environment);
+        let request = file.requests[0].resolve(&variables).unwrap();
+        assert_eq!(request.url, "http://localhost:8080/api/items");
+        assert_eq!(
+            request.headers,
+            vec![("X-Env".to_string(), "dev".to_string())]
+        );
+        assert_eq!(request.body.as_deref(), Some("{\"id\": 1}"));
+
+        let offset = text.find("X-Env").unwrap();
+        assert_eq!(file.request_at(offset), Some(&file.requests[0]));
+
+        let error = HttpFile::parse("GET {{missing}}/path").requests[0]
+            .resolve(&variables)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "undefined variable `missing`");
+
+        let cyclic = HashMap::from_iter([("a".to_string(), "{{a}}".to_string())]);
+        assert!(substitute("{{a}}", &cyclic).is_err());
+    }
+}
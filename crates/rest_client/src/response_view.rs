@@ -0,0 +1,221 @@
+use editor::Editor;
+use gpui::{
+    AppContext, EventEmitter, FocusHandle, FocusableView, Model, Render, SharedString, View,
+    ViewContext,
+};
+use language::{Buffer, LanguageRegistry};
+use std::{sync::Arc, time::Duration};
+use ui::{prelude::*, Divider};
+use workspace::item::{Item, ItemEvent};
+
+/// A response received for a request in a `.http` file.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub reason: Option<&'static str>,
+    pub elapsed: Duration,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn is_json(&self) -> bool {
+        self.headers.iter().any(|(name, value)| {
+            name.eq_ignore_ascii_case("content-type") && value.contains("json")
+        })
+    }
+
+    /// Returns the body, pretty-printed if it is JSON.
+    fn formatted_body(&self) -> String {
+        if self.is_json() {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&self.body) {
+                if let Ok(pretty) = serde_json::to_string_pretty(&json) {
+                    return pretty;
+                }
+            }
+        }
+        self.body.clone()
+    }
+}
+
+enum ResponseState {
+    Pending,
+    Received(HttpResponse),
+    Failed(SharedString),
+}
+
+/// Shows the status, headers and body of the most recently sent request.
+pub struct ResponseView {
+    request_name: SharedString,
+    state: ResponseState,
+    body_buffer: Model<Buffer>,
+    body_editor: View<Editor>,
+    language_registry: Arc<LanguageRegistry>,
+    focus_handle: FocusHandle,
+}
+
+impl ResponseView {
+    pub fn new(language_registry: Arc<LanguageRegistry>, cx: &mut ViewContext<Self>) -> Self {
+        let body_buffer = cx.new_model(|cx| Buffer::local("", cx));
+        let body_editor = cx.new_view(|cx| {
+            let mut editor = Editor::for_buffer(body_buffer.clone(), None, cx);
+            editor.set_read_only(true);
+            editor.set_show_inline_completions(Some(false), cx);
+            editor
+        });
+        Self {
+            request_name: SharedString::default(),
+            state: ResponseState::Pending,
+            body_buffer,
+            body_editor,
+            language_registry,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn set_pending(&mut self, request_name: String, cx: &mut ViewContext<Self>) {
+        self.request_name = request_name.into();
+        self.state = ResponseState::Pending;
+        self.set_body(String::new(), false, cx);
+        cx.emit(ItemEvent::UpdateTab);
+        cx.notify();
+    }
+
+    pub fn set_response(&mut self, response: HttpResponse, cx: &mut ViewContext<Self>) {
+        self.set_body(response.formatted_body(), response.is_json(), cx);
+        self.state = ResponseState::Received(response);
+        cx.notify();
+    }
+
+    pub fn set_error(&mut self, error: anyhow::Error, cx: &mut ViewContext<Self>) {
+        self.state = ResponseState::Failed(format!("{error:#}").into());
+        cx.notify();
+    }
+
+    fn set_body(&mut self, body: String, is_json: bool, cx: &mut ViewContext<Self>) {
+        self.body_buffer.update(cx, |buffer, cx| {
+            buffer.set_text(body, cx);
+            if !is_json {
+                buffer.set_language(None, cx);
+            }
+        });
+        if is_json {
+            let language = self.language_registry.language_for_name("JSON");
+            let buffer = self.body_buffer.downgrade();
+            cx.spawn(|_, mut cx| async move {
+                let language = language.await?;
+                buffer.update(&mut cx, |buffer, cx| {
+                    buffer.set_language(Some(language), cx)
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    fn render_summary(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let summary = match &self.state {
+            ResponseState::Pending => Label::new("Sending request…")
+                .color(Color::Muted)
+                .into_any_element(),
+            ResponseState::Failed(error) => Label::new(error.clone())
+                .color(Color::Error)
+                .into_any_element(),
+            ResponseState::Received(response) => {
+                let status_color = match response.status {
+                    200..=299 => Color::Success,
+                    300..=399 => Color::Info,
+                    _ => Color::Error,
+                };
+                let status = match response.reason {
+                    Some(reason) => format!("{} {}", response.status, reason),
+                    None => response.status.to_string(),
+                };
+                v_flex()
+                    .gap_2()
+                    .child(
+                        h_flex()
+                            .gap_3()
+                            .child(Label::new(status).color(status_color))
+                            .child(
+                                Label::new(format!("{} ms", response.elapsed.as_millis()))
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new(format_size(response.body.len())).color(Color::Muted),
+                            ),
+                    )
+                    .child(Divider::horizontal())
+                    .children(response.headers.iter().map(|(name, value)| {
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Label::new(format!("{name}:"))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(value.clone()).size(LabelSize::Small))
+                    }))
+                    .into_any_element()
+            }
+        };
+        div()
+            .p_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(summary)
+    }
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024. * 1024.))
+    }
+}
+
+impl Render for ResponseView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("ResponseView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_summary(cx))
+            .child(div().flex_1().child(self.body_editor.clone()))
+    }
+}
+
+impl EventEmitter<ItemEvent> for ResponseView {}
+
+impl FocusableView for ResponseView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ResponseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _cx: &WindowContext) -> Option<SharedString> {
+        if self.request_name.is_empty() {
+            Some("Response".into())
+        } else {
+            Some(format!("Response: {}", self.request_name).into())
+        }
+    }
+
+    fn tab_icon(&self, _cx: &WindowContext) -> Option<Icon> {
+        Some(Icon::new(IconName::Public))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -0,0 +1,20 @@
+mod editor_requests;
+mod environment;
+mod http_file;
+mod response_view;
+mod rest_client_settings;
+
+use gpui::{actions, AppContext};
+use settings::Settings;
+
+pub use environment::{parse_environment, ENVIRONMENT_FILE_NAME, PRIVATE_ENVIRONMENT_FILE_NAME};
+pub use http_file::{HttpFile, HttpRequest, ResolvedRequest};
+pub use response_view::{HttpResponse, ResponseView};
+pub use rest_client_settings::RestClientSettings;
+
+actions!(rest_client, [SendRequest]);
+
+pub fn init(cx: &mut AppContext) {
+    RestClientSettings::register(cx);
+    cx.observe_new_views(editor_requests::register).detach();
+}
@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RestClientSettings {
+    pub enabled: bool,
+    pub environment: Option<String>,
+    pub follow_redirects: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct RestClientSettingsContent {
+    /// Whether to show "Send Request" buttons above requests in `.http` and `.rest` files.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The environment from `http-client.env.json` to take variables from. Variables in
+    /// the `$shared` environment are always available.
+    ///
+    /// Default: null
+    pub environment: Option<String>,
+    /// Whether to follow redirects when sending requests.
+    ///
+    /// Default: true
+    pub follow_redirects: Option<bool>,
+}
+
+impl Settings for RestClientSettings {
+    const KEY: Option<&'static str> = Some("rest_client");
+
+    type FileContent = RestClientSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
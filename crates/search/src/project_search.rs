@@ -1,4 +1,5 @@
 use crate::{
+    saved_searches::{SavedSearch, SavedSearches},
     FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOptions,
     SelectNextMatch, SelectPrevMatch, ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex,
     ToggleReplace, ToggleWholeWord,
@@ -13,10 +14,11 @@ use editor::{
 };
 use futures::StreamExt;
 use gpui::{
-    actions, div, Action, AnyElement, AnyView, AppContext, Context as _, EntityId, EventEmitter,
-    FocusHandle, FocusableView, Global, Hsla, InteractiveElement, IntoElement, KeyContext, Model,
-    ModelContext, ParentElement, Point, Render, SharedString, Styled, Subscription, Task,
-    TextStyle, UpdateGlobal, View, ViewContext, VisualContext, WeakModel, WeakView, WindowContext,
+    actions, div, Action, AnchorCorner, AnyElement, AnyView, AppContext, Context as _, EntityId,
+    EventEmitter, FocusHandle, FocusableView, Global, Hsla, InteractiveElement, IntoElement,
+    KeyContext, Model, ModelContext, ParentElement, Point, Render, SharedString, Styled,
+    Subscription, Task, TextStyle, UpdateGlobal, View, ViewContext, VisualContext, WeakModel,
+    WeakView, WindowContext,
 };
 use language::Buffer;
 use menu::Confirm;
@@ -34,8 +36,8 @@ use std::{
 };
 use theme::ThemeSettings;
 use ui::{
-    h_flex, prelude::*, v_flex, Icon, IconButton, IconName, KeyBinding, Label, LabelCommon,
-    LabelSize, Selectable, Tooltip,
+    h_flex, prelude::*, v_flex, ContextMenu, Icon, IconButton, IconName, KeyBinding, Label,
+    LabelCommon, LabelSize, PopoverMenu, Selectable, Tooltip,
 };
 use util::paths::PathMatcher;
 use workspace::{
@@ -47,10 +49,18 @@ use workspace::{
 
 const MIN_INPUT_WIDTH_REMS: f32 = 15.;
 const MAX_INPUT_WIDTH_REMS: f32 = 30.;
+/// How many lines the query editor grows to before it scrolls, for multiline queries.
+const MAX_QUERY_LINES: usize = 8;
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        ToggleFocus,
+        NextField,
+        ToggleFilters,
+        SaveSearch
+    ]
 );
 
 #[derive(Default)]
@@ -67,6 +77,9 @@ pub fn init(cx: &mut AppContext) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleFilters, cx| {
             search_bar.toggle_filters(cx);
         });
+        register_workspace_action(workspace, move |search_bar, _: &SaveSearch, cx| {
+            search_bar.save_search(cx);
+        });
         register_workspace_action(workspace, move |search_bar, _: &ToggleCaseSensitive, cx| {
             search_bar.toggle_search_option(SearchOptions::CASE_SENSITIVE, cx);
         });
@@ -599,6 +612,40 @@ impl ProjectSearchView {
         });
     }
 
+    fn saved_search(&self, cx: &WindowContext) -> Option<SavedSearch> {
+        let query = self.search_query_text(cx);
+        if query.is_empty() {
+            return None;
+        }
+        Some(SavedSearch::new(
+            query,
+            self.included_files_editor.read(cx).text(cx),
+            self.excluded_files_editor.read(cx).text(cx),
+            self.search_options,
+        ))
+    }
+
+    /// Fills in the query, filters and options of a saved search, and runs it.
+    fn apply_saved_search(&mut self, saved_search: &SavedSearch, cx: &mut ViewContext<Self>) {
+        self.set_search_editor(SearchInputKind::Query, &saved_search.query, cx);
+        self.set_search_editor(SearchInputKind::Include, &saved_search.files_to_include, cx);
+        self.set_search_editor(SearchInputKind::Exclude, &saved_search.files_to_exclude, cx);
+        self.search_options = saved_search.options();
+        ActiveSettings::update_global(cx, |settings, cx| {
+            settings.0.insert(
+                self.model.read(cx).project.downgrade(),
+                self.current_settings(),
+            );
+        });
+        let has_filters = !saved_search.files_to_include.trim().is_empty()
+            || !saved_search.files_to_exclude.trim().is_empty();
+        if has_filters != self.filters_enabled {
+            self.toggle_filters(cx);
+        }
+        self.search(cx);
+        cx.notify();
+    }
+
     fn toggle_opened_only(&mut self, _cx: &mut ViewContext<Self>) {
         self.included_opened_only = !self.included_opened_only;
     }
@@ -685,8 +732,9 @@ impl ProjectSearchView {
         }
         subscriptions.push(cx.observe(&model, |this, _, cx| this.model_changed(cx)));
 
+        // The query editor grows with multiline queries, such as blocks of code pasted into it.
         let query_editor = cx.new_view(|cx| {
-            let mut editor = Editor::single_line(cx);
+            let mut editor = Editor::auto_height(MAX_QUERY_LINES, cx);
             editor.set_placeholder_text("Search all files..", cx);
             editor.set_text(query_text, cx);
             editor
@@ -1428,6 +1476,69 @@ impl ProjectSearchBar {
         }
     }
 
+    fn save_search(&mut self, cx: &mut ViewContext<Self>) {
+        let saved_search = self
+            .active_project_search
+            .as_ref()
+            .and_then(|search_view| search_view.read(cx).saved_search(cx));
+        if let Some(saved_search) = saved_search {
+            SavedSearches::save(saved_search, cx);
+            cx.notify();
+        }
+    }
+
+    fn render_saved_searches_menu(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let search_bar = cx.view().downgrade();
+        let search_view = self
+            .active_project_search
+            .as_ref()
+            .map(|search_view| search_view.downgrade());
+        PopoverMenu::new("project-search-saved-searches")
+            .trigger(
+                IconButton::new("project-search-saved-searches-button", IconName::Star)
+                    .tooltip(|cx| Tooltip::text("Saved searches", cx)),
+            )
+            .anchor(AnchorCorner::TopRight)
+            .menu(move |cx| {
+                let search_view = search_view.clone()?.upgrade()?;
+                let current_search = search_view.read(cx).saved_search(cx);
+                let saved_searches = SavedSearches::get(cx).to_vec();
+                let search_bar = search_bar.clone();
+                Some(ContextMenu::build(cx, move |mut menu, _| {
+                    for saved_search in saved_searches.iter().cloned() {
+                        let search_view = search_view.downgrade();
+                        menu = menu.entry(saved_search.name.clone(), None, move |cx| {
+                            search_view
+                                .update(cx, |search_view, cx| {
+                                    search_view.apply_saved_search(&saved_search, cx)
+                                })
+                                .ok();
+                        });
+                    }
+                    if !saved_searches.is_empty() {
+                        menu = menu.separator();
+                    }
+
+                    let current_name = current_search.map(|search| search.name);
+                    let is_current_saved = current_name.as_ref().map_or(false, |name| {
+                        saved_searches
+                            .iter()
+                            .any(|saved_search| saved_search.name == *name)
+                    });
+                    match current_name {
+                        Some(name) if is_current_saved => {
+                            menu.entry(format!("Remove \"{name}\""), None, move |cx| {
+                                SavedSearches::remove(&name, cx);
+                                search_bar.update(cx, |_, cx| cx.notify()).ok();
+                            })
+                        }
+                        Some(_) => menu.action("Save Current Search", SaveSearch.boxed_clone()),
+                        None => menu,
+                    }
+                }))
+            })
+    }
+
     fn is_option_enabled(&self, option: SearchOptions, cx: &AppContext) -> bool {
         if let Some(search) = self.active_project_search.as_ref() {
             search.read(cx).search_options.contains(option)
@@ -1574,7 +1685,7 @@ impl Render for ProjectSearchBar {
 
         let query_column = h_flex()
             .flex_1()
-            .h_8()
+            .min_h_8()
             .mr_2()
             .px_2()
             .py_1()
@@ -1637,7 +1748,8 @@ impl Render for ProjectSearchBar {
                                 .unwrap_or_default(),
                         )
                         .tooltip(|cx| Tooltip::for_action("Toggle replace", &ToggleReplace, cx)),
-                ),
+                )
+                .child(self.render_saved_searches_menu(cx)),
         );
 
         let limit_reached = search.model.read(cx).limit_reached;
@@ -2104,6 +2216,57 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_multiline_query_and_saved_search(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "fn one() {\n    two();\n}\n",
+                "two.rs": "fn two() {\n    one();\n}\n",
+                "three.rs": "fn three() {\n    two();\n    one();\n}\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let window = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new_model(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), cx, None)
+        });
+
+        perform_search(search_view, "{\n    two();", cx);
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(search_view.model.read(cx).match_ranges.len(), 2);
+
+                let saved_search = search_view.saved_search(cx).unwrap();
+                assert_eq!(saved_search.name, "{…");
+                SavedSearches::save(saved_search, cx);
+            })
+            .unwrap();
+
+        perform_search(search_view, "three", cx);
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(search_view.model.read(cx).match_ranges.len(), 1);
+
+                let saved_search = SavedSearches::get(cx)[0].clone();
+                search_view.apply_saved_search(&saved_search, cx);
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(search_view.search_query_text(cx), "{\n    two();");
+                assert_eq!(search_view.model.read(cx).match_ranges.len(), 2);
+            })
+            .unwrap();
+    }
+
     #[gpui::test]
     async fn test_deploy_project_search_focus(cx: &mut TestAppContext) {
         init_test(cx);
@@ -0,0 +1,145 @@
+//! Named project searches, kept across restarts so they can be recalled from the project search
+//! bar.
+
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{AppContext, Global};
+use serde::{Deserialize, Serialize};
+use std::mem;
+use util::ResultExt;
+
+use crate::SearchOptions;
+
+const SAVED_SEARCHES_KEY: &str = "project_search_saved_searches";
+
+/// The longest a saved search's name can be before it's truncated.
+const MAX_NAME_LEN: usize = 40;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    pub files_to_include: String,
+    pub files_to_exclude: String,
+    #[serde(default)]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub include_ignored: bool,
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl SavedSearch {
+    pub fn new(
+        query: String,
+        files_to_include: String,
+        files_to_exclude: String,
+        options: SearchOptions,
+    ) -> Self {
+        Self {
+            name: Self::name_for(&query, &files_to_include),
+            query,
+            files_to_include,
+            files_to_exclude,
+            whole_word: options.contains(SearchOptions::WHOLE_WORD),
+            case_sensitive: options.contains(SearchOptions::CASE_SENSITIVE),
+            include_ignored: options.contains(SearchOptions::INCLUDE_IGNORED),
+            regex: options.contains(SearchOptions::REGEX),
+        }
+    }
+
+    /// Names a search after the first line of its query, and the files it's limited to.
+    fn name_for(query: &str, files_to_include: &str) -> String {
+        let first_line = query.lines().next().unwrap_or_default().trim();
+        let mut name = if first_line.chars().count() > MAX_NAME_LEN {
+            let truncated = first_line.chars().take(MAX_NAME_LEN).collect::<String>();
+            format!("{truncated}…")
+        } else if query.trim().contains('\n') {
+            format!("{first_line}…")
+        } else {
+            first_line.to_string()
+        };
+        let files_to_include = files_to_include.trim();
+        if !files_to_include.is_empty() {
+            name.push_str(" in ");
+            name.push_str(files_to_include);
+        }
+        name
+    }
+
+    pub fn options(&self) -> SearchOptions {
+        let mut options = SearchOptions::NONE;
+        options.set(SearchOptions::WHOLE_WORD, self.whole_word);
+        options.set(SearchOptions::CASE_SENSITIVE, self.case_sensitive);
+        options.set(SearchOptions::INCLUDE_IGNORED, self.include_ignored);
+        options.set(SearchOptions::REGEX, self.regex);
+        options
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SavedSearches(Vec<SavedSearch>);
+
+impl Global for SavedSearches {}
+
+impl SavedSearches {
+    pub fn init(cx: &mut AppContext) {
+        cx.set_global(Self::default());
+        cx.spawn(|mut cx| async move {
+            let saved_searches = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(SAVED_SEARCHES_KEY) })
+                .await
+                .log_err()
+                .flatten()
+                .and_then(|json| serde_json::from_str::<Vec<SavedSearch>>(&json).log_err());
+            if let Some(saved_searches) = saved_searches {
+                cx.update(|cx| {
+                    // Keep the searches that were saved while these were loading.
+                    let newly_saved =
+                        mem::replace(&mut cx.default_global::<Self>().0, saved_searches);
+                    for search in newly_saved {
+                        Self::save(search, cx);
+                    }
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    pub fn get(cx: &AppContext) -> &[SavedSearch] {
+        cx.try_global::<Self>()
+            .map_or(&[], |saved_searches| saved_searches.0.as_slice())
+    }
+
+    /// Saves the given search, replacing the one with the same name if there is one.
+    pub fn save(search: SavedSearch, cx: &mut AppContext) {
+        let saved_searches = &mut cx.default_global::<Self>().0;
+        saved_searches.retain(|saved_search| saved_search.name != search.name);
+        saved_searches.push(search);
+        Self::persist(cx);
+    }
+
+    pub fn remove(name: &str, cx: &mut AppContext) {
+        cx.default_global::<Self>()
+            .0
+            .retain(|saved_search| saved_search.name != name);
+        Self::persist(cx);
+    }
+
+    fn persist(cx: &mut AppContext) {
+        let Some(json) = serde_json::to_string(&Self::get(cx)).log_err() else {
+            return;
+        };
+        cx.background_executor()
+            .spawn(async move {
+                KEY_VALUE_STORE
+                    .write_kvp(SAVED_SEARCHES_KEY.to_string(), json)
+                    .await
+                    .log_err();
+            })
+            .detach();
+    }
+}
@@ -11,12 +11,14 @@ use workspace::{Toast, Workspace};
 
 pub mod buffer_search;
 pub mod project_search;
+pub(crate) mod saved_searches;
 pub(crate) mod search_bar;
 
 pub fn init(cx: &mut AppContext) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    saved_searches::SavedSearches::init(cx);
 }
 
 actions!(
@@ -173,6 +173,8 @@ impl WorktreeIndex {
         mut cx: AsyncAppContext,
     ) -> Result<()> {
         let is_auto_available = cx.update(|cx| cx.wait_for_flag::<AutoCommand>())?.await;
+        // Indexing is expensive, so it's paused while the machine should conserve power.
+        cx.update(|cx| cx.wait_while_conserving_power())?.await;
         let index = this.update(&mut cx, |this, cx| {
             futures::future::try_join(
                 this.embedding_index.index_entries_changed_on_disk(cx),
@@ -183,6 +185,7 @@ impl WorktreeIndex {
         index.await.log_err();
 
         while let Ok(updated_entries) = updated_entries.recv().await {
+            cx.update(|cx| cx.wait_while_conserving_power())?.await;
             let is_auto_available = cx
                 .update(|cx| cx.has_flag::<AutoCommand>())
                 .unwrap_or(false);
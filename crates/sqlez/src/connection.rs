@@ -50,6 +50,12 @@ impl Connection {
         Self::open(uri, true).unwrap_or_else(|_| Self::open_memory(Some(uri)))
     }
 
+    /// Opens the database at uri, returning an error rather than falling back to an
+    /// in-memory database if it cannot be opened.
+    pub fn try_open_file(uri: &str) -> Result<Self> {
+        Self::open(uri, true)
+    }
+
     pub fn open_memory(uri: Option<&str>) -> Self {
         let in_memory_path = if let Some(uri) = uri {
             format!("file:{}?mode=memory&cache=shared", uri)
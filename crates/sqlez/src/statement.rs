@@ -258,6 +258,21 @@ impl<'a> Statement<'a> {
         }
     }
 
+    pub fn column_count(&mut self) -> i32 {
+        unsafe { sqlite3_column_count(self.current_statement()) }
+    }
+
+    pub fn column_name(&mut self, index: i32) -> Result<&str> {
+        let name = unsafe {
+            let pointer = sqlite3_column_name(self.current_statement(), index);
+            if pointer.is_null() {
+                return Err(anyhow!("No column at index {index}"));
+            }
+            CStr::from_ptr(pointer)
+        };
+        Ok(name.to_str()?)
+    }
+
     pub fn with_bindings(&mut self, bindings: &impl Bind) -> Result<&mut Self> {
         self.bind(bindings, 1)?;
         Ok(self)
@@ -459,6 +474,22 @@ mod test {
         assert_eq!(read.step().unwrap(), StepResult::Done);
     }
 
+    #[test]
+    fn column_names() {
+        let connection = Connection::open_memory(Some("column_names"));
+        connection
+            .exec("CREATE TABLE people (id INTEGER, name TEXT)")
+            .unwrap()()
+        .unwrap();
+
+        let mut statement =
+            Statement::prepare(&connection, "SELECT id, name AS full_name FROM people").unwrap();
+        assert_eq!(statement.column_count(), 2);
+        assert_eq!(statement.column_name(0).unwrap(), "id");
+        assert_eq!(statement.column_name(1).unwrap(), "full_name");
+        assert!(statement.column_name(2).is_err());
+    }
+
     #[test]
     pub fn maybe_returns_options() {
         let connection = Connection::open_memory(Some("maybe_returns_options"));
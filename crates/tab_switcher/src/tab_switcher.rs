@@ -2,6 +2,7 @@
 mod tab_switcher_tests;
 
 use collections::HashMap;
+use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
     actions, impl_actions, rems, Action, AnyElement, AppContext, DismissEvent, EntityId,
     EventEmitter, FocusHandle, FocusableView, Modifiers, ModifiersChangedEvent, MouseButton,
@@ -15,7 +16,7 @@ use util::ResultExt;
 use workspace::{
     item::{ItemHandle, TabContentParams},
     pane::{render_item_indicator, tab_details, Event as PaneEvent},
-    ModalView, Pane, SaveIntent, Workspace,
+    ModalView, Pane, SaveIntent, ToggleTabSearch, Workspace,
 };
 
 const PANEL_WIDTH_REMS: f32 = 28.;
@@ -44,7 +45,7 @@ impl TabSwitcher {
     fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
         workspace.register_action(|workspace, action: &Toggle, cx| {
             let Some(tab_switcher) = workspace.active_modal::<Self>(cx) else {
-                Self::open(action, workspace, cx);
+                Self::open(action, false, workspace, cx);
                 return;
             };
 
@@ -54,9 +55,17 @@ impl TabSwitcher {
                     .update(cx, |picker, cx| picker.cycle_selection(cx))
             });
         });
+        workspace.register_action(|workspace, _: &ToggleTabSearch, cx| {
+            Self::open(&Toggle::default(), true, workspace, cx);
+        });
     }
 
-    fn open(action: &Toggle, workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    fn open(
+        action: &Toggle,
+        searchable: bool,
+        workspace: &mut Workspace,
+        cx: &mut ViewContext<Workspace>,
+    ) {
         let mut weak_pane = workspace.active_pane().downgrade();
         for dock in [
             workspace.left_dock(),
@@ -78,14 +87,23 @@ impl TabSwitcher {
 
         workspace.toggle_modal(cx, |cx| {
             let delegate = TabSwitcherDelegate::new(action, cx.view().downgrade(), weak_pane, cx);
-            TabSwitcher::new(delegate, cx)
+            TabSwitcher::new(delegate, searchable, cx)
         });
     }
 
-    fn new(delegate: TabSwitcherDelegate, cx: &mut ViewContext<Self>) -> Self {
-        Self {
-            picker: cx.new_view(|cx| Picker::nonsearchable_uniform_list(delegate, cx)),
-            init_modifiers: cx.modifiers().modified().then_some(cx.modifiers()),
+    fn new(delegate: TabSwitcherDelegate, searchable: bool, cx: &mut ViewContext<Self>) -> Self {
+        // When searching, the modal stays open after the modifiers are released, so that a query
+        // can be typed.
+        if searchable {
+            Self {
+                picker: cx.new_view(|cx| Picker::uniform_list(delegate, cx)),
+                init_modifiers: None,
+            }
+        } else {
+            Self {
+                picker: cx.new_view(|cx| Picker::nonsearchable_uniform_list(delegate, cx)),
+                init_modifiers: cx.modifiers().modified().then_some(cx.modifiers()),
+            }
         }
     }
 
@@ -148,6 +166,7 @@ pub struct TabSwitcherDelegate {
     selected_index: usize,
     pane: WeakView<Pane>,
     matches: Vec<TabMatch>,
+    query: String,
 }
 
 impl TabSwitcherDelegate {
@@ -164,6 +183,7 @@ impl TabSwitcherDelegate {
             selected_index: 0,
             pane,
             matches: Vec::new(),
+            query: String::new(),
         }
     }
 
@@ -176,6 +196,10 @@ impl TabSwitcherDelegate {
                 PaneEvent::AddItem { .. }
                 | PaneEvent::RemovedItem { .. }
                 | PaneEvent::Remove { .. } => tab_switcher.picker.update(cx, |picker, cx| {
+                    if !picker.delegate.query.is_empty() {
+                        picker.refresh(cx);
+                        return;
+                    }
                     let selected_item_id = picker.delegate.selected_item_id();
                     picker.delegate.update_matches(cx);
                     if let Some(item_id) = selected_item_id {
@@ -273,7 +297,7 @@ impl PickerDelegate for TabSwitcherDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
-        Arc::default()
+        "Search open tabs…".into()
     }
 
     fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
@@ -299,11 +323,59 @@ impl PickerDelegate for TabSwitcherDelegate {
 
     fn update_matches(
         &mut self,
-        _raw_query: String,
+        raw_query: String,
         cx: &mut ViewContext<Picker<Self>>,
     ) -> Task<()> {
+        self.query = raw_query.trim().to_string();
         self.update_matches(cx);
-        Task::ready(())
+        if self.query.is_empty() {
+            return Task::ready(());
+        }
+
+        // Tabs are matched by their path when they have one, so that their directories can be
+        // searched too.
+        let candidates = self
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(id, tab_match)| {
+                let text = tab_match
+                    .item
+                    .tab_tooltip_text(cx)
+                    .or_else(|| tab_match.item.tab_description(tab_match.detail, cx))
+                    .or_else(|| tab_match.item.tab_content_text(cx))
+                    .unwrap_or_default();
+                StringMatchCandidate::new(id, text.to_string())
+            })
+            .collect::<Vec<_>>();
+        let query = self.query.clone();
+        let executor = cx.background_executor().clone();
+        cx.spawn(|picker, mut cx| async move {
+            let string_matches = match_strings(
+                &candidates,
+                &query,
+                true,
+                candidates.len(),
+                &Default::default(),
+                executor,
+            )
+            .await;
+
+            picker
+                .update(&mut cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    let mut tab_matches = delegate.matches.drain(..).map(Some).collect::<Vec<_>>();
+                    delegate.matches = string_matches
+                        .into_iter()
+                        .filter_map(|string_match| {
+                            tab_matches.get_mut(string_match.candidate_id)?.take()
+                        })
+                        .collect();
+                    delegate.selected_index = 0;
+                    cx.notify();
+                })
+                .log_err();
+        })
     }
 
     fn confirm(&mut self, _secondary: bool, cx: &mut ViewContext<Picker<TabSwitcherDelegate>>) {
@@ -5,7 +5,7 @@ use menu::SelectPrev;
 use project::{Project, ProjectPath};
 use serde_json::json;
 use std::path::Path;
-use workspace::{AppState, Workspace};
+use workspace::{AppState, ToggleTabSearch, Workspace};
 
 #[ctor::ctor]
 fn init_logger() {
@@ -228,6 +228,63 @@ async fn test_close_selected_item(cx: &mut gpui::TestAppContext) {
     assert_tab_switcher_is_closed(workspace, cx);
 }
 
+#[gpui::test]
+async fn test_search_open_tabs(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "one.txt": "First file",
+                "two.txt": "Second file",
+                "three.txt": "Third file",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+    let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+
+    let tab_1 = open_buffer("one.txt", &workspace, cx).await;
+    let _tab_2 = open_buffer("two.txt", &workspace, cx).await;
+    let tab_3 = open_buffer("three.txt", &workspace, cx).await;
+
+    // Stays open when modifiers are released, so that a query can be typed
+    cx.simulate_modifiers_change(Modifiers::control());
+    cx.dispatch_action(ToggleTabSearch);
+    cx.simulate_modifiers_change(Modifiers::none());
+    let tab_switcher = get_active_tab_switcher(&workspace, cx);
+    tab_switcher.update(cx, |tab_switcher, _| {
+        assert_eq!(tab_switcher.delegate.matches.len(), 3);
+    });
+
+    tab_switcher.update(cx, |tab_switcher, cx| {
+        tab_switcher.update_matches("ne".to_string(), cx)
+    });
+    cx.run_until_parked();
+    tab_switcher.update(cx, |tab_switcher, _| {
+        assert_eq!(tab_switcher.delegate.matches.len(), 1);
+        assert_match_selection(tab_switcher, 0, tab_1.boxed_clone());
+    });
+
+    tab_switcher.update(cx, |tab_switcher, cx| {
+        tab_switcher.update_matches("thr".to_string(), cx)
+    });
+    cx.run_until_parked();
+    tab_switcher.update(cx, |tab_switcher, _| {
+        assert_eq!(tab_switcher.delegate.matches.len(), 1);
+        assert_match_selection(tab_switcher, 0, tab_3.boxed_clone());
+    });
+
+    cx.dispatch_action(menu::Confirm);
+    cx.read(|cx| {
+        let active_editor = workspace.read(cx).active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "three.txt");
+    });
+}
+
 fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
     cx.update(|cx| {
         let state = AppState::test(cx);
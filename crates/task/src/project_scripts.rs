@@ -0,0 +1,348 @@
+//! Scripts defined by the project's build tools — npm scripts, Cargo aliases and binaries,
+//! Makefile targets and justfile recipes — turned into task templates.
+
+use std::path::Path;
+
+use anyhow::Context;
+use collections::HashSet;
+use serde::Deserialize;
+
+use crate::{TaskTemplate, TaskTemplates};
+
+/// A tag attached to every template created from a project script,
+/// to tell them apart from the tasks defined in task files.
+pub const SCRIPT_TAG: &str = "project-script";
+
+/// A file that defines scripts which can be run as tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptFile {
+    /// `package.json`, with its `scripts` run through npm.
+    PackageJson,
+    /// `Cargo.toml`, with its `[[bin]]` targets run through `cargo run`.
+    CargoManifest,
+    /// `.cargo/config.toml`, with its `[alias]` commands.
+    CargoConfig,
+    /// A Makefile, with its targets run through make.
+    Makefile,
+    /// A justfile, with its recipes run through just.
+    Justfile,
+}
+
+impl ScriptFile {
+    /// Determines which kind of script file is at the given path, if any.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "package.json" => Some(Self::PackageJson),
+            "Cargo.toml" => Some(Self::CargoManifest),
+            "config.toml" | "config"
+                if path
+                    .parent()
+                    .and_then(|parent| parent.file_name())
+                    .map_or(false, |parent| parent == ".cargo") =>
+            {
+                Some(Self::CargoConfig)
+            }
+            "Makefile" | "makefile" | "GNUmakefile" => Some(Self::Makefile),
+            "justfile" | "Justfile" | ".justfile" => Some(Self::Justfile),
+            _ => None,
+        }
+    }
+
+    /// A base for the ids of the tasks created from this kind of file.
+    pub fn id_base(&self) -> &'static str {
+        match self {
+            Self::PackageJson => "package_json_scripts",
+            Self::CargoManifest => "cargo_manifest_scripts",
+            Self::CargoConfig => "cargo_config_scripts",
+            Self::Makefile => "makefile_scripts",
+            Self::Justfile => "justfile_scripts",
+        }
+    }
+
+    /// The directory scripts from the file at the given path should be run in.
+    pub fn working_directory<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        match self {
+            Self::CargoConfig => path.parent()?.parent(),
+            _ => path.parent(),
+        }
+    }
+
+    /// Parses the file's contents into task templates, one per script, that run in `cwd`.
+    pub fn scripts(&self, contents: &str, cwd: &Path) -> anyhow::Result<TaskTemplates> {
+        let scripts = match self {
+            Self::PackageJson => package_json_scripts(contents)?,
+            Self::CargoManifest => cargo_binaries(contents)?,
+            Self::CargoConfig => cargo_aliases(contents)?,
+            Self::Makefile => makefile_targets(contents),
+            Self::Justfile => justfile_recipes(contents),
+        };
+        let cwd = cwd.to_string_lossy().into_owned();
+        Ok(TaskTemplates(
+            scripts
+                .into_iter()
+                .map(|(label, command, args)| TaskTemplate {
+                    label,
+                    command: command.to_owned(),
+                    args,
+                    cwd: Some(cwd.clone()),
+                    tags: vec![SCRIPT_TAG.to_owned()],
+                    ..TaskTemplate::default()
+                })
+                .collect(),
+        ))
+    }
+}
+
+type Script = (String, &'static str, Vec<String>);
+
+fn package_json_scripts(contents: &str) -> anyhow::Result<Vec<Script>> {
+    #[derive(Deserialize)]
+    struct PackageJson {
+        #[serde(default)]
+        scripts: serde_json_lenient::Map<String, serde_json_lenient::Value>,
+    }
+
+    let package_json: PackageJson =
+        serde_json_lenient::from_str(contents).context("parsing package.json")?;
+    Ok(package_json
+        .scripts
+        .into_iter()
+        .map(|(name, _)| (format!("npm: {name}"), "npm", vec!["run".to_owned(), name]))
+        .collect())
+}
+
+fn cargo_binaries(contents: &str) -> anyhow::Result<Vec<Script>> {
+    #[derive(Deserialize)]
+    struct Manifest {
+        #[serde(default)]
+        bin: Vec<Binary>,
+    }
+    #[derive(Deserialize)]
+    struct Binary {
+        name: Option<String>,
+    }
+
+    let manifest: Manifest = toml::from_str(contents).context("parsing Cargo.toml")?;
+    Ok(manifest
+        .bin
+        .into_iter()
+        .filter_map(|binary| binary.name)
+        .map(|name| {
+            (
+                format!("cargo: run --bin {name}"),
+                "cargo",
+                vec!["run".to_owned(), "--bin".to_owned(), name],
+            )
+        })
+        .collect())
+}
+
+fn cargo_aliases(contents: &str) -> anyhow::Result<Vec<Script>> {
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(default)]
+        alias: toml::Table,
+    }
+
+    let config: Config = toml::from_str(contents).context("parsing Cargo config")?;
+    Ok(config
+        .alias
+        .into_iter()
+        .map(|(name, _)| (format!("cargo: {name}"), "cargo", vec![name]))
+        .collect())
+}
+
+fn makefile_targets(contents: &str) -> Vec<Script> {
+    let mut seen = HashSet::default();
+    let mut targets = Vec::new();
+    for line in contents.lines() {
+        // Recipe lines start with a tab, and other indented lines are continuations.
+        if line.starts_with(char::is_whitespace) || line.starts_with('#') {
+            continue;
+        }
+        let Some((names, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // `:=` and `::=` are assignments, not rules.
+        if rest.starts_with('=') || rest.starts_with(":=") || names.contains('=') {
+            continue;
+        }
+        for name in names.split_whitespace() {
+            let is_runnable = !name.starts_with('.')
+                && !name.contains(['%', '$', '(', ')', '/'])
+                && seen.insert(name.to_owned());
+            if is_runnable {
+                targets.push((format!("make: {name}"), "make", vec![name.to_owned()]));
+            }
+        }
+    }
+    targets
+}
+
+fn justfile_recipes(contents: &str) -> Vec<Script> {
+    let mut recipes = Vec::new();
+    for line in contents.lines() {
+        if line.starts_with(char::is_whitespace) || line.starts_with(['#', '[']) {
+            continue;
+        }
+        let Some((header, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // Assignments, aliases and settings all use `:=`.
+        if rest.starts_with('=') {
+            continue;
+        }
+        let Some(name) = header.trim_start_matches('@').split_whitespace().next() else {
+            continue;
+        };
+        let is_runnable = !name.starts_with('_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if is_runnable {
+            recipes.push((format!("just: {name}"), "just", vec![name.to_owned()]));
+        }
+    }
+    recipes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(file: ScriptFile, contents: &str) -> Vec<String> {
+        file.scripts(contents, Path::new("/project"))
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|template| template.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_script_file_for_path() {
+        assert_eq!(
+            ScriptFile::for_path(Path::new("web/package.json")),
+            Some(ScriptFile::PackageJson)
+        );
+        assert_eq!(
+            ScriptFile::for_path(Path::new(".cargo/config.toml")),
+            Some(ScriptFile::CargoConfig)
+        );
+        assert_eq!(ScriptFile::for_path(Path::new("config.toml")), None);
+        assert_eq!(
+            ScriptFile::for_path(Path::new("Makefile")),
+            Some(ScriptFile::Makefile)
+        );
+        assert_eq!(
+            ScriptFile::for_path(Path::new("justfile")),
+            Some(ScriptFile::Justfile)
+        );
+        assert_eq!(
+            ScriptFile::CargoConfig.working_directory(Path::new("/project/.cargo/config.toml")),
+            Some(Path::new("/project"))
+        );
+    }
+
+    #[test]
+    fn test_package_json_scripts() {
+        let templates = ScriptFile::PackageJson
+            .scripts(
+                r#"{
+                    "name": "app",
+                    "scripts": {
+                        "build": "tsc",
+                        "test": "jest",
+                    },
+                }"#,
+                Path::new("/project/web"),
+            )
+            .unwrap();
+        assert_eq!(
+            templates.0[0],
+            TaskTemplate {
+                label: "npm: build".into(),
+                command: "npm".into(),
+                args: vec!["run".into(), "build".into()],
+                cwd: Some("/project/web".into()),
+                tags: vec![SCRIPT_TAG.into()],
+                ..TaskTemplate::default()
+            }
+        );
+        assert_eq!(templates.0[1].label, "npm: test");
+        assert!(labels(ScriptFile::PackageJson, r#"{ "name": "app" }"#).is_empty());
+    }
+
+    #[test]
+    fn test_cargo_scripts() {
+        assert_eq!(
+            labels(
+                ScriptFile::CargoManifest,
+                r#"
+                [package]
+                name = "app"
+
+                [[bin]]
+                name = "server"
+                path = "src/server.rs"
+                "#
+            ),
+            vec!["cargo: run --bin server"]
+        );
+        assert_eq!(
+            labels(
+                ScriptFile::CargoConfig,
+                r#"
+                [alias]
+                lint = ["clippy", "--all-targets"]
+                xtask = "run --package xtask --"
+                "#
+            ),
+            vec!["cargo: lint", "cargo: xtask"]
+        );
+    }
+
+    #[test]
+    fn test_makefile_targets() {
+        assert_eq!(
+            labels(
+                ScriptFile::Makefile,
+                "CC := gcc\n\
+                 FLAGS = -O2\n\
+                 .PHONY: all clean\n\
+                 all: build test\n\
+                 build test: deps\n\
+                 \t$(CC) $(FLAGS) main.c\n\
+                 %.o: %.c\n\
+                 clean:\n\
+                 \trm -rf out\n\
+                 # lint: disabled\n"
+            ),
+            vec!["make: all", "make: build", "make: test", "make: clean"]
+        );
+    }
+
+    #[test]
+    fn test_justfile_recipes() {
+        assert_eq!(
+            labels(
+                ScriptFile::Justfile,
+                "set shell := [\"bash\", \"-c\"]\n\
+                 alias b := build\n\
+                 version := \"1.0\"\n\
+                 \n\
+                 # Builds everything\n\
+                 build:\n\
+                 \tcargo build\n\
+                 \n\
+                 [unix]\n\
+                 @test filter='': build\n\
+                 \tcargo test {{filter}}\n\
+                 _helper:\n\
+                 \techo hidden\n"
+            ),
+            vec!["just: build", "just: test"]
+        );
+    }
+}
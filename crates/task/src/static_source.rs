@@ -112,6 +112,47 @@ impl<T: PartialEq + 'static + Sync> TrackedFile<T> {
             parsed_contents: Default::default(),
         }
     }
+
+    /// Initializes new [`TrackedFile`] with a type that's parsed from the file's contents with the given function,
+    /// for files that are not JSON.
+    pub fn new_parsed_with(
+        mut tracker: UnboundedReceiver<String>,
+        notification_outlet: UnboundedSender<()>,
+        parse: impl Fn(&str) -> anyhow::Result<T> + Send + 'static,
+        cx: &mut AppContext,
+    ) -> Self
+    where
+        T: Default + Send,
+    {
+        let parsed_contents: Arc<RwLock<T>> = Arc::default();
+        cx.background_executor()
+            .spawn({
+                let parsed_contents = parsed_contents.clone();
+                async move {
+                    while let Some(new_contents) = tracker.next().await {
+                        if Arc::strong_count(&parsed_contents) == 1 {
+                            // We're no longer being observed. Stop polling.
+                            break;
+                        }
+
+                        let Some(new_contents) = parse(&new_contents).log_err() else {
+                            continue;
+                        };
+                        let mut contents = parsed_contents.write();
+                        if *contents != new_contents {
+                            *contents = new_contents;
+                            if notification_outlet.unbounded_send(()).is_err() {
+                                // Whoever cared about contents is not around anymore.
+                                break;
+                            }
+                        }
+                    }
+                    anyhow::Ok(())
+                }
+            })
+            .detach_and_log_err(cx);
+        Self { parsed_contents }
+    }
 }
 
 impl StaticSource {
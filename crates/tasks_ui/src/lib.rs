@@ -9,7 +9,7 @@ use workspace::{tasks::schedule_resolved_task, Workspace};
 mod modal;
 mod settings;
 
-pub use modal::{Rerun, Spawn};
+pub use modal::{Rerun, RunScript, Spawn};
 
 pub fn init(cx: &mut AppContext) {
     settings::TaskSettings::register(cx);
@@ -17,6 +17,9 @@ pub fn init(cx: &mut AppContext) {
         |workspace: &mut Workspace, _: &mut ViewContext<Workspace>| {
             workspace
                 .register_action(spawn_task_or_modal)
+                .register_action(|workspace, _: &RunScript, cx| {
+                    toggle_modal(workspace, true, cx).detach();
+                })
                 .register_action(move |workspace, action: &modal::Rerun, cx| {
                     if let Some((task_source_kind, mut last_scheduled_task)) =
                         workspace.project().update(cx, |project, cx| {
@@ -70,7 +73,7 @@ pub fn init(cx: &mut AppContext) {
                             );
                         }
                     } else {
-                        toggle_modal(workspace, cx).detach();
+                        toggle_modal(workspace, false, cx).detach();
                     };
                 });
         },
@@ -81,11 +84,15 @@ pub fn init(cx: &mut AppContext) {
 fn spawn_task_or_modal(workspace: &mut Workspace, action: &Spawn, cx: &mut ViewContext<Workspace>) {
     match &action.task_name {
         Some(name) => spawn_task_with_name(name.clone(), cx).detach_and_log_err(cx),
-        None => toggle_modal(workspace, cx).detach(),
+        None => toggle_modal(workspace, false, cx).detach(),
     }
 }
 
-fn toggle_modal(workspace: &mut Workspace, cx: &mut ViewContext<'_, Workspace>) -> AsyncTask<()> {
+fn toggle_modal(
+    workspace: &mut Workspace,
+    scripts_only: bool,
+    cx: &mut ViewContext<'_, Workspace>,
+) -> AsyncTask<()> {
     let project = workspace.project().clone();
     let workspace_handle = workspace.weak_handle();
     let context_task = task_context(workspace, cx);
@@ -97,7 +104,7 @@ fn toggle_modal(workspace: &mut Workspace, cx: &mut ViewContext<'_, Workspace>)
                     project.is_local_or_ssh() || project.ssh_connection_string(cx).is_some()
                 }) {
                     workspace.toggle_modal(cx, |cx| {
-                        TasksModal::new(project, task_context, workspace_handle, cx)
+                        TasksModal::new(project, task_context, scripts_only, workspace_handle, cx)
                     })
                 }
             })
@@ -3,13 +3,13 @@ use std::sync::Arc;
 use crate::active_item_selection_properties;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
-    impl_actions, rems, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusableView,
-    InteractiveElement, Model, ParentElement, Render, SharedString, Styled, Subscription, Task,
-    View, ViewContext, VisualContext, WeakView,
+    actions, impl_actions, rems, Action, AnyElement, AppContext, DismissEvent, EventEmitter,
+    FocusableView, InteractiveElement, Model, ParentElement, Render, SharedString, Styled,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView,
 };
 use picker::{highlighted_match_with_paths::HighlightedText, Picker, PickerDelegate};
 use project::{Project, TaskSourceKind};
-use task::{ResolvedTask, TaskContext, TaskId, TaskTemplate};
+use task::{project_scripts::SCRIPT_TAG, ResolvedTask, TaskContext, TaskId, TaskTemplate};
 use ui::{
     div, h_flex, v_flex, ActiveTheme, Button, ButtonCommon, ButtonSize, Clickable, Color,
     FluentBuilder as _, Icon, IconButton, IconButtonShape, IconName, IconSize, IntoElement,
@@ -61,6 +61,8 @@ pub struct Rerun {
 
 impl_actions!(task, [Rerun, Spawn]);
 
+actions!(task, [RunScript]);
+
 /// A modal used to spawn new tasks.
 pub(crate) struct TasksModalDelegate {
     project: Model<Project>,
@@ -73,14 +75,21 @@ pub(crate) struct TasksModalDelegate {
     prompt: String,
     task_context: TaskContext,
     placeholder_text: Arc<str>,
+    scripts_only: bool,
 }
 
 impl TasksModalDelegate {
     fn new(
         project: Model<Project>,
         task_context: TaskContext,
+        scripts_only: bool,
         workspace: WeakView<Workspace>,
     ) -> Self {
+        let placeholder_text = if scripts_only {
+            "Find a script to run"
+        } else {
+            "Find a task, or run a command"
+        };
         Self {
             project,
             workspace,
@@ -91,7 +100,8 @@ impl TasksModalDelegate {
             selected_index: 0,
             prompt: String::default(),
             task_context,
-            placeholder_text: Arc::from("Find a task, or run a command"),
+            placeholder_text: Arc::from(placeholder_text),
+            scripts_only,
         }
     }
 
@@ -141,12 +151,13 @@ impl TasksModal {
     pub(crate) fn new(
         project: Model<Project>,
         task_context: TaskContext,
+        scripts_only: bool,
         workspace: WeakView<Workspace>,
         cx: &mut ViewContext<Self>,
     ) -> Self {
         let picker = cx.new_view(|cx| {
             Picker::uniform_list(
-                TasksModalDelegate::new(project, task_context, workspace),
+                TasksModalDelegate::new(project, task_context, scripts_only, workspace),
                 cx,
             )
         });
@@ -253,8 +264,12 @@ impl PickerDelegate for TasksModalDelegate {
                                     }
                                 });
                             cx.spawn(|picker, mut cx| async move {
-                                let (used, current) = resolved_task.await;
+                                let (mut used, mut current) = resolved_task.await;
                                 picker.update(&mut cx, |picker, _| {
+                                    if picker.delegate.scripts_only {
+                                        used.retain(|(_, task)| is_script(task));
+                                        current.retain(|(_, task)| is_script(task));
+                                    }
                                     picker.delegate.last_used_candidate_index = if used.is_empty() {
                                         None
                                     } else {
@@ -582,6 +597,13 @@ impl PickerDelegate for TasksModalDelegate {
     }
 }
 
+fn is_script(task: &ResolvedTask) -> bool {
+    task.original_task()
+        .tags
+        .iter()
+        .any(|tag| tag == SCRIPT_TAG)
+}
+
 fn string_match_candidates<'a>(
     candidates: impl Iterator<Item = &'a (TaskSourceKind, ResolvedTask)> + 'a,
 ) -> Vec<StringMatchCandidate> {
@@ -1047,6 +1069,88 @@ mod tests {
         cx.executor().run_until_parked()
     }
 
+    #[gpui::test]
+    async fn test_run_script_modal(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                ".zed": {
+                    "tasks.json": r#"[{ "label": "example task", "command": "echo" }]"#,
+                },
+                "package.json": r#"{ "scripts": { "build": "tsc", "lint": "eslint ." } }"#,
+                "Makefile": "test:\n\tcargo test\n",
+                "a.ts": "a"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let tasks_picker = open_spawn_tasks(&workspace, cx);
+        assert_eq!(
+            task_names(&tasks_picker, cx),
+            vec!["example task", "make: test", "npm: build", "npm: lint"],
+            "Scripts should be listed among other tasks"
+        );
+        tasks_picker.update(cx, |_, cx| {
+            cx.emit(DismissEvent);
+        });
+        drop(tasks_picker);
+        cx.executor().run_until_parked();
+
+        cx.dispatch_action(RunScript);
+        let scripts_picker = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<TasksModal>(cx)
+                .expect("no task modal after `RunScript` action was dispatched")
+                .read(cx)
+                .picker
+                .clone()
+        });
+        assert_eq!(
+            task_names(&scripts_picker, cx),
+            vec!["make: test", "npm: build", "npm: lint"],
+            "Only scripts should be listed"
+        );
+
+        cx.simulate_input("lint");
+        cx.dispatch_action(menu::Confirm);
+        cx.executor().run_until_parked();
+        let (_, last_task) = workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .project()
+                    .read(cx)
+                    .task_inventory()
+                    .read(cx)
+                    .last_scheduled_task(None)
+            })
+            .expect("a script should have been scheduled");
+        assert_eq!(last_task.resolved_label, "npm: lint");
+        let resolved = last_task.resolved.expect("resolved script");
+        assert_eq!(resolved.command, "npm");
+        assert_eq!(resolved.args, vec!["run", "lint"]);
+        assert_eq!(resolved.cwd, Some(PathBuf::from("/dir")));
+
+        cx.dispatch_action(RunScript);
+        let scripts_picker = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<TasksModal>(cx)
+                .expect("no task modal after `RunScript` action was dispatched")
+                .read(cx)
+                .picker
+                .clone()
+        });
+        assert_eq!(
+            task_names(&scripts_picker, cx),
+            vec!["npm: lint", "make: test", "npm: build"],
+            "Recently run scripts should be listed first"
+        );
+    }
+
     fn open_spawn_tasks(
         workspace: &View<Workspace>,
         cx: &mut VisualTestContext,
@@ -22,6 +22,7 @@ use alacritty_terminal::{
     Term,
 };
 use anyhow::{bail, Result};
+use audio::{Audio, Sound};
 
 use futures::{
     channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
@@ -50,7 +51,7 @@ use std::{
     ops::{Deref, Index, RangeInclusive},
     path::PathBuf,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use thiserror::Error;
 
@@ -611,6 +612,7 @@ pub struct TaskState {
     pub status: TaskStatus,
     pub completion_rx: Receiver<()>,
     pub hide: HideStrategy,
+    pub started_at: Instant,
 }
 
 /// A status of the current terminal tab's task.
@@ -1561,6 +1563,11 @@ impl Terminal {
             }
         };
 
+        // Let people who switched to something else while a long task ran know it's done.
+        if task.started_at.elapsed() >= LONG_TASK_DURATION {
+            Audio::play_sound(Sound::TaskFinished, cx);
+        }
+
         let (finished_successfully, task_line, command_line) = task_summary(task, error_code);
         // SAFETY: the invocation happens on non `TaskStatus::Running` tasks, once,
         // after either `AlacTermEvent::Exit` or `AlacTermEvent::ChildExit` events that are spawned
@@ -1582,6 +1589,7 @@ impl Terminal {
 }
 
 const TASK_DELIMITER: &str = "⏵ ";
+const LONG_TASK_DURATION: Duration = Duration::from_secs(10);
 fn task_summary(task: &TaskState, error_code: Option<i32>) -> (bool, String, String) {
     let escaped_full_label = task.full_label.replace("\r\n", "\r").replace('\n', "\r");
     let (success, task_line) = match error_code {
@@ -42,6 +42,7 @@ pub struct TerminalSettings {
     pub default_height: Pixels,
     pub detect_venv: VenvSettings,
     pub max_scroll_history_lines: Option<usize>,
+    pub restore_command: Option<String>,
     pub toolbar: Toolbar,
 }
 
@@ -176,6 +177,11 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: 10_000
     pub max_scroll_history_lines: Option<usize>,
+    /// A command to run in each terminal restored when a project is reopened,
+    /// such as one that restarts a development server.
+    ///
+    /// Default: null
+    pub restore_command: Option<String>,
     /// Toolbar related settings
     pub toolbar: Option<ToolbarContent>,
 }
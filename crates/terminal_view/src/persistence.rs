@@ -35,6 +35,9 @@ define_connection! {
             DROP TABLE terminals;
 
             ALTER TABLE terminals2 RENAME TO terminals;
+        ),
+        sql!(
+            ALTER TABLE terminals ADD COLUMN title TEXT DEFAULT NULL;
         )];
 }
 
@@ -52,19 +55,21 @@ impl TerminalDb {
     }
 
     query! {
-        pub async fn save_working_directory(
+        pub async fn save_terminal(
             item_id: ItemId,
             workspace_id: WorkspaceId,
-            working_directory: PathBuf
+            working_directory: PathBuf,
+            title: Option<String>
         ) -> Result<()> {
-            INSERT OR REPLACE INTO terminals(item_id, workspace_id, working_directory)
-            VALUES (?, ?, ?)
+            INSERT OR REPLACE INTO terminals(item_id, workspace_id, working_directory, title)
+            VALUES (?, ?, ?, ?)
         }
     }
 
+    // Returns the working directory, and the title the terminal was renamed to
     query! {
-        pub fn get_working_directory(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<PathBuf>> {
-            SELECT working_directory
+        pub fn get_terminal(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<(PathBuf, Option<String>)>> {
+            SELECT working_directory, title
             FROM terminals
             WHERE item_id = ? AND workspace_id = ?
         }
@@ -96,3 +101,37 @@ impl TerminalDb {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_get_terminal() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        TERMINAL_DB
+            .save_terminal(1234, workspace_id, PathBuf::from("/project"), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            TERMINAL_DB.get_terminal(1234, workspace_id).unwrap(),
+            Some((PathBuf::from("/project"), None))
+        );
+
+        TERMINAL_DB
+            .save_terminal(
+                1234,
+                workspace_id,
+                PathBuf::from("/project/server"),
+                Some("Server".to_owned()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            TERMINAL_DB.get_terminal(1234, workspace_id).unwrap(),
+            Some((PathBuf::from("/project/server"), Some("Server".to_owned())))
+        );
+        assert_eq!(TERMINAL_DB.get_terminal(5678, workspace_id).unwrap(), None);
+    }
+}
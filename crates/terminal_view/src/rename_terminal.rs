@@ -0,0 +1,102 @@
+use editor::Editor;
+use gpui::{
+    div, prelude::*, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render,
+    View, ViewContext,
+};
+use theme::ActiveTheme;
+use ui::{h_flex, prelude::*, v_flex, Label};
+use workspace::ModalView;
+
+use crate::TerminalView;
+
+/// A modal to give a terminal a name of its own, shown in its tab instead of the title set by
+/// the shell, and kept when the project is reopened.
+pub struct RenameTerminalModal {
+    title_editor: View<Editor>,
+    terminal_view: View<TerminalView>,
+}
+
+impl ModalView for RenameTerminalModal {}
+
+impl FocusableView for RenameTerminalModal {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.title_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for RenameTerminalModal {}
+
+impl RenameTerminalModal {
+    pub fn new(terminal_view: View<TerminalView>, cx: &mut ViewContext<Self>) -> Self {
+        let custom_title = terminal_view.read(cx).custom_title().map(ToOwned::to_owned);
+        let title = terminal_view.read(cx).terminal().read(cx).title(true);
+        let title_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text(title, cx);
+            if let Some(custom_title) = custom_title {
+                editor.set_text(custom_title, cx);
+                editor.select_all(&editor::actions::SelectAll, cx);
+            }
+            editor
+        });
+        cx.subscribe(&title_editor, |_, _, event: &editor::EditorEvent, cx| {
+            if let editor::EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        })
+        .detach();
+
+        Self {
+            title_editor,
+            terminal_view,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let title = self.title_editor.read(cx).text(cx);
+        self.terminal_view.update(cx, |terminal_view, cx| {
+            terminal_view.set_custom_title(Some(title), cx);
+            cx.focus_self();
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for RenameTerminalModal {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .elevation_2(cx)
+            .key_context("RenameTerminal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .w_96()
+            .child(
+                v_flex()
+                    .px_1()
+                    .pt_0p5()
+                    .gap_px()
+                    .child(
+                        v_flex()
+                            .py_0p5()
+                            .px_1()
+                            .child(div().px_1().py_0p5().child(self.title_editor.clone())),
+                    )
+                    .child(
+                        div()
+                            .h_px()
+                            .w_full()
+                            .bg(cx.theme().colors().element_background),
+                    )
+                    .child(
+                        h_flex().justify_between().px_2().py_1().child(
+                            Label::new("Rename the terminal, or leave empty to show its own title")
+                                .color(Color::Muted),
+                        ),
+                    ),
+            )
+    }
+}
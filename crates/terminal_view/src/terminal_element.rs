@@ -5,7 +5,8 @@ use gpui::{
     HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement, Interactivity, IntoElement,
     LayoutId, Model, ModelContext, ModifiersChangedEvent, MouseButton, MouseMoveEvent, Pixels,
     Point, ShapedLine, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle,
-    UTF16Selection, UnderlineStyle, View, WeakView, WhiteSpace, WindowContext, WindowTextSystem,
+    UTF16Selection, UnderlineKind, UnderlineStyle, View, WeakView, WhiteSpace, WindowContext,
+    WindowTextSystem,
 };
 use itertools::Itertools;
 use language::CursorShape;
@@ -358,6 +359,14 @@ impl TerminalElement {
             color: Some(fg),
             thickness: Pixels::from(1.0),
             wavy: flags.contains(Flags::UNDERCURL),
+            kind: if flags.contains(Flags::DOUBLE_UNDERLINE) {
+                UnderlineKind::Double
+            } else if flags.contains(Flags::DOTTED_UNDERLINE) {
+                UnderlineKind::Dotted
+            } else {
+                UnderlineKind::Single
+            },
+            ..Default::default()
         });
 
         let strikethrough = flags
@@ -649,6 +658,7 @@ impl Element for TerminalElement {
                         thickness: px(1.0),
                         color: Some(theme.colors().link_text_hover),
                         wavy: false,
+                        ..Default::default()
                     }),
                     strikethrough: None,
                     fade_out: None,
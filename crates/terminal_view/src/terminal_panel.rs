@@ -29,7 +29,7 @@ use workspace::{
     item::SerializableItem,
     pane,
     ui::IconName,
-    DraggedTab, ItemId, NewTerminal, Pane, ToggleZoom, Workspace,
+    DraggedTab, ItemId, NewTerminal, Pane, ToggleZoom, Workspace, WorkspaceId,
 };
 
 use anyhow::Result;
@@ -37,6 +37,12 @@ use zed_actions::InlineAssist;
 
 const TERMINAL_PANEL_KEY: &str = "TerminalPanel";
 
+/// Each workspace keeps its own terminals, so that reopening a project restores the terminals
+/// that were open in it.
+fn serialization_key(workspace_id: WorkspaceId) -> String {
+    format!("{TERMINAL_PANEL_KEY}-{}", i64::from(workspace_id))
+}
+
 actions!(terminal_panel, [ToggleFocus]);
 
 pub fn init(cx: &mut AppContext) {
@@ -62,6 +68,7 @@ pub struct TerminalPanel {
     pane: View<Pane>,
     fs: Arc<dyn Fs>,
     workspace: WeakView<Workspace>,
+    workspace_id: Option<WorkspaceId>,
     width: Option<Pixels>,
     height: Option<Pixels>,
     pending_serialization: Task<Option<()>>,
@@ -149,6 +156,7 @@ impl TerminalPanel {
             pane,
             fs: workspace.app_state().fs.clone(),
             workspace: workspace.weak_handle(),
+            workspace_id: workspace.database_id(),
             pending_serialization: Task::ready(None),
             width: None,
             height: None,
@@ -241,9 +249,17 @@ impl TerminalPanel {
         workspace: WeakView<Workspace>,
         mut cx: AsyncWindowContext,
     ) -> Result<View<Self>> {
+        let workspace_id = workspace.update(&mut cx, |workspace, _| workspace.database_id())?;
         let serialized_panel = cx
             .background_executor()
-            .spawn(async move { KEY_VALUE_STORE.read_kvp(TERMINAL_PANEL_KEY) })
+            .spawn(async move {
+                match workspace_id {
+                    Some(workspace_id) => {
+                        KEY_VALUE_STORE.read_kvp(&serialization_key(workspace_id))
+                    }
+                    None => Ok(None),
+                }
+            })
             .await
             .log_err()
             .flatten()
@@ -604,6 +620,9 @@ impl TerminalPanel {
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
         let mut items_to_serialize = HashSet::default();
         let items = self
             .pane
@@ -632,7 +651,7 @@ impl TerminalPanel {
             async move {
                 KEY_VALUE_STORE
                     .write_kvp(
-                        TERMINAL_PANEL_KEY.into(),
+                        serialization_key(workspace_id),
                         serde_json::to_string(&SerializedTerminalPanel {
                             items,
                             active_item_id,
@@ -1,4 +1,5 @@
 mod persistence;
+mod rename_terminal;
 pub mod terminal_element;
 pub mod terminal_panel;
 
@@ -6,14 +7,15 @@ use collections::HashSet;
 use editor::{actions::SelectAll, scroll::Autoscroll, Editor};
 use futures::{stream::FuturesUnordered, StreamExt};
 use gpui::{
-    anchored, deferred, div, impl_actions, AnyElement, AppContext, DismissEvent, EventEmitter,
-    FocusHandle, FocusableView, KeyContext, KeyDownEvent, Keystroke, Model, MouseButton,
-    MouseDownEvent, Pixels, Render, ScrollWheelEvent, Styled, Subscription, Task, View,
-    VisualContext, WeakView,
+    actions, anchored, deferred, div, impl_actions, AnyElement, AppContext, DismissEvent,
+    EventEmitter, FocusHandle, FocusableView, KeyContext, KeyDownEvent, Keystroke, Model,
+    MouseButton, MouseDownEvent, Pixels, Render, ScrollWheelEvent, Styled, Subscription, Task,
+    View, VisualContext, WeakView,
 };
 use language::Bias;
 use persistence::TERMINAL_DB;
 use project::{search::SearchQuery, terminals::TerminalKind, Fs, Metadata, Project};
+use rename_terminal::RenameTerminalModal;
 use terminal::{
     alacritty_terminal::{
         index::Point,
@@ -39,7 +41,7 @@ use workspace::{
 
 use anyhow::Context;
 use serde::Deserialize;
-use settings::{Settings, SettingsStore};
+use settings::{Settings, SettingsLocation, SettingsStore};
 use smol::Timer;
 use zed_actions::InlineAssist;
 
@@ -72,6 +74,8 @@ pub struct SendKeystroke(String);
 
 impl_actions!(terminal, [SendText, SendKeystroke]);
 
+actions!(terminal, [RenameTerminal]);
+
 pub fn init(cx: &mut AppContext) {
     terminal_panel::init(cx);
     terminal::init(cx);
@@ -108,6 +112,8 @@ pub struct TerminalView {
     blink_epoch: usize,
     can_navigate_to_selected_word: bool,
     workspace_id: Option<WorkspaceId>,
+    /// The name given to the terminal, shown in its tab instead of the title set by the shell.
+    custom_title: Option<String>,
     show_title: bool,
     block_below_cursor: Option<Rc<BlockProperties>>,
     scroll_top: Pixels,
@@ -184,6 +190,7 @@ impl TerminalView {
             blink_epoch: 0,
             can_navigate_to_selected_word: false,
             workspace_id,
+            custom_title: None,
             show_title: TerminalSettings::get_global(cx).toolbar.title,
             block_below_cursor: None,
             scroll_top: Pixels::ZERO,
@@ -229,6 +236,7 @@ impl TerminalView {
                 .action("Paste", Box::new(Paste))
                 .action("Select All", Box::new(SelectAll))
                 .action("Clear", Box::new(Clear))
+                .action("Rename", Box::new(RenameTerminal))
                 .when(assistant_enabled, |menu| {
                     menu.separator()
                         .action("Inline Assist", Box::new(InlineAssist::default()))
@@ -282,6 +290,16 @@ impl TerminalView {
         cx.notify();
     }
 
+    fn rename(&mut self, _: &RenameTerminal, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let terminal_view = cx.view().clone();
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(cx, |cx| RenameTerminalModal::new(terminal_view, cx));
+        });
+    }
+
     fn clear(&mut self, _: &Clear, cx: &mut ViewContext<Self>) {
         self.scroll_top = px(0.);
         self.terminal.update(cx, |term, _| term.clear());
@@ -471,6 +489,19 @@ impl TerminalView {
         &self.terminal
     }
 
+    pub fn custom_title(&self) -> Option<&str> {
+        self.custom_title.as_deref()
+    }
+
+    /// Give the terminal a name, or go back to showing the title set by the shell with `None`.
+    pub fn set_custom_title(&mut self, title: Option<String>, cx: &mut ViewContext<Self>) {
+        self.custom_title = title
+            .map(|title| title.trim().to_owned())
+            .filter(|title| !title.is_empty());
+        cx.emit(ItemEvent::UpdateTab);
+        cx.notify();
+    }
+
     pub fn set_block_below_cursor(&mut self, block: BlockProperties, cx: &mut ViewContext<Self>) {
         self.block_below_cursor = Some(Rc::new(block));
         self.scroll_to_bottom(&ScrollToBottom, cx);
@@ -934,6 +965,7 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::copy))
             .on_action(cx.listener(TerminalView::paste))
             .on_action(cx.listener(TerminalView::clear))
+            .on_action(cx.listener(TerminalView::rename))
             .on_action(cx.listener(TerminalView::scroll_line_up))
             .on_action(cx.listener(TerminalView::scroll_line_down))
             .on_action(cx.listener(TerminalView::scroll_page_up))
@@ -986,7 +1018,10 @@ impl Item for TerminalView {
 
     fn tab_content(&self, params: TabContentParams, cx: &WindowContext) -> AnyElement {
         let terminal = self.terminal().read(cx);
-        let title = terminal.title(true);
+        let title = self
+            .custom_title
+            .clone()
+            .unwrap_or_else(|| terminal.title(true));
         let rerun_button = |task_id: task::TaskId| {
             IconButton::new("rerun-icon", IconName::Rerun)
                 .icon_size(IconSize::Small)
@@ -1143,15 +1178,19 @@ impl SerializableItem for TerminalView {
             return None;
         }
 
-        if let Some((cwd, workspace_id)) = terminal.get_cwd().zip(self.workspace_id) {
-            Some(cx.background_executor().spawn(async move {
-                TERMINAL_DB
-                    .save_working_directory(item_id, workspace_id, cwd)
-                    .await
-            }))
-        } else {
-            None
+        let workspace_id = self.workspace_id?;
+        // Renamed terminals are kept even before the shell reports its working directory, which
+        // is then restored as the default one.
+        let cwd = terminal.get_cwd();
+        let title = self.custom_title.clone();
+        if cwd.is_none() && title.is_none() {
+            return None;
         }
+        Some(cx.background_executor().spawn(async move {
+            TERMINAL_DB
+                .save_terminal(item_id, workspace_id, cwd.unwrap_or_default(), title)
+                .await
+        }))
     }
 
     fn should_serialize(&self, event: &Self::Event) -> bool {
@@ -1167,17 +1206,15 @@ impl SerializableItem for TerminalView {
     ) -> Task<anyhow::Result<View<Self>>> {
         let window = cx.window_handle();
         cx.spawn(|pane, mut cx| async move {
+            let (cwd, custom_title) = TERMINAL_DB
+                .get_terminal(item_id, workspace_id)
+                .log_err()
+                .flatten()
+                .unzip();
             let cwd = cx
                 .update(|cx| {
-                    let from_db = TERMINAL_DB
-                        .get_working_directory(item_id, workspace_id)
-                        .log_err()
-                        .flatten();
-                    if from_db
-                        .as_ref()
-                        .is_some_and(|from_db| !from_db.as_os_str().is_empty())
-                    {
-                        from_db
+                    if cwd.as_ref().is_some_and(|cwd| !cwd.as_os_str().is_empty()) {
+                        cwd
                     } else {
                         workspace
                             .upgrade()
@@ -1188,15 +1225,46 @@ impl SerializableItem for TerminalView {
                 .flatten();
 
             let terminal = project.update(&mut cx, |project, cx| {
-                project.create_terminal(TerminalKind::Shell(cwd), window, cx)
+                let restore_command = restore_command(project, cwd.as_deref(), cx);
+                let terminal = project.create_terminal(TerminalKind::Shell(cwd), window, cx)?;
+                if let Some(restore_command) = restore_command {
+                    terminal.update(cx, |terminal, _| terminal.input(restore_command));
+                }
+                anyhow::Ok(terminal)
             })??;
             pane.update(&mut cx, |_, cx| {
-                cx.new_view(|cx| TerminalView::new(terminal, workspace, Some(workspace_id), cx))
+                cx.new_view(|cx| {
+                    let mut terminal_view =
+                        TerminalView::new(terminal, workspace, Some(workspace_id), cx);
+                    terminal_view.custom_title = custom_title.flatten();
+                    terminal_view
+                })
             })
         })
     }
 }
 
+/// The command configured to run in terminals restored in the given working directory, ending
+/// with a line break so that the shell runs it.
+fn restore_command(project: &Project, cwd: Option<&Path>, cx: &AppContext) -> Option<String> {
+    let worktree = cwd.and_then(|cwd| project.find_worktree(cwd, cx));
+    let settings_location = worktree
+        .as_ref()
+        .map(|(worktree, relative_path)| SettingsLocation {
+            worktree_id: worktree.read(cx).id(),
+            path: relative_path,
+        });
+    let command = TerminalSettings::get(settings_location, cx)
+        .restore_command
+        .as_deref()?
+        .trim();
+    if command.is_empty() {
+        return None;
+    }
+    let line_ending = if cfg!(windows) { "\r" } else { "\n" };
+    Some(format!("{command}{line_ending}"))
+}
+
 impl SearchableItem for TerminalView {
     type Match = RangeInclusive<Point>;
 
@@ -13,6 +13,13 @@ use crate::{StatusColorsRefinement, ThemeColorsRefinement};
 
 pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     let rgba = gpui::Rgba::try_from(color)?;
+    // Colors outside of sRGB's gamut, like Display P3 ones, would be clamped to it below.
+    if [rgba.r, rgba.g, rgba.b]
+        .iter()
+        .any(|component| !(0. ..=1.).contains(component))
+    {
+        return Ok(Hsla::from(rgba));
+    }
     let rgba = palette::rgb::Srgba::from_components((rgba.r, rgba.g, rgba.b, rgba.a));
     let hsla = palette::Hsla::from_color(rgba);
 
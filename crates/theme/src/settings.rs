@@ -1,10 +1,11 @@
 use crate::one_themes::one_dark;
 use crate::{Appearance, SyntaxTheme, Theme, ThemeRegistry, ThemeStyleContent};
 use anyhow::Result;
+use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    px, AppContext, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Global, Pixels,
-    Subscription, ViewContext, WindowContext,
+    px, AccessibilityOverrides, AccessibilityPreferences, AppContext, Font, FontFallbacks,
+    FontFeatures, FontStyle, FontWeight, Global, Pixels, Subscription, ViewContext, WindowContext,
 };
 use refineable::Refineable;
 use schemars::{
@@ -17,8 +18,11 @@ use serde_json::Value;
 use settings::{add_references_to_properties, Settings, SettingsJsonSchemaParams, SettingsSources};
 use std::sync::Arc;
 use util::ResultExt as _;
+use uuid::Uuid;
 
 const MIN_FONT_SIZE: Pixels = px(6.0);
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 3.0;
 const MIN_LINE_HEIGHT: f32 = 1.0;
 
 #[derive(
@@ -83,6 +87,8 @@ impl From<UiDensity> for String {
 #[derive(Clone)]
 pub struct ThemeSettings {
     pub ui_font_size: Pixels,
+    /// The factor the rem size of every window is multiplied by.
+    pub ui_scale: f32,
     pub ui_font: Font,
     pub buffer_font: Font,
     pub buffer_font_size: Pixels,
@@ -92,6 +98,10 @@ pub struct ThemeSettings {
     pub theme_overrides: Option<ThemeStyleContent>,
     pub ui_density: UiDensity,
     pub unnecessary_code_fade: f32,
+    /// The overrides for the accessibility preferences of the system, from the settings.
+    pub accessibility_overrides: AccessibilityOverrides,
+    /// The accessibility preferences the theme was adjusted for.
+    pub accessibility: AccessibilityPreferences,
 }
 
 impl ThemeSettings {
@@ -181,6 +191,13 @@ pub(crate) struct AdjustedUiFontSize(Pixels);
 
 impl Global for AdjustedUiFontSize {}
 
+/// The UI scales chosen for each display, keyed by the display's UUID, which take precedence
+/// over the `ui_scale` setting on that display.
+#[derive(Default)]
+pub(crate) struct AdjustedUiScales(HashMap<Uuid, f32>);
+
+impl Global for AdjustedUiScales {}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ThemeSelection {
@@ -242,6 +259,11 @@ pub struct ThemeSettingsContent {
     /// The default font size for text in the UI.
     #[serde(default)]
     pub ui_font_size: Option<f32>,
+    /// The factor to scale the whole UI by, independently of the font size of buffers.
+    ///
+    /// Default: 1.0
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
     /// The name of a font to use for rendering in the UI.
     #[serde(default)]
     pub ui_font_family: Option<String>,
@@ -290,6 +312,10 @@ pub struct ThemeSettingsContent {
     #[serde(default)]
     pub unnecessary_code_fade: Option<f32>,
 
+    /// Overrides for the accessibility preferences configured in the operating system.
+    #[serde(default)]
+    pub accessibility: Option<AccessibilityContent>,
+
     /// EXPERIMENTAL: Overrides for the current theme.
     ///
     /// These values will override the ones on the current theme specified in `theme`.
@@ -297,6 +323,21 @@ pub struct ThemeSettingsContent {
     pub theme_overrides: Option<ThemeStyleContent>,
 }
 
+/// Overrides for the accessibility preferences configured in the operating system. Preferences
+/// that aren't set follow the system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccessibilityContent {
+    /// Whether to skip decorative animations.
+    #[serde(default)]
+    pub reduce_motion: Option<bool>,
+    /// Whether to draw deemphasized text, icons and borders with their regular colors.
+    #[serde(default)]
+    pub increase_contrast: Option<bool>,
+    /// Whether to replace translucent and blurred backgrounds with opaque ones.
+    #[serde(default)]
+    pub reduce_transparency: Option<bool>,
+}
+
 fn default_font_features() -> Option<FontFeatures> {
     Some(FontFeatures::default())
 }
@@ -430,6 +471,17 @@ impl ThemeSettings {
 
             self.active_theme = Arc::new(base_theme);
         }
+
+        self.apply_accessibility_preferences();
+    }
+
+    /// Adjusts the current theme for the accessibility preferences.
+    fn apply_accessibility_preferences(&mut self) {
+        if self.accessibility.increase_contrast {
+            let mut base_theme = (*self.active_theme).clone();
+            base_theme.styles.colors.increase_contrast();
+            self.active_theme = Arc::new(base_theme);
+        }
     }
 }
 
@@ -487,7 +539,7 @@ pub fn setup_ui_font(cx: &mut WindowContext) -> gpui::Font {
         (font, get_ui_font_size(cx))
     };
 
-    cx.set_rem_size(ui_font_size);
+    cx.set_rem_size(ui_font_size * get_ui_scale(cx));
     ui_font
 }
 
@@ -520,6 +572,67 @@ pub fn reset_ui_font_size(cx: &mut AppContext) {
     }
 }
 
+fn display_uuid(cx: &WindowContext) -> Option<Uuid> {
+    cx.display()?.uuid().ok()
+}
+
+/// The factor the UI of the given window is scaled by, which depends on the display it's on.
+pub fn get_ui_scale(cx: &WindowContext) -> f32 {
+    let ui_scale = ThemeSettings::get_global(cx).ui_scale;
+    display_uuid(cx)
+        .and_then(|uuid| cx.try_global::<AdjustedUiScales>()?.0.get(&uuid).copied())
+        .unwrap_or(ui_scale)
+}
+
+/// Adjusts the UI scale of the display the given window is on, rescaling all the windows on it.
+pub fn adjust_ui_scale(cx: &mut WindowContext, f: fn(&mut f32)) {
+    let Some(uuid) = display_uuid(cx) else {
+        return;
+    };
+    let mut ui_scale = get_ui_scale(cx);
+    f(&mut ui_scale);
+    ui_scale = ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    cx.default_global::<AdjustedUiScales>()
+        .0
+        .insert(uuid, ui_scale);
+    cx.refresh();
+}
+
+pub fn has_adjusted_ui_scale(cx: &WindowContext) -> bool {
+    display_uuid(cx).map_or(false, |uuid| {
+        cx.try_global::<AdjustedUiScales>()
+            .map_or(false, |scales| scales.0.contains_key(&uuid))
+    })
+}
+
+/// Resets the UI scale of the display the given window is on to the `ui_scale` setting.
+pub fn reset_ui_scale(cx: &mut WindowContext) {
+    if !has_adjusted_ui_scale(cx) {
+        return;
+    }
+    if let Some(uuid) = display_uuid(cx) {
+        cx.global_mut::<AdjustedUiScales>().0.remove(&uuid);
+        cx.refresh();
+    }
+}
+
+/// The UI scales adjusted for each display, to persist them between sessions.
+pub fn adjusted_ui_scales(cx: &AppContext) -> HashMap<Uuid, f32> {
+    cx.try_global::<AdjustedUiScales>()
+        .map(|scales| scales.0.clone())
+        .unwrap_or_default()
+}
+
+/// Restores the UI scales adjusted for each display in a previous session.
+pub fn set_adjusted_ui_scales(cx: &mut AppContext, scales: HashMap<Uuid, f32>) {
+    let scales = scales
+        .into_iter()
+        .map(|(uuid, scale)| (uuid, scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)))
+        .collect();
+    cx.set_global(AdjustedUiScales(scales));
+    cx.refresh();
+}
+
 impl settings::Settings for ThemeSettings {
     const KEY: Option<&'static str> = None;
 
@@ -532,6 +645,7 @@ impl settings::Settings for ThemeSettings {
         let defaults = sources.default;
         let mut this = Self {
             ui_font_size: defaults.ui_font_size.unwrap().into(),
+            ui_scale: defaults.ui_scale.unwrap_or(1.0),
             ui_font: Font {
                 family: defaults.ui_font_family.as_ref().unwrap().clone().into(),
                 features: defaults.ui_font_features.clone().unwrap(),
@@ -562,6 +676,8 @@ impl settings::Settings for ThemeSettings {
             theme_overrides: None,
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
             unnecessary_code_fade: defaults.unnecessary_code_fade.unwrap_or(0.0),
+            accessibility_overrides: AccessibilityOverrides::default(),
+            accessibility: AccessibilityPreferences::default(),
         };
 
         for value in sources.user.into_iter().chain(sources.release_channel) {
@@ -611,6 +727,9 @@ impl settings::Settings for ThemeSettings {
             merge(&mut this.ui_font_size, value.ui_font_size.map(Into::into));
             this.ui_font_size = this.ui_font_size.clamp(px(6.), px(100.));
 
+            merge(&mut this.ui_scale, value.ui_scale);
+            this.ui_scale = this.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+
             merge(
                 &mut this.buffer_font_size,
                 value.buffer_font_size.map(Into::into),
@@ -622,8 +741,24 @@ impl settings::Settings for ThemeSettings {
             // Clamp the `unnecessary_code_fade` to ensure text can't disappear entirely.
             merge(&mut this.unnecessary_code_fade, value.unnecessary_code_fade);
             this.unnecessary_code_fade = this.unnecessary_code_fade.clamp(0.0, 0.9);
+
+            if let Some(accessibility) = value.accessibility {
+                let overrides = &mut this.accessibility_overrides;
+                overrides.reduce_motion = accessibility.reduce_motion.or(overrides.reduce_motion);
+                overrides.increase_contrast = accessibility
+                    .increase_contrast
+                    .or(overrides.increase_contrast);
+                overrides.reduce_transparency = accessibility
+                    .reduce_transparency
+                    .or(overrides.reduce_transparency);
+            }
         }
 
+        this.accessibility = this
+            .accessibility_overrides
+            .apply(cx.system_accessibility_preferences());
+        this.apply_accessibility_preferences();
+
         Ok(this)
     }
 
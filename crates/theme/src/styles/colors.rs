@@ -240,6 +240,16 @@ pub struct ThemeColors {
     pub link_text_hover: Hsla,
 }
 
+impl ThemeColors {
+    /// Adjusts the colors for users who prefer more contrast, by drawing deemphasized text, icons
+    /// and borders with their regular colors.
+    pub fn increase_contrast(&mut self) {
+        self.text_muted = self.text;
+        self.icon_muted = self.icon;
+        self.border_variant = self.border;
+    }
+}
+
 #[derive(Refineable, Clone)]
 pub struct ThemeStyles {
     /// The background appearance of the window.
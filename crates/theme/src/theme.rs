@@ -87,7 +87,10 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut AppContext) {
     FontFamilyCache::init_global(cx);
 
     let mut prev_buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size;
+    cx.set_accessibility_overrides(ThemeSettings::get_global(cx).accessibility_overrides);
     cx.observe_global::<SettingsStore>(move |cx| {
+        cx.set_accessibility_overrides(ThemeSettings::get_global(cx).accessibility_overrides);
+
         let buffer_font_size = ThemeSettings::get_global(cx).buffer_font_size;
         if buffer_font_size != prev_buffer_font_size {
             prev_buffer_font_size = buffer_font_size;
@@ -89,6 +89,42 @@ impl RenderOnce for ApplicationMenu {
                                 )
                                 .into_any_element()
                         })
+                        .custom_row(move |cx| {
+                            h_flex()
+                                .gap_2()
+                                .w_full()
+                                .justify_between()
+                                .cursor(gpui::CursorStyle::Arrow)
+                                .child(Label::new("UI Scale"))
+                                .child(
+                                    NumericStepper::new(
+                                        "ui-scale",
+                                        format!("{:.0}%", theme::get_ui_scale(cx) * 100.),
+                                        |_, cx| {
+                                            cx.dispatch_action(Box::new(
+                                                zed_actions::DecreaseUiScale,
+                                            ))
+                                        },
+                                        |_, cx| {
+                                            cx.dispatch_action(Box::new(
+                                                zed_actions::IncreaseUiScale,
+                                            ))
+                                        },
+                                    )
+                                    .reserve_space_for_reset(true)
+                                    .when(
+                                        theme::has_adjusted_ui_scale(cx),
+                                        |stepper| {
+                                            stepper.on_reset(|_, cx| {
+                                                cx.dispatch_action(Box::new(
+                                                    zed_actions::ResetUiScale,
+                                                ))
+                                            })
+                                        },
+                                    ),
+                                )
+                                .into_any_element()
+                        })
                         .header("Project")
                         .action(
                             "Add Folder to Project...",
@@ -1,6 +1,7 @@
 mod application_menu;
 mod collab;
 mod platforms;
+mod title_bar_settings;
 mod window_controls;
 
 #[cfg(feature = "stories")]
@@ -13,13 +14,15 @@ use call::ActiveCall;
 use client::{Client, UserStore};
 use feature_flags::{FeatureFlagAppExt, ZedPro};
 use gpui::{
-    actions, div, px, Action, AnyElement, AppContext, Decorations, Element, InteractiveElement,
-    Interactivity, IntoElement, Model, MouseButton, ParentElement, Render, Stateful,
-    StatefulInteractiveElement, Styled, Subscription, ViewContext, VisualContext, WeakView,
+    actions, div, px, Action, AnyElement, AnyView, AppContext, Decorations, Element,
+    InteractiveElement, Interactivity, IntoElement, Model, MouseButton, ParentElement, Render,
+    Stateful, StatefulInteractiveElement, Styled, Subscription, ViewContext, VisualContext,
+    WeakView,
 };
-use project::{Project, RepositoryEntry};
+use project::Project;
 use recent_projects::RecentProjects;
 use rpc::proto::{self, DevServerStatus};
+use settings::{Settings, SettingsStore};
 use smallvec::SmallVec;
 use std::sync::Arc;
 use theme::ActiveTheme;
@@ -27,15 +30,20 @@ use ui::{
     h_flex, prelude::*, Avatar, Button, ButtonLike, ButtonStyle, ContextMenu, Icon, IconName,
     Indicator, PopoverMenu, Tooltip,
 };
-use util::ResultExt;
+use util::{paths::PathExt, ResultExt};
 use vcs_menu::{BranchList, OpenRecent as ToggleVcsMenu};
 use workspace::{notifications::NotifyResultExt, Workspace};
 
 #[cfg(feature = "stories")]
 pub use stories::*;
+pub use title_bar_settings::*;
 
 const MAX_PROJECT_NAME_LENGTH: usize = 40;
 const MAX_BRANCH_NAME_LENGTH: usize = 40;
+const MAX_LSP_STATUS_LENGTH: usize = 40;
+
+/// Windows shorter than this have a compact title bar when `compact_mode` is `auto`.
+const COMPACT_WINDOW_HEIGHT: Pixels = px(640.);
 
 actions!(
     collab,
@@ -49,6 +57,8 @@ actions!(
 );
 
 pub fn init(cx: &mut AppContext) {
+    TitleBarSettings::register(cx);
+
     cx.observe_new_views(|workspace: &mut Workspace, cx| {
         let item = cx.new_view(|cx| TitleBar::new("title-bar", workspace, cx));
         workspace.set_titlebar_item(item.into(), cx)
@@ -65,11 +75,19 @@ pub struct TitleBar {
     client: Arc<Client>,
     workspace: WeakView<Workspace>,
     should_move: bool,
+    compact: bool,
+    left_items: Vec<AnyView>,
+    right_items: Vec<AnyView>,
     _subscriptions: Vec<Subscription>,
 }
 
 impl Render for TitleBar {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = *TitleBarSettings::get_global(cx);
+        if self.compact {
+            return self.render_compact(&settings, cx);
+        }
+
         let close_action = Box::new(workspace::CloseWindow);
         let height = Self::height(cx);
         let supported_controls = cx.window_controls();
@@ -138,28 +156,29 @@ impl Render for TitleBar {
                                 }
                             })
                             .children(self.render_project_host(cx))
-                            .child(self.render_project_name(cx))
-                            .children(self.render_project_branch(cx))
+                            .children(self.render_project_name(&settings, cx))
+                            .when(settings.show_branch_name, |this| {
+                                this.children(self.render_project_branch(cx))
+                            })
+                            .when(settings.show_lsp_status, |this| {
+                                this.children(self.render_lsp_status(cx))
+                            })
+                            .children(self.left_items.iter().cloned())
                             .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation()),
                     )
-                    .child(self.render_collaborator_list(cx))
+                    .when(settings.show_collab_buttons, |this| {
+                        this.child(self.render_collaborator_list(cx))
+                    })
                     .child(
                         h_flex()
                             .gap_1()
                             .pr_1()
                             .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
-                            .children(self.render_call_controls(cx))
-                            .map(|el| {
-                                let status = self.client.status();
-                                let status = &*status.borrow();
-                                if matches!(status, client::Status::Connected { .. }) {
-                                    el.child(self.render_user_menu_button(cx))
-                                } else {
-                                    el.children(self.render_connection_status(status, cx))
-                                        .child(self.render_sign_in_button(cx))
-                                        .child(self.render_user_menu_button(cx))
-                                }
-                            }),
+                            .children(self.right_items.iter().cloned())
+                            .when(settings.show_collab_buttons, |this| {
+                                this.children(self.render_call_controls(cx))
+                            })
+                            .map(|el| self.render_account_controls(el, cx)),
                     ),
             )
             .when(!cx.is_fullscreen(), |title_bar| match self.platform_style {
@@ -202,6 +221,7 @@ impl Render for TitleBar {
                     title_bar.child(platform_windows::WindowsWindowControls::new(height))
                 }
             })
+            .into_any_element()
     }
 }
 
@@ -216,12 +236,22 @@ impl TitleBar {
         let client = workspace.app_state().client.clone();
         let active_call = ActiveCall::global(cx);
         let mut subscriptions = Vec::new();
-        subscriptions.push(
-            cx.observe(&workspace.weak_handle().upgrade().unwrap(), |_, _, cx| {
+        subscriptions.push(cx.observe(
+            &workspace.weak_handle().upgrade().unwrap(),
+            |this, _, cx| {
+                this.update_compact_mode(cx);
                 cx.notify()
-            }),
-        );
+            },
+        ));
         subscriptions.push(cx.observe(&project, |_, _, cx| cx.notify()));
+        subscriptions.push(cx.observe(&project.read(cx).lsp_store(), |_, _, cx| cx.notify()));
+        subscriptions.push(cx.observe_global::<SettingsStore>(|this, cx| {
+            this.update_compact_mode(cx);
+            cx.notify()
+        }));
+        subscriptions.push(cx.observe_window_bounds(|this, cx| this.update_compact_mode(cx)));
+        // The workspace is being updated while the title bar is created.
+        cx.defer(|this, cx| this.update_compact_mode(cx));
         subscriptions.push(cx.observe(&active_call, |this, _, cx| this.active_call_changed(cx)));
         subscriptions.push(cx.observe_window_activation(Self::window_activation_changed));
         subscriptions.push(cx.observe(&user_store, |_, _, cx| cx.notify()));
@@ -232,6 +262,9 @@ impl TitleBar {
             children: SmallVec::new(),
             workspace: workspace.weak_handle(),
             should_move: false,
+            compact: false,
+            left_items: Vec::new(),
+            right_items: Vec::new(),
             project,
             user_store,
             client,
@@ -256,6 +289,108 @@ impl TitleBar {
         self
     }
 
+    /// Adds an item after the project's name and branch. Crates add their items by observing
+    /// new `TitleBar` views.
+    pub fn add_left_item(&mut self, item: impl Into<AnyView>, cx: &mut ViewContext<Self>) {
+        self.left_items.push(item.into());
+        cx.notify();
+    }
+
+    /// Adds an item before the call controls and the user menu. Crates add their items by
+    /// observing new `TitleBar` views.
+    pub fn add_right_item(&mut self, item: impl Into<AnyView>, cx: &mut ViewContext<Self>) {
+        self.right_items.push(item.into());
+        cx.notify();
+    }
+
+    fn update_compact_mode(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let is_mac = self.platform_style == PlatformStyle::Mac;
+        let compact = match TitleBarSettings::get_global(cx).compact_mode {
+            CompactMode::Never => false,
+            CompactMode::Auto => cx.viewport_size().height < COMPACT_WINDOW_HEIGHT,
+            CompactMode::Always => true,
+        };
+        // The traffic lights would cover the left dock, which has no room left for them.
+        let compact = compact && !(is_mac && workspace.read(cx).left_dock().read(cx).is_open());
+        let leading_padding = if is_mac && !cx.is_fullscreen() {
+            px(platform_mac::TRAFFIC_LIGHT_PADDING)
+        } else {
+            px(0.)
+        };
+
+        if self.compact != compact {
+            self.compact = compact;
+            cx.notify();
+        }
+        workspace.update(cx, |workspace, cx| {
+            workspace.set_compact_titlebar(compact.then_some(leading_padding), cx)
+        });
+    }
+
+    /// Renders the title bar's items alone, for the end of the tab bar it's merged into.
+    fn render_compact(
+        &mut self,
+        settings: &TitleBarSettings,
+        cx: &mut ViewContext<Self>,
+    ) -> AnyElement {
+        let height = Self::height(cx);
+        let decorations = cx.window_decorations();
+        h_flex()
+            .id("titlebar")
+            .h_full()
+            .gap_1()
+            .pl_1()
+            .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+            .children(self.render_project_host(cx))
+            .children(self.render_project_name(settings, cx))
+            .when(settings.show_branch_name, |this| {
+                this.children(self.render_project_branch(cx))
+            })
+            .when(settings.show_lsp_status, |this| {
+                this.children(self.render_lsp_status(cx))
+            })
+            .children(self.left_items.iter().cloned())
+            .children(self.right_items.iter().cloned())
+            .when(settings.show_collab_buttons, |this| {
+                this.children(self.render_call_controls(cx))
+            })
+            .map(|el| self.render_account_controls(el, cx))
+            .when(!cx.is_fullscreen(), |title_bar| match self.platform_style {
+                PlatformStyle::Mac => title_bar,
+                PlatformStyle::Linux => title_bar.when(
+                    matches!(decorations, Decorations::Client { .. }),
+                    |title_bar| {
+                        title_bar.child(platform_linux::LinuxWindowControls::new(Box::new(
+                            workspace::CloseWindow,
+                        )))
+                    },
+                ),
+                PlatformStyle::Windows => {
+                    title_bar.child(platform_windows::WindowsWindowControls::new(height))
+                }
+            })
+            .into_any_element()
+    }
+
+    fn render_account_controls<E: ParentElement>(
+        &mut self,
+        el: E,
+        cx: &mut ViewContext<Self>,
+    ) -> E {
+        let status = self.client.status();
+        let status = &*status.borrow();
+        if matches!(status, client::Status::Connected { .. }) {
+            el.child(self.render_user_menu_button(cx))
+        } else {
+            el.children(self.render_connection_status(status, cx))
+                .child(self.render_sign_in_button(cx))
+                .child(self.render_user_menu_button(cx))
+        }
+    }
+
     pub fn render_project_host(&self, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
         if let Some(dev_server) =
             self.project
@@ -337,59 +472,90 @@ impl TitleBar {
         )
     }
 
-    pub fn render_project_name(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    pub fn render_project_name(
+        &self,
+        settings: &TitleBarSettings,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
         let name = {
-            let mut names = self.project.read(cx).visible_worktrees(cx).map(|worktree| {
-                let worktree = worktree.read(cx);
-                worktree.root_name()
-            });
-
-            names.next()
+            let mut worktrees = self.project.read(cx).visible_worktrees(cx);
+            match settings.project_name {
+                ProjectNameFormat::RootName => worktrees
+                    .next()
+                    .map(|worktree| worktree.read(cx).root_name().to_string()),
+                ProjectNameFormat::AllRootNames => {
+                    let names = worktrees
+                        .map(|worktree| worktree.read(cx).root_name())
+                        .collect::<Vec<_>>();
+                    (!names.is_empty()).then(|| names.join(", "))
+                }
+                ProjectNameFormat::Path => worktrees.next().map(|worktree| {
+                    let abs_path = worktree.read(cx).abs_path();
+                    abs_path.compact().to_string_lossy().into_owned()
+                }),
+                ProjectNameFormat::Hidden => return None,
+            }
         };
         let is_project_selected = name.is_some();
         let name = if let Some(name) = name {
-            util::truncate_and_trailoff(name, MAX_PROJECT_NAME_LENGTH)
+            util::truncate_and_trailoff(&name, MAX_PROJECT_NAME_LENGTH)
         } else {
             "Open recent project".to_string()
         };
 
         let workspace = self.workspace.clone();
-        Button::new("project_name_trigger", name)
-            .when(!is_project_selected, |b| b.color(Color::Muted))
-            .style(ButtonStyle::Subtle)
-            .label_size(LabelSize::Small)
-            .tooltip(move |cx| {
-                Tooltip::for_action(
-                    "Recent Projects",
-                    &recent_projects::OpenRecent {
-                        create_new_window: false,
-                    },
-                    cx,
-                )
-            })
-            .on_click(cx.listener(move |_, _, cx| {
-                if let Some(workspace) = workspace.upgrade() {
-                    workspace.update(cx, |workspace, cx| {
-                        RecentProjects::open(workspace, false, cx);
-                    })
-                }
-            }))
+        Some(
+            Button::new("project_name_trigger", name)
+                .when(!is_project_selected, |b| b.color(Color::Muted))
+                .style(ButtonStyle::Subtle)
+                .label_size(LabelSize::Small)
+                .tooltip(move |cx| {
+                    Tooltip::for_action(
+                        "Recent Projects",
+                        &recent_projects::OpenRecent {
+                            create_new_window: false,
+                        },
+                        cx,
+                    )
+                })
+                .on_click(cx.listener(move |_, _, cx| {
+                    if let Some(workspace) = workspace.upgrade() {
+                        workspace.update(cx, |workspace, cx| {
+                            RecentProjects::open(workspace, false, cx);
+                        })
+                    }
+                })),
+        )
     }
 
     pub fn render_project_branch(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
-        let entry = {
-            let mut names_and_branches =
-                self.project.read(cx).visible_worktrees(cx).map(|worktree| {
-                    let worktree = worktree.read(cx);
-                    worktree.root_git_entry()
-                });
+        let project = self.project.read(cx);
+        // Prefer the repository of the active file, so that the branches of nested
+        // repositories and submodules are shown while working in them.
+        let active_entry = project.active_entry().and_then(|entry_id| {
+            let worktree = project.worktree_for_entry(entry_id, cx)?.read(cx);
+            let path = &worktree.entry_for_id(entry_id)?.path;
+            worktree.repository_for_path(path)
+        });
+        let entry = active_entry.or_else(|| {
+            let mut names_and_branches = project.visible_worktrees(cx).map(|worktree| {
+                let worktree = worktree.read(cx);
+                worktree.root_git_entry()
+            });
 
             names_and_branches.next().flatten()
-        };
+        });
         let workspace = self.workspace.upgrade()?;
         let branch_name = entry
             .as_ref()
-            .and_then(RepositoryEntry::branch)
+            .and_then(|entry| {
+                let branch = entry.branch()?;
+                // Distinguish linked worktrees that aren't named after their branch.
+                Some(match entry.linked_worktree() {
+                    Some(worktree) if worktree != branch => format!("{branch} ({worktree})"),
+                    _ => branch.to_string(),
+                })
+            })
             .map(|branch| util::truncate_and_trailoff(&branch, MAX_BRANCH_NAME_LENGTH))?;
         Some(
             Button::new("project_branch_trigger", branch_name)
@@ -412,6 +578,49 @@ impl TitleBar {
         )
     }
 
+    pub fn render_lsp_status(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let mut names = Vec::new();
+        let mut is_busy = false;
+        for (_, status) in self.project.read(cx).language_server_statuses(cx) {
+            is_busy |= !status.pending_work.is_empty() || status.has_pending_diagnostic_updates;
+            names.push(status.name.as_str());
+        }
+        if names.is_empty() {
+            return None;
+        }
+        names.sort_unstable();
+        names.dedup();
+        let label = util::truncate_and_trailoff(&names.join(", "), MAX_LSP_STATUS_LENGTH);
+
+        Some(
+            ButtonLike::new("lsp_status")
+                .style(ButtonStyle::Subtle)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(Indicator::dot().color(if is_busy {
+                            Color::Warning
+                        } else {
+                            Color::Success
+                        }))
+                        .child(
+                            Label::new(label)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .line_height_style(LineHeightStyle::UiLabel),
+                        ),
+                )
+                .tooltip(move |cx| {
+                    let text = if is_busy {
+                        "Language servers are busy"
+                    } else {
+                        "Language servers are idle"
+                    };
+                    Tooltip::text(text, cx)
+                }),
+        )
+    }
+
     fn window_activation_changed(&mut self, cx: &mut ViewContext<Self>) {
         if cx.is_window_active() {
             ActiveCall::global(cx)
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TitleBarSettings {
+    pub project_name: ProjectNameFormat,
+    pub show_branch_name: bool,
+    pub show_collab_buttons: bool,
+    pub show_lsp_status: bool,
+    pub compact_mode: CompactMode,
+}
+
+/// How the project is named in the title bar.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectNameFormat {
+    /// The name of the project's first folder.
+    #[default]
+    RootName,
+    /// The names of all of the project's folders.
+    AllRootNames,
+    /// The path of the project's first folder.
+    Path,
+    /// Don't show the project's name.
+    Hidden,
+}
+
+/// When the title bar is merged into the tab bar.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactMode {
+    /// Always give the title bar a row of its own.
+    #[default]
+    Never,
+    /// Merge the title bar into the tab bar when the window is short.
+    Auto,
+    /// Always merge the title bar into the tab bar.
+    Always,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct TitleBarSettingsContent {
+    /// How to name the project in the title bar.
+    ///
+    /// Default: root_name
+    pub project_name: Option<ProjectNameFormat>,
+    /// Whether to show the branch of the project's repository.
+    ///
+    /// Default: true
+    pub show_branch_name: Option<bool>,
+    /// Whether to show the collaborators in the current call, and the buttons to share the
+    /// project and control the call.
+    ///
+    /// Default: true
+    pub show_collab_buttons: Option<bool>,
+    /// Whether to show the project's language servers, and whether they're busy.
+    ///
+    /// Default: false
+    pub show_lsp_status: Option<bool>,
+    /// When to merge the title bar into the tab bar of the top-left pane, to save vertical
+    /// space.
+    ///
+    /// Default: never
+    pub compact_mode: Option<CompactMode>,
+}
+
+impl Settings for TitleBarSettings {
+    const KEY: Option<&'static str> = Some("title_bar");
+
+    type FileContent = TitleBarSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -288,12 +288,32 @@ pub enum IconName {
     ZedXCopilot,
 }
 
+impl IconName {
+    /// Returns whether the icon points left or right, and so should be mirrored when the UI is
+    /// laid out right to left.
+    pub fn is_directional(self) -> bool {
+        matches!(
+            self,
+            IconName::ArrowLeft
+                | IconName::ArrowRight
+                | IconName::ArrowUpRight
+                | IconName::Backspace
+                | IconName::ChevronLeft
+                | IconName::ChevronRight
+                | IconName::ReplyArrowRight
+                | IconName::TriangleRight
+                | IconName::Undo
+        )
+    }
+}
+
 #[derive(IntoElement)]
 pub struct Icon {
     path: SharedString,
     color: Color,
     size: Rems,
     transformation: Transformation,
+    mirror_in_rtl: bool,
 }
 
 impl Icon {
@@ -303,6 +323,7 @@ impl Icon {
             color: Color::default(),
             size: IconSize::default().rems(),
             transformation: Transformation::default(),
+            mirror_in_rtl: icon.is_directional(),
         }
     }
 
@@ -312,6 +333,7 @@ impl Icon {
             color: Color::default(),
             size: IconSize::default().rems(),
             transformation: Transformation::default(),
+            mirror_in_rtl: false,
         }
     }
 
@@ -337,12 +359,26 @@ impl Icon {
         self.transformation = transformation;
         self
     }
+
+    /// Sets whether the icon is flipped horizontally when the UI is laid out right to left.
+    ///
+    /// Defaults to true for icons that point in a direction, like arrows and chevrons.
+    pub fn mirror_in_rtl(mut self, mirror_in_rtl: bool) -> Self {
+        self.mirror_in_rtl = mirror_in_rtl;
+        self
+    }
 }
 
 impl RenderOnce for Icon {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let transformation = if self.mirror_in_rtl && cx.layout_direction().is_rtl() {
+            self.transformation.mirrored_horizontally()
+        } else {
+            self.transformation
+        };
+
         svg()
-            .with_transformation(self.transformation)
+            .with_transformation(transformation)
             .size(self.size)
             .flex_none()
             .path(self.path)
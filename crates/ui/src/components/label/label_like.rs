@@ -182,6 +182,7 @@ impl RenderOnce for LabelLike {
                     thickness: px(1.),
                     color: None,
                     wavy: false,
+                    ..Default::default()
                 });
                 this
             })
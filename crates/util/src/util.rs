@@ -202,6 +202,29 @@ pub fn truncate_and_remove_front(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Removes characters from the middle of the string if its length is greater than `max_chars` and
+/// puts "..." in their place. Returns string unchanged if its length is smaller than max_chars.
+pub fn truncate_and_remove_middle(s: &str, max_chars: usize) -> String {
+    debug_assert!(max_chars >= 5);
+
+    let char_count = s.chars().count();
+    if char_count <= max_chars {
+        return s.to_string();
+    }
+
+    let front_chars = max_chars.div_ceil(2);
+    let back_chars = max_chars - front_chars;
+    let front_end = s
+        .char_indices()
+        .nth(front_chars)
+        .map_or(s.len(), |(ix, _)| ix);
+    let back_start = s
+        .char_indices()
+        .nth(char_count - back_chars)
+        .map_or(s.len(), |(ix, _)| ix);
+    format!("{}…{}", &s[..front_end], &s[back_start..])
+}
+
 /// Takes only `max_lines` from the string and, if there were more than `max_lines-1`, appends a
 /// a newline and "..." to the string, so that `max_lines` are returned.
 /// Returns string unchanged if its length is smaller than max_lines.
@@ -815,6 +838,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_truncate_and_remove_middle() {
+        assert_eq!(truncate_and_remove_middle("short.rs", 10), "short.rs");
+        assert_eq!(
+            truncate_and_remove_middle("a_long_file_name.rs", 10),
+            "a_lon…me.rs"
+        );
+        assert_eq!(truncate_and_remove_middle("ääääääääää", 5), "äää…ää");
+    }
+
     #[test]
     fn test_truncate_lines_and_trailoff() {
         let text = r#"Line 1
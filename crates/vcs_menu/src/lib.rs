@@ -14,16 +14,56 @@ use workspace::notifications::NotificationId;
 use workspace::{ModalView, Toast, Workspace};
 
 actions!(branches, [OpenRecent]);
+actions!(git, [UpdateSubmodules]);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
         workspace.register_action(|workspace, action, cx| {
             BranchList::open(workspace, action, cx).log_err();
         });
+        workspace.register_action(|workspace, _: &UpdateSubmodules, cx| {
+            update_submodules(workspace, cx);
+        });
     })
     .detach();
 }
 
+/// Initializes and updates the submodules of the repositories at the roots of the project.
+fn update_submodules(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+    let repositories = workspace
+        .project()
+        .read(cx)
+        .visible_worktrees(cx)
+        .filter_map(|worktree| {
+            let worktree = worktree.read(cx).as_local()?;
+            let root_entry = worktree.root_git_entry()?;
+            Some(worktree.get_local_repo(&root_entry)?.repo().clone())
+        })
+        .collect::<Vec<_>>();
+
+    cx.spawn(|workspace, mut cx| async move {
+        let result = cx
+            .background_executor()
+            .spawn(async move {
+                for repository in repositories {
+                    repository.update_submodules()?;
+                }
+                anyhow::Ok(())
+            })
+            .await;
+        workspace.update(&mut cx, |workspace, cx| {
+            struct SubmoduleUpdate;
+            let id = NotificationId::unique::<SubmoduleUpdate>();
+            let message = match result {
+                Ok(()) => "Updated submodules".to_string(),
+                Err(error) => format!("Failed to update submodules: {error}"),
+            };
+            workspace.show_toast(Toast::new(id, message), cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub struct BranchList {
     pub picker: View<Picker<BranchListDelegate>>,
     rem_width: f32,
@@ -17,6 +17,7 @@ use serde::Deserialize;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
 pub enum Object {
     Word { ignore_punctuation: bool },
+    Subword,
     Sentence,
     Paragraph,
     Quotes,
@@ -43,6 +44,7 @@ impl_actions!(vim, [Word]);
 actions!(
     vim,
     [
+        Subword,
         Sentence,
         Paragraph,
         Quotes,
@@ -66,6 +68,9 @@ pub fn register(editor: &mut Editor, cx: &mut ViewContext<Vim>) {
             vim.object(Object::Word { ignore_punctuation }, cx)
         },
     );
+    Vim::action(editor, cx, |vim, _: &Subword, cx| {
+        vim.object(Object::Subword, cx)
+    });
     Vim::action(editor, cx, |vim, _: &Tag, cx| vim.object(Object::Tag, cx));
     Vim::action(editor, cx, |vim, _: &Sentence, cx| {
         vim.object(Object::Sentence, cx)
@@ -118,6 +123,7 @@ impl Object {
     pub fn is_multiline(self) -> bool {
         match self {
             Object::Word { .. }
+            | Object::Subword
             | Object::Quotes
             | Object::BackQuotes
             | Object::VerticalBars
@@ -135,7 +141,11 @@ impl Object {
 
     pub fn always_expands_both_ways(self) -> bool {
         match self {
-            Object::Word { .. } | Object::Sentence | Object::Paragraph | Object::Argument => false,
+            Object::Word { .. }
+            | Object::Subword
+            | Object::Sentence
+            | Object::Paragraph
+            | Object::Argument => false,
             Object::Quotes
             | Object::BackQuotes
             | Object::DoubleQuotes
@@ -151,6 +161,7 @@ impl Object {
     pub fn target_visual_mode(self, current_mode: Mode) -> Mode {
         match self {
             Object::Word { .. }
+            | Object::Subword
             | Object::Sentence
             | Object::Quotes
             | Object::BackQuotes
@@ -187,6 +198,7 @@ impl Object {
                     in_word(map, relative_to, ignore_punctuation)
                 }
             }
+            Object::Subword => subword(map, relative_to, around),
             Object::Sentence => sentence(map, relative_to, around),
             Object::Paragraph => paragraph(map, relative_to, around),
             Object::Quotes => {
@@ -262,6 +274,30 @@ fn in_word(
     Some(start..end)
 }
 
+/// Returns the subword `relative_to` is in, as delimited by the subword motions.
+///
+/// If `around` is set, the underscores or dashes following the subword are included.
+fn subword(
+    map: &DisplaySnapshot,
+    relative_to: DisplayPoint,
+    around: bool,
+) -> Option<Range<DisplayPoint>> {
+    let range = movement::surrounding_subword(map, relative_to);
+    if !around {
+        return Some(range);
+    }
+
+    let mut end = range.end.to_offset(map, Bias::Left);
+    for (c, char_range) in movement::chars_after(map, end) {
+        if c == '_' || c == '-' {
+            end = char_range.end;
+        } else {
+            break;
+        }
+    }
+    Some(range.start..end.to_display_point(map))
+}
+
 fn surrounding_html_tag(
     map: &DisplaySnapshot,
     selection: Selection<DisplayPoint>,
@@ -1396,6 +1432,23 @@ mod test {
         );
     }
 
+    #[gpui::test]
+    async fn test_subword_object(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("let fooˇBarBaz = 1;", Mode::Normal);
+        cx.simulate_keystrokes("d i , w");
+        cx.assert_state("let fooˇBaz = 1;", Mode::Normal);
+
+        cx.set_state("let foo_bˇar_baz = 1;", Mode::Normal);
+        cx.simulate_keystrokes("d a , w");
+        cx.assert_state("let foo_ˇbaz = 1;", Mode::Normal);
+
+        cx.set_state("let fooBˇarBaz = 1;", Mode::Normal);
+        cx.simulate_keystrokes("v i , w");
+        cx.assert_state("let foo«Barˇ»Baz = 1;", Mode::Visual);
+    }
+
     #[gpui::test]
     async fn test_argument_object(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
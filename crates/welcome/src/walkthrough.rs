@@ -0,0 +1,93 @@
+use crate::BaseKeymap;
+use collections::HashSet;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::AppContext;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A common action whose keybinding the welcome page teaches. A step is completed by using its
+/// keybinding, so clicking through the walkthrough doesn't count.
+pub(crate) struct WalkthroughStep {
+    pub action_name: &'static str,
+    pub description: &'static str,
+}
+
+pub(crate) const WALKTHROUGH_STEPS: &[WalkthroughStep] = &[
+    WalkthroughStep {
+        action_name: "command_palette::Toggle",
+        description: "Run any command",
+    },
+    WalkthroughStep {
+        action_name: "file_finder::Toggle",
+        description: "Open a file by name",
+    },
+    WalkthroughStep {
+        action_name: "pane::DeploySearch",
+        description: "Search the whole project",
+    },
+    WalkthroughStep {
+        action_name: "editor::SelectNext",
+        description: "Add a cursor at the next occurrence",
+    },
+    WalkthroughStep {
+        action_name: "pane::SplitRight",
+        description: "Split the editor",
+    },
+    WalkthroughStep {
+        action_name: "workspace::ToggleLeftDock",
+        description: "Show or hide the project panel",
+    },
+    WalkthroughStep {
+        action_name: "terminal_panel::ToggleFocus",
+        description: "Open the terminal",
+    },
+];
+
+pub(crate) const TIPS: &[&str] = &[
+    "Hold alt while clicking to add another cursor.",
+    "Double-click a tab to keep a preview tab open.",
+    "Drag a tab to the edge of a pane to split it.",
+    "Type a line number after a file name in the file finder, like main.rs:42, to jump to it.",
+    "Run `pane: reopen closed item` to bring back the last tab you closed.",
+    "Project search results are an editable multibuffer, so you can fix every match in place.",
+    "Put a .zed/settings.json in a project to give it its own settings.",
+    "Select a block and run `editor: split selection into lines` to edit each line at once.",
+];
+
+/// The tip shown when the welcome page opens, which changes daily.
+pub(crate) fn tip_of_the_day() -> usize {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / (60 * 60 * 24));
+    days as usize % TIPS.len()
+}
+
+/// Progress is tracked per base keymap, since each teaches different keybindings.
+fn progress_key(base_keymap: BaseKeymap) -> String {
+    format!("welcome_walkthrough_{base_keymap:?}")
+}
+
+pub(crate) fn completed_steps(base_keymap: BaseKeymap) -> HashSet<&'static str> {
+    let Ok(Some(completed)) = KEY_VALUE_STORE.read_kvp(&progress_key(base_keymap)) else {
+        return HashSet::default();
+    };
+    WALKTHROUGH_STEPS
+        .iter()
+        .map(|step| step.action_name)
+        .filter(|action_name| completed.split(',').any(|name| name == *action_name))
+        .collect()
+}
+
+pub(crate) fn save_completed_steps(
+    base_keymap: BaseKeymap,
+    completed: &HashSet<&'static str>,
+    cx: &mut AppContext,
+) {
+    let key = progress_key(base_keymap);
+    let value = WALKTHROUGH_STEPS
+        .iter()
+        .map(|step| step.action_name)
+        .filter(|action_name| completed.contains(action_name))
+        .collect::<Vec<_>>()
+        .join(",");
+    db::write_and_log(cx, || KEY_VALUE_STORE.write_kvp(key, value));
+}
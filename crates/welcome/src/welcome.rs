@@ -1,26 +1,33 @@
 mod base_keymap_picker;
 mod base_keymap_setting;
 mod multibuffer_hint;
+mod walkthrough;
+mod welcome_settings;
 
 use client::{telemetry::Telemetry, TelemetrySettings};
+use collections::HashSet;
 use db::kvp::KEY_VALUE_STORE;
 use gpui::{
-    actions, svg, AppContext, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
+    actions, svg, Action, AppContext, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
     ParentElement, Render, Styled, Subscription, Task, View, ViewContext, VisualContext, WeakView,
     WindowContext,
 };
 use settings::{Settings, SettingsStore};
 use std::sync::Arc;
-use ui::{prelude::*, CheckboxWithLabel};
+use ui::{prelude::*, CheckboxWithLabel, KeyBinding, Tooltip};
+use util::{paths::PathExt, ResultExt};
 use vim::VimModeSetting;
+use walkthrough::{TIPS, WALKTHROUGH_STEPS};
 use workspace::{
     dock::DockPosition,
     item::{Item, ItemEvent},
-    open_new, AppState, Welcome, Workspace, WorkspaceId,
+    open_new, AppState, LocalPaths, NewFile, Open, SerializedWorkspaceLocation, Welcome, Workspace,
+    WorkspaceId, WORKSPACE_DB,
 };
 
 pub use base_keymap_setting::BaseKeymap;
 pub use multibuffer_hint::*;
+pub use welcome_settings::WelcomeSettings;
 
 actions!(welcome, [ResetHints]);
 
@@ -29,6 +36,7 @@ pub const DOCS_URL: &str = "https://zed.dev/docs/";
 
 pub fn init(cx: &mut AppContext) {
     BaseKeymap::register(cx);
+    WelcomeSettings::register(cx);
 
     cx.observe_new_views(|workspace: &mut Workspace, _cx| {
         workspace.register_action(|workspace, _: &Welcome, cx| {
@@ -64,20 +72,27 @@ pub struct WelcomePage {
     workspace: WeakView<Workspace>,
     focus_handle: FocusHandle,
     telemetry: Arc<Telemetry>,
+    recent_projects: Vec<LocalPaths>,
+    /// The walkthrough steps completed with the current base keymap.
+    completed_steps: HashSet<&'static str>,
+    tip_ix: usize,
     _settings_subscription: Subscription,
+    _keystroke_subscription: Subscription,
 }
 
 impl Render for WelcomePage {
     fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
         h_flex()
+            .id("welcome-page")
             .size_full()
+            .overflow_y_scroll()
             .bg(cx.theme().colors().editor_background)
             .track_focus(&self.focus_handle)
             .child(
                 v_flex()
-                    .w_80()
                     .gap_6()
                     .mx_auto()
+                    .py_8()
                     .child(
                         svg()
                             .path("icons/logo_96.svg")
@@ -86,181 +101,20 @@ impl Render for WelcomePage {
                             .h(px(80.))
                             .mx_auto(),
                     )
+                    .child(self.render_quick_actions(cx))
                     .child(
-                        v_flex()
-                            .gap_2()
-                            .child(
-                                Button::new("choose-theme", "Choose Theme")
-                                    .full_width()
-                                    .on_click(cx.listener(|this, _, cx| {
-                                        this.telemetry.report_app_event(
-                                            "welcome page: change theme".to_string(),
-                                        );
-                                        this.workspace
-                                            .update(cx, |workspace, cx| {
-                                                theme_selector::toggle(
-                                                    workspace,
-                                                    &Default::default(),
-                                                    cx,
-                                                )
-                                            })
-                                            .ok();
-                                    })),
-                            )
-                            .child(
-                                Button::new("choose-keymap", "Choose Keymap")
-                                    .full_width()
-                                    .on_click(cx.listener(|this, _, cx| {
-                                        this.telemetry.report_app_event(
-                                            "welcome page: change keymap".to_string(),
-                                        );
-                                        this.workspace
-                                            .update(cx, |workspace, cx| {
-                                                base_keymap_picker::toggle(
-                                                    workspace,
-                                                    &Default::default(),
-                                                    cx,
-                                                )
-                                            })
-                                            .ok();
-                                    })),
-                            )
-                            .child(
-                                Button::new("edit settings", "Edit Settings")
-                                    .full_width()
-                                    .on_click(cx.listener(|this, _, cx| {
-                                        this.telemetry.report_app_event(
-                                            "welcome page: edit settings".to_string(),
-                                        );
-                                        cx.dispatch_action(Box::new(zed_actions::OpenSettings));
-                                    })),
-                            )
-                            .child(Button::new("view docs", "View Docs").full_width().on_click(
-                                cx.listener(|this, _, cx| {
-                                    this.telemetry
-                                        .report_app_event("welcome page: view docs".to_string());
-                                    cx.open_url(DOCS_URL);
-                                }),
-                            )),
-                    )
-                    .child(
-                        v_flex()
-                            .gap_2()
-                            .when(cfg!(target_os = "macos"), |el| {
-                                el.child(
-                                    Button::new("install-cli", "Install the CLI")
-                                        .full_width()
-                                        .on_click(cx.listener(|this, _, cx| {
-                                            this.telemetry.report_app_event(
-                                                "welcome page: install cli".to_string(),
-                                            );
-                                            cx.app_mut()
-                                                .spawn(|cx| async move {
-                                                    install_cli::install_cli(&cx).await
-                                                })
-                                                .detach_and_log_err(cx);
-                                        })),
-                                )
-                            })
-                            .child(
-                                Button::new("sign-in-to-copilot", "Sign in to GitHub Copilot")
-                                    .full_width()
-                                    .on_click(cx.listener(|this, _, cx| {
-                                        this.telemetry.report_app_event(
-                                            "welcome page: sign in to copilot".to_string(),
-                                        );
-                                        inline_completion_button::initiate_sign_in(cx);
-                                    })),
-                            )
+                        h_flex()
+                            .gap_8()
+                            .items_start()
+                            .child(self.render_setup(cx))
                             .child(
-                                Button::new("explore extensions", "Explore extensions")
-                                    .full_width()
-                                    .on_click(cx.listener(|this, _, cx| {
-                                        this.telemetry.report_app_event(
-                                            "welcome page: open extensions".to_string(),
-                                        );
-                                        cx.dispatch_action(Box::new(extensions_ui::Extensions));
-                                    })),
+                                v_flex()
+                                    .w_96()
+                                    .gap_6()
+                                    .child(self.render_recent_projects(cx))
+                                    .child(self.render_walkthrough(cx))
+                                    .child(self.render_tip(cx)),
                             ),
-                    )
-                    .child(
-                        v_flex()
-                            .p_3()
-                            .gap_2()
-                            .bg(cx.theme().colors().elevated_surface_background)
-                            .border_1()
-                            .border_color(cx.theme().colors().border)
-                            .rounded_md()
-                            .child(CheckboxWithLabel::new(
-                                "enable-vim",
-                                Label::new("Enable vim mode"),
-                                if VimModeSetting::get_global(cx).0 {
-                                    ui::Selection::Selected
-                                } else {
-                                    ui::Selection::Unselected
-                                },
-                                cx.listener(move |this, selection, cx| {
-                                    this.telemetry
-                                        .report_app_event("welcome page: toggle vim".to_string());
-                                    this.update_settings::<VimModeSetting>(
-                                        selection,
-                                        cx,
-                                        |setting, value| *setting = Some(value),
-                                    );
-                                }),
-                            ))
-                            .child(CheckboxWithLabel::new(
-                                "enable-telemetry",
-                                Label::new("Send anonymous usage data"),
-                                if TelemetrySettings::get_global(cx).metrics {
-                                    ui::Selection::Selected
-                                } else {
-                                    ui::Selection::Unselected
-                                },
-                                cx.listener(move |this, selection, cx| {
-                                    this.telemetry.report_app_event(
-                                        "welcome page: toggle metric telemetry".to_string(),
-                                    );
-                                    this.update_settings::<TelemetrySettings>(selection, cx, {
-                                        let telemetry = this.telemetry.clone();
-
-                                        move |settings, value| {
-                                            settings.metrics = Some(value);
-
-                                            telemetry.report_setting_event(
-                                                "metric telemetry",
-                                                value.to_string(),
-                                            );
-                                        }
-                                    });
-                                }),
-                            ))
-                            .child(CheckboxWithLabel::new(
-                                "enable-crash",
-                                Label::new("Send crash reports"),
-                                if TelemetrySettings::get_global(cx).diagnostics {
-                                    ui::Selection::Selected
-                                } else {
-                                    ui::Selection::Unselected
-                                },
-                                cx.listener(move |this, selection, cx| {
-                                    this.telemetry.report_app_event(
-                                        "welcome page: toggle diagnostic telemetry".to_string(),
-                                    );
-                                    this.update_settings::<TelemetrySettings>(selection, cx, {
-                                        let telemetry = this.telemetry.clone();
-
-                                        move |settings, value| {
-                                            settings.diagnostics = Some(value);
-
-                                            telemetry.report_setting_event(
-                                                "diagnostic telemetry",
-                                                value.to_string(),
-                                            );
-                                        }
-                                    });
-                                }),
-                            )),
                     ),
             )
     }
@@ -275,18 +129,471 @@ impl WelcomePage {
             })
             .detach();
 
-            WelcomePage {
-                focus_handle: cx.focus_handle(),
-                workspace: workspace.weak_handle(),
-                telemetry: workspace.client().telemetry().clone(),
-                _settings_subscription: cx
-                    .observe_global::<SettingsStore>(move |_, cx| cx.notify()),
-            }
+            WelcomePage::build(
+                workspace.weak_handle(),
+                workspace.client().telemetry().clone(),
+                cx,
+            )
         });
 
         this
     }
 
+    fn build(
+        workspace: WeakView<Workspace>,
+        telemetry: Arc<Telemetry>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        cx.spawn(|this, mut cx| async move {
+            let workspaces = WORKSPACE_DB.recent_workspaces_on_disk().await?;
+            this.update(&mut cx, |this, cx| {
+                this.recent_projects = workspaces
+                    .into_iter()
+                    .filter_map(|(_, location)| match location {
+                        SerializedWorkspaceLocation::Local(paths, _) => Some(paths),
+                        _ => None,
+                    })
+                    .collect();
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+
+        WelcomePage {
+            focus_handle: cx.focus_handle(),
+            workspace,
+            telemetry,
+            recent_projects: Vec::new(),
+            completed_steps: walkthrough::completed_steps(*BaseKeymap::get_global(cx)),
+            tip_ix: walkthrough::tip_of_the_day(),
+            _settings_subscription: cx.observe_global::<SettingsStore>(move |this, cx| {
+                this.completed_steps = walkthrough::completed_steps(*BaseKeymap::get_global(cx));
+                cx.notify();
+            }),
+            _keystroke_subscription: cx.observe_keystrokes(|this, event, cx| {
+                let Some(action) = event.action.as_ref() else {
+                    return;
+                };
+                let Some(step) = WALKTHROUGH_STEPS
+                    .iter()
+                    .find(|step| step.action_name == action.name())
+                else {
+                    return;
+                };
+                if this.completed_steps.insert(step.action_name) {
+                    walkthrough::save_completed_steps(
+                        *BaseKeymap::get_global(cx),
+                        &this.completed_steps,
+                        cx,
+                    );
+                    cx.notify();
+                }
+            }),
+        }
+    }
+
+    fn render_quick_actions(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        fn quick_action(
+            id: &'static str,
+            label: &'static str,
+            icon: IconName,
+            action: Box<dyn Action>,
+            cx: &mut ViewContext<WelcomePage>,
+        ) -> Button {
+            Button::new(id, label)
+                .style(ButtonStyle::Filled)
+                .icon(icon)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Muted)
+                .on_click(cx.listener(move |this, _, cx| {
+                    this.telemetry
+                        .report_app_event(format!("welcome page: {}", action.name()));
+                    cx.dispatch_action(action.boxed_clone());
+                }))
+        }
+
+        // Cloning is offered once something handles it.
+        let can_clone = cx.is_action_available(&zed_actions::CloneRepository);
+
+        h_flex()
+            .gap_2()
+            .justify_center()
+            .child(quick_action(
+                "new-file",
+                "New File",
+                IconName::Plus,
+                Box::new(NewFile),
+                cx,
+            ))
+            .child(quick_action(
+                "open-folder",
+                "Open Folder",
+                IconName::FolderOpen,
+                Box::new(Open),
+                cx,
+            ))
+            .when(can_clone, |el| {
+                el.child(quick_action(
+                    "clone-repository",
+                    "Clone Repository",
+                    IconName::Download,
+                    Box::new(zed_actions::CloneRepository),
+                    cx,
+                ))
+            })
+    }
+
+    fn render_recent_projects(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let max_recent_projects = WelcomeSettings::get_global(cx).max_recent_projects;
+        let recent_projects =
+            &self.recent_projects[..max_recent_projects.min(self.recent_projects.len())];
+        if recent_projects.is_empty() {
+            return div().into_any_element();
+        }
+
+        let colors = cx.theme().colors();
+        v_flex()
+            .gap_2()
+            .child(section_header("Recent Projects"))
+            .children(recent_projects.chunks(2).enumerate().map(|(row_ix, row)| {
+                h_flex()
+                    .gap_2()
+                    .children(row.iter().enumerate().map(|(ix, paths)| {
+                        let name = paths
+                            .paths()
+                            .iter()
+                            .filter_map(|path| path.file_name())
+                            .map(|name| name.to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let location = paths
+                            .paths()
+                            .iter()
+                            .map(|path| path.compact().to_string_lossy().into_owned())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let paths = paths.clone();
+
+                        v_flex()
+                            .id(("recent-project", row_ix * 2 + ix))
+                            .flex_1()
+                            .min_w_0()
+                            .p_2()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(colors.border)
+                            .bg(colors.elevated_surface_background)
+                            .hover(|style| style.bg(colors.element_hover))
+                            .cursor_pointer()
+                            .child(div().text_ellipsis().child(Label::new(name)))
+                            .child(
+                                div().text_ellipsis().child(
+                                    Label::new(location)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                            )
+                            .on_click(
+                                cx.listener(move |this, _, cx| {
+                                    this.open_recent_project(&paths, cx)
+                                }),
+                            )
+                    }))
+            }))
+            .into_any_element()
+    }
+
+    fn open_recent_project(&mut self, paths: &LocalPaths, cx: &mut ViewContext<Self>) {
+        self.telemetry
+            .report_app_event("welcome page: open recent project".to_string());
+        let paths = paths.paths().to_vec();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_workspace_for_paths(false, paths, cx)
+                    .detach_and_log_err(cx)
+            })
+            .ok();
+    }
+
+    fn render_walkthrough(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let title = match *BaseKeymap::get_global(cx) {
+            BaseKeymap::None => "Learn the Keybindings".to_string(),
+            base_keymap => format!("Learn the {base_keymap} Keybindings"),
+        };
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(section_header(title))
+                    .child(
+                        Label::new(format!(
+                            "{} of {}",
+                            self.completed_steps.len(),
+                            WALKTHROUGH_STEPS.len()
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .children(WALKTHROUGH_STEPS.iter().filter_map(|step| {
+                let action = cx.build_action(step.action_name, None).log_err()?;
+                let completed = self.completed_steps.contains(step.action_name);
+                Some(
+                    h_flex()
+                        .gap_2()
+                        .justify_between()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Icon::new(if completed {
+                                        IconName::Check
+                                    } else {
+                                        IconName::Dash
+                                    })
+                                    .size(IconSize::Small)
+                                    .color(if completed {
+                                        Color::Success
+                                    } else {
+                                        Color::Muted
+                                    }),
+                                )
+                                .child(Label::new(step.description).color(if completed {
+                                    Color::Muted
+                                } else {
+                                    Color::Default
+                                })),
+                        )
+                        .children(KeyBinding::for_action(&*action, cx)),
+                )
+            }))
+    }
+
+    fn render_tip(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .p_3()
+            .gap_2()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_md()
+            .child(
+                Icon::new(IconName::Sparkle)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .child(Label::new(TIPS[self.tip_ix]).size(LabelSize::Small)),
+            )
+            .child(
+                IconButton::new("next-tip", IconName::ChevronRight)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Next Tip", cx))
+                    .on_click(cx.listener(|this, _, cx| {
+                        this.tip_ix = (this.tip_ix + 1) % TIPS.len();
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_setup(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .w_80()
+            .gap_6()
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("choose-theme", "Choose Theme")
+                            .full_width()
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.telemetry
+                                    .report_app_event("welcome page: change theme".to_string());
+                                this.workspace
+                                    .update(cx, |workspace, cx| {
+                                        theme_selector::toggle(workspace, &Default::default(), cx)
+                                    })
+                                    .ok();
+                            })),
+                    )
+                    .child(
+                        Button::new("choose-keymap", "Choose Keymap")
+                            .full_width()
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.telemetry
+                                    .report_app_event("welcome page: change keymap".to_string());
+                                this.workspace
+                                    .update(cx, |workspace, cx| {
+                                        base_keymap_picker::toggle(
+                                            workspace,
+                                            &Default::default(),
+                                            cx,
+                                        )
+                                    })
+                                    .ok();
+                            })),
+                    )
+                    .child(
+                        Button::new("edit settings", "Edit Settings")
+                            .full_width()
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.telemetry
+                                    .report_app_event("welcome page: edit settings".to_string());
+                                cx.dispatch_action(Box::new(zed_actions::OpenSettings));
+                            })),
+                    )
+                    .child(Button::new("view docs", "View Docs").full_width().on_click(
+                        cx.listener(|this, _, cx| {
+                            this.telemetry
+                                .report_app_event("welcome page: view docs".to_string());
+                            cx.open_url(DOCS_URL);
+                        }),
+                    )),
+            )
+            .child(
+                v_flex()
+                    .gap_2()
+                    .when(cfg!(target_os = "macos"), |el| {
+                        el.child(
+                            Button::new("install-cli", "Install the CLI")
+                                .full_width()
+                                .on_click(cx.listener(|this, _, cx| {
+                                    this.telemetry
+                                        .report_app_event("welcome page: install cli".to_string());
+                                    cx.app_mut()
+                                        .spawn(
+                                            |cx| async move { install_cli::install_cli(&cx).await },
+                                        )
+                                        .detach_and_log_err(cx);
+                                })),
+                        )
+                    })
+                    .child(
+                        Button::new("sign-in-to-copilot", "Sign in to GitHub Copilot")
+                            .full_width()
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.telemetry.report_app_event(
+                                    "welcome page: sign in to copilot".to_string(),
+                                );
+                                inline_completion_button::initiate_sign_in(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("explore extensions", "Explore extensions")
+                            .full_width()
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.telemetry
+                                    .report_app_event("welcome page: open extensions".to_string());
+                                cx.dispatch_action(Box::new(extensions_ui::Extensions));
+                            })),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .p_3()
+                    .gap_2()
+                    .bg(cx.theme().colors().elevated_surface_background)
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .child(CheckboxWithLabel::new(
+                        "enable-vim",
+                        Label::new("Enable vim mode"),
+                        if VimModeSetting::get_global(cx).0 {
+                            ui::Selection::Selected
+                        } else {
+                            ui::Selection::Unselected
+                        },
+                        cx.listener(move |this, selection, cx| {
+                            this.telemetry
+                                .report_app_event("welcome page: toggle vim".to_string());
+                            this.update_settings::<VimModeSetting>(
+                                selection,
+                                cx,
+                                |setting, value| *setting = Some(value),
+                            );
+                        }),
+                    ))
+                    .child(CheckboxWithLabel::new(
+                        "enable-telemetry",
+                        Label::new("Send anonymous usage data"),
+                        if TelemetrySettings::get_global(cx).metrics {
+                            ui::Selection::Selected
+                        } else {
+                            ui::Selection::Unselected
+                        },
+                        cx.listener(move |this, selection, cx| {
+                            this.telemetry.report_app_event(
+                                "welcome page: toggle metric telemetry".to_string(),
+                            );
+                            this.update_settings::<TelemetrySettings>(selection, cx, {
+                                let telemetry = this.telemetry.clone();
+
+                                move |settings, value| {
+                                    settings.metrics = Some(value);
+
+                                    telemetry.report_setting_event(
+                                        "metric telemetry",
+                                        value.to_string(),
+                                    );
+                                }
+                            });
+                        }),
+                    ))
+                    .child(CheckboxWithLabel::new(
+                        "enable-crash",
+                        Label::new("Send crash reports"),
+                        if TelemetrySettings::get_global(cx).diagnostics {
+                            ui::Selection::Selected
+                        } else {
+                            ui::Selection::Unselected
+                        },
+                        cx.listener(move |this, selection, cx| {
+                            this.telemetry.report_app_event(
+                                "welcome page: toggle diagnostic telemetry".to_string(),
+                            );
+                            this.update_settings::<TelemetrySettings>(selection, cx, {
+                                let telemetry = this.telemetry.clone();
+
+                                move |settings, value| {
+                                    settings.diagnostics = Some(value);
+
+                                    telemetry.report_setting_event(
+                                        "diagnostic telemetry",
+                                        value.to_string(),
+                                    );
+                                }
+                            });
+                        }),
+                    ))
+                    .child(CheckboxWithLabel::new(
+                        "show-on-startup",
+                        Label::new("Show this page on startup"),
+                        if WelcomeSettings::get_global(cx).show_on_startup {
+                            ui::Selection::Selected
+                        } else {
+                            ui::Selection::Unselected
+                        },
+                        cx.listener(move |this, selection, cx| {
+                            this.telemetry.report_app_event(
+                                "welcome page: toggle show on startup".to_string(),
+                            );
+                            this.update_settings::<WelcomeSettings>(
+                                selection,
+                                cx,
+                                |settings, value| settings.show_on_startup = Some(value),
+                            );
+                        }),
+                    )),
+            )
+    }
+
     fn update_settings<T: Settings>(
         &mut self,
         selection: &Selection,
@@ -337,15 +644,18 @@ impl Item for WelcomePage {
         _workspace_id: Option<WorkspaceId>,
         cx: &mut ViewContext<Self>,
     ) -> Option<View<Self>> {
-        Some(cx.new_view(|cx| WelcomePage {
-            focus_handle: cx.focus_handle(),
-            workspace: self.workspace.clone(),
-            telemetry: self.telemetry.clone(),
-            _settings_subscription: cx.observe_global::<SettingsStore>(move |_, cx| cx.notify()),
-        }))
+        Some(
+            cx.new_view(|cx| {
+                WelcomePage::build(self.workspace.clone(), self.telemetry.clone(), cx)
+            }),
+        )
     }
 
     fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
         f(*event)
     }
 }
+
+fn section_header(title: impl Into<SharedString>) -> Label {
+    Label::new(title).size(LabelSize::Small).color(Color::Muted)
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize)]
+pub struct WelcomeSettings {
+    pub show_on_startup: bool,
+    pub max_recent_projects: usize,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WelcomeSettingsContent {
+    /// Whether to show the welcome page when Zed starts without a project to restore.
+    /// When disabled, an empty file is opened instead.
+    ///
+    /// Default: true
+    pub show_on_startup: Option<bool>,
+    /// The maximum number of recent projects to list on the welcome page.
+    ///
+    /// Default: 6
+    pub max_recent_projects: Option<usize>,
+}
+
+impl Settings for WelcomeSettings {
+    const KEY: Option<&'static str> = Some("welcome");
+
+    type FileContent = WelcomeSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
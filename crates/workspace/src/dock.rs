@@ -3,10 +3,11 @@ use crate::{status_bar::StatusItemView, Workspace};
 use crate::{DraggedDock, Event, Pane};
 use client::proto;
 use gpui::{
-    deferred, div, px, Action, AnchorCorner, AnyView, AppContext, Axis, Entity, EntityId,
-    EventEmitter, FocusHandle, FocusableView, IntoElement, KeyContext, MouseButton, MouseDownEvent,
-    MouseUpEvent, ParentElement, Render, SharedString, StyleRefinement, Styled, Subscription, View,
-    ViewContext, VisualContext, WeakView, WindowContext,
+    deferred, div, px, size, Action, AnchorCorner, AnyView, AnyWindowHandle, AppContext, Axis,
+    Bounds, Entity, EntityId, EventEmitter, FocusHandle, FocusableView, IntoElement, KeyContext,
+    MouseButton, MouseDownEvent, MouseUpEvent, ParentElement, Render, SharedString,
+    StyleRefinement, Styled, Subscription, TitlebarOptions, View, ViewContext, VisualContext,
+    WeakView, WindowBounds, WindowContext, WindowHandle, WindowOptions,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,8 +15,11 @@ use settings::SettingsStore;
 use std::sync::Arc;
 use ui::{h_flex, ContextMenu, IconButton, Tooltip};
 use ui::{prelude::*, right_click_menu};
+use util::ResultExt;
 
 const RESIZE_HANDLE_SIZE: Pixels = Pixels(6.);
+/// The length of a floating panel's window along its dock's axis, which has no size of its own.
+const FLOATING_PANEL_LENGTH: Pixels = Pixels(720.);
 
 pub enum PanelEvent {
     ZoomIn,
@@ -45,6 +49,11 @@ pub trait Panel: FocusableView + EventEmitter<PanelEvent> {
     fn starts_open(&self, _cx: &WindowContext) -> bool {
         false
     }
+    /// Whether the panel should be drawn to a texture that's reused while it paints the same,
+    /// which saves drawing large panels that rarely change.
+    fn cache_to_texture(&self, _cx: &WindowContext) -> bool {
+        false
+    }
     fn set_zoomed(&mut self, _zoomed: bool, _cx: &mut ViewContext<Self>) {}
     fn set_active(&mut self, _active: bool, _cx: &mut ViewContext<Self>) {}
     fn pane(&self) -> Option<View<Pane>> {
@@ -72,6 +81,7 @@ pub trait PanelHandle: Send + Sync {
     fn icon_tooltip(&self, cx: &WindowContext) -> Option<&'static str>;
     fn toggle_action(&self, cx: &WindowContext) -> Box<dyn Action>;
     fn icon_label(&self, cx: &WindowContext) -> Option<String>;
+    fn cache_to_texture(&self, cx: &WindowContext) -> bool;
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle;
     fn to_any(&self) -> AnyView;
 }
@@ -144,6 +154,10 @@ where
         self.read(cx).icon_label(cx)
     }
 
+    fn cache_to_texture(&self, cx: &WindowContext) -> bool {
+        self.read(cx).cache_to_texture(cx)
+    }
+
     fn to_any(&self) -> AnyView {
         self.clone().into()
     }
@@ -169,7 +183,7 @@ pub struct Dock {
     focus_handle: FocusHandle,
     pub(crate) serialized_dock: Option<DockData>,
     resizeable: bool,
-    _subscriptions: [Subscription; 2],
+    _subscriptions: [Subscription; 3],
 }
 
 impl FocusableView for Dock {
@@ -205,6 +219,8 @@ impl DockPosition {
 
 struct PanelEntry {
     panel: Arc<dyn PanelHandle>,
+    /// The window the panel has been popped out into, if it's floating.
+    floating_window: Option<WindowHandle<FloatingPanel>>,
     _subscriptions: [Subscription; 3],
 }
 
@@ -218,7 +234,7 @@ impl Dock {
         let workspace = cx.view().clone();
         let dock = cx.new_view(|cx: &mut ViewContext<Self>| {
             let focus_subscription = cx.on_focus(&focus_handle, |dock, cx| {
-                if let Some(active_entry) = dock.panel_entries.get(dock.active_panel_index) {
+                if let Some(active_entry) = dock.visible_entry() {
                     active_entry.panel.focus_handle(cx).focus(cx)
                 }
             });
@@ -228,13 +244,21 @@ impl Dock {
                     dock.resizeable = !is_zoomed;
                 }
             });
+            // Floating panels can't outlive their workspace's window.
+            let release_subscription = cx.on_release(|dock, _, cx| {
+                for entry in &mut dock.panel_entries {
+                    if let Some(window) = entry.floating_window.take() {
+                        window.update(cx, |_, cx| cx.remove_window()).ok();
+                    }
+                }
+            });
             Self {
                 position,
                 panel_entries: Default::default(),
                 active_panel_index: 0,
                 is_open: false,
                 focus_handle: focus_handle.clone(),
-                _subscriptions: [focus_subscription, zoom_subscription],
+                _subscriptions: [focus_subscription, zoom_subscription, release_subscription],
                 serialized_dock: None,
                 resizeable: true,
             }
@@ -328,7 +352,7 @@ impl Dock {
     pub(crate) fn set_open(&mut self, open: bool, cx: &mut ViewContext<Self>) {
         if open != self.is_open {
             self.is_open = open;
-            if let Some(active_panel) = self.panel_entries.get(self.active_panel_index) {
+            if let Some(active_panel) = self.docked_entry(self.active_panel_index) {
                 active_panel.panel.set_active(open, cx);
             }
 
@@ -440,13 +464,18 @@ impl Dock {
                         .iter()
                         .position(|entry| entry.panel.panel_id() == Entity::entity_id(&panel))
                     {
+                        if this.activate_floating_panel(ix, cx) {
+                            return;
+                        }
                         this.set_open(true, cx);
                         this.activate_panel(ix, cx);
                         cx.focus_view(&panel);
                     }
                 }
                 PanelEvent::Close => {
-                    if this
+                    if let Some(ix) = this.floating_panel_index(Entity::entity_id(&panel)) {
+                        this.dock_panel(ix, false, cx);
+                    } else if this
                         .visible_panel()
                         .map_or(false, |p| p.panel_id() == Entity::entity_id(&panel))
                     {
@@ -458,6 +487,7 @@ impl Dock {
 
         self.panel_entries.push(PanelEntry {
             panel: Arc::new(panel.clone()),
+            floating_window: None,
             _subscriptions: subscriptions,
         });
 
@@ -494,6 +524,9 @@ impl Dock {
             .iter()
             .position(|entry| entry.panel.panel_id() == Entity::entity_id(panel))
         {
+            if let Some(window) = self.panel_entries[panel_ix].floating_window.take() {
+                window.update(cx, |_, cx| cx.remove_window()).ok();
+            }
             match panel_ix.cmp(&self.active_panel_index) {
                 std::cmp::Ordering::Less => {
                     self.active_panel_index -= 1;
@@ -515,12 +548,12 @@ impl Dock {
 
     pub fn activate_panel(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) {
         if panel_ix != self.active_panel_index {
-            if let Some(active_panel) = self.panel_entries.get(self.active_panel_index) {
+            if let Some(active_panel) = self.docked_entry(self.active_panel_index) {
                 active_panel.panel.set_active(false, cx);
             }
 
             self.active_panel_index = panel_ix;
-            if let Some(active_panel) = self.panel_entries.get(self.active_panel_index) {
+            if let Some(active_panel) = self.docked_entry(self.active_panel_index) {
                 active_panel.panel.set_active(true, cx);
             }
 
@@ -528,6 +561,123 @@ impl Dock {
         }
     }
 
+    /// Whether the panel at the given index has been popped out into a window of its own.
+    pub fn is_panel_floating(&self, panel_ix: usize) -> bool {
+        self.panel_entries
+            .get(panel_ix)
+            .map_or(false, |entry| entry.floating_window.is_some())
+    }
+
+    fn floating_panel_index(&self, panel_id: EntityId) -> Option<usize> {
+        self.panel_entries
+            .iter()
+            .position(|entry| entry.panel.panel_id() == panel_id && entry.floating_window.is_some())
+    }
+
+    fn docked_entry(&self, panel_ix: usize) -> Option<&PanelEntry> {
+        self.panel_entries
+            .get(panel_ix)
+            .filter(|entry| entry.floating_window.is_none())
+    }
+
+    /// Pops the panel at the given index out of the dock into a window of its own, keeping the
+    /// panel's state, so that it can be moved to another screen.
+    pub fn float_panel(&mut self, panel_ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(entry) = self.docked_entry(panel_ix) else {
+            return;
+        };
+        let panel = entry.panel.clone();
+        let window_size = match self.position.axis() {
+            Axis::Horizontal => size(panel.size(cx), FLOATING_PANEL_LENGTH),
+            Axis::Vertical => size(FLOATING_PANEL_LENGTH, panel.size(cx)),
+        };
+        let options = WindowOptions {
+            titlebar: Some(TitlebarOptions {
+                title: panel.icon_tooltip(cx).map(SharedString::from),
+                ..Default::default()
+            }),
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                window_size,
+                cx,
+            ))),
+            ..Default::default()
+        };
+        let dock = cx.view().downgrade();
+        let dock_window = cx.window_handle();
+        let Some(window) = cx
+            .open_window(options, |cx| {
+                cx.new_view(|cx| FloatingPanel::new(panel.clone(), dock, dock_window, cx))
+            })
+            .log_err()
+        else {
+            return;
+        };
+
+        let was_visible = self.is_open && panel_ix == self.active_panel_index;
+        self.panel_entries[panel_ix].floating_window = Some(window);
+        if panel_ix == self.active_panel_index {
+            // Show another docked panel in its place, or close the dock if there are none.
+            match (0..self.panel_entries.len()).find(|ix| self.docked_entry(*ix).is_some()) {
+                Some(ix) => {
+                    self.active_panel_index = ix;
+                    if self.is_open {
+                        self.panel_entries[ix].panel.set_active(true, cx);
+                    }
+                }
+                None => self.is_open = false,
+            }
+        }
+        if !was_visible {
+            panel.set_active(true, cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns the floating panel at the given index to the dock, closing its window. The
+    /// panel is revealed if `reveal` is true.
+    pub fn dock_panel(&mut self, panel_ix: usize, reveal: bool, cx: &mut ViewContext<Self>) {
+        let Some(window) = self
+            .panel_entries
+            .get_mut(panel_ix)
+            .and_then(|entry| entry.floating_window.take())
+        else {
+            return;
+        };
+        window.update(cx, |_, cx| cx.remove_window()).ok();
+
+        let panel = self.panel_entries[panel_ix].panel.clone();
+        if reveal {
+            if let Some(active_panel) = self.visible_entry() {
+                active_panel.panel.set_active(false, cx);
+            }
+            self.active_panel_index = panel_ix;
+            self.is_open = true;
+            panel.focus_handle(cx).focus(cx);
+        } else if !(self.is_open && panel_ix == self.active_panel_index) {
+            panel.set_active(false, cx);
+        }
+        cx.notify();
+    }
+
+    /// Brings the window of the panel at the given index to the front and focuses the panel,
+    /// returning false if the panel isn't floating.
+    pub fn activate_floating_panel(&self, panel_ix: usize, cx: &mut WindowContext) -> bool {
+        let Some(entry) = self.panel_entries.get(panel_ix) else {
+            return false;
+        };
+        let Some(window) = entry.floating_window else {
+            return false;
+        };
+        let focus_handle = entry.panel.focus_handle(cx);
+        window
+            .update(cx, |_, cx| {
+                cx.activate_window();
+                focus_handle.focus(cx);
+            })
+            .is_ok()
+    }
+
     pub fn visible_panel(&self) -> Option<&Arc<dyn PanelHandle>> {
         let entry = self.visible_entry()?;
         Some(&entry.panel)
@@ -539,7 +689,7 @@ impl Dock {
 
     fn visible_entry(&self) -> Option<&PanelEntry> {
         if self.is_open {
-            self.panel_entries.get(self.active_panel_index)
+            self.docked_entry(self.active_panel_index)
         } else {
             None
         }
@@ -562,13 +712,7 @@ impl Dock {
     }
 
     pub fn active_panel_size(&self, cx: &WindowContext) -> Option<Pixels> {
-        if self.is_open {
-            self.panel_entries
-                .get(self.active_panel_index)
-                .map(|entry| entry.panel.size(cx))
-        } else {
-            None
-        }
+        self.visible_entry().map(|entry| entry.panel.size(cx))
     }
 
     pub fn resize_active_panel(&mut self, size: Option<Pixels>, cx: &mut ViewContext<Self>) {
@@ -682,7 +826,10 @@ impl Render for Dock {
                             entry
                                 .panel
                                 .to_any()
-                                .cached(StyleRefinement::default().v_flex().size_full()),
+                                .cached(StyleRefinement::default().v_flex().size_full())
+                                .when(entry.panel.cache_to_texture(cx), |panel| {
+                                    panel.cache_to_texture()
+                                }),
                         ),
                 )
                 .when(self.resizeable, |this| this.child(create_resize_handle()))
@@ -694,6 +841,90 @@ impl Render for Dock {
     }
 }
 
+/// The root view of a window that a panel has been popped out into.
+pub struct FloatingPanel {
+    panel: Arc<dyn PanelHandle>,
+    dock: WeakView<Dock>,
+    dock_window: AnyWindowHandle,
+}
+
+impl FloatingPanel {
+    fn new(
+        panel: Arc<dyn PanelHandle>,
+        dock: WeakView<Dock>,
+        dock_window: AnyWindowHandle,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let this = cx.view().downgrade();
+        cx.on_window_should_close(move |cx| {
+            this.update(cx, |this, cx| this.dock_panel(false, cx)).ok();
+            // Docking the panel closes its window.
+            false
+        });
+        cx.focus(&panel.focus_handle(cx));
+        Self {
+            panel,
+            dock,
+            dock_window,
+        }
+    }
+
+    fn dock_panel(&mut self, reveal: bool, cx: &mut ViewContext<Self>) {
+        let panel_id = self.panel.panel_id();
+        // The dock is updated in its own window, where the panel is focused when it's revealed.
+        self.dock_window
+            .update(cx, |_, cx| {
+                self.dock.update(cx, |dock, cx| {
+                    if let Some(ix) = dock.floating_panel_index(panel_id) {
+                        dock.dock_panel(ix, reveal, cx);
+                    }
+                })?;
+                if reveal {
+                    cx.activate_window();
+                }
+                anyhow::Ok(())
+            })
+            .ok();
+        cx.remove_window();
+    }
+}
+
+impl Render for FloatingPanel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let ui_font = theme::setup_ui_font(cx);
+        v_flex()
+            .size_full()
+            .font(ui_font)
+            .bg(cx.theme().colors().panel_background)
+            .text_color(cx.theme().colors().text)
+            .child(
+                h_flex()
+                    .flex_none()
+                    .justify_end()
+                    .px_1()
+                    .py_0p5()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        IconButton::new("dock-panel", IconName::Minimize)
+                            .icon_size(IconSize::Small)
+                            .tooltip(|cx| Tooltip::text("Return to Dock", cx))
+                            .on_click(cx.listener(|this, _, cx| this.dock_panel(true, cx))),
+                    ),
+            )
+            .child(
+                div().flex_1().min_h_0().overflow_hidden().child(
+                    self.panel
+                        .to_any()
+                        .cached(StyleRefinement::default().v_flex().size_full())
+                        .when(self.panel.cache_to_texture(cx), |panel| {
+                            panel.cache_to_texture()
+                        }),
+                ),
+            )
+    }
+}
+
 impl PanelButtons {
     pub fn new(dock: View<Dock>, cx: &mut ViewContext<Self>) -> Self {
         cx.observe(&dock, |_, _, cx| cx.notify()).detach();
@@ -724,8 +955,10 @@ impl Render for PanelButtons {
                 let icon_tooltip = entry.panel.icon_tooltip(cx)?;
                 let name = entry.panel.persistent_name();
                 let panel = entry.panel.clone();
+                let dock = self.dock.clone();
+                let is_floating = entry.floating_window.is_some();
 
-                let is_active_button = i == active_index && is_open;
+                let is_active_button = i == active_index && is_open && !is_floating;
                 let (action, tooltip) = if is_active_button {
                     let action = dock.toggle_action();
 
@@ -749,6 +982,16 @@ impl Render for PanelButtons {
                             ];
 
                             ContextMenu::build(cx, |mut menu, cx| {
+                                let dock = dock.clone();
+                                menu = if is_floating {
+                                    menu.entry("Return to Dock", None, move |cx| {
+                                        dock.update(cx, |dock, cx| dock.dock_panel(i, true, cx))
+                                    })
+                                } else {
+                                    menu.entry("Pop Out", None, move |cx| {
+                                        dock.update(cx, |dock, cx| dock.float_panel(i, cx))
+                                    })
+                                };
                                 for position in POSITIONS {
                                     if position != dock_position
                                         && panel.position_is_valid(position, cx)
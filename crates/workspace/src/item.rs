@@ -41,6 +41,9 @@ pub struct ItemSettings {
     pub git_status: bool,
     pub close_position: ClosePosition,
     pub file_icons: bool,
+    pub path_display: TabPathDisplay,
+    pub directory_first: bool,
+    pub max_title_length: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -67,6 +70,17 @@ impl ClosePosition {
     }
 }
 
+/// Which part of a file's path to show next to its name in a tab.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TabPathDisplay {
+    /// Only show the directory telling tabs with the same name apart.
+    #[default]
+    Minimal,
+    /// Always show the full directory of the file.
+    Full,
+}
+
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ItemSettingsContent {
     /// Whether to show the Git file status on a tab item.
@@ -81,6 +95,18 @@ pub struct ItemSettingsContent {
     ///
     /// Default: false
     file_icons: Option<bool>,
+    /// Which part of a file's path to show next to its name in a tab.
+    ///
+    /// Default: minimal
+    path_display: Option<TabPathDisplay>,
+    /// Whether to show a file's directory before its name in a tab.
+    ///
+    /// Default: false
+    directory_first: Option<bool>,
+    /// The maximum number of characters of a tab's title, past which its middle is truncated.
+    ///
+    /// Default: null
+    max_title_length: Option<usize>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -369,6 +395,7 @@ pub trait ItemHandle: 'static + Send {
     fn tab_tooltip_text(&self, cx: &AppContext) -> Option<SharedString>;
     fn tab_description(&self, detail: usize, cx: &AppContext) -> Option<SharedString>;
     fn tab_content(&self, params: TabContentParams, cx: &WindowContext) -> AnyElement;
+    fn tab_content_text(&self, cx: &WindowContext) -> Option<SharedString>;
     fn tab_icon(&self, cx: &WindowContext) -> Option<Icon>;
     fn telemetry_event_text(&self, cx: &WindowContext) -> Option<&'static str>;
     fn dragged_tab_content(&self, params: TabContentParams, cx: &WindowContext) -> AnyElement;
@@ -485,6 +512,10 @@ impl<T: Item> ItemHandle for View<T> {
         self.read(cx).tab_content(params, cx)
     }
 
+    fn tab_content_text(&self, cx: &WindowContext) -> Option<SharedString> {
+        self.read(cx).tab_content_text(cx)
+    }
+
     fn tab_icon(&self, cx: &WindowContext) -> Option<Icon> {
         self.read(cx).tab_icon(cx)
     }
@@ -1,4 +1,5 @@
 use crate::{Toast, Workspace};
+use audio::{Audio, Sound};
 use collections::HashMap;
 use gpui::{
     svg, AnyView, AppContext, AsyncWindowContext, ClipboardItem, DismissEvent, Entity, EntityId,
@@ -15,6 +16,26 @@ pub fn init(cx: &mut AppContext) {
     cx.set_global(NotificationTracker::new());
 }
 
+/// Whether notifications are held back in every workspace.
+#[derive(Default)]
+struct DoNotDisturb(bool);
+
+impl Global for DoNotDisturb {}
+
+/// Holds back the notifications of every workspace while enabled, such as during a focus
+/// session. They're still collected, and shown once it's disabled again.
+pub fn set_do_not_disturb(enabled: bool, cx: &mut AppContext) {
+    if is_do_not_disturb_enabled(cx) != enabled {
+        cx.set_global(DoNotDisturb(enabled));
+        cx.refresh();
+    }
+}
+
+pub fn is_do_not_disturb_enabled(cx: &AppContext) -> bool {
+    cx.try_global::<DoNotDisturb>()
+        .map_or(false, |do_not_disturb| do_not_disturb.0)
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct NotificationId {
     /// A [`TypeId`] used to uniquely identify this notification.
@@ -157,6 +178,9 @@ impl Workspace {
     {
         struct WorkspaceErrorNotification;
 
+        if !is_do_not_disturb_enabled(cx) {
+            Audio::play_sound(Sound::Error, cx);
+        }
         self.show_notification(
             NotificationId::unique::<WorkspaceErrorNotification>(),
             cx,
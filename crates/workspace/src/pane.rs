@@ -8,19 +8,20 @@ use crate::{
     toolbar::Toolbar,
     workspace_settings::{AutosaveSetting, TabBarSettings, WorkspaceSettings},
     CloseWindow, CopyPath, CopyRelativePath, NewFile, NewTerminal, OpenInTerminal, OpenTerminal,
-    OpenVisible, SplitDirection, ToggleFileFinder, ToggleProjectSymbols, ToggleZoom, Workspace,
+    OpenVisible, SplitDirection, ToggleFileFinder, ToggleProjectSymbols, ToggleTabSearch,
+    ToggleZoom, Workspace,
 };
 use anyhow::Result;
 use collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use futures::{stream::FuturesUnordered, StreamExt};
 use git::repository::GitFileStatus;
 use gpui::{
-    actions, anchored, deferred, impl_actions, prelude::*, Action, AnchorCorner, AnyElement,
-    AppContext, AsyncWindowContext, ClickEvent, ClipboardItem, Div, DragMoveEvent, EntityId,
-    EventEmitter, ExternalPaths, FocusHandle, FocusOutEvent, FocusableView, KeyContext, Model,
-    MouseButton, MouseDownEvent, NavigationDirection, Pixels, Point, PromptLevel, Render,
-    ScrollHandle, Subscription, Task, View, ViewContext, VisualContext, WeakFocusHandle, WeakView,
-    WindowContext,
+    actions, anchored, deferred, impl_actions, linear_color_stop, linear_gradient, prelude::*,
+    Action, AnchorCorner, AnyElement, AppContext, AsyncWindowContext, ClickEvent, ClipboardItem,
+    Div, DragMoveEvent, EntityId, EventEmitter, ExternalPaths, FocusHandle, FocusOutEvent,
+    FocusableView, KeyContext, Model, MouseButton, MouseDownEvent, NavigationDirection, Pixels,
+    Point, PromptLevel, Render, ScrollHandle, Subscription, Task, View, ViewContext, VisualContext,
+    WeakFocusHandle, WeakView, WindowContext,
 };
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -173,7 +174,7 @@ impl DeploySearch {
     }
 }
 
-const MAX_NAVIGATION_HISTORY_LEN: usize = 1024;
+pub(crate) const MAX_NAVIGATION_HISTORY_LEN: usize = 1024;
 
 pub enum Event {
     AddItem {
@@ -272,6 +273,8 @@ pub struct Pane {
         Rc<dyn Fn(&mut Pane, &mut ViewContext<Pane>) -> (Option<AnyElement>, Option<AnyElement>)>,
     _subscriptions: Vec<Subscription>,
     tab_bar_scroll_handle: ScrollHandle,
+    /// Whether the unpinned tabs didn't fit in the tab bar when it was last rendered.
+    tab_bar_overflows: bool,
     /// Is None if navigation buttons are permanently turned off (and should not react to setting changes).
     /// Otherwise, when `display_nav_history_buttons` is Some, it determines whether nav buttons should be displayed.
     display_nav_history_buttons: Option<bool>,
@@ -381,6 +384,7 @@ impl Pane {
             }))),
             toolbar: cx.new_view(|_| Toolbar::new()),
             tab_bar_scroll_handle: ScrollHandle::new(),
+            tab_bar_overflows: false,
             drag_split_direction: None,
             workspace,
             project,
@@ -2147,6 +2151,60 @@ impl Pane {
         })
     }
 
+    /// Renders a menu listing the tabs that are scrolled out of view in the tab bar.
+    fn render_tab_overflow_menu(&self, cx: &mut ViewContext<'_, Pane>) -> impl IntoElement {
+        let pane = cx.view().downgrade();
+        PopoverMenu::new("pane-tab-overflow-menu")
+            .trigger(
+                IconButton::new("tab_overflow", IconName::ChevronDown)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::for_action("Show All Tabs", &ToggleTabSearch, cx)),
+            )
+            .anchor(AnchorCorner::TopRight)
+            .menu(move |cx| {
+                let pane = pane.upgrade()?;
+                let hidden_tabs = pane.read(cx).hidden_tabs(cx);
+                Some(ContextMenu::build(cx, |mut menu, _| {
+                    for (ix, label) in hidden_tabs {
+                        let pane = pane.downgrade();
+                        menu = menu.entry(label, None, move |cx| {
+                            pane.update(cx, |pane, cx| pane.activate_item(ix, true, true, cx))
+                                .ok();
+                        });
+                    }
+                    menu.separator()
+                        .action("Search Tabs…", ToggleTabSearch.boxed_clone())
+                }))
+            })
+    }
+
+    /// Returns the index and label of the unpinned tabs that aren't fully visible in the tab bar,
+    /// as of the last frame.
+    fn hidden_tabs(&self, cx: &WindowContext) -> Vec<(usize, SharedString)> {
+        let visible_bounds = self.tab_bar_scroll_handle.bounds();
+        self.items
+            .iter()
+            .zip(tab_details(&self.items, cx))
+            .enumerate()
+            .skip(self.pinned_tab_count)
+            .filter(|(ix, _)| {
+                self.tab_bar_scroll_handle
+                    .bounds_for_item(ix - self.pinned_tab_count)
+                    .map_or(false, |bounds| {
+                        bounds.left() < visible_bounds.left()
+                            || bounds.right() > visible_bounds.right()
+                    })
+            })
+            .map(|(ix, (item, detail))| {
+                let label = item
+                    .tab_description(detail, cx)
+                    .or_else(|| item.tab_content_text(cx))
+                    .unwrap_or_else(|| "untitled".into());
+                (ix, label)
+            })
+            .collect()
+    }
+
     fn render_tab_bar(&mut self, cx: &mut ViewContext<'_, Pane>) -> impl IntoElement {
         let focus_handle = self.focus_handle.clone();
         let navigate_backward = IconButton::new("navigate_backward", IconName::ArrowLeft)
@@ -2185,7 +2243,43 @@ impl Pane {
 
         let unpinned_tabs = tab_items.split_off(self.pinned_tab_count);
         let pinned_tabs = tab_items;
+        // The title bar is rendered in this tab bar when it's compact and this is the top-left pane.
+        let compact_titlebar = self.workspace.upgrade().and_then(|workspace| {
+            let workspace = workspace.read(cx);
+            let (pane, leading_padding) = workspace.compact_titlebar_pane(cx)?;
+            if pane.entity_id() != cx.entity_id() {
+                return None;
+            }
+            Some((workspace.titlebar_item()?, leading_padding))
+        });
+        let titlebar_padding = compact_titlebar.as_ref().map(|(_, padding)| *padding);
+
+        // Whether the tabs overflow is only known after they're laid out, so check again once
+        // this frame is drawn and render the tab bar again if it changed.
+        self.tab_bar_overflows = self.tab_bar_scroll_handle.max_offset().width > Pixels::ZERO;
+        cx.on_next_frame(|pane, cx| {
+            let overflows = pane.tab_bar_scroll_handle.max_offset().width > Pixels::ZERO;
+            if overflows != pane.tab_bar_overflows {
+                cx.notify();
+            }
+        });
+        let scroll_offset = -self.tab_bar_scroll_handle.offset().x;
+        let max_scroll_offset = self.tab_bar_scroll_handle.max_offset().width;
+        let tab_bar_background = cx.theme().colors().tab_bar_background;
+        let edge_indicator = |angle: f32| {
+            div().absolute().top_0().h_full().w_4().bg(linear_gradient(
+                angle,
+                [
+                    linear_color_stop(tab_bar_background, 0.),
+                    linear_color_stop(tab_bar_background.opacity(0.), 1.),
+                ],
+            ))
+        };
+
         TabBar::new("tab_bar")
+            .when_some(titlebar_padding, |tab_bar, padding| {
+                tab_bar.start_child(div().flex_none().w(padding))
+            })
             .when(
                 self.display_nav_history_buttons.unwrap_or_default(),
                 |tab_bar| {
@@ -2194,6 +2288,9 @@ impl Pane {
                         .start_child(navigate_forward)
                 },
             )
+            .when(self.tab_bar_overflows, |tab_bar| {
+                tab_bar.end_child(self.render_tab_overflow_menu(cx))
+            })
             .map(|tab_bar| {
                 let render_tab_buttons = self.render_tab_bar_buttons.clone();
                 let (left_children, right_children) = render_tab_buttons(self, cx);
@@ -2202,6 +2299,9 @@ impl Pane {
                     .start_children(left_children)
                     .end_children(right_children)
             })
+            .when_some(compact_titlebar, |tab_bar, (titlebar, _)| {
+                tab_bar.end_child(titlebar)
+            })
             .children(pinned_tabs.len().ne(&0).then(|| {
                 h_flex()
                     .children(pinned_tabs)
@@ -2210,49 +2310,66 @@ impl Pane {
             }))
             .child(
                 h_flex()
-                    .id("unpinned tabs")
-                    .overflow_x_scroll()
+                    .relative()
                     .w_full()
-                    .track_scroll(&self.tab_bar_scroll_handle)
-                    .children(unpinned_tabs)
+                    .min_w_0()
+                    .h_full()
                     .child(
-                        div()
-                            .id("tab_bar_drop_target")
-                            .min_w_6()
-                            // HACK: This empty child is currently necessary to force the drop target to appear
-                            // despite us setting a min width above.
-                            .child("")
-                            .h_full()
-                            .flex_grow()
-                            .drag_over::<DraggedTab>(|bar, _, cx| {
-                                bar.bg(cx.theme().colors().drop_target_background)
-                            })
-                            .drag_over::<DraggedSelection>(|bar, _, cx| {
-                                bar.bg(cx.theme().colors().drop_target_background)
-                            })
-                            .on_drop(cx.listener(move |this, dragged_tab: &DraggedTab, cx| {
-                                this.drag_split_direction = None;
-                                this.handle_tab_drop(dragged_tab, this.items.len(), cx)
-                            }))
-                            .on_drop(cx.listener(move |this, selection: &DraggedSelection, cx| {
-                                this.drag_split_direction = None;
-                                this.handle_project_entry_drop(
-                                    &selection.active_selection.entry_id,
-                                    cx,
-                                )
-                            }))
-                            .on_drop(cx.listener(move |this, paths, cx| {
-                                this.drag_split_direction = None;
-                                this.handle_external_paths_drop(paths, cx)
-                            }))
-                            .on_click(cx.listener(move |this, event: &ClickEvent, cx| {
-                                if event.up.click_count == 2 {
-                                    cx.dispatch_action(
-                                        this.double_click_dispatch_action.boxed_clone(),
-                                    )
-                                }
-                            })),
-                    ),
+                        h_flex()
+                            .id("unpinned tabs")
+                            .overflow_x_scroll()
+                            .w_full()
+                            .track_scroll(&self.tab_bar_scroll_handle)
+                            .children(unpinned_tabs)
+                            .child(
+                                div()
+                                    .id("tab_bar_drop_target")
+                                    .min_w_6()
+                                    // HACK: This empty child is currently necessary to force the drop target to appear
+                                    // despite us setting a min width above.
+                                    .child("")
+                                    .h_full()
+                                    .flex_grow()
+                                    .drag_over::<DraggedTab>(|bar, _, cx| {
+                                        bar.bg(cx.theme().colors().drop_target_background)
+                                    })
+                                    .drag_over::<DraggedSelection>(|bar, _, cx| {
+                                        bar.bg(cx.theme().colors().drop_target_background)
+                                    })
+                                    .on_drop(cx.listener(
+                                        move |this, dragged_tab: &DraggedTab, cx| {
+                                            this.drag_split_direction = None;
+                                            this.handle_tab_drop(dragged_tab, this.items.len(), cx)
+                                        },
+                                    ))
+                                    .on_drop(cx.listener(
+                                        move |this, selection: &DraggedSelection, cx| {
+                                            this.drag_split_direction = None;
+                                            this.handle_project_entry_drop(
+                                                &selection.active_selection.entry_id,
+                                                cx,
+                                            )
+                                        },
+                                    ))
+                                    .on_drop(cx.listener(move |this, paths, cx| {
+                                        this.drag_split_direction = None;
+                                        this.handle_external_paths_drop(paths, cx)
+                                    }))
+                                    .on_click(cx.listener(move |this, event: &ClickEvent, cx| {
+                                        if event.up.click_count == 2 {
+                                            cx.dispatch_action(
+                                                this.double_click_dispatch_action.boxed_clone(),
+                                            )
+                                        }
+                                    })),
+                            ),
+                    )
+                    .when(scroll_offset > Pixels::ZERO, |this| {
+                        this.child(edge_indicator(90.).left_0())
+                    })
+                    .when(scroll_offset < max_scroll_offset, |this| {
+                        this.child(edge_indicator(270.).right_0())
+                    }),
             )
     }
 
@@ -2813,6 +2930,11 @@ impl NavHistory {
             })
     }
 
+    /// Whether any item closed in this pane is left to reopen.
+    pub fn has_closed_items(&self) -> bool {
+        !self.0.lock().closed_stack.is_empty()
+    }
+
     pub fn set_mode(&mut self, mode: NavigationMode) {
         self.0.lock().mode = mode;
     }
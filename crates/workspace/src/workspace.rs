@@ -18,7 +18,7 @@ use client::{
     proto::{self, ErrorCode, PanelId, PeerId},
     ChannelId, Client, DevServerProjectId, ErrorExt, ProjectId, Status, TypedEnvelope, UserStore,
 };
-use collections::{hash_map, HashMap, HashSet};
+use collections::{hash_map, HashMap, HashSet, VecDeque};
 use derive_more::{Deref, DerefMut};
 use dock::{Dock, DockPosition, Panel, PanelButtons, PanelHandle};
 use futures::{
@@ -34,9 +34,9 @@ use gpui::{
     transparent_black, Action, AnyElement, AnyView, AnyWeakView, AppContext, AsyncAppContext,
     AsyncWindowContext, Bounds, CursorStyle, Decorations, DragMoveEvent, Entity as _, EntityId,
     EventEmitter, Flatten, FocusHandle, FocusableView, Global, Hsla, KeyContext, Keystroke,
-    ManagedView, Model, ModelContext, MouseButton, PathPromptOptions, Point, PromptLevel, Render,
-    ResizeEdge, Size, Stateful, Subscription, Task, Tiling, View, WeakView, WindowBounds,
-    WindowHandle, WindowId, WindowOptions,
+    ManagedView, Model, ModelContext, Modifiers, ModifiersChangedEvent, MouseButton,
+    PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription, Task,
+    Tiling, View, WeakView, WindowBounds, WindowHandle, WindowId, WindowOptions,
 };
 pub use item::{
     FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
@@ -137,6 +137,8 @@ actions!(
         CopyPath,
         CopyRelativePath,
         Feedback,
+        FocusNextRegion,
+        FocusPreviousRegion,
         FollowNextCollaborator,
         NewCenterTerminal,
         NewFile,
@@ -207,6 +209,7 @@ pub struct Reload {
 }
 
 action_as!(project_symbols, ToggleProjectSymbols as Toggle);
+action_as!(tab_switcher, ToggleTabSearch as Search);
 
 #[derive(Default, PartialEq, Eq, Clone, serde::Deserialize)]
 pub struct ToggleFileFinder {
@@ -692,6 +695,7 @@ pub enum Event {
         language: &'static str,
     },
     ZoomChanged,
+    ModifiersChanged(Modifiers),
 }
 
 #[derive(Debug)]
@@ -731,12 +735,15 @@ pub struct Workspace {
     right_dock: View<Dock>,
     panes: Vec<View<Pane>>,
     panes_by_item: HashMap<EntityId, WeakView<Pane>>,
+    /// The paths of the items closed in any pane, the most recently closed last.
+    closed_items: VecDeque<(ProjectPath, Option<PathBuf>)>,
     active_pane: View<Pane>,
     last_active_center_pane: Option<WeakView<Pane>>,
     last_active_view_id: Option<proto::ViewId>,
     status_bar: View<StatusBar>,
     modal_layer: View<ModalLayer>,
     titlebar_item: Option<AnyView>,
+    compact_titlebar: Option<Pixels>,
     notifications: Vec<(NotificationId, Box<dyn NotificationHandle>)>,
     project: Model<Project>,
     follower_states: HashMap<PeerId, FollowerState>,
@@ -1041,12 +1048,14 @@ impl Workspace {
             center: PaneGroup::new(center_pane.clone()),
             panes: vec![center_pane.clone()],
             panes_by_item: Default::default(),
+            closed_items: Default::default(),
             active_pane: center_pane.clone(),
             last_active_center_pane: Some(center_pane.downgrade()),
             last_active_view_id: None,
             status_bar,
             modal_layer,
             titlebar_item: None,
+            compact_titlebar: None,
             notifications: Default::default(),
             left_dock,
             bottom_dock,
@@ -1436,12 +1445,66 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, cx)
     }
 
+    /// Reopens the item most recently closed in the active pane. Once the active pane has none
+    /// left, walks back through the items closed anywhere else in the workspace, including in
+    /// panes that have since been closed.
     pub fn reopen_closed_item(&mut self, cx: &mut ViewContext<Workspace>) -> Task<Result<()>> {
-        self.navigate_history(
-            self.active_pane().downgrade(),
-            NavigationMode::ReopeningClosedItem,
-            cx,
-        )
+        let pane = self.active_pane().clone();
+        if pane.read(cx).nav_history().has_closed_items() {
+            return self.navigate_history(
+                pane.downgrade(),
+                NavigationMode::ReopeningClosedItem,
+                cx,
+            );
+        }
+
+        while let Some((project_path, abs_path)) = self.closed_items.pop_back() {
+            let is_open = self
+                .items(cx)
+                .any(|item| item.project_path(cx).as_ref() == Some(&project_path));
+            if is_open {
+                continue;
+            }
+
+            let has_worktree = self
+                .project
+                .read(cx)
+                .worktree_for_id(project_path.worktree_id, cx)
+                .is_some();
+            let task = match abs_path {
+                Some(abs_path) if !has_worktree => self.open_abs_path(abs_path, false, cx),
+                _ => self.open_path(project_path, None, true, cx),
+            };
+            return cx.spawn(|_, _| async move {
+                task.await?;
+                Ok(())
+            });
+        }
+
+        Task::ready(Ok(()))
+    }
+
+    /// The paths of the items most recently closed in any pane that aren't open anymore, the
+    /// most recently closed first.
+    pub fn recently_closed_items(
+        &self,
+        limit: Option<usize>,
+        cx: &AppContext,
+    ) -> Vec<(ProjectPath, Option<PathBuf>)> {
+        let open_paths = self
+            .items(cx)
+            .filter_map(|item| item.project_path(cx))
+            .collect::<HashSet<_>>();
+        let mut seen_paths = HashSet::default();
+        self.closed_items
+            .iter()
+            .rev()
+            .filter(|(project_path, _)| {
+                !open_paths.contains(project_path) && seen_paths.insert(project_path.clone())
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
     }
 
     pub fn client(&self) -> &Arc<Client> {
@@ -1590,6 +1653,34 @@ impl Workspace {
         self.titlebar_item.clone()
     }
 
+    /// Sets whether the title bar is merged into the tab bar of the top-left pane, to save
+    /// vertical space. When it is, the given space is left at the start of the tab bar for the
+    /// window's controls.
+    pub fn set_compact_titlebar(
+        &mut self,
+        leading_padding: Option<Pixels>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self.compact_titlebar != leading_padding {
+            self.compact_titlebar = leading_padding;
+            for pane in &self.panes {
+                pane.update(cx, |_, cx| cx.notify());
+            }
+            cx.notify();
+        }
+    }
+
+    /// The pane whose tab bar the title bar is rendered in, and the space left at the start of
+    /// that tab bar. The title bar keeps a row of its own while the pane has no tab bar.
+    pub(crate) fn compact_titlebar_pane(&self, cx: &AppContext) -> Option<(View<Pane>, Pixels)> {
+        let leading_padding = self.compact_titlebar?;
+        self.titlebar_item.as_ref()?;
+        let pane = self.center.first_pane();
+        let shows_tab_bar =
+            TabBarSettings::get_global(cx).show && pane.read(cx).active_item().is_some();
+        shows_tab_bar.then_some((pane, leading_padding))
+    }
+
     /// Call the given callback with a workspace whose project is local.
     ///
     /// If the given workspace has a local project, then it will be passed
@@ -2337,6 +2428,12 @@ impl Workspace {
         let mut serialize = false;
         for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
             if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+                // Floating panels are focused in their own windows.
+                if dock.update(cx, |dock, cx| dock.activate_floating_panel(panel_index, cx)) {
+                    let panel = dock.read(cx).panel::<T>()?;
+                    return Some(Arc::new(panel));
+                }
+
                 let mut focus_center = false;
                 let panel = dock.update(cx, |dock, cx| {
                     dock.activate_panel(panel_index, cx);
@@ -2375,6 +2472,9 @@ impl Workspace {
     pub fn open_panel<T: Panel>(&mut self, cx: &mut ViewContext<Self>) {
         for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
             if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
+                if dock.update(cx, |dock, cx| dock.activate_floating_panel(panel_index, cx)) {
+                    continue;
+                }
                 dock.update(cx, |dock, cx| {
                     dock.activate_panel(panel_index, cx);
                     dock.set_open(true, cx);
@@ -2797,6 +2897,66 @@ impl Workspace {
         }
     }
 
+    /// Moves focus to the next region of the workspace. Regions are visited in reading order:
+    /// the left dock, the panes in the center, the right dock and the bottom dock. Closed docks
+    /// are skipped, and focus wraps around after the last region.
+    pub fn focus_next_region(&mut self, cx: &mut WindowContext) {
+        self.focus_adjacent_region(true, cx);
+    }
+
+    /// Moves focus to the previous region of the workspace, in the reverse order of
+    /// [`Workspace::focus_next_region`].
+    pub fn focus_previous_region(&mut self, cx: &mut WindowContext) {
+        self.focus_adjacent_region(false, cx);
+    }
+
+    fn focus_adjacent_region(&mut self, forward: bool, cx: &mut WindowContext) {
+        use ActivateInDirectionTarget as Target;
+
+        let open_dock = |dock: &View<Dock>| {
+            let is_focusable = dock.read(cx).is_open() && dock.read(cx).active_panel().is_some();
+            is_focusable.then(|| Target::Dock(dock.clone()))
+        };
+        let regions = open_dock(&self.left_dock)
+            .into_iter()
+            .chain(
+                self.center
+                    .panes()
+                    .into_iter()
+                    .map(|pane| Target::Pane(pane.clone())),
+            )
+            .chain(open_dock(&self.right_dock))
+            .chain(open_dock(&self.bottom_dock))
+            .collect::<Vec<_>>();
+
+        // The active pane stays active while a dock is focused, so docks are checked first.
+        let current_ix = regions
+            .iter()
+            .position(|region| {
+                matches!(region, Target::Dock(dock) if dock.focus_handle(cx).contains_focused(cx))
+            })
+            .or_else(|| {
+                regions.iter().position(
+                    |region| matches!(region, Target::Pane(pane) if *pane == self.active_pane),
+                )
+            });
+        let next_ix = match current_ix {
+            Some(ix) if forward => (ix + 1) % regions.len(),
+            Some(ix) => (ix + regions.len() - 1) % regions.len(),
+            None => 0,
+        };
+
+        match regions.get(next_ix) {
+            Some(Target::Pane(pane)) => cx.focus_view(pane),
+            Some(Target::Dock(dock)) => {
+                if let Some(panel) = dock.read(cx).active_panel() {
+                    panel.focus_handle(cx).focus(cx);
+                }
+            }
+            None => {}
+        }
+    }
+
     pub fn activate_pane_in_direction(
         &mut self,
         direction: SplitDirection,
@@ -2983,6 +3143,10 @@ impl Workspace {
     ) {
         match event {
             pane::Event::AddItem { item } => {
+                if let Some(project_path) = item.project_path(cx) {
+                    self.closed_items
+                        .retain(|(closed_path, _)| *closed_path != project_path);
+                }
                 item.added_to_pane(self, pane, cx);
                 cx.emit(Event::ItemAdded);
             }
@@ -3023,6 +3187,12 @@ impl Workspace {
             }
             pane::Event::RemoveItem { .. } => {}
             pane::Event::RemovedItem { item_id } => {
+                if let Some(path) = pane.read(cx).nav_history().path_for_item(*item_id) {
+                    if self.closed_items.len() >= pane::MAX_NAVIGATION_HISTORY_LEN {
+                        self.closed_items.pop_front();
+                    }
+                    self.closed_items.push_back(path);
+                }
                 cx.emit(Event::ActiveItemChanged);
                 self.update_window_edited(cx);
                 if let hash_map::Entry::Occupied(entry) = self.panes_by_item.entry(*item_id) {
@@ -3448,8 +3618,8 @@ impl Workspace {
         }
     }
 
-    fn render_notifications(&self, _cx: &ViewContext<Self>) -> Option<Div> {
-        if self.notifications.is_empty() {
+    fn render_notifications(&self, cx: &ViewContext<Self>) -> Option<Div> {
+        if self.notifications.is_empty() || notifications::is_do_not_disturb_enabled(cx) {
             None
         } else {
             Some(
@@ -4373,6 +4543,12 @@ impl Workspace {
             .on_action(
                 cx.listener(|workspace, _: &ActivateNextPane, cx| workspace.activate_next_pane(cx)),
             )
+            .on_action(
+                cx.listener(|workspace, _: &FocusNextRegion, cx| workspace.focus_next_region(cx)),
+            )
+            .on_action(cx.listener(|workspace, _: &FocusPreviousRegion, cx| {
+                workspace.focus_previous_region(cx)
+            }))
             .on_action(
                 cx.listener(|workspace, action: &ActivatePaneInDirection, cx| {
                     workspace.activate_pane_in_direction(action.0, cx)
@@ -4723,6 +4899,9 @@ impl Render for Workspace {
         client_side_decorations(
             self.actions(div(), cx)
                 .key_context(context)
+                .on_modifiers_changed(cx.listener(|_, event: &ModifiersChangedEvent, cx| {
+                    cx.emit(Event::ModifiersChanged(event.modifiers))
+                }))
                 .relative()
                 .size_full()
                 .flex()
@@ -4733,7 +4912,9 @@ impl Render for Workspace {
                 .items_start()
                 .text_color(colors.text)
                 .overflow_hidden()
-                .children(self.titlebar_item.clone())
+                .when(self.compact_titlebar_pane(cx).is_none(), |this| {
+                    this.children(self.titlebar_item.clone())
+                })
                 .child(
                     div()
                         .id("workspace")
@@ -5906,6 +6087,7 @@ pub fn client_side_decorations(element: impl IntoElement, cx: &mut WindowContext
                                 },
                                 blur_radius: theme::CLIENT_SIDE_DECORATION_SHADOW / 2.,
                                 spread_radius: px(0.),
+                                inset: false,
                                 offset: point(px(0.0), px(0.0)),
                             }])
                         }),
@@ -6865,6 +7047,59 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_focus_regions(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let (left_panel, right_panel, left_pane, right_pane) =
+            workspace.update(cx, |workspace, cx| {
+                let left_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Left, cx));
+                workspace.add_panel(left_panel.clone(), cx);
+                let right_panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+                workspace.add_panel(right_panel.clone(), cx);
+                workspace
+                    .left_dock()
+                    .update(cx, |dock, cx| dock.set_open(true, cx));
+                workspace
+                    .right_dock()
+                    .update(cx, |dock, cx| dock.set_open(true, cx));
+
+                let left_pane = workspace.active_pane().clone();
+                let right_pane = workspace.split_pane(left_pane.clone(), SplitDirection::Right, cx);
+                (left_panel, right_panel, left_pane, right_pane)
+            });
+        cx.focus_view(&left_pane);
+
+        let assert_focused = |expected: usize, cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let focused = [
+                    left_panel.read(cx).focus_handle(cx).contains_focused(cx),
+                    left_pane.focus_handle(cx).contains_focused(cx),
+                    right_pane.focus_handle(cx).contains_focused(cx),
+                    right_panel.read(cx).focus_handle(cx).contains_focused(cx),
+                ];
+                let expected = (0..focused.len())
+                    .map(|ix| ix == expected)
+                    .collect::<Vec<_>>();
+                assert_eq!(focused.to_vec(), expected);
+            });
+        };
+
+        // The bottom dock is closed, so it's skipped.
+        for expected in [2, 3, 0, 1] {
+            workspace.update(cx, |workspace, cx| workspace.focus_next_region(cx));
+            assert_focused(expected, cx);
+        }
+        for expected in [0, 3, 2, 1] {
+            workspace.update(cx, |workspace, cx| workspace.focus_previous_region(cx));
+            assert_focused(expected, cx);
+        }
+    }
+
     #[gpui::test]
     async fn test_join_pane_into_next(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -7100,6 +7335,55 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_floating_panels(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let panel = workspace.update(cx, |workspace, cx| {
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), cx);
+            workspace
+                .right_dock()
+                .update(cx, |right_dock, cx| right_dock.set_open(true, cx));
+            panel
+        });
+        let right_dock = workspace.update(cx, |workspace, _| workspace.right_dock().clone());
+
+        // Popping the panel out closes the dock, but leaves the panel active.
+        right_dock.update(cx, |right_dock, cx| right_dock.float_panel(0, cx));
+        right_dock.update(cx, |right_dock, cx| {
+            assert!(right_dock.is_panel_floating(0));
+            assert!(!right_dock.is_open());
+            assert!(right_dock.visible_panel().is_none());
+            assert!(panel.read(cx).active);
+        });
+        assert_eq!(cx.windows().len(), 2);
+
+        // Toggling the panel's focus doesn't reopen the dock.
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(cx);
+        });
+        right_dock.update(cx, |right_dock, _| assert!(!right_dock.is_open()));
+
+        // Returning the panel to the dock closes its window and reveals it.
+        right_dock.update(cx, |right_dock, cx| right_dock.dock_panel(0, true, cx));
+        cx.run_until_parked();
+        right_dock.update(cx, |right_dock, cx| {
+            assert!(!right_dock.is_panel_floating(0));
+            assert!(right_dock.is_open());
+            assert_eq!(
+                right_dock.visible_panel().unwrap().panel_id(),
+                panel.panel_id()
+            );
+            assert!(panel.read(cx).active);
+        });
+        assert_eq!(cx.windows().len(), 1);
+    }
+
     #[gpui::test]
     async fn test_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -180,6 +180,10 @@ pub struct RepositoryEntry {
     ///     work_directory: pointing to "" entry
     ///     location_in_repo: Some("my_sub_folder_1/project_root")
     pub(crate) location_in_repo: Option<Arc<Path>>,
+
+    /// The name of the linked worktree, if the repository was checked out with
+    /// `git worktree add`.
+    pub(crate) linked_worktree: Option<Arc<str>>,
 }
 
 impl RepositoryEntry {
@@ -187,6 +191,10 @@ impl RepositoryEntry {
         self.branch.clone()
     }
 
+    pub fn linked_worktree(&self) -> Option<Arc<str>> {
+        self.linked_worktree.clone()
+    }
+
     pub fn work_directory_id(&self) -> ProjectEntryId {
         *self.work_directory
     }
@@ -225,6 +233,19 @@ impl RepositoryEntry {
             relativize_path(path)
         }
     }
+
+    /// unrelativize is the inverse of [RepositoryEntry::relativize]: it returns the path
+    /// inside the worktree for a path relative to the root folder of the repository, or
+    /// `None` if the path lies outside of the worktree.
+    pub fn unrelativize(&self, worktree: &Snapshot, path: &RepoPath) -> Option<Arc<Path>> {
+        let entry = worktree.entry_for_id(self.work_directory.0)?;
+        let path = if let Some(location_in_repo) = &self.location_in_repo {
+            path.strip_prefix(location_in_repo).ok()?
+        } else {
+            path.as_ref()
+        };
+        Some(entry.path.join(path).into())
+    }
 }
 
 impl From<&RepositoryEntry> for proto::RepositoryEntry {
@@ -302,15 +323,26 @@ struct BackgroundScannerState {
 pub struct LocalRepositoryEntry {
     pub(crate) git_dir_scan_id: usize,
     pub(crate) repo_ptr: Arc<dyn GitRepository>,
-    /// Path to the actual .git folder.
-    /// Note: if .git is a file, this points to the folder indicated by the .git file
+    /// Path to the .git entry, relative to the worktree root.
+    /// Note: this is a file, rather than a folder, for linked worktrees and submodules.
     pub(crate) git_dir_path: Arc<Path>,
+    /// Absolute path to the actual git directory. When .git is a file, this is the
+    /// folder indicated by the .git file.
+    pub(crate) git_dir_abs_path: Arc<Path>,
+    /// Absolute path to the git directory shared with the repository's linked worktrees.
+    pub(crate) main_git_dir_abs_path: Arc<Path>,
 }
 
 impl LocalRepositoryEntry {
     pub fn repo(&self) -> &Arc<dyn GitRepository> {
         &self.repo_ptr
     }
+
+    /// Whether the given absolute path lies within the repository's git directories.
+    fn contains_git_path(&self, abs_path: &Path) -> bool {
+        abs_path.starts_with(&self.git_dir_abs_path)
+            || abs_path.starts_with(&self.main_git_dir_abs_path)
+    }
 }
 
 impl Deref for LocalSnapshot {
@@ -2143,6 +2175,7 @@ impl Snapshot {
                             // the location_in_repo field, since git operations don't happen locally
                             // anyway.
                             location_in_repo: None,
+                            linked_worktree: None,
                         },
                     )
                 }
@@ -2556,7 +2589,7 @@ impl LocalSnapshot {
                     new_ignores.push((ancestor, None));
                 }
             }
-            if ancestor.join(*DOT_GIT).is_dir() {
+            if ancestor.join(*DOT_GIT).exists() {
                 break;
             }
         }
@@ -2949,12 +2982,21 @@ impl BackgroundScannerState {
         log::trace!("constructed libgit2 repo in {:?}", t0.elapsed());
         let work_directory = RepositoryWorkDirectory(work_dir_path.clone());
 
+        let git_dir_abs_path: Arc<Path> = repository.path().into();
+        let main_git_dir_abs_path: Arc<Path> = repository.main_repository_path().into();
+        // Linked worktrees keep their git directory in `<main git dir>/worktrees/<name>`.
+        let linked_worktree = (git_dir_abs_path != main_git_dir_abs_path)
+            .then(|| git_dir_abs_path.file_name())
+            .flatten()
+            .map(|name| name.to_string_lossy().into());
+
         self.snapshot.repository_entries.insert(
             work_directory.clone(),
             RepositoryEntry {
                 work_directory: work_dir_id.into(),
                 branch: repository.branch_name().map(Into::into),
                 location_in_repo,
+                linked_worktree,
             },
         );
         self.snapshot.git_repositories.insert(
@@ -2963,6 +3005,8 @@ impl BackgroundScannerState {
                 git_dir_scan_id: 0,
                 repo_ptr: repository.clone(),
                 git_dir_path: dot_git_path.clone(),
+                git_dir_abs_path,
+                main_git_dir_abs_path,
             },
         );
 
@@ -3194,6 +3238,16 @@ impl File {
         self.worktree.read(cx).id()
     }
 
+    /// Whether the file is generated or vendored code, according to the `generated_files`
+    /// setting.
+    pub fn is_generated(&self, cx: &AppContext) -> bool {
+        let location = SettingsLocation {
+            worktree_id: self.worktree_id(cx),
+            path: &self.path,
+        };
+        WorktreeSettings::get(Some(location), cx).is_path_generated(&self.path)
+    }
+
     pub fn project_entry_id(&self, _: &AppContext) -> Option<ProjectEntryId> {
         if self.is_deleted {
             None
@@ -3533,8 +3587,9 @@ impl BackgroundScanner {
                 }
             }
 
+            // `.git` is a file rather than a folder in linked worktrees and submodules.
             let ancestor_dot_git = ancestor.join(*DOT_GIT);
-            if ancestor_dot_git.is_dir() {
+            if ancestor_dot_git.exists() {
                 if index != 0 {
                     // We canonicalize, since the FS events use the canonicalized path.
                     if let Some(ancestor_dot_git) =
@@ -3586,6 +3641,15 @@ impl BackgroundScanner {
 
         self.send_status_update(false, SmallVec::new());
 
+        // Linked worktrees and submodules can keep their git directories outside of the
+        // worktree, where changes to them wouldn't be noticed otherwise.
+        for git_dir in self.external_git_dirs() {
+            if let Some(git_dir) = self.fs.canonicalize(&git_dir).await.log_err() {
+                let (git_events, _) = self.fs.watch(&git_dir, FS_WATCH_LATENCY).await;
+                fs_events_rx = select(fs_events_rx, git_events).boxed();
+            }
+        }
+
         // Process any any FS events that occurred while performing the initial scan.
         // For these events, update events cannot be as precise, because we didn't
         // have the previous state loaded yet.
@@ -3642,6 +3706,25 @@ impl BackgroundScanner {
         }
     }
 
+    /// Returns the git directories of the worktree's repositories that lie outside of
+    /// the worktree and are not watched along with it.
+    fn external_git_dirs(&self) -> Vec<Arc<Path>> {
+        let state = self.state.lock();
+        let root_abs_path = state.snapshot.abs_path();
+        let mut git_dirs = Vec::new();
+        for repo in state.snapshot.git_repositories.values() {
+            for git_dir in [&repo.git_dir_abs_path, &repo.main_git_dir_abs_path] {
+                // Repositories in ancestor folders are already watched via their `.git` folder.
+                let is_watched = git_dir.starts_with(root_abs_path)
+                    || (repo.git_dir_path.is_absolute() && git_dir.starts_with(&repo.git_dir_path));
+                if !is_watched && !git_dirs.contains(git_dir) {
+                    git_dirs.push(git_dir.clone());
+                }
+            }
+        }
+        git_dirs
+    }
+
     async fn process_scan_request(&self, mut request: ScanRequest, scanning: bool) -> bool {
         log::debug!("rescanning paths {:?}", request.relative_paths);
 
@@ -3719,6 +3802,17 @@ impl BackgroundScanner {
                         dot_git_paths.push(dot_git_path);
                     }
                     is_git_related = true;
+                } else if let Some(repo) = snapshot
+                    .git_repositories
+                    .values()
+                    .find(|repo| repo.contains_git_path(abs_path))
+                {
+                    // The git directories of linked worktrees aren't necessarily named `.git`.
+                    let dot_git_path = repo.git_dir_abs_path.to_path_buf();
+                    if !dot_git_paths.contains(&dot_git_path) {
+                        dot_git_paths.push(dot_git_path);
+                    }
+                    is_git_related = true;
                 }
 
                 let relative_path: Arc<Path> =
@@ -4457,62 +4551,69 @@ impl BackgroundScanner {
             let mut state = self.state.lock();
             let scan_id = state.snapshot.scan_id;
             for dot_git_dir in dot_git_paths {
-                let existing_repository_entry =
-                    state
+                // A single git directory can back several repositories: linked worktrees
+                // share the main repository's git directory, and submodules keep theirs
+                // inside the superproject's.
+                let dot_git_abs_path = state.snapshot.abs_path.join(&dot_git_dir);
+                let existing_repository_entries = state
+                    .snapshot
+                    .git_repositories
+                    .iter()
+                    .filter(|(_, repo)| {
+                        repo.git_dir_path.as_ref() == dot_git_dir
+                            || repo.contains_git_path(&dot_git_abs_path)
+                            || repo.git_dir_abs_path.starts_with(&dot_git_abs_path)
+                    })
+                    .map(|(entry_id, repo)| (*entry_id, repo.clone()))
+                    .collect::<Vec<_>>();
+
+                let mut updated_repositories = Vec::new();
+                if existing_repository_entries.is_empty() {
+                    match state.build_git_repository(dot_git_dir.into(), self.fs.as_ref()) {
+                        Some(output) => updated_repositories.push(output),
+                        None => continue,
+                    }
+                }
+                for (entry_id, repository) in existing_repository_entries {
+                    if repository.git_dir_scan_id == scan_id {
+                        continue;
+                    }
+                    let Some(work_dir) = state
                         .snapshot
-                        .git_repositories
-                        .iter()
-                        .find_map(|(entry_id, repo)| {
-                            (repo.git_dir_path.as_ref() == dot_git_dir)
-                                .then(|| (*entry_id, repo.clone()))
-                        });
+                        .entry_for_id(entry_id)
+                        .map(|entry| RepositoryWorkDirectory(entry.path.clone()))
+                    else {
+                        continue;
+                    };
 
-                let (work_directory, repository) = match existing_repository_entry {
-                    None => {
-                        match state.build_git_repository(dot_git_dir.into(), self.fs.as_ref()) {
-                            Some(output) => output,
-                            None => continue,
-                        }
-                    }
-                    Some((entry_id, repository)) => {
-                        if repository.git_dir_scan_id == scan_id {
-                            continue;
-                        }
-                        let Some(work_dir) = state
-                            .snapshot
-                            .entry_for_id(entry_id)
-                            .map(|entry| RepositoryWorkDirectory(entry.path.clone()))
-                        else {
-                            continue;
-                        };
+                    let repo = &repository.repo_ptr;
+                    let branch = repo.branch_name();
+                    repo.reload_index();
 
-                        let repo = &repository.repo_ptr;
-                        let branch = repo.branch_name();
-                        repo.reload_index();
+                    state
+                        .snapshot
+                        .git_repositories
+                        .update(&entry_id, |entry| entry.git_dir_scan_id = scan_id);
+                    state
+                        .snapshot
+                        .snapshot
+                        .repository_entries
+                        .update(&work_dir, |entry| entry.branch = branch.map(Into::into));
+                    updated_repositories.push((work_dir, repository.repo_ptr.clone()));
+                }
 
-                        state
-                            .snapshot
-                            .git_repositories
-                            .update(&entry_id, |entry| entry.git_dir_scan_id = scan_id);
-                        state
-                            .snapshot
+                for (work_directory, repository) in updated_repositories {
+                    repo_updates.push(UpdateGitStatusesJob {
+                        location_in_repo: state
                             .snapshot
                             .repository_entries
-                            .update(&work_dir, |entry| entry.branch = branch.map(Into::into));
-                        (work_dir, repository.repo_ptr.clone())
-                    }
-                };
-
-                repo_updates.push(UpdateGitStatusesJob {
-                    location_in_repo: state
-                        .snapshot
-                        .repository_entries
-                        .get(&work_directory)
-                        .and_then(|repo| repo.location_in_repo.clone())
-                        .clone(),
-                    work_directory,
-                    repository,
-                });
+                            .get(&work_directory)
+                            .and_then(|repo| repo.location_in_repo.clone())
+                            .clone(),
+                        work_directory,
+                        repository,
+                    });
+                }
             }
 
             // Remove any git repositories whose .git entry no longer exists.
@@ -11,6 +11,7 @@ use util::paths::PathMatcher;
 pub struct WorktreeSettings {
     pub file_scan_exclusions: PathMatcher,
     pub private_files: PathMatcher,
+    pub generated_files: PathMatcher,
 }
 
 impl WorktreeSettings {
@@ -19,6 +20,11 @@ impl WorktreeSettings {
             .any(|ancestor| self.private_files.is_match(ancestor))
     }
 
+    pub fn is_path_generated(&self, path: &Path) -> bool {
+        path.ancestors()
+            .any(|ancestor| self.generated_files.is_match(ancestor))
+    }
+
     pub fn is_path_excluded(&self, path: &Path) -> bool {
         path.ancestors()
             .any(|ancestor| self.file_scan_exclusions.is_match(ancestor))
@@ -45,6 +51,12 @@ pub struct WorktreeSettingsContent {
     /// Treat the files matching these globs as `.env` files.
     /// Default: [ "**/.env*" ]
     pub private_files: Option<Vec<String>>,
+
+    /// Treat the files matching these globs as generated or vendored code.
+    /// They aren't formatted on save, only error diagnostics are shown for them,
+    /// and project search lists them after other files.
+    /// Default: []
+    pub generated_files: Option<Vec<String>>,
 }
 
 impl Settings for WorktreeSettings {
@@ -59,11 +71,14 @@ impl Settings for WorktreeSettings {
         let result: WorktreeSettingsContent = sources.json_merge()?;
         let mut file_scan_exclusions = result.file_scan_exclusions.unwrap_or_default();
         let mut private_files = result.private_files.unwrap_or_default();
+        let mut generated_files = result.generated_files.unwrap_or_default();
         file_scan_exclusions.sort();
         private_files.sort();
+        generated_files.sort();
         Ok(Self {
             file_scan_exclusions: path_matchers(&file_scan_exclusions, "file_scan_exclusions")?,
             private_files: path_matchers(&private_files, "private_files")?,
+            generated_files: path_matchers(&generated_files, "generated_files")?,
         })
     }
 }
@@ -974,6 +974,28 @@ async fn test_file_scan_exclusions(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_generated_files(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<WorktreeSettings>(cx, |project_settings| {
+                project_settings.generated_files =
+                    Some(vec!["**/vendor".to_string(), "**/*.pb.go".to_string()]);
+            });
+        });
+    });
+
+    cx.read(|cx| {
+        let settings = WorktreeSettings::get_global(cx);
+        assert!(settings.is_path_generated(Path::new("vendor/lib/lib.go")));
+        assert!(settings.is_path_generated(Path::new("src/vendor")));
+        assert!(settings.is_path_generated(Path::new("api/service.pb.go")));
+        assert!(!settings.is_path_generated(Path::new("api/service.go")));
+        assert!(!settings.is_path_generated(Path::new("vendored/lib.go")));
+    });
+}
+
 #[gpui::test]
 async fn test_fs_events_in_exclusions(cx: &mut TestAppContext) {
     init_test(cx);
@@ -2381,6 +2403,55 @@ async fn test_repository_subfolder_git_status(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_linked_git_worktree(cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.executor().allow_parking();
+
+    let root = temp_tree(json!({
+        "main": {
+            "a.txt": "a",
+        },
+    }));
+
+    let repo = git_init(&root.path().join("main"));
+    git_add("a.txt", &repo);
+    git_commit("Initial commit", &repo);
+    repo.worktree("feature", &root.path().join("feature"), None)
+        .expect("Failed to add worktree");
+    std::fs::write(root.path().join("feature/b.txt"), "b").unwrap();
+
+    // Open the linked worktree, whose `.git` is a file pointing into the main repository.
+    let tree = Worktree::local(
+        root.path().join("feature"),
+        true,
+        Arc::new(RealFs::default()),
+        Default::default(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+
+    tree.flush_fs_events(cx).await;
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+    cx.executor().run_until_parked();
+
+    tree.read_with(cx, |tree, _cx| {
+        let snapshot = tree.snapshot();
+        assert_eq!(snapshot.repositories().count(), 1);
+        let (dir, repo_entry) = snapshot.repositories().next().unwrap();
+        assert_eq!(dir.as_ref(), Path::new(""));
+        assert_eq!(repo_entry.linked_worktree(), Some("feature".into()));
+
+        assert_eq!(snapshot.status_for_file("a.txt"), None);
+        assert_eq!(
+            snapshot.status_for_file("b.txt"),
+            Some(GitFileStatus::Added)
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_propagate_git_statuses(cx: &mut TestAppContext) {
     init_test(cx);
@@ -51,7 +51,7 @@ use theme::{ActiveTheme, SystemAppearance, ThemeRegistry, ThemeSettings};
 use time::UtcOffset;
 use util::{maybe, parse_env_output, ResultExt, TryFutureExt};
 use uuid::Uuid;
-use welcome::{show_welcome_view, BaseKeymap, FIRST_OPEN};
+use welcome::{show_welcome_view, BaseKeymap, WelcomeSettings, FIRST_OPEN};
 use workspace::{
     notifications::{simple_message_notification::MessageNotification, NotificationId},
     AppState, WorkspaceSettings, WorkspaceStore,
@@ -191,6 +191,7 @@ fn init_common(app_state: Arc<AppState>, cx: &mut AppContext) -> Arc<PromptBuild
         cx,
     );
     snippet_provider::init(cx);
+    i18n::init(cx);
     inline_completion_registry::init(app_state.client.telemetry().clone(), cx);
     let prompt_builder = assistant::init(
         app_state.fs.clone(),
@@ -252,22 +253,36 @@ fn init_ui(
     dev_server_projects::init(app_state.client.clone(), cx);
     outline::init(cx);
     project_symbols::init(cx);
+    activity_tracker::init(app_state.fs.clone(), cx);
+    coverage::init(app_state.fs.clone(), cx);
+    database_explorer::init(cx);
+    docker_panel::init(cx);
     project_panel::init(Assets, cx);
     outline_panel::init(Assets, cx);
     tasks_ui::init(cx);
+    rest_client::init(cx);
+    git_clone::init(cx);
+    git_compare::init(cx);
+    git_panel::init(cx);
     channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
     search::init(cx);
     vim::init(cx);
     terminal_view::init(cx);
     journal::init(app_state.clone(), cx);
+    keybinding_tutor::init(cx);
     language_selector::init(cx);
     theme_selector::init(cx);
     language_tools::init(cx);
+    log_viewer::init(cx);
+    env_file::init(cx);
     call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
     notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
     collab_ui::init(&app_state, cx);
     feedback::init(cx);
+    focus_timer::init(cx);
     markdown_preview::init(cx);
+    html_preview::init(cx);
+    ansi_view::init(cx);
     welcome::init(cx);
     settings_ui::init(cx);
     extensions_ui::init(cx);
@@ -843,7 +858,9 @@ async fn restore_or_create_workspace(
             })?
             .await?;
         }
-    } else if matches!(KEY_VALUE_STORE.read_kvp(FIRST_OPEN), Ok(None)) {
+    } else if matches!(KEY_VALUE_STORE.read_kvp(FIRST_OPEN), Ok(None))
+        || cx.update(|cx| WelcomeSettings::get_global(cx).show_on_startup)?
+    {
         cx.update(|cx| show_welcome_view(app_state, cx))?.await?;
     } else {
         cx.update(|cx| {
@@ -8,14 +8,20 @@ mod open_listener;
 #[cfg(target_os = "windows")]
 pub(crate) mod windows_only_instance;
 
+use activity_tracker::ActivityPanel;
 pub use app_menus::*;
 use assistant::PromptBuilder;
 use breadcrumbs::Breadcrumbs;
 use client::ZED_URL_SCHEME;
 use collections::VecDeque;
 use command_palette_hooks::CommandPaletteFilter;
+use coverage::CoveragePanel;
+use database_explorer::DatabasePanel;
+use db::kvp::KEY_VALUE_STORE;
+use docker_panel::DockerPanel;
 use editor::{scroll::Autoscroll, Editor, MultiBuffer};
 use feature_flags::FeatureFlagAppExt;
+use git_panel::GitPanel;
 use gpui::{
     actions, point, px, AppContext, AsyncAppContext, Context, FocusableView, MenuItem, PromptLevel,
     ReadGlobal, TitlebarOptions, View, ViewContext, VisualContext, WindowKind, WindowOptions,
@@ -68,6 +74,7 @@ actions!(
         OpenLocalSettings,
         OpenLocalTasks,
         OpenTasks,
+        RecordScenes,
         ResetDatabase,
         ShowAll,
         ToggleFullScreen,
@@ -76,7 +83,14 @@ actions!(
     ]
 );
 
+const ADJUSTED_UI_SCALES_KEY: &str = "adjusted_ui_scales";
+const UI_SCALE_STEP: f32 = 0.1;
+/// How many frames [`RecordScenes`] records, enough for a couple of seconds of animation.
+const RECORDED_FRAME_COUNT: usize = 120;
+
 pub fn init(cx: &mut AppContext) {
+    restore_adjusted_ui_scales(cx);
+
     #[cfg(target_os = "macos")]
     cx.on_action(|_: &Hide, cx| cx.hide());
     #[cfg(target_os = "macos")]
@@ -96,6 +110,12 @@ pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut AppContext) ->
             .into_iter()
             .find(|display| display.uuid().ok() == Some(uuid))
     });
+    // Windows are tagged with the color space of their display, so that Display P3 colors in
+    // themes are shown as given on wide-gamut displays.
+    let color_space = display
+        .clone()
+        .or_else(|| cx.primary_display())
+        .map_or(gpui::ColorSpace::default(), |display| display.color_space());
     let app_id = ReleaseChannel::global(cx).app_id();
     let window_decorations = match std::env::var("ZED_WINDOW_DECORATIONS") {
         Ok(val) if val == "server" => gpui::WindowDecorations::Server,
@@ -117,11 +137,13 @@ pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut AppContext) ->
         display_id: display.map(|display| display.id()),
         window_background: cx.theme().window_background_appearance(),
         app_id: Some(app_id.to_owned()),
+        icon: None,
         window_decorations: Some(window_decorations),
         window_min_size: Some(gpui::Size {
             width: px(360.0),
             height: px(240.0),
         }),
+        color_space,
     }
 }
 
@@ -203,13 +225,19 @@ pub fn initialize_workspace(
             activity_indicator::ActivityIndicator::new(workspace, app_state.languages.clone(), cx);
         let active_buffer_language =
             cx.new_view(|_| language_selector::ActiveBufferLanguage::new(workspace));
+        let generated_file_indicator =
+            cx.new_view(|_| language_selector::GeneratedFileIndicator::new());
         let vim_mode_indicator = cx.new_view(vim::ModeIndicator::new);
+        let focus_timer_button =
+            cx.new_view(|cx| focus_timer::FocusTimerButton::new(workspace, cx));
         let cursor_position =
             cx.new_view(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(diagnostic_summary, cx);
             status_bar.add_left_item(activity_indicator, cx);
+            status_bar.add_right_item(focus_timer_button, cx);
             status_bar.add_right_item(inline_completion_button, cx);
+            status_bar.add_right_item(generated_file_indicator, cx);
             status_bar.add_right_item(active_buffer_language, cx);
             status_bar.add_right_item(vim_mode_indicator, cx);
             status_bar.add_right_item(cursor_position, cx);
@@ -265,6 +293,11 @@ pub fn initialize_workspace(
                 workspace_handle.clone(),
                 cx.clone(),
             );
+            let activity_panel = ActivityPanel::load(workspace_handle.clone(), cx.clone());
+            let coverage_panel = CoveragePanel::load(workspace_handle.clone(), cx.clone());
+            let database_panel = DatabasePanel::load(workspace_handle.clone(), cx.clone());
+            let docker_panel = DockerPanel::load(workspace_handle.clone(), cx.clone());
+            let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
 
             let (
                 project_panel,
@@ -274,6 +307,11 @@ pub fn initialize_workspace(
                 channels_panel,
                 chat_panel,
                 notification_panel,
+                activity_panel,
+                coverage_panel,
+                database_panel,
+                docker_panel,
+                git_panel,
             ) = futures::try_join!(
                 project_panel,
                 outline_panel,
@@ -282,6 +320,11 @@ pub fn initialize_workspace(
                 channels_panel,
                 chat_panel,
                 notification_panel,
+                activity_panel,
+                coverage_panel,
+                database_panel,
+                docker_panel,
+                git_panel,
             )?;
 
             workspace_handle.update(&mut cx, |workspace, cx| {
@@ -292,6 +335,11 @@ pub fn initialize_workspace(
                 workspace.add_panel(channels_panel, cx);
                 workspace.add_panel(chat_panel, cx);
                 workspace.add_panel(notification_panel, cx);
+                workspace.add_panel(activity_panel, cx);
+                workspace.add_panel(coverage_panel, cx);
+                workspace.add_panel(database_panel, cx);
+                workspace.add_panel(docker_panel, cx);
+                workspace.add_panel(git_panel, cx);
                 cx.focus_self();
             })
         })
@@ -330,6 +378,18 @@ pub fn initialize_workspace(
             .register_action(move |_, _: &zed_actions::ResetUiFontSize, cx| {
                 theme::reset_ui_font_size(cx)
             })
+            .register_action(move |_, _: &zed_actions::IncreaseUiScale, cx| {
+                theme::adjust_ui_scale(cx, |scale| *scale += UI_SCALE_STEP);
+                persist_adjusted_ui_scales(cx);
+            })
+            .register_action(move |_, _: &zed_actions::DecreaseUiScale, cx| {
+                theme::adjust_ui_scale(cx, |scale| *scale -= UI_SCALE_STEP);
+                persist_adjusted_ui_scales(cx);
+            })
+            .register_action(move |_, _: &zed_actions::ResetUiScale, cx| {
+                theme::reset_ui_scale(cx);
+                persist_adjusted_ui_scales(cx);
+            })
             .register_action(move |_, _: &zed_actions::IncreaseBufferFontSize, cx| {
                 theme::adjust_buffer_font_size(cx, |size| *size += px(1.0))
             })
@@ -400,6 +460,35 @@ pub fn initialize_workspace(
                     |_, _| None,
                 );
             })
+            .register_action(|_, _: &RecordScenes, cx| {
+                let recording = cx.record_scenes(RECORDED_FRAME_COUNT);
+                cx.spawn(|workspace, mut cx| async move {
+                    let recording = recording.await?;
+                    let path = paths::logs_dir().join("scenes.json");
+                    cx.background_executor()
+                        .spawn({
+                            let path = path.clone();
+                            async move { recording.save(&path) }
+                        })
+                        .await?;
+                    workspace.update(&mut cx, |workspace, cx| {
+                        struct RecordedScenes;
+
+                        workspace.show_toast(
+                            Toast::new(
+                                NotificationId::unique::<RecordedScenes>(),
+                                format!(
+                                    "Recorded {RECORDED_FRAME_COUNT} frames to {}. Replay them with gpui's `replay_scenes` example.",
+                                    path.display()
+                                ),
+                            ),
+                            cx,
+                        )
+                    })?;
+                    Ok(())
+                })
+                .detach_and_prompt_err("Error recording scenes", cx, |_, _| None);
+            })
             .register_action(|workspace, _: &OpenLog, cx| {
                 open_log_file(workspace, cx);
             })
@@ -594,6 +683,8 @@ fn initialize_pane(workspace: &mut Workspace, pane: &View<Pane>, cx: &mut ViewCo
             let syntax_tree_item =
                 cx.new_view(|_| language_tools::SyntaxTreeToolbarItemView::new());
             toolbar.add_item(syntax_tree_item, cx);
+            let log_toolbar = cx.new_view(log_viewer::LogToolbar::new);
+            toolbar.add_item(log_toolbar, cx);
         })
     });
 }
@@ -612,6 +703,26 @@ fn about(_: &mut Workspace, _: &zed_actions::About, cx: &mut gpui::ViewContext<W
         .detach();
 }
 
+fn restore_adjusted_ui_scales(cx: &mut AppContext) {
+    let scales = KEY_VALUE_STORE
+        .read_kvp(ADJUSTED_UI_SCALES_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|scales| serde_json::from_str(&scales).log_err());
+    if let Some(scales) = scales {
+        theme::set_adjusted_ui_scales(cx, scales);
+    }
+}
+
+fn persist_adjusted_ui_scales(cx: &mut AppContext) {
+    let Some(scales) = serde_json::to_string(&theme::adjusted_ui_scales(cx)).log_err() else {
+        return;
+    };
+    db::write_and_log(cx, move || {
+        KEY_VALUE_STORE.write_kvp(ADJUSTED_UI_SCALES_KEY.into(), scales)
+    });
+}
+
 fn test_panic(_: &TestPanic, _: &mut AppContext) {
     panic!("Ran the TestPanic action")
 }
@@ -3047,6 +3158,77 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_reopening_items_closed_in_other_panes(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/root", json!({ "a": { "file1": "", "file2": "" } }))
+            .await;
+
+        let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project, cx));
+        let entries = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx));
+        let file1 = entries[0].clone();
+        let file2 = entries[1].clone();
+
+        workspace
+            .update(cx, |w, cx| w.open_path(file1.clone(), None, true, cx))
+            .unwrap()
+            .await
+            .unwrap();
+        let right_pane = workspace
+            .update(cx, |w, cx| {
+                w.split_pane(w.active_pane().clone(), SplitDirection::Right, cx)
+            })
+            .unwrap();
+        let file2_item_id = workspace
+            .update(cx, |w, cx| {
+                w.open_path(file2.clone(), Some(right_pane.downgrade()), true, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .item_id();
+
+        // Closing the last item of the right pane closes the pane along with its history.
+        workspace
+            .update(cx, |_, cx| {
+                right_pane.update(cx, |pane, cx| {
+                    pane.close_item_by_id(file2_item_id, SaveIntent::Close, cx)
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        workspace
+            .read_with(cx, |workspace, cx| {
+                assert_eq!(workspace.panes().len(), 1);
+                assert_eq!(
+                    workspace.recently_closed_items(None, cx),
+                    vec![(file2.clone(), Some(PathBuf::from("/root/a/file2")))]
+                );
+            })
+            .unwrap();
+
+        // The remaining pane has nothing to reopen, so the item closed in the other one is.
+        workspace
+            .update(cx, Workspace::reopen_closed_item)
+            .unwrap()
+            .await
+            .unwrap();
+        workspace
+            .read_with(cx, |workspace, cx| {
+                assert_eq!(
+                    workspace.active_item(cx).unwrap().project_path(cx),
+                    Some(file2.clone())
+                );
+                assert_eq!(workspace.recently_closed_items(None, cx), Vec::new());
+            })
+            .unwrap();
+    }
+
     fn init_keymap_test(cx: &mut TestAppContext) -> Arc<AppState> {
         cx.update(|cx| {
             let app_state = AppState::test(cx);
@@ -3407,6 +3589,12 @@ mod tests {
             language::init(cx);
             editor::init(cx);
             collab_ui::init(&app_state, cx);
+            activity_tracker::init(app_state.fs.clone(), cx);
+            coverage::init(app_state.fs.clone(), cx);
+            database_explorer::init(cx);
+            docker_panel::init(cx);
+            focus_timer::init(cx);
+            git_panel::init(cx);
             project_panel::init((), cx);
             outline_panel::init((), cx);
             terminal_view::init(cx);
@@ -45,6 +45,7 @@ pub fn app_menus() -> Vec<Menu> {
                         create_new_window: true,
                     },
                 ),
+                MenuItem::action("Clone Repository…", zed_actions::CloneRepository),
                 MenuItem::separator(),
                 MenuItem::action("Add Folder to Project…", workspace::AddFolderToProject),
                 MenuItem::action("Save", workspace::Save { save_intent: None }),
@@ -87,8 +88,14 @@ pub fn app_menus() -> Vec<Menu> {
                 MenuItem::action("Expand Selection", editor::actions::SelectLargerSyntaxNode),
                 MenuItem::action("Shrink Selection", editor::actions::SelectSmallerSyntaxNode),
                 MenuItem::separator(),
-                MenuItem::action("Add Cursor Above", editor::actions::AddSelectionAbove),
-                MenuItem::action("Add Cursor Below", editor::actions::AddSelectionBelow),
+                MenuItem::action(
+                    "Add Cursor Above",
+                    editor::actions::AddSelectionAbove::default(),
+                ),
+                MenuItem::action(
+                    "Add Cursor Below",
+                    editor::actions::AddSelectionBelow::default(),
+                ),
                 MenuItem::action(
                     "Select Next Occurrence",
                     editor::actions::SelectNext {
@@ -98,7 +105,7 @@ pub fn app_menus() -> Vec<Menu> {
                 MenuItem::separator(),
                 MenuItem::action("Move Line Up", editor::actions::MoveLineUp),
                 MenuItem::action("Move Line Down", editor::actions::MoveLineDown),
-                MenuItem::action("Duplicate Selection", editor::actions::DuplicateLineDown),
+                MenuItem::action("Duplicate Selection", editor::actions::DuplicateSelection),
             ],
         },
         Menu {
@@ -167,6 +174,7 @@ pub fn app_menus() -> Vec<Menu> {
                 MenuItem::action("View Telemetry", zed_actions::OpenTelemetryLog),
                 MenuItem::action("View Dependency Licenses", zed_actions::OpenLicenses),
                 MenuItem::action("Show Welcome", workspace::Welcome),
+                MenuItem::action("Keybinding Cheat Sheet", keybinding_tutor::ToggleCheatSheet),
                 MenuItem::action("Give Feedback...", feedback::GiveFeedback),
                 MenuItem::separator(),
                 MenuItem::action(
@@ -37,10 +37,15 @@ actions!(
         ResetBufferFontSize,
         DecreaseUiFontSize,
         IncreaseUiFontSize,
-        ResetUiFontSize
+        ResetUiFontSize,
+        DecreaseUiScale,
+        IncreaseUiScale,
+        ResetUiScale
     ]
 );
 
+actions!(git, [CloneRepository]);
+
 #[derive(Clone, Default, Deserialize, PartialEq)]
 pub struct InlineAssist {
     pub prompt: Option<String>,